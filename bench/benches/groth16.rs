@@ -0,0 +1,35 @@
+//! Criterion benchmarks for Groth16 setup, proving and verification over
+//! circuits of `2^k` constraints. Run with `cargo bench -p rust-gnark-bench`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_gnark::{groth16_verify, ProverSession};
+use rust_gnark_bench::{Fixture, WITNESS};
+
+const SIZES: [u32; 3] = [10, 12, 14];
+
+fn groth16(c: &mut Criterion) {
+    rust_gnark::init().unwrap();
+    let mut group = c.benchmark_group("groth16");
+    group.sample_size(10);
+
+    for k in SIZES {
+        group.bench_with_input(BenchmarkId::new("setup", k), &k, |b, &k| {
+            b.iter(|| Fixture::groth16(k).unwrap())
+        });
+
+        let (fixture, _) = Fixture::groth16(k).unwrap();
+        let session = ProverSession::new(&fixture.r1cs, &fixture.pk).unwrap();
+        group.bench_with_input(BenchmarkId::new("prove", k), &k, |b, _| {
+            b.iter(|| session.prove(WITNESS).unwrap())
+        });
+
+        let proof = session.prove(WITNESS).unwrap();
+        group.bench_with_input(BenchmarkId::new("verify", k), &k, |b, _| {
+            b.iter(|| groth16_verify(&fixture.r1cs, &fixture.vk, &proof).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, groth16);
+criterion_main!(benches);
@@ -0,0 +1,102 @@
+//! Parameterized circuits shared by the `rust-gnark-bench` binary and the
+//! criterion benchmarks.
+//!
+//! A circuit of size `k` has exactly `2^k` constraints: a chain of
+//! `2^k - 1` multiplications by a secret `X`, plus `X == Y` for a public `Y`.
+
+use anyhow::{ensure, Result};
+use rust_gnark::CircuitBuilder;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Witness for every [`chain_circuit`].
+pub const WITNESS: &str = r#"{"X": "7", "Y": "7"}"#;
+
+/// A circuit with `2^k` constraints.
+pub fn chain_circuit(k: u32) -> CircuitBuilder {
+    let mut c = CircuitBuilder::new();
+    let y = c.public_input("Y");
+    let x = c.secret_input("X");
+    let mut acc = x;
+    for _ in 1..1u64 << k {
+        acc = c.mul(acc, x);
+    }
+    c.assert_eq(x, y);
+    c
+}
+
+/// A compiled [`chain_circuit`] and its Groth16 keys, in a temporary
+/// directory removed on drop.
+pub struct Fixture {
+    dir: PathBuf,
+    /// Path to the `.r1cs` file.
+    pub r1cs: String,
+    /// Path to the `.pk` file.
+    pub pk: String,
+    /// Path to the `.vk` file.
+    pub vk: String,
+    /// Number of constraints of the compiled circuit.
+    pub nb_constraints: u64,
+}
+
+impl Fixture {
+    /// Compile the circuit of size `k` and run the Groth16 setup, returning the
+    /// fixture and the time taken by the setup alone.
+    pub fn groth16(k: u32) -> Result<(Self, Duration)> {
+        let dir = std::env::temp_dir().join(format!("rust-gnark-bench-{}-{k}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let (r1cs, pk, vk) = (path("chain.r1cs"), path("chain.pk"), path("chain.vk"));
+
+        let circuit = chain_circuit(k).compile()?;
+        let nb_constraints = circuit.nb_constraints()?;
+        ensure!(
+            nb_constraints == 1 << k,
+            "expected 2^{k} constraints, gnark compiled {nb_constraints}"
+        );
+        circuit.write(&r1cs)?;
+
+        let start = Instant::now();
+        circuit.groth16_setup(&pk, &vk)?;
+        let setup = start.elapsed();
+
+        let fixture = Fixture {
+            dir,
+            r1cs,
+            pk,
+            vk,
+            nb_constraints,
+        };
+        Ok((fixture, setup))
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Peak resident set size of this process (Rust and Go heaps) in bytes, if
+/// the platform reports it.
+#[cfg(unix)]
+pub fn peak_rss() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let max = unsafe { usage.assume_init() }.ru_maxrss as u64;
+    // Bytes on Apple platforms, kilobytes elsewhere.
+    Some(if cfg!(target_vendor = "apple") {
+        max
+    } else {
+        max * 1024
+    })
+}
+
+/// Peak resident set size of this process, if the platform reports it.
+#[cfg(not(unix))]
+pub fn peak_rss() -> Option<u64> {
+    None
+}
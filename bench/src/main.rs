@@ -0,0 +1,142 @@
+//! `rust-gnark-bench`: Groth16 setup/prove/verify times and peak memory for
+//! circuits of `2^k` constraints, to compare devices and gnark versions.
+
+use anyhow::{bail, Context, Result};
+use rust_gnark::{groth16_verify, ProverSession};
+use rust_gnark_bench::{peak_rss, Fixture, WITNESS};
+use std::env;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: rust-gnark-bench [options]
+
+Options:
+  --min-k <k>   Smallest circuit, 2^k constraints (default: 10)
+  --max-k <k>   Largest circuit, 2^k constraints (default: 16)
+  --runs <n>    Proofs and verifications per circuit; the median is reported (default: 3)
+  --csv         Print CSV instead of a table";
+
+struct Args {
+    min_k: u32,
+    max_k: u32,
+    runs: usize,
+    csv: bool,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self {
+            min_k: 10,
+            max_k: 16,
+            runs: 3,
+            csv: false,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("{arg} needs a value\n\n{USAGE}"))
+            };
+            match arg.as_str() {
+                "--min-k" => parsed.min_k = value()?.parse()?,
+                "--max-k" => parsed.max_k = value()?.parse()?,
+                "--runs" => parsed.runs = value()?.parse()?,
+                "--csv" => parsed.csv = true,
+                "--help" | "-h" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                _ => bail!("unknown option `{arg}`\n\n{USAGE}"),
+            }
+        }
+        if parsed.min_k > parsed.max_k || parsed.max_k > 26 || parsed.runs == 0 {
+            bail!("need min-k <= max-k <= 26 and runs >= 1\n\n{USAGE}");
+        }
+        Ok(parsed)
+    }
+}
+
+/// Measurements for one circuit.
+struct Row {
+    k: u32,
+    constraints: u64,
+    setup: Duration,
+    prove: Duration,
+    verify: Duration,
+    /// Peak RSS of the process so far, in bytes.
+    peak_rss: Option<u64>,
+}
+
+fn bench(k: u32, runs: usize) -> Result<Row> {
+    let (fixture, setup) = Fixture::groth16(k)?;
+    let session = ProverSession::new(&fixture.r1cs, &fixture.pk)?;
+
+    let mut prove = Vec::with_capacity(runs);
+    let mut verify = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        let proof = session.prove(WITNESS)?;
+        prove.push(start.elapsed());
+
+        let start = Instant::now();
+        let valid = groth16_verify(&fixture.r1cs, &fixture.vk, &proof)?;
+        verify.push(start.elapsed());
+        if !valid {
+            bail!("proof for k = {k} does not verify");
+        }
+    }
+
+    Ok(Row {
+        k,
+        constraints: fixture.nb_constraints,
+        setup,
+        prove: median(prove),
+        verify: median(verify),
+        peak_rss: peak_rss(),
+    })
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse(env::args().skip(1))?;
+    rust_gnark::init()?;
+
+    let ms = |d: Duration| d.as_secs_f64() * 1e3;
+    let mib = |rss: Option<u64>| rss.map_or("-".into(), |b| format!("{:.1}", b as f64 / 1048576.0));
+    if args.csv {
+        println!("k,constraints,setup_ms,prove_ms,verify_ms,peak_rss_mib");
+    } else {
+        println!(
+            "{:>3} {:>12} {:>12} {:>12} {:>12} {:>14}",
+            "k", "constraints", "setup ms", "prove ms", "verify ms", "peak RSS MiB"
+        );
+    }
+    for k in args.min_k..=args.max_k {
+        let row = bench(k, args.runs)?;
+        if args.csv {
+            println!(
+                "{},{},{:.3},{:.3},{:.3},{}",
+                row.k,
+                row.constraints,
+                ms(row.setup),
+                ms(row.prove),
+                ms(row.verify),
+                mib(row.peak_rss)
+            );
+        } else {
+            println!(
+                "{:>3} {:>12} {:>12.1} {:>12.1} {:>12.2} {:>14}",
+                row.k,
+                row.constraints,
+                ms(row.setup),
+                ms(row.prove),
+                ms(row.verify),
+                mib(row.peak_rss)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,289 @@
+/// Dart bindings for rust-gnark's C ABI (`crates/include/rust_gnark.h`,
+/// feature `capi`).
+///
+/// ```dart
+/// final gnark = RustGnark.open('libmyapp.so')..init();
+/// final prover = gnark.loadProver('circuit.r1cs', 'circuit.pk');
+/// final proof = await prover.prove('{"X": "3", "Y": "35"}');
+/// assert(gnark.verify('circuit.r1cs', 'circuit.vk', proof));
+/// prover.dispose();
+/// ```
+///
+/// [Prover.prove] runs on a Rust thread and completes on the calling isolate,
+/// so proving does not block Flutter's UI thread.
+library rust_gnark;
+
+import 'dart:async';
+import 'dart:ffi';
+
+import 'package:ffi/ffi.dart';
+
+/// The `RUST_GNARK_ABI_VERSION` these bindings were written against.
+const int abiVersion = 2;
+
+/// Outcome of a failed call, mirroring the crate's `GnarkError`.
+enum GnarkStatus {
+  ok,
+  invalidInput,
+  io,
+  invalidWitness,
+  witnessSolveFailed,
+  invalidKey,
+  curveMismatch,
+  serialization,
+  cancelled,
+  outOfMemory,
+  invalidProof,
+  internalPanic,
+  other;
+
+  static GnarkStatus fromCode(int code) =>
+      code >= 0 && code < values.length ? values[code] : other;
+}
+
+/// A failure reported by rust-gnark.
+class GnarkException implements Exception {
+  GnarkException(this.status, this.message);
+
+  final GnarkStatus status;
+  final String message;
+
+  @override
+  String toString() => 'GnarkException(${status.name}): $message';
+}
+
+/// A hex-encoded Groth16 proof and its public inputs (`Groth16ProofResult`).
+class Groth16Proof {
+  const Groth16Proof(this.proof, this.publicInputs);
+
+  final String proof;
+  final String publicInputs;
+}
+
+typedef _Err = Pointer<Pointer<Utf8>>;
+
+typedef _AbiVersionC = Uint32 Function();
+typedef _AbiVersion = int Function();
+typedef _StringFreeC = Void Function(Pointer<Utf8>);
+typedef _StringFree = void Function(Pointer<Utf8>);
+typedef _InitC = Int32 Function(_Err);
+typedef _Init = int Function(_Err);
+typedef _ProveC = Int32 Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>,
+    Pointer<Pointer<Utf8>>, Pointer<Pointer<Utf8>>, _Err);
+typedef _Prove = int Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>,
+    Pointer<Pointer<Utf8>>, Pointer<Pointer<Utf8>>, _Err);
+typedef _VerifyC = Int32 Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>,
+    Pointer<Utf8>, Pointer<Bool>, _Err);
+typedef _Verify = int Function(Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>,
+    Pointer<Utf8>, Pointer<Bool>, _Err);
+typedef _ProverNewC = Int32 Function(
+    Pointer<Utf8>, Pointer<Utf8>, Pointer<Pointer<Void>>, _Err);
+typedef _ProverNew = int Function(
+    Pointer<Utf8>, Pointer<Utf8>, Pointer<Pointer<Void>>, _Err);
+typedef _ProverProveC = Int32 Function(Pointer<Void>, Pointer<Utf8>,
+    Pointer<Pointer<Utf8>>, Pointer<Pointer<Utf8>>, _Err);
+typedef _ProverProve = int Function(Pointer<Void>, Pointer<Utf8>,
+    Pointer<Pointer<Utf8>>, Pointer<Pointer<Utf8>>, _Err);
+typedef _ProveCallbackC = Void Function(
+    Pointer<Void>, Int32, Pointer<Utf8>, Pointer<Utf8>, Pointer<Utf8>);
+typedef _ProverProveAsyncC = Int32 Function(Pointer<Void>, Pointer<Utf8>,
+    Pointer<NativeFunction<_ProveCallbackC>>, Pointer<Void>, _Err);
+typedef _ProverProveAsync = int Function(Pointer<Void>, Pointer<Utf8>,
+    Pointer<NativeFunction<_ProveCallbackC>>, Pointer<Void>, _Err);
+typedef _ProverFreeC = Void Function(Pointer<Void>);
+typedef _ProverFree = void Function(Pointer<Void>);
+
+/// The `rust_gnark_*` functions of a loaded library.
+class RustGnark {
+  /// Bind to `lib`, which must export the `capi` functions.
+  ///
+  /// Throws a [StateError] if the library implements an older ABI.
+  RustGnark(DynamicLibrary lib)
+      : _stringFree = lib.lookupFunction<_StringFreeC, _StringFree>(
+            'rust_gnark_string_free'),
+        _init = lib.lookupFunction<_InitC, _Init>('rust_gnark_init'),
+        _prove =
+            lib.lookupFunction<_ProveC, _Prove>('rust_gnark_groth16_prove'),
+        _verify =
+            lib.lookupFunction<_VerifyC, _Verify>('rust_gnark_groth16_verify'),
+        _proverNew =
+            lib.lookupFunction<_ProverNewC, _ProverNew>('rust_gnark_prover_new'),
+        _proverProve = lib.lookupFunction<_ProverProveC, _ProverProve>(
+            'rust_gnark_prover_prove'),
+        _proverProveAsync =
+            lib.lookupFunction<_ProverProveAsyncC, _ProverProveAsync>(
+                'rust_gnark_prover_prove_async'),
+        _proverFree = lib.lookupFunction<_ProverFreeC, _ProverFree>(
+            'rust_gnark_prover_free'),
+        _proverFinalizer = NativeFinalizer(
+            lib.lookup<NativeFunction<_ProverFreeC>>('rust_gnark_prover_free')
+                .cast()) {
+    final version =
+        lib.lookupFunction<_AbiVersionC, _AbiVersion>('rust_gnark_abi_version')();
+    if (version < abiVersion) {
+      throw StateError(
+          'the library implements rust-gnark ABI $version, $abiVersion is needed');
+    }
+  }
+
+  /// Bind to the library at `path`, e.g. your app's `libmyapp.so`.
+  factory RustGnark.open(String path) => RustGnark(DynamicLibrary.open(path));
+
+  /// Bind to a library linked into the executable (iOS, static linking).
+  factory RustGnark.process() => RustGnark(DynamicLibrary.process());
+
+  final _StringFree _stringFree;
+  final _Init _init;
+  final _Prove _prove;
+  final _Verify _verify;
+  final _ProverNew _proverNew;
+  final _ProverProve _proverProve;
+  final _ProverProveAsync _proverProveAsync;
+  final _ProverFree _proverFree;
+  final NativeFinalizer _proverFinalizer;
+
+  /// Initialize the gnark Go runtime.
+  void init() => _call((err) => _init(err));
+
+  /// Prove `witnessJson` from `.r1cs` and `.pk` files, blocking the isolate.
+  Groth16Proof prove(String r1csPath, String pkPath, String witnessJson) =>
+      using((arena) {
+        final proof = arena<Pointer<Utf8>>();
+        final public = arena<Pointer<Utf8>>();
+        _call(
+            (err) => _prove(
+                r1csPath.toNativeUtf8(allocator: arena),
+                pkPath.toNativeUtf8(allocator: arena),
+                witnessJson.toNativeUtf8(allocator: arena),
+                proof,
+                public,
+                err),
+            arena);
+        return Groth16Proof(_take(proof.value), _take(public.value));
+      });
+
+  /// Verify `proof`; false for a well-formed proof that does not verify.
+  bool verify(String r1csPath, String vkPath, Groth16Proof proof) =>
+      using((arena) {
+        final valid = arena<Bool>();
+        _call(
+            (err) => _verify(
+                r1csPath.toNativeUtf8(allocator: arena),
+                vkPath.toNativeUtf8(allocator: arena),
+                proof.proof.toNativeUtf8(allocator: arena),
+                proof.publicInputs.toNativeUtf8(allocator: arena),
+                valid,
+                err),
+            arena);
+        return valid.value;
+      });
+
+  /// Load a circuit and (trusted, raw) proving key once for repeated proofs.
+  Prover loadProver(String r1csPath, String pkPath) => using((arena) {
+        final out = arena<Pointer<Void>>();
+        _call(
+            (err) => _proverNew(r1csPath.toNativeUtf8(allocator: arena),
+                pkPath.toNativeUtf8(allocator: arena), out, err),
+            arena);
+        return Prover._(this, out.value);
+      });
+
+  /// Run `f` with an error out pointer and throw what it reports.
+  void _call(int Function(_Err) f, [Allocator allocator = calloc]) {
+    final err = allocator<Pointer<Utf8>>();
+    try {
+      final status = f(err);
+      if (status != 0) {
+        throw GnarkException(GnarkStatus.fromCode(status), _take(err.value));
+      }
+    } finally {
+      if (allocator == calloc) calloc.free(err);
+    }
+  }
+
+  /// Copy out and free a string returned by the library.
+  String _take(Pointer<Utf8> s) {
+    if (s == nullptr) return '';
+    try {
+      return s.toDartString();
+    } finally {
+      _stringFree(s);
+    }
+  }
+}
+
+/// A loaded circuit and proving key (`ProverSession`).
+///
+/// Freed by [dispose], or when garbage collected; proofs already started keep
+/// running either way.
+class Prover implements Finalizable {
+  Prover._(this._gnark, this._handle) {
+    _gnark._proverFinalizer.attach(this, _handle, detach: this);
+  }
+
+  final RustGnark _gnark;
+  Pointer<Void> _handle;
+
+  /// Prove `witnessJson` on a background thread.
+  Future<Groth16Proof> prove(String witnessJson) {
+    _checkOpen();
+    final completer = Completer<Groth16Proof>();
+    late final NativeCallable<_ProveCallbackC> callback;
+    callback = NativeCallable<_ProveCallbackC>.listener(
+        (Pointer<Void> _, int status, Pointer<Utf8> proof,
+            Pointer<Utf8> public, Pointer<Utf8> error) {
+      callback.close();
+      final p = _gnark._take(proof), pub = _gnark._take(public);
+      final message = _gnark._take(error);
+      if (status == 0) {
+        completer.complete(Groth16Proof(p, pub));
+      } else {
+        completer.completeError(
+            GnarkException(GnarkStatus.fromCode(status), message));
+      }
+    });
+    try {
+      using((arena) => _gnark._call(
+          (err) => _gnark._proverProveAsync(
+              _handle,
+              witnessJson.toNativeUtf8(allocator: arena),
+              callback.nativeFunction,
+              nullptr,
+              err),
+          arena));
+    } catch (_) {
+      callback.close();
+      rethrow;
+    }
+    return completer.future;
+  }
+
+  /// Prove `witnessJson`, blocking the isolate.
+  Groth16Proof proveSync(String witnessJson) => using((arena) {
+        _checkOpen();
+        final proof = arena<Pointer<Utf8>>();
+        final public = arena<Pointer<Utf8>>();
+        _gnark._call(
+            (err) => _gnark._proverProve(
+                _handle,
+                witnessJson.toNativeUtf8(allocator: arena),
+                proof,
+                public,
+                err),
+            arena);
+        return Groth16Proof(
+            _gnark._take(proof.value), _gnark._take(public.value));
+      });
+
+  /// Free the prover. It must not be used afterwards.
+  void dispose() {
+    if (_handle == nullptr) return;
+    _gnark._proverFinalizer.detach(this);
+    _gnark._proverFree(_handle);
+    _handle = nullptr;
+  }
+
+  void _checkOpen() {
+    if (_handle == nullptr) throw StateError('the prover was disposed');
+  }
+}
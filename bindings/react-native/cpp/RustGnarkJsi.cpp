@@ -0,0 +1,230 @@
+#include "RustGnarkJsi.h"
+
+#include <functional>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "rust_gnark.h"
+
+namespace rustgnark {
+
+namespace jsi = facebook::jsi;
+using facebook::react::CallInvoker;
+
+namespace {
+
+// Indexed by RustGnarkStatus; the `code` of rejected errors.
+const char *const kStatusNames[] = {
+    "Ok",         "InvalidInput",  "Io",           "InvalidWitness",
+    "WitnessSolveFailed", "InvalidKey", "CurveMismatch", "Serialization",
+    "Cancelled",  "OutOfMemory",   "InvalidProof", "InternalPanic",
+    "Other",
+};
+
+const char *statusName(RustGnarkStatus status) {
+  auto index = static_cast<size_t>(status);
+  return index < sizeof(kStatusNames) / sizeof(*kStatusNames)
+             ? kStatusNames[index]
+             : "Other";
+}
+
+// Copy out and free a string returned by the library.
+std::string take(char *s) {
+  if (s == nullptr) {
+    return {};
+  }
+  std::string out(s);
+  rust_gnark_string_free(s);
+  return out;
+}
+
+// An `Error` whose `code` is the status name.
+jsi::Value makeError(jsi::Runtime &rt, RustGnarkStatus status,
+                     const std::string &message) {
+  auto error = rt.global()
+                   .getPropertyAsFunction(rt, "Error")
+                   .callAsConstructor(rt, jsi::String::createFromUtf8(rt, message))
+                   .asObject(rt);
+  error.setProperty(rt, "code", jsi::String::createFromAscii(rt, statusName(status)));
+  return jsi::Value(std::move(error));
+}
+
+void check(jsi::Runtime &rt, RustGnarkStatus status, char *error) {
+  auto message = take(error);
+  if (status != RUST_GNARK_STATUS_OK) {
+    throw jsi::JSError(rt, makeError(rt, status, message));
+  }
+}
+
+std::string stringArg(jsi::Runtime &rt, const jsi::Value *args, size_t count,
+                      size_t i, const char *name) {
+  if (i >= count || !args[i].isString()) {
+    throw jsi::JSError(rt, std::string(name) + " must be a string");
+  }
+  return args[i].asString(rt).utf8(rt);
+}
+
+jsi::Value proofObject(jsi::Runtime &rt, const std::string &proof,
+                       const std::string &publicInputs) {
+  jsi::Object out(rt);
+  out.setProperty(rt, "proof", jsi::String::createFromUtf8(rt, proof));
+  out.setProperty(rt, "publicInputs", jsi::String::createFromUtf8(rt, publicInputs));
+  return jsi::Value(std::move(out));
+}
+
+using HostFunction = std::function<jsi::Value(jsi::Runtime &, const jsi::Value *, size_t)>;
+
+jsi::Function function(jsi::Runtime &rt, const char *name, unsigned int arity,
+                       HostFunction f) {
+  return jsi::Function::createFromHostFunction(
+      rt, jsi::PropNameID::forAscii(rt, name), arity,
+      [f = std::move(f)](jsi::Runtime &rt, const jsi::Value &, const jsi::Value *args,
+                         size_t count) { return f(rt, args, count); });
+}
+
+// Owns a RustGnarkProver; shared by its host object and the functions it
+// hands out, which may outlive it.
+struct ProverHandle {
+  explicit ProverHandle(RustGnarkProver *prover) : prover(prover) {}
+  ~ProverHandle() { rust_gnark_prover_free(prover); }
+  ProverHandle(const ProverHandle &) = delete;
+  ProverHandle &operator=(const ProverHandle &) = delete;
+
+  RustGnarkProver *prover;
+};
+
+// A proof in flight: the promise to settle on the JS thread.
+struct Pending {
+  jsi::Runtime *runtime;
+  std::shared_ptr<CallInvoker> callInvoker;
+  std::shared_ptr<jsi::Function> resolve;
+  std::shared_ptr<jsi::Function> reject;
+};
+
+// Runs on the proving thread.
+void onProved(void *userData, RustGnarkStatus status, char *proof,
+              char *publicInputs, char *error) {
+  std::shared_ptr<Pending> pending(static_cast<Pending *>(userData));
+  auto proofHex = take(proof);
+  auto publicHex = take(publicInputs);
+  auto message = take(error);
+  auto callInvoker = pending->callInvoker;
+  // Move the only reference so the JS functions are released on the JS thread.
+  callInvoker->invokeAsync([pending = std::move(pending), status, proofHex, publicHex,
+                            message]() {
+    auto &rt = *pending->runtime;
+    if (status == RUST_GNARK_STATUS_OK) {
+      pending->resolve->call(rt, proofObject(rt, proofHex, publicHex));
+    } else {
+      pending->reject->call(rt, makeError(rt, status, message));
+    }
+  });
+}
+
+class ProverHostObject : public jsi::HostObject {
+ public:
+  ProverHostObject(std::shared_ptr<ProverHandle> handle,
+                   std::shared_ptr<CallInvoker> callInvoker)
+      : handle_(std::move(handle)), callInvoker_(std::move(callInvoker)) {}
+
+  jsi::Value get(jsi::Runtime &rt, const jsi::PropNameID &name) override {
+    if (name.utf8(rt) != "prove") {
+      return jsi::Value::undefined();
+    }
+    auto handle = handle_;
+    auto callInvoker = callInvoker_;
+    return function(rt, "prove", 1,
+                    [handle, callInvoker](jsi::Runtime &rt, const jsi::Value *args,
+                                          size_t count) {
+                      auto witness = stringArg(rt, args, count, 0, "witnessJson");
+                      return prove(rt, handle, callInvoker, witness);
+                    });
+  }
+
+  std::vector<jsi::PropNameID> getPropertyNames(jsi::Runtime &rt) override {
+    std::vector<jsi::PropNameID> names;
+    names.push_back(jsi::PropNameID::forAscii(rt, "prove"));
+    return names;
+  }
+
+ private:
+  static jsi::Value prove(jsi::Runtime &rt, const std::shared_ptr<ProverHandle> &handle,
+                          const std::shared_ptr<CallInvoker> &callInvoker,
+                          const std::string &witness) {
+    auto executor = function(
+        rt, "executor", 2,
+        [handle, callInvoker, witness](jsi::Runtime &rt, const jsi::Value *args,
+                                       size_t) {
+          auto *pending = new Pending{
+              &rt,
+              callInvoker,
+              std::make_shared<jsi::Function>(args[0].asObject(rt).asFunction(rt)),
+              std::make_shared<jsi::Function>(args[1].asObject(rt).asFunction(rt)),
+          };
+          char *error = nullptr;
+          auto status = rust_gnark_prover_prove_async(handle->prover, witness.c_str(),
+                                                      onProved, pending, &error);
+          if (status != RUST_GNARK_STATUS_OK) {
+            auto reject = pending->reject;
+            delete pending;
+            reject->call(rt, makeError(rt, status, take(error)));
+          }
+          return jsi::Value::undefined();
+        });
+    return rt.global().getPropertyAsFunction(rt, "Promise").callAsConstructor(rt, std::move(executor));
+  }
+
+  std::shared_ptr<ProverHandle> handle_;
+  std::shared_ptr<CallInvoker> callInvoker_;
+};
+
+}  // namespace
+
+void install(jsi::Runtime &runtime, std::shared_ptr<CallInvoker> callInvoker) {
+  auto &rt = runtime;
+  jsi::Object module(rt);
+
+  module.setProperty(rt, "abiVersion", static_cast<double>(rust_gnark_abi_version()));
+
+  module.setProperty(rt, "init", function(rt, "init", 0, [](jsi::Runtime &rt, const jsi::Value *, size_t) {
+                       char *error = nullptr;
+                       check(rt, rust_gnark_init(&error), error);
+                       return jsi::Value::undefined();
+                     }));
+
+  module.setProperty(
+      rt, "verify",
+      function(rt, "verify", 4, [](jsi::Runtime &rt, const jsi::Value *args, size_t count) {
+        auto r1cs = stringArg(rt, args, count, 0, "r1csPath");
+        auto vk = stringArg(rt, args, count, 1, "vkPath");
+        auto proof = stringArg(rt, args, count, 2, "proof");
+        auto publicInputs = stringArg(rt, args, count, 3, "publicInputs");
+        bool valid = false;
+        char *error = nullptr;
+        check(rt,
+              rust_gnark_groth16_verify(r1cs.c_str(), vk.c_str(), proof.c_str(),
+                                        publicInputs.c_str(), &valid, &error),
+              error);
+        return jsi::Value(valid);
+      }));
+
+  module.setProperty(
+      rt, "loadProver",
+      function(rt, "loadProver", 2,
+               [callInvoker](jsi::Runtime &rt, const jsi::Value *args, size_t count) {
+                 auto r1cs = stringArg(rt, args, count, 0, "r1csPath");
+                 auto pk = stringArg(rt, args, count, 1, "pkPath");
+                 RustGnarkProver *prover = nullptr;
+                 char *error = nullptr;
+                 check(rt, rust_gnark_prover_new(r1cs.c_str(), pk.c_str(), &prover, &error),
+                       error);
+                 auto host = std::make_shared<ProverHostObject>(
+                     std::make_shared<ProverHandle>(prover), callInvoker);
+                 return jsi::Value(jsi::Object::createFromHostObject(rt, host));
+               }));
+
+  rt.global().setProperty(rt, "__rustGnark", std::move(module));
+}
+
+}  // namespace rustgnark
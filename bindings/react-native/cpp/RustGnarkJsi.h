@@ -0,0 +1,20 @@
+// JSI module for React Native over rust-gnark's C ABI (rust_gnark.h, feature
+// `capi`).
+//
+// install() defines `global.__rustGnark`, which src/index.ts wraps with
+// types. Proofs run on Rust threads and settle their promises on the JS
+// thread through the CallInvoker, so proving never blocks the JS thread.
+
+#pragma once
+
+#include <memory>
+
+#include <ReactCommon/CallInvoker.h>
+#include <jsi/jsi.h>
+
+namespace rustgnark {
+
+void install(facebook::jsi::Runtime &runtime,
+             std::shared_ptr<facebook::react::CallInvoker> callInvoker);
+
+}  // namespace rustgnark
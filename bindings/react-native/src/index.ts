@@ -0,0 +1,74 @@
+// Typed wrapper over the JSI module installed by cpp/RustGnarkJsi.cpp.
+
+/** A hex-encoded Groth16 proof and its public inputs (`Groth16ProofResult`). */
+export interface Groth16Proof {
+  proof: string;
+  publicInputs: string;
+}
+
+/** Error `code`s, mirroring the crate's `GnarkError`. */
+export type GnarkErrorCode =
+  | 'InvalidInput'
+  | 'Io'
+  | 'InvalidWitness'
+  | 'WitnessSolveFailed'
+  | 'InvalidKey'
+  | 'CurveMismatch'
+  | 'Serialization'
+  | 'Cancelled'
+  | 'OutOfMemory'
+  | 'InvalidProof'
+  | 'InternalPanic'
+  | 'Other';
+
+/** An `Error` thrown or rejected by rust-gnark. */
+export interface GnarkError extends Error {
+  code: GnarkErrorCode;
+}
+
+/** A loaded circuit and proving key (`ProverSession`), freed when collected. */
+export interface Prover {
+  /** Prove `witnessJson` on a background thread. */
+  prove(witnessJson: string): Promise<Groth16Proof>;
+}
+
+interface NativeModule {
+  abiVersion: number;
+  init(): void;
+  verify(r1csPath: string, vkPath: string, proof: string, publicInputs: string): boolean;
+  loadProver(r1csPath: string, pkPath: string): Prover;
+}
+
+declare global {
+  // eslint-disable-next-line no-var
+  var __rustGnark: NativeModule | undefined;
+}
+
+/** The `RUST_GNARK_ABI_VERSION` this wrapper was written against. */
+export const ABI_VERSION = 2;
+
+function native(): NativeModule {
+  const module = globalThis.__rustGnark;
+  if (module === undefined) {
+    throw new Error('rust-gnark is not installed: call rustgnark::install() from native code');
+  }
+  if (module.abiVersion < ABI_VERSION) {
+    throw new Error(`the library implements rust-gnark ABI ${module.abiVersion}, ${ABI_VERSION} is needed`);
+  }
+  return module;
+}
+
+/** Initialize the gnark Go runtime. */
+export function init(): void {
+  native().init();
+}
+
+/** Verify `proof`; false for a well-formed proof that does not verify. */
+export function verify(r1csPath: string, vkPath: string, proof: Groth16Proof): boolean {
+  return native().verify(r1csPath, vkPath, proof.proof, proof.publicInputs);
+}
+
+/** Load a circuit and (trusted, raw) proving key once for repeated proofs. */
+export function loadProver(r1csPath: string, pkPath: string): Prover {
+  return native().loadProver(r1csPath, pkPath);
+}
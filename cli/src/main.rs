@@ -0,0 +1,236 @@
+//! `gnark-cli`: Groth16 setup, proving, verification and Solidity export from
+//! the command line, shaped after snarkjs' `groth16` commands so workflows can
+//! be scripted without writing Rust.
+//!
+//! Proofs are written as [`GnarkProofBundle`]s, which record the circuit they
+//! belong to, so `verify` rejects a proof for another circuit by name.
+
+use anyhow::{bail, ensure, Context, Result};
+use rust_gnark::{groth16_prove, CircuitAbi, CompiledCircuit, GnarkProofBundle, VerifyingKey};
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: gnark-cli <command> [options]
+
+Commands:
+  setup <circuit.r1cs> [--pk <path>] [--vk <path>]
+      Run a single-party Groth16 setup, for development; keys default to the
+      circuit's path with .pk and .vk extensions
+  prove --r1cs <path> --pk <path> --vk <path> --witness <input.json> [--proof <path>]
+      Prove an assignment of the circuit's inputs, given as a JSON object of
+      gnark names (as groth16_prove takes it), and write a proof bundle
+      (default: proof.bundle)
+  verify --r1cs <path> --vk <path> --proof <path>
+      Verify a proof bundle; exits with status 1 if the proof is invalid
+  export-solidity --vk <path> [--output <path>]
+      Write a Solidity verifier contract for the key (default: stdout)
+  export-calldata --proof <path>
+      Print the proof bundle as 0x-prefixed calldata for the verifier's
+      verifyProof
+  export-abi <circuit.r1cs> [--output <path>]
+      Write the circuit's inputs, with their visibility and array shapes, as
+      ABI JSON for #[gnark(abi)] (default: stdout)
+  migrate <file> [--output <path>]
+      Convert a key, a key written by gnark itself, or a proof bundle from an
+      older gnark to this build's format (default: in place); fails if the
+      setup or the proof has to be redone
+
+Errors exit with status 2.";
+
+/// The `--name value` options of a command, and its positional arguments.
+struct Options {
+    values: HashMap<String, String>,
+    positional: Vec<String>,
+}
+
+impl Options {
+    /// Parse `args`, accepting only the options in `allowed`.
+    fn parse(mut args: impl Iterator<Item = String>, allowed: &[&str]) -> Result<Self> {
+        let mut parsed = Self {
+            values: HashMap::new(),
+            positional: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                parsed.positional.push(arg);
+                continue;
+            };
+            ensure!(allowed.contains(&name), "unknown option `{arg}`\n\n{USAGE}");
+            let value = args
+                .next()
+                .with_context(|| format!("{arg} needs a value\n\n{USAGE}"))?;
+            ensure!(
+                parsed.values.insert(name.to_owned(), value).is_none(),
+                "{arg} given twice"
+            );
+        }
+        Ok(parsed)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    fn require(&self, name: &str) -> Result<&str> {
+        self.get(name)
+            .with_context(|| format!("missing --{name}\n\n{USAGE}"))
+    }
+
+    fn no_positional(&self) -> Result<()> {
+        match self.positional.first() {
+            Some(arg) => bail!("unexpected argument `{arg}`\n\n{USAGE}"),
+            None => Ok(()),
+        }
+    }
+}
+
+fn setup(options: &Options) -> Result<()> {
+    let [r1cs] = options.positional.as_slice() else {
+        bail!("setup takes one circuit\n\n{USAGE}");
+    };
+    let beside = |extension| {
+        Path::new(r1cs)
+            .with_extension(extension)
+            .to_str()
+            .map(str::to_owned)
+            .context("circuit path is not valid UTF-8")
+    };
+    let pk = options
+        .get("pk")
+        .map_or_else(|| beside("pk"), |p| Ok(p.into()))?;
+    let vk = options
+        .get("vk")
+        .map_or_else(|| beside("vk"), |p| Ok(p.into()))?;
+    let circuit = CompiledCircuit::read(r1cs)?;
+    circuit.groth16_setup(&pk, &vk)?;
+    eprintln!(
+        "{} constraints; wrote {pk} and {vk}",
+        circuit.nb_constraints()?
+    );
+    Ok(())
+}
+
+fn prove(options: &Options) -> Result<()> {
+    options.no_positional()?;
+    let r1cs = options.require("r1cs")?;
+    let vk = VerifyingKey::open(options.require("vk")?)?;
+    let witness_path = options.require("witness")?;
+    if witness_path.ends_with(".wtns") {
+        bail!(
+            "{witness_path}: snarkjs .wtns files hold circom's full wire assignment, which does \
+             not map onto a gnark circuit; pass the circuit's inputs as JSON instead"
+        );
+    }
+    let witness = std::fs::read_to_string(witness_path)
+        .with_context(|| format!("failed to read {witness_path}"))?;
+    let proof = groth16_prove(r1cs, options.require("pk")?, &witness)?;
+    let out = options.get("proof").unwrap_or("proof.bundle");
+    GnarkProofBundle::new(proof, &vk)?.write(out)?;
+    eprintln!("wrote {out}");
+    Ok(())
+}
+
+fn verify(options: &Options) -> Result<bool> {
+    options.no_positional()?;
+    let bundle = GnarkProofBundle::read(options.require("proof")?)?;
+    bundle.verify(options.require("r1cs")?, options.require("vk")?)
+}
+
+fn export_solidity(options: &Options) -> Result<()> {
+    options.no_positional()?;
+    let contract = VerifyingKey::open(options.require("vk")?)?.export_solidity()?;
+    match options.get("output") {
+        Some(out) => {
+            std::fs::write(out, contract).with_context(|| format!("failed to write {out}"))
+        }
+        None => {
+            print!("{contract}");
+            Ok(())
+        }
+    }
+}
+
+fn export_calldata(options: &Options) -> Result<()> {
+    options.no_positional()?;
+    let bundle = GnarkProofBundle::read(options.require("proof")?)?;
+    let calldata = bundle.proof.solidity_calldata()?;
+    let hex: String = calldata.iter().map(|b| format!("{b:02x}")).collect();
+    println!("0x{hex}");
+    Ok(())
+}
+
+fn export_abi(options: &Options) -> Result<()> {
+    let [r1cs] = options.positional.as_slice() else {
+        bail!("export-abi takes the circuit's .r1cs\n\n{USAGE}");
+    };
+    let abi = CircuitAbi::from_r1cs(r1cs)?.to_abi_json();
+    match options.get("output") {
+        Some(out) => {
+            std::fs::write(out, abi + "\n").with_context(|| format!("failed to write {out}"))
+        }
+        None => {
+            println!("{abi}");
+            Ok(())
+        }
+    }
+}
+
+fn migrate(options: &Options) -> Result<()> {
+    let [input] = options.positional.as_slice() else {
+        bail!("migrate takes the file to convert\n\n{USAGE}");
+    };
+    let output = options.get("output").unwrap_or(input.as_str());
+    let report = rust_gnark::migrate(input, output)?;
+    let from = match &report.from_gnark {
+        Some(version) => format!("gnark {version}"),
+        None => "a key written by gnark".into(),
+    };
+    if report.migrated {
+        eprintln!("{output}: converted from {from}");
+    } else {
+        eprintln!("{input}: already current ({from})");
+    }
+    Ok(())
+}
+
+fn run() -> Result<ExitCode> {
+    let mut args = env::args().skip(1);
+    let command = args.next().unwrap_or_default();
+    match command.as_str() {
+        "setup" => setup(&Options::parse(args, &["pk", "vk"])?)?,
+        "prove" => prove(&Options::parse(
+            args,
+            &["r1cs", "pk", "vk", "witness", "proof"],
+        )?)?,
+        "verify" => {
+            let valid = verify(&Options::parse(args, &["r1cs", "vk", "proof"])?)?;
+            eprintln!("{}", if valid { "OK" } else { "INVALID" });
+            if !valid {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        "export-solidity" => export_solidity(&Options::parse(args, &["vk", "output"])?)?,
+        "export-calldata" => export_calldata(&Options::parse(args, &["proof"])?)?,
+        "export-abi" => export_abi(&Options::parse(args, &["output"])?)?,
+        "migrate" => migrate(&Options::parse(args, &["output"])?)?,
+        "help" | "--help" | "-h" => println!("{USAGE}"),
+        "" => bail!("missing command\n\n{USAGE}"),
+        _ => bail!("unknown command `{command}`\n\n{USAGE}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    // Proving and verifying initialize the runtime when they need it, so the
+    // other commands do not.
+    match run() {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,183 @@
+#ifndef RUST_GNARK_H
+#define RUST_GNARK_H
+
+/* Generated by cbindgen from src/capi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of the `rust_gnark_*` ABI declared in `rust_gnark.h`.
+#define RUST_GNARK_ABI_VERSION 3
+
+// Outcome of a `rust_gnark_*` call, mirroring [`GnarkError`].
+typedef enum RustGnarkStatus {
+  // The call succeeded.
+  RUST_GNARK_STATUS_OK = 0,
+  // See [`GnarkError::InvalidInput`], also reported for a null or
+  // non-UTF-8 argument.
+  RUST_GNARK_STATUS_INVALID_INPUT = 1,
+  // See [`GnarkError::Io`].
+  RUST_GNARK_STATUS_IO = 2,
+  // See [`GnarkError::InvalidWitness`].
+  RUST_GNARK_STATUS_INVALID_WITNESS = 3,
+  // See [`GnarkError::WitnessSolveFailed`].
+  RUST_GNARK_STATUS_WITNESS_SOLVE_FAILED = 4,
+  // See [`GnarkError::InvalidKey`].
+  RUST_GNARK_STATUS_INVALID_KEY = 5,
+  // See [`GnarkError::CurveMismatch`].
+  RUST_GNARK_STATUS_CURVE_MISMATCH = 6,
+  // See [`GnarkError::SerializationError`].
+  RUST_GNARK_STATUS_SERIALIZATION = 7,
+  // See [`GnarkError::Cancelled`].
+  RUST_GNARK_STATUS_CANCELLED = 8,
+  // See [`GnarkError::OutOfMemory`].
+  RUST_GNARK_STATUS_OUT_OF_MEMORY = 9,
+  // See [`GnarkError::InvalidProof`].
+  RUST_GNARK_STATUS_INVALID_PROOF = 10,
+  // gnark or this crate panicked; the message carries the panic.
+  RUST_GNARK_STATUS_INTERNAL_PANIC = 11,
+  // Any other failure.
+  RUST_GNARK_STATUS_OTHER = 12,
+} RustGnarkStatus;
+
+#if !defined(RUST_GNARK_VERIFIER)
+// A circuit and proving key loaded once for repeated proofs (a
+// [`ProverSession`]), created with [`rust_gnark_prover_new`]. Safe to share
+// between threads.
+typedef struct RustGnarkProver RustGnarkProver;
+#endif
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Receives the outcome of [`rust_gnark_prover_prove_async`], on one of the
+// crate's prover threads. On success `proof` and `public_inputs` are set and `error` is
+// null, on failure the reverse; the callback owns the strings and frees them
+// with [`rust_gnark_string_free`].
+typedef void (*RustGnarkProveCallback)(void *user_data,
+                                       enum RustGnarkStatus status,
+                                       char *proof,
+                                       char *public_inputs,
+                                       char *error);
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The ABI version the library implements, to compare with
+// `RUST_GNARK_ABI_VERSION` from the header the host was built against.
+uint32_t rust_gnark_abi_version(void);
+
+// Free a string returned by a `rust_gnark_*` function. Null is ignored.
+//
+// # Safety
+//
+// `s` must be null or a string from this library, not yet freed.
+void rust_gnark_string_free(char *s);
+
+// Initialize the gnark Go runtime (see [`crate::init`]).
+//
+// # Safety
+//
+// `out_error` must be null or valid for writes.
+enum RustGnarkStatus rust_gnark_init(char **out_error);
+
+// Release the Go runtime's memory and idle threads (see [`crate::shutdown`]).
+void rust_gnark_shutdown(void);
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Generate a proof from `.r1cs` and `.pk` files (see [`crate::groth16_prove`]).
+// The hex-encoded proof and public inputs go to `out_proof` and
+// `out_public_inputs`.
+//
+// # Safety
+//
+// The string arguments must be null or NUL-terminated, and the out pointers
+// must be valid for writes (`out_error` may be null).
+enum RustGnarkStatus rust_gnark_groth16_prove(const char *r1cs_path,
+                                              const char *pk_path,
+                                              const char *witness_json,
+                                              char **out_proof,
+                                              char **out_public_inputs,
+                                              char **out_error);
+#endif
+
+// Verify a hex-encoded proof (see [`crate::groth16_verify`]). `out_valid` is
+// set to false for a well-formed proof that does not verify.
+//
+// # Safety
+//
+// The string arguments must be null or NUL-terminated, and the out pointers
+// must be valid for writes (`out_error` may be null).
+enum RustGnarkStatus rust_gnark_groth16_verify(const char *r1cs_path,
+                                               const char *vk_path,
+                                               const char *proof,
+                                               const char *public_inputs,
+                                               bool *out_valid,
+                                               char **out_error);
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Load a `.r1cs` and (trusted, raw) `.pk` file into a prover, freed with
+// [`rust_gnark_prover_free`].
+//
+// # Safety
+//
+// As for [`rust_gnark_groth16_prove`].
+enum RustGnarkStatus rust_gnark_prover_new(const char *r1cs_path,
+                                           const char *pk_path,
+                                           RustGnarkProver **out_prover,
+                                           char **out_error);
+#endif
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Prove `witness_json` with a loaded prover, as [`rust_gnark_groth16_prove`].
+// May be called from several threads at once.
+//
+// # Safety
+//
+// `prover` must come from [`rust_gnark_prover_new`] and not be freed; the
+// other arguments as for [`rust_gnark_groth16_prove`].
+enum RustGnarkStatus rust_gnark_prover_prove(const RustGnarkProver *prover,
+                                             const char *witness_json,
+                                             char **out_proof,
+                                             char **out_public_inputs,
+                                             char **out_error);
+#endif
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Prove `witness_json` on the crate's prover threads, as [`ProveTask`] does,
+// and report the result to `callback`, so UI threads (Dart's, React Native's
+// JS thread) are not blocked. Returns once the proof is queued; argument
+// errors are returned here and do not reach `callback`, while a proof that
+// panics reports [`RustGnarkStatus::Other`]. The prover may be freed while
+// the proof runs.
+//
+// # Safety
+//
+// `prover` must come from [`rust_gnark_prover_new`] and not be freed, and
+// `witness_json` must be null or NUL-terminated; both are only read before
+// this returns. `user_data` is passed to `callback` as is, from another
+// thread.
+enum RustGnarkStatus rust_gnark_prover_prove_async(const RustGnarkProver *prover,
+                                                   const char *witness_json,
+                                                   RustGnarkProveCallback callback,
+                                                   void *user_data,
+                                                   char **out_error);
+#endif
+
+#if !defined(RUST_GNARK_VERIFIER)
+// Free a prover. Null is ignored.
+//
+// # Safety
+//
+// `prover` must be null or come from [`rust_gnark_prover_new`], with no
+// synchronous call using it still running.
+void rust_gnark_prover_free(RustGnarkProver *prover);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUST_GNARK_H */
@@ -0,0 +1,322 @@
+//! Structured circuit ABIs and typed witnesses.
+//!
+//! [`CircuitAbi`] lists a circuit's inputs by gnark's flattened leaf names.
+//! [`CircuitAbi::inputs`] groups them back into named inputs with a
+//! visibility and an array shape, and [`CircuitAbi::to_abi_json`] writes that
+//! as a machine-readable ABI for other tools.
+//!
+//! [`GnarkWitness`] is the typed side: a struct whose fields are the
+//! circuit's inputs, usually with `#[derive(GnarkWitness)]` (the `derive`
+//! feature), turns into a [`Witness`] without positional packing. The derive
+//! can also check the struct against an ABI file at compile time.
+
+use anyhow::{bail, ensure, Result};
+use std::fmt::Write as _;
+
+use crate::circuit::push_json_string;
+use crate::{CircuitAbi, Fr, Witness};
+
+/// Version of the ABI JSON written by [`CircuitAbi::to_abi_json`].
+const ABI_VERSION: u32 = 1;
+
+/// An input of a circuit in a [`CircuitAbi`]: a field element or an array of
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiInput {
+    /// The input's name: the leaf name for a field element, the common prefix
+    /// of the leaves (`A` for `A_0`, `A_1`, ...) for an array.
+    pub name: String,
+    /// Whether the input is public.
+    pub public: bool,
+    /// The array's lengths, outermost first; empty for a field element.
+    pub shape: Vec<usize>,
+}
+
+impl AbiInput {
+    /// The input's leaf names, in witness order.
+    pub fn leaf_names(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        push_leaf_names(&self.name, &self.shape, &mut out);
+        out
+    }
+}
+
+impl CircuitAbi {
+    /// The inputs, public then secret, each in witness order, with the leaves
+    /// of arrays grouped back into one input.
+    ///
+    /// Leaves `A_0`, `A_1`, ... (`M_0_0`, `M_0_1`, ... for nested arrays) that
+    /// follow each other and cover every index of a shape form an array `A`.
+    /// Any other name is a field element, including the fields of structs
+    /// (`S_F`), which gnark flattens the same way.
+    pub fn inputs(&self) -> Vec<AbiInput> {
+        let mut inputs = group(self.public(), true);
+        inputs.extend(group(self.secret(), false));
+        inputs
+    }
+
+    /// The ABI as JSON: the field, the [inputs](CircuitAbi::inputs) with
+    /// their visibility and shape, and the
+    /// [hashed public inputs](CircuitAbi::hashed_public_inputs) if any.
+    ///
+    /// ```json
+    /// {"version":1,"field":"bn254","inputs":[
+    ///   {"name":"Root","visibility":"public","shape":[]},
+    ///   {"name":"Path","visibility":"secret","shape":[20]}
+    /// ],"hashed":[]}
+    /// ```
+    pub fn to_abi_json(&self) -> String {
+        let mut out = format!("{{\"version\":{ABI_VERSION},\"field\":\"bn254\",\"inputs\":[");
+        for (i, input) in self.inputs().iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            push_json_string(&mut out, &input.name);
+            let visibility = if input.public { "public" } else { "secret" };
+            let shape: Vec<String> = input.shape.iter().map(usize::to_string).collect();
+            let _ = write!(
+                out,
+                ",\"visibility\":\"{visibility}\",\"shape\":[{}]}}",
+                shape.join(",")
+            );
+        }
+        out.push_str("],\"hashed\":[");
+        for (i, name) in self.hashed_public_inputs().iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            push_json_string(&mut out, name);
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Group consecutive leaves of one visibility into inputs.
+fn group(names: &[String], public: bool) -> Vec<AbiInput> {
+    let mut inputs = Vec::new();
+    let mut i = 0;
+    while i < names.len() {
+        let (base, indices) = split_indices(&names[i]);
+        let mut end = i + 1;
+        if !indices.is_empty() {
+            while end < names.len() {
+                let (next, next_indices) = split_indices(&names[end]);
+                if next != base || next_indices.len() != indices.len() {
+                    break;
+                }
+                end += 1;
+            }
+            if let Some(shape) = grid_shape(&names[i..end], indices.len()) {
+                inputs.push(AbiInput {
+                    name: base.to_owned(),
+                    public,
+                    shape,
+                });
+                i = end;
+                continue;
+            }
+            end = i + 1;
+        }
+        for name in &names[i..end] {
+            inputs.push(AbiInput {
+                name: name.clone(),
+                public,
+                shape: Vec::new(),
+            });
+        }
+        i = end;
+    }
+    inputs
+}
+
+/// Split the trailing `_<index>` parts off `name`: `("M", [1, 2])` for
+/// `M_1_2`.
+fn split_indices(name: &str) -> (&str, Vec<usize>) {
+    let mut base = name;
+    let mut indices = Vec::new();
+    while let Some((rest, last)) = base.rsplit_once('_') {
+        if rest.is_empty() || last.is_empty() || !last.bytes().all(|b| b.is_ascii_digit()) {
+            break;
+        }
+        // A leading zero is not an index gnark writes (`A_01`).
+        if last.len() > 1 && last.starts_with('0') {
+            break;
+        }
+        let Ok(index) = last.parse() else { break };
+        indices.push(index);
+        base = rest;
+    }
+    indices.reverse();
+    (base, indices)
+}
+
+/// The shape whose row-major enumeration `leaves` is, if any.
+fn grid_shape(leaves: &[String], rank: usize) -> Option<Vec<usize>> {
+    let mut shape = vec![0; rank];
+    for leaf in leaves {
+        for (dim, index) in shape.iter_mut().zip(split_indices(leaf).1) {
+            *dim = (*dim).max(index + 1);
+        }
+    }
+    let (base, _) = split_indices(&leaves[0]);
+    let mut expected = Vec::new();
+    push_leaf_names(base, &shape, &mut expected);
+    (expected == leaves).then_some(shape)
+}
+
+/// Append the leaf names of an input `name` of `shape`, in witness order.
+fn push_leaf_names(name: &str, shape: &[usize], out: &mut Vec<String>) {
+    match shape.split_first() {
+        None => out.push(name.to_owned()),
+        Some((&len, inner)) => {
+            for i in 0..len {
+                push_leaf_names(&format!("{name}_{i}"), inner, out);
+            }
+        }
+    }
+}
+
+/// A value that fills one input of a [`GnarkWitness`]: a field element or an
+/// array of them.
+pub trait WitnessValue {
+    /// The number of dimensions: 0 for a field element.
+    const RANK: usize;
+    /// The number of field elements.
+    const LEN: usize;
+
+    /// The array's lengths, outermost first; empty for a field element.
+    fn shape() -> Vec<usize>;
+
+    /// Append the value's leaves, named as gnark flattens an input `name`.
+    fn push_elements(&self, name: &str, out: &mut Vec<(String, Fr)>);
+}
+
+impl WitnessValue for Fr {
+    const RANK: usize = 0;
+    const LEN: usize = 1;
+
+    fn shape() -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn push_elements(&self, name: &str, out: &mut Vec<(String, Fr)>) {
+        out.push((name.to_owned(), *self));
+    }
+}
+
+macro_rules! scalar_witness_values {
+    ($($t:ty),*) => {
+        $(
+            impl WitnessValue for $t {
+                const RANK: usize = 0;
+                const LEN: usize = 1;
+
+                fn shape() -> Vec<usize> {
+                    Vec::new()
+                }
+
+                fn push_elements(&self, name: &str, out: &mut Vec<(String, Fr)>) {
+                    out.push((name.to_owned(), Fr::from(u64::from(*self))));
+                }
+            }
+        )*
+    };
+}
+
+scalar_witness_values!(u8, u16, u32, u64, bool);
+
+impl<T: WitnessValue, const N: usize> WitnessValue for [T; N] {
+    const RANK: usize = 1 + T::RANK;
+    const LEN: usize = N * T::LEN;
+
+    fn shape() -> Vec<usize> {
+        let mut shape = vec![N];
+        shape.extend(T::shape());
+        shape
+    }
+
+    fn push_elements(&self, name: &str, out: &mut Vec<(String, Fr)>) {
+        for (i, value) in self.iter().enumerate() {
+            value.push_elements(&format!("{name}_{i}"), out);
+        }
+    }
+}
+
+/// A struct whose fields are a circuit's inputs.
+///
+/// Derive it with `#[derive(GnarkWitness)]` (the `derive` feature). Field
+/// `path_elements` is input `PathElements` unless renamed with
+/// `#[gnark(name = "...")]`, fields are secret unless marked
+/// `#[gnark(public)]`, and field types implement [`WitnessValue`]: [`Fr`],
+/// unsigned integers, `bool`, and arrays of them. Public fields come first in
+/// the witness regardless of where they are declared, as in gnark.
+///
+/// `#[gnark(abi = "circuit.abi.json")]` on the struct checks it at compile
+/// time against an ABI written by [`CircuitAbi::to_abi_json`] (the path is
+/// relative to the crate's `Cargo.toml`), so a circuit change that the
+/// struct does not follow fails the build:
+///
+/// ```ignore
+/// use rust_gnark::{Fr, GnarkWitness};
+///
+/// #[derive(GnarkWitness)]
+/// #[gnark(abi = "circuits/membership.abi.json")]
+/// struct Membership {
+///     #[gnark(public)]
+///     root: Fr,
+///     leaf: Fr,
+///     path: [Fr; 20],
+///     directions: [bool; 20],
+/// }
+///
+/// let witness = Membership { root, leaf, path, directions }.to_witness()?;
+/// let proof = session.prove(&witness.to_json())?;
+/// ```
+pub trait GnarkWitness {
+    /// The circuit inputs the struct declares.
+    fn abi() -> CircuitAbi;
+
+    /// The struct's field elements, keyed by leaf name.
+    fn elements(&self) -> Vec<(String, Fr)>;
+
+    /// The struct as a [`Witness`] of [`GnarkWitness::abi`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two fields map to the same input name.
+    fn to_witness(&self) -> Result<Witness> {
+        Witness::from_elements(&Self::abi(), self.elements())
+    }
+
+    /// Check that the struct declares exactly the inputs of `abi`, e.g. of a
+    /// circuit read at run time with [`CircuitAbi::from_r1cs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first input that differs.
+    fn check_abi(abi: &CircuitAbi) -> Result<()> {
+        let own = Self::abi();
+        for (kind, theirs, ours) in [
+            ("public", abi.public(), own.public()),
+            ("secret", abi.secret(), own.secret()),
+        ] {
+            if let Some((i, (a, b))) = theirs
+                .iter()
+                .zip(ours)
+                .enumerate()
+                .find(|(_, (a, b))| a != b)
+            {
+                bail!("{kind} input {i} is {a:?} in the circuit but {b:?} in the struct");
+            }
+            ensure!(
+                theirs.len() == ours.len(),
+                "the circuit has {} {kind} inputs, the struct {}",
+                theirs.len(),
+                ours.len()
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Noir ACIR programs proven with gnark.
+
+use anyhow::Result;
+use std::os::raw::c_int;
+
+use crate::handle::Handle;
+use crate::{bind, CompiledCircuit, GnarkError};
+
+/// A Noir program in ACIR form, owned by the Go runtime.
+///
+/// Only a Brillig-less subset is supported for now: the main function's
+/// `AssertZero` opcodes, where every witness that is not a parameter or return
+/// value is determined (linearly) by the first opcode it appears in. Programs
+/// with Brillig calls, black-box functions, memory or call opcodes are
+/// rejected by [`AcirProgram::load`].
+///
+/// Compiled circuits name their inputs by ACIR witness index (`w0`, `w1`,
+/// ...); parameters are secret inputs, public parameters and return values
+/// public ones, in index order.
+#[derive(Debug)]
+pub struct AcirProgram {
+    handle: Handle,
+}
+
+impl AcirProgram {
+    /// Parse an ACIR program: the `bytecode` of a nargo artifact, base64-decoded
+    /// (the gzipped bincode serialization of an acir `Program`; uncompressed
+    /// bincode is accepted too).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a bincode ACIR program or uses
+    /// opcodes outside the supported subset.
+    pub fn load(bytes: &[u8]) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_acir_load(bytes.as_ptr() as *mut u8, bytes.len() as u64, &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Compile into a BN254 R1CS, for Groth16.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a witness is not determined by the opcodes (it
+    /// would need Brillig) or gnark fails to compile the program.
+    pub fn compile(&self) -> Result<CompiledCircuit> {
+        self.compile_with(false)
+    }
+
+    /// Compile into a sparse R1CS (PLONKish constraints), for PLONK.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`AcirProgram::compile`].
+    pub fn compile_sparse(&self) -> Result<CompiledCircuit> {
+        self.compile_with(true)
+    }
+
+    fn compile_with(&self, sparse: bool) -> Result<CompiledCircuit> {
+        let mut id = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_acir_compile(self.handle.id(), sparse as c_int, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(CompiledCircuit {
+            handle: Handle::from_raw(id),
+            gadgets: Vec::new(),
+            hashed_public: Vec::new(),
+        })
+    }
+}
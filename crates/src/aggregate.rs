@@ -0,0 +1,180 @@
+//! Aggregation of many Groth16 proofs into one.
+
+use anyhow::{ensure, Result};
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::buffer::GnarkBuffer;
+use crate::handle::Handle;
+use crate::metrics::{self, Scheme};
+use crate::{bind, take_go_string, GnarkError, Groth16ProofResult};
+
+/// A single Groth16 proof attesting that a batch of inner proofs is valid.
+///
+/// Same hex encoding as [`Groth16ProofResult`], but over BW6-761: the public
+/// inputs are the inner proofs' public inputs, in order.
+#[derive(Debug, Clone)]
+pub struct AggregatedProof {
+    /// Hex-encoded gnark binary proof (BW6-761).
+    pub proof: String,
+    /// Hex-encoded gnark binary public witness (BW6-761).
+    pub public_inputs: String,
+}
+
+/// Recursively verifies a fixed number of BN254 proofs, all for the same
+/// circuit, inside an outer Groth16 circuit over BW6-761.
+///
+/// The inner verifier runs in emulated BN254 arithmetic, so building the outer
+/// circuit and proving with it are expensive (minutes and several GB of memory
+/// per inner proof); build one `Aggregator` and reuse it.
+///
+/// ```no_run
+/// # fn example(proofs: &[rust_gnark::Groth16ProofResult]) -> anyhow::Result<()> {
+/// let aggregator = rust_gnark::Aggregator::new("circuit.r1cs", "circuit.vk", proofs.len())?;
+/// let aggregated = aggregator.aggregate(proofs)?;
+/// assert!(aggregator.verify(&aggregated)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Aggregator {
+    handle: Handle,
+    capacity: usize,
+}
+
+impl Aggregator {
+    /// Compile the aggregation circuit for `capacity` proofs of the inner
+    /// circuit and run its Groth16 setup.
+    ///
+    /// The setup is generated locally by a single party, so the aggregated
+    /// proofs are only as trustworthy as the machine that ran it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `capacity` is zero, the inner `.r1cs`/`.vk` files
+    /// cannot be read, or compilation or setup fails.
+    pub fn new(inner_r1cs_path: &str, inner_vk_path: &str, capacity: usize) -> Result<Self> {
+        ensure!(capacity > 0, "aggregator needs at least one proof");
+        let r1cs = CString::new(inner_r1cs_path)?;
+        let vk = CString::new(inner_vk_path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_aggregator_new(
+                r1cs.as_ptr() as *mut c_char,
+                vk.as_ptr() as *mut c_char,
+                u32::try_from(capacity)?,
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+            capacity,
+        })
+    }
+
+    /// Number of inner proofs every call to [`Aggregator::aggregate`] must pass.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Prove that every proof in `proofs` is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `proofs.len()` differs from [`Aggregator::capacity`],
+    /// an inner proof fails to decode, or an inner proof is invalid (the outer
+    /// circuit is then unsatisfiable).
+    pub fn aggregate(&self, proofs: &[Groth16ProofResult]) -> Result<AggregatedProof> {
+        metrics::record_prove(Scheme::Groth16, 1, false, || {
+            ensure!(
+                proofs.len() == self.capacity,
+                "aggregator expects {} proofs, got {}",
+                self.capacity,
+                proofs.len()
+            );
+            let proof_strs = proofs
+                .iter()
+                .map(|p| CString::new(p.proof.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let public_strs = proofs
+                .iter()
+                .map(|p| CString::new(p.public_inputs.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut proof_ptrs: Vec<*mut c_char> =
+                proof_strs.iter().map(|s| s.as_ptr() as *mut _).collect();
+            let mut public_ptrs: Vec<*mut c_char> =
+                public_strs.iter().map(|s| s.as_ptr() as *mut _).collect();
+
+            let mut out_proof = std::ptr::null_mut();
+            let mut out_public = std::ptr::null_mut();
+            let err_ptr = unsafe {
+                bind::gnark_aggregator_aggregate(
+                    self.handle.id(),
+                    proof_ptrs.as_mut_ptr(),
+                    public_ptrs.as_mut_ptr(),
+                    u32::try_from(proofs.len())?,
+                    &mut out_proof,
+                    &mut out_public,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            unsafe {
+                Ok(AggregatedProof {
+                    proof: take_go_string(out_proof),
+                    public_inputs: take_go_string(out_public),
+                })
+            }
+        })
+    }
+
+    /// Verify an aggregated proof against this aggregator's outer key.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the proof is valid, `Ok(false)` if it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof or public inputs fail to deserialize.
+    pub fn verify(&self, aggregated: &AggregatedProof) -> Result<bool> {
+        metrics::record_verify(Scheme::Groth16, false, || {
+            let proof = CString::new(aggregated.proof.as_str())?;
+            let public_inputs = CString::new(aggregated.public_inputs.as_str())?;
+            let err_ptr = unsafe {
+                bind::gnark_aggregator_verify(
+                    self.handle.id(),
+                    proof.as_ptr() as *mut c_char,
+                    public_inputs.as_ptr() as *mut c_char,
+                )
+            };
+            if err_ptr.is_null() {
+                return Ok(true);
+            }
+            match unsafe { GnarkError::take(err_ptr) } {
+                GnarkError::InvalidProof(_) => Ok(false),
+                err => Err(err.into()),
+            }
+        })
+    }
+
+    /// The outer (BW6-761) verifying key, as written by gnark's `WriteTo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn verifying_key(&self) -> Result<GnarkBuffer> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_aggregator_vk_to_bytes(self.handle.id(), &mut data, &mut len) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+    }
+}
@@ -0,0 +1,72 @@
+//! Conversions between the [`native`](crate::native) types and ark-groth16.
+//!
+//! Enabled by the `ark` feature. Combined with the gnark decoders and encoders
+//! this lets gnark proofs be checked by arkworks-based verifiers, and arkworks
+//! proofs be handed to gnark. ark-groth16 has no Pedersen commitments:
+//! converting a key or proof that has one drops it, and the result does not
+//! verify.
+//!
+//! ```no_run
+//! # fn example(proof_bytes: &[u8], vk_bytes: &[u8]) -> anyhow::Result<()> {
+//! use rust_gnark::native::{Proof, VerifyingKey};
+//! let proof: ark_groth16::Proof<ark_bn254::Bn254> = Proof::from_gnark_bytes(proof_bytes)?.into();
+//! let vk: ark_groth16::VerifyingKey<ark_bn254::Bn254> = VerifyingKey::from_gnark_bytes(vk_bytes)?.into();
+//! # Ok(())
+//! # }
+//! ```
+
+use ark_bn254::{Bn254, G1Affine};
+
+use crate::native::{Proof, VerifyingKey};
+
+impl From<Proof> for ark_groth16::Proof<Bn254> {
+    fn from(proof: Proof) -> Self {
+        Self {
+            a: proof.ar,
+            b: proof.bs,
+            c: proof.krs,
+        }
+    }
+}
+
+impl From<ark_groth16::Proof<Bn254>> for Proof {
+    fn from(proof: ark_groth16::Proof<Bn254>) -> Self {
+        Self {
+            ar: proof.a,
+            bs: proof.b,
+            krs: proof.c,
+            commitments: Vec::new(),
+            commitment_pok: G1Affine::identity(),
+        }
+    }
+}
+
+impl From<VerifyingKey> for ark_groth16::VerifyingKey<Bn254> {
+    fn from(vk: VerifyingKey) -> Self {
+        Self {
+            alpha_g1: vk.alpha_g1,
+            beta_g2: vk.beta_g2,
+            gamma_g2: vk.gamma_g2,
+            delta_g2: vk.delta_g2,
+            gamma_abc_g1: vk.k,
+        }
+    }
+}
+
+/// arkworks keys do not carry `[β]1` and `[δ]1`, which gnark stores but does
+/// not use for verification; they are set to the point at infinity.
+impl From<ark_groth16::VerifyingKey<Bn254>> for VerifyingKey {
+    fn from(vk: ark_groth16::VerifyingKey<Bn254>) -> Self {
+        Self {
+            alpha_g1: vk.alpha_g1,
+            beta_g1: G1Affine::identity(),
+            beta_g2: vk.beta_g2,
+            gamma_g2: vk.gamma_g2,
+            delta_g1: G1Affine::identity(),
+            delta_g2: vk.delta_g2,
+            k: vk.gamma_abc_g1,
+            public_and_commitment_committed: Vec::new(),
+            commitment_keys: Vec::new(),
+        }
+    }
+}
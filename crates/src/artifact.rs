@@ -0,0 +1,174 @@
+//! Telling what an artifact file holds from its header.
+
+#[cfg(feature = "verifier")]
+use anyhow::bail;
+use anyhow::{Context, Result};
+use std::ffi::CString;
+use std::io::Read;
+use std::os::raw::{c_char, c_int};
+
+use crate::hash::Curve;
+#[cfg(not(feature = "verifier"))]
+use crate::ProvingKey;
+use crate::{bind, GnarkError, GnarkProofBundle, ProofScheme, VerifyingKey};
+
+/// Magic bytes at the start of a versioned key file.
+const KEY_FILE_MAGIC: &[u8] = b"GNRKKEY\0";
+/// Start of a proof bundle's header line.
+const BUNDLE_MAGIC: &[u8] = b"gnark-proof-bundle ";
+
+/// What an artifact file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactContent {
+    /// A proving key, as written by [`ProvingKey::save`].
+    ProvingKey,
+    /// A verifying key, as written by [`VerifyingKey::save`].
+    VerifyingKey,
+    /// A proof bundle, as written by [`GnarkProofBundle::write`].
+    Proof,
+}
+
+/// The content, scheme and curve of an artifact file, read by
+/// [`ArtifactHeader::read`] without decoding the artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArtifactHeader {
+    /// What the file holds.
+    pub content: ArtifactContent,
+    /// The proving system the artifact is for.
+    pub scheme: ProofScheme,
+    /// The curve the artifact is over.
+    pub curve: Curve,
+}
+
+impl ArtifactHeader {
+    /// Read the header of the artifact at `path`.
+    ///
+    /// Versioned key files and proof bundles are recognised. Keys written by
+    /// gnark itself carry no header, so they cannot be told apart here; open
+    /// them with [`ProvingKey::open_mmap`] or [`VerifyingKey::open`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::SerializationError`] if the file is neither a
+    /// versioned key file nor a proof bundle, and
+    /// [`GnarkError::CurveMismatch`] if it is over a curve this crate has no
+    /// [`Curve`] for.
+    pub fn read(path: &str) -> Result<Self> {
+        let mut magic = Vec::with_capacity(BUNDLE_MAGIC.len());
+        std::fs::File::open(path)
+            .and_then(|f| f.take(BUNDLE_MAGIC.len() as u64).read_to_end(&mut magic))
+            .with_context(|| format!("failed to read {path}"))?;
+        if magic.starts_with(KEY_FILE_MAGIC) {
+            Self::read_key_file(path)
+        } else if magic.starts_with(BUNDLE_MAGIC) {
+            let bundle = GnarkProofBundle::read(path)?;
+            Ok(Self {
+                content: ArtifactContent::Proof,
+                scheme: bundle.scheme,
+                curve: bundle.curve,
+            })
+        } else {
+            Err(GnarkError::SerializationError(format!(
+                "{path} is neither a versioned key file nor a proof bundle"
+            ))
+            .into())
+        }
+    }
+
+    fn read_key_file(path: &str) -> Result<Self> {
+        let c_path = CString::new(path)?;
+        let (mut kind, mut curve): (c_int, c_int) = (0, 0);
+        let err_ptr = unsafe {
+            bind::gnark_artifact_sniff(c_path.as_ptr() as *mut c_char, &mut kind, &mut curve)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            content: match kind {
+                1 => ArtifactContent::ProvingKey,
+                2 => ArtifactContent::VerifyingKey,
+                _ => unreachable!("gnark_artifact_sniff reported key kind {kind}"),
+            },
+            scheme: ProofScheme::Groth16,
+            curve: match curve {
+                0 => Curve::Bn254,
+                1 => Curve::Bls12_381,
+                _ => unreachable!("gnark_artifact_sniff reported curve {curve}"),
+            },
+        })
+    }
+}
+
+/// An artifact loaded by [`Artifact::load`], tagged by what its header says
+/// it is, so tooling can take artifacts of several circuits without being
+/// told which file is which.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use rust_gnark::Artifact;
+///
+/// for path in ["a.pk", "a.vk", "a.bundle"] {
+///     match Artifact::load(path)? {
+///         Artifact::ProvingKey(pk) => println!("{path}: proving key {}", pk.fingerprint()?),
+///         Artifact::VerifyingKey(vk) => println!("{path}: verifying key {}", vk.fingerprint()?),
+///         Artifact::Proof(bundle) => println!("{path}: proof for {}", bundle.circuit),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum Artifact {
+    /// A Groth16 BN254 proving key.
+    #[cfg(not(feature = "verifier"))]
+    ProvingKey(ProvingKey),
+    /// A Groth16 BN254 verifying key.
+    VerifyingKey(VerifyingKey),
+    /// A proof bundle, recording its own scheme and curve.
+    Proof(GnarkProofBundle),
+}
+
+impl Artifact {
+    /// Read the header of the artifact at `path` and load the artifact it
+    /// describes, with [`ProvingKey::load`], [`VerifyingKey::load`] or
+    /// [`GnarkProofBundle::read`].
+    ///
+    /// # Errors
+    ///
+    /// As [`ArtifactHeader::read`], or as the loader of the artifact. Keys
+    /// over curves other than BN254 fail with [`GnarkError::CurveMismatch`],
+    /// and proving keys with the `verifier` feature, which cannot load them.
+    pub fn load(path: &str) -> Result<Self> {
+        Ok(match ArtifactHeader::read(path)?.content {
+            #[cfg(not(feature = "verifier"))]
+            ArtifactContent::ProvingKey => Self::ProvingKey(ProvingKey::load(path)?),
+            #[cfg(feature = "verifier")]
+            ArtifactContent::ProvingKey => {
+                bail!("{path} holds a proving key, which the verifier build cannot load")
+            }
+            ArtifactContent::VerifyingKey => Self::VerifyingKey(VerifyingKey::load(path)?),
+            ArtifactContent::Proof => Self::Proof(GnarkProofBundle::read(path)?),
+        })
+    }
+
+    /// The proving system the artifact is for.
+    pub fn scheme(&self) -> ProofScheme {
+        match self {
+            #[cfg(not(feature = "verifier"))]
+            Self::ProvingKey(_) => ProofScheme::Groth16,
+            Self::VerifyingKey(_) => ProofScheme::Groth16,
+            Self::Proof(bundle) => bundle.scheme,
+        }
+    }
+
+    /// The curve the artifact is over.
+    pub fn curve(&self) -> Curve {
+        match self {
+            #[cfg(not(feature = "verifier"))]
+            Self::ProvingKey(_) => Curve::Bn254,
+            Self::VerifyingKey(_) => Curve::Bn254,
+            Self::Proof(bundle) => bundle.curve,
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! BLS signatures over BLS12-381, as used by Ethereum consensus and other
+//! validator sets.
+//!
+//! This is the minimal-pubkey-size variant of the IETF BLS signature draft:
+//! public keys are compressed G1 points (48 bytes), signatures compressed G2
+//! points (96 bytes), both in the zcash encoding, and secret keys 32-byte
+//! big-endian scalars. These are the encodings `blst` and the Ethereum
+//! consensus specs use, so keys and signatures interoperate with them.
+//!
+//! The domain separation tag selects the ciphersuite; Ethereum uses the
+//! proof-of-possession one, [`DST_POP`]. Requires the `bls12-381` feature.
+//!
+//! ```no_run
+//! # fn example() -> anyhow::Result<()> {
+//! use rust_gnark::bls::{SecretKey, DST_POP};
+//! let key = SecretKey::key_gen(&[7u8; 32])?;
+//! let signature = key.sign(b"message", DST_POP)?;
+//! assert!(key.public_key()?.verify(b"message", DST_POP, &signature)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{ensure, Result};
+use std::os::raw::c_int;
+use zeroize::Zeroize;
+
+use crate::eddsa::take_buffer;
+use crate::{bind, GnarkError};
+
+/// Signature tag of the proof-of-possession ciphersuite
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`.
+pub const DST_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Tag [`SecretKey::prove_possession`] signs the public key with.
+pub const DST_POP_PROOF: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Signature tag of the basic ciphersuite
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_`, under which aggregated
+/// messages must be distinct.
+pub const DST_BASIC: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// A BLS secret key, a non-zero scalar below the group order. Zeroized on
+/// drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey([u8; 32]);
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretKey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl SecretKey {
+    /// Derive a key from `ikm`, at least 32 bytes of secret key material,
+    /// with the draft's `KeyGen` (EIP-2333's base derivation).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ikm` is shorter than 32 bytes.
+    pub fn key_gen(ikm: &[u8]) -> Result<Self> {
+        let mut bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_bls_keygen(ikm.as_ptr() as *mut u8, ikm.len() as u64, data, len)
+        })?;
+        let key = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        key
+    }
+
+    /// Load a 32-byte big-endian key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a scalar in `[1, r)`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == 32,
+            "BLS secret key must be 32 bytes, got {}",
+            bytes.len()
+        );
+        let mut key = Self([0u8; 32]);
+        key.0.copy_from_slice(bytes);
+        key.public_key()?;
+        Ok(key)
+    }
+
+    /// The key as a 32-byte big-endian integer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// The matching public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go library was built without BLS12-381.
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_bls_public_key(self.0.as_ptr() as *mut u8, 32, data, len)
+        })?;
+        PublicKey::from_go(&bytes)
+    }
+
+    /// Sign `message` under domain separation tag `dst`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dst` is longer than 255 bytes.
+    pub fn sign(&self, message: &[u8], dst: &[u8]) -> Result<Signature> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_bls_sign(
+                self.0.as_ptr() as *mut u8,
+                32,
+                message.as_ptr() as *mut u8,
+                message.len() as u64,
+                dst.as_ptr() as *mut u8,
+                dst.len() as u64,
+                data,
+                len,
+            )
+        })?;
+        Signature::from_go(&bytes)
+    }
+
+    /// Proof of possession of this key: the public key signed under
+    /// [`DST_POP_PROOF`], checked by [`PublicKey::verify_possession`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go library was built without BLS12-381.
+    pub fn prove_possession(&self) -> Result<Signature> {
+        self.sign(&self.public_key()?.0, DST_POP_PROOF)
+    }
+}
+
+/// A BLS public key, a compressed G1 point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PublicKey([u8; 48]);
+
+impl PublicKey {
+    /// Load a compressed public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless `bytes` is a point of the prime-order subgroup
+    /// other than the identity.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let err_ptr = unsafe {
+            bind::gnark_bls_check_public_key(bytes.as_ptr() as *mut u8, bytes.len() as u64)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Self::from_go(bytes)
+    }
+
+    fn from_go(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(bytes.try_into()?))
+    }
+
+    /// The compressed point.
+    pub fn to_bytes(&self) -> [u8; 48] {
+        self.0
+    }
+
+    /// Check `signature` over `message` under `dst`.
+    ///
+    /// A malformed signature is reported as `Ok(false)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go library was built without BLS12-381.
+    pub fn verify(&self, message: &[u8], dst: &[u8], signature: &Signature) -> Result<bool> {
+        aggregate_verify(&[(*self, message)], dst, signature)
+    }
+
+    /// Check a proof of possession made by [`SecretKey::prove_possession`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go library was built without BLS12-381.
+    pub fn verify_possession(&self, proof: &Signature) -> Result<bool> {
+        self.verify(&self.0, DST_POP_PROOF, proof)
+    }
+
+    /// The sum of `keys`, against which [`fast_aggregate_verify`] checks an
+    /// aggregate signature over one message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keys` is empty or sums to the identity.
+    pub fn aggregate(keys: &[PublicKey]) -> Result<PublicKey> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_bls_aggregate_public_keys(
+                keys.as_ptr() as *mut u8,
+                keys.len() as u64,
+                data,
+                len,
+            )
+        })?;
+        Self::from_go(&bytes)
+    }
+}
+
+/// A BLS signature, a compressed G2 point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Signature([u8; 96]);
+
+impl Signature {
+    /// Load a compressed signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless `bytes` is a point of the prime-order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let err_ptr = unsafe {
+            bind::gnark_bls_check_signature(bytes.as_ptr() as *mut u8, bytes.len() as u64)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Self::from_go(bytes)
+    }
+
+    fn from_go(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(bytes.try_into()?))
+    }
+
+    /// The compressed point.
+    pub fn to_bytes(&self) -> [u8; 96] {
+        self.0
+    }
+
+    /// The sum of `signatures`, verified with [`aggregate_verify`] or
+    /// [`fast_aggregate_verify`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `signatures` is empty.
+    pub fn aggregate(signatures: &[Signature]) -> Result<Signature> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_bls_aggregate_signatures(
+                signatures.as_ptr() as *mut u8,
+                signatures.len() as u64,
+                data,
+                len,
+            )
+        })?;
+        Self::from_go(&bytes)
+    }
+}
+
+/// Check an aggregate `signature` over one message per signer with one
+/// multi-pairing.
+///
+/// Under [`DST_BASIC`] the messages must be distinct; the caller checks
+/// that.
+///
+/// # Errors
+///
+/// Returns an error if `signers` is empty.
+pub fn aggregate_verify(
+    signers: &[(PublicKey, &[u8])],
+    dst: &[u8],
+    signature: &Signature,
+) -> Result<bool> {
+    let mut keys = Vec::with_capacity(signers.len() * 48);
+    let mut messages = Vec::new();
+    let mut lens = Vec::with_capacity(signers.len());
+    for (key, message) in signers {
+        keys.extend_from_slice(&key.0);
+        messages.extend_from_slice(message);
+        lens.push(message.len() as u64);
+    }
+    let mut valid: c_int = 0;
+    let err_ptr = unsafe {
+        bind::gnark_bls_verify(
+            keys.as_mut_ptr(),
+            signers.len() as u64,
+            messages.as_mut_ptr(),
+            messages.len() as u64,
+            lens.as_mut_ptr(),
+            signature.0.as_ptr() as *mut u8,
+            96,
+            dst.as_ptr() as *mut u8,
+            dst.len() as u64,
+            &mut valid,
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(valid != 0)
+}
+
+/// Check an aggregate `signature` by `keys` over the same `message`. Only
+/// sound when every key's possession was proven (see
+/// [`PublicKey::verify_possession`]).
+///
+/// # Errors
+///
+/// Returns an error if `keys` is empty or sums to the identity.
+pub fn fast_aggregate_verify(
+    keys: &[PublicKey],
+    message: &[u8],
+    dst: &[u8],
+    signature: &Signature,
+) -> Result<bool> {
+    PublicKey::aggregate(keys)?.verify(message, dst, signature)
+}
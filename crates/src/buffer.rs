@@ -0,0 +1,73 @@
+//! Byte buffers allocated by the Go side and owned by Rust.
+
+use std::fmt;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+use crate::bind;
+
+/// Bytes written by Go directly into C memory, handed over without copying.
+///
+/// Dereferences to `[u8]` and frees the underlying allocation (via
+/// `gnark_free_buffer`) on drop. Use this instead of copying multi-hundred-MB
+/// serialized keys into a `Vec`; call `.to_vec()` only if an owned Rust
+/// allocation is really needed.
+pub struct GnarkBuffer {
+    data: Option<NonNull<u8>>,
+    len: usize,
+}
+
+// SAFETY: the buffer is a plain heap allocation exclusively owned by this value.
+unsafe impl Send for GnarkBuffer {}
+unsafe impl Sync for GnarkBuffer {}
+
+impl GnarkBuffer {
+    /// Take ownership of `len` bytes at `data`, as returned by a Go export.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be null or a `malloc` allocation of at least `len` bytes that
+    /// nothing else frees.
+    pub(crate) unsafe fn from_raw(data: *mut u8, len: u64) -> Self {
+        let data = NonNull::new(data);
+        let len = match data {
+            Some(_) => usize::try_from(len).expect("Go buffers fit in the address space"),
+            None => 0,
+        };
+        Self { data, len }
+    }
+}
+
+impl Deref for GnarkBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self.data {
+            // SAFETY: `from_raw` guarantees `len` initialized bytes at `data`.
+            Some(data) => unsafe { std::slice::from_raw_parts(data.as_ptr(), self.len) },
+            None => &[],
+        }
+    }
+}
+
+impl AsRef<[u8]> for GnarkBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for GnarkBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GnarkBuffer")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Drop for GnarkBuffer {
+    fn drop(&mut self) {
+        if let Some(data) = self.data {
+            unsafe { bind::gnark_free_buffer(data.as_ptr()) };
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Self-describing proof files.
+
+use anyhow::{bail, ensure, Context, Result};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::hash::Curve;
+use crate::{groth16_verify, Groth16ProofResult, KeyFingerprint, VerifyingKey, GNARK_VERSION};
+
+/// First line of every bundle, naming the format and its version.
+const HEADER: &str = "gnark-proof-bundle v1";
+
+/// A proving system a bundle's proof belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProofScheme {
+    /// Groth16, as produced by [`crate::groth16_prove`].
+    Groth16,
+}
+
+impl fmt::Display for ProofScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Groth16 => "groth16",
+        })
+    }
+}
+
+impl FromStr for ProofScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "groth16" => Ok(Self::Groth16),
+            _ => bail!("unknown proof scheme {s:?}"),
+        }
+    }
+}
+
+/// A proof with the metadata needed to check it belongs to a verifier.
+///
+/// Besides the proof and public inputs, a bundle records the curve, the
+/// scheme, the circuit (as the [`VerifyingKey::fingerprint`] of its key) and
+/// the rust-gnark and gnark versions that produced it. [`GnarkProofBundle::check`]
+/// compares the first three against a verifying key, so a proof for another
+/// circuit or key fails with an error naming the mismatch instead of as an
+/// invalid proof. The versions are informational.
+///
+/// The encoding is UTF-8 text, one `key: value` line per field after a
+/// header line, with the proof and public inputs in gnark's hex encoding:
+///
+/// ```text
+/// gnark-proof-bundle v1
+/// scheme: groth16
+/// curve: bn254
+/// circuit: 3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29
+/// rust-gnark: 0.0.1
+/// gnark: v0.14.0
+/// public-inputs: 000000010000000000000001...
+/// proof: a1b2c3...
+/// ```
+#[derive(Debug, Clone)]
+pub struct GnarkProofBundle {
+    /// The proof and its public inputs.
+    pub proof: Groth16ProofResult,
+    /// The proving system.
+    pub scheme: ProofScheme,
+    /// The curve the proof is over.
+    pub curve: Curve,
+    /// The fingerprint of the circuit's verifying key.
+    pub circuit: KeyFingerprint,
+    /// The rust-gnark version that wrote the bundle.
+    pub crate_version: String,
+    /// The gnark version that generated the proof.
+    pub gnark_version: String,
+}
+
+impl GnarkProofBundle {
+    /// Bundle a BN254 Groth16 proof for the circuit of `vk`, recording this
+    /// crate's versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key's fingerprint cannot be computed.
+    pub fn new(proof: Groth16ProofResult, vk: &VerifyingKey) -> Result<Self> {
+        Ok(Self {
+            proof,
+            scheme: ProofScheme::Groth16,
+            curve: Curve::Bn254,
+            circuit: vk.fingerprint()?,
+            crate_version: env!("CARGO_PKG_VERSION").into(),
+            gnark_version: GNARK_VERSION.into(),
+        })
+    }
+
+    /// Encode the bundle.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    /// Decode a bundle written by [`GnarkProofBundle::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a bundle, is of a newer format
+    /// version or has a missing, repeated or malformed field.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        std::str::from_utf8(bytes)
+            .context("a proof bundle is UTF-8 text")?
+            .parse()
+    }
+
+    /// Write the bundle to the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes())
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Read a bundle from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or as
+    /// [`GnarkProofBundle::from_bytes`].
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_bytes(&bytes).with_context(|| format!("invalid proof bundle {}", path.display()))
+    }
+
+    /// Check that the bundle is a BN254 Groth16 proof for the circuit of `vk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first field that does not match.
+    pub fn check(&self, vk: &VerifyingKey) -> Result<()> {
+        ensure!(
+            self.scheme == ProofScheme::Groth16,
+            "the bundle holds a {} proof, expected groth16",
+            self.scheme
+        );
+        ensure!(
+            self.curve == Curve::Bn254,
+            "the bundle holds a proof over {}, expected bn254",
+            curve_name(self.curve)
+        );
+        let expected = vk.fingerprint()?;
+        ensure!(
+            self.circuit == expected,
+            "the bundle is for circuit {}, but the verifying key is {expected} \
+             (proof made with rust-gnark {}, gnark {})",
+            self.circuit,
+            self.crate_version,
+            self.gnark_version
+        );
+        Ok(())
+    }
+
+    /// [`GnarkProofBundle::check`] the bundle against the `.vk` file at
+    /// `vk_path`, then verify its proof as [`groth16_verify`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key cannot be read, the bundle does not match
+    /// it, or verification fails to run.
+    pub fn verify(&self, r1cs_path: &str, vk_path: &str) -> Result<bool> {
+        self.check(&VerifyingKey::open(vk_path)?)?;
+        groth16_verify(r1cs_path, vk_path, &self.proof)
+    }
+}
+
+impl fmt::Display for GnarkProofBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "scheme: {}", self.scheme)?;
+        writeln!(f, "curve: {}", curve_name(self.curve))?;
+        writeln!(f, "circuit: {}", self.circuit)?;
+        writeln!(f, "rust-gnark: {}", self.crate_version)?;
+        writeln!(f, "gnark: {}", self.gnark_version)?;
+        writeln!(f, "public-inputs: {}", self.proof.public_inputs)?;
+        writeln!(f, "proof: {}", self.proof.proof)
+    }
+}
+
+impl FromStr for GnarkProofBundle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s.lines();
+        match lines.next() {
+            Some(HEADER) => {}
+            Some(header) if header.starts_with("gnark-proof-bundle ") => {
+                bail!("unsupported proof bundle version {header:?}, expected {HEADER:?}")
+            }
+            _ => bail!("not a proof bundle"),
+        }
+
+        let mut fields = std::collections::HashMap::new();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let (key, value) = line
+                .split_once(": ")
+                .with_context(|| format!("malformed proof bundle line {line:?}"))?;
+            ensure!(
+                fields.insert(key, value).is_none(),
+                "repeated proof bundle field {key:?}"
+            );
+        }
+        let mut field = |key: &str| {
+            fields
+                .remove(key)
+                .with_context(|| format!("proof bundle has no {key:?} field"))
+        };
+
+        let bundle = Self {
+            scheme: field("scheme")?.parse()?,
+            curve: parse_curve(field("curve")?)?,
+            circuit: field("circuit")?.parse()?,
+            crate_version: field("rust-gnark")?.into(),
+            gnark_version: field("gnark")?.into(),
+            proof: Groth16ProofResult {
+                public_inputs: field("public-inputs")?.into(),
+                proof: field("proof")?.into(),
+            },
+        };
+        if let Some(key) = fields.keys().next() {
+            bail!("unknown proof bundle field {key:?}");
+        }
+        Ok(bundle)
+    }
+}
+
+fn curve_name(curve: Curve) -> &'static str {
+    match curve {
+        Curve::Bn254 => "bn254",
+        Curve::Bls12_381 => "bls12-381",
+    }
+}
+
+fn parse_curve(s: &str) -> Result<Curve> {
+    match s {
+        "bn254" => Ok(Curve::Bn254),
+        "bls12-381" => Ok(Curve::Bls12_381),
+        _ => bail!("unknown curve {s:?}"),
+    }
+}
@@ -0,0 +1,191 @@
+//! Per-device tuning of the prover's parallelism.
+//!
+//! Groth16 proving time is dominated by multi-scalar multiplications, and
+//! the thread count that runs them fastest differs widely between devices:
+//! on big.LITTLE phone SoCs, spreading over every core often loses to using
+//! the big ones only. [`calibrate`] times a short MSM at each candidate
+//! thread count; [`ArtifactStore::calibration`] runs it once per device and
+//! keeps the result next to the artifacts.
+//!
+//! gnark-crypto chooses the MSM bucket window from the number of points, so
+//! the thread count is the parameter left to tune.
+
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+use crate::store::write_atomic;
+use crate::{bind, ArtifactStore, GnarkError, ProverConfig};
+
+/// Points in the calibration MSM: large enough to use the windows of real
+/// proofs, small enough to take tens of milliseconds on a phone.
+pub(crate) const CALIBRATION_POINTS: u64 = 1 << 14;
+
+/// Runs per thread count; the fastest counts.
+const CALIBRATION_RUNS: usize = 2;
+
+/// A thread count within this factor of the fastest is preferred if smaller,
+/// since it leaves cores to the app for about the same proving time.
+const TOLERANCE: f64 = 1.05;
+
+/// File holding the calibration, in the store's root next to the versioned
+/// layout directories.
+const CALIBRATION_FILE: &str = "calibration";
+
+/// Prover parameters measured on this device by [`calibrate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    threads: usize,
+    msm_nanos: u64,
+    cpus: usize,
+}
+
+impl Calibration {
+    /// The thread count to prove with.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// How long the calibration MSM took with [`Calibration::threads`].
+    pub fn msm_time(&self) -> std::time::Duration {
+        std::time::Duration::from_nanos(self.msm_nanos)
+    }
+
+    /// `config` with the calibrated thread count (see
+    /// [`ProverConfig::with_threads`]).
+    pub fn apply(&self, config: ProverConfig) -> ProverConfig {
+        config.with_threads(self.threads)
+    }
+
+    fn encode(&self) -> String {
+        format!(
+            "threads={}\nmsm_nanos={}\ncpus={}\n",
+            self.threads, self.msm_nanos, self.cpus
+        )
+    }
+
+    fn decode(s: &str) -> Result<Self> {
+        let mut fields = [None; 3];
+        for line in s.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                bail!("malformed line {line:?}");
+            };
+            let slot = match key {
+                "threads" => 0,
+                "msm_nanos" => 1,
+                "cpus" => 2,
+                _ => continue,
+            };
+            fields[slot] = Some(value.parse::<u64>().with_context(|| format!("bad {key}"))?);
+        }
+        let [Some(threads), Some(msm_nanos), Some(cpus)] = fields else {
+            bail!("missing fields");
+        };
+        if threads == 0 {
+            bail!("zero threads");
+        }
+        Ok(Self {
+            threads: threads as usize,
+            msm_nanos,
+            cpus: cpus as usize,
+        })
+    }
+}
+
+/// Time the calibration MSM at 1, 2, 4, ... threads up to the number of
+/// CPUs (which is always tried) and pick the fastest, preferring fewer
+/// threads when they are within 5%.
+///
+/// This takes between a fraction of a second and a few seconds; run it
+/// once, e.g. through [`ArtifactStore::calibration`].
+///
+/// # Errors
+///
+/// Returns an error if the Go side fails to run the MSM.
+pub fn calibrate() -> Result<Calibration> {
+    let cpus = available_cpus();
+    let mut candidates: Vec<usize> = std::iter::successors(Some(1usize), |t| Some(t * 2))
+        .take_while(|&t| t < cpus)
+        .collect();
+    candidates.push(cpus);
+
+    let mut timings = Vec::with_capacity(candidates.len());
+    for threads in candidates {
+        let mut best = u64::MAX;
+        for _ in 0..CALIBRATION_RUNS {
+            best = best.min(msm_benchmark(CALIBRATION_POINTS, threads)?);
+        }
+        timings.push((threads, best));
+    }
+    let fastest = timings.iter().map(|&(_, t)| t).min().unwrap_or(0);
+    let (threads, msm_nanos) = timings
+        .into_iter()
+        .find(|&(_, t)| t as f64 <= fastest as f64 * TOLERANCE)
+        .expect("the fastest candidate is within tolerance");
+    Ok(Calibration {
+        threads,
+        msm_nanos,
+        cpus,
+    })
+}
+
+/// Nanoseconds one BN254 G1 MSM of `points` random points takes with
+/// `threads` threads.
+fn msm_benchmark(points: u64, threads: usize) -> Result<u64> {
+    let mut nanos = 0i64;
+    let err_ptr = unsafe { bind::gnark_msm_benchmark(points, threads as i32, &mut nanos) };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(nanos.max(0) as u64)
+}
+
+fn available_cpus() -> usize {
+    std::thread::available_parallelism().map_or(1, usize::from)
+}
+
+impl ArtifactStore {
+    /// This device's [`Calibration`], measured with [`calibrate`] on first
+    /// use and kept in the store.
+    ///
+    /// The saved calibration is measured again if it is unreadable or the
+    /// number of CPUs changed, e.g. when the store was restored on another
+    /// device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if calibrating fails or the result cannot be saved.
+    pub fn calibration(&self) -> Result<Calibration> {
+        let path = self.calibration_path();
+        if let Ok(saved) = std::fs::read_to_string(&path) {
+            if let Ok(calibration) = Calibration::decode(&saved) {
+                if calibration.cpus == available_cpus() {
+                    return Ok(calibration);
+                }
+            }
+        }
+        let calibration = calibrate()?;
+        write_atomic(&path, calibration.encode())?;
+        Ok(calibration)
+    }
+
+    /// Forget the saved calibration, so the next
+    /// [`ArtifactStore::calibration`] measures again (e.g. after an OS or
+    /// library update).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn clear_calibration(&self) -> Result<()> {
+        let path = self.calibration_path();
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn calibration_path(&self) -> PathBuf {
+        self.root().join(CALIBRATION_FILE)
+    }
+}
@@ -0,0 +1,58 @@
+//! Cooperative cancellation of in-flight proofs.
+//!
+//! A [`CancelToken`] is backed by a Go `context.Context`. Cancelling it makes the
+//! Go prover stop reading keys and abandon the proof at the next phase boundary,
+//! returning control to the caller instead of running to completion.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::bind;
+
+/// Handle to a Go-side cancellation context.
+///
+/// Pass it to [`crate::groth16_prove_cancellable`], or use [`crate::ProveTask::cancel`]
+/// which manages one internally. Cancelling is idempotent and may happen from
+/// any thread.
+#[derive(Debug)]
+pub struct CancelToken {
+    id: u64,
+    cancelled: AtomicBool,
+}
+
+impl CancelToken {
+    /// Create a new, not yet cancelled token.
+    pub fn new() -> Self {
+        let id = unsafe { bind::gnark_cancel_token_new() };
+        Self {
+            id,
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    /// Request cancellation of every call using this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        unsafe { bind::gnark_cancel(self.id) };
+    }
+
+    /// Returns `true` if [`CancelToken::cancel`] has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CancelToken {
+    fn drop(&mut self) {
+        unsafe { bind::gnark_cancel_token_free(self.id) };
+    }
+}
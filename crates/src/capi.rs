@@ -0,0 +1,385 @@
+//! Stable C ABI over the safe API, for C, C++, Flutter (`dart:ffi`) and React
+//! Native hosts.
+//!
+//! With the `capi` feature the crate exports the `rust_gnark_*` functions
+//! declared in `include/rust_gnark.h`, which cbindgen generates from this
+//! module (see `cbindgen.toml`). Unlike the raw `gnark_*` functions of
+//! `libgnark.h`, they take paths and JSON, return this crate's errors and
+//! manage handles themselves. Link the crate into a `staticlib` or `cdylib`
+//! crate of your own and ship its library with the header.
+//!
+//! Every function returns a [`RustGnarkStatus`]; on failure, if `out_error` is
+//! not null, it receives the error message. Strings returned through out
+//! pointers are owned by the caller and freed with [`rust_gnark_string_free`].
+//! Rust panics are caught and reported as
+//! [`RustGnarkStatus::InternalPanic`].
+//!
+//! Functions are only ever added to this ABI; [`RUST_GNARK_ABI_VERSION`]
+//! increases when they are.
+
+use anyhow::Result;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+#[cfg(not(feature = "verifier"))]
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+#[cfg(not(feature = "verifier"))]
+use std::sync::Arc;
+
+use crate::{GnarkError, Groth16ProofResult};
+#[cfg(not(feature = "verifier"))]
+use crate::{ProveTask, ProverSession};
+
+/// Version of the `rust_gnark_*` ABI declared in `rust_gnark.h`.
+pub const RUST_GNARK_ABI_VERSION: u32 = 3;
+
+/// Outcome of a `rust_gnark_*` call, mirroring [`GnarkError`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RustGnarkStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// See [`GnarkError::InvalidInput`], also reported for a null or
+    /// non-UTF-8 argument.
+    InvalidInput = 1,
+    /// See [`GnarkError::Io`].
+    Io = 2,
+    /// See [`GnarkError::InvalidWitness`].
+    InvalidWitness = 3,
+    /// See [`GnarkError::WitnessSolveFailed`].
+    WitnessSolveFailed = 4,
+    /// See [`GnarkError::InvalidKey`].
+    InvalidKey = 5,
+    /// See [`GnarkError::CurveMismatch`].
+    CurveMismatch = 6,
+    /// See [`GnarkError::SerializationError`].
+    Serialization = 7,
+    /// See [`GnarkError::Cancelled`].
+    Cancelled = 8,
+    /// See [`GnarkError::OutOfMemory`].
+    OutOfMemory = 9,
+    /// See [`GnarkError::InvalidProof`].
+    InvalidProof = 10,
+    /// gnark or this crate panicked; the message carries the panic.
+    InternalPanic = 11,
+    /// Any other failure.
+    Other = 12,
+}
+
+impl From<&GnarkError> for RustGnarkStatus {
+    fn from(err: &GnarkError) -> Self {
+        match err {
+            GnarkError::InvalidInput(_) => Self::InvalidInput,
+            GnarkError::Io(_) => Self::Io,
+            GnarkError::InvalidWitness(_) => Self::InvalidWitness,
+            GnarkError::WitnessSolveFailed { .. } => Self::WitnessSolveFailed,
+            GnarkError::InvalidKey(_) => Self::InvalidKey,
+            GnarkError::CurveMismatch(_) => Self::CurveMismatch,
+            GnarkError::SerializationError(_) => Self::Serialization,
+            GnarkError::Cancelled => Self::Cancelled,
+            GnarkError::OutOfMemory => Self::OutOfMemory,
+            GnarkError::InvalidProof(_) => Self::InvalidProof,
+            GnarkError::InternalPanic { .. } => Self::InternalPanic,
+            GnarkError::Other(_) => Self::Other,
+        }
+    }
+}
+
+/// A circuit and proving key loaded once for repeated proofs (a
+/// [`ProverSession`]), created with [`rust_gnark_prover_new`]. Safe to share
+/// between threads.
+#[cfg(not(feature = "verifier"))]
+pub struct RustGnarkProver {
+    session: Arc<ProverSession>,
+}
+
+/// Receives the outcome of [`rust_gnark_prover_prove_async`], on one of the
+/// crate's prover threads. On success `proof` and `public_inputs` are set and `error` is
+/// null, on failure the reverse; the callback owns the strings and frees them
+/// with [`rust_gnark_string_free`].
+#[cfg(not(feature = "verifier"))]
+pub type RustGnarkProveCallback = extern "C" fn(
+    user_data: *mut c_void,
+    status: RustGnarkStatus,
+    proof: *mut c_char,
+    public_inputs: *mut c_char,
+    error: *mut c_char,
+);
+
+/// The caller's `user_data`, handed to the proving thread.
+#[cfg(not(feature = "verifier"))]
+struct UserData(*mut c_void);
+
+// SAFETY: rust_gnark_prover_prove_async requires user_data to be usable from
+// the callback's thread.
+#[cfg(not(feature = "verifier"))]
+unsafe impl Send for UserData {}
+
+/// Run `f`, reporting its error or panic through `out_error`.
+unsafe fn run(out_error: *mut *mut c_char, f: impl FnOnce() -> Result<()>) -> RustGnarkStatus {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return RustGnarkStatus::Ok,
+        Ok(Err(err)) => {
+            let status = err
+                .downcast_ref::<GnarkError>()
+                .map_or(RustGnarkStatus::Other, RustGnarkStatus::from);
+            (status, format!("{err:#}"))
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".into());
+            (RustGnarkStatus::InternalPanic, message)
+        }
+    };
+    if !out_error.is_null() {
+        *out_error = into_c_string(message);
+    }
+    status
+}
+
+/// Borrow a NUL-terminated UTF-8 argument.
+unsafe fn arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    non_null(ptr, name)?;
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| GnarkError::InvalidInput(format!("{name} is not UTF-8")).into())
+}
+
+fn non_null<T>(ptr: *const T, name: &str) -> Result<()> {
+    if ptr.is_null() {
+        return Err(GnarkError::InvalidInput(format!("{name} is null")).into());
+    }
+    Ok(())
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', "\u{fffd}"))
+        .expect("NULs were replaced")
+        .into_raw()
+}
+
+/// Write a proof to the caller's out pointers.
+#[cfg(not(feature = "verifier"))]
+unsafe fn write_proof(
+    proof: Groth16ProofResult,
+    out_proof: *mut *mut c_char,
+    out_public_inputs: *mut *mut c_char,
+) {
+    *out_proof = into_c_string(proof.proof);
+    *out_public_inputs = into_c_string(proof.public_inputs);
+}
+
+/// The ABI version the library implements, to compare with
+/// `RUST_GNARK_ABI_VERSION` from the header the host was built against.
+#[no_mangle]
+pub extern "C" fn rust_gnark_abi_version() -> u32 {
+    RUST_GNARK_ABI_VERSION
+}
+
+/// Free a string returned by a `rust_gnark_*` function. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string from this library, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Initialize the gnark Go runtime (see [`crate::init`]).
+///
+/// # Safety
+///
+/// `out_error` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_init(out_error: *mut *mut c_char) -> RustGnarkStatus {
+    run(out_error, crate::init)
+}
+
+/// Release the Go runtime's memory and idle threads (see [`crate::shutdown`]).
+#[no_mangle]
+pub extern "C" fn rust_gnark_shutdown() {
+    crate::shutdown();
+}
+
+/// Generate a proof from `.r1cs` and `.pk` files (see [`crate::groth16_prove`]).
+/// The hex-encoded proof and public inputs go to `out_proof` and
+/// `out_public_inputs`.
+///
+/// # Safety
+///
+/// The string arguments must be null or NUL-terminated, and the out pointers
+/// must be valid for writes (`out_error` may be null).
+#[cfg(not(feature = "verifier"))]
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_groth16_prove(
+    r1cs_path: *const c_char,
+    pk_path: *const c_char,
+    witness_json: *const c_char,
+    out_proof: *mut *mut c_char,
+    out_public_inputs: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> RustGnarkStatus {
+    run(out_error, || {
+        non_null(out_proof, "out_proof")?;
+        non_null(out_public_inputs, "out_public_inputs")?;
+        let proof = crate::groth16_prove(
+            arg(r1cs_path, "r1cs_path")?,
+            arg(pk_path, "pk_path")?,
+            arg(witness_json, "witness_json")?,
+        )?;
+        write_proof(proof, out_proof, out_public_inputs);
+        Ok(())
+    })
+}
+
+/// Verify a hex-encoded proof (see [`crate::groth16_verify`]). `out_valid` is
+/// set to false for a well-formed proof that does not verify.
+///
+/// # Safety
+///
+/// The string arguments must be null or NUL-terminated, and the out pointers
+/// must be valid for writes (`out_error` may be null).
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_groth16_verify(
+    r1cs_path: *const c_char,
+    vk_path: *const c_char,
+    proof: *const c_char,
+    public_inputs: *const c_char,
+    out_valid: *mut bool,
+    out_error: *mut *mut c_char,
+) -> RustGnarkStatus {
+    run(out_error, || {
+        non_null(out_valid, "out_valid")?;
+        let proof = Groth16ProofResult {
+            proof: arg(proof, "proof")?.to_owned(),
+            public_inputs: arg(public_inputs, "public_inputs")?.to_owned(),
+        };
+        *out_valid = crate::groth16_verify(
+            arg(r1cs_path, "r1cs_path")?,
+            arg(vk_path, "vk_path")?,
+            &proof,
+        )?;
+        Ok(())
+    })
+}
+
+/// Load a `.r1cs` and (trusted, raw) `.pk` file into a prover, freed with
+/// [`rust_gnark_prover_free`].
+///
+/// # Safety
+///
+/// As for [`rust_gnark_groth16_prove`].
+#[cfg(not(feature = "verifier"))]
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_prover_new(
+    r1cs_path: *const c_char,
+    pk_path: *const c_char,
+    out_prover: *mut *mut RustGnarkProver,
+    out_error: *mut *mut c_char,
+) -> RustGnarkStatus {
+    run(out_error, || {
+        non_null(out_prover, "out_prover")?;
+        let session = ProverSession::new(arg(r1cs_path, "r1cs_path")?, arg(pk_path, "pk_path")?)?;
+        *out_prover = Box::into_raw(Box::new(RustGnarkProver {
+            session: Arc::new(session),
+        }));
+        Ok(())
+    })
+}
+
+/// Prove `witness_json` with a loaded prover, as [`rust_gnark_groth16_prove`].
+/// May be called from several threads at once.
+///
+/// # Safety
+///
+/// `prover` must come from [`rust_gnark_prover_new`] and not be freed; the
+/// other arguments as for [`rust_gnark_groth16_prove`].
+#[cfg(not(feature = "verifier"))]
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_prover_prove(
+    prover: *const RustGnarkProver,
+    witness_json: *const c_char,
+    out_proof: *mut *mut c_char,
+    out_public_inputs: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> RustGnarkStatus {
+    run(out_error, || {
+        non_null(prover, "prover")?;
+        non_null(out_proof, "out_proof")?;
+        non_null(out_public_inputs, "out_public_inputs")?;
+        let proof = (*prover)
+            .session
+            .prove(arg(witness_json, "witness_json")?)?;
+        write_proof(proof, out_proof, out_public_inputs);
+        Ok(())
+    })
+}
+
+/// Prove `witness_json` on the crate's prover threads, as [`ProveTask`] does,
+/// and report the result to `callback`, so UI threads (Dart's, React Native's
+/// JS thread) are not blocked. Returns once the proof is queued; argument
+/// errors are returned here and do not reach `callback`, while a proof that
+/// panics reports [`RustGnarkStatus::Other`]. The prover may be freed while
+/// the proof runs.
+///
+/// # Safety
+///
+/// `prover` must come from [`rust_gnark_prover_new`] and not be freed, and
+/// `witness_json` must be null or NUL-terminated; both are only read before
+/// this returns. `user_data` is passed to `callback` as is, from another
+/// thread.
+#[cfg(not(feature = "verifier"))]
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_prover_prove_async(
+    prover: *const RustGnarkProver,
+    witness_json: *const c_char,
+    callback: RustGnarkProveCallback,
+    user_data: *mut c_void,
+    out_error: *mut *mut c_char,
+) -> RustGnarkStatus {
+    run(out_error, || {
+        non_null(prover, "prover")?;
+        let session = Arc::clone(&(*prover).session);
+        let witness_json = arg(witness_json, "witness_json")?.to_owned();
+        let user_data = UserData(user_data);
+        ProveTask::spawn(move |token| session.prove_cancellable(&witness_json, token)).then(
+            move |result| {
+                // Move the whole UserData, not just its (non-Send) pointer.
+                let user_data = user_data;
+                let (mut proof, mut public_inputs, mut error) = (
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                );
+                let status = unsafe {
+                    run(&mut error, || {
+                        write_proof(result?, &mut proof, &mut public_inputs);
+                        Ok(())
+                    })
+                };
+                callback(user_data.0, status, proof, public_inputs, error);
+            },
+        );
+        Ok(())
+    })
+}
+
+/// Free a prover. Null is ignored.
+///
+/// # Safety
+///
+/// `prover` must be null or come from [`rust_gnark_prover_new`], with no
+/// synchronous call using it still running.
+#[cfg(not(feature = "verifier"))]
+#[no_mangle]
+pub unsafe extern "C" fn rust_gnark_prover_free(prover: *mut RustGnarkProver) {
+    if !prover.is_null() {
+        drop(Box::from_raw(prover));
+    }
+}
@@ -0,0 +1,275 @@
+//! Multi-party Groth16 trusted-setup ceremonies.
+//!
+//! Wraps gnark's BN254 `mpcsetup`. Phase 1 produces circuit-independent
+//! powers of tau ([`Phase1`], finalized into [`SrsCommons`]); phase 2
+//! specializes them to one R1CS ([`Phase2`], finalized into `.pk`/`.vk`
+//! files). The setup is sound as long as one participant discards their
+//! randomness.
+//!
+//! A coordinator hands each participant the latest state as bytes; the
+//! participant loads it, calls `contribute`, and sends back `to_bytes`. The
+//! coordinator checks each step with `verify_contribution` and, once done,
+//! seals the chain with a public random beacon:
+//!
+//! ```no_run
+//! # fn example() -> anyhow::Result<()> {
+//! use rust_gnark::ceremony::{finalize_phase1, finalize_phase2, Phase1, Phase2};
+//!
+//! let initial = Phase1::new(1 << 10)?;
+//! let mut first = Phase1::from_bytes(&initial.to_bytes()?)?;
+//! first.contribute()?; // on participant 1's machine
+//! initial.verify_contribution(&first)?;
+//! let commons = finalize_phase1(1 << 10, b"beacon 1", &[&first])?;
+//!
+//! let initial = Phase2::new("circuit.r1cs", &commons)?;
+//! let mut first = Phase2::from_bytes(&initial.to_bytes()?)?;
+//! first.contribute()?;
+//! initial.verify_contribution(&first)?;
+//! finalize_phase2("circuit.r1cs", &commons, b"beacon 2", &[&first], "circuit.pk", "circuit.vk")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Result;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+use crate::buffer::GnarkBuffer;
+use crate::handle::Handle;
+use crate::{bind, GnarkError};
+
+/// Object kinds for `gnark_mpc_from_bytes`.
+const KIND_PHASE1: c_int = 0;
+const KIND_PHASE2: c_int = 1;
+const KIND_SRS_COMMONS: c_int = 2;
+
+fn check(err_ptr: *mut c_char) -> Result<()> {
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(())
+}
+
+fn from_bytes(kind: c_int, bytes: &[u8]) -> Result<Handle> {
+    let mut id = 0u64;
+    check(unsafe {
+        bind::gnark_mpc_from_bytes(kind, bytes.as_ptr() as *mut u8, bytes.len() as u64, &mut id)
+    })?;
+    Ok(Handle::from_raw(id))
+}
+
+fn to_bytes(handle: &Handle) -> Result<GnarkBuffer> {
+    let mut data = std::ptr::null_mut();
+    let mut len = 0u64;
+    check(unsafe { bind::gnark_mpc_to_bytes(handle.id(), &mut data, &mut len) })?;
+    Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+}
+
+/// Phase 1 state: powers of tau for circuits up to a fixed size.
+#[derive(Debug)]
+pub struct Phase1 {
+    handle: Handle,
+}
+
+impl Phase1 {
+    /// The initial state for circuits of up to `size` constraints.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is not a power of two.
+    pub fn new(size: u64) -> Result<Self> {
+        let mut id = 0u64;
+        check(unsafe { bind::gnark_mpc_phase1_new(size, &mut id) })?;
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Load a state serialized with [`Phase1::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a phase 1 state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            handle: from_bytes(KIND_PHASE1, bytes)?,
+        })
+    }
+
+    /// Serialize the state to hand to the next participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<GnarkBuffer> {
+        to_bytes(&self.handle)
+    }
+
+    /// Add a contribution with fresh randomness, which is then discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this state.
+    pub fn contribute(&mut self) -> Result<()> {
+        check(unsafe { bind::gnark_mpc_contribute(self.handle.id()) })
+    }
+
+    /// Check that `next` is a valid contribution on top of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidProof`] if it is not.
+    pub fn verify_contribution(&self, next: &Phase1) -> Result<()> {
+        check(unsafe { bind::gnark_mpc_verify(self.handle.id(), next.handle.id()) })
+    }
+}
+
+/// The finalized output of phase 1, input to every phase 2.
+#[derive(Debug)]
+pub struct SrsCommons {
+    handle: Handle,
+}
+
+impl SrsCommons {
+    /// Load commons serialized with [`SrsCommons::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` are not phase 1 commons.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            handle: from_bytes(KIND_SRS_COMMONS, bytes)?,
+        })
+    }
+
+    /// Serialize the commons.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<GnarkBuffer> {
+        to_bytes(&self.handle)
+    }
+}
+
+/// Phase 2 state: a setup specialized to one R1CS.
+#[derive(Debug)]
+pub struct Phase2 {
+    handle: Handle,
+}
+
+impl Phase2 {
+    /// The initial phase 2 state for the R1CS at `r1cs_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the R1CS cannot be read or is larger than the
+    /// phase 1 size.
+    pub fn new(r1cs_path: &str, commons: &SrsCommons) -> Result<Self> {
+        let path = CString::new(r1cs_path)?;
+        let mut id = 0u64;
+        check(unsafe {
+            bind::gnark_mpc_phase2_new(path.as_ptr() as *mut c_char, commons.handle.id(), &mut id)
+        })?;
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Load a state serialized with [`Phase2::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a phase 2 state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            handle: from_bytes(KIND_PHASE2, bytes)?,
+        })
+    }
+
+    /// Serialize the state to hand to the next participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<GnarkBuffer> {
+        to_bytes(&self.handle)
+    }
+
+    /// Add a contribution with fresh randomness, which is then discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this state.
+    pub fn contribute(&mut self) -> Result<()> {
+        check(unsafe { bind::gnark_mpc_contribute(self.handle.id()) })
+    }
+
+    /// Check that `next` is a valid contribution on top of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidProof`] if it is not.
+    pub fn verify_contribution(&self, next: &Phase2) -> Result<()> {
+        check(unsafe { bind::gnark_mpc_verify(self.handle.id(), next.handle.id()) })
+    }
+}
+
+/// Verify the chain of phase 1 `contributions` (in order, without the
+/// initial state) and seal it with `beacon`, a public random value fixed
+/// after the last contribution (e.g. a future block hash).
+///
+/// # Errors
+///
+/// Returns [`GnarkError::InvalidProof`] if any contribution is invalid.
+pub fn finalize_phase1(size: u64, beacon: &[u8], contributions: &[&Phase1]) -> Result<SrsCommons> {
+    let mut ids: Vec<u64> = contributions.iter().map(|p| p.handle.id()).collect();
+    let mut id = 0u64;
+    check(unsafe {
+        bind::gnark_mpc_phase1_finalize(
+            size,
+            beacon.as_ptr() as *mut u8,
+            beacon.len() as u64,
+            ids.as_mut_ptr(),
+            u32::try_from(ids.len())?,
+            &mut id,
+        )
+    })?;
+    Ok(SrsCommons {
+        handle: Handle::from_raw(id),
+    })
+}
+
+/// Verify the chain of phase 2 `contributions` for the R1CS at `r1cs_path`,
+/// seal it with `beacon`, and write the proving key (raw format, as read by
+/// [`crate::groth16_prove`]) and verifying key.
+///
+/// # Errors
+///
+/// Returns [`GnarkError::InvalidProof`] if any contribution is invalid, or an
+/// error if a file cannot be read or written.
+pub fn finalize_phase2(
+    r1cs_path: &str,
+    commons: &SrsCommons,
+    beacon: &[u8],
+    contributions: &[&Phase2],
+    pk_path: &str,
+    vk_path: &str,
+) -> Result<()> {
+    let r1cs = CString::new(r1cs_path)?;
+    let pk = CString::new(pk_path)?;
+    let vk = CString::new(vk_path)?;
+    let mut ids: Vec<u64> = contributions.iter().map(|p| p.handle.id()).collect();
+    check(unsafe {
+        bind::gnark_mpc_phase2_finalize(
+            r1cs.as_ptr() as *mut c_char,
+            commons.handle.id(),
+            beacon.as_ptr() as *mut u8,
+            beacon.len() as u64,
+            ids.as_mut_ptr(),
+            u32::try_from(ids.len())?,
+            pk.as_ptr() as *mut c_char,
+            vk.as_ptr() as *mut c_char,
+        )
+    })
+}
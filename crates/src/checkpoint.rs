@@ -0,0 +1,277 @@
+//! Proving in two steps, which can run in different processes.
+//!
+//! [`ProverSession::solve_witness`] (or [`CompiledCircuit::solve_witness`],
+//! which needs no proving key) runs the witness solver, including every hint,
+//! and returns a [`SolvedWitness`] that can be written to disk or sent
+//! elsewhere. [`ProverSession::prove_from_solved`] (or
+//! [`groth16_prove_with_full_witness`]) proves from it later, e.g. after an
+//! app restart or on a server, replaying the recorded hint outputs instead of
+//! computing them again: the prover needs neither the witness JSON nor the
+//! circuit's [`crate::register_hint`] closures and feeders.
+//!
+//! gnark's prover solves the constraint system and runs its MSMs in one call,
+//! so that call cannot be checkpointed itself: an interrupted proof restarts
+//! from the solved witness. What is kept is the witness and everything the
+//! hints computed (fed values, [`crate::register_hint`] closures, gadgets'
+//! divisions and decompositions); the prover still re-runs the cheap linear
+//! part of the solve.
+
+use anyhow::{Context, Result};
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_char;
+use std::path::Path;
+use zeroize::Zeroize;
+
+use crate::store::write_atomic;
+use crate::{
+    bind, run_prove, CompiledCircuit, GnarkBuffer, GnarkError, Groth16ProofResult, ProveOptions,
+    ProverConfig, ProverSession, ProvingKey, Witness,
+};
+
+/// A full witness solved by [`ProverSession::solve_witness`] or
+/// [`CompiledCircuit::solve_witness`], with the outputs of every hint call.
+///
+/// It holds the secret inputs in the clear: store it where the witness
+/// itself could be stored, and delete it once the proof is done. The bytes
+/// are zeroized when it is dropped.
+pub struct SolvedWitness {
+    bytes: Vec<u8>,
+}
+
+impl SolvedWitness {
+    /// The serialized witness, as [`SolvedWitness::from_bytes`] reads it.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Take a serialized solved witness. It is only decoded, and checked
+    /// against the circuit, when proving.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Write the witness to `path`, atomically: an interrupted write leaves
+    /// any previous file in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomic(path.as_ref(), &self.bytes)
+    }
+
+    /// Read a witness written by [`SolvedWitness::write`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(Self { bytes })
+    }
+}
+
+impl Drop for SolvedWitness {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl fmt::Debug for SolvedWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolvedWitness")
+            .field("len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProverSession {
+    /// Solve `witness_json` (see [`crate::groth16_prove`]) against the
+    /// session's circuit, for a later [`ProverSession::prove_from_solved`].
+    ///
+    /// Circuits whose proofs carry commitments cannot be solved ahead, since
+    /// the prover computes the commitments while solving.
+    ///
+    /// ```no_run
+    /// # fn example(session: &rust_gnark::ProverSession) -> anyhow::Result<()> {
+    /// use rust_gnark::SolvedWitness;
+    ///
+    /// let checkpoint = "proof.solved";
+    /// if !std::path::Path::new(checkpoint).exists() {
+    ///     session.solve_witness(r#"{"X": "3", "Y": "35"}"#)?.write(checkpoint)?;
+    /// }
+    /// // If the app is killed from here on, it starts again on this line.
+    /// let proof = session.prove_from_solved(&SolvedWitness::read(checkpoint)?)?;
+    /// std::fs::remove_file(checkpoint)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the witness does not fit the circuit, does not
+    /// satisfy it, or a hint fails.
+    pub fn solve_witness(&self, witness_json: &str) -> Result<SolvedWitness> {
+        self.solve_witness_with_config(witness_json, &ProverConfig::default())
+    }
+
+    /// Like [`ProverSession::solve_witness`], with the solver settings of
+    /// `config` ([`ProverConfig::with_solver_threads`] and
+    /// [`ProverConfig::with_hint_timeout`]).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::solve_witness`].
+    pub fn solve_witness_with_config(
+        &self,
+        witness_json: &str,
+        config: &ProverConfig,
+    ) -> Result<SolvedWitness> {
+        let witness = CString::new(witness_json)?;
+        let (mut data, mut len) = (std::ptr::null_mut(), 0u64);
+        let err_ptr = unsafe {
+            bind::gnark_prover_session_solve(
+                self.handle_id(),
+                witness.as_ptr() as *mut c_char,
+                config.raw_solver_tasks(),
+                config.raw_hint_timeout_ms(),
+                &mut data,
+                &mut len,
+            )
+        };
+        unsafe { take_solved(err_ptr, data, len) }
+    }
+
+    /// Generate a proof from a witness solved for this session's circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidWitness`] if `solved` was solved for
+    /// another circuit, [`GnarkError::SerializationError`] if it is corrupt, or an
+    /// error if proof generation fails.
+    pub fn prove_from_solved(&self, solved: &SolvedWitness) -> Result<Groth16ProofResult> {
+        self.prove_from_solved_with_config(solved, &ProverConfig::default())
+    }
+
+    /// Like [`ProverSession::prove_from_solved`], using the prover settings
+    /// in `config`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::prove_from_solved`].
+    pub fn prove_from_solved_with_config(
+        &self,
+        solved: &SolvedWitness,
+        config: &ProverConfig,
+    ) -> Result<Groth16ProofResult> {
+        let options = ProveOptions {
+            config: Some(config),
+            ..Default::default()
+        };
+        run_prove(&options, |c_options| {
+            Ok(unsafe {
+                bind::gnark_prover_session_prove_solved(
+                    self.handle_id(),
+                    solved.bytes.as_ptr() as *mut u8,
+                    solved.bytes.len() as u64,
+                    c_options,
+                )
+            })
+        })
+    }
+}
+
+impl CompiledCircuit {
+    /// Solve `witness` against the circuit like
+    /// [`ProverSession::solve_witness`], without loading a proving key, e.g.
+    /// on a client that leaves proving to a server (see
+    /// [`groth16_prove_with_full_witness`]).
+    ///
+    /// The solved witness holds the secret inputs, so whoever proves from it
+    /// learns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `witness` does not fit the circuit, does not
+    /// satisfy it, or a hint fails or times out.
+    pub fn solve_witness(&self, witness: &Witness, config: &ProverConfig) -> Result<SolvedWitness> {
+        let witness = CString::new(witness.to_json())?;
+        let (mut data, mut len) = (std::ptr::null_mut(), 0u64);
+        let err_ptr = unsafe {
+            bind::gnark_circuit_solve(
+                self.handle.id(),
+                witness.as_ptr() as *mut c_char,
+                config.raw_solver_tasks(),
+                config.raw_hint_timeout_ms(),
+                &mut data,
+                &mut len,
+            )
+        };
+        unsafe { take_solved(err_ptr, data, len) }
+    }
+}
+
+/// Prove with `pk` from a witness solved for `circuit`, which must be an
+/// R1CS and `pk` sized for it.
+///
+/// ```no_run
+/// # use rust_gnark::{CompiledCircuit, ProvingKey};
+/// # fn example(circuit: &CompiledCircuit, pk: &ProvingKey) -> anyhow::Result<()> {
+/// use rust_gnark::{groth16_prove_with_full_witness, ProverConfig, SolvedWitness, Witness};
+///
+/// // On the client:
+/// let witness = Witness::from_assignments(&circuit.abi()?, [("X", "3"), ("Y", "35")])?;
+/// let bytes = circuit.solve_witness(&witness, &ProverConfig::default())?.as_bytes().to_vec();
+///
+/// // On the prover, with the same circuit:
+/// let solved = SolvedWitness::from_bytes(bytes);
+/// let proof = groth16_prove_with_full_witness(circuit, pk, &solved, &ProverConfig::default())?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GnarkError::InvalidKey`] if `pk` is for another circuit size,
+/// and otherwise the errors of [`ProverSession::prove_from_solved`].
+pub fn groth16_prove_with_full_witness(
+    circuit: &CompiledCircuit,
+    pk: &ProvingKey,
+    solved: &SolvedWitness,
+    config: &ProverConfig,
+) -> Result<Groth16ProofResult> {
+    let options = ProveOptions {
+        config: Some(config),
+        ..Default::default()
+    };
+    run_prove(&options, |c_options| {
+        Ok(unsafe {
+            bind::gnark_groth16_prove_solved(
+                circuit.handle.id(),
+                pk.handle_id(),
+                solved.bytes.as_ptr() as *mut u8,
+                solved.bytes.len() as u64,
+                c_options,
+            )
+        })
+    })
+}
+
+/// Turn the outcome of a Go solve into a [`SolvedWitness`].
+///
+/// # Safety
+///
+/// `err_ptr` must be null or an error string from Go, and on success
+/// `(data, len)` a Go buffer.
+unsafe fn take_solved(err_ptr: *mut c_char, data: *mut u8, len: u64) -> Result<SolvedWitness> {
+    if !err_ptr.is_null() {
+        return Err(GnarkError::take(err_ptr).into());
+    }
+    let buf = GnarkBuffer::from_raw(data, len);
+    Ok(SolvedWitness {
+        bytes: buf.to_vec(),
+    })
+}
@@ -0,0 +1,1050 @@
+//! Circuits defined in Rust and compiled by gnark.
+
+use anyhow::{bail, ensure, Result};
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::fmt::{self, Write as _};
+use std::os::raw::{c_char, c_int};
+use std::time::Duration;
+
+use crate::handle::Handle;
+use crate::{
+    bind, take_go_string, trace, CircuitAbi, GnarkError, ProverConfig, Witness, APP_CONTEXT,
+    PUBLIC_INPUTS_HASH,
+};
+
+/// Version of the description format understood by the Go side.
+const DESCRIPTION_VERSION: u32 = 1;
+
+/// A value in a circuit under construction.
+///
+/// Only meaningful for the [`CircuitBuilder`] that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+/// Out-of-circuit computations available to [`CircuitBuilder::hint`].
+///
+/// A hint's outputs are unconstrained witness values: the circuit must
+/// constrain them itself (e.g. `assert_eq(mul(x, inv), one)` for
+/// [`Hint::Inverse`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    /// `1 / x`, or 0 for `x = 0`. One input, one output.
+    Inverse,
+    /// `a / b`, or 0 for `b = 0`. Two inputs, one output.
+    Div,
+    /// The `n` low bits of `x`, least significant first. One input, `n` outputs.
+    Bits(usize),
+    /// The Rust closure registered under `id` with [`crate::register_hint`].
+    /// Any number of inputs, `outputs` outputs.
+    Rust { id: u64, outputs: usize },
+}
+
+impl Hint {
+    fn name(self) -> &'static str {
+        match self {
+            Hint::Inverse => "inverse",
+            Hint::Div => "div",
+            Hint::Bits(_) => "bits",
+            Hint::Rust { .. } => "rust",
+        }
+    }
+
+    /// `None` if the hint takes any number of inputs.
+    fn nb_inputs(self) -> Option<usize> {
+        match self {
+            Hint::Div => Some(2),
+            Hint::Inverse | Hint::Bits(_) => Some(1),
+            Hint::Rust { .. } => None,
+        }
+    }
+
+    fn nb_outputs(self) -> usize {
+        match self {
+            Hint::Bits(n) | Hint::Rust { outputs: n, .. } => n,
+            Hint::Inverse | Hint::Div => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Input { name: String, public: bool },
+    Const(u64),
+    Add(Var, Var),
+    Sub(Var, Var),
+    Mul(Var, Var),
+    AssertEq(Var, Var),
+    Hint(Hint, Vec<Var>),
+    Commit(Vec<Var>),
+    RangeCheck(Var, usize),
+    Feed(u64, usize),
+    Mimc { values: Vec<Var>, gkr: bool },
+}
+
+impl Op {
+    fn nb_outputs(&self) -> usize {
+        match self {
+            Op::Input { .. } | Op::Const(_) | Op::Add(..) | Op::Sub(..) | Op::Mul(..) => 1,
+            Op::Commit(_) | Op::Mimc { .. } => 1,
+            Op::Hint(hint, _) => hint.nb_outputs(),
+            Op::Feed(_, count) => *count,
+            Op::AssertEq(..) | Op::RangeCheck(..) => 0,
+        }
+    }
+}
+
+/// Declarative builder for BN254 circuits.
+///
+/// Each call records an operation; [`CircuitBuilder::compile`] sends the
+/// recorded description to gnark, which compiles it into a constraint system.
+/// Inputs are named, and those names are the keys of the witness JSON passed
+/// to [`crate::groth16_prove`].
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// // Prove knowledge of X such that X^3 + X + 5 == Y.
+/// let mut c = rust_gnark::CircuitBuilder::new();
+/// let y = c.public_input("Y");
+/// let x = c.secret_input("X");
+/// let x2 = c.mul(x, x);
+/// let x3 = c.mul(x2, x);
+/// let five = c.constant(5);
+/// let lhs = c.add(x3, x);
+/// let lhs = c.add(lhs, five);
+/// c.assert_eq(lhs, y);
+///
+/// let circuit = c.compile()?;
+/// circuit.write("cubic.r1cs")?;
+/// circuit.groth16_setup("cubic.pk", "cubic.vk")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+    ops: Vec<Op>,
+    nb_vars: usize,
+    profile: bool,
+    hash_public: bool,
+    app_context: bool,
+}
+
+impl CircuitBuilder {
+    /// An empty circuit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `op`, which produces `nb_outputs` new values, and return the first.
+    fn push(&mut self, op: Op, nb_outputs: usize) -> usize {
+        self.ops.push(op);
+        let first = self.nb_vars;
+        self.nb_vars += nb_outputs;
+        first
+    }
+
+    /// Declare a public input named `name`.
+    pub fn public_input(&mut self, name: &str) -> Var {
+        Var(self.push(
+            Op::Input {
+                name: name.to_owned(),
+                public: true,
+            },
+            1,
+        ))
+    }
+
+    /// Declare a secret input named `name`.
+    pub fn secret_input(&mut self, name: &str) -> Var {
+        Var(self.push(
+            Op::Input {
+                name: name.to_owned(),
+                public: false,
+            },
+            1,
+        ))
+    }
+
+    /// A constant value.
+    pub fn constant(&mut self, value: u64) -> Var {
+        Var(self.push(Op::Const(value), 1))
+    }
+
+    /// `a + b`.
+    pub fn add(&mut self, a: Var, b: Var) -> Var {
+        Var(self.push(Op::Add(a, b), 1))
+    }
+
+    /// `a - b`.
+    pub fn sub(&mut self, a: Var, b: Var) -> Var {
+        Var(self.push(Op::Sub(a, b), 1))
+    }
+
+    /// `a * b`.
+    pub fn mul(&mut self, a: Var, b: Var) -> Var {
+        Var(self.push(Op::Mul(a, b), 1))
+    }
+
+    /// Constrain `a == b`.
+    pub fn assert_eq(&mut self, a: Var, b: Var) {
+        self.push(Op::AssertEq(a, b), 0);
+    }
+
+    /// Compute `hint` over `inputs` outside the circuit and return its outputs
+    /// as new, unconstrained values.
+    pub fn hint(&mut self, hint: Hint, inputs: &[Var]) -> Vec<Var> {
+        let n = hint.nb_outputs();
+        let first = self.push(Op::Hint(hint, inputs.to_vec()), n);
+        (first..first + n).map(Var).collect()
+    }
+
+    /// Declare `count` values that a [`crate::WitnessFeeder`] supplies under
+    /// `id` while the witness is solved, instead of the witness JSON.
+    ///
+    /// Like hint outputs, they are unconstrained: bind them to the statement,
+    /// e.g. by checking them against a public digest. Circuits with feeds can
+    /// only be proven with [`crate::groth16_prove_with_feeder`] or
+    /// [`crate::ProverSession::prove_with_feeder`] (and not checked with
+    /// [`CompiledCircuit::check_witness`]).
+    pub fn feed(&mut self, id: u64, count: usize) -> Vec<Var> {
+        let first = self.push(Op::Feed(id, count), count);
+        (first..first + count).map(Var).collect()
+    }
+
+    /// Commit to `values` with a Pedersen commitment and return its hash: a
+    /// random challenge the prover cannot choose without changing `values`.
+    ///
+    /// Circuits use this for cheap randomized checks (e.g. permutation or
+    /// lookup arguments), as gnark's `std/multicommit` and range checks do.
+    /// The proof then carries the commitment and a proof of knowledge of its
+    /// opening, which [`crate::groth16_verify`] checks. Each call adds a
+    /// commitment; the `native` verifier supports circuits with one.
+    pub fn commit(&mut self, values: &[Var]) -> Var {
+        Var(self.push(Op::Commit(values.to_vec()), 1))
+    }
+
+    /// Constrain `value < 2^bits`.
+    ///
+    /// Uses gnark's lookup-based range checker (`std/rangecheck`), which is
+    /// much cheaper than bit decomposition once a circuit checks many
+    /// values. It relies on a commitment, like [`CircuitBuilder::commit`],
+    /// and all range checks of a circuit share it.
+    pub fn range_check(&mut self, value: Var, bits: usize) {
+        self.push(Op::RangeCheck(value, bits), 0);
+    }
+
+    /// The MiMC hash of `values`, as gnark's `std/hash/mimc` computes it and
+    /// [`crate::hash::mimc`] on the host.
+    pub fn mimc(&mut self, values: &[Var]) -> Var {
+        Var(self.push(
+            Op::Mimc {
+                values: values.to_vec(),
+                gkr: false,
+            },
+            1,
+        ))
+    }
+
+    /// The same hash as [`CircuitBuilder::mimc`], proven with gnark's GKR
+    /// prover (`std/hash/mimc/gkr-mimc`).
+    ///
+    /// The permutations of all the calls in a circuit are instances of one
+    /// GKR circuit, checked by a single sumcheck proof at the end of the
+    /// circuit, so each hash costs far fewer constraints than the gadget
+    /// once a circuit hashes a few dozen times; below that, the fixed cost of
+    /// verifying the GKR proof dominates. The proof's challenges come from a
+    /// commitment, like [`CircuitBuilder::commit`].
+    pub fn mimc_gkr(&mut self, values: &[Var]) -> Var {
+        Var(self.push(
+            Op::Mimc {
+                values: values.to_vec(),
+                gkr: true,
+            },
+            1,
+        ))
+    }
+
+    /// The circuit's inputs, in witness order.
+    pub fn abi(&self) -> CircuitAbi {
+        let (mut public, mut secret, mut hashed) = (Vec::new(), Vec::new(), Vec::new());
+        for op in &self.ops {
+            if let Op::Input { name, public: p } = op {
+                if *p && self.hash_public {
+                    hashed.push(name.clone());
+                }
+                if *p && !self.hash_public {
+                    &mut public
+                } else {
+                    &mut secret
+                }
+                .push(name.clone());
+            }
+        }
+        if self.hash_public {
+            public.push(PUBLIC_INPUTS_HASH.to_owned());
+        }
+        if self.app_context {
+            public.push(APP_CONTEXT.to_owned());
+        }
+        CircuitAbi::new(public, secret).with_hashed_public_inputs(hashed)
+    }
+
+    /// Serialize the circuit into the JSON description the Go side compiles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an input name is empty or declared twice, a hint has
+    /// the wrong number of inputs, a commitment, range check, feed or hash is
+    /// empty, a [`Var`] does not belong to this builder, or public inputs are
+    /// [hashed](CircuitBuilder::hash_public_inputs) but there are none or
+    /// one is named [`PUBLIC_INPUTS_HASH`], or the circuit is
+    /// [bound to a context](CircuitBuilder::bind_app_context) and an input is
+    /// named [`APP_CONTEXT`].
+    pub fn to_description(&self) -> Result<String> {
+        let mut names = HashSet::new();
+        let mut hashed = Vec::new();
+        let mut next_var = 0;
+        let mut out = format!("{{\"version\":{DESCRIPTION_VERSION},\"ops\":[");
+        for (i, op) in self.ops.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            match op {
+                Op::Input { name, public } => {
+                    ensure!(!name.is_empty(), "input names must not be empty");
+                    ensure!(names.insert(name.as_str()), "duplicate input {name:?}");
+                    if *public && self.hash_public {
+                        hashed.push(next_var);
+                    }
+                    let public = *public && !self.hash_public;
+                    out.push_str("{\"op\":\"input\",\"name\":");
+                    push_json_string(&mut out, name);
+                    write!(out, ",\"public\":{public}}}")?;
+                }
+                Op::Const(value) => write!(out, "{{\"op\":\"const\",\"value\":\"{value}\"}}")?,
+                Op::Add(a, b) => self.push_binary(&mut out, "add", *a, *b)?,
+                Op::Sub(a, b) => self.push_binary(&mut out, "sub", *a, *b)?,
+                Op::Mul(a, b) => self.push_binary(&mut out, "mul", *a, *b)?,
+                Op::AssertEq(a, b) => self.push_binary(&mut out, "assert_eq", *a, *b)?,
+                Op::Hint(hint, args) => {
+                    if let Some(n) = hint.nb_inputs() {
+                        ensure!(
+                            args.len() == n,
+                            "hint {} takes {n} inputs, got {}",
+                            hint.name(),
+                            args.len()
+                        );
+                    }
+                    write!(out, "{{\"op\":\"hint\",\"hint\":\"{}\",", hint.name())?;
+                    if let Hint::Rust { id, outputs } = hint {
+                        ensure!(*outputs > 0, "Rust hint {id} needs at least one output");
+                        write!(out, "\"value\":\"{id}\",")?;
+                    }
+                    out.push_str("\"args\":");
+                    self.push_args(&mut out, args)?;
+                    write!(out, ",\"outputs\":{}}}", hint.nb_outputs())?;
+                }
+                Op::Commit(args) => {
+                    ensure!(!args.is_empty(), "commit needs at least one value");
+                    out.push_str("{\"op\":\"commit\",\"args\":");
+                    self.push_args(&mut out, args)?;
+                    out.push('}');
+                }
+                Op::Feed(id, count) => {
+                    ensure!(*count > 0, "feed {id} needs at least one value");
+                    write!(
+                        out,
+                        "{{\"op\":\"feed\",\"value\":\"{id}\",\"outputs\":{count}}}"
+                    )?;
+                }
+                Op::RangeCheck(v, bits) => {
+                    ensure!(*bits > 0, "range checks need a positive bit width");
+                    out.push_str("{\"op\":\"range_check\",\"args\":");
+                    self.push_args(&mut out, &[*v])?;
+                    write!(out, ",\"bits\":{bits}}}")?;
+                }
+                Op::Mimc { values, gkr } => {
+                    ensure!(!values.is_empty(), "mimc needs at least one value");
+                    let op = if *gkr { "mimc_gkr" } else { "mimc" };
+                    write!(out, "{{\"op\":\"{op}\",\"args\":")?;
+                    self.push_args(&mut out, values)?;
+                    out.push('}');
+                }
+            }
+            next_var += op.nb_outputs();
+        }
+        if self.hash_public {
+            ensure!(
+                !hashed.is_empty(),
+                "hashing public inputs needs at least one public input"
+            );
+            ensure!(
+                !names.contains(PUBLIC_INPUTS_HASH),
+                "input {PUBLIC_INPUTS_HASH:?} is reserved for the public input hash"
+            );
+            // The digest input and the hash are the two values after the
+            // recorded ones.
+            let (digest, hash) = (self.nb_vars, self.nb_vars + 1);
+            if !self.ops.is_empty() {
+                out.push(',');
+            }
+            out.push_str("{\"op\":\"input\",\"name\":");
+            push_json_string(&mut out, PUBLIC_INPUTS_HASH);
+            out.push_str(",\"public\":true},{\"op\":\"sha256_fields\",\"args\":");
+            self.push_args(&mut out, &hashed.into_iter().map(Var).collect::<Vec<_>>())?;
+            write!(
+                out,
+                "}},{{\"op\":\"assert_eq\",\"args\":[{digest},{hash}]}}"
+            )?;
+        }
+        if self.app_context {
+            ensure!(
+                !names.contains(APP_CONTEXT),
+                "input {APP_CONTEXT:?} is reserved for the application context"
+            );
+            // After the recorded values and those of the hash. An input no
+            // constraint uses would not be bound by the proof, so it is
+            // squared.
+            let context = self.nb_vars + if self.hash_public { 2 } else { 0 };
+            if !self.ops.is_empty() || self.hash_public {
+                out.push(',');
+            }
+            out.push_str("{\"op\":\"input\",\"name\":");
+            push_json_string(&mut out, APP_CONTEXT);
+            write!(
+                out,
+                ",\"public\":true}},{{\"op\":\"mul\",\"args\":[{context},{context}]}}"
+            )?;
+        }
+        out.push_str("]}");
+        Ok(out)
+    }
+
+    fn push_binary(&self, out: &mut String, op: &str, a: Var, b: Var) -> Result<()> {
+        write!(out, "{{\"op\":\"{op}\",\"args\":")?;
+        self.push_args(out, &[a, b])?;
+        out.push('}');
+        Ok(())
+    }
+
+    fn push_args(&self, out: &mut String, args: &[Var]) -> Result<()> {
+        out.push('[');
+        for (i, v) in args.iter().enumerate() {
+            if v.0 >= self.nb_vars {
+                bail!("variable {} does not belong to this circuit", v.0);
+            }
+            if i > 0 {
+                out.push(',');
+            }
+            write!(out, "{}", v.0)?;
+        }
+        out.push(']');
+        Ok(())
+    }
+
+    /// Replace the circuit's public inputs by a single one,
+    /// [`PUBLIC_INPUTS_HASH`], constrained to equal [`crate::public_inputs_hash`]
+    /// of them in declaration order.
+    ///
+    /// The inputs declared with [`CircuitBuilder::public_input`] become
+    /// secret, and a verifier checks proofs against their hash: an EVM
+    /// verifier then does one SHA-256 and one scalar multiplication instead
+    /// of a scalar multiplication per input. [`crate::Witness::from_elements`]
+    /// fills in the hash from the [`CircuitAbi`] of the circuit, and
+    /// `native::PublicInputs::commitment` computes it on the verifier's side.
+    /// The hash costs about 30,000 constraints per 64 bytes of inputs.
+    pub fn hash_public_inputs(&mut self, hash: bool) {
+        self.hash_public = hash;
+    }
+
+    /// Add a last public input, [`APP_CONTEXT`], that binds each proof to the
+    /// context of an application and a challenge (see [`crate::app_context`]),
+    /// so that it only verifies, with [`crate::groth16_verify_in_context`],
+    /// where it was meant to. The input costs one constraint, and is not
+    /// hashed with the others by [`CircuitBuilder::hash_public_inputs`].
+    pub fn bind_app_context(&mut self, bind: bool) {
+        self.app_context = bind;
+    }
+
+    /// Record which Go functions add each constraint when compiling, for the
+    /// [`CircuitStats::gadgets`] of the compiled circuit.
+    ///
+    /// This runs gnark's profiler, which captures a call stack per constraint
+    /// and makes compiling noticeably slower. Profiled compilations run one
+    /// at a time, and other compilations wait for them.
+    pub fn profile_gadgets(&mut self, profile: bool) {
+        self.profile = profile;
+    }
+
+    /// Compile into an R1CS, for Groth16.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the description is invalid (see
+    /// [`CircuitBuilder::to_description`]) or gnark fails to compile it.
+    pub fn compile(&self) -> Result<CompiledCircuit> {
+        self.compile_with(false)
+    }
+
+    /// Compile into a sparse R1CS (PLONKish constraints), for PLONK.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CircuitBuilder::compile`].
+    pub fn compile_sparse(&self) -> Result<CompiledCircuit> {
+        self.compile_with(true)
+    }
+
+    fn compile_with(&self, sparse: bool) -> Result<CompiledCircuit> {
+        let desc = CString::new(self.to_description()?)?;
+        let mut id = 0u64;
+        let mut gadgets = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_circuit_compile(
+                desc.as_ptr() as *mut c_char,
+                sparse as c_int,
+                self.profile as c_int,
+                &mut id,
+                &mut gadgets,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        let gadgets = if gadgets.is_null() {
+            Vec::new()
+        } else {
+            parse_gadgets(&unsafe { take_go_string(gadgets) })
+        };
+        Ok(CompiledCircuit {
+            handle: Handle::from_raw(id),
+            gadgets,
+            hashed_public: self.abi().hashed_public_inputs().to_vec(),
+        })
+    }
+}
+
+/// Parse the `name\town\tcum` lines of `gnark_circuit_compile`.
+fn parse_gadgets(s: &str) -> Vec<GadgetStats> {
+    s.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some(GadgetStats {
+                name: fields.next()?.to_owned(),
+                own_constraints: fields.next()?.parse().ok()?,
+                constraints: fields.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Append `s` as a JSON string literal.
+pub(crate) fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// A constraint system compiled from a [`CircuitBuilder`] (or read from a
+/// file), owned by the Go runtime.
+#[derive(Debug)]
+pub struct CompiledCircuit {
+    pub(crate) handle: Handle,
+    /// Recorded at compile time, see [`CircuitBuilder::profile_gadgets`].
+    pub(crate) gadgets: Vec<GadgetStats>,
+    /// See [`CircuitBuilder::hash_public_inputs`].
+    pub(crate) hashed_public: Vec<String>,
+}
+
+impl CompiledCircuit {
+    /// Load the BN254 R1CS at `path`, e.g. to [check](CompiledCircuit::check_witness)
+    /// witnesses against it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a BN254 R1CS.
+    pub fn read(path: &str) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_circuit_read(path.as_ptr() as *mut c_char, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+            gadgets: Vec::new(),
+            hashed_public: Vec::new(),
+        })
+    }
+
+    /// Load a BN254 sparse R1CS (PLONK constraint system) written by
+    /// [`CompiledCircuit::write`] for a circuit compiled with
+    /// [`CircuitBuilder::compile_sparse`].
+    ///
+    /// gnark's encoding keeps the custom gates and the commitment that
+    /// lookup-based range checks depend on, so the circuit read back solves
+    /// and checks witnesses like the one written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a BN254 sparse
+    /// R1CS.
+    pub fn read_sparse(path: &str) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_circuit_read_sparse(path.as_ptr() as *mut c_char, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+            gadgets: Vec::new(),
+            hashed_public: Vec::new(),
+        })
+    }
+
+    /// Run gnark's solver on `witness` without proving.
+    ///
+    /// Returns `None` if every constraint is satisfied, or the first violated
+    /// constraint otherwise. Much faster than a failed proof, and the
+    /// [`ConstraintViolation`] says which constraint failed and on which inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `witness` does not fit the circuit or solving fails
+    /// for a reason other than an unsatisfied constraint (e.g. a failing hint).
+    pub fn check_witness(&self, witness: &Witness) -> Result<Option<ConstraintViolation>> {
+        let witness = CString::new(witness.to_json())?;
+        let mut index = 0i64;
+        let mut constraint = std::ptr::null_mut();
+        let mut detail = std::ptr::null_mut();
+        let mut inputs = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_circuit_check_witness(
+                self.handle.id(),
+                witness.as_ptr() as *mut c_char,
+                &mut index,
+                &mut constraint,
+                &mut detail,
+                &mut inputs,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        if index < 0 {
+            return Ok(None);
+        }
+        let (constraint, detail, inputs) = unsafe {
+            (
+                take_go_string(constraint),
+                take_go_string(detail),
+                take_go_string(inputs),
+            )
+        };
+        let inputs = inputs
+            .split('\x1f')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect();
+        Ok(Some(ConstraintViolation {
+            constraint_index: index as usize,
+            constraint,
+            detail,
+            inputs,
+        }))
+    }
+
+    /// Solve `witness` with the solver settings of `config` (see
+    /// [`crate::ProverConfig::with_solver_threads`] and
+    /// [`crate::ProverConfig::with_hint_timeout`]) and report where the time
+    /// went.
+    ///
+    /// gnark does not time individual constraints; the [`SolveProfile`] gives
+    /// the total, the sizes of the constraint levels the solver works through
+    /// and the time spent in each hint, which is where witness generation
+    /// spends whatever is not plain field arithmetic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `witness` does not fit the circuit, does not satisfy
+    /// it, or a hint fails or times out.
+    pub fn solve_profile(&self, witness: &Witness, config: &ProverConfig) -> Result<SolveProfile> {
+        let witness = CString::new(witness.to_json())?;
+        let mut profile = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_circuit_solve_profile(
+                self.handle.id(),
+                witness.as_ptr() as *mut c_char,
+                config.raw_solver_tasks(),
+                config.raw_hint_timeout_ms(),
+                &mut profile,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(SolveProfile::parse(&unsafe { take_go_string(profile) }))
+    }
+
+    /// Number of constraints in the compiled system.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this circuit.
+    pub fn nb_constraints(&self) -> Result<u64> {
+        let mut n = 0u64;
+        let err_ptr = unsafe { bind::gnark_circuit_nb_constraints(self.handle.id(), &mut n) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(n)
+    }
+
+    /// The circuit's size: constraints, inputs, internal variables and, if
+    /// it was compiled with [`CircuitBuilder::profile_gadgets`], the
+    /// constraints each gadget added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this circuit.
+    pub fn stats(&self) -> Result<CircuitStats> {
+        let (mut constraints, mut public, mut secret, mut internal) = (0u64, 0u64, 0u64, 0u64);
+        let err_ptr = unsafe {
+            bind::gnark_circuit_stats(
+                self.handle.id(),
+                &mut constraints,
+                &mut public,
+                &mut secret,
+                &mut internal,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(CircuitStats {
+            nb_constraints: constraints,
+            nb_public_inputs: public,
+            nb_secret_inputs: secret,
+            nb_internal_variables: internal,
+            gadgets: self.gadgets.clone(),
+        })
+    }
+
+    /// Number of G1 powers a KZG SRS needs for a PLONK setup of this circuit,
+    /// comparable to [`crate::Srs::size`].
+    ///
+    /// This is the next power of two above the number of constraints plus
+    /// public inputs, plus 3: a `.ptau` file of power `p` is large enough if
+    /// `2^p + 3` is at least this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit is an R1CS rather than a sparse R1CS
+    /// (see [`CircuitBuilder::compile_sparse`]).
+    pub fn srs_size_needed(&self) -> Result<u64> {
+        let mut n = 0u64;
+        let err_ptr = unsafe { bind::gnark_circuit_srs_size(self.handle.id(), &mut n) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(n)
+    }
+
+    /// The circuit's inputs, in witness order.
+    ///
+    /// For a circuit read from a file, the inputs hashed into
+    /// [`PUBLIC_INPUTS_HASH`] are not known; add them with
+    /// [`CircuitAbi::with_hashed_public_inputs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this circuit.
+    pub fn abi(&self) -> Result<CircuitAbi> {
+        let mut public = std::ptr::null_mut();
+        let mut secret = std::ptr::null_mut();
+        let err_ptr =
+            unsafe { bind::gnark_circuit_inputs(self.handle.id(), &mut public, &mut secret) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { CircuitAbi::from_go(public, secret) }
+            .with_hashed_public_inputs(self.hashed_public.clone()))
+    }
+
+    /// Write the constraint system to `path`, in the format
+    /// [`crate::groth16_prove`] reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: &str) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr =
+            unsafe { bind::gnark_circuit_write(self.handle.id(), path.as_ptr() as *mut c_char) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Write the wires and constraints to `path` as JSON, for inspecting a
+    /// circuit with external tools (Sage, Python, `jq`).
+    ///
+    /// The file is one object:
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "curve": "bn254",
+    ///   "system": "r1cs",
+    ///   "field": "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    ///   "public": ["1", "Y"],
+    ///   "secret": ["X"],
+    ///   "internal": 3,
+    ///   "constraints": [{"a": [[2, "1"]], "b": [[2, "1"]], "c": [[3, "1"]]}]
+    /// }
+    /// ```
+    ///
+    /// Wires are numbered in order: the `public` inputs (for an R1CS, wire 0
+    /// is the constant one, named `"1"`), then the `secret` inputs, then
+    /// `internal` wires. Coefficients are decimal strings in `[0, field)`.
+    ///
+    /// For an R1CS (`"system": "r1cs"`), each constraint lists the terms
+    /// `[wire, coefficient]` of three linear combinations with
+    /// `a · b = c`. For a sparse R1CS (`"system": "sparse_r1cs"`), each
+    /// constraint is `{"xa", "xb", "xc", "ql", "qr", "qo", "qm", "qc"}`, wires
+    /// and coefficients of `ql·xa + qr·xb + qo·xc + qm·xa·xb + qc = 0`.
+    ///
+    /// `version` changes whenever the format does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_json(&self, path: &str) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr = unsafe {
+            bind::gnark_circuit_export_json(self.handle.id(), path.as_ptr() as *mut c_char)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Write the R1CS to `path` in [zkInterface](https://github.com/QED-it/zkinterface)'s
+    /// format: a `CircuitHeader` message (public inputs as instance
+    /// variables, the field size) followed by a `ConstraintSystem` message.
+    ///
+    /// gnark's wire numbers are used as zkInterface variable ids, so variable
+    /// 0 is the constant one and the public inputs are variables `1..=n`, in
+    /// the order of [`CompiledCircuit::abi`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit is a sparse R1CS (zkInterface
+    /// constraints are bilinear) or the file cannot be written.
+    pub fn export_zkinterface(&self, path: &str) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr = unsafe {
+            bind::gnark_circuit_export_zkif(self.handle.id(), path.as_ptr() as *mut c_char)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Run a Groth16 setup and write the proving key (raw format, as read by
+    /// [`crate::groth16_prove`] and [`crate::ProvingKey::open_mmap`]) and the
+    /// verifying key.
+    ///
+    /// The setup is generated locally by a single party: suitable for tests
+    /// and development, not as a substitute for a ceremony.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit was compiled with
+    /// [`CircuitBuilder::compile_sparse`], the setup fails, or a key file
+    /// cannot be written.
+    pub fn groth16_setup(&self, pk_path: &str, vk_path: &str) -> Result<()> {
+        let _span = enter_span!("setup", pk = pk_path, vk = vk_path);
+        trace::timed(|| {
+            let pk = CString::new(pk_path)?;
+            let vk = CString::new(vk_path)?;
+            let err_ptr = unsafe {
+                bind::gnark_circuit_groth16_setup(
+                    self.handle.id(),
+                    pk.as_ptr() as *mut c_char,
+                    vk.as_ptr() as *mut c_char,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            Ok(())
+        })
+    }
+
+    /// [`CompiledCircuit::groth16_setup`] with the toxic waste drawn from a
+    /// deterministic RNG seeded with `seed`, so the same circuit and seed
+    /// always give byte-identical keys (e.g. for checked-in test fixtures).
+    ///
+    /// **Unsafe for production:** anyone who knows the seed knows the toxic
+    /// waste and can forge proofs for the keys. As with
+    /// [`crate::ProverConfig::with_test_rng`], the RNG replaces Go's
+    /// process-wide `crypto/rand.Reader` during the setup, so anything using
+    /// it concurrently (including proofs) becomes predictable too. Never use
+    /// this outside tests.
+    ///
+    /// # Errors
+    ///
+    /// As [`CompiledCircuit::groth16_setup`].
+    pub fn unsafe_setup_with_seed(&self, seed: u64, pk_path: &str, vk_path: &str) -> Result<()> {
+        let _span = enter_span!("setup", pk = pk_path, vk = vk_path);
+        trace::timed(|| {
+            let pk = CString::new(pk_path)?;
+            let vk = CString::new(vk_path)?;
+            let err_ptr = unsafe {
+                bind::gnark_circuit_groth16_setup_seeded(
+                    self.handle.id(),
+                    seed,
+                    pk.as_ptr() as *mut c_char,
+                    vk.as_ptr() as *mut c_char,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The first constraint a witness fails, as reported by
+/// [`CompiledCircuit::check_witness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// Index of the constraint in the compiled system.
+    pub constraint_index: usize,
+    /// The constraint in symbolic form, with input wires named
+    /// (e.g. `X ⋅ X == v3`).
+    pub constraint: String,
+    /// The constraint evaluated on the witness (e.g. `9 ⋅ 3 != 35`).
+    pub detail: String,
+    /// The circuit inputs the constraint reads, with their witness values.
+    pub inputs: Vec<(String, String)>,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "constraint #{} is not satisfied: {} ({})",
+            self.constraint_index, self.constraint, self.detail
+        )?;
+        for (i, (name, value)) in self.inputs.iter().enumerate() {
+            write!(f, "{}{name} = {value}", if i == 0 { "; " } else { ", " })?;
+        }
+        Ok(())
+    }
+}
+
+/// The size of a compiled circuit, from [`CompiledCircuit::stats`].
+///
+/// Comparing the stats of a circuit across releases catches gadgets that
+/// grew unexpectedly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Number of constraints.
+    pub nb_constraints: u64,
+    /// Number of public inputs, not counting an R1CS's constant wire.
+    pub nb_public_inputs: u64,
+    /// Number of secret inputs.
+    pub nb_secret_inputs: u64,
+    /// Number of internal variables (wires that are not inputs).
+    pub nb_internal_variables: u64,
+    /// Constraints by Go function, most constraints first. Empty unless the
+    /// circuit was compiled with [`CircuitBuilder::profile_gadgets`].
+    pub gadgets: Vec<GadgetStats>,
+}
+
+/// The constraints one Go function added at compile time, as recorded by
+/// gnark's profiler.
+///
+/// gnark's own constraint builders are left out, so the constraints of
+/// `api.Mul` count toward its caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GadgetStats {
+    /// Fully qualified function name, e.g.
+    /// `github.com/consensys/gnark/std/rangecheck.(*commitChecker).commit`.
+    pub name: String,
+    /// Constraints added by the function or anything it called.
+    pub constraints: u64,
+    /// Constraints added by the function itself.
+    pub own_constraints: u64,
+}
+
+/// Timing of a witness solve, from [`CompiledCircuit::solve_profile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveProfile {
+    /// Wall-clock time of the whole solve.
+    pub solve_time: Duration,
+    /// Number of constraints in each level, in solving order. The constraints
+    /// of a level are solved in parallel, so a circuit made of many small
+    /// levels gains little from more solver threads.
+    pub levels: Vec<usize>,
+    /// Time spent in each hint, in order of first call.
+    pub hints: Vec<HintTiming>,
+}
+
+/// The calls of one hint during a solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HintTiming {
+    /// The hint: `inverse`, `div` or `bits` for [`Hint`]s, `feed` for
+    /// [`CircuitBuilder::feed`] values, `rust:<id>` for a
+    /// [`crate::register_hint`] closure, and gnark's name otherwise.
+    pub name: String,
+    /// How often the hint was called.
+    pub calls: u64,
+    /// Total time spent in the hint. Calls run in parallel, so the totals can
+    /// add up to more than [`SolveProfile::solve_time`].
+    pub total: Duration,
+}
+
+impl SolveProfile {
+    fn parse(s: &str) -> Self {
+        let mut profile = Self {
+            solve_time: Duration::ZERO,
+            levels: Vec::new(),
+            hints: Vec::new(),
+        };
+        let nanos = |s: &str| Duration::from_nanos(s.parse().unwrap_or_default());
+        for line in s.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[..] {
+                ["solve", ns] => profile.solve_time = nanos(ns),
+                ["levels", levels] => {
+                    profile.levels = levels.split(',').filter_map(|n| n.parse().ok()).collect()
+                }
+                ["hint", name, calls, ns] => profile.hints.push(HintTiming {
+                    name: name.to_owned(),
+                    calls: calls.parse().unwrap_or_default(),
+                    total: nanos(ns),
+                }),
+                _ => {}
+            }
+        }
+        profile
+    }
+}
@@ -0,0 +1,242 @@
+//! Prover tuning knobs that do not affect the proof's validity.
+
+use std::os::raw::c_int;
+use std::time::Duration;
+
+/// Hash-to-field function used to derive the challenge for Pedersen
+/// commitments (circuits using `api.Commit` / range checks).
+///
+/// Proofs must be verified with the same function they were generated with,
+/// see [`crate::groth16_verify_with_hash`]. Circuits without commitments are
+/// unaffected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HashFunction {
+    /// gnark's default: RFC 9380 `expand_message_xmd` over SHA-256.
+    #[default]
+    Default,
+    /// Plain SHA-256, as expected by gnark's exported Solidity verifiers.
+    Sha256,
+    /// Legacy Keccak-256 (Ethereum's `keccak256`).
+    Keccak256,
+}
+
+impl HashFunction {
+    /// Value of the matching `hash*` constant in `go/config.go`.
+    pub(crate) fn as_raw(self) -> c_int {
+        match self {
+            Self::Default => 0,
+            Self::Sha256 => 1,
+            Self::Keccak256 => 2,
+        }
+    }
+}
+
+/// How keys and proofs are encoded.
+///
+/// Compressed points take about half the space of raw ones but cost a square
+/// root each to decompress, which dominates load time for large proving keys.
+/// Reading auto-detects compression, so the format only decides how objects
+/// are written and whether points are validated when read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SerializationFormat {
+    /// Compressed points (gnark's `WriteTo`), validated on read.
+    #[default]
+    Compressed,
+    /// Uncompressed points (`WriteRawTo`), validated on read.
+    Raw,
+    /// Uncompressed points, read without subgroup checks (`UnsafeReadFrom`).
+    /// The fastest to load; only for trusted, locally produced files.
+    RawUnchecked,
+}
+
+impl SerializationFormat {
+    /// Value of the matching `format*` constant in `go/keys.go`.
+    pub(crate) fn as_raw(self) -> c_int {
+        match self {
+            Self::Compressed => 0,
+            Self::Raw => 1,
+            Self::RawUnchecked => 2,
+        }
+    }
+}
+
+/// Configuration for [`crate::groth16_prove_with_config`].
+///
+/// Built with `ProverConfig::default()` and the `with_*` methods:
+///
+/// ```no_run
+/// // Keep a mobile app from spreading the prover over every core.
+/// let config = rust_gnark::ProverConfig::default()
+///     .with_threads(2)
+///     .with_memory_limit(512 << 20);
+/// ```
+///
+/// The thread count and memory limit are process-wide settings of the Go
+/// runtime. They are applied for the duration of each call and then restored,
+/// and calls that set them run one at a time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProverConfig {
+    pub(crate) gpu_device: Option<u32>,
+    pub(crate) threads: Option<usize>,
+    pub(crate) memory_limit: Option<u64>,
+    pub(crate) hash: HashFunction,
+    pub(crate) proof_format: SerializationFormat,
+    pub(crate) test_rng_seed: Option<u64>,
+    pub(crate) scrub_witness: bool,
+    pub(crate) solver_threads: Option<usize>,
+    pub(crate) hint_timeout: Option<Duration>,
+}
+
+impl ProverConfig {
+    /// Run MSM/NTT on CUDA device `device_id` through the ICICLE backend.
+    ///
+    /// Falls back to CPU proving (with a warning on stderr) if the ICICLE
+    /// backend cannot be loaded or the device is not present, so the same
+    /// binary works on machines without a GPU.
+    #[cfg(feature = "gpu")]
+    pub fn with_gpu(mut self, device_id: u32) -> Self {
+        self.gpu_device = Some(device_id);
+        self
+    }
+
+    /// Limit the Go runtime to `threads` OS threads (`GOMAXPROCS`) while proving.
+    ///
+    /// `0` leaves the current setting unchanged.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = (threads > 0).then_some(threads);
+        self
+    }
+
+    /// Set a soft memory limit (`GOMEMLIMIT`) of `bytes` while proving.
+    ///
+    /// The Go garbage collector works harder as the heap approaches the limit;
+    /// it is not a hard cap. `0` leaves the current setting unchanged.
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = (bytes > 0).then_some(bytes);
+        self
+    }
+
+    /// Use `hash` to derive commitment challenges.
+    pub fn with_hash_function(mut self, hash: HashFunction) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Encode the proof in `format` instead of gnark's default compressed form.
+    ///
+    /// [`crate::groth16_verify`] reads either encoding.
+    pub fn with_proof_format(mut self, format: SerializationFormat) -> Self {
+        self.proof_format = format;
+        self
+    }
+
+    /// Draw the prover's randomness from a deterministic RNG seeded with
+    /// `seed`, so the same inputs give byte-identical proofs (e.g. for
+    /// snapshot tests in CI).
+    ///
+    /// Proofs stay valid but are no longer zero-knowledge: anyone who knows
+    /// the seed can recover the witness. The RNG replaces Go's process-wide
+    /// `crypto/rand.Reader` while proving, so seeded proofs run one at a time
+    /// and anything else using it concurrently (including other proofs)
+    /// becomes predictable too. Never use this outside tests.
+    pub fn with_test_rng(mut self, seed: u64) -> Self {
+        self.test_rng_seed = Some(seed);
+        self
+    }
+
+    /// Have the Go side scrub its copies of the witness after each proof: the
+    /// assignment vector is zeroed and a garbage collection returns the freed
+    /// heap (which held the parsed witness) to the operating system.
+    ///
+    /// This is best effort. gnark's solver keeps intermediate values in
+    /// buffers it does not expose, which are freed but not zeroed, and the
+    /// forced collection costs a few milliseconds per proof. Pair it with a
+    /// [`crate::SecretWitness`] to keep the Rust side's copy out of swap.
+    pub fn with_witness_scrubbing(mut self, scrub: bool) -> Self {
+        self.scrub_witness = scrub;
+        self
+    }
+
+    /// Solve the witness with `threads` parallel workers instead of one per
+    /// CPU.
+    ///
+    /// Unlike [`ProverConfig::with_threads`] this only affects the solver and
+    /// is not a process-wide setting, so concurrent proofs can each use a few
+    /// workers. `0` restores the default.
+    pub fn with_solver_threads(mut self, threads: usize) -> Self {
+        self.solver_threads = (threads > 0).then_some(threads);
+        self
+    }
+
+    /// Fail the proof if a single hint call runs longer than `timeout`, e.g.
+    /// to bound a [`crate::register_hint`] closure that may hang.
+    ///
+    /// A call that times out is abandoned, not stopped: it finishes in the
+    /// background and its outputs are discarded. Witness feeder calls are not
+    /// bounded. A zero `timeout` removes the limit.
+    pub fn with_hint_timeout(mut self, timeout: Duration) -> Self {
+        self.hint_timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// The CUDA device requested with `with_gpu`, if any.
+    pub fn gpu_device(&self) -> Option<u32> {
+        self.gpu_device
+    }
+
+    /// The thread limit set with [`ProverConfig::with_threads`], if any.
+    pub fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// The memory limit set with [`ProverConfig::with_memory_limit`], if any.
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.memory_limit
+    }
+
+    /// The hash-to-field function proofs are generated with.
+    pub fn hash_function(&self) -> HashFunction {
+        self.hash
+    }
+
+    /// The encoding proofs are written in.
+    pub fn proof_format(&self) -> SerializationFormat {
+        self.proof_format
+    }
+
+    /// The seed set with [`ProverConfig::with_test_rng`], if any.
+    pub fn test_rng_seed(&self) -> Option<u64> {
+        self.test_rng_seed
+    }
+
+    /// Whether [`ProverConfig::with_witness_scrubbing`] is on.
+    pub fn witness_scrubbing(&self) -> bool {
+        self.scrub_witness
+    }
+
+    /// The solver worker count set with [`ProverConfig::with_solver_threads`],
+    /// if any.
+    pub fn solver_threads(&self) -> Option<usize> {
+        self.solver_threads
+    }
+
+    /// The hint timeout set with [`ProverConfig::with_hint_timeout`], if any.
+    pub fn hint_timeout(&self) -> Option<Duration> {
+        self.hint_timeout
+    }
+
+    /// `solver_tasks` of `C_ProveOptions`.
+    #[cfg(not(feature = "verifier"))]
+    pub(crate) fn raw_solver_tasks(&self) -> c_int {
+        self.solver_threads
+            .map_or(0, |n| n.try_into().unwrap_or(c_int::MAX))
+    }
+
+    /// `hint_timeout_ms` of `C_ProveOptions`, rounding sub-millisecond
+    /// timeouts up since 0 means none.
+    #[cfg(not(feature = "verifier"))]
+    pub(crate) fn raw_hint_timeout_ms(&self) -> i64 {
+        self.hint_timeout
+            .map_or(0, |d| d.as_millis().max(1).try_into().unwrap_or(i64::MAX))
+    }
+}
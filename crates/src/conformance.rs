@@ -0,0 +1,192 @@
+//! Byte-for-byte conformance with upstream gnark's encodings.
+//!
+//! Canonical vectors are generated by upstream gnark alone
+//! (`go/cmd/gen_conformance`, none of this crate's Go code), by default into
+//! [`CANONICAL_DIR`]: for a plain circuit and one with a commitment, the
+//! verifying key in both encodings, a proof in both encodings and as Solidity
+//! calldata, and the public witness. [`check`] decodes and re-encodes each
+//! with this crate and reports every difference, so a release that drifts
+//! from gnark's formats fails its tests rather than a Go service's.
+//!
+//! The generator also runs against any gnark version: point [`check`] at its
+//! output to see whether the gnark of a service you exchange keys and proofs
+//! with agrees with this crate.
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::Path;
+
+use crate::{
+    groth16_prove, groth16_verify, CircuitAbi, Groth16ProofResult, SerializationFormat,
+    VerifyingKey, Witness,
+};
+
+/// Where `go/cmd/gen_conformance` writes the canonical vectors by default.
+pub const CANONICAL_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/conformance");
+
+/// The circuits of a vector directory, each in the subdirectory of its name:
+/// `x^3 + x + 5 == y`, and the same with a commitment to `x`.
+pub const CIRCUITS: [&str; 2] = ["cubic", "committed"];
+
+/// The witness every vector's proof is for.
+pub const ASSIGNMENT: [(&str, &str); 2] = [("X", "3"), ("Y", "35")];
+
+/// A difference between this crate and the gnark that wrote a vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The circuit of the vector, one of [`CIRCUITS`].
+    pub circuit: &'static str,
+    /// What was checked, e.g. `vk encoding`.
+    pub check: &'static str,
+    /// How the crate disagreed.
+    pub detail: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.circuit, self.check, self.detail)
+    }
+}
+
+/// The gnark version that generated the vectors in `dir`, e.g. `v0.14.0`.
+///
+/// # Errors
+///
+/// Returns an error if `dir` has no `GNARK_VERSION` file.
+pub fn gnark_version(dir: &str) -> Result<String> {
+    let path = Path::new(dir).join("GNARK_VERSION");
+    let version = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(version.trim().to_owned())
+}
+
+/// Check this crate against the vectors in `dir`, e.g. [`CANONICAL_DIR`]:
+/// that it decodes the verifying key from either encoding and re-encodes it
+/// into the same bytes in both, builds the same public witness from
+/// [`ASSIGNMENT`], accepts both proof encodings, derives the same Solidity
+/// calldata, and that its own proofs with the vector's proving key verify
+/// with the vector's verifying key.
+///
+/// # Returns
+///
+/// Every mismatch found, none if the crate conforms.
+///
+/// # Errors
+///
+/// Returns an error if a vector file is missing or the circuit cannot be
+/// read.
+pub fn check(dir: &str) -> Result<Vec<Mismatch>> {
+    let mut mismatches = Vec::new();
+    for circuit in CIRCUITS {
+        check_circuit(&Path::new(dir).join(circuit), circuit, &mut mismatches)?;
+    }
+    Ok(mismatches)
+}
+
+fn check_circuit(dir: &Path, circuit: &'static str, out: &mut Vec<Mismatch>) -> Result<()> {
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let read = |name: &str| {
+        std::fs::read(dir.join(name))
+            .with_context(|| format!("failed to read {}", dir.join(name).display()))
+    };
+    let mut mismatch = |check, detail: String| {
+        out.push(Mismatch {
+            circuit,
+            check,
+            detail,
+        })
+    };
+    let vk_compressed = read("vk.compressed")?;
+    let vk_raw = read("vk.raw")?;
+    let public_witness = to_hex(&read("public_witness")?);
+
+    // Each encoding of the key decodes, and re-encodes into both.
+    let encodings = [
+        (
+            SerializationFormat::Compressed,
+            "vk.compressed",
+            &vk_compressed,
+        ),
+        (SerializationFormat::Raw, "vk.raw", &vk_raw),
+    ];
+    for (format, name, bytes) in encodings {
+        let vk = match VerifyingKey::from_bytes_with_format(bytes, format) {
+            Ok(vk) => vk,
+            Err(err) => {
+                mismatch("vk decoding", format!("{name} does not decode: {err:#}"));
+                continue;
+            }
+        };
+        for (out_format, out_name, expected) in encodings {
+            if vk.to_bytes(out_format)?[..] != expected[..] {
+                mismatch(
+                    "vk encoding",
+                    format!("{name} re-encoded as {out_format:?} differs from {out_name}"),
+                );
+            }
+        }
+    }
+
+    let abi = CircuitAbi::from_r1cs(&path("circuit.r1cs"))?;
+    let witness = Witness::from_assignments(&abi, ASSIGNMENT)?;
+    if witness.public_inputs_hex() != public_witness {
+        mismatch(
+            "public witness",
+            "the public witness of ASSIGNMENT differs from public_witness".into(),
+        );
+    }
+
+    for name in ["proof.compressed", "proof.raw"] {
+        let proof = Groth16ProofResult {
+            proof: to_hex(&read(name)?),
+            public_inputs: public_witness.clone(),
+        };
+        match groth16_verify(&path("circuit.r1cs"), &path("vk.compressed"), &proof) {
+            Ok(true) => {}
+            Ok(false) => mismatch("proof verification", format!("{name} does not verify")),
+            Err(err) => mismatch("proof decoding", format!("{name} does not decode: {err:#}")),
+        }
+    }
+
+    let proof = Groth16ProofResult {
+        proof: to_hex(&read("proof.compressed")?),
+        public_inputs: public_witness.clone(),
+    };
+    match proof.solidity_calldata() {
+        Ok(calldata) if calldata == read("proof.solidity")? => {}
+        Ok(_) => mismatch(
+            "solidity calldata",
+            "the calldata of proof.compressed differs from proof.solidity".into(),
+        ),
+        Err(err) => mismatch("solidity calldata", format!("{err:#}")),
+    }
+
+    // Proofs made here with gnark's proving key verify with gnark's key.
+    let ours = match groth16_prove(
+        &path("circuit.r1cs"),
+        &path("circuit.pk"),
+        &witness.to_json(),
+    ) {
+        Ok(ours) => ours,
+        Err(err) => {
+            mismatch("proving", format!("no proof with circuit.pk: {err:#}"));
+            return Ok(());
+        }
+    };
+    if ours.public_inputs != public_witness {
+        mismatch(
+            "public witness",
+            "the public witness of a proof differs from public_witness".into(),
+        );
+    }
+    match groth16_verify(&path("circuit.r1cs"), &path("vk.compressed"), &ours) {
+        Ok(true) => {}
+        Ok(false) => mismatch("proving", "a proof with circuit.pk does not verify".into()),
+        Err(err) => mismatch("proving", format!("{err:#}")),
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
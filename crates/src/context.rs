@@ -0,0 +1,122 @@
+//! Binding proofs to an application context.
+//!
+//! A Groth16 proof verifies for anyone holding the verifying key, so a proof
+//! made for one application, or one session of it, can be replayed wherever
+//! the same circuit is accepted. Circuits built with
+//! [`CircuitBuilder::bind_app_context`](crate::CircuitBuilder::bind_app_context)
+//! take a last public input, [`APP_CONTEXT`], that the proof is bound to:
+//! the prover fills it in with [`app_context`] of the application's domain
+//! and the verifier's challenge, and the verifier accepts the proof only for
+//! the context it expects, with [`groth16_verify_in_context`].
+//!
+//! ```no_run
+//! # fn example(witness: rust_gnark::Witness, nonce: [u8; 32]) -> anyhow::Result<()> {
+//! let context = rust_gnark::app_context("com.example.voting", &nonce)?;
+//! let witness = witness.with_app_context(context)?;
+//! let proof = rust_gnark::groth16_prove("circuit.r1cs", "circuit.pk", &witness.to_json())?;
+//!
+//! // On the verifier's side, with the nonce it handed out.
+//! let valid = rust_gnark::groth16_verify_in_context(
+//!     "circuit.r1cs", "circuit.vk", &proof, "com.example.voting", &nonce,
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{bail, ensure, Result};
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::{bind, groth16_verify, Fr, GnarkError, Groth16ProofResult};
+
+/// Name of the public input of a circuit built with
+/// [`crate::CircuitBuilder::bind_app_context`], always its last.
+pub const APP_CONTEXT: &str = "AppContext";
+
+/// The context value for the application named `domain` (e.g. a reverse
+/// DNS name) and `challenge`: RFC 9380 `hash_to_field` of `challenge` over
+/// SHA-256, with `domain` in the domain separation tag, so that two
+/// applications never derive the same context.
+///
+/// `challenge` is what makes a proof single-use: a nonce the verifier hands
+/// out, a session id, or the message the proof authorizes. With a constant
+/// challenge, proofs are only bound to the application.
+///
+/// # Errors
+///
+/// Returns [`GnarkError::InvalidInput`] if `domain` is longer than 229 bytes
+/// or contains a NUL byte.
+pub fn app_context(domain: &str, challenge: &[u8]) -> Result<Fr> {
+    let c_domain = CString::new(domain)
+        .map_err(|_| GnarkError::InvalidInput("the domain contains a NUL byte".into()))?;
+    let mut out = [0u8; 32];
+    let err_ptr = unsafe {
+        bind::gnark_app_context(
+            c_domain.as_ptr() as *mut c_char,
+            challenge.as_ptr() as *mut u8,
+            challenge.len() as u64,
+            out.as_mut_ptr(),
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Fr::from_bytes_be(&out)
+}
+
+/// The context `proof` is bound to: the last of its public inputs, where
+/// [`crate::CircuitBuilder::bind_app_context`] puts [`APP_CONTEXT`].
+///
+/// # Errors
+///
+/// Returns an error if the public inputs do not decode or there are none.
+pub fn proof_app_context(proof: &Groth16ProofResult) -> Result<Fr> {
+    let hex = proof.public_inputs.as_str();
+    // Untrusted: only hex digits, so the byte offsets below are char boundaries.
+    ensure!(
+        hex.bytes().all(|b| b.is_ascii_hexdigit()),
+        "the public inputs are not a hex-encoded gnark public witness"
+    );
+    let header = |i: usize| {
+        hex.get(8 * i..8 * i + 8)
+            .and_then(|s| u32::from_str_radix(s, 16).ok())
+    };
+    let (Some(nb_public), Some(nb_secret), Some(len)) = (header(0), header(1), header(2)) else {
+        bail!("the public inputs are not a hex-encoded gnark public witness");
+    };
+    let expected = (len as usize)
+        .checked_mul(64)
+        .and_then(|n| n.checked_add(24));
+    ensure!(
+        nb_secret == 0 && nb_public == len && Some(hex.len()) == expected,
+        "the public inputs are not a hex-encoded gnark public witness"
+    );
+    ensure!(len > 0, "the proof has no public inputs, so no context");
+    let last = &hex[hex.len() - 64..];
+    let mut bytes = [0u8; 32];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&last[2 * i..2 * i + 2], 16)?;
+    }
+    Fr::from_bytes_be(&bytes)
+}
+
+/// [`groth16_verify`] for a circuit built with
+/// [`crate::CircuitBuilder::bind_app_context`], which also checks that the
+/// proof is bound to [`app_context`] of `domain` and `challenge`.
+///
+/// # Errors
+///
+/// Same as [`groth16_verify`] and [`app_context`]. A proof bound to another
+/// context is not an error: it gives `Ok(false)`.
+pub fn groth16_verify_in_context(
+    r1cs_path: &str,
+    vk_path: &str,
+    proof: &Groth16ProofResult,
+    domain: &str,
+    challenge: &[u8],
+) -> Result<bool> {
+    if proof_app_context(proof)? != app_context(domain, challenge)? {
+        return Ok(false);
+    }
+    groth16_verify(r1cs_path, vk_path, proof)
+}
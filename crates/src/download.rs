@@ -0,0 +1,333 @@
+//! Resumable HTTP downloads of large artifacts (the `downloader` feature).
+//!
+//! Files are fetched in `Range` requests of a fixed size and appended to the
+//! destination as they arrive, so an interrupted download (a dropped
+//! connection, the app being killed) continues from the bytes already on disk
+//! instead of starting over. Servers that ignore `Range` are read in one go.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{ArtifactKind, ArtifactStore, KeyFingerprint};
+
+/// Called with the bytes on disk and, once the server reports it, the total.
+type ProgressFn = dyn Fn(u64, Option<u64>) + Send + Sync;
+
+/// A resumable, chunked HTTP downloader.
+///
+/// Built with `Downloader::default()` and the `with_*` methods:
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use rust_gnark::{ArtifactKind, ArtifactStore, Downloader};
+///
+/// let store = ArtifactStore::open_default("com.example.app")?;
+/// let downloader = Downloader::default()
+///     .with_chunk_size(16 << 20)
+///     .with_progress(|done, total| println!("{done} of {total:?} bytes"));
+/// store.download(
+///     &downloader,
+///     "age-check",
+///     "1.2.0",
+///     ArtifactKind::ProvingKey,
+///     "https://example.com/age-check/1.2.0/circuit.pk",
+///     &"5d41402abc4b2a76b9719d911017c592ae5e5f3cd0bd9f17e1fa8e58f1c9b4f3".parse()?,
+/// )?;
+/// let pk = store.proving_key("age-check", "1.2.0")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Downloader {
+    agent: ureq::Agent,
+    chunk_size: u64,
+    retries: u32,
+    progress: Option<Arc<ProgressFn>>,
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(30))
+                .timeout_read(Duration::from_secs(60))
+                .build(),
+            chunk_size: 8 << 20,
+            retries: 3,
+            progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for Downloader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Downloader")
+            .field("chunk_size", &self.chunk_size)
+            .field("retries", &self.retries)
+            .field("progress", &self.progress.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Downloader {
+    /// Bytes requested per `Range` request (default 8 MiB). Smaller chunks
+    /// lose less on a dropped connection; larger ones make fewer requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    pub fn with_chunk_size(mut self, bytes: u64) -> Self {
+        assert!(bytes > 0, "chunk size must be positive");
+        self.chunk_size = bytes;
+        self
+    }
+
+    /// Consecutive failed requests tolerated before giving up (default 3).
+    /// Transport errors and 5xx responses are retried after a short delay;
+    /// any progress resets the count.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Use `agent` for requests, e.g. for a proxy or custom timeouts.
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Call `progress(done, total)` after each write to the file. `total` is
+    /// `None` until a response reports the full size.
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Download `url` into `dest`, resuming after the bytes `dest` already
+    /// holds, and return the file's final size.
+    ///
+    /// The file is only appended to, except when the server ignores `Range`
+    /// and sends the whole file, which then replaces it. Nothing checks the
+    /// contents; see [`ArtifactStore::download`] for verified downloads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the retries run out, the server answers with an
+    /// unexpected status or range, or the file cannot be written. The bytes
+    /// received so far stay in `dest` for the next attempt.
+    pub fn download_to(&self, url: &str, dest: &Path) -> Result<u64> {
+        let _span = enter_span!("download", url);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dest)
+            .with_context(|| format!("failed to open {}", dest.display()))?;
+        let mut done = file.metadata()?.len();
+        let mut total = None;
+        let mut failures = 0;
+
+        while total != Some(done) {
+            let before = done;
+            match self.fetch(url, &mut file, &mut done, &mut total) {
+                Ok(()) => failures = 0,
+                Err(Fetch::Fatal(err)) => return Err(err),
+                Err(Fetch::Retry(err)) => {
+                    if done > before {
+                        failures = 0;
+                    }
+                    failures += 1;
+                    if failures > self.retries {
+                        return Err(err.context(format!(
+                            "giving up on {url} after {failures} failed requests"
+                        )));
+                    }
+                    std::thread::sleep(Duration::from_millis(500 << failures.min(5)));
+                }
+            }
+        }
+        file.sync_all()?;
+        Ok(done)
+    }
+
+    /// Request the next chunk after `done` bytes and append what arrives,
+    /// updating `done` and, once known, `total`.
+    fn fetch(
+        &self,
+        url: &str,
+        file: &mut std::fs::File,
+        done: &mut u64,
+        total: &mut Option<u64>,
+    ) -> Result<(), Fetch> {
+        let start = *done;
+        let end = start.saturating_add(self.chunk_size) - 1;
+        let response = match self
+            .agent
+            .get(url)
+            .set("Range", &format!("bytes={start}-{end}"))
+            .call()
+        {
+            Ok(response) => response,
+            Err(ureq::Error::Status(416, response)) => {
+                // Nothing left past `start`: complete if that is the size.
+                return match content_range(&response).map_err(Fetch::Fatal)? {
+                    (None, Some(size)) if size == start => {
+                        *total = Some(size);
+                        Ok(())
+                    }
+                    _ => Err(Fetch::Fatal(anyhow!(
+                        "{url}: the server rejected the range from byte {start}; \
+                         the partial file may be from a different artifact"
+                    ))),
+                };
+            }
+            Err(ureq::Error::Status(status, _)) if status >= 500 => {
+                return Err(Fetch::Retry(anyhow!("{url}: HTTP {status}")));
+            }
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(Fetch::Fatal(anyhow!("{url}: HTTP {status}")));
+            }
+            Err(err @ ureq::Error::Transport(_)) => {
+                return Err(Fetch::Retry(
+                    anyhow!(err).context(format!("failed to fetch {url}")),
+                ));
+            }
+        };
+
+        match response.status() {
+            206 => {
+                let (range, size) = content_range(&response).map_err(Fetch::Fatal)?;
+                if range.map(|(first, _)| first) != Some(start) {
+                    return Err(Fetch::Fatal(anyhow!(
+                        "{url}: the server sent a different range than requested (from byte {start})"
+                    )));
+                }
+                *total = size.or(*total);
+            }
+            200 => {
+                // Range ignored: this is the whole file.
+                file.set_len(0).map_err(|e| Fetch::Fatal(e.into()))?;
+                *done = 0;
+                *total = response
+                    .header("Content-Length")
+                    .and_then(|len| len.parse().ok());
+            }
+            status => {
+                return Err(Fetch::Fatal(anyhow!(
+                    "{url}: unexpected HTTP {status} to a range request"
+                )));
+            }
+        }
+
+        let whole = response.status() == 200;
+        let mut body = response.into_reader();
+        let mut buf = vec![0u8; 256 << 10];
+        let before = *done;
+        loop {
+            let n = match body.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    return Err(Fetch::Retry(
+                        anyhow!(e).context(format!("connection to {url} dropped")),
+                    ))
+                }
+            };
+            file.write_all(&buf[..n])
+                .map_err(|e| Fetch::Fatal(anyhow!(e).context("failed to write download")))?;
+            *done += n as u64;
+            if let Some(progress) = &self.progress {
+                progress(*done, *total);
+            }
+        }
+
+        if whole && total.is_none() {
+            *total = Some(*done);
+        }
+        if *done == before && *total != Some(*done) {
+            return Err(Fetch::Retry(anyhow!("{url}: empty response")));
+        }
+        Ok(())
+    }
+}
+
+/// A failed request, and whether trying again might help.
+enum Fetch {
+    Retry(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+/// Parse `Content-Range: bytes <first>-<last>/<size>` (either side may be `*`).
+#[allow(clippy::type_complexity)]
+fn content_range(response: &ureq::Response) -> Result<(Option<(u64, u64)>, Option<u64>)> {
+    let header = response
+        .header("Content-Range")
+        .ok_or_else(|| anyhow!("missing Content-Range header"))?;
+    let invalid = || anyhow!("invalid Content-Range header {header:?}");
+    let (range, size) = header
+        .strip_prefix("bytes ")
+        .and_then(|rest| rest.split_once('/'))
+        .ok_or_else(invalid)?;
+    let range = match range {
+        "*" => None,
+        range => {
+            let (first, last) = range.split_once('-').ok_or_else(invalid)?;
+            Some((
+                first.parse().map_err(|_| invalid())?,
+                last.parse().map_err(|_| invalid())?,
+            ))
+        }
+    };
+    let size = match size {
+        "*" => None,
+        size => Some(size.parse().map_err(|_| invalid())?),
+    };
+    Ok((range, size))
+}
+
+impl ArtifactStore {
+    /// Download an artifact from `url` into the store and check it against
+    /// `expected`, the SHA-256 of the file.
+    ///
+    /// The download streams straight into the artifact's temporary file,
+    /// which becomes the file [`ArtifactStore::proving_key`] memory-maps, and
+    /// resumes from it if an earlier call was interrupted. For a raw proving
+    /// key the file's SHA-256 is [`crate::ProvingKey::fingerprint`]. Nothing is
+    /// fetched if the store already holds the artifact with that checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the download fails (see
+    /// [`Downloader::download_to`]; the partial file is kept for the next
+    /// call), or the file does not match `expected`, in which case it is
+    /// deleted.
+    pub fn download(
+        &self,
+        downloader: &Downloader,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+        url: &str,
+        expected: &KeyFingerprint,
+    ) -> Result<()> {
+        if self.contains(circuit, version, kind)
+            && self.checksum(circuit, version, kind)? == *expected
+        {
+            return Ok(());
+        }
+        self.insert_with(circuit, version, kind, Some(expected), |tmp| {
+            let size = downloader.download_to(url, tmp)?;
+            ensure!(size > 0, "{url} is empty");
+            Ok(())
+        })
+        .map(drop)
+    }
+}
@@ -0,0 +1,251 @@
+//! Raw gnark-crypto primitives for protocols built outside gnark.
+//!
+//! Multi-scalar multiplication over G1 and FFTs over the scalar field, for
+//! BN254 and BLS12-381, using gnark-crypto's optimized implementations (the
+//! ones its provers run) instead of a second Rust implementation.
+//!
+//! Scalars are [`FieldBytes`], as for [`crate::hash`]. G1 points use
+//! gnark-crypto's uncompressed encoding (`G1Affine.RawBytes`): big-endian `x`
+//! and `y`, [`Curve::g1_size`] bytes per point, concatenated in slices of
+//! points. Input points are checked to be on the curve and in the subgroup.
+//!
+//! For BN254 there is also group arithmetic on [`G1Bytes`] / [`G2Bytes`] and
+//! [`pairing_check`], for custom verification equations (e.g. aggregated BLS
+//! signatures) on gnark-crypto's assembly-optimized arithmetic.
+
+use anyhow::{ensure, Result};
+use std::os::raw::{c_char, c_int};
+
+use crate::{bind, GnarkError};
+
+pub use crate::hash::{Curve, FieldBytes};
+
+impl Curve {
+    /// Size of a G1 point in gnark-crypto's uncompressed encoding.
+    pub const fn g1_size(self) -> usize {
+        match self {
+            Curve::Bn254 => 64,
+            Curve::Bls12_381 => 96,
+        }
+    }
+}
+
+/// `sum(scalars[i] * points[i])` over G1 of `curve`, uncompressed.
+///
+/// `points` holds `scalars.len()` concatenated uncompressed points. An empty
+/// input gives the point at infinity.
+///
+/// # Errors
+///
+/// Returns an error if the lengths disagree, a point is invalid or a scalar
+/// is not canonical.
+pub fn msm_g1(curve: Curve, points: &[u8], scalars: &[FieldBytes]) -> Result<Vec<u8>> {
+    ensure!(
+        points.len() == scalars.len() * curve.g1_size(),
+        "expected {} bytes of points for {} scalars, got {}",
+        scalars.len() * curve.g1_size(),
+        scalars.len(),
+        points.len()
+    );
+    let mut out = vec![0u8; curve.g1_size()];
+    let err_ptr = unsafe {
+        bind::gnark_msm_g1(
+            curve.as_raw(),
+            points.as_ptr() as *mut u8,
+            scalars.as_ptr() as *mut u8,
+            scalars.len() as u64,
+            out.as_mut_ptr(),
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(out)
+}
+
+/// Replace the coefficients of a polynomial with its evaluations on the
+/// subgroup of `coeffs.len()`-th roots of unity, in natural order (`ω^0`,
+/// `ω^1`, ...), with gnark-crypto's generator `ω`.
+///
+/// # Errors
+///
+/// Returns an error if `coeffs.len()` is not a power of two (or exceeds the
+/// field's two-adicity: 2^28 for BN254, 2^32 for BLS12-381) or an element
+/// is not canonical.
+pub fn fft(curve: Curve, coeffs: &mut [FieldBytes]) -> Result<()> {
+    fft_with(curve, coeffs, false)
+}
+
+/// Inverse of [`fft`]: evaluations on the roots of unity back to coefficients.
+///
+/// # Errors
+///
+/// Same as [`fft`].
+pub fn ifft(curve: Curve, evals: &mut [FieldBytes]) -> Result<()> {
+    fft_with(curve, evals, true)
+}
+
+fn fft_with(curve: Curve, values: &mut [FieldBytes], inverse: bool) -> Result<()> {
+    let err_ptr = unsafe {
+        bind::gnark_fft(
+            curve.as_raw(),
+            values.as_mut_ptr() as *mut u8,
+            values.len() as u64,
+            inverse.into(),
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(())
+}
+
+/// A BN254 G1 point in gnark-crypto's uncompressed encoding.
+pub type G1Bytes = [u8; 64];
+
+/// A BN254 G2 point in gnark-crypto's uncompressed encoding
+/// (`x.A1 || x.A0 || y.A1 || y.A0`).
+pub type G2Bytes = [u8; 128];
+
+/// Call a Go export writing one point of `N` bytes.
+fn point_op<const N: usize>(f: impl FnOnce(*mut u8) -> *mut c_char) -> Result<[u8; N]> {
+    let mut out = [0u8; N];
+    let err_ptr = f(out.as_mut_ptr());
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(out)
+}
+
+/// `a + b` in BN254 G1.
+///
+/// # Errors
+///
+/// Returns an error if a point is invalid.
+pub fn g1_add(a: &G1Bytes, b: &G1Bytes) -> Result<G1Bytes> {
+    point_op(|out| unsafe {
+        bind::gnark_bn254_g1_add(a.as_ptr() as *mut u8, b.as_ptr() as *mut u8, out)
+    })
+}
+
+/// `-a` in BN254 G1.
+///
+/// # Errors
+///
+/// Returns an error if the point is invalid.
+pub fn g1_neg(a: &G1Bytes) -> Result<G1Bytes> {
+    point_op(|out| unsafe { bind::gnark_bn254_g1_neg(a.as_ptr() as *mut u8, out) })
+}
+
+/// `scalar * a` in BN254 G1.
+///
+/// # Errors
+///
+/// Returns an error if the point is invalid or the scalar is not canonical.
+pub fn g1_scalar_mul(a: &G1Bytes, scalar: &FieldBytes) -> Result<G1Bytes> {
+    point_op(|out| unsafe {
+        bind::gnark_bn254_g1_scalar_mul(a.as_ptr() as *mut u8, scalar.as_ptr() as *mut u8, out)
+    })
+}
+
+/// `a + b` in BN254 G2.
+///
+/// # Errors
+///
+/// Returns an error if a point is invalid.
+pub fn g2_add(a: &G2Bytes, b: &G2Bytes) -> Result<G2Bytes> {
+    point_op(|out| unsafe {
+        bind::gnark_bn254_g2_add(a.as_ptr() as *mut u8, b.as_ptr() as *mut u8, out)
+    })
+}
+
+/// `scalar * a` in BN254 G2.
+///
+/// # Errors
+///
+/// Returns an error if the point is invalid or the scalar is not canonical.
+pub fn g2_scalar_mul(a: &G2Bytes, scalar: &FieldBytes) -> Result<G2Bytes> {
+    point_op(|out| unsafe {
+        bind::gnark_bn254_g2_scalar_mul(a.as_ptr() as *mut u8, scalar.as_ptr() as *mut u8, out)
+    })
+}
+
+/// Whether `prod e(g1[i], g2[i]) == 1` over BN254.
+///
+/// # Errors
+///
+/// Returns an error if the slices differ in length, are empty, or hold an
+/// invalid point.
+pub fn pairing_check(g1: &[G1Bytes], g2: &[G2Bytes]) -> Result<bool> {
+    ensure!(
+        g1.len() == g2.len(),
+        "pairing check needs as many G1 as G2 points, got {} and {}",
+        g1.len(),
+        g2.len()
+    );
+    let mut ok: c_int = 0;
+    let err_ptr = unsafe {
+        bind::gnark_bn254_pairing_check(
+            g1.as_ptr() as *mut u8,
+            g2.as_ptr() as *mut u8,
+            g1.len() as u64,
+            &mut ok,
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(ok != 0)
+}
+
+/// [`msm_g1`] over BN254 on arkworks points and scalars.
+///
+/// # Errors
+///
+/// Returns an error if the lengths disagree.
+#[cfg(feature = "native-verify")]
+pub fn msm_g1_bn254(
+    points: &[ark_bn254::G1Affine],
+    scalars: &[ark_bn254::Fr],
+) -> Result<ark_bn254::G1Affine> {
+    use crate::native::{fr_to_gnark_bytes, g1_from_gnark_bytes, g1_to_gnark_raw_bytes};
+
+    let points: Vec<u8> = points.iter().flat_map(g1_to_gnark_raw_bytes).collect();
+    let scalars: Vec<FieldBytes> = scalars.iter().map(fr_to_gnark_bytes).collect();
+    g1_from_gnark_bytes(&msm_g1(Curve::Bn254, &points, &scalars)?)
+}
+
+/// [`fft`] over BN254 on `ark_bn254::Fr` values.
+///
+/// # Errors
+///
+/// Same as [`fft`].
+#[cfg(feature = "native-verify")]
+pub fn fft_bn254(coeffs: &mut [ark_bn254::Fr]) -> Result<()> {
+    map_bn254(coeffs, fft)
+}
+
+/// [`ifft`] over BN254 on `ark_bn254::Fr` values.
+///
+/// # Errors
+///
+/// Same as [`fft`].
+#[cfg(feature = "native-verify")]
+pub fn ifft_bn254(evals: &mut [ark_bn254::Fr]) -> Result<()> {
+    map_bn254(evals, ifft)
+}
+
+#[cfg(feature = "native-verify")]
+fn map_bn254(
+    values: &mut [ark_bn254::Fr],
+    f: fn(Curve, &mut [FieldBytes]) -> Result<()>,
+) -> Result<()> {
+    use crate::native::{fr_from_gnark_bytes, fr_to_gnark_bytes};
+
+    let mut bytes: Vec<FieldBytes> = values.iter().map(fr_to_gnark_bytes).collect();
+    f(Curve::Bn254, &mut bytes)?;
+    for (value, b) in values.iter_mut().zip(&bytes) {
+        *value = fr_from_gnark_bytes(b)?;
+    }
+    Ok(())
+}
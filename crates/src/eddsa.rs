@@ -0,0 +1,202 @@
+//! EdDSA signatures verifiable by gnark's `std/signature/eddsa` gadget.
+//!
+//! Keys live on twisted Edwards curves whose base field is the scalar field of
+//! a pairing curve, so signatures can be checked cheaply inside circuits over
+//! that curve. Messages are sequences of field elements of that scalar field
+//! (see [`crate::hash::FieldBytes`]) and are hashed with its MiMC, as the
+//! gadget does.
+
+use anyhow::Result;
+use std::os::raw::{c_char, c_int};
+
+use crate::buffer::GnarkBuffer;
+use crate::hash::FieldBytes;
+use crate::{bind, GnarkError};
+
+/// Twisted Edwards curve a key belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdwardsCurve {
+    /// Baby Jubjub, embedded in BN254: for circuits over BN254.
+    BabyJubjub,
+    /// Jubjub, embedded in BLS12-381. Requires the `bls12-381` feature.
+    Jubjub,
+    /// Bandersnatch, embedded in BLS12-381. Requires the `bls12-381` feature.
+    Bandersnatch,
+}
+
+impl EdwardsCurve {
+    /// The id the Go exports expect.
+    fn as_raw(self) -> c_int {
+        match self {
+            EdwardsCurve::BabyJubjub => 0,
+            EdwardsCurve::Jubjub => 1,
+            EdwardsCurve::Bandersnatch => 2,
+        }
+    }
+}
+
+/// Call a Go export that writes a single buffer.
+pub(crate) fn take_buffer(
+    f: impl FnOnce(*mut *mut u8, *mut u64) -> *mut c_char,
+) -> Result<Vec<u8>> {
+    let mut data = std::ptr::null_mut();
+    let mut len = 0u64;
+    let err_ptr = f(&mut data, &mut len);
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(unsafe { GnarkBuffer::from_raw(data, len) }.to_vec())
+}
+
+/// An EdDSA private key, in gnark-crypto's `PrivateKey.Bytes()` encoding.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    curve: EdwardsCurve,
+    bytes: Vec<u8>,
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("curve", &self.curve)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SigningKey {
+    /// Generate a key from the operating system's randomness.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key generation fails.
+    pub fn generate(curve: EdwardsCurve) -> Result<Self> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_eddsa_generate(curve.as_raw(), data, len)
+        })?;
+        Ok(Self { curve, bytes })
+    }
+
+    /// Load a key serialized by [`SigningKey::to_bytes`] or gnark-crypto.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid private key for `curve`.
+    pub fn from_bytes(curve: EdwardsCurve, bytes: &[u8]) -> Result<Self> {
+        let key = Self {
+            curve,
+            bytes: bytes.to_vec(),
+        };
+        key.public_key()?;
+        Ok(key)
+    }
+
+    /// The key in gnark-crypto's encoding.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Curve of this key.
+    pub fn curve(&self) -> EdwardsCurve {
+        self.curve
+    }
+
+    /// The matching public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key bytes are invalid.
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_eddsa_public_key(
+                self.curve.as_raw(),
+                self.bytes.as_ptr() as *mut u8,
+                self.bytes.len() as u64,
+                data,
+                len,
+            )
+        })?;
+        Ok(PublicKey {
+            curve: self.curve,
+            bytes,
+        })
+    }
+
+    /// Sign `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a message element is not canonical in the curve's
+    /// base field.
+    pub fn sign(&self, message: &[FieldBytes]) -> Result<Signature> {
+        let bytes = take_buffer(|data, len| unsafe {
+            bind::gnark_eddsa_sign(
+                self.curve.as_raw(),
+                self.bytes.as_ptr() as *mut u8,
+                self.bytes.len() as u64,
+                message.as_ptr() as *mut u8,
+                message.len() as u64,
+                data,
+                len,
+            )
+        })?;
+        Ok(Signature(bytes))
+    }
+}
+
+/// An EdDSA public key, a compressed point in gnark-crypto's encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    curve: EdwardsCurve,
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Wrap a compressed public key. It is validated by [`PublicKey::verify`].
+    pub fn from_bytes(curve: EdwardsCurve, bytes: &[u8]) -> Self {
+        Self {
+            curve,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// The key in gnark-crypto's encoding.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Curve of this key.
+    pub fn curve(&self) -> EdwardsCurve {
+        self.curve
+    }
+
+    /// Check `signature` over `message`.
+    ///
+    /// A malformed signature or message is reported as `Ok(false)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key is not a valid point.
+    pub fn verify(&self, message: &[FieldBytes], signature: &Signature) -> Result<bool> {
+        let mut valid: c_int = 0;
+        let err_ptr = unsafe {
+            bind::gnark_eddsa_verify(
+                self.curve.as_raw(),
+                self.bytes.as_ptr() as *mut u8,
+                self.bytes.len() as u64,
+                message.as_ptr() as *mut u8,
+                message.len() as u64,
+                signature.0.as_ptr() as *mut u8,
+                signature.0.len() as u64,
+                &mut valid,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(valid != 0)
+    }
+}
+
+/// An EdDSA signature, `R || S` in gnark-crypto's encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature(pub Vec<u8>);
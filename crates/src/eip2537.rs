@@ -0,0 +1,168 @@
+//! BLS12-381 points in the encoding of the EIP-2537 precompiles.
+//!
+//! EIP-2537 adds BLS12-381 G1/G2 addition, MSM and pairing precompiles to the
+//! EVM. They take each base field element as 64 big-endian bytes (48 bytes of
+//! value behind 16 zero bytes), a G1 point as `x || y` (128 bytes), a G2 point
+//! as `x.c0 || x.c1 || y.c0 || y.c1` (256 bytes), and the point at infinity as
+//! all zeros.
+//!
+//! The functions here convert from and to gnark-crypto's uncompressed
+//! BLS12-381 encoding (`RawBytes`), as [`crate::ecc::msm_g1`] returns it and
+//! as gnark writes BLS12-381 proofs and keys, so their points can be passed to
+//! the precompiles directly. This crate's own Groth16 proofs are over BN254,
+//! whose precompiles (EIP-196/197) take
+//! [`crate::Groth16ProofResult::solidity_calldata`] instead.
+//!
+//! Only the encodings are converted: points are not checked to be on the
+//! curve, which the precompiles do themselves.
+
+use anyhow::{bail, ensure, Result};
+
+/// A BLS12-381 G1 point in gnark-crypto's uncompressed encoding.
+pub type G1Raw = [u8; 96];
+
+/// A BLS12-381 G2 point in gnark-crypto's uncompressed encoding
+/// (`x.A1 || x.A0 || y.A1 || y.A0`).
+pub type G2Raw = [u8; 192];
+
+/// A G1 point as the EIP-2537 precompiles take it.
+pub type G1Eip2537 = [u8; 128];
+
+/// A G2 point as the EIP-2537 precompiles take it.
+pub type G2Eip2537 = [u8; 256];
+
+const FP_SIZE: usize = 48;
+const PADDED_FP_SIZE: usize = 64;
+
+/// Flag bits gnark-crypto keeps in the top bits of a point's first byte.
+const FLAG_MASK: u8 = 0b111 << 5;
+const FLAG_UNCOMPRESSED: u8 = 0b000 << 5;
+const FLAG_UNCOMPRESSED_INFINITY: u8 = 0b010 << 5;
+
+/// Convert a G1 point from gnark-crypto's uncompressed encoding.
+///
+/// # Errors
+///
+/// Returns an error if `point` is compressed or a malformed point at infinity.
+pub fn g1_to_eip2537(point: &G1Raw) -> Result<G1Eip2537> {
+    let mut out = [0u8; 128];
+    if is_infinity(point)? {
+        return Ok(out);
+    }
+    for (i, fp) in point.chunks_exact(FP_SIZE).enumerate() {
+        pad(fp, &mut out[i * PADDED_FP_SIZE..(i + 1) * PADDED_FP_SIZE]);
+    }
+    Ok(out)
+}
+
+/// Convert a G1 point to gnark-crypto's uncompressed encoding.
+///
+/// # Errors
+///
+/// Returns an error if a coordinate's padding is not zero.
+pub fn g1_from_eip2537(point: &G1Eip2537) -> Result<G1Raw> {
+    let mut out = [0u8; 96];
+    if point.iter().all(|&b| b == 0) {
+        out[0] = FLAG_UNCOMPRESSED_INFINITY;
+        return Ok(out);
+    }
+    for (i, fp) in point.chunks_exact(PADDED_FP_SIZE).enumerate() {
+        out[i * FP_SIZE..(i + 1) * FP_SIZE].copy_from_slice(unpad(fp)?);
+    }
+    Ok(out)
+}
+
+/// Convert a G2 point from gnark-crypto's uncompressed encoding, which orders
+/// each coordinate's components the other way round.
+///
+/// # Errors
+///
+/// Returns an error if `point` is compressed or a malformed point at infinity.
+pub fn g2_to_eip2537(point: &G2Raw) -> Result<G2Eip2537> {
+    let mut out = [0u8; 256];
+    if is_infinity(point)? {
+        return Ok(out);
+    }
+    for (i, fp) in point.chunks_exact(FP_SIZE).enumerate() {
+        let j = swap_components(i);
+        pad(fp, &mut out[j * PADDED_FP_SIZE..(j + 1) * PADDED_FP_SIZE]);
+    }
+    Ok(out)
+}
+
+/// Convert a G2 point to gnark-crypto's uncompressed encoding.
+///
+/// # Errors
+///
+/// Returns an error if a coordinate's padding is not zero.
+pub fn g2_from_eip2537(point: &G2Eip2537) -> Result<G2Raw> {
+    let mut out = [0u8; 192];
+    if point.iter().all(|&b| b == 0) {
+        out[0] = FLAG_UNCOMPRESSED_INFINITY;
+        return Ok(out);
+    }
+    for (i, fp) in point.chunks_exact(PADDED_FP_SIZE).enumerate() {
+        let j = swap_components(i);
+        out[j * FP_SIZE..(j + 1) * FP_SIZE].copy_from_slice(unpad(fp)?);
+    }
+    Ok(out)
+}
+
+/// The input of the EIP-2537 pairing check precompile for
+/// `e(g1[0], g2[0]) * ... == 1`: each pair's G1 then G2 point.
+///
+/// # Errors
+///
+/// Returns an error if the slices differ in length or a point does not
+/// convert.
+pub fn pairing_input(g1: &[G1Raw], g2: &[G2Raw]) -> Result<Vec<u8>> {
+    ensure!(
+        g1.len() == g2.len(),
+        "expected as many G2 points as G1 points, got {} and {}",
+        g2.len(),
+        g1.len()
+    );
+    let mut out = Vec::with_capacity(g1.len() * (128 + 256));
+    for (p, q) in g1.iter().zip(g2) {
+        out.extend_from_slice(&g1_to_eip2537(p)?);
+        out.extend_from_slice(&g2_to_eip2537(q)?);
+    }
+    Ok(out)
+}
+
+/// Whether a gnark-crypto uncompressed point is the point at infinity.
+fn is_infinity(point: &[u8]) -> Result<bool> {
+    match point[0] & FLAG_MASK {
+        FLAG_UNCOMPRESSED => Ok(false),
+        FLAG_UNCOMPRESSED_INFINITY => {
+            ensure!(
+                point[0] == FLAG_UNCOMPRESSED_INFINITY && point[1..].iter().all(|&b| b == 0),
+                "malformed point at infinity"
+            );
+            Ok(true)
+        }
+        _ => bail!("expected an uncompressed point"),
+    }
+}
+
+/// Index of the base field element `i` of a G2 point in the other encoding:
+/// gnark-crypto writes `A1` before `A0`, EIP-2537 `c0` before `c1`.
+fn swap_components(i: usize) -> usize {
+    i ^ 1
+}
+
+fn pad(fp: &[u8], out: &mut [u8]) {
+    out[PADDED_FP_SIZE - FP_SIZE..].copy_from_slice(fp);
+}
+
+fn unpad(fp: &[u8]) -> Result<&[u8]> {
+    let (padding, value) = fp.split_at(PADDED_FP_SIZE - FP_SIZE);
+    ensure!(
+        padding.iter().all(|&b| b == 0),
+        "EIP-2537 field elements have 16 zero bytes of padding"
+    );
+    // gnark-crypto's flag bits must be clear, which holds for any element
+    // below the 381-bit modulus.
+    ensure!(value[0] & FLAG_MASK == 0, "base field element out of range");
+    Ok(value)
+}
@@ -0,0 +1,148 @@
+//! Structured errors reported by the Go side.
+
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_char;
+
+use crate::{bind, take_go_string};
+
+/// A failure reported by gnark, classified so callers can branch on it.
+///
+/// Functions in this crate return [`anyhow::Result`]; errors originating in Go
+/// carry a `GnarkError` that can be recovered with `downcast_ref`:
+///
+/// ```no_run
+/// # fn example() {
+/// match rust_gnark::groth16_prove("circuit.r1cs", "circuit.pk", r#"{"X": "3", "Y": "0"}"#) {
+///     Err(e) => match e.downcast_ref::<rust_gnark::GnarkError>() {
+///         Some(rust_gnark::GnarkError::WitnessSolveFailed { constraint_index, .. }) => {
+///             eprintln!("assignment violates constraint {constraint_index:?}")
+///         }
+///         _ => eprintln!("{e:#}"),
+///     },
+///     Ok(_) => {}
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+#[non_exhaustive]
+pub enum GnarkError {
+    /// An argument was rejected before doing any work (bad handle, wrong
+    /// number of proofs, unknown option, ...).
+    InvalidInput(String),
+    /// A file could not be opened or read.
+    Io(String),
+    /// The witness JSON could not be mapped onto the circuit's variables.
+    InvalidWitness(String),
+    /// The witness does not satisfy the circuit.
+    WitnessSolveFailed {
+        /// Index of the first unsatisfied constraint, if gnark reported it.
+        constraint_index: Option<usize>,
+        /// gnark's error message.
+        message: String,
+    },
+    /// A proving or verifying key is malformed or unusable.
+    InvalidKey(String),
+    /// An artifact was built for a different curve than expected.
+    CurveMismatch(String),
+    /// A proof, witness or other artifact failed to (de)serialize.
+    SerializationError(String),
+    /// The operation was cancelled through a [`crate::CancelToken`].
+    Cancelled,
+    /// A memory allocation on the Go side failed.
+    OutOfMemory,
+    /// The proof does not verify.
+    InvalidProof(String),
+    /// gnark panicked. The panic was recovered at the FFI boundary, so the
+    /// process keeps running, but the call's Go objects may be left in an
+    /// inconsistent state; this is a bug in gnark or in this crate and worth
+    /// reporting with the stack. Go runtime faults (running out of memory,
+    /// data races) still abort.
+    InternalPanic {
+        /// The panic value, after the message of the failing operation.
+        message: String,
+        /// The Go stack of the goroutine that panicked.
+        stack: String,
+    },
+    /// Any other failure.
+    Other(String),
+}
+
+impl GnarkError {
+    /// Decode an error string in the format written by `cError` in
+    /// `go/errors.go`: `<code>\x1f<constraint index>\x1f<message>`, with
+    /// `\x1e<stack>` after the message of a panic.
+    pub(crate) fn decode(raw: &str) -> Self {
+        let mut parts = raw.splitn(3, '\x1f');
+        let (Some(code), Some(index), Some(message)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Self::Other(raw.to_owned());
+        };
+        let message = message.to_owned();
+        match code.parse::<u32>() {
+            Ok(1) => Self::InvalidInput(message),
+            Ok(2) => Self::Io(message),
+            Ok(3) => Self::InvalidWitness(message),
+            Ok(4) => Self::WitnessSolveFailed {
+                constraint_index: index.parse().ok(),
+                message,
+            },
+            Ok(5) => Self::InvalidKey(message),
+            Ok(6) => Self::CurveMismatch(message),
+            Ok(7) => Self::SerializationError(message),
+            Ok(8) => Self::Cancelled,
+            Ok(9) => Self::OutOfMemory,
+            Ok(10) => Self::InvalidProof(message),
+            Ok(11) => {
+                let (message, stack) = message.split_once('\x1e').unwrap_or((&message, ""));
+                Self::InternalPanic {
+                    message: message.to_owned(),
+                    stack: stack.to_owned(),
+                }
+            }
+            _ => Self::Other(message),
+        }
+    }
+
+    /// Copy out, free and decode an error returned by the Go side.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`take_go_string`].
+    pub(crate) unsafe fn take(ptr: *mut c_char) -> Self {
+        Self::decode(&take_go_string(ptr))
+    }
+
+    /// Make the Go side panic with `message` and return the resulting error,
+    /// for testing how panics surface.
+    #[doc(hidden)]
+    pub fn debug_panic(message: &str) -> Self {
+        let message = CString::new(message).unwrap_or_default();
+        let err_ptr = unsafe { bind::gnark_debug_panic(message.as_ptr() as *mut c_char) };
+        assert!(!err_ptr.is_null(), "gnark_debug_panic returned");
+        unsafe { Self::take(err_ptr) }
+    }
+}
+
+impl fmt::Display for GnarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled => f.write_str("cancelled"),
+            Self::OutOfMemory => f.write_str("out of memory"),
+            Self::WitnessSolveFailed { message, .. } | Self::InternalPanic { message, .. } => {
+                f.write_str(message)
+            }
+            Self::InvalidInput(message)
+            | Self::Io(message)
+            | Self::InvalidWitness(message)
+            | Self::InvalidKey(message)
+            | Self::CurveMismatch(message)
+            | Self::SerializationError(message)
+            | Self::InvalidProof(message)
+            | Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for GnarkError {}
@@ -0,0 +1,111 @@
+//! Cost of a Groth16 proof, estimated before proving.
+//!
+//! A compiled circuit's size does not depend on the witness, so its proofs'
+//! size, the prover's memory and, with this device's [`Calibration`], the
+//! proving time follow from [`CompiledCircuit::stats`] alone. Apps can warn
+//! before starting a proof that will not fit in memory, or hand it to a
+//! proving service instead.
+//!
+//! Proving time is dominated by multi-scalar multiplications over the
+//! proving key: one per G1 key vector (`A`, `B` and `K` over the wires, `Z`
+//! over the FFT domain) and one in G2. The calibration MSM gives this
+//! device's time per point, scaled for larger MSMs by Pippenger's
+//! `n / log n`. Memory is the decoded proving key, the constraint system and
+//! the prover's wire and FFT vectors. Both are estimates within a factor of
+//! about two, good for deciding, not for promising.
+
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::calibrate::CALIBRATION_POINTS;
+use crate::{bind, Calibration, CompiledCircuit, GnarkError};
+
+/// Bytes of an affine G1 and G2 point in memory and of a scalar.
+const G1_BYTES: u64 = 64;
+const G2_BYTES: u64 = 128;
+const FR_BYTES: u64 = 32;
+
+/// Bytes of the solver's representation of one constraint, on average.
+const CONSTRAINT_BYTES: u64 = 64;
+
+/// A G2 point operation costs about three G1 ones (arithmetic over Fp2).
+const G2_COST: u64 = 3;
+
+/// Witness solving, FFTs and the final combination, on top of the MSMs.
+const OVERHEAD: f64 = 1.2;
+
+/// Estimated cost of proving a circuit with Groth16, from
+/// [`CompiledCircuit::estimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Estimate {
+    /// Size of a proof in [`SerializationFormat::Compressed`](crate::SerializationFormat::Compressed),
+    /// exact.
+    pub proof_bytes: u64,
+    /// Peak memory of a proof with the proving key loaded, in bytes.
+    pub peak_memory: u64,
+    /// Time of a proof with [`Calibration::apply`]'s thread count.
+    pub prove_time: Duration,
+}
+
+impl Estimate {
+    /// Whether [`Estimate::peak_memory`] fits in `budget` bytes, e.g. the
+    /// memory an app may use on a device, or a
+    /// [`ProverConfig::with_memory_limit`](crate::ProverConfig::with_memory_limit).
+    pub fn fits_in(&self, budget: u64) -> bool {
+        self.peak_memory <= budget
+    }
+}
+
+impl CompiledCircuit {
+    /// Estimate the cost of a Groth16 proof of this circuit on the device
+    /// `calibration` was measured on (see
+    /// [`ArtifactStore::calibration`](crate::ArtifactStore::calibration)),
+    /// without a witness or the proving key.
+    ///
+    /// ```no_run
+    /// # fn example(store: rust_gnark::ArtifactStore) -> anyhow::Result<()> {
+    /// let circuit = rust_gnark::CompiledCircuit::read("circuit.r1cs")?;
+    /// let estimate = circuit.estimate(&store.calibration()?)?;
+    /// if !estimate.fits_in(1 << 30) {
+    ///     println!("this proof needs ~{} MB of RAM", estimate.peak_memory >> 20);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this circuit.
+    pub fn estimate(&self, calibration: &Calibration) -> Result<Estimate> {
+        let stats = self.stats()?;
+        let mut commitments = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_circuit_nb_commitments(self.handle.id(), &mut commitments) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+
+        // Wires, the constant one included, and the FFT domain.
+        let wires =
+            1 + stats.nb_public_inputs + stats.nb_secret_inputs + stats.nb_internal_variables;
+        let domain = stats.nb_constraints.max(1).next_power_of_two();
+
+        let key = G1_BYTES * (3 * wires + domain) + G2_BYTES * wires;
+        let prover = FR_BYTES * (wires + 4 * domain);
+        let peak_memory = key + prover + CONSTRAINT_BYTES * stats.nb_constraints;
+
+        let g1_points = 3 * wires + domain + G2_COST * wires;
+        let per_point = calibration.msm_time().as_nanos() as f64 / CALIBRATION_POINTS as f64;
+        let calibration_log = (CALIBRATION_POINTS as f64).log2();
+        let log_points = (wires.max(domain) as f64).log2().max(calibration_log);
+        let nanos = per_point * g1_points as f64 * calibration_log / log_points * OVERHEAD;
+
+        Ok(Estimate {
+            // A, B and C, then the commitments' length, points and proof of
+            // knowledge, as gnark writes them.
+            proof_bytes: 32 + 64 + 32 + 4 + 32 * commitments + 32,
+            peak_memory,
+            prove_time: Duration::from_nanos(nanos as u64),
+        })
+    }
+}
@@ -0,0 +1,265 @@
+//! Verifying proofs with a deployed Solidity verifier (the `eth-rpc` feature).
+//!
+//! An [`EthVerifier`] encodes a proof as a call of the verifier contract's
+//! `verifyProof` ([`Groth16ProofResult::solidity_verify_call`]) and runs it
+//! with `eth_call` on an Ethereum node's JSON-RPC endpoint, so an app can
+//! check that a proof will pass on-chain before submitting a transaction that
+//! relies on it. The contract is gnark's, from
+//! [`VerifyingKey::export_solidity`](crate::VerifyingKey::export_solidity):
+//! `verifyProof` returns nothing and reverts on an invalid proof.
+//!
+//! The node is spoken to directly, one `eth_call` per proof, so no Ethereum
+//! client library is needed.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::metrics::{self, Scheme};
+use crate::{GnarkError, Groth16ProofResult};
+
+/// Outcome of an [`EthVerifier::verify`] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthVerification {
+    /// Whether `verifyProof` accepted the proof, i.e. did not revert.
+    pub valid: bool,
+    /// The node's message for a rejected proof, e.g. `execution reverted`.
+    pub revert_reason: Option<String>,
+    /// The revert data of a rejected proof, the ABI encoding of the
+    /// contract's error (`ProofInvalid()`, `PublicInputNotInField()`, ...);
+    /// empty if the node gives none.
+    pub revert_data: Vec<u8>,
+    /// The block the call was run against, as passed to `eth_call`.
+    pub block: String,
+}
+
+/// Verifies proofs with a Solidity verifier deployed at an address, through
+/// an Ethereum node's JSON-RPC API.
+///
+/// ```no_run
+/// # fn example(proof: rust_gnark::Groth16ProofResult) -> anyhow::Result<()> {
+/// use rust_gnark::EthVerifier;
+///
+/// let verifier = EthVerifier::new(
+///     "https://sepolia.example.com/rpc",
+///     "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+/// )?;
+/// let result = verifier.verify(&proof)?;
+/// if !result.valid {
+///     println!("rejected on-chain: {:?}", result.revert_reason);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct EthVerifier {
+    rpc_url: String,
+    address: String,
+    agent: ureq::Agent,
+    headers: Vec<(String, String)>,
+    block: String,
+    /// Set once the address is known to hold a contract.
+    deployed: OnceLock<()>,
+}
+
+impl fmt::Debug for EthVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // RPC URLs and header values often carry API keys.
+        let headers: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("EthVerifier")
+            .field("address", &self.address)
+            .field("headers", &headers)
+            .field("block", &self.block)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EthVerifier {
+    /// Verify with the contract at `address` (`0x`-prefixed hex) through the
+    /// JSON-RPC endpoint `rpc_url`, against the `latest` block.
+    ///
+    /// Requests time out after 10 seconds without a connection, or 30
+    /// seconds in all; see [`EthVerifier::with_agent`] to change that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidInput`] if `address` is not 20 bytes of
+    /// hex.
+    pub fn new(rpc_url: impl Into<String>, address: &str) -> Result<Self> {
+        let hex = address.strip_prefix("0x").unwrap_or(address);
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(GnarkError::InvalidInput(format!(
+                "{address:?} is not a 20-byte hex contract address"
+            ))
+            .into());
+        }
+        Ok(Self {
+            rpc_url: rpc_url.into(),
+            address: format!("0x{hex}"),
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30))
+                .build(),
+            headers: Vec::new(),
+            block: "latest".into(),
+            deployed: OnceLock::new(),
+        })
+    }
+
+    /// Use `agent` for requests, e.g. for a proxy or custom timeouts.
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Send the header `name: value` with every request, e.g. for a
+    /// provider's API key.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Run calls against `block`: a tag (`latest`, `safe`, `finalized`,
+    /// `pending`) or a `0x`-prefixed block number.
+    pub fn with_block(mut self, block: impl Into<String>) -> Self {
+        self.block = block.into();
+        self.deployed = OnceLock::new();
+        self
+    }
+
+    /// The verifier contract's address.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Call the contract's `verifyProof` with `proof` and its public inputs.
+    ///
+    /// A rejected proof is not an error: it gives an [`EthVerification`]
+    /// that is not `valid`, with the revert the node reported. Before the
+    /// first call, the address is checked to hold a contract, since a call
+    /// to an account without code succeeds whatever its data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidInput`] if there is no contract at the
+    /// address, and an error if the proof does not encode, the node cannot be
+    /// reached or it answers with an error other than a revert.
+    pub fn verify(&self, proof: &Groth16ProofResult) -> Result<EthVerification> {
+        let _span = enter_span!("verify", eth_call = self.address.as_str());
+        let verify = || {
+            let calldata = proof.solidity_verify_call()?;
+            self.ensure_deployed()?;
+            let call = serde_json::json!({ "to": self.address, "data": to_hex(&calldata) });
+            match self.rpc("eth_call", serde_json::json!([call, self.block]))? {
+                Ok(_) => Ok(EthVerification {
+                    valid: true,
+                    revert_reason: None,
+                    revert_data: Vec::new(),
+                    block: self.block.clone(),
+                }),
+                Err(error) => {
+                    let message = error["message"].as_str().unwrap_or_default();
+                    // Geth and most providers answer a revert with code 3 and
+                    // the revert data; some only say so in the message.
+                    if error["code"] != 3 && !message.contains("revert") {
+                        bail!("eth_call failed: {error}");
+                    }
+                    let revert_data = match error.get("data") {
+                        Some(serde_json::Value::String(data)) => from_hex(data)?,
+                        _ => Vec::new(),
+                    };
+                    Ok(EthVerification {
+                        valid: false,
+                        revert_reason: Some(message.to_owned()),
+                        revert_data,
+                        block: self.block.clone(),
+                    })
+                }
+            }
+        };
+        crate::trace::timed(|| {
+            metrics::record_verify_with(Scheme::Groth16, true, verify, |v| v.valid)
+        })
+    }
+
+    fn ensure_deployed(&self) -> Result<()> {
+        if self.deployed.get().is_some() {
+            return Ok(());
+        }
+        let code = match self.rpc("eth_getCode", serde_json::json!([self.address, self.block]))? {
+            Ok(code) => code,
+            Err(error) => bail!("eth_getCode failed: {error}"),
+        };
+        if matches!(code.as_str(), None | Some("0x") | Some("")) {
+            return Err(GnarkError::InvalidInput(format!(
+                "there is no contract at {} in block {}",
+                self.address, self.block
+            ))
+            .into());
+        }
+        let _ = self.deployed.set(());
+        Ok(())
+    }
+
+    /// Send one JSON-RPC request, returning its `result`, or its `error`
+    /// object as `Err`.
+    fn rpc(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Result<serde_json::Value, serde_json::Value>> {
+        let body =
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut request = self
+            .agent
+            .post(&self.rpc_url)
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        let response = match request.send_string(&body.to_string()) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                bail!("{method}: HTTP {status}: {message}");
+            }
+            Err(err @ ureq::Error::Transport(_)) => {
+                return Err(anyhow!(err).context("failed to reach the JSON-RPC endpoint"));
+            }
+        };
+        let mut response: serde_json::Value = serde_json::from_str(
+            &response
+                .into_string()
+                .context("failed to read the JSON-RPC response")?,
+        )
+        .context("the JSON-RPC endpoint did not answer with JSON")?;
+        if let Some(error) = response.get_mut("error") {
+            return Ok(Err(error.take()));
+        }
+        match response.get_mut("result") {
+            Some(result) => Ok(Ok(result.take())),
+            None => bail!("the {method} response has neither a result nor an error: {response}"),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + 2 * bytes.len());
+    out.push_str("0x");
+    for b in bytes {
+        out.push_str(&format!("{b:02x}"));
+    }
+    out
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    ensure!(
+        s.len().is_multiple_of(2) && s.is_ascii(),
+        "malformed hex in the JSON-RPC response"
+    );
+    (0..s.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&s[i..i + 2], 16)?))
+        .collect()
+}
@@ -0,0 +1,98 @@
+//! Witness values supplied while the solver runs.
+//!
+//! [`crate::CircuitBuilder::feed`] declares values that are not part of the
+//! witness JSON. When the solver reaches one, the Go side calls back into
+//! Rust, and the [`WitnessFeeder`] given to the prove call writes them, so
+//! values derived from large datasets can be produced (or read from disk) as
+//! they are needed instead of all being assembled into one buffer up front.
+
+use anyhow::Result;
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+use crate::Fr;
+
+/// Supplies the values of a circuit's feeds during witness solving.
+///
+/// The solver may ask for several feeds at once from different threads, and
+/// in any order. Implemented for any `Fn(u64, &mut [Fr]) -> Result<()> + Send +
+/// Sync` closure.
+pub trait WitnessFeeder: Send + Sync {
+    /// Fill `out` with the values of the feed declared with id `id`.
+    ///
+    /// # Errors
+    ///
+    /// An error fails the proof and is returned from the prove call.
+    fn feed(&self, id: u64, out: &mut [Fr]) -> Result<()>;
+}
+
+impl<F> WitnessFeeder for F
+where
+    F: Fn(u64, &mut [Fr]) -> Result<()> + Send + Sync,
+{
+    fn feed(&self, id: u64, out: &mut [Fr]) -> Result<()> {
+        self(id, out)
+    }
+}
+
+/// What the trampoline's `user_data` points to for the duration of a proof.
+pub(crate) struct FeedContext<'a> {
+    pub(crate) feeder: &'a dyn WitnessFeeder,
+    /// The first error the feeder returned, reported in place of the solver
+    /// error it causes.
+    pub(crate) error: Mutex<Option<anyhow::Error>>,
+}
+
+impl<'a> FeedContext<'a> {
+    pub(crate) fn new(feeder: &'a dyn WitnessFeeder) -> Self {
+        Self {
+            feeder,
+            error: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn take_error(&self) -> Option<anyhow::Error> {
+        self.error.lock().expect("feed error poisoned").take()
+    }
+}
+
+/// C trampoline for `gnark_feed_cb`.
+///
+/// `user_data` must point to a [`FeedContext`] that outlives the prove call,
+/// and `out` to `n` 32-byte slots.
+pub(crate) unsafe extern "C" fn feed_trampoline(
+    user_data: *mut c_void,
+    feed_id: u64,
+    out: *mut u8,
+    n: u64,
+) -> c_int {
+    let context = &*(user_data as *const FeedContext<'_>);
+    let Ok(n) = usize::try_from(n) else {
+        return 1;
+    };
+    // Unwinding into Go would abort the process.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut values = vec![Fr::from(0); n];
+        context.feeder.feed(feed_id, &mut values)?;
+        let out = std::slice::from_raw_parts_mut(out, 32 * n);
+        for (slot, value) in out.chunks_exact_mut(32).zip(&values) {
+            slot.copy_from_slice(&value.to_bytes_be());
+        }
+        Ok(())
+    }))
+    .unwrap_or_else(|_| {
+        Err(anyhow::anyhow!(
+            "the witness feeder panicked on feed {feed_id}"
+        ))
+    });
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            let mut error = context.error.lock().expect("feed error poisoned");
+            error.get_or_insert(err);
+            1
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! BN254 field elements.
+//!
+//! [`Fr`] (the scalar field, which circuits compute over) and [`Fq`] (the base
+//! field, holding point coordinates) are pure-Rust Montgomery-form elements,
+//! laid out like gnark-crypto's `fr.Element` / `fp.Element`: four
+//! little-endian 64-bit limbs of `x * 2^256 mod p`. Byte encodings are 32
+//! bytes and canonical; big-endian is gnark's `Element.Bytes()` and the
+//! [`crate::hash::FieldBytes`] layout used by the FFI.
+//!
+//! ```
+//! use rust_gnark::Fr;
+//!
+//! let x: Fr = "3".parse()?;
+//! let y = x * x * x + x + Fr::from(5u64);
+//! assert_eq!(y.to_string(), "35");
+//! assert_eq!(-Fr::ONE, Fr::from_str_radix("-1", 10)?);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::{anyhow, ensure, Result};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// `a < b` on little-endian limbs.
+fn lt(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// `a - b` modulo 2^256.
+fn sub(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    let mut borrow = false;
+    for (i, limb) in out.iter_mut().enumerate() {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(u64::from(borrow));
+        *limb = d;
+        borrow = b1 || b2;
+    }
+    out
+}
+
+/// `a + b mod p` for `a, b < p < 2^255`.
+fn add_mod(a: &[u64; 4], b: &[u64; 4], p: &[u64; 4]) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    let mut carry = 0u64;
+    for (i, limb) in out.iter_mut().enumerate() {
+        let t = u128::from(a[i]) + u128::from(b[i]) + u128::from(carry);
+        *limb = t as u64;
+        carry = (t >> 64) as u64;
+    }
+    if lt(&out, p) {
+        out
+    } else {
+        sub(&out, p)
+    }
+}
+
+/// `a + b * c + carry`, as (low, high) words.
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = u128::from(a) + u128::from(b) * u128::from(c) + u128::from(carry);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Montgomery product `a * b / 2^256 mod p` (CIOS), where `inv = -p^-1 mod 2^64`.
+fn mont_mul(a: &[u64; 4], b: &[u64; 4], p: &[u64; 4], inv: u64) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for &bi in b {
+        let mut carry = 0;
+        for j in 0..4 {
+            (t[j], carry) = mac(t[j], a[j], bi, carry);
+        }
+        (t[4], t[5]) = mac(t[4], 1, carry, 0);
+
+        let m = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], m, p[0], 0);
+        for j in 1..4 {
+            (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+        }
+        (t[3], carry) = mac(t[4], 1, carry, 0);
+        t[4] = t[5] + carry;
+    }
+    let out = [t[0], t[1], t[2], t[3]];
+    if t[4] != 0 || !lt(&out, p) {
+        sub(&out, p)
+    } else {
+        out
+    }
+}
+
+/// Decimal digits of a little-endian 256-bit integer.
+fn to_decimal(mut limbs: [u64; 4]) -> String {
+    const CHUNK: u64 = 10_000_000_000_000_000_000;
+    let mut chunks = Vec::new();
+    while limbs != [0; 4] {
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let cur = rem << 64 | u128::from(*limb);
+            *limb = (cur / u128::from(CHUNK)) as u64;
+            rem = cur % u128::from(CHUNK);
+        }
+        chunks.push(rem as u64);
+    }
+    match chunks.split_last() {
+        None => "0".to_owned(),
+        Some((top, rest)) => {
+            let mut out = top.to_string();
+            for chunk in rest.iter().rev() {
+                out.push_str(&format!("{chunk:019}"));
+            }
+            out
+        }
+    }
+}
+
+macro_rules! prime_field {
+    (
+        $(#[$doc:meta])*
+        $name:ident, modulus: $modulus:expr, inv: $inv:expr, r: $r:expr, r2: $r2:expr
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct $name([u64; 4]);
+
+        impl $name {
+            /// The modulus, as little-endian 64-bit limbs.
+            pub const MODULUS: [u64; 4] = $modulus;
+            const INV: u64 = $inv;
+            /// `2^512 mod p`: the Montgomery form of `2^256`.
+            const R2: [u64; 4] = $r2;
+
+            /// Zero.
+            pub const ZERO: Self = Self([0; 4]);
+            /// One.
+            pub const ONE: Self = Self($r);
+
+            /// Map a little-endian 256-bit integer to the element it is
+            /// congruent to.
+            fn reduce(limbs: [u64; 4]) -> Self {
+                Self(mont_mul(&limbs, &Self::R2, &Self::MODULUS, Self::INV))
+            }
+
+            /// The element with canonical (reduced, non-Montgomery) limbs
+            /// `limbs`, or `None` if they are not less than the modulus.
+            pub fn from_limbs(limbs: [u64; 4]) -> Option<Self> {
+                lt(&limbs, &Self::MODULUS).then(|| Self::reduce(limbs))
+            }
+
+            /// The canonical (reduced, non-Montgomery) little-endian limbs.
+            pub fn to_limbs(self) -> [u64; 4] {
+                mont_mul(&self.0, &[1, 0, 0, 0], &Self::MODULUS, Self::INV)
+            }
+
+            /// The element whose Montgomery form is `limbs`, as gnark-crypto
+            /// stores it, or `None` if they are not less than the modulus.
+            pub fn from_montgomery(limbs: [u64; 4]) -> Option<Self> {
+                lt(&limbs, &Self::MODULUS).then_some(Self(limbs))
+            }
+
+            /// The Montgomery form `x * 2^256 mod p`, gnark-crypto's in-memory
+            /// representation.
+            pub fn to_montgomery(self) -> [u64; 4] {
+                self.0
+            }
+
+            /// Decode the 32-byte big-endian canonical encoding (gnark's
+            /// `Element.Bytes()`).
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the value is not less than the modulus.
+            pub fn from_bytes_be(bytes: &[u8; 32]) -> Result<Self> {
+                let mut limbs = [0u64; 4];
+                for (i, chunk) in bytes.chunks(8).enumerate() {
+                    limbs[3 - i] = u64::from_be_bytes(chunk.try_into().expect("8-byte chunk"));
+                }
+                Self::from_limbs(limbs).ok_or_else(|| {
+                    anyhow!(concat!("not a canonical ", stringify!($name), " element"))
+                })
+            }
+
+            /// Decode the 32-byte little-endian canonical encoding.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the value is not less than the modulus.
+            pub fn from_bytes_le(bytes: &[u8; 32]) -> Result<Self> {
+                let mut be = *bytes;
+                be.reverse();
+                Self::from_bytes_be(&be)
+            }
+
+            /// The 32-byte big-endian canonical encoding.
+            pub fn to_bytes_be(self) -> [u8; 32] {
+                let mut out = [0u8; 32];
+                for (i, limb) in self.to_limbs().iter().rev().enumerate() {
+                    out[8 * i..8 * i + 8].copy_from_slice(&limb.to_be_bytes());
+                }
+                out
+            }
+
+            /// The 32-byte little-endian canonical encoding.
+            pub fn to_bytes_le(self) -> [u8; 32] {
+                let mut out = self.to_bytes_be();
+                out.reverse();
+                out
+            }
+
+            /// Parse an integer in `radix` (2 to 36), optionally negative and of
+            /// any size, reduced modulo the field.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `s` is empty or has a digit outside `radix`.
+            pub fn from_str_radix(s: &str, radix: u32) -> Result<Self> {
+                ensure!((2..=36).contains(&radix), "radix {radix} is not in 2..=36");
+                let (negative, digits) = match s.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, s),
+                };
+                let value = Self::from_digits(digits, radix)
+                    .ok_or_else(|| anyhow!("{s:?} is not an integer"))?;
+                Ok(if negative { -value } else { value })
+            }
+
+            /// The non-empty, unsigned digits `s` in `radix`, reduced.
+            fn from_digits(s: &str, radix: u32) -> Option<Self> {
+                if s.is_empty() {
+                    return None;
+                }
+                let base = Self::from(u64::from(radix));
+                let mut acc = Self::ZERO;
+                for c in s.chars() {
+                    acc = acc * base + Self::from(u64::from(c.to_digit(radix)?));
+                }
+                Some(acc)
+            }
+
+            /// A uniformly random element, from the operating system's RNG.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the RNG is unavailable.
+            pub fn random() -> Result<Self> {
+                let mut buf = [0u8; 64];
+                getrandom::getrandom(&mut buf)
+                    .map_err(|e| anyhow!("failed to sample random bytes: {e}"))?;
+                // 512 random bits reduced mod p: lo + hi * 2^256.
+                let limbs = |b: &[u8]| -> [u64; 4] {
+                    std::array::from_fn(|i| {
+                        u64::from_le_bytes(b[8 * i..8 * i + 8].try_into().expect("8-byte chunk"))
+                    })
+                };
+                let lo = Self::reduce(limbs(&buf[..32]));
+                let hi = Self::reduce(limbs(&buf[32..]));
+                Ok(lo + hi * Self(Self::R2))
+            }
+
+            /// Whether this is zero.
+            pub fn is_zero(self) -> bool {
+                self == Self::ZERO
+            }
+
+            /// `self^2`.
+            pub fn square(self) -> Self {
+                self * self
+            }
+
+            /// `self^exp`, with `exp` as little-endian 64-bit limbs.
+            pub fn pow(self, exp: &[u64]) -> Self {
+                let mut acc = Self::ONE;
+                for limb in exp.iter().rev() {
+                    for bit in (0..64).rev() {
+                        acc = acc.square();
+                        if limb >> bit & 1 == 1 {
+                            acc *= self;
+                        }
+                    }
+                }
+                acc
+            }
+
+            /// The multiplicative inverse, or `None` for zero.
+            pub fn inverse(self) -> Option<Self> {
+                if self.is_zero() {
+                    return None;
+                }
+                // Fermat: x^(p - 2).
+                Some(self.pow(&sub(&Self::MODULUS, &[2, 0, 0, 0])))
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(v: u64) -> Self {
+                Self::reduce([v, 0, 0, 0])
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = anyhow::Error;
+
+            /// Parse a decimal or `0x`-prefixed hexadecimal integer, optionally
+            /// negative, reduced modulo the field (as [`crate::Witness`] values).
+            fn from_str(s: &str) -> Result<Self> {
+                let s = s.trim();
+                let (negative, rest) = match s.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, s),
+                };
+                // One sign at most: the digits themselves are unsigned.
+                let value = match rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+                    Some(hex) => Self::from_digits(hex, 16),
+                    None => Self::from_digits(rest, 10),
+                }
+                .ok_or_else(|| anyhow!("{s:?} is not an integer"))?;
+                Ok(if negative { -value } else { value })
+            }
+        }
+
+        /// Decimal.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&to_decimal(self.to_limbs()))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!(stringify!($name), "({})"), self)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(add_mod(&self.0, &rhs.0, &Self::MODULUS))
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self + -rhs
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                Self(mont_mul(&self.0, &rhs.0, &Self::MODULUS, Self::INV))
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                if self.is_zero() {
+                    self
+                } else {
+                    Self(sub(&Self::MODULUS, &self.0))
+                }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+
+prime_field! {
+    /// An element of the BN254 scalar field `Fr`, the field circuits,
+    /// witnesses and public inputs live in.
+    Fr,
+    modulus: [0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029],
+    inv: 0xc2e1f593efffffff,
+    r: [0xac96341c4ffffffb, 0x36fc76959f60cd29, 0x666ea36f7879462e, 0x0e0a77c19a07df2f],
+    r2: [0x1bb8e645ae216da7, 0x53fe3ab1e35c59e3, 0x8c49833d53bb8085, 0x0216d0b17f4e44a5]
+}
+
+prime_field! {
+    /// An element of the BN254 base field `Fq`, the field of G1 coordinates.
+    Fq,
+    modulus: [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029],
+    inv: 0x87d20782e4866389,
+    r: [0xd35d438dc58f0d9d, 0x0a78eb28f5c70b3d, 0x666ea36f7879462c, 0x0e0a77c19a07df2f],
+    r2: [0xf32cfc5b538afa89, 0xb5e71911d44501fb, 0x47ab1eff0a417ff6, 0x06d89f71cab8351f]
+}
+
+/// Conversions to and from arkworks, which use the same moduli.
+#[cfg(feature = "native-verify")]
+mod ark_conv {
+    use ark_ff::{BigInt, PrimeField};
+
+    macro_rules! ark_field {
+        ($name:ident, $ark:ty) => {
+            impl From<super::$name> for $ark {
+                fn from(x: super::$name) -> Self {
+                    <$ark>::from_bigint(BigInt(x.to_limbs())).expect("canonical limbs")
+                }
+            }
+
+            impl From<$ark> for super::$name {
+                fn from(x: $ark) -> Self {
+                    super::$name::from_limbs(x.into_bigint().0).expect("canonical limbs")
+                }
+            }
+        };
+    }
+
+    ark_field!(Fr, ark_bn254::Fr);
+    ark_field!(Fq, ark_bn254::Fq);
+}
@@ -0,0 +1,134 @@
+//! Circuits written in Go and compiled at runtime with the `go` toolchain.
+
+use anyhow::{bail, Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{trace, CompiledCircuit, GNARK_CRYPTO_VERSION, GNARK_VERSION};
+
+/// The `main` added next to the circuit source: compiles `Circuit` to a BN254
+/// R1CS and writes it to the path given as the only argument.
+const MAIN_GO: &str = r#"package main
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+)
+
+func main() {
+	cs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, &Circuit{})
+	if err != nil {
+		fmt.Fprintln(os.Stderr, "compile circuit:", err)
+		os.Exit(1)
+	}
+	f, err := os.Create(os.Args[1])
+	if err != nil {
+		fmt.Fprintln(os.Stderr, err)
+		os.Exit(1)
+	}
+	defer f.Close()
+	if _, err := cs.WriteTo(f); err != nil {
+		fmt.Fprintln(os.Stderr, "write r1cs:", err)
+		os.Exit(1)
+	}
+}
+"#;
+
+impl CompiledCircuit {
+    /// Compile a gnark circuit from Go source, for iterating on a circuit
+    /// without rebuilding the crate.
+    ///
+    /// `source` is a Go file in `package main` that defines a `Circuit` type
+    /// implementing `frontend.Circuit` (and no `main` function). It is built
+    /// with the `go` toolchain on `PATH` into a module under the system temp
+    /// directory, keyed by the source, so recompiling unchanged source reuses
+    /// the previous R1CS. Use [`CompiledCircuit::groth16_setup`] for keys.
+    ///
+    /// ```no_run
+    /// # fn example() -> anyhow::Result<()> {
+    /// let circuit = rust_gnark::CompiledCircuit::compile_from_go(r#"
+    /// package main
+    ///
+    /// import "github.com/consensys/gnark/frontend"
+    ///
+    /// type Circuit struct {
+    ///     X frontend.Variable
+    ///     Y frontend.Variable `gnark:",public"`
+    /// }
+    ///
+    /// func (c *Circuit) Define(api frontend.API) error {
+    ///     api.AssertIsEqual(api.Add(api.Mul(c.X, c.X, c.X), c.X, 5), c.Y)
+    ///     return nil
+    /// }
+    /// "#)?;
+    /// circuit.groth16_setup("circuit.pk", "circuit.vk")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `go` cannot be run, or with the toolchain's output
+    /// if the source does not build or the circuit does not compile.
+    pub fn compile_from_go(source: &str) -> Result<Self> {
+        let _span = enter_span!("compile_from_go");
+        trace::timed(|| {
+            let dir = module_dir(source);
+            let r1cs = dir.join("circuit.r1cs");
+            if !r1cs.exists() {
+                build(&dir, source, &r1cs)?;
+            }
+            Self::read(r1cs.to_str().context("temp directory is not valid UTF-8")?)
+        })
+    }
+}
+
+fn module_dir(source: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    (source, GNARK_VERSION, GNARK_CRYPTO_VERSION).hash(&mut hasher);
+    std::env::temp_dir()
+        .join("rust-gnark-go-circuits")
+        .join(format!("{:016x}", hasher.finish()))
+}
+
+fn build(dir: &Path, source: &str, r1cs: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    std::fs::write(
+        dir.join("go.mod"),
+        format!(
+            "module rustgnarkcircuit\n\ngo 1.24.0\n\nrequire (\n\
+             \tgithub.com/consensys/gnark {GNARK_VERSION}\n\
+             \tgithub.com/consensys/gnark-crypto {GNARK_CRYPTO_VERSION}\n)\n"
+        ),
+    )?;
+    std::fs::write(dir.join("circuit.go"), source)?;
+    std::fs::write(dir.join("rust_gnark_main.go"), MAIN_GO)?;
+
+    go(dir, &["mod", "tidy"])?;
+    // Write to a temporary name so an interrupted build is not cached.
+    go(dir, &["run", ".", "circuit.r1cs.partial"])?;
+    std::fs::rename(dir.join("circuit.r1cs.partial"), r1cs)?;
+    Ok(())
+}
+
+fn go(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("go")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("failed to run `go` (is the Go toolchain installed and on PATH?)")?;
+    if !output.status.success() {
+        bail!(
+            "`go {}` failed:\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        );
+    }
+    Ok(())
+}
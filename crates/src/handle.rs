@@ -0,0 +1,65 @@
+//! Owned references to objects living in the Go-side handle registry.
+//!
+//! # Thread safety
+//!
+//! The registry itself is guarded by a mutex on the Go side, and a `Handle` is
+//! freed only when its owner drops it, so no call can race with the release.
+//! Wrappers hand out `&self` only for operations that read the Go object
+//! (proving, verifying, serializing), which gnark supports concurrently;
+//! operations that mutate it (e.g. ceremony contributions) take `&mut self`.
+//! Handle types are therefore `Send + Sync` without further locking, which is
+//! checked at compile time below. The one Go-side exception, GPU proving, is
+//! serialized in `go/gpu_icicle.go`.
+
+use crate::bind;
+
+/// An id into the Go handle registry, released on drop.
+#[derive(Debug)]
+pub(crate) struct Handle(u64);
+
+impl Handle {
+    /// Take ownership of a handle returned by the Go side.
+    pub(crate) fn from_raw(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe { bind::gnark_handle_free(self.0) };
+    }
+}
+
+/// Types wrapping Go objects, which must stay shareable across threads.
+#[allow(dead_code)]
+fn assert_send_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<Handle>();
+    check::<crate::GnarkBuffer>();
+    check::<crate::VerifyingKey>();
+    #[cfg(not(feature = "verifier"))]
+    {
+        check::<crate::CancelToken>();
+        check::<crate::CircuitRegistry>();
+        check::<crate::CompiledCircuit>();
+        check::<crate::ProverSession>();
+        check::<crate::ProvingKey>();
+        check::<crate::Srs>();
+        check::<crate::Transcript>();
+        check::<crate::Kzg>();
+        check::<crate::PlonkProvingKey>();
+        check::<crate::PlonkVerifyingKey>();
+    }
+    #[cfg(all(feature = "bw6-761", not(feature = "verifier")))]
+    check::<crate::Aggregator>();
+    #[cfg(all(feature = "ceremony", not(feature = "verifier")))]
+    {
+        check::<crate::ceremony::Phase1>();
+        check::<crate::ceremony::Phase2>();
+        check::<crate::ceremony::SrsCommons>();
+    }
+}
@@ -0,0 +1,124 @@
+//! Host-side hashes matching gnark's in-circuit gadgets.
+//!
+//! Circuits often commit to their inputs with a SNARK-friendly hash; the host
+//! has to compute the same digest to fill in the witness. These functions call
+//! the gnark-crypto implementations that gnark's `std/hash` gadgets mirror, so
+//! parameters (round constants, padding, state width) cannot drift apart.
+//!
+//! Field elements are passed as 32-byte big-endian canonical encodings, the
+//! same layout as [`crate::native::fr_to_gnark_bytes`].
+//!
+//! There is no Pedersen hash: gnark's std library has no Pedersen hash gadget
+//! (`std/commitments/pedersen` is a vector commitment whose bases come from a
+//! setup), so no host-side Pedersen hash could match a gnark circuit.
+//!
+//! The verifier build (the `verifier` feature) has no hashes; only [`Curve`]
+//! is available.
+
+#[cfg(not(feature = "verifier"))]
+use anyhow::Result;
+#[cfg(not(feature = "verifier"))]
+use std::os::raw::{c_char, c_int};
+
+#[cfg(not(feature = "verifier"))]
+use crate::{bind, GnarkError};
+
+/// A field element as 32 big-endian bytes, canonical (less than the modulus).
+pub type FieldBytes = [u8; 32];
+
+/// Curve whose scalar field a hash (or an [`crate::ecc`] primitive) is computed
+/// over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Curve {
+    /// BN254 (alt_bn128), the curve of this crate's provers.
+    Bn254,
+    /// BLS12-381. Requires the `bls12-381` feature.
+    Bls12_381,
+}
+
+#[cfg(not(feature = "verifier"))]
+impl Curve {
+    /// The id the Go exports expect.
+    pub(crate) fn as_raw(self) -> c_int {
+        match self {
+            Curve::Bn254 => 0,
+            Curve::Bls12_381 => 1,
+        }
+    }
+}
+
+/// Poseidon2 hash of `inputs` over the scalar field of `curve`.
+///
+/// Uses gnark-crypto's Merkle-Damgård Poseidon2 hasher with its default
+/// parameters, which is what gnark's `std/hash/poseidon2` computes in-circuit.
+/// gnark ships no original (2019) Poseidon; digests from circomlib's Poseidon
+/// are different.
+///
+/// # Errors
+///
+/// Returns an error if an input is not a canonical field element.
+#[cfg(not(feature = "verifier"))]
+pub fn poseidon2(curve: Curve, inputs: &[FieldBytes]) -> Result<FieldBytes> {
+    hash_with(bind::gnark_poseidon2, curve, inputs)
+}
+
+/// MiMC hash of `inputs` over the scalar field of `curve`.
+///
+/// gnark-crypto's Miyaguchi-Preneel MiMC with its default round constants,
+/// which is what gnark's `std/hash/mimc` computes in-circuit.
+///
+/// # Errors
+///
+/// Returns an error if an input is not a canonical field element.
+#[cfg(not(feature = "verifier"))]
+pub fn mimc(curve: Curve, inputs: &[FieldBytes]) -> Result<FieldBytes> {
+    hash_with(bind::gnark_mimc, curve, inputs)
+}
+
+/// Call a Go hash export with the layout shared by all of them.
+#[cfg(not(feature = "verifier"))]
+fn hash_with(
+    export: unsafe extern "C" fn(c_int, *mut u8, u64, *mut u8) -> *mut c_char,
+    curve: Curve,
+    inputs: &[FieldBytes],
+) -> Result<FieldBytes> {
+    let mut out = [0u8; 32];
+    let err_ptr = unsafe {
+        export(
+            curve.as_raw(),
+            inputs.as_ptr() as *mut u8,
+            inputs.len() as u64,
+            out.as_mut_ptr(),
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(out)
+}
+
+/// [`poseidon2`] over BN254 on `ark_bn254::Fr` values.
+///
+/// # Errors
+///
+/// Never fails for in-range elements; errors only surface FFI failures.
+#[cfg(all(feature = "native-verify", not(feature = "verifier")))]
+pub fn poseidon2_bn254(inputs: &[ark_bn254::Fr]) -> Result<ark_bn254::Fr> {
+    use crate::native::{fr_from_gnark_bytes, fr_to_gnark_bytes};
+
+    let inputs: Vec<FieldBytes> = inputs.iter().map(fr_to_gnark_bytes).collect();
+    fr_from_gnark_bytes(&poseidon2(Curve::Bn254, &inputs)?)
+}
+
+/// [`mimc`] over BN254 on `ark_bn254::Fr` values.
+///
+/// # Errors
+///
+/// Never fails for in-range elements; errors only surface FFI failures.
+#[cfg(all(feature = "native-verify", not(feature = "verifier")))]
+pub fn mimc_bn254(inputs: &[ark_bn254::Fr]) -> Result<ark_bn254::Fr> {
+    use crate::native::{fr_from_gnark_bytes, fr_to_gnark_bytes};
+
+    let inputs: Vec<FieldBytes> = inputs.iter().map(fr_to_gnark_bytes).collect();
+    fr_from_gnark_bytes(&mimc(Curve::Bn254, &inputs)?)
+}
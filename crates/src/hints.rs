@@ -0,0 +1,134 @@
+//! Hints implemented in Rust.
+//!
+//! [`register_hint`] stores a closure under an id; [`crate::Hint::Rust`]
+//! calls it from a [`crate::CircuitBuilder`] circuit. While the witness is
+//! solved the Go solver calls back into Rust with the hint's inputs, so
+//! out-of-circuit computations that gnark has no hint for (square roots,
+//! lookups, solving for a preimage) need no Go code.
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::{bind, Fr};
+
+type HintFn = dyn Fn(&[Fr], &mut [Fr]) -> Result<()> + Send + Sync;
+
+fn registry() -> &'static RwLock<HashMap<u64, Arc<HintFn>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<u64, Arc<HintFn>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        unsafe { bind::gnark_set_hint_callback(Some(hint_trampoline)) };
+        RwLock::new(HashMap::new())
+    })
+}
+
+/// Register `hint` under `id`, for circuits that use [`crate::Hint::Rust`]
+/// with that id, replacing any hint already registered under it.
+///
+/// The hint is given the values of its inputs and fills in its outputs, which
+/// start out as zero. Its outputs are unconstrained, like those of every
+/// hint: the circuit must check them.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use rust_gnark::{CircuitBuilder, Fr, Hint};
+///
+/// // Out of circuit: halve a value (in the field, so odd values work too).
+/// rust_gnark::register_hint(7, |inputs: &[Fr], outputs: &mut [Fr]| {
+///     outputs[0] = inputs[0] * Fr::from(2).inverse().expect("2 is invertible");
+///     Ok(())
+/// });
+///
+/// // In circuit: check the result by doubling it back.
+/// let mut b = CircuitBuilder::new();
+/// let x = b.public_input("X");
+/// let half = b.hint(Hint::Rust { id: 7, outputs: 1 }, &[x])[0];
+/// let doubled = b.add(half, half);
+/// b.assert_eq(doubled, x);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The solver may call the hint from several threads at once. An error fails
+/// the proof (as [`crate::GnarkError::InvalidWitness`]) with its message.
+pub fn register_hint(
+    id: u64,
+    hint: impl Fn(&[Fr], &mut [Fr]) -> Result<()> + Send + Sync + 'static,
+) {
+    registry()
+        .write()
+        .expect("hint registry poisoned")
+        .insert(id, Arc::new(hint));
+}
+
+/// Remove the hint registered under `id`, returning whether there was one.
+///
+/// Proofs of circuits that use it fail from then on.
+pub fn unregister_hint(id: u64) -> bool {
+    registry()
+        .write()
+        .expect("hint registry poisoned")
+        .remove(&id)
+        .is_some()
+}
+
+/// Run the hint registered under `id` on 32-byte big-endian values.
+fn call_hint(id: u64, inputs: &[u8], outputs: &mut [u8]) -> Result<()> {
+    // Clone out of the registry so a slow hint doesn't block registration.
+    let hint = registry()
+        .read()
+        .expect("hint registry poisoned")
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| anyhow!("no Rust hint is registered with id {id}"))?;
+    let inputs = inputs
+        .chunks_exact(32)
+        .map(|bytes| Fr::from_bytes_be(bytes.try_into().expect("32-byte chunk")))
+        .collect::<Result<Vec<_>>>()?;
+    let mut values = vec![Fr::from(0); outputs.len() / 32];
+    hint(&inputs, &mut values)?;
+    for (slot, value) in outputs.chunks_exact_mut(32).zip(&values) {
+        slot.copy_from_slice(&value.to_bytes_be());
+    }
+    Ok(())
+}
+
+/// C trampoline for `gnark_hint_cb`.
+///
+/// `inputs` must point to `n_in` and `outputs` to `n_out` 32-byte slots, and
+/// `err` to `err_cap` writable bytes.
+unsafe extern "C" fn hint_trampoline(
+    id: u64,
+    inputs: *const u8,
+    n_in: u64,
+    outputs: *mut u8,
+    n_out: u64,
+    err: *mut c_char,
+    err_cap: u64,
+) -> c_int {
+    let (Ok(n_in), Ok(n_out)) = (usize::try_from(n_in), usize::try_from(n_out)) else {
+        return 1;
+    };
+    let inputs = std::slice::from_raw_parts(inputs, 32 * n_in);
+    let outputs = std::slice::from_raw_parts_mut(outputs, 32 * n_out);
+    // Unwinding into Go would abort the process.
+    let result = catch_unwind(AssertUnwindSafe(|| call_hint(id, inputs, outputs)))
+        .unwrap_or_else(|_| Err(anyhow!("the hint panicked")));
+    let Err(e) = result else {
+        return 0;
+    };
+    let message = format!("{e:#}");
+    let cap = usize::try_from(err_cap).unwrap_or(usize::MAX);
+    if cap > 0 {
+        // Truncate to fit the NUL terminator, on a character boundary.
+        let mut len = message.len().min(cap - 1);
+        while !message.is_char_boundary(len) {
+            len -= 1;
+        }
+        std::ptr::copy_nonoverlapping(message.as_ptr(), err.cast::<u8>(), len);
+        *err.add(len) = 0;
+    }
+    1
+}
@@ -0,0 +1,648 @@
+//! Proving and verifying keys held in Go memory.
+
+use anyhow::{anyhow, Context, Result};
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use crate::buffer::GnarkBuffer;
+use crate::handle::Handle;
+use crate::metrics::{self, Scheme};
+use crate::{
+    bind, GnarkError, Groth16ProofResult, HashFunction, SerializationFormat, VerifierConfig,
+};
+
+/// A deserialized Groth16 BN254 proving key owned by the Go runtime.
+///
+/// Loading a key once and passing it to [`crate::groth16_prove_with_key`]
+/// avoids re-reading and re-deserializing the `.pk` file on every proof. The
+/// key is released when this value is dropped.
+#[cfg(not(feature = "verifier"))]
+#[derive(Debug)]
+pub struct ProvingKey {
+    handle: Handle,
+}
+
+#[cfg(not(feature = "verifier"))]
+impl ProvingKey {
+    /// Memory-map the `.pk` file at `path` and deserialize it on the Go side.
+    ///
+    /// The file is read straight from the mapping (via `UnsafeReadFrom`, so it
+    /// must be a trusted key written with `WriteRawTo`); no copy of the file is
+    /// made in Rust or Go heap memory, which roughly halves peak memory
+    /// compared to reading the file into a buffer first. On platforms without
+    /// `mmap` the file is read normally.
+    ///
+    /// `path` may also be a directory written by
+    /// [`ProvingKey::save_segmented`]: its segments are checked against their
+    /// manifest, mapped and read as one file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be mapped or is not a valid proving key.
+    pub fn open_mmap(path: &str) -> Result<Self> {
+        Self::open_mmap_with_format(path, SerializationFormat::RawUnchecked)
+    }
+
+    /// Like [`ProvingKey::open_mmap`], but reading the file in `format`.
+    ///
+    /// [`SerializationFormat::Compressed`] and [`SerializationFormat::Raw`]
+    /// validate every point, which is slower but safe for keys from
+    /// untrusted sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be mapped or is not a valid proving key.
+    pub fn open_mmap_with_format(path: &str, format: SerializationFormat) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_open_mmap(path.as_ptr() as *mut c_char, format.as_raw(), &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Deserialize a proving key from memory.
+    ///
+    /// The Go side reads `bytes` in place for the duration of the call, without
+    /// copying it first. Like [`ProvingKey::open_mmap`], this expects a trusted
+    /// key written with `WriteRawTo` (or [`ProvingKey::to_bytes`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid proving key.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_format(bytes, SerializationFormat::RawUnchecked)
+    }
+
+    /// Like [`ProvingKey::from_bytes`], but reading `bytes` in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid proving key.
+    pub fn from_bytes_with_format(bytes: &[u8], format: SerializationFormat) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_from_bytes(
+                bytes.as_ptr() as *mut u8,
+                bytes.len() as u64,
+                format.as_raw(),
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Serialize the key in gnark's raw (uncompressed) format.
+    ///
+    /// The bytes are written once, straight into a buffer owned by the
+    /// returned [`GnarkBuffer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<GnarkBuffer> {
+        self.to_bytes_with_format(SerializationFormat::Raw)
+    }
+
+    /// Serialize the key in `format`. A compressed key is about half the size
+    /// of a raw one but much slower to load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes_with_format(&self, format: SerializationFormat) -> Result<GnarkBuffer> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_to_bytes(self.handle.id(), format.as_raw(), &mut data, &mut len)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+    }
+
+    /// Write the key to `path` as a versioned key file, in gnark's raw
+    /// encoding behind a header naming the file format and gnark version.
+    ///
+    /// Unlike the bare `.pk` files gnark writes, [`ProvingKey::load`] rejects
+    /// such a file up front, with an error naming both versions, when it was
+    /// written by a gnark release whose encoding this build cannot read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written; no partial file is
+    /// left behind.
+    pub fn save(&self, path: &str) -> Result<()> {
+        self.save_with_format(path, SerializationFormat::Raw)
+    }
+
+    /// Like [`ProvingKey::save`], but encoding the key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_with_format(&self, path: &str, format: SerializationFormat) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_save(
+                self.handle.id(),
+                path.as_ptr() as *mut c_char,
+                format.as_raw(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Read and validate a key written by [`ProvingKey::save`], streaming it
+    /// from disk. Every point is subgroup-checked, so the file may come from
+    /// anywhere; see [`ProvingKey::load_trusted`] for keys generated locally.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::SerializationError`] if the file is not a
+    /// versioned key file (or of a newer file format), and
+    /// [`GnarkError::InvalidKey`] if it holds a verifying key, was written
+    /// by an incompatible gnark version or does not decode.
+    pub fn load(path: &str) -> Result<Self> {
+        Self::load_with_format(path, SerializationFormat::Raw)
+    }
+
+    /// Like [`ProvingKey::load`], but without subgroup checks (gnark's
+    /// `UnsafeReadFrom`), which take most of the time of loading a large
+    /// key: several times faster, and only for keys this deployment
+    /// generated or checked itself, since invalid points go undetected.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProvingKey::load`], except that invalid points are not
+    /// detected.
+    pub fn load_trusted(path: &str) -> Result<Self> {
+        Self::load_with_format(path, SerializationFormat::RawUnchecked)
+    }
+
+    /// Like [`ProvingKey::load`], but decoding the key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProvingKey::load`].
+    pub fn load_with_format(path: &str, format: SerializationFormat) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_load(path.as_ptr() as *mut c_char, format.as_raw(), &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Like [`ProvingKey::save`], but split into numbered files of at most
+    /// `chunk_size` bytes (`pk.000`, `pk.001`, ...) in the directory `dir`,
+    /// created if needed, for keys over a download or package size limit
+    /// (e.g. iOS' cellular download limit or an APK's). A `manifest` file
+    /// lists the segments with their sizes and SHA-256, and is written last.
+    /// Returns the number of segments.
+    ///
+    /// Read the key back with [`ProvingKey::load_segmented`], or
+    /// [`ProvingKey::open_mmap`] on `dir`, which maps the segments in place.
+    ///
+    /// ```no_run
+    /// # fn example(pk: rust_gnark::ProvingKey) -> anyhow::Result<()> {
+    /// let segments = pk.save_segmented("assets/circuit.pk.d", 100 << 20)?;
+    /// println!("{segments} segments of at most 100 MiB");
+    /// let pk = rust_gnark::ProvingKey::open_mmap("assets/circuit.pk.d")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidInput`] if `chunk_size` is 0, and an
+    /// error if a file cannot be written; the segments written so far are
+    /// removed.
+    pub fn save_segmented(&self, dir: &str, chunk_size: u64) -> Result<usize> {
+        let dir = CString::new(dir)?;
+        let mut segments = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_save_segmented(
+                self.handle.id(),
+                dir.as_ptr() as *mut c_char,
+                chunk_size,
+                SerializationFormat::Raw.as_raw(),
+                &mut segments,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(segments as usize)
+    }
+
+    /// Read and validate a key written by [`ProvingKey::save_segmented`]
+    /// into `dir`. Each segment is memory-mapped and checked against the
+    /// size and SHA-256 the manifest records before the key is decoded from
+    /// the segments in order, and every point is subgroup-checked as by
+    /// [`ProvingKey::load`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidKey`] if a segment is missing, truncated
+    /// or altered, and otherwise the errors of [`ProvingKey::load`].
+    pub fn load_segmented(dir: &str) -> Result<Self> {
+        Self::load_segmented_with_format(dir, SerializationFormat::Raw)
+    }
+
+    /// Like [`ProvingKey::load_segmented`], but decoding the key in `format`;
+    /// [`SerializationFormat::RawUnchecked`] skips the subgroup checks, as
+    /// [`ProvingKey::load_trusted`] does.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProvingKey::load_segmented`].
+    pub fn load_segmented_with_format(dir: &str, format: SerializationFormat) -> Result<Self> {
+        let dir = CString::new(dir)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_pk_load_segmented(
+                dir.as_ptr() as *mut c_char,
+                format.as_raw(),
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// SHA-256 of the key's raw serialized form, computed on the Go side.
+    ///
+    /// The digest does not depend on how the key was loaded, so it can be
+    /// pinned in configuration to catch corrupted or swapped `.pk` files
+    /// before proving.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn fingerprint(&self) -> Result<KeyFingerprint> {
+        let mut out = [0u8; 32];
+        let err_ptr =
+            unsafe { bind::gnark_groth16_pk_fingerprint(self.handle.id(), out.as_mut_ptr()) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(KeyFingerprint(out))
+    }
+
+    pub(crate) fn from_handle(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) fn handle_id(&self) -> u64 {
+        self.handle.id()
+    }
+}
+
+/// A deserialized Groth16 BN254 verifying key owned by the Go runtime.
+#[derive(Debug)]
+pub struct VerifyingKey {
+    handle: Handle,
+}
+
+impl VerifyingKey {
+    /// Read and validate the `.vk` file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid verifying key.
+    pub fn open(path: &str) -> Result<Self> {
+        Self::open_with_format(path, SerializationFormat::Raw)
+    }
+
+    /// Like [`VerifyingKey::open`], but reading the file in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid verifying key.
+    pub fn open_with_format(path: &str, format: SerializationFormat) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_read(path.as_ptr() as *mut c_char, format.as_raw(), &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Deserialize and validate a verifying key from memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid verifying key.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_format(bytes, SerializationFormat::Raw)
+    }
+
+    /// Like [`VerifyingKey::from_bytes`], but reading `bytes` in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid verifying key.
+    pub fn from_bytes_with_format(bytes: &[u8], format: SerializationFormat) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_from_bytes(
+                bytes.as_ptr() as *mut u8,
+                bytes.len() as u64,
+                format.as_raw(),
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Serialize the key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self, format: SerializationFormat) -> Result<GnarkBuffer> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_to_bytes(self.handle.id(), format.as_raw(), &mut data, &mut len)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+    }
+
+    /// Write the key to `path` as a versioned key file: gnark's compressed
+    /// encoding (as in a `.vk` file) behind a header naming the file format
+    /// and gnark version. See [`ProvingKey::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &str) -> Result<()> {
+        self.save_with_format(path, SerializationFormat::Compressed)
+    }
+
+    /// Like [`VerifyingKey::save`], but encoding the key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_with_format(&self, path: &str, format: SerializationFormat) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_save(
+                self.handle.id(),
+                path.as_ptr() as *mut c_char,
+                format.as_raw(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Read and validate a key written by [`VerifyingKey::save`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProvingKey::load`], with the kinds of key swapped.
+    pub fn load(path: &str) -> Result<Self> {
+        Self::load_with_format(path, SerializationFormat::Raw)
+    }
+
+    /// Like [`VerifyingKey::load`], but decoding the key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VerifyingKey::load`].
+    pub fn load_with_format(path: &str, format: SerializationFormat) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_load(path.as_ptr() as *mut c_char, format.as_raw(), &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// The Solidity source of a contract verifying proofs for this key, as
+    /// generated by gnark. Pass it proofs as
+    /// [`crate::Groth16ProofResult::solidity_calldata`] encodes them.
+    ///
+    /// For circuits with Pedersen commitments the contract recomputes the
+    /// commitment hash with SHA-256, so proofs must be generated with
+    /// [`crate::HashFunction::Sha256`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if gnark cannot export the key (e.g. a circuit with
+    /// more commitments than its template supports).
+    pub fn export_solidity(&self) -> Result<String> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_vk_export_solidity(self.handle.id(), &mut data, &mut len)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        let buf = unsafe { GnarkBuffer::from_raw(data, len) };
+        String::from_utf8(buf.to_vec()).context("solidity verifier is not valid UTF-8")
+    }
+
+    /// SHA-256 of the key's raw serialized form, computed on the Go side.
+    ///
+    /// Compressed and raw `.vk` files of the same key have the same
+    /// fingerprint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn fingerprint(&self) -> Result<KeyFingerprint> {
+        let mut out = [0u8; 32];
+        let err_ptr =
+            unsafe { bind::gnark_groth16_vk_fingerprint(self.handle.id(), out.as_mut_ptr()) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(KeyFingerprint(out))
+    }
+
+    /// Prepare the key for verifying many proofs: e(α, β) and the pairing
+    /// precomputation of γ and δ are done once, on the Go side, instead of on
+    /// every verification, like arkworks' `prepare_verifying_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this key.
+    pub fn prepare(&self) -> Result<PreparedVk> {
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_groth16_vk_prepare(self.handle.id(), &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(PreparedVk {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    #[cfg(all(feature = "bw6-761", not(feature = "verifier")))]
+    pub(crate) fn from_handle(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    #[cfg(not(feature = "verifier"))]
+    pub(crate) fn handle_id(&self) -> u64 {
+        self.handle.id()
+    }
+}
+
+/// A verifying key prepared by [`VerifyingKey::prepare`], for servers and
+/// relayers that verify many proofs for one circuit.
+///
+/// It verifies the same proofs as [`crate::groth16_verify`] without reading
+/// any file. For keys with commitments gnark's own verifier runs, so only
+/// reading the key is saved.
+#[derive(Debug)]
+pub struct PreparedVk {
+    handle: Handle,
+}
+
+impl PreparedVk {
+    /// Verify a proof generated with the default hash-to-field function.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the proof is valid, `Ok(false)` if it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof or public inputs fail to decode.
+    pub fn verify(&self, proof: &Groth16ProofResult) -> Result<bool> {
+        self.verify_with_hash(proof, HashFunction::Default)
+    }
+
+    /// Like [`PreparedVk::verify`], for a proof generated with `hash` (see
+    /// [`crate::ProverConfig::with_hash_function`]).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`PreparedVk::verify`].
+    pub fn verify_with_hash(&self, proof: &Groth16ProofResult, hash: HashFunction) -> Result<bool> {
+        prepared_verify(self.handle.id(), proof, hash)
+    }
+
+    /// Like [`PreparedVk::verify`], with the settings of `config`, e.g. on a
+    /// background thread with [`VerifierConfig::with_qos`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`PreparedVk::verify`].
+    pub fn verify_with_config(
+        &self,
+        proof: &Groth16ProofResult,
+        config: &VerifierConfig,
+    ) -> Result<bool> {
+        // The key outlives the job: run returns only once the job has.
+        let (id, proof, hash) = (self.handle.id(), proof.clone(), config.hash);
+        config.run(move || prepared_verify(id, &proof, hash))
+    }
+}
+
+fn prepared_verify(id: u64, proof: &Groth16ProofResult, hash: HashFunction) -> Result<bool> {
+    let _span = enter_span!("verify", prepared = true);
+    crate::trace::timed(|| {
+        metrics::record_verify(Scheme::Groth16, false, || {
+            let proof_hex = CString::new(proof.proof.as_str())?;
+            let public = CString::new(proof.public_inputs.as_str())?;
+            let err_ptr = unsafe {
+                bind::gnark_groth16_prepared_vk_verify(
+                    id,
+                    proof_hex.as_ptr() as *mut c_char,
+                    public.as_ptr() as *mut c_char,
+                    hash.as_raw(),
+                )
+            };
+            if err_ptr.is_null() {
+                return Ok(true);
+            }
+            match unsafe { GnarkError::take(err_ptr) } {
+                GnarkError::InvalidProof(_) => Ok(false),
+                err => Err(err.into()),
+            }
+        })
+    })
+}
+
+/// SHA-256 digest identifying a key, displayed and parsed as 64 lowercase
+/// hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyFingerprint(pub [u8; 32]);
+
+impl fmt::Display for KeyFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in &self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyFingerprint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(anyhow!("a key fingerprint is 64 hex digits, got {s:?}"));
+        }
+        let mut out = [0u8; 32];
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+                .map_err(|_| anyhow!("invalid hex in key fingerprint {s:?}"))?;
+        }
+        Ok(Self(out))
+    }
+}
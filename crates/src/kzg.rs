@@ -0,0 +1,122 @@
+//! KZG polynomial commitments over an imported SRS.
+
+use anyhow::Result;
+
+use crate::hash::FieldBytes;
+use crate::{bind, GnarkError, Srs};
+
+/// A compressed BN254 G1 point (KZG commitment or opening proof), in
+/// gnark-crypto's `G1Affine.Bytes` encoding.
+pub type G1Compressed = [u8; 32];
+
+/// An evaluation of a committed polynomial and its proof, from [`Kzg::open`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgOpening {
+    /// The quotient commitment proving the evaluation.
+    pub proof: G1Compressed,
+    /// The polynomial's value at the opened point.
+    pub value: FieldBytes,
+}
+
+/// gnark-crypto's BN254 KZG commitment scheme over an [`Srs`].
+///
+/// Polynomials are coefficient vectors, lowest degree first, of field elements
+/// encoded as in [`crate::hash`]; they may have at most as many coefficients
+/// as the SRS has G1 powers ([`Srs::size`]).
+#[derive(Debug)]
+pub struct Kzg {
+    srs: Srs,
+}
+
+impl Kzg {
+    /// Commit with the powers of tau of `srs`.
+    pub fn new(srs: Srs) -> Self {
+        Self { srs }
+    }
+
+    /// The SRS this scheme commits with.
+    pub fn srs(&self) -> &Srs {
+        &self.srs
+    }
+
+    /// Commit to `poly`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a coefficient is not canonical or `poly` is longer
+    /// than the SRS.
+    pub fn commit(&self, poly: &[FieldBytes]) -> Result<G1Compressed> {
+        let mut out = [0u8; 32];
+        let err_ptr = unsafe {
+            bind::gnark_kzg_commit(
+                self.srs.handle_id(),
+                poly.as_ptr() as *mut u8,
+                poly.len() as u64,
+                out.as_mut_ptr(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(out)
+    }
+
+    /// Evaluate `poly` at `point` and prove the evaluation against
+    /// [`Kzg::commit`]`(poly)`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Kzg::commit`], or if `point` is not canonical.
+    pub fn open(&self, poly: &[FieldBytes], point: &FieldBytes) -> Result<KzgOpening> {
+        let mut opening = KzgOpening {
+            proof: [0u8; 32],
+            value: [0u8; 32],
+        };
+        let err_ptr = unsafe {
+            bind::gnark_kzg_open(
+                self.srs.handle_id(),
+                poly.as_ptr() as *mut u8,
+                poly.len() as u64,
+                point.as_ptr() as *mut u8,
+                opening.proof.as_mut_ptr(),
+                opening.value.as_mut_ptr(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(opening)
+    }
+
+    /// Check that `proof` opens `commitment` to `value` at `point`.
+    ///
+    /// Returns `Ok(false)` for a well-formed opening that does not verify.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a point or field element is malformed.
+    pub fn verify(
+        &self,
+        commitment: &G1Compressed,
+        proof: &G1Compressed,
+        point: &FieldBytes,
+        value: &FieldBytes,
+    ) -> Result<bool> {
+        let err_ptr = unsafe {
+            bind::gnark_kzg_verify(
+                self.srs.handle_id(),
+                commitment.as_ptr() as *mut u8,
+                proof.as_ptr() as *mut u8,
+                point.as_ptr() as *mut u8,
+                value.as_ptr() as *mut u8,
+            )
+        };
+        if err_ptr.is_null() {
+            return Ok(true);
+        }
+        match unsafe { GnarkError::take(err_ptr) } {
+            GnarkError::InvalidProof(_) => Ok(false),
+            err => Err(err.into()),
+        }
+    }
+}
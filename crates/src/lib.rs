@@ -1,41 +1,388 @@
 //! Rust bindings for gnark Groth16 BN254 prover/verifier.
 //!
 //! This crate provides safe Rust wrappers around the gnark Go library, compiled
-//! via CGO into a static C archive. The bindings are auto-generated by `bindgen`
-//! from the C header produced by `go build -buildmode=c-archive`.
+//! via CGO into a static C archive. Building and linking the library, and the
+//! raw `bindgen` bindings to its C header, live in the `rust-gnark-sys` crate.
 //!
 //! # Supported platforms
 //!
 //! - aarch64-apple-ios
 //! - aarch64-apple-ios-sim
 //! - x86_64-apple-ios
+//! - aarch64-apple-ios-macabi
+//! - x86_64-apple-ios-macabi
+//! - aarch64-apple-tvos
+//! - aarch64-apple-tvos-sim
+//! - x86_64-apple-tvos
+//! - aarch64-apple-visionos
+//! - aarch64-apple-visionos-sim
 //! - aarch64-apple-darwin
 //! - x86_64-apple-darwin
 //! - aarch64-linux-android
 //! - x86_64-linux-android
 //! - x86_64-unknown-linux-gnu
 //! - aarch64-unknown-linux-gnu
+//! - x86_64-unknown-linux-musl
+//! - aarch64-unknown-linux-musl
+//! - x86_64-pc-windows-gnu
+//! - x86_64-pc-windows-msvc
+//!
+//! # Features
+//!
+//! - `native-verify`: pure-Rust Groth16 verifier ([`native`]) that reads the same
+//!   serialized vk/proof/public-input formats the Go side produces, and
+//!   encoders for the `alt_bn128` interfaces of [`solana`] and [`near`].
+//! - `verify-only`: implies `native-verify` and skips building and linking the Go
+//!   library entirely. Only the [`native`] verifier and the [`field`] types are
+//!   available in this mode.
+//! - `verifier`: links the verifier build of the Go library, which leaves out
+//!   the prover, circuit compilation, setup, hashes and curves other than
+//!   BN254. [`groth16_verify`], [`VerifyingKey`] and the artifact and proof
+//!   bundle types remain; [`ProvingKey`], [`ProverSession`],
+//!   [`CompiledCircuit`] and the other proving APIs are not available.
+//! - `wasm`: implies `verify-only` and exports the verifier to JavaScript via
+//!   `wasm-bindgen` (see [`wasm`]).
+//! - `serde`: `Serialize`/`Deserialize` for [`Groth16ProofResult`] and, with
+//!   `native-verify`, for the [`native`] proof, key and public-input types and
+//!   [`snarkjs::SnarkjsProof`].
+//! - `json`: [`Witness::from_json`], building witnesses from snarkjs-style
+//!   `input.json` objects (`serde_json::Value`).
+//! - `derive`: `#[derive(GnarkWitness)]` for typed witness structs (see
+//!   [`GnarkWitness`]).
+//! - `snarkjs`: implies `native-verify`, `serde` and `json`; reads and writes snarkjs'
+//!   `proof.json` / `public.json` (see [`snarkjs`]).
+//! - `ark`: implies `native-verify`; `From` conversions between the [`native`]
+//!   types and `ark_groth16::{Proof, VerifyingKey}`.
+//! - `gpu`: builds the Go prover with the ICICLE CUDA backend and enables
+//!   [`ProverConfig::with_gpu`]. Requires building from source.
+//! - `portable`: builds the Go library from source without gnark-crypto's
+//!   assembly, for x86-64 CPUs without ADX/BMI2 (see
+//!   [`runtime::cpu_features`]).
+//! - `uniffi`: UniFFI scaffolding for Swift/Kotlin bindings (see [`mobile`]).
+//! - `capi`: a stable C ABI over the safe API with a cbindgen-generated
+//!   header, for C, C++, Flutter and React Native hosts (see [`capi`]).
+//! - `remote`: [`RemoteProver`], a [`Prover`] that delegates to a proving
+//!   service over HTTPS.
+//! - `eth-rpc`: [`EthVerifier`], which verifies proofs with a deployed
+//!   Solidity verifier through an Ethereum node's JSON-RPC `eth_call`.
+//! - `tracing`: `info` spans named `setup`, `prove` and `verify` around
+//!   [`CompiledCircuit::groth16_setup`], every proving function and
+//!   [`groth16_verify`], closed by an event carrying `elapsed_ms`. [`init`] also
+//!   routes gnark's own logger, which otherwise writes to stderr, to `tracing`
+//!   events with target `gnark`.
+//! - `bls12-381`, `bw6-761`, `ceremony` (default): the parts of the Go library
+//!   that link curves other than BN254 -- BLS12-381 [`hash`], [`eddsa`]
+//!   curves and [`bls`] signatures, proof aggregation and recursion, and
+//!   trusted-setup ceremonies respectively. Disabling them shrinks the library when it is
+//!   built from source; the BLS12-381 functions then return
+//!   [`GnarkError::InvalidInput`].
+//!
+//! # Thread safety
+//!
+//! Every type wrapping a Go object ([`ProvingKey`], [`VerifyingKey`],
+//! [`ProverSession`], [`CompiledCircuit`], ...) is `Send + Sync`. Methods taking
+//! `&self` only read the Go object and may run concurrently from any number of
+//! threads, e.g. proving with one shared key; methods that change it take
+//! `&mut self`. Process-wide Go settings ([`ProverConfig::with_threads`],
+//! [`ProverConfig::with_memory_limit`]) are applied under a lock, so proofs
+//! overriding them run one at a time.
 //!
 //! # Note
 //!
-//! WASM is **not** supported because `c-archive` does not target WASM.
+//! Proving is **not** available on WASM because `c-archive` does not target WASM.
+//! `wasm32-unknown-unknown` builds must enable the `wasm` (or `verify-only`) feature.
 
+#[cfg(not(feature = "verify-only"))]
 use anyhow::{bail, Result};
+#[cfg(not(feature = "verify-only"))]
 use std::ffi::{CStr, CString};
+#[cfg(not(feature = "verify-only"))]
 use std::os::raw::c_char;
+#[cfg(not(feature = "verify-only"))]
+use std::os::raw::c_int;
+#[cfg(not(feature = "verify-only"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Versions of gnark and gnark-crypto the Go library is built with; keep in
+/// sync with `go/go.mod`.
+#[cfg(not(feature = "verify-only"))]
+pub(crate) const GNARK_VERSION: &str = "v0.14.0";
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) const GNARK_CRYPTO_VERSION: &str = "v0.19.0";
+
+// Declared first: other modules use its span macro.
+#[cfg(not(feature = "verify-only"))]
+#[macro_use]
+mod trace;
 
-// Suppress warnings on bindgen-generated code (doesn't follow Rust conventions).
-#[allow(warnings, clippy::all)]
-mod bind {
-    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod abi;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod acir;
+#[cfg(all(
+    not(any(feature = "verify-only", feature = "verifier")),
+    feature = "bw6-761"
+))]
+mod aggregate;
+#[cfg(feature = "ark")]
+mod ark;
+#[cfg(not(feature = "verify-only"))]
+mod artifact;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod bls;
+#[cfg(not(feature = "verify-only"))]
+mod buffer;
+#[cfg(not(feature = "verify-only"))]
+mod bundle;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod calibrate;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod cancel;
+#[cfg(all(feature = "capi", not(feature = "verify-only")))]
+pub mod capi;
+#[cfg(all(
+    not(any(feature = "verify-only", feature = "verifier")),
+    feature = "ceremony"
+))]
+pub mod ceremony;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod checkpoint;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod circuit;
+#[cfg(not(feature = "verify-only"))]
+mod config;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod conformance;
+#[cfg(not(feature = "verify-only"))]
+mod context;
+#[cfg(all(feature = "downloader", not(feature = "verify-only")))]
+mod download;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod ecc;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod eddsa;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod eip2537;
+#[cfg(not(feature = "verify-only"))]
+mod error;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod estimate;
+#[cfg(all(feature = "eth-rpc", not(feature = "verify-only")))]
+mod eth;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod feed;
+pub mod field;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod go_circuit;
+#[cfg(not(feature = "verify-only"))]
+mod handle;
+#[cfg(not(feature = "verify-only"))]
+pub mod hash;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod hints;
+#[cfg(not(feature = "verify-only"))]
+mod key;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod kzg;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod merkle;
+pub mod metrics;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod migrate;
+#[cfg(all(
+    feature = "uniffi",
+    not(any(feature = "verify-only", feature = "verifier"))
+))]
+pub mod mobile;
+#[cfg(feature = "native-verify")]
+pub mod native;
+#[cfg(feature = "native-verify")]
+pub mod near;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod plonk;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod pool;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod progress;
+#[cfg(not(feature = "verify-only"))]
+mod prover;
+mod public_inputs;
+#[cfg(not(feature = "verify-only"))]
+mod qos;
+#[cfg(all(
+    not(any(feature = "verify-only", feature = "verifier")),
+    feature = "bw6-761"
+))]
+mod recursion;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod registry;
+#[cfg(all(feature = "remote", not(feature = "verify-only")))]
+mod remote;
+#[cfg(not(feature = "verify-only"))]
+pub mod runtime;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod secret;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod self_test;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod session;
+#[cfg(feature = "native-verify")]
+pub mod snarkjs;
+#[cfg(feature = "native-verify")]
+pub mod solana;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod srs;
+#[cfg(not(feature = "verify-only"))]
+mod store;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod task;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub mod test_utils;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod transcript;
+#[cfg(not(feature = "verify-only"))]
+mod version;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+mod witness;
+
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use abi::{AbiInput, GnarkWitness, WitnessValue};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use acir::AcirProgram;
+#[cfg(all(
+    not(any(feature = "verify-only", feature = "verifier")),
+    feature = "bw6-761"
+))]
+pub use aggregate::{AggregatedProof, Aggregator};
+#[cfg(not(feature = "verify-only"))]
+pub use artifact::{Artifact, ArtifactContent, ArtifactHeader};
+#[cfg(not(feature = "verify-only"))]
+pub use buffer::GnarkBuffer;
+#[cfg(not(feature = "verify-only"))]
+pub use bundle::{GnarkProofBundle, ProofScheme};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use calibrate::{calibrate, Calibration};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use cancel::CancelToken;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use checkpoint::{groth16_prove_with_full_witness, SolvedWitness};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use circuit::{
+    CircuitBuilder, CircuitStats, CompiledCircuit, ConstraintViolation, GadgetStats, Hint,
+    HintTiming, SolveProfile, Var,
+};
+#[cfg(not(feature = "verify-only"))]
+pub use config::{HashFunction, ProverConfig, SerializationFormat};
+#[cfg(not(feature = "verify-only"))]
+pub use context::{app_context, groth16_verify_in_context, proof_app_context, APP_CONTEXT};
+#[cfg(all(feature = "downloader", not(feature = "verify-only")))]
+pub use download::Downloader;
+#[cfg(not(feature = "verify-only"))]
+pub use error::GnarkError;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use estimate::Estimate;
+#[cfg(all(feature = "eth-rpc", not(feature = "verify-only")))]
+pub use eth::{EthVerification, EthVerifier};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use feed::WitnessFeeder;
+pub use field::{Fq, Fr};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use hints::{register_hint, unregister_hint};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use key::ProvingKey;
+#[cfg(not(feature = "verify-only"))]
+pub use key::{KeyFingerprint, PreparedVk, VerifyingKey};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use kzg::{G1Compressed, Kzg, KzgOpening};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use migrate::{migrate, MigrationReport};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use plonk::{
+    plonk_prove, plonk_verify, PlonkConfig, PlonkProofResult, PlonkProvingKey, PlonkVerifyingKey,
+};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use pool::ProvePool;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use progress::{Progress, ProgressReporter, ProvePhase};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use prover::{Groth16, Plonk};
+#[cfg(not(feature = "verify-only"))]
+pub use prover::{ProofSystem, Prover};
+pub use public_inputs::PublicInputsBuilder;
+#[cfg(not(feature = "verify-only"))]
+pub use qos::{Qos, VerifierConfig};
+#[cfg(all(
+    not(any(feature = "verify-only", feature = "verifier")),
+    feature = "bw6-761"
+))]
+pub use recursion::{RecursionPipeline, RecursionProof};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use registry::{CircuitHandle, CircuitRegistry};
+#[cfg(all(feature = "remote", not(feature = "verify-only")))]
+pub use remote::RemoteProver;
+/// Derive [`GnarkWitness`] for a struct of circuit inputs.
+#[cfg(all(
+    feature = "derive",
+    not(any(feature = "verify-only", feature = "verifier"))
+))]
+pub use rust_gnark_derive::GnarkWitness;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use secret::SecretWitness;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use self_test::{self_test, SelfTestReport};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use session::ProverSession;
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use srs::Srs;
+#[cfg(not(feature = "verify-only"))]
+pub use store::{ArtifactKind, ArtifactStore};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use task::{prove_async, prove_async_with_progress, ProveTask};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use transcript::{Transcript, TranscriptHash};
+#[cfg(not(feature = "verify-only"))]
+pub use version::{version_info, GoModule, VersionInfo};
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub use witness::{public_inputs_hash, CircuitAbi, Witness, PUBLIC_INPUTS_HASH};
+
+#[cfg(all(
+    feature = "uniffi",
+    not(any(feature = "verify-only", feature = "verifier"))
+))]
+uniffi::setup_scaffolding!();
+
+#[cfg(not(feature = "verify-only"))]
+use rust_gnark_sys as bind;
+
+/// Copy out and free an error string returned by the Go side.
+///
+/// # Safety
+///
+/// `ptr` must be a non-null string allocated by the Go side and not freed yet.
+#[cfg(not(feature = "verify-only"))]
+pub(crate) unsafe fn take_go_string(ptr: *mut c_char) -> String {
+    let s = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    bind::gnark_free_string(ptr);
+    s
 }
 
 /// Result of a Groth16 BN254 proof generation.
 ///
 /// Both fields are hex-encoded binary serializations from gnark:
-/// - `proof`: the compressed Groth16 proof (via `proof.WriteTo()`)
+/// - `proof`: the Groth16 proof, compressed (via `proof.WriteTo()`) unless
+///   another [`SerializationFormat`] was configured
 /// - `public_inputs`: the public witness (via `witness.MarshalBinary()`)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(
+        feature = "uniffi",
+        not(any(feature = "verify-only", feature = "verifier"))
+    ),
+    derive(uniffi::Record)
+)]
 pub struct Groth16ProofResult {
     /// Hex-encoded gnark binary proof.
     pub proof: String,
@@ -43,22 +390,179 @@ pub struct Groth16ProofResult {
     pub public_inputs: String,
 }
 
+#[cfg(not(feature = "verify-only"))]
+impl Groth16ProofResult {
+    /// Pair a hex-encoded gnark proof with the public inputs of the witness it
+    /// was generated from, e.g. for a proof received without them.
+    #[cfg(not(feature = "verifier"))]
+    pub fn bind_public_inputs(proof: impl Into<String>, witness: &Witness) -> Self {
+        Self {
+            proof: proof.into(),
+            public_inputs: witness.public_inputs_hex(),
+        }
+    }
+
+    /// The proof as the Solidity verifier from
+    /// [`VerifyingKey::export_solidity`] takes it: `A`, `B` and `C`, then any
+    /// commitments and their proof of knowledge, as uncompressed big-endian
+    /// coordinates. Public inputs are passed to the contract separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not decode.
+    pub fn solidity_calldata(&self) -> Result<Vec<u8>> {
+        let proof = CString::new(self.proof.as_str())?;
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_proof_solidity_calldata(
+                proof.as_ptr() as *mut c_char,
+                &mut data,
+                &mut len,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) }.to_vec())
+    }
+
+    /// The complete calldata of a call of the Solidity verifier's
+    /// `verifyProof` with this proof and its public inputs: the function
+    /// selector for the proof's number of commitments and public inputs, then
+    /// [`Groth16ProofResult::solidity_calldata`] and the inputs as `uint256`
+    /// words. Send it to the deployed contract as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof or the public inputs do not decode.
+    pub fn solidity_verify_call(&self) -> Result<Vec<u8>> {
+        let proof = CString::new(self.proof.as_str())?;
+        let public_inputs = CString::new(self.public_inputs.as_str())?;
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_groth16_solidity_verify_call(
+                proof.as_ptr() as *mut c_char,
+                public_inputs.as_ptr() as *mut c_char,
+                &mut data,
+                &mut len,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) }.to_vec())
+    }
+
+    /// The same statement's proof with fresh randomness applied, so that it
+    /// cannot be linked to this one (e.g. by a relayer submitting proofs on
+    /// behalf of their provers).
+    ///
+    /// With random `r`, `s` the new proof is `(A/r, r·B + r·s·δ, C + s·A)`,
+    /// which verifies against `vk` and the same public inputs. Pedersen
+    /// commitments, if the circuit has any, are kept unchanged and so remain
+    /// linkable. The proof is written in [`SerializationFormat::Compressed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not decode.
+    #[cfg(not(feature = "verifier"))]
+    pub fn rerandomize(&self, vk: &VerifyingKey) -> Result<Self> {
+        let proof = CString::new(self.proof.as_str())?;
+        let mut out = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_groth16_proof_rerandomize(
+                vk.handle_id(),
+                proof.as_ptr() as *mut c_char,
+                SerializationFormat::Compressed.as_raw(),
+                &mut out,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            proof: unsafe { take_go_string(out) },
+            public_inputs: self.public_inputs.clone(),
+        })
+    }
+}
+
 /// Initialize the gnark Go runtime.
 ///
 /// Sets `GODEBUG=asyncpreemptoff=1` on iOS/macOS to prevent signal flood crashes
-/// in debug builds. Call once before any prove/verify operations.
+/// in debug builds. Calling it is optional: the first proof or
+/// [`groth16_verify`] runs it if it has not run since the library was loaded
+/// or [`shutdown`], so tools that never prove skip it. Call it first to handle its error, or use
+/// [`init_with_options`] to change the defaults.
+///
+/// The Go runtime itself starts on a background thread when the library is
+/// loaded, without blocking the host's startup, and runs until the process
+/// exits.
 ///
 /// # Errors
 ///
 /// Returns an error if the Go runtime initialization fails.
+#[cfg(not(feature = "verify-only"))]
 pub fn init() -> Result<()> {
-    let ret = unsafe { bind::gnark_init() };
+    init_with_options(&runtime::InitOptions::default())
+}
+
+/// [`init`] with settings for the Go runtime's debug options and its signal
+/// handling next to the host's (see [`runtime`]).
+///
+/// # Errors
+///
+/// Returns an error if a `GODEBUG` setting contains a NUL byte or the Go
+/// runtime initialization fails.
+#[cfg(not(feature = "verify-only"))]
+pub fn init_with_options(options: &runtime::InitOptions) -> Result<()> {
+    let godebug = options.godebug_raw()?;
+    let ret = unsafe {
+        bind::gnark_init(
+            godebug.as_ptr() as *mut c_char,
+            options.async_preemption_raw(),
+            options.crash_on_fatal as c_int,
+        )
+    };
     if ret != 0 {
         bail!("gnark_init failed with code {ret}");
     }
+    #[cfg(feature = "tracing")]
+    trace::forward_go_logs();
+    INITIALIZED.store(true, Ordering::Release);
     Ok(())
 }
 
+/// Whether [`init`] has run since the library was loaded or [`shutdown`].
+#[cfg(not(feature = "verify-only"))]
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Run [`init`] unless it has run already.
+#[cfg(not(feature = "verify-only"))]
+pub(crate) fn ensure_init() {
+    if !INITIALIZED.load(Ordering::Acquire) {
+        // The default options hold no GODEBUG setting, so this cannot fail.
+        let _ = init();
+    }
+}
+
+/// Release what the Go runtime holds between uses: the memory of past proofs
+/// goes back to the OS and the OS threads its goroutines left parked exit.
+/// For long-lived hosts that prove rarely; loaded keys, circuits and
+/// sessions stay valid.
+///
+/// The runtime keeps running, and a later call starts threads again as it
+/// needs them and runs [`init`] again. Call it while no proof is running, or
+/// the threads of that proof stay.
+#[cfg(not(feature = "verify-only"))]
+pub fn shutdown() {
+    let _span = enter_span!("shutdown");
+    unsafe { bind::gnark_shutdown() };
+    INITIALIZED.store(false, Ordering::Release);
+}
+
 /// Generate a Groth16 BN254 proof.
 ///
 /// # Arguments
@@ -74,53 +578,353 @@ pub fn init() -> Result<()> {
 ///
 /// # Errors
 ///
-/// Returns an error if file loading, witness construction, or proof generation fails.
+/// Returns an error if file loading, witness construction, or proof generation
+/// fails. Errors from the Go side carry a [`GnarkError`] describing the cause.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
 pub fn groth16_prove(
     r1cs_path: &str,
     pk_path: &str,
     witness_json: &str,
+) -> Result<Groth16ProofResult> {
+    prove_with_options(
+        r1cs_path,
+        PkRef::Path(pk_path),
+        witness_json,
+        &ProveOptions::default(),
+    )
+}
+
+/// Generate a Groth16 BN254 proof that can be aborted through `token`.
+///
+/// Same as [`groth16_prove`], but calling [`CancelToken::cancel`] from another
+/// thread makes the call return early with a cancellation error. The Go prover
+/// checks the token while reading the proving key, between proving phases and
+/// at every hint call of the solver; past the solver, gnark cannot be
+/// interrupted and the call returns once the proof is done, so it never leaves
+/// work running.
+///
+/// # Errors
+///
+/// Returns an error if the proof is cancelled, or for the same reasons as
+/// [`groth16_prove`].
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_cancellable(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness_json: &str,
+    token: &CancelToken,
+) -> Result<Groth16ProofResult> {
+    let options = ProveOptions {
+        cancel: Some(token),
+        ..Default::default()
+    };
+    prove_with_options(r1cs_path, PkRef::Path(pk_path), witness_json, &options)
+}
+
+/// Generate a Groth16 BN254 proof, reporting progress to `reporter`.
+///
+/// Same as [`groth16_prove`]; `reporter` is called from the proving thread at
+/// each [`ProvePhase`] boundary and while the `.r1cs`/`.pk` files are read.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// let result = rust_gnark::groth16_prove_with_progress(
+///     "circuit.r1cs",
+///     "circuit.pk",
+///     r#"{"X": "3", "Y": "35"}"#,
+///     &|p: rust_gnark::Progress| println!("{:?}: {:.0}%", p.phase, p.fraction() * 100.0),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Same as [`groth16_prove`].
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_with_progress(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness_json: &str,
+    reporter: &dyn ProgressReporter,
+) -> Result<Groth16ProofResult> {
+    let options = ProveOptions {
+        progress: Some(reporter),
+        ..Default::default()
+    };
+    prove_with_options(r1cs_path, PkRef::Path(pk_path), witness_json, &options)
+}
+
+/// Generate a Groth16 BN254 proof using the prover settings in `config`.
+///
+/// Same as [`groth16_prove`]; `config` only affects how the proof is computed
+/// (e.g. on which device), never the resulting proof's validity.
+///
+/// # Errors
+///
+/// Same as [`groth16_prove`].
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_with_config(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness_json: &str,
+    config: &ProverConfig,
+) -> Result<Groth16ProofResult> {
+    let options = ProveOptions {
+        config: Some(config),
+        ..Default::default()
+    };
+    prove_with_options(r1cs_path, PkRef::Path(pk_path), witness_json, &options)
+}
+
+/// Generate a Groth16 BN254 proof with a proving key already loaded in Go memory.
+///
+/// Same as [`groth16_prove`], but skips reading and deserializing the `.pk`
+/// file, which dominates proving time for large circuits when proving
+/// repeatedly with the same key.
+///
+/// # Errors
+///
+/// Returns an error if loading the constraint system, witness construction, or
+/// proof generation fails.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_with_key(
+    r1cs_path: &str,
+    pk: &ProvingKey,
+    witness_json: &str,
+) -> Result<Groth16ProofResult> {
+    prove_with_options(
+        r1cs_path,
+        PkRef::Key(pk),
+        witness_json,
+        &ProveOptions::default(),
+    )
+}
+
+/// Generate a Groth16 BN254 proof for a [`SecretWitness`].
+///
+/// Same as [`groth16_prove`], with [`ProverConfig::with_witness_scrubbing`]
+/// on, so the Go side wipes its copies of the witness after proving.
+///
+/// # Errors
+///
+/// Same as [`groth16_prove`].
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_secret(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness: &SecretWitness,
+) -> Result<Groth16ProofResult> {
+    let config = ProverConfig::default().with_witness_scrubbing(true);
+    let options = ProveOptions {
+        config: Some(&config),
+        ..Default::default()
+    };
+    prove_with_witness(
+        r1cs_path,
+        PkRef::Path(pk_path),
+        witness.as_c_str(),
+        &options,
+    )
+}
+
+/// Generate a Groth16 BN254 proof for a circuit with
+/// [`CircuitBuilder::feed`] values, which `feeder` supplies while the witness
+/// is solved.
+///
+/// `witness_json` assigns the circuit's inputs, as for [`groth16_prove`].
+///
+/// # Errors
+///
+/// Returns the feeder's error if it fails, or an error for the same reasons as
+/// [`groth16_prove`].
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub fn groth16_prove_with_feeder(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness_json: &str,
+    feeder: &dyn WitnessFeeder,
+) -> Result<Groth16ProofResult> {
+    let options = ProveOptions {
+        feeder: Some(feeder),
+        ..Default::default()
+    };
+    prove_with_options(r1cs_path, PkRef::Path(pk_path), witness_json, &options)
+}
+
+/// Where the prover gets its proving key from.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+#[derive(Clone, Copy)]
+pub(crate) enum PkRef<'a> {
+    /// A `.pk` file, read on every call.
+    Path(&'a str),
+    /// A key already deserialized in Go memory.
+    Key(&'a ProvingKey),
+}
+
+/// Per-call knobs forwarded to `gnark_groth16_prove_with_options`.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+#[derive(Default)]
+pub(crate) struct ProveOptions<'a> {
+    pub(crate) cancel: Option<&'a CancelToken>,
+    pub(crate) progress: Option<&'a dyn ProgressReporter>,
+    pub(crate) config: Option<&'a ProverConfig>,
+    pub(crate) feeder: Option<&'a dyn WitnessFeeder>,
+}
+
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn prove_with_options(
+    r1cs_path: &str,
+    pk: PkRef<'_>,
+    witness_json: &str,
+    options: &ProveOptions<'_>,
+) -> Result<Groth16ProofResult> {
+    prove_with_witness(r1cs_path, pk, &CString::new(witness_json)?, options)
+}
+
+/// [`prove_with_options`] on a witness that is already a C string, so
+/// [`SecretWitness`] bytes reach Go without a copy.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn prove_with_witness(
+    r1cs_path: &str,
+    pk: PkRef<'_>,
+    witness: &CStr,
+    options: &ProveOptions<'_>,
 ) -> Result<Groth16ProofResult> {
     let r1cs = CString::new(r1cs_path)?;
-    let pk = CString::new(pk_path)?;
-    let witness = CString::new(witness_json)?;
-
-    let result = unsafe {
-        bind::gnark_groth16_prove(
-            r1cs.as_ptr() as *mut c_char,
-            pk.as_ptr() as *mut c_char,
-            witness.as_ptr() as *mut c_char,
-        )
+
+    run_prove(options, |c_options| {
+        Ok(match pk {
+            PkRef::Path(pk_path) => {
+                let pk = CString::new(pk_path)?;
+                unsafe {
+                    bind::gnark_groth16_prove_with_options(
+                        r1cs.as_ptr() as *mut c_char,
+                        pk.as_ptr() as *mut c_char,
+                        witness.as_ptr() as *mut c_char,
+                        c_options,
+                    )
+                }
+            }
+            PkRef::Key(pk) => unsafe {
+                bind::gnark_groth16_prove_with_key(
+                    r1cs.as_ptr() as *mut c_char,
+                    pk.handle_id(),
+                    witness.as_ptr() as *mut c_char,
+                    c_options,
+                )
+            },
+        })
+    })
+}
+
+/// Translate `options` to `C_ProveOptions`, run `prove` with them and decode
+/// the `C_Groth16ProofResult` it returns.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn run_prove(
+    options: &ProveOptions<'_>,
+    prove: impl FnOnce(*mut bind::C_ProveOptions) -> Result<*mut bind::C_Groth16ProofResult>,
+) -> Result<Groth16ProofResult> {
+    let _span = enter_span!("prove");
+    let run = || {
+        // The trampoline receives a thin pointer to this fat `&dyn` reference,
+        // which lives on this stack frame for the duration of the call.
+        let reporter = options.progress;
+        let default_config = ProverConfig::default();
+        ensure_init();
+        let mut c_options =
+            c_prove_options(options.config.unwrap_or(&default_config), options.cancel);
+        if let Some(reporter) = &reporter {
+            c_options.progress = Some(progress::progress_trampoline);
+            c_options.progress_user_data = reporter as *const &dyn ProgressReporter as *mut _;
+        }
+        let feed_context = options.feeder.map(feed::FeedContext::new);
+        if let Some(context) = &feed_context {
+            c_options.feed = Some(feed::feed_trampoline);
+            c_options.feed_user_data = context as *const feed::FeedContext<'_> as *mut _;
+        }
+
+        let result = prove(&mut c_options)?;
+        let out = unsafe { take_proof_result(result) };
+        // A failing feeder makes the solver fail; its own error says why.
+        match feed_context
+            .as_ref()
+            .and_then(feed::FeedContext::take_error)
+        {
+            Some(err) if out.is_err() => Err(err.context("the witness feeder failed")),
+            _ => out,
+        }
     };
+    trace::timed(|| metrics::record_prove(metrics::Scheme::Groth16, 1, false, run))
+}
+
+/// `C_ProveOptions` for `config` and `cancel`, without progress reporting or
+/// a witness feeder.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn c_prove_options(
+    config: &ProverConfig,
+    cancel: Option<&CancelToken>,
+) -> bind::C_ProveOptions {
+    bind::C_ProveOptions {
+        cancel_token: cancel.map_or(0, CancelToken::id),
+        progress: None,
+        progress_user_data: std::ptr::null_mut(),
+        gpu_enabled: config.gpu_device.is_some().into(),
+        gpu_device: config.gpu_device.unwrap_or(0) as _,
+        threads: config
+            .threads
+            .map_or(0, |n| n.try_into().unwrap_or(c_int::MAX)),
+        memory_limit: config
+            .memory_limit
+            .map_or(0, |b| b.try_into().unwrap_or(i64::MAX)),
+        hash: config.hash.as_raw(),
+        proof_format: config.proof_format.as_raw(),
+        test_rng_enabled: config.test_rng_seed.is_some().into(),
+        test_rng_seed: config.test_rng_seed.unwrap_or(0),
+        scrub_witness: config.scrub_witness.into(),
+        feed: None,
+        feed_user_data: std::ptr::null_mut(),
+        solver_tasks: config.raw_solver_tasks(),
+        hint_timeout_ms: config.raw_hint_timeout_ms(),
+    }
+}
 
+/// Decode and free a `C_Groth16ProofResult` returned by the Go side.
+///
+/// # Safety
+///
+/// `result` must be null or a result allocated by the Go side and not freed
+/// yet.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) unsafe fn take_proof_result(
+    result: *mut bind::C_Groth16ProofResult,
+) -> Result<Groth16ProofResult> {
     if result.is_null() {
         bail!("gnark_groth16_prove returned null");
     }
 
-    unsafe {
-        let out = if (*result).error.is_null() {
-            let proof_ptr = (*result).proof;
-            let pub_ptr = (*result).public_inputs;
-
-            if proof_ptr.is_null() || pub_ptr.is_null() {
-                Err(anyhow::anyhow!(
-                    "gnark_groth16_prove: proof or public_inputs pointer is null"
-                ))
-            } else {
-                Ok(Groth16ProofResult {
-                    proof: CStr::from_ptr(proof_ptr).to_string_lossy().into_owned(),
-                    public_inputs: CStr::from_ptr(pub_ptr).to_string_lossy().into_owned(),
-                })
-            }
+    metrics::note_constraints((*result).nb_constraints);
+    let out = if (*result).error.is_null() {
+        let proof_ptr = (*result).proof;
+        let pub_ptr = (*result).public_inputs;
+
+        if proof_ptr.is_null() || pub_ptr.is_null() {
+            Err(anyhow::anyhow!(
+                "gnark_groth16_prove: proof or public_inputs pointer is null"
+            ))
         } else {
-            let err = CStr::from_ptr((*result).error)
-                .to_string_lossy()
-                .into_owned();
-            Err(anyhow::anyhow!("Proof generation failed: {err}"))
-        };
+            Ok(Groth16ProofResult {
+                proof: CStr::from_ptr(proof_ptr).to_string_lossy().into_owned(),
+                public_inputs: CStr::from_ptr(pub_ptr).to_string_lossy().into_owned(),
+            })
+        }
+    } else {
+        let err = GnarkError::decode(&CStr::from_ptr((*result).error).to_string_lossy());
+        Err(err.into())
+    };
 
-        bind::gnark_free_proof_result(result);
-        out
-    }
+    bind::gnark_free_proof_result(result);
+    out
 }
 
 /// Verify a Groth16 BN254 proof.
@@ -139,38 +943,74 @@ pub fn groth16_prove(
 /// # Errors
 ///
 /// Returns an error if file loading or deserialization fails.
+#[cfg(not(feature = "verify-only"))]
 pub fn groth16_verify(
     r1cs_path: &str,
     vk_path: &str,
     proof_result: &Groth16ProofResult,
 ) -> Result<bool> {
-    let r1cs = CString::new(r1cs_path)?;
-    let vk = CString::new(vk_path)?;
-    let proof = CString::new(proof_result.proof.as_str())?;
-    let pub_inputs = CString::new(proof_result.public_inputs.as_str())?;
-
-    let err_ptr = unsafe {
-        bind::gnark_groth16_verify(
-            r1cs.as_ptr() as *mut c_char,
-            vk.as_ptr() as *mut c_char,
-            proof.as_ptr() as *mut c_char,
-            pub_inputs.as_ptr() as *mut c_char,
-        )
-    };
+    groth16_verify_with_hash(r1cs_path, vk_path, proof_result, HashFunction::Default)
+}
 
-    if err_ptr.is_null() {
-        // NULL = valid proof
-        Ok(true)
-    } else {
-        let err = unsafe {
-            let s = CStr::from_ptr(err_ptr).to_string_lossy().into_owned();
-            bind::gnark_free_string(err_ptr);
-            s
-        };
-        if err.contains("invalid proof") {
-            Ok(false)
-        } else {
-            bail!("Verification error: {err}")
-        }
-    }
+/// [`groth16_verify`] with the settings of `config`: the [`HashFunction`] the
+/// proof was generated with, and the thread priority to verify at
+/// ([`VerifierConfig::with_qos`]).
+///
+/// # Errors
+///
+/// Same as [`groth16_verify`].
+#[cfg(not(feature = "verify-only"))]
+pub fn groth16_verify_with_config(
+    r1cs_path: &str,
+    vk_path: &str,
+    proof_result: &Groth16ProofResult,
+    config: &VerifierConfig,
+) -> Result<bool> {
+    let (r1cs_path, vk_path) = (r1cs_path.to_owned(), vk_path.to_owned());
+    let (proof_result, hash) = (proof_result.clone(), config.hash);
+    config.run(move || groth16_verify_with_hash(&r1cs_path, &vk_path, &proof_result, hash))
+}
+
+/// Verify a Groth16 BN254 proof generated with a non-default [`HashFunction`]
+/// (see [`ProverConfig::with_hash_function`]).
+///
+/// # Errors
+///
+/// Same as [`groth16_verify`].
+#[cfg(not(feature = "verify-only"))]
+pub fn groth16_verify_with_hash(
+    r1cs_path: &str,
+    vk_path: &str,
+    proof_result: &Groth16ProofResult,
+    hash: HashFunction,
+) -> Result<bool> {
+    let _span = enter_span!("verify", r1cs = r1cs_path, vk = vk_path);
+    ensure_init();
+    trace::timed(|| {
+        metrics::record_verify(metrics::Scheme::Groth16, false, || {
+            let r1cs = CString::new(r1cs_path)?;
+            let vk = CString::new(vk_path)?;
+            let proof = CString::new(proof_result.proof.as_str())?;
+            let pub_inputs = CString::new(proof_result.public_inputs.as_str())?;
+
+            let err_ptr = unsafe {
+                bind::gnark_groth16_verify_with_hash(
+                    r1cs.as_ptr() as *mut c_char,
+                    vk.as_ptr() as *mut c_char,
+                    proof.as_ptr() as *mut c_char,
+                    pub_inputs.as_ptr() as *mut c_char,
+                    hash.as_raw(),
+                )
+            };
+
+            if err_ptr.is_null() {
+                // NULL = valid proof
+                return Ok(true);
+            }
+            match unsafe { GnarkError::take(err_ptr) } {
+                GnarkError::InvalidProof(_) => Ok(false),
+                err => Err(err.into()),
+            }
+        })
+    })
 }
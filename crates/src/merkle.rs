@@ -0,0 +1,186 @@
+//! Merkle trees in the layout of gnark's `std/accumulator/merkle`.
+//!
+//! gnark's gadget verifies a [`MerkleProof`] against a binary tree where a
+//! leaf's node is `H(leaf)` and an inner node is `H(left, right)`, with `H` a
+//! field hasher (Poseidon2 or MiMC here). Building the tree on the host with
+//! the same hashes keeps roots and paths in sync with the circuit.
+
+use anyhow::{ensure, Result};
+
+use crate::hash::{mimc, poseidon2, Curve, FieldBytes};
+
+/// Hash used for leaves and inner nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeHash {
+    /// [`crate::hash::poseidon2`], matching gnark's `std/hash/poseidon2`.
+    Poseidon2,
+    /// [`crate::hash::mimc`], matching gnark's `std/hash/mimc`.
+    Mimc,
+}
+
+impl TreeHash {
+    fn hash(self, curve: Curve, inputs: &[FieldBytes]) -> Result<FieldBytes> {
+        match self {
+            TreeHash::Poseidon2 => poseidon2(curve, inputs),
+            TreeHash::Mimc => mimc(curve, inputs),
+        }
+    }
+}
+
+/// A complete binary Merkle tree over field-element leaves.
+///
+/// gnark's gadget only handles binary trees of fixed depth, so the leaves are
+/// padded with zero elements up to the next power of two.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    curve: Curve,
+    hash: TreeHash,
+    leaves: Vec<FieldBytes>,
+    /// `levels[0]` holds the leaf hashes, the last level the root.
+    levels: Vec<Vec<FieldBytes>>,
+}
+
+impl MerkleTree {
+    /// Build the tree over `leaves`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `leaves` is empty or a leaf is not a canonical
+    /// field element of `curve`.
+    pub fn new(curve: Curve, hash: TreeHash, leaves: &[FieldBytes]) -> Result<Self> {
+        ensure!(!leaves.is_empty(), "a Merkle tree needs at least one leaf");
+        let mut padded = leaves.to_vec();
+        padded.resize(leaves.len().next_power_of_two(), [0u8; 32]);
+
+        let mut level = padded
+            .iter()
+            .map(|leaf| hash.hash(curve, std::slice::from_ref(leaf)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut levels = Vec::new();
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| hash.hash(curve, pair))
+                .collect::<Result<Vec<_>>>()?;
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+
+        Ok(Self {
+            curve,
+            hash,
+            leaves: padded,
+            levels,
+        })
+    }
+
+    /// The root hash.
+    pub fn root(&self) -> FieldBytes {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Number of levels above the leaves; proofs have `depth + 1` path entries.
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Number of leaves, including padding.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Always `false`: a tree has at least one leaf.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Membership proof for the leaf at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof> {
+        ensure!(
+            index < self.leaves.len(),
+            "leaf index {index} out of range for {} leaves",
+            self.leaves.len()
+        );
+        let mut path = vec![self.leaves[index]];
+        let mut i = index;
+        for level in &self.levels[..self.depth()] {
+            path.push(level[i ^ 1]);
+            i >>= 1;
+        }
+        Ok(MerkleProof {
+            root: self.root(),
+            path,
+            index,
+        })
+    }
+
+    /// Curve whose scalar field the tree is over.
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Hash the tree uses.
+    pub fn hash(&self) -> TreeHash {
+        self.hash
+    }
+}
+
+/// A membership proof, laid out like gnark's `merkle.MerkleProof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// The tree's root (`RootHash`).
+    pub root: FieldBytes,
+    /// The leaf itself followed by the sibling at each level, bottom up (`Path`).
+    pub path: Vec<FieldBytes>,
+    /// The leaf index, the gadget's `leaf` argument.
+    pub index: usize,
+}
+
+impl MerkleProof {
+    /// Recompute the root the way gnark's `VerifyProof` does and compare.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is empty or hashing fails.
+    pub fn verify(&self, curve: Curve, hash: TreeHash) -> Result<bool> {
+        ensure!(!self.path.is_empty(), "empty Merkle path");
+        let mut node = hash.hash(curve, &self.path[..1])?;
+        for (level, sibling) in self.path[1..].iter().enumerate() {
+            node = if self.index >> level & 1 == 1 {
+                hash.hash(curve, &[*sibling, node])?
+            } else {
+                hash.hash(curve, &[node, *sibling])?
+            };
+        }
+        Ok(node == self.root)
+    }
+
+    /// Witness values for a `merkle.MerkleProof` field named `name`:
+    /// `{name}_RootHash` and `{name}_Path_i`, hex-encoded, ready for
+    /// [`crate::Witness::from_assignments`]. The leaf index is a separate
+    /// circuit input.
+    pub fn assignments(&self, name: &str) -> Vec<(String, String)> {
+        let mut out = vec![(format!("{name}_RootHash"), to_hex(&self.root))];
+        out.extend(
+            self.path
+                .iter()
+                .enumerate()
+                .map(|(i, node)| (format!("{name}_Path_{i}"), to_hex(node))),
+        );
+        out
+    }
+}
+
+fn to_hex(bytes: &FieldBytes) -> String {
+    let mut out = String::with_capacity(2 + 2 * bytes.len());
+    out.push_str("0x");
+    for b in bytes {
+        out.push_str(&format!("{b:02x}"));
+    }
+    out
+}
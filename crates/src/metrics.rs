@@ -0,0 +1,276 @@
+//! Opt-in proving and verification statistics.
+//!
+//! Install a [`Metrics`] with [`set_metrics`] and every proof and verification
+//! the crate runs reports to it when it ends: how long it took, how many
+//! constraints the circuit has and how much memory the prover peaked at, and
+//! if it failed, a [`FailureCategory`]. Events carry no witness values, keys,
+//! proofs or paths, so they can be forwarded to an analytics backend as they
+//! are. Without a [`Metrics`] nothing is measured.
+//!
+//! ```no_run
+//! use rust_gnark::metrics::{self, Metrics, ProveEvent};
+//!
+//! struct Log;
+//!
+//! impl Metrics for Log {
+//!     fn on_prove(&self, event: &ProveEvent) {
+//!         eprintln!("proved in {:?}: {:?}", event.duration, event.failure);
+//!     }
+//! }
+//!
+//! metrics::set_metrics(Log);
+//! ```
+
+use anyhow::Result;
+#[cfg(not(feature = "verify-only"))]
+use std::cell::Cell;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// The proof system of an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// Groth16, including aggregation and recursion.
+    Groth16,
+    /// PLONK.
+    Plonk,
+}
+
+/// Why a call failed, coarse enough to aggregate over devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FailureCategory {
+    /// An argument was rejected, including an artifact for another curve.
+    InvalidInput,
+    /// A file could not be read or written.
+    Io,
+    /// The witness did not fit the circuit.
+    InvalidWitness,
+    /// The witness does not satisfy the circuit.
+    UnsatisfiedWitness,
+    /// A key is malformed or unusable.
+    InvalidKey,
+    /// A proof or artifact failed to (de)serialize.
+    Serialization,
+    /// A proof the call depends on does not verify.
+    InvalidProof,
+    /// The call was cancelled.
+    Cancelled,
+    /// The Go side ran out of memory.
+    OutOfMemory,
+    /// gnark panicked.
+    Panic,
+    /// Anything else, e.g. a proving service that could not be reached.
+    Other,
+}
+
+impl FailureCategory {
+    /// The category of an error returned by this crate.
+    pub fn of(err: &anyhow::Error) -> Self {
+        #[cfg(not(feature = "verify-only"))]
+        if let Some(err) = err.downcast_ref::<crate::GnarkError>() {
+            use crate::GnarkError;
+            return match err {
+                GnarkError::InvalidInput(_) | GnarkError::CurveMismatch(_) => Self::InvalidInput,
+                GnarkError::Io(_) => Self::Io,
+                GnarkError::InvalidWitness(_) => Self::InvalidWitness,
+                GnarkError::WitnessSolveFailed { .. } => Self::UnsatisfiedWitness,
+                GnarkError::InvalidKey(_) => Self::InvalidKey,
+                GnarkError::SerializationError(_) => Self::Serialization,
+                GnarkError::InvalidProof(_) => Self::InvalidProof,
+                GnarkError::Cancelled => Self::Cancelled,
+                GnarkError::OutOfMemory => Self::OutOfMemory,
+                GnarkError::InternalPanic { .. } => Self::Panic,
+                _ => Self::Other,
+            };
+        }
+        if err.chain().any(|cause| cause.is::<std::io::Error>()) {
+            return Self::Io;
+        }
+        Self::Other
+    }
+}
+
+/// A finished proof, or batch of proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProveEvent {
+    /// The proof system.
+    pub scheme: Scheme,
+    /// The proofs the call made: 1, or the size of a batch.
+    pub proofs: usize,
+    /// Whether a proving service made them, e.g. a `RemoteProver`.
+    pub remote: bool,
+    /// Wall time of the call.
+    pub duration: Duration,
+    /// Constraints of the circuit, if the prover loaded it.
+    pub nb_constraints: Option<u64>,
+    /// Peak memory of the Go runtime during the call, in bytes, for local
+    /// proofs. The runtime is shared, so this includes whatever else ran in
+    /// Go at the same time.
+    pub peak_memory: Option<u64>,
+    /// Why the call failed, `None` if it succeeded.
+    pub failure: Option<FailureCategory>,
+}
+
+/// A finished verification, or batch of verifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VerifyEvent {
+    /// The proof system.
+    pub scheme: Scheme,
+    /// The proofs the call checked: 1, or the size of a batch.
+    pub proofs: usize,
+    /// Whether another process checked the proof, e.g. an Ethereum node.
+    pub remote: bool,
+    /// Wall time of the call.
+    pub duration: Duration,
+    /// Whether the proof, or every proof of a batch, verified. An invalid
+    /// proof is not a failure.
+    pub valid: bool,
+    /// Why the call failed, `None` if it returned a verdict.
+    pub failure: Option<FailureCategory>,
+}
+
+/// Receives an event after every proof and verification.
+///
+/// Events are delivered on the thread that ran the call, after it finished
+/// and before it returns, so implementations should be quick: queue and send
+/// from elsewhere.
+pub trait Metrics: Send + Sync {
+    /// A proof, or batch of proofs, finished.
+    fn on_prove(&self, event: &ProveEvent);
+
+    /// A verification finished. Ignored by default.
+    fn on_verify(&self, event: &VerifyEvent) {
+        let _ = event;
+    }
+}
+
+static METRICS: RwLock<Option<Arc<dyn Metrics>>> = RwLock::new(None);
+
+/// Report every proof and verification to `metrics` from now on, replacing
+/// any [`Metrics`] installed before.
+pub fn set_metrics(metrics: impl Metrics + 'static) {
+    *METRICS.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(metrics));
+}
+
+/// Stop reporting proofs and verifications.
+pub fn clear_metrics() {
+    *METRICS.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn current() -> Option<Arc<dyn Metrics>> {
+    METRICS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Whether a [`Metrics`] is installed, to skip measurements nobody reads.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn enabled() -> bool {
+    METRICS.read().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+#[cfg(not(feature = "verify-only"))]
+thread_local! {
+    static NB_CONSTRAINTS: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Note the constraint count reported with a proof, for the event of the
+/// [`record_prove`] running on this thread.
+#[cfg(not(any(feature = "verify-only", feature = "verifier")))]
+pub(crate) fn note_constraints(nb_constraints: u64) {
+    if nb_constraints > 0 {
+        NB_CONSTRAINTS.with(|n| n.set(Some(nb_constraints)));
+    }
+}
+
+/// Run `prove`, which makes `proofs` proofs, and report it.
+#[cfg(not(feature = "verify-only"))]
+pub(crate) fn record_prove<T>(
+    scheme: Scheme,
+    proofs: usize,
+    remote: bool,
+    prove: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let Some(metrics) = current() else {
+        return prove();
+    };
+    let outer = NB_CONSTRAINTS.with(Cell::take);
+    let watch = (!remote).then(MemoryWatch::start);
+    let start = Instant::now();
+    let result = prove();
+    let duration = start.elapsed();
+    metrics.on_prove(&ProveEvent {
+        scheme,
+        proofs,
+        remote,
+        duration,
+        nb_constraints: NB_CONSTRAINTS.with(|n| n.replace(outer)),
+        peak_memory: watch.map(MemoryWatch::stop),
+        failure: result.as_ref().err().map(FailureCategory::of),
+    });
+    result
+}
+
+/// Run `verify` and report it.
+pub(crate) fn record_verify(
+    scheme: Scheme,
+    remote: bool,
+    verify: impl FnOnce() -> Result<bool>,
+) -> Result<bool> {
+    record_verify_with(scheme, remote, verify, |valid| *valid)
+}
+
+/// [`record_verify`] for a verification whose verdict `valid` extracts.
+pub(crate) fn record_verify_with<T>(
+    scheme: Scheme,
+    remote: bool,
+    verify: impl FnOnce() -> Result<T>,
+    valid: impl FnOnce(&T) -> bool,
+) -> Result<T> {
+    record_verify_batch(scheme, 1, remote, verify, valid)
+}
+
+/// [`record_verify_with`] for a call that checks `proofs` proofs.
+pub(crate) fn record_verify_batch<T>(
+    scheme: Scheme,
+    proofs: usize,
+    remote: bool,
+    verify: impl FnOnce() -> Result<T>,
+    valid: impl FnOnce(&T) -> bool,
+) -> Result<T> {
+    let Some(metrics) = current() else {
+        return verify();
+    };
+    let start = Instant::now();
+    let result = verify();
+    metrics.on_verify(&VerifyEvent {
+        scheme,
+        proofs,
+        remote,
+        duration: start.elapsed(),
+        valid: result.as_ref().is_ok_and(valid),
+        failure: result.as_ref().err().map(FailureCategory::of),
+    });
+    result
+}
+
+/// A watch of the Go runtime's peak memory (`go/memwatch.go`).
+#[cfg(not(feature = "verify-only"))]
+struct MemoryWatch(u64);
+
+#[cfg(not(feature = "verify-only"))]
+impl MemoryWatch {
+    fn start() -> Self {
+        let mut id = 0;
+        unsafe { crate::bind::gnark_memory_watch_start(&mut id) };
+        Self(id)
+    }
+
+    fn stop(self) -> u64 {
+        let mut peak = 0;
+        unsafe { crate::bind::gnark_memory_watch_stop(self.0, &mut peak) };
+        peak
+    }
+}
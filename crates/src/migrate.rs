@@ -0,0 +1,146 @@
+//! Bringing keys and proofs written by other gnark versions up to date.
+//!
+//! gnark changes its encodings between minor releases, so
+//! [`ProvingKey::load`](crate::ProvingKey::load) and
+//! [`VerifyingKey::load`](crate::VerifyingKey::load) refuse a key file
+//! recorded as written by another one. [`migrate`] decodes such a file with
+//! the linked gnark anyway and encodes it again: when that gives back the same
+//! bytes, the encoding did not change between the versions and the artifact
+//! is rewritten as current; when it does not, the artifact cannot be converted
+//! by this build and the error says what to redo.
+
+use anyhow::{Context, Result};
+use std::ffi::CString;
+use std::io::Read;
+use std::os::raw::{c_char, c_int};
+
+use crate::hash::Curve;
+use crate::{bind, take_go_string, ArtifactContent, GnarkError, GnarkProofBundle, GNARK_VERSION};
+
+/// Start of a proof bundle's header line.
+const BUNDLE_MAGIC: &[u8] = b"gnark-proof-bundle ";
+
+/// What [`migrate`] found and did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// What the file holds.
+    pub content: ArtifactContent,
+    /// The gnark version the file records, or `None` for a key written by
+    /// gnark itself, which records none.
+    pub from_gnark: Option<String>,
+    /// Whether the output was written; `false` if the input was already
+    /// current, in which case the output is left alone.
+    pub migrated: bool,
+}
+
+/// Convert the artifact at `input` to the format of this build, writing it
+/// to `output` (which may be `input`).
+///
+/// `input` is a versioned key file ([`ProvingKey::save`](crate::ProvingKey::save),
+/// [`VerifyingKey::save`](crate::VerifyingKey::save)), a key written by gnark
+/// itself, which becomes a versioned key file, or a [`GnarkProofBundle`].
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// let report = rust_gnark::migrate("circuit.pk", "circuit.pk")?;
+/// if report.migrated {
+///     println!("converted from gnark {:?}", report.from_gnark);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GnarkError::InvalidKey`] if the key's encoding changed since the
+/// gnark that wrote it, so that the setup has to be run again (or the key
+/// converted with a rust-gnark release built on that gnark), and
+/// [`GnarkError::InvalidProof`] for a proof that has to be generated again.
+/// Returns [`GnarkError::CurveMismatch`] for artifacts over other curves, and
+/// an error if a file cannot be read or written.
+pub fn migrate(input: &str, output: &str) -> Result<MigrationReport> {
+    let mut magic = Vec::with_capacity(BUNDLE_MAGIC.len());
+    std::fs::File::open(input)
+        .and_then(|f| f.take(BUNDLE_MAGIC.len() as u64).read_to_end(&mut magic))
+        .with_context(|| format!("failed to read {input}"))?;
+    if magic == BUNDLE_MAGIC {
+        migrate_bundle(input, output)
+    } else {
+        migrate_key(input, output)
+    }
+}
+
+fn migrate_key(input: &str, output: &str) -> Result<MigrationReport> {
+    let (c_input, c_output) = (CString::new(input)?, CString::new(output)?);
+    let (mut kind, mut from, mut migrated): (c_int, *mut c_char, c_int) =
+        (0, std::ptr::null_mut(), 0);
+    let err_ptr = unsafe {
+        bind::gnark_migrate_key(
+            c_input.as_ptr() as *mut c_char,
+            c_output.as_ptr() as *mut c_char,
+            &mut kind,
+            &mut from,
+            &mut migrated,
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    let from = unsafe { take_go_string(from) };
+    Ok(MigrationReport {
+        content: match kind {
+            1 => ArtifactContent::ProvingKey,
+            2 => ArtifactContent::VerifyingKey,
+            _ => unreachable!("gnark_migrate_key reported key kind {kind}"),
+        },
+        from_gnark: (!from.is_empty()).then_some(from),
+        migrated: migrated != 0,
+    })
+}
+
+fn migrate_bundle(input: &str, output: &str) -> Result<MigrationReport> {
+    let mut bundle = GnarkProofBundle::read(input)?;
+    if bundle.curve != Curve::Bn254 {
+        return Err(GnarkError::CurveMismatch(format!(
+            "{input} holds a proof that is not over bn254, which migrate does not convert"
+        ))
+        .into());
+    }
+    let proof = CString::new(bundle.proof.proof.as_str())?;
+    let (mut out, mut changed): (*mut c_char, c_int) = (std::ptr::null_mut(), 0);
+    let err_ptr =
+        unsafe { bind::gnark_migrate_proof(proof.as_ptr() as *mut c_char, &mut out, &mut changed) };
+    if !err_ptr.is_null() {
+        let err = unsafe { GnarkError::take(err_ptr) };
+        return Err(anyhow::Error::from(err).context(format!(
+            "the proof in {input} was generated with gnark {}",
+            bundle.gnark_version
+        )));
+    }
+    let migrated_proof = unsafe { take_go_string(out) };
+
+    let from = bundle.gnark_version.clone();
+    if changed == 0 && same_minor(&from, GNARK_VERSION) {
+        return Ok(MigrationReport {
+            content: ArtifactContent::Proof,
+            from_gnark: Some(from),
+            migrated: false,
+        });
+    }
+    bundle.proof.proof = migrated_proof;
+    bundle.crate_version = env!("CARGO_PKG_VERSION").into();
+    bundle.gnark_version = GNARK_VERSION.into();
+    bundle.write(output)?;
+    Ok(MigrationReport {
+        content: ArtifactContent::Proof,
+        from_gnark: Some(from),
+        migrated: true,
+    })
+}
+
+/// Whether gnark versions `a` and `b` share a major and minor version, as
+/// the key files' compatibility check in Go requires.
+fn same_minor(a: &str, b: &str) -> bool {
+    let minor = |v: &str| v.splitn(3, '.').take(2).collect::<Vec<_>>().join(".");
+    minor(a) == minor(b)
+}
@@ -0,0 +1,175 @@
+//! UniFFI bindings for Swift and Kotlin.
+//!
+//! With the `uniffi` feature the crate carries UniFFI scaffolding (namespace
+//! `rust_gnark`) for proving, verification, loaded provers, progress
+//! callbacks, the self-test and Go memory reporting. Link the crate into your app's `cdylib`/`staticlib` and generate
+//! the foreign bindings from that library:
+//!
+//! ```sh
+//! uniffi-bindgen generate --library target/release/libmyapp.so --language kotlin --out-dir out
+//! ```
+//!
+//! Errors surface as [`GnarkError`], whose variants map to exception cases in
+//! Swift and Kotlin, with the Go-side message as description.
+
+use std::sync::Arc;
+
+use crate::runtime::MemStats;
+use crate::{
+    GnarkError, Groth16ProofResult, Progress, ProgressReporter, ProverSession, SelfTestReport,
+};
+
+fn ffi_error(err: anyhow::Error) -> GnarkError {
+    err.downcast::<GnarkError>()
+        .unwrap_or_else(|err| GnarkError::Other(format!("{err:#}")))
+}
+
+/// Receives progress events while proving. Implemented on the foreign side.
+#[uniffi::export(with_foreign)]
+pub trait ProgressListener: Send + Sync {
+    /// Called for every progress event, on the proving thread.
+    fn on_progress(&self, progress: Progress);
+}
+
+struct ListenerReporter(Arc<dyn ProgressListener>);
+
+impl ProgressReporter for ListenerReporter {
+    fn on_progress(&self, progress: Progress) {
+        self.0.on_progress(progress);
+    }
+}
+
+/// Initialize the gnark Go runtime (see [`crate::init`]).
+#[uniffi::export]
+pub fn initialize() -> Result<(), GnarkError> {
+    crate::init().map_err(ffi_error)
+}
+
+/// Initialize the gnark Go runtime with settings for its signal handling
+/// (see [`crate::init_with_options`] and [`crate::runtime::InitOptions`]).
+/// `async_preemption` is `None` for the platform default.
+#[uniffi::export]
+pub fn initialize_with_options(
+    async_preemption: Option<bool>,
+    godebug: Vec<String>,
+    crash_on_fatal: bool,
+) -> Result<(), GnarkError> {
+    let mut options = crate::runtime::InitOptions::default().with_crash_on_fatal(crash_on_fatal);
+    if let Some(enabled) = async_preemption {
+        options = options.with_async_preemption(enabled);
+    }
+    for setting in godebug {
+        options = options.with_godebug(setting);
+    }
+    crate::init_with_options(&options).map_err(ffi_error)
+}
+
+/// Make signal handlers installed since initialization, e.g. by a crash
+/// reporter, safe for the Go runtime (see
+/// [`crate::runtime::fix_signal_stacks`]).
+#[uniffi::export]
+pub fn fix_signal_stacks() -> u64 {
+    crate::runtime::fix_signal_stacks() as u64
+}
+
+/// Bound the Go runtime's threads, `0` for one per CPU (see
+/// [`crate::runtime::set_thread_budget`]). Returns the previous budget.
+#[uniffi::export]
+pub fn set_thread_budget(threads: u32) -> u32 {
+    crate::runtime::set_thread_budget(threads as usize) as u32
+}
+
+/// Release the Go runtime's memory and idle threads between uses (see
+/// [`crate::shutdown`]), e.g. when the app moves to the background.
+#[uniffi::export]
+pub fn shutdown() {
+    crate::shutdown();
+}
+
+/// Check that the prover works on this device (see [`crate::self_test`]),
+/// e.g. at app startup.
+#[uniffi::export]
+pub fn self_test() -> Result<SelfTestReport, GnarkError> {
+    crate::self_test().map_err(ffi_error)
+}
+
+/// Generate a proof from `.r1cs` and `.pk` files (see [`crate::groth16_prove`]).
+#[uniffi::export]
+pub fn generate_proof(
+    r1cs_path: String,
+    pk_path: String,
+    witness_json: String,
+) -> Result<Groth16ProofResult, GnarkError> {
+    crate::groth16_prove(&r1cs_path, &pk_path, &witness_json).map_err(ffi_error)
+}
+
+/// [`generate_proof`], reporting progress to `listener`.
+#[uniffi::export]
+pub fn generate_proof_with_progress(
+    r1cs_path: String,
+    pk_path: String,
+    witness_json: String,
+    listener: Arc<dyn ProgressListener>,
+) -> Result<Groth16ProofResult, GnarkError> {
+    let reporter = ListenerReporter(listener);
+    crate::groth16_prove_with_progress(&r1cs_path, &pk_path, &witness_json, &reporter)
+        .map_err(ffi_error)
+}
+
+/// Verify `proof` (see [`crate::groth16_verify`]). Returns `false` for a
+/// well-formed proof that does not verify.
+#[uniffi::export]
+pub fn verify_proof(
+    r1cs_path: String,
+    vk_path: String,
+    proof: Groth16ProofResult,
+) -> Result<bool, GnarkError> {
+    crate::groth16_verify(&r1cs_path, &vk_path, &proof).map_err(ffi_error)
+}
+
+/// The Go runtime's memory statistics (see [`crate::runtime::read_mem_stats`]).
+#[uniffi::export]
+pub fn memory_stats() -> MemStats {
+    crate::runtime::read_mem_stats()
+}
+
+/// Return the Go runtime's free memory to the OS, e.g. after proving (see
+/// [`crate::runtime::free_os_memory`]).
+#[uniffi::export]
+pub fn free_os_memory() {
+    crate::runtime::free_os_memory();
+}
+
+/// A circuit and proving key loaded once for repeated proofs (a
+/// [`ProverSession`]). Safe to share between threads.
+#[derive(uniffi::Object)]
+pub struct Prover {
+    session: ProverSession,
+}
+
+#[uniffi::export]
+impl Prover {
+    /// Load the `.r1cs` and (trusted, raw) `.pk` files.
+    #[uniffi::constructor]
+    pub fn new(r1cs_path: String, pk_path: String) -> Result<Arc<Self>, GnarkError> {
+        let session = ProverSession::new(&r1cs_path, &pk_path).map_err(ffi_error)?;
+        Ok(Arc::new(Self { session }))
+    }
+
+    /// Generate a proof for `witness_json`.
+    pub fn prove(&self, witness_json: String) -> Result<Groth16ProofResult, GnarkError> {
+        self.session.prove(&witness_json).map_err(ffi_error)
+    }
+
+    /// [`Prover::prove`], reporting progress to `listener`.
+    pub fn prove_with_progress(
+        &self,
+        witness_json: String,
+        listener: Arc<dyn ProgressListener>,
+    ) -> Result<Groth16ProofResult, GnarkError> {
+        let reporter = ListenerReporter(listener);
+        self.session
+            .prove_with_progress(&witness_json, &reporter)
+            .map_err(ffi_error)
+    }
+}
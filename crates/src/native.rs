@@ -0,0 +1,1170 @@
+//! Pure-Rust Groth16 BN254 verifier for gnark-serialized artifacts.
+//!
+//! Enabled by the `native-verify` feature. Verification runs entirely in Rust
+//! (via arkworks) and does not touch the Go runtime, so it also works in
+//! `verify-only` builds where the gnark library is not linked at all.
+//!
+//! The decoders accept exactly what the Go side produces:
+//! - verifying keys written by `vk.WriteTo()` (compressed) or `vk.WriteRawTo()`
+//! - proofs written by `proof.WriteTo()` / `proof.WriteRawTo()`
+//! - public witnesses written by `witness.MarshalBinary()`
+//!
+//! Points are decoded following gnark-crypto's encoding: big-endian coordinates,
+//! with the two most significant bits of the first byte used as flags
+//! (compressed / uncompressed / infinity, and the sign of `y`). `G2` coordinates
+//! are written as `A1 || A0`.
+//!
+//! Keys and proofs with a Pedersen commitment (circuits using `api.Commit`,
+//! `std/multicommit` or range checks) are supported when the proof was made
+//! with the default [`crate::HashFunction`]. Circuits with more than one
+//! commitment are rejected; verify those with [`crate::groth16_verify`].
+
+use anyhow::{bail, ensure, Context, Result};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::CurveGroup;
+use ark_ff::{BigInt, Field, One, PrimeField, Zero};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+use crate::metrics::{self, Scheme};
+use crate::public_inputs::public_witness_bytes;
+use crate::Groth16ProofResult;
+
+const M_MASK: u8 = 0b11 << 6;
+const M_UNCOMPRESSED: u8 = 0b00 << 6;
+const M_COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const M_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+const M_COMPRESSED_INFINITY: u8 = 0b01 << 6;
+
+const FQ_SIZE: usize = 32;
+const FR_SIZE: usize = 32;
+
+/// Domain separation tag of gnark's default commitment hash
+/// (`constraint.CommitmentDst`).
+const COMMITMENT_DST: &[u8] = b"bsb22-commitment";
+
+/// A gnark Groth16 BN254 verifying key, decoded into arkworks types.
+#[derive(Debug, Clone)]
+pub struct VerifyingKey {
+    /// `[α]1`
+    pub alpha_g1: G1Affine,
+    /// `[β]1`, unused by verification but kept so the key round-trips.
+    pub beta_g1: G1Affine,
+    /// `[β]2`
+    pub beta_g2: G2Affine,
+    /// `[γ]2`
+    pub gamma_g2: G2Affine,
+    /// `[δ]1`, unused by verification but kept so the key round-trips.
+    pub delta_g1: G1Affine,
+    /// `[δ]2`
+    pub delta_g2: G2Affine,
+    /// `[Kvk]1`, one point per public input plus the constant wire, then one
+    /// per commitment.
+    pub k: Vec<G1Affine>,
+    /// For each commitment, the indices of the public inputs it commits to,
+    /// counting the constant wire as 0.
+    pub public_and_commitment_committed: Vec<Vec<u64>>,
+    /// For each commitment, the key its proof of knowledge is checked with.
+    pub commitment_keys: Vec<CommitmentKey>,
+}
+
+/// A Pedersen commitment verifying key (gnark-crypto's `pedersen.VerifyingKey`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitmentKey {
+    /// `G`, the G2 generator of the commitment scheme.
+    pub g: G2Affine,
+    /// `GSigmaNeg`, `G` scaled by the negated secret `σ`.
+    pub g_sigma_neg: G2Affine,
+}
+
+/// A gnark Groth16 BN254 proof, decoded into arkworks types.
+#[derive(Debug, Clone)]
+pub struct Proof {
+    /// `[A]1`
+    pub ar: G1Affine,
+    /// `[B]2`
+    pub bs: G2Affine,
+    /// `[C]1`
+    pub krs: G1Affine,
+    /// Pedersen commitments to committed witness values, one per
+    /// commitment of the circuit.
+    pub commitments: Vec<G1Affine>,
+    /// Proof of knowledge of the commitments' openings; the point at infinity
+    /// without commitments.
+    pub commitment_pok: G1Affine,
+}
+
+impl VerifyingKey {
+    /// Decode a verifying key from gnark's binary serialization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are truncated, contain invalid points, or
+    /// describe a key with more than one Pedersen commitment (not supported by
+    /// this verifier).
+    pub fn from_gnark_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader::new(bytes);
+        let alpha_g1 = r.g1().context("vk: [α]1")?;
+        let beta_g1 = r.g1().context("vk: [β]1")?;
+        let beta_g2 = r.g2().context("vk: [β]2")?;
+        let gamma_g2 = r.g2().context("vk: [γ]2")?;
+        let delta_g1 = r.g1().context("vk: [δ]1")?;
+        let delta_g2 = r.g2().context("vk: [δ]2")?;
+
+        let nb_k = r.u32().context("vk: len(K)")? as usize;
+        let k = (0..nb_k)
+            .map(|i| r.g1().with_context(|| format!("vk: K[{i}]")))
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            !k.is_empty(),
+            "vk: K must contain at least the constant wire"
+        );
+
+        // Trailing commitment sections (absent in older serializations).
+        let mut public_and_commitment_committed = Vec::new();
+        let mut commitment_keys = Vec::new();
+        if !r.is_empty() {
+            let nb_committed = r.u32().context("vk: len(PublicAndCommitmentCommitted)")?;
+            for i in 0..nb_committed {
+                let len = r.u32()?;
+                let committed = (0..len)
+                    .map(|_| r.u64())
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("vk: PublicAndCommitmentCommitted[{i}]"))?;
+                public_and_commitment_committed.push(committed);
+            }
+            let nb_keys = r.u32().context("vk: len(CommitmentKeys)")?;
+            for i in 0..nb_keys {
+                commitment_keys.push(CommitmentKey {
+                    g: r.g2()
+                        .with_context(|| format!("vk: CommitmentKeys[{i}].G"))?,
+                    g_sigma_neg: r
+                        .g2()
+                        .with_context(|| format!("vk: CommitmentKeys[{i}].GSigmaNeg"))?,
+                });
+            }
+        }
+        ensure!(
+            commitment_keys.len() == public_and_commitment_committed.len(),
+            "vk: {} commitment keys for {} commitments",
+            commitment_keys.len(),
+            public_and_commitment_committed.len()
+        );
+        if commitment_keys.len() > 1 {
+            bail!(
+                "vk uses {} Pedersen commitments; the native verifier supports at most one",
+                commitment_keys.len()
+            );
+        }
+        ensure!(
+            k.len() > commitment_keys.len(),
+            "vk: K must contain the constant wire and one point per commitment"
+        );
+        let nb_public = k.len() - commitment_keys.len();
+        for committed in &public_and_commitment_committed {
+            ensure!(
+                committed.iter().all(|&j| j >= 1 && j < nb_public as u64),
+                "vk: commitment to a wire that is not a public input"
+            );
+        }
+
+        Ok(Self {
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g1,
+            delta_g2,
+            k,
+            public_and_commitment_committed,
+            commitment_keys,
+        })
+    }
+
+    /// Encode the key in gnark's compressed binary format (`vk.WriteTo()`).
+    pub fn to_gnark_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.g1(&self.alpha_g1);
+        w.g1(&self.beta_g1);
+        w.g2(&self.beta_g2);
+        w.g2(&self.gamma_g2);
+        w.g1(&self.delta_g1);
+        w.g2(&self.delta_g2);
+        w.u32(self.k.len() as u32);
+        for k in &self.k {
+            w.g1(k);
+        }
+        w.u32(self.public_and_commitment_committed.len() as u32);
+        for committed in &self.public_and_commitment_committed {
+            w.u32(committed.len() as u32);
+            for &j in committed {
+                w.u64(j);
+            }
+        }
+        w.u32(self.commitment_keys.len() as u32);
+        for key in &self.commitment_keys {
+            w.g2(&key.g);
+            w.g2(&key.g_sigma_neg);
+        }
+        w.buf
+    }
+}
+
+impl Proof {
+    /// Decode a proof from gnark's binary serialization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are truncated or contain invalid points.
+    pub fn from_gnark_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader::new(bytes);
+        let ar = r.g1().context("proof: Ar")?;
+        let bs = r.g2().context("proof: Bs")?;
+        let krs = r.g1().context("proof: Krs")?;
+
+        let mut commitments = Vec::new();
+        let mut commitment_pok = G1Affine::identity();
+        if !r.is_empty() {
+            let nb_commitments = r.u32().context("proof: len(Commitments)")?;
+            for i in 0..nb_commitments {
+                commitments.push(r.g1().with_context(|| format!("proof: Commitments[{i}]"))?);
+            }
+            commitment_pok = r.g1().context("proof: CommitmentPok")?;
+        }
+
+        Ok(Self {
+            ar,
+            bs,
+            krs,
+            commitments,
+            commitment_pok,
+        })
+    }
+
+    /// Decode a proof, accepting only the canonical encoding:
+    /// [`Proof::to_gnark_bytes`] of the points decoded, byte for byte.
+    ///
+    /// [`Proof::from_gnark_bytes`] also accepts uncompressed points,
+    /// `(0, 0)` and non-zero padding for the point at infinity, a proof
+    /// without the commitment section and trailing bytes, so one proof has
+    /// many encodings; this gives it exactly one. Every point must also be in
+    /// its prime-order subgroup.
+    ///
+    /// # Errors
+    ///
+    /// As [`Proof::from_gnark_bytes`], and if the encoding is not canonical.
+    pub fn from_gnark_bytes_strict(bytes: &[u8]) -> Result<Self> {
+        let proof = Self::from_gnark_bytes(bytes)?;
+        let g1 = [("Ar", &proof.ar), ("Krs", &proof.krs)]
+            .into_iter()
+            .chain(proof.commitments.iter().map(|c| ("Commitments", c)))
+            .chain([("CommitmentPok", &proof.commitment_pok)]);
+        for (name, p) in g1 {
+            // Trivially true on BN254, whose G1 has cofactor 1; checked so the
+            // guarantee does not rest on that.
+            ensure!(
+                p.is_in_correct_subgroup_assuming_on_curve(),
+                "proof: {name} is not in the prime-order subgroup"
+            );
+        }
+        let canonical = proof.to_gnark_bytes();
+        ensure!(
+            bytes.len() == canonical.len(),
+            "proof: non-canonical encoding of {} bytes, expected {}",
+            bytes.len(),
+            canonical.len()
+        );
+        if let Some(at) = bytes.iter().zip(&canonical).position(|(a, b)| a != b) {
+            bail!("proof: non-canonical encoding at byte {at}");
+        }
+        Ok(proof)
+    }
+
+    /// Encode the proof in gnark's compressed binary format (`proof.WriteTo()`).
+    pub fn to_gnark_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.g1(&self.ar);
+        w.g2(&self.bs);
+        w.g1(&self.krs);
+        w.u32(self.commitments.len() as u32);
+        for c in &self.commitments {
+            w.g1(c);
+        }
+        w.g1(&self.commitment_pok);
+        w.buf
+    }
+}
+
+/// Public inputs of a proof, in circuit order (without the constant wire).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicInputs(pub Vec<Fr>);
+
+impl PublicInputs {
+    /// Decode a public witness from gnark's `witness.MarshalBinary()` format.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`public_inputs_from_gnark_bytes`].
+    pub fn from_gnark_bytes(bytes: &[u8]) -> Result<Self> {
+        public_inputs_from_gnark_bytes(bytes).map(Self)
+    }
+
+    /// Encode in gnark's `witness.MarshalBinary()` format.
+    pub fn to_gnark_bytes(&self) -> Vec<u8> {
+        let inputs: Vec<crate::Fr> = self.0.iter().map(|&x| x.into()).collect();
+        public_witness_bytes(&inputs)
+    }
+
+    /// The hash these inputs are committed to by a circuit built with
+    /// `CircuitBuilder::hash_public_inputs`, which is the proof's only public
+    /// input: SHA-256 over the inputs' 32-byte big-endian encodings, modulo
+    /// `2^253`. Equal to `rust_gnark::public_inputs_hash`.
+    pub fn commitment(&self) -> Fr {
+        let mut hasher = Sha256::new();
+        for x in &self.0 {
+            hasher.update(fr_to_gnark_bytes(x));
+        }
+        let mut digest: [u8; 32] = hasher.finalize().into();
+        digest[0] &= 0x1f;
+        Fr::from_be_bytes_mod_order(&digest)
+    }
+}
+
+/// Decode a public witness from gnark's `witness.MarshalBinary()` format.
+///
+/// Layout: `u32 nbPublic || u32 nbSecret || u32 len || len * Fr (32 bytes, big-endian)`.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are truncated, the witness is not public-only,
+/// or an element is not a canonical field element.
+pub fn public_inputs_from_gnark_bytes(bytes: &[u8]) -> Result<Vec<Fr>> {
+    let mut r = Reader::new(bytes);
+    let nb_public = r.u32().context("public witness: nbPublic")?;
+    let nb_secret = r.u32().context("public witness: nbSecret")?;
+    let len = r.u32().context("public witness: vector length")?;
+    ensure!(
+        nb_secret == 0 && nb_public == len,
+        "expected a public-only witness, got nbPublic={nb_public} nbSecret={nb_secret} len={len}"
+    );
+    (0..len)
+        .map(|i| {
+            r.fr()
+                .with_context(|| format!("public witness: element {i}"))
+        })
+        .collect()
+}
+
+/// Decode one scalar field element from gnark's encoding (32 bytes, big-endian).
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not a canonical field element.
+pub fn fr_from_gnark_bytes(bytes: &[u8; FR_SIZE]) -> Result<Fr> {
+    Reader::new(bytes).fr()
+}
+
+/// Encode one scalar field element the way gnark does (32 bytes, big-endian).
+pub fn fr_to_gnark_bytes(x: &Fr) -> [u8; FR_SIZE] {
+    be_bytes(x.into_bigint())
+}
+
+/// Decode one G1 point from gnark's encoding, compressed or uncompressed
+/// (e.g. a [`crate::Kzg`] commitment or an [`crate::ecc::msm_g1`] result).
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not exactly one point on the curve.
+pub fn g1_from_gnark_bytes(bytes: &[u8]) -> Result<G1Affine> {
+    let mut r = Reader::new(bytes);
+    let p = r.g1()?;
+    ensure!(r.is_empty(), "trailing bytes after G1 point");
+    Ok(p)
+}
+
+/// Encode one G1 point uncompressed, as gnark-crypto's `G1Affine.RawBytes`.
+pub(crate) fn g1_to_gnark_raw_bytes(p: &G1Affine) -> [u8; 2 * FQ_SIZE] {
+    let mut out = [0u8; 2 * FQ_SIZE];
+    if !p.infinity {
+        out[..FQ_SIZE].copy_from_slice(&be_bytes(p.x.into_bigint()));
+        out[FQ_SIZE..].copy_from_slice(&be_bytes(p.y.into_bigint()));
+    }
+    out
+}
+
+/// Verify a Groth16 BN254 proof without the Go runtime.
+///
+/// Counterpart of [`crate::groth16_verify`] that only needs the verifying key;
+/// the constraint system is not required for verification.
+///
+/// # Arguments
+///
+/// * `vk_path` - Path to the `.vk` file written by gnark
+/// * `proof_result` - The hex-encoded proof and public inputs
+///
+/// # Returns
+///
+/// `Ok(true)` if the proof is valid, `Ok(false)` if the pairing check fails.
+///
+/// # Errors
+///
+/// Returns an error if file loading or deserialization fails.
+pub fn groth16_verify(vk_path: &str, proof_result: &Groth16ProofResult) -> Result<bool> {
+    let vk = std::fs::read(vk_path).with_context(|| format!("failed to read vk file {vk_path}"))?;
+    groth16_verify_with_vk_bytes(&vk, proof_result)
+}
+
+/// Verify a Groth16 BN254 proof against an in-memory verifying key.
+///
+/// Same as [`groth16_verify`] for environments without a filesystem (e.g. WASM).
+///
+/// # Errors
+///
+/// Returns an error if hex decoding or deserialization fails.
+pub fn groth16_verify_with_vk_bytes(vk: &[u8], proof_result: &Groth16ProofResult) -> Result<bool> {
+    let proof = decode_hex(&proof_result.proof).context("failed to decode proof hex")?;
+    let public_inputs =
+        decode_hex(&proof_result.public_inputs).context("failed to decode public inputs hex")?;
+    groth16_verify_bytes(vk, &proof, &public_inputs)
+}
+
+/// Verify a Groth16 BN254 proof from raw gnark-serialized bytes.
+///
+/// # Returns
+///
+/// `Ok(true)` if the proof is valid, `Ok(false)` if the pairing check fails.
+///
+/// # Errors
+///
+/// Returns an error if any of the inputs fail to deserialize, or if the number
+/// of public inputs does not match the verifying key.
+pub fn groth16_verify_bytes(vk: &[u8], proof: &[u8], public_inputs: &[u8]) -> Result<bool> {
+    let vk = VerifyingKey::from_gnark_bytes(vk)?;
+    let proof = Proof::from_gnark_bytes(proof)?;
+    let public_inputs = public_inputs_from_gnark_bytes(public_inputs)?;
+    verify_proof(&vk, &proof, &public_inputs)
+}
+
+/// Check the Groth16 pairing equation
+/// `e(A, B) · e(-Σ Kᵢ·xᵢ, γ) · e(-C, δ) · e(-α, β) == 1`.
+///
+/// For a key with a commitment, the proof's commitment `D` and its hash `h`
+/// extend the public inputs (the sum becomes `Σ Kᵢ·xᵢ + K·h + D`), and the
+/// proof of knowledge is checked with `e(D, GSigmaNeg) · e(pok, G) == 1`.
+///
+/// # Errors
+///
+/// Returns an error if the number of public inputs or commitments does not
+/// match the verifying key.
+pub fn verify_proof(vk: &VerifyingKey, proof: &Proof, public_inputs: &[Fr]) -> Result<bool> {
+    metrics::record_verify(Scheme::Groth16, false, || {
+        let acc = public_input_acc(vk, proof, public_inputs)?;
+        Ok(commitments_hold(vk, proof) && pairing_holds(vk, proof, acc))
+    })
+}
+
+/// The pairing equation, given the public-input term from [`public_input_acc`].
+fn pairing_holds(vk: &VerifyingKey, proof: &Proof, acc: G1Projective) -> bool {
+    let out = Bn254::multi_pairing(
+        [proof.ar, (-acc).into_affine(), -proof.krs, -vk.alpha_g1],
+        [proof.bs, vk.gamma_g2, vk.delta_g2, vk.beta_g2],
+    );
+    out.0.is_one()
+}
+
+/// `K₀ + Σ Kᵢ₊₁·xᵢ`, the public-input term of the pairing equation, where the
+/// `xᵢ` are followed by the commitment hashes, plus the commitments.
+fn public_input_acc(
+    vk: &VerifyingKey,
+    proof: &Proof,
+    public_inputs: &[Fr],
+) -> Result<G1Projective> {
+    let nb_commitments = vk.commitment_keys.len();
+    ensure!(
+        public_inputs.len() + nb_commitments + 1 == vk.k.len(),
+        "expected {} public inputs, got {}",
+        vk.k.len() - nb_commitments - 1,
+        public_inputs.len()
+    );
+    ensure!(
+        proof.commitments.len() == nb_commitments,
+        "expected {nb_commitments} commitments in the proof, got {}",
+        proof.commitments.len()
+    );
+
+    let mut acc = G1Projective::from(vk.k[0]);
+    for (k, x) in vk.k[1..].iter().zip(public_inputs) {
+        acc += *k * x;
+    }
+    let offset = 1 + public_inputs.len();
+    for (i, (commitment, committed)) in proof
+        .commitments
+        .iter()
+        .zip(&vk.public_and_commitment_committed)
+        .enumerate()
+    {
+        let committed: Vec<Fr> = committed
+            .iter()
+            .map(|&j| public_inputs[j as usize - 1])
+            .collect();
+        acc += vk.k[offset + i] * commitment_hash(commitment, &committed);
+        acc += commitment;
+    }
+    Ok(acc)
+}
+
+/// Whether the proof of knowledge of the commitment openings is valid
+/// (trivially, without commitments).
+fn commitments_hold(vk: &VerifyingKey, proof: &Proof) -> bool {
+    match (vk.commitment_keys.as_slice(), proof.commitments.as_slice()) {
+        ([], []) => true,
+        ([key], [commitment]) => Bn254::multi_pairing(
+            [*commitment, proof.commitment_pok],
+            [key.g_sigma_neg, key.g],
+        )
+        .0
+        .is_one(),
+        _ => false,
+    }
+}
+
+/// gnark's default hash of a commitment and the public inputs it commits to:
+/// RFC 9380 `hash_to_field` (SHA-256 `expand_message_xmd`, 48 bytes reduced
+/// modulo r) over the uncompressed commitment and the inputs.
+fn commitment_hash(commitment: &G1Affine, committed: &[Fr]) -> Fr {
+    let mut msg = g1_to_gnark_raw_bytes(commitment).to_vec();
+    for x in committed {
+        msg.extend_from_slice(&fr_to_gnark_bytes(x));
+    }
+    Fr::from_be_bytes_mod_order(&expand_message_xmd(&msg, COMMITMENT_DST, 48))
+}
+
+/// RFC 9380 `expand_message_xmd` with SHA-256, for outputs of at most 255
+/// blocks.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let ell = len.div_ceil(32);
+    debug_assert!(ell <= 255 && dst.len() <= 255);
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let b0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut bi = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut out = bi.to_vec();
+    for i in 2..=ell {
+        let xored: Vec<u8> = b0.iter().zip(&bi).map(|(a, b)| a ^ b).collect();
+        bi = Sha256::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        out.extend_from_slice(&bi);
+    }
+    out.truncate(len);
+    out
+}
+
+/// Bounds on untrusted input for [`Verifier::verify_with_limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The most public inputs a witness may declare.
+    pub max_public_inputs: usize,
+    /// How long verification may take, or `None` for no limit.
+    pub timeout: Option<Duration>,
+}
+
+/// Verifies proofs against a single decoded verifying key.
+///
+/// ```no_run
+/// # fn example(results: &[rust_gnark::Groth16ProofResult]) -> anyhow::Result<()> {
+/// let verifier = rust_gnark::native::Verifier::from_gnark_bytes(&std::fs::read("circuit.vk")?)?;
+/// let failed = verifier.verify_batch_results(results)?;
+/// assert!(failed.is_empty(), "invalid proofs at {failed:?}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    vk: VerifyingKey,
+}
+
+impl Verifier {
+    /// Create a verifier for `vk`.
+    pub fn new(vk: VerifyingKey) -> Self {
+        Self { vk }
+    }
+
+    /// Create a verifier from a gnark-serialized verifying key.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VerifyingKey::from_gnark_bytes`].
+    pub fn from_gnark_bytes(vk: &[u8]) -> Result<Self> {
+        VerifyingKey::from_gnark_bytes(vk).map(Self::new)
+    }
+
+    /// The verifying key proofs are checked against.
+    pub fn vk(&self) -> &VerifyingKey {
+        &self.vk
+    }
+
+    /// Verify a single proof, see [`verify_proof`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of public inputs does not match the verifying key.
+    pub fn verify(&self, proof: &Proof, public_inputs: &[Fr]) -> Result<bool> {
+        verify_proof(&self.vk, proof, public_inputs)
+    }
+
+    /// Verify gnark-serialized untrusted input within `limits`.
+    ///
+    /// The sizes are checked before anything is decoded, so a length prefix
+    /// claiming billions of inputs is rejected instead of allocated. The
+    /// timeout is checked between decoding, the public-input sum, the
+    /// commitment check and the pairing; with the inputs bounded, each step
+    /// takes bounded time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input exceeds a limit or the deadline passes,
+    /// and otherwise as [`groth16_verify_bytes`].
+    pub fn verify_with_limits(
+        &self,
+        proof: &[u8],
+        public_inputs: &[u8],
+        limits: Limits,
+    ) -> Result<bool> {
+        metrics::record_verify(Scheme::Groth16, false, || {
+            let start = Instant::now();
+            let deadline = |step: &str| -> Result<()> {
+                if let Some(timeout) = limits.timeout {
+                    ensure!(
+                        start.elapsed() <= timeout,
+                        "verification timed out after {step} (limit {timeout:?})"
+                    );
+                }
+                Ok(())
+            };
+
+            let max = limits.max_public_inputs;
+            ensure!(
+                public_inputs.len() <= max.saturating_mul(FR_SIZE).saturating_add(12),
+                "public witness of {} bytes exceeds the limit of {max} inputs",
+                public_inputs.len()
+            );
+            if let Some(len) = public_inputs.get(8..12) {
+                let len = u32::from_be_bytes(len.try_into().expect("4 bytes")) as usize;
+                ensure!(len <= max, "{len} public inputs exceed the limit of {max}");
+            }
+            // Uncompressed Ar, Bs and Krs, then the commitments and their proof
+            // of knowledge.
+            let max_proof = 8 * FQ_SIZE + 4 + (self.vk.commitment_keys.len() + 1) * 2 * FQ_SIZE;
+            ensure!(
+                proof.len() <= max_proof,
+                "proof of {} bytes exceeds the {max_proof} bytes the key allows",
+                proof.len()
+            );
+
+            let proof = Proof::from_gnark_bytes(proof)?;
+            let public_inputs = public_inputs_from_gnark_bytes(public_inputs)?;
+            deadline("decoding")?;
+            let acc = public_input_acc(&self.vk, &proof, &public_inputs)?;
+            deadline("the public-input sum")?;
+            if !commitments_hold(&self.vk, &proof) {
+                return Ok(false);
+            }
+            deadline("the commitment check")?;
+            Ok(pairing_holds(&self.vk, &proof, acc))
+        })
+    }
+
+    /// Verify gnark-serialized input, rejecting any but the canonical
+    /// encoding of the proof (see [`Proof::from_gnark_bytes_strict`]) and of
+    /// the public witness, for callers that key on the bytes, e.g. to
+    /// deduplicate proofs.
+    ///
+    /// This makes the encoding of a proof unique, not the proof: anyone
+    /// holding a valid Groth16 proof can re-randomize it into another valid
+    /// proof of the same statement. A nullifier belongs in the public inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an encoding is not canonical, and otherwise as
+    /// [`groth16_verify_bytes`].
+    pub fn verify_strict(&self, proof: &[u8], public_inputs: &[u8]) -> Result<bool> {
+        let proof = Proof::from_gnark_bytes_strict(proof)?;
+        let inputs = PublicInputs::from_gnark_bytes(public_inputs)?;
+        ensure!(
+            inputs.to_gnark_bytes() == public_inputs,
+            "public witness: non-canonical encoding"
+        );
+        self.verify(&proof, &inputs.0)
+    }
+
+    /// Verify many proofs at once and return the indices of the invalid ones.
+    ///
+    /// All proofs are folded into one multi-pairing with random 128-bit
+    /// coefficients rᵢ, checking
+    /// `Π e(rᵢ·Aᵢ, Bᵢ) · e(-Σ rᵢ·accᵢ, γ) · e(-Σ rᵢ·Cᵢ, δ) · e(-(Σ rᵢ)·α, β) == 1`,
+    /// which costs `n + 3` Miller loops and a single final exponentiation
+    /// instead of `4n` and `n`. If the combined check fails, the batch is
+    /// bisected until the failing proofs are isolated. Entries whose
+    /// public-input count does not match the key, or whose commitment proof of
+    /// knowledge is invalid (checked one by one), are reported as failed.
+    ///
+    /// An empty result means every proof is valid, except with probability
+    /// about `2⁻¹²⁸` per check.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the OS random number generator fails.
+    pub fn verify_batch(&self, batch: &[(Proof, Vec<Fr>)]) -> Result<Vec<usize>> {
+        metrics::record_verify_batch(
+            Scheme::Groth16,
+            batch.len(),
+            false,
+            || self.batch_failures(batch),
+            Vec::is_empty,
+        )
+    }
+
+    /// [`Verifier::verify_batch`] over hex-encoded prover output.
+    ///
+    /// Entries that fail to decode are reported as failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the OS random number generator fails.
+    pub fn verify_batch_results(&self, results: &[Groth16ProofResult]) -> Result<Vec<usize>> {
+        metrics::record_verify_batch(
+            Scheme::Groth16,
+            results.len(),
+            false,
+            || {
+                let mut failed = Vec::new();
+                let mut batch = Vec::with_capacity(results.len());
+                let mut indices = Vec::with_capacity(results.len());
+                for (i, result) in results.iter().enumerate() {
+                    match decode_result(result) {
+                        Ok(entry) => {
+                            batch.push(entry);
+                            indices.push(i);
+                        }
+                        Err(_) => failed.push(i),
+                    }
+                }
+
+                failed.extend(self.batch_failures(&batch)?.into_iter().map(|j| indices[j]));
+                failed.sort_unstable();
+                Ok(failed)
+            },
+            Vec::is_empty,
+        )
+    }
+
+    /// [`Verifier::verify_batch`], unreported.
+    fn batch_failures(&self, batch: &[(Proof, Vec<Fr>)]) -> Result<Vec<usize>> {
+        let mut failed = Vec::new();
+        let mut prepared = Vec::with_capacity(batch.len());
+        for (i, (proof, public_inputs)) in batch.iter().enumerate() {
+            match public_input_acc(&self.vk, proof, public_inputs) {
+                Ok(acc) if commitments_hold(&self.vk, proof) => prepared.push((i, proof, acc)),
+                _ => failed.push(i),
+            }
+        }
+
+        let mut pending = vec![prepared.as_slice()];
+        while let Some(group) = pending.pop() {
+            if group.is_empty() || self.batch_holds(group)? {
+                continue;
+            }
+            if let [(i, _, _)] = group {
+                failed.push(*i);
+            } else {
+                let (left, right) = group.split_at(group.len() / 2);
+                pending.push(right);
+                pending.push(left);
+            }
+        }
+
+        failed.sort_unstable();
+        Ok(failed)
+    }
+
+    /// Random linear combination check over `group`.
+    fn batch_holds(&self, group: &[(usize, &Proof, G1Projective)]) -> Result<bool> {
+        if let [(_, proof, acc)] = group {
+            let out = Bn254::multi_pairing(
+                [
+                    proof.ar,
+                    (-*acc).into_affine(),
+                    -proof.krs,
+                    -self.vk.alpha_g1,
+                ],
+                [
+                    proof.bs,
+                    self.vk.gamma_g2,
+                    self.vk.delta_g2,
+                    self.vk.beta_g2,
+                ],
+            );
+            return Ok(out.0.is_one());
+        }
+
+        let mut g1 = Vec::with_capacity(group.len() + 3);
+        let mut g2 = Vec::with_capacity(group.len() + 3);
+        let mut sum_r = Fr::zero();
+        let mut sum_acc = G1Projective::zero();
+        let mut sum_krs = G1Projective::zero();
+        for (_, proof, acc) in group {
+            let r = random_coefficient()?;
+            g1.push((proof.ar * r).into_affine());
+            g2.push(proof.bs);
+            sum_r += r;
+            sum_acc += *acc * r;
+            sum_krs += proof.krs * r;
+        }
+        g1.extend([
+            (-sum_acc).into_affine(),
+            (-sum_krs).into_affine(),
+            (-(self.vk.alpha_g1 * sum_r)).into_affine(),
+        ]);
+        g2.extend([self.vk.gamma_g2, self.vk.delta_g2, self.vk.beta_g2]);
+
+        Ok(Bn254::multi_pairing(g1, g2).0.is_one())
+    }
+}
+
+fn decode_result(result: &Groth16ProofResult) -> Result<(Proof, Vec<Fr>)> {
+    let proof = Proof::from_gnark_bytes(&decode_hex(&result.proof)?)?;
+    let public_inputs = public_inputs_from_gnark_bytes(&decode_hex(&result.public_inputs)?)?;
+    Ok((proof, public_inputs))
+}
+
+/// A uniformly random non-zero 128-bit scalar from the OS RNG.
+fn random_coefficient() -> Result<Fr> {
+    loop {
+        let mut buf = [0u8; 16];
+        getrandom::getrandom(&mut buf)
+            .map_err(|e| anyhow::anyhow!("failed to get random coefficients: {e}"))?;
+        let r = u128::from_le_bytes(buf);
+        if r != 0 {
+            return Ok(Fr::from(r));
+        }
+    }
+}
+
+/// Decode a hex string (as produced by Go's `hex.EncodeToString`).
+pub(crate) fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    ensure!(s.len().is_multiple_of(2), "odd-length hex string");
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let hi = hex_nibble(pair[0])?;
+            let lo = hex_nibble(pair[1])?;
+            Ok((hi << 4) | lo)
+        })
+        .collect()
+}
+
+fn hex_nibble(c: u8) -> Result<u8> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => bail!("invalid hex character {:?}", c as char),
+    }
+}
+
+/// Cursor over gnark-crypto encoder output.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    fn peek(&self) -> Result<u8> {
+        self.buf.first().copied().context("unexpected end of input")
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        ensure!(self.buf.len() >= n, "unexpected end of input");
+        let (head, tail) = self.buf.split_at(n);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let b = self.take(8)?;
+        Ok(u64::from_be_bytes(b.try_into().expect("8 bytes")))
+    }
+
+    fn fr(&mut self) -> Result<Fr> {
+        let b = self.take(FR_SIZE)?;
+        Fr::from_bigint(be_bigint(b)).context("non-canonical scalar field element")
+    }
+
+    fn g1(&mut self) -> Result<G1Affine> {
+        let flag = self.peek()? & M_MASK;
+        let point = match flag {
+            M_COMPRESSED_INFINITY => {
+                self.take(FQ_SIZE)?;
+                return Ok(G1Affine::identity());
+            }
+            M_UNCOMPRESSED => {
+                let x = fq(self.take(FQ_SIZE)?)?;
+                let y = fq(self.take(FQ_SIZE)?)?;
+                if x.is_zero() && y.is_zero() {
+                    return Ok(G1Affine::identity());
+                }
+                G1Affine::new_unchecked(x, y)
+            }
+            _ => {
+                let x = fq(&masked(self.take(FQ_SIZE)?))?;
+                let rhs = x.square() * x + ark_bn254::g1::Config::COEFF_B;
+                let y = rhs.sqrt().context("G1 x-coordinate is not on the curve")?;
+                G1Affine::new_unchecked(x, select_y(y, flag))
+            }
+        };
+        ensure!(point.is_on_curve(), "G1 point is not on the curve");
+        Ok(point)
+    }
+
+    fn g2(&mut self) -> Result<G2Affine> {
+        let flag = self.peek()? & M_MASK;
+        let point = match flag {
+            M_COMPRESSED_INFINITY => {
+                self.take(2 * FQ_SIZE)?;
+                return Ok(G2Affine::identity());
+            }
+            M_UNCOMPRESSED => {
+                let x = fq2(self.take(2 * FQ_SIZE)?)?;
+                let y = fq2(self.take(2 * FQ_SIZE)?)?;
+                if x.is_zero() && y.is_zero() {
+                    return Ok(G2Affine::identity());
+                }
+                G2Affine::new_unchecked(x, y)
+            }
+            _ => {
+                let x = fq2(&masked(self.take(2 * FQ_SIZE)?))?;
+                let rhs = x.square() * x + ark_bn254::g2::Config::COEFF_B;
+                let y = rhs.sqrt().context("G2 x-coordinate is not on the curve")?;
+                G2Affine::new_unchecked(x, select_y(y, flag))
+            }
+        };
+        ensure!(point.is_on_curve(), "G2 point is not on the curve");
+        ensure!(
+            point.is_in_correct_subgroup_assuming_on_curve(),
+            "G2 point is not in the prime-order subgroup"
+        );
+        Ok(point)
+    }
+}
+
+impl From<PublicInputs> for Vec<Fr> {
+    fn from(inputs: PublicInputs) -> Self {
+        inputs.0
+    }
+}
+
+impl From<Vec<Fr>> for PublicInputs {
+    fn from(inputs: Vec<Fr>) -> Self {
+        Self(inputs)
+    }
+}
+
+/// Encoder for the subset of gnark's binary format the decoders accept, always
+/// writing points compressed.
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn g1(&mut self, p: &G1Affine) {
+        if p.infinity {
+            self.infinity(FQ_SIZE);
+            return;
+        }
+        let start = self.buf.len();
+        self.buf.extend_from_slice(&be_bytes(p.x.into_bigint()));
+        self.buf[start] |= y_flag(p.y);
+    }
+
+    fn g2(&mut self, p: &G2Affine) {
+        if p.infinity {
+            self.infinity(2 * FQ_SIZE);
+            return;
+        }
+        let start = self.buf.len();
+        self.buf.extend_from_slice(&be_bytes(p.x.c1.into_bigint()));
+        self.buf.extend_from_slice(&be_bytes(p.x.c0.into_bigint()));
+        self.buf[start] |= y_flag(p.y);
+    }
+
+    fn infinity(&mut self, len: usize) {
+        let start = self.buf.len();
+        self.buf.resize(start + len, 0);
+        self.buf[start] = M_COMPRESSED_INFINITY;
+    }
+}
+
+/// Compression flag for a point with ordinate `y`, see [`select_y`].
+fn y_flag<F: Field + Ord>(y: F) -> u8 {
+    if y > -y {
+        M_COMPRESSED_LARGEST
+    } else {
+        M_COMPRESSED_SMALLEST
+    }
+}
+
+fn be_bytes(n: BigInt<4>) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, limb) in n.0.iter().rev().enumerate() {
+        out[8 * i..8 * i + 8].copy_from_slice(&limb.to_be_bytes());
+    }
+    out
+}
+
+/// Pick `y` or `-y` according to gnark's "lexicographically largest" flag.
+///
+/// arkworks orders `Fq2` by `c1` first, then `c0`, which matches gnark's
+/// `E2.LexicographicallyLargest`; for `Fq` both compare canonical integers.
+fn select_y<F: Field + Ord>(y: F, flag: u8) -> F {
+    let neg = -y;
+    let largest = if y > neg { y } else { neg };
+    if flag == M_COMPRESSED_LARGEST {
+        largest
+    } else {
+        debug_assert_eq!(flag, M_COMPRESSED_SMALLEST);
+        -largest
+    }
+}
+
+fn masked(b: &[u8]) -> Vec<u8> {
+    let mut out = b.to_vec();
+    out[0] &= !M_MASK;
+    out
+}
+
+fn be_bigint(b: &[u8]) -> BigInt<4> {
+    let mut limbs = [0u64; 4];
+    for (i, chunk) in b.chunks(8).enumerate() {
+        limbs[3 - i] = u64::from_be_bytes(chunk.try_into().expect("8-byte chunk"));
+    }
+    BigInt::new(limbs)
+}
+
+fn fq(b: &[u8]) -> Result<Fq> {
+    Fq::from_bigint(be_bigint(b)).context("non-canonical base field element")
+}
+
+fn fq2(b: &[u8]) -> Result<Fq2> {
+    // gnark writes E2 as A1 || A0.
+    let c1 = fq(&b[..FQ_SIZE])?;
+    let c0 = fq(&b[FQ_SIZE..])?;
+    Ok(Fq2::new(c0, c1))
+}
+
+/// serde support: every type serializes to its gnark binary encoding, as a
+/// lowercase hex string in human-readable formats (JSON, TOML, ...) and as raw
+/// bytes otherwise. This is the same encoding as [`Groth16ProofResult`].
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{decode_hex, Proof, PublicInputs, VerifyingKey};
+    use crate::public_inputs::encode_hex;
+
+    struct GnarkBytes;
+
+    impl<'de> Visitor<'de> for GnarkBytes {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("gnark-serialized bytes or a hex string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+            decode_hex(v).map_err(|e| E::custom(format!("{e:#}")))
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                out.push(b);
+            }
+            Ok(out)
+        }
+    }
+
+    macro_rules! gnark_bytes_serde {
+        ($($ty:ty),*) => {$(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let bytes = self.to_gnark_bytes();
+                    if serializer.is_human_readable() {
+                        serializer.serialize_str(&encode_hex(&bytes))
+                    } else {
+                        serializer.serialize_bytes(&bytes)
+                    }
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let bytes = if deserializer.is_human_readable() {
+                        deserializer.deserialize_str(GnarkBytes)?
+                    } else {
+                        deserializer.deserialize_bytes(GnarkBytes)?
+                    };
+                    <$ty>::from_gnark_bytes(&bytes).map_err(|e| de::Error::custom(format!("{e:#}")))
+                }
+            }
+        )*};
+    }
+
+    gnark_bytes_serde!(Proof, VerifyingKey, PublicInputs);
+}
@@ -0,0 +1,224 @@
+//! PLONK proofs over BN254 with a KZG [`Srs`].
+//!
+//! Circuits are compiled with [`crate::CircuitBuilder::compile_sparse`], and
+//! one SRS serves every circuit up to its size, so there is no per-circuit
+//! trusted setup. Verifiers outside gnark fix the hashes the proof is built
+//! with; [`PlonkConfig`] selects them.
+//!
+//! ```no_run
+//! # fn example(circuit: &rust_gnark::CompiledCircuit) -> anyhow::Result<()> {
+//! use rust_gnark::{plonk_prove, plonk_verify, PlonkConfig, Srs, TranscriptHash};
+//!
+//! let srs = Srs::generate_insecure(circuit.srs_size_needed()?)?;
+//! let (pk, vk) = circuit.plonk_setup(&srs)?;
+//! let config = PlonkConfig::default().with_challenge_hash(TranscriptHash::Keccak256);
+//! let proof = plonk_prove(circuit, &pk, r#"{"X": "3", "Y": "9"}"#, &config)?;
+//! assert!(plonk_verify(&vk, &proof, &config)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Result;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::buffer::GnarkBuffer;
+use crate::handle::Handle;
+use crate::metrics::{self, Scheme};
+use crate::{bind, take_go_string, CompiledCircuit, GnarkError, HashFunction, Srs, TranscriptHash};
+
+/// The hashes a PLONK proof is built and checked with; the prover and
+/// verifier must use the same.
+///
+/// The defaults are gnark's, which its exported Solidity verifier expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PlonkConfig {
+    challenge_hash: TranscriptHash,
+    hash_to_field: HashFunction,
+}
+
+impl PlonkConfig {
+    /// Derive the Fiat-Shamir challenges (and fold the KZG openings) with
+    /// `hash` instead of SHA-256.
+    pub fn with_challenge_hash(mut self, hash: TranscriptHash) -> Self {
+        self.challenge_hash = hash;
+        self
+    }
+
+    /// Derive the challenges of the circuit's commitments (see
+    /// [`crate::CircuitBuilder::commit`]) with `hash`.
+    pub fn with_hash_to_field(mut self, hash: HashFunction) -> Self {
+        self.hash_to_field = hash;
+        self
+    }
+
+    /// The Fiat-Shamir hash.
+    pub fn challenge_hash(&self) -> TranscriptHash {
+        self.challenge_hash
+    }
+
+    /// The hash-to-field function.
+    pub fn hash_to_field(&self) -> HashFunction {
+        self.hash_to_field
+    }
+}
+
+/// A PLONK proof and its public witness, hex-encoded like
+/// [`crate::Groth16ProofResult`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlonkProofResult {
+    /// Hex-encoded gnark binary proof (`proof.WriteTo()`).
+    pub proof: String,
+    /// Hex-encoded gnark binary public witness.
+    pub public_inputs: String,
+}
+
+/// A PLONK proving key owned by the Go runtime.
+#[derive(Debug)]
+pub struct PlonkProvingKey {
+    handle: Handle,
+}
+
+/// A PLONK verifying key owned by the Go runtime.
+#[derive(Debug)]
+pub struct PlonkVerifyingKey {
+    handle: Handle,
+}
+
+impl PlonkVerifyingKey {
+    /// Load a key written by [`PlonkVerifyingKey::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a BN254 PLONK verifying key.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_plonk_vk_from_bytes(bytes.as_ptr() as *mut u8, bytes.len() as u64, &mut id)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// gnark's encoding of the key (`vk.WriteTo()`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<GnarkBuffer> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_plonk_vk_to_bytes(self.handle.id(), &mut data, &mut len) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+    }
+}
+
+impl CompiledCircuit {
+    /// Derive PLONK keys for this circuit from `srs`, which needs at least
+    /// [`CompiledCircuit::srs_size_needed`] powers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit is not a sparse R1CS or the SRS is too
+    /// small.
+    pub fn plonk_setup(&self, srs: &Srs) -> Result<(PlonkProvingKey, PlonkVerifyingKey)> {
+        let (mut pk, mut vk) = (0u64, 0u64);
+        let err_ptr =
+            unsafe { bind::gnark_plonk_setup(self.handle.id(), srs.handle_id(), &mut pk, &mut vk) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok((
+            PlonkProvingKey {
+                handle: Handle::from_raw(pk),
+            },
+            PlonkVerifyingKey {
+                handle: Handle::from_raw(vk),
+            },
+        ))
+    }
+}
+
+/// Prove a JSON witness (as for [`crate::groth16_prove`]) with PLONK.
+///
+/// # Errors
+///
+/// Returns an error if the witness does not satisfy the circuit or does not
+/// fit it, or `pk` was set up for another circuit.
+pub fn plonk_prove(
+    circuit: &CompiledCircuit,
+    pk: &PlonkProvingKey,
+    witness_json: &str,
+    config: &PlonkConfig,
+) -> Result<PlonkProofResult> {
+    metrics::record_prove(Scheme::Plonk, 1, false, || {
+        if metrics::enabled() {
+            metrics::note_constraints(circuit.nb_constraints().unwrap_or(0));
+        }
+        let witness = CString::new(witness_json)?;
+        let mut proof = std::ptr::null_mut();
+        let mut public = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_plonk_prove(
+                circuit.handle.id(),
+                pk.handle.id(),
+                witness.as_ptr() as *mut c_char,
+                config.challenge_hash.as_raw(),
+                config.hash_to_field.as_raw(),
+                &mut proof,
+                &mut public,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe {
+            PlonkProofResult {
+                proof: take_go_string(proof),
+                public_inputs: take_go_string(public),
+            }
+        })
+    })
+}
+
+/// Verify a PLONK proof with the [`PlonkConfig`] it was generated with.
+///
+/// `Ok(false)` if the proof is invalid, `Err` if it cannot be decoded.
+///
+/// # Errors
+///
+/// Returns an error if the proof or public inputs fail to decode.
+pub fn plonk_verify(
+    vk: &PlonkVerifyingKey,
+    proof: &PlonkProofResult,
+    config: &PlonkConfig,
+) -> Result<bool> {
+    metrics::record_verify(Scheme::Plonk, false, || {
+        let proof_hex = CString::new(proof.proof.as_str())?;
+        let public = CString::new(proof.public_inputs.as_str())?;
+        let err_ptr = unsafe {
+            bind::gnark_plonk_verify(
+                vk.handle.id(),
+                proof_hex.as_ptr() as *mut c_char,
+                public.as_ptr() as *mut c_char,
+                config.challenge_hash.as_raw(),
+                config.hash_to_field.as_raw(),
+            )
+        };
+        if err_ptr.is_null() {
+            return Ok(true);
+        }
+        match unsafe { GnarkError::take(err_ptr) } {
+            GnarkError::InvalidProof(_) => Ok(false),
+            err => Err(err.into()),
+        }
+    })
+}
@@ -0,0 +1,172 @@
+//! A fixed set of prover threads fed from a bounded queue.
+
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::task::TaskSlot;
+use crate::{GnarkError, ProveTask, ProverSession};
+
+/// A proof waiting for a worker.
+struct Job {
+    session: Arc<ProverSession>,
+    witness_json: String,
+    slot: TaskSlot,
+}
+
+/// Runs queued proofs on a fixed number of worker threads.
+///
+/// Each job names the [`ProverSession`] to prove with, so one pool can serve
+/// several circuits while every circuit stays loaded between proofs. At most
+/// `workers` proofs run at once; up to `queue_capacity` more wait in the queue,
+/// after which [`ProvePool::submit`] blocks and [`ProvePool::try_submit`]
+/// refuses the job, so a service that accepts work faster than it proves
+/// slows down instead of buffering without bound.
+///
+/// Every job returns a [`ProveTask`]. Cancelling it skips the proof if it is
+/// still queued, or aborts the Go prover if it is running.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use std::sync::Arc;
+///
+/// let session = Arc::new(rust_gnark::ProverSession::new("circuit.r1cs", "circuit.pk")?);
+/// let pool = rust_gnark::ProvePool::new(4, 16)?;
+/// let tasks: Vec<_> = (0..100u64)
+///     .map(|x| {
+///         let y = x * x * x + x + 5;
+///         pool.submit(&session, &format!(r#"{{"X": "{x}", "Y": "{y}"}}"#))
+///     })
+///     .collect();
+/// for task in tasks {
+///     let proof = task.wait()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Dropping the pool finishes the queued jobs, then joins the workers.
+pub struct ProvePool {
+    sender: Option<SyncSender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ProvePool {
+    /// Start `workers` prover threads behind a queue of `queue_capacity`
+    /// jobs. With a capacity of zero a job is only accepted once a worker is
+    /// free to take it.
+    ///
+    /// Each worker's proofs still use gnark's own parallelism; with several
+    /// workers, consider limiting it with [`crate::ProverConfig::with_threads`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a thread cannot be spawned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn new(workers: usize, queue_capacity: usize) -> Result<Self> {
+        assert!(workers > 0, "a prove pool needs at least one worker");
+        let (sender, receiver) = mpsc::sync_channel(queue_capacity);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut pool = Self {
+            sender: Some(sender),
+            workers: Vec::with_capacity(workers),
+        };
+        for i in 0..workers {
+            let receiver = Arc::clone(&receiver);
+            let worker = thread::Builder::new()
+                .name(format!("rust-gnark-pool-{i}"))
+                .spawn(move || work(&receiver))
+                .map_err(|e| anyhow!("failed to spawn prove pool worker: {e}"))?;
+            pool.workers.push(worker);
+        }
+        Ok(pool)
+    }
+
+    /// Queue a proof of `witness_json` with `session`, blocking while the
+    /// queue is full.
+    pub fn submit(&self, session: &Arc<ProverSession>, witness_json: &str) -> ProveTask {
+        let (task, job) = Self::job(session, witness_json);
+        if let Err(mpsc::SendError(job)) = self.sender().send(job) {
+            job.slot
+                .complete(Err(anyhow!("the prove pool has shut down")));
+        }
+        task
+    }
+
+    /// Queue a proof of `witness_json` with `session`, or return `None`
+    /// without queueing it if the queue is full.
+    pub fn try_submit(
+        &self,
+        session: &Arc<ProverSession>,
+        witness_json: &str,
+    ) -> Option<ProveTask> {
+        let (task, job) = Self::job(session, witness_json);
+        match self.sender().try_send(job) {
+            Ok(()) => Some(task),
+            Err(TrySendError::Full(_)) => None,
+            Err(TrySendError::Disconnected(job)) => {
+                job.slot
+                    .complete(Err(anyhow!("the prove pool has shut down")));
+                Some(task)
+            }
+        }
+    }
+
+    /// The number of worker threads.
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    fn job(session: &Arc<ProverSession>, witness_json: &str) -> (ProveTask, Job) {
+        let (task, slot) = ProveTask::detached();
+        let job = Job {
+            session: Arc::clone(session),
+            witness_json: witness_json.to_owned(),
+            slot,
+        };
+        (task, job)
+    }
+
+    fn sender(&self) -> &SyncSender<Job> {
+        self.sender.as_ref().expect("sender is only taken on drop")
+    }
+}
+
+impl Drop for ProvePool {
+    fn drop(&mut self) {
+        // Closing the queue lets each worker exit once it is drained.
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl std::fmt::Debug for ProvePool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProvePool")
+            .field("workers", &self.workers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Prove jobs from `receiver` until the pool closes the queue.
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock().expect("prove pool queue poisoned").recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        let token = job.slot.token();
+        let result = if token.is_cancelled() {
+            Err(GnarkError::Cancelled.into())
+        } else {
+            job.session.prove_cancellable(&job.witness_json, token)
+        };
+        job.slot.complete(result);
+    }
+}
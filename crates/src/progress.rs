@@ -0,0 +1,105 @@
+//! Progress reporting during proving.
+//!
+//! The Go prover invokes a C callback at each phase boundary and, while reading
+//! the constraint system and proving key from disk, as bytes are consumed. The
+//! callback is forwarded to a [`ProgressReporter`].
+//!
+//! gnark does not expose hooks inside `groth16.Prove`, so witness solving, MSMs
+//! and FFTs are reported together as [`ProvePhase::Prove`].
+
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// A step of proof generation, in the order the Go prover runs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ProvePhase {
+    /// Reading the `.r1cs` file.
+    LoadConstraintSystem,
+    /// Reading the `.pk` file.
+    LoadProvingKey,
+    /// Building the witness from the JSON assignment.
+    BuildWitness,
+    /// `groth16.Prove`: witness solving, MSMs and FFTs.
+    Prove,
+    /// Serializing the proof and public inputs.
+    Serialize,
+}
+
+impl ProvePhase {
+    /// All phases, in execution order.
+    pub const ALL: [ProvePhase; 5] = [
+        ProvePhase::LoadConstraintSystem,
+        ProvePhase::LoadProvingKey,
+        ProvePhase::BuildWitness,
+        ProvePhase::Prove,
+        ProvePhase::Serialize,
+    ];
+
+    fn from_raw(phase: c_int) -> Option<Self> {
+        usize::try_from(phase)
+            .ok()
+            .and_then(|i| Self::ALL.get(i).copied())
+    }
+}
+
+/// A single progress event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Progress {
+    /// The phase this event belongs to.
+    pub phase: ProvePhase,
+    /// Units completed within the phase (bytes while loading files).
+    pub done: u64,
+    /// Total units in the phase; `done == total` marks the end of the phase.
+    pub total: u64,
+}
+
+impl Progress {
+    /// Fraction of the current phase that is complete, in `[0, 1]`.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.done as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// Receives progress events during proving.
+///
+/// Events are delivered on the thread running the proof, before the prove call
+/// returns. Implemented for any `Fn(Progress) + Send + Sync` closure.
+pub trait ProgressReporter: Send + Sync {
+    /// Called for every progress event.
+    fn on_progress(&self, progress: Progress);
+}
+
+impl<F> ProgressReporter for F
+where
+    F: Fn(Progress) + Send + Sync,
+{
+    fn on_progress(&self, progress: Progress) {
+        self(progress)
+    }
+}
+
+/// C trampoline for `gnark_progress_cb`.
+///
+/// `user_data` must point to a `&dyn ProgressReporter` that outlives the prove call.
+pub(crate) unsafe extern "C" fn progress_trampoline(
+    user_data: *mut c_void,
+    phase: c_int,
+    done: u64,
+    total: u64,
+) {
+    let Some(phase) = ProvePhase::from_raw(phase) else {
+        return;
+    };
+    let reporter = &*(user_data as *const &dyn ProgressReporter);
+    // Unwinding into Go would abort the process.
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        reporter.on_progress(Progress { phase, done, total })
+    }));
+}
@@ -0,0 +1,195 @@
+//! The [`Prover`] and [`ProofSystem`] traits, over local and remote proving
+//! and over proving systems.
+
+use anyhow::Result;
+
+use crate::Groth16ProofResult;
+#[cfg(not(feature = "verifier"))]
+use crate::{
+    groth16_verify, plonk_prove, plonk_verify, CompiledCircuit, PlonkConfig, PlonkProofResult,
+    PlonkProvingKey, PlonkVerifyingKey, ProverSession,
+};
+
+/// Something that proves JSON witnesses (as [`crate::groth16_prove`] takes
+/// them) for one circuit.
+///
+/// Code written against `&dyn Prover` works the same with a local
+/// [`crate::ProverSession`] and, with the `remote` feature, a
+/// [`crate::RemoteProver`], so an app can choose where to prove at run time:
+///
+/// ```no_run
+/// # #[cfg(feature = "remote")]
+/// # fn example(session: rust_gnark::ProverSession, weak_device: bool) -> anyhow::Result<()> {
+/// use rust_gnark::{Prover, RemoteProver};
+///
+/// let prover: Box<dyn Prover> = if weak_device {
+///     Box::new(RemoteProver::new("https://prover.example.com/v1/prove"))
+/// } else {
+///     Box::new(session)
+/// };
+/// let proof = prover.prove(r#"{"X": "3", "Y": "35"}"#)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Prover: Send + Sync {
+    /// Generate a proof for `witness_json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the witness does not satisfy the circuit or
+    /// proving fails.
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult>;
+}
+
+#[cfg(not(feature = "verifier"))]
+impl Prover for ProverSession {
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        ProverSession::prove(self, witness_json)
+    }
+}
+
+/// A circuit's prover and verifier in one proving system, for code that is
+/// generic over the backend.
+///
+/// [`Groth16`], [`Plonk`] and, with the `remote` feature,
+/// [`crate::RemoteProver`] implement it:
+///
+/// ```no_run
+/// use rust_gnark::ProofSystem;
+///
+/// fn prove_checked<S: ProofSystem>(system: &S, witness_json: &str) -> anyhow::Result<S::Proof> {
+///     let proof = system.prove(witness_json)?;
+///     anyhow::ensure!(system.verify(&proof)?, "the proof does not verify");
+///     Ok(proof)
+/// }
+/// ```
+pub trait ProofSystem: Send + Sync {
+    /// The proof and its public inputs.
+    type Proof;
+
+    /// Generate a proof for `witness_json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the witness does not satisfy the circuit or
+    /// proving fails.
+    fn prove(&self, witness_json: &str) -> Result<Self::Proof>;
+
+    /// Verify `proof`: `Ok(false)` if it is well-formed but invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof cannot be decoded or verification fails
+    /// to run.
+    fn verify(&self, proof: &Self::Proof) -> Result<bool>;
+}
+
+/// Groth16 over a [`ProverSession`] and the circuit's verifying key.
+#[cfg(not(feature = "verifier"))]
+#[derive(Debug)]
+pub struct Groth16 {
+    session: ProverSession,
+    r1cs_path: String,
+    vk_path: String,
+}
+
+#[cfg(not(feature = "verifier"))]
+impl Groth16 {
+    /// Load the `.r1cs` and proving key for proving (see
+    /// [`ProverSession::new`]), verifying against the `.vk` at `vk_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the constraint system or proving key cannot be
+    /// loaded.
+    pub fn new(r1cs_path: &str, pk_path: &str, vk_path: &str) -> Result<Self> {
+        Ok(Self::from_session(
+            ProverSession::new(r1cs_path, pk_path)?,
+            r1cs_path,
+            vk_path,
+        ))
+    }
+
+    /// Prove with `session`, which must be for the circuit at `r1cs_path`,
+    /// verifying against the `.vk` at `vk_path`.
+    pub fn from_session(session: ProverSession, r1cs_path: &str, vk_path: &str) -> Self {
+        Self {
+            session,
+            r1cs_path: r1cs_path.to_owned(),
+            vk_path: vk_path.to_owned(),
+        }
+    }
+
+    /// The session proofs are generated with.
+    pub fn session(&self) -> &ProverSession {
+        &self.session
+    }
+}
+
+#[cfg(not(feature = "verifier"))]
+impl ProofSystem for Groth16 {
+    type Proof = Groth16ProofResult;
+
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        self.session.prove(witness_json)
+    }
+
+    fn verify(&self, proof: &Groth16ProofResult) -> Result<bool> {
+        groth16_verify(&self.r1cs_path, &self.vk_path, proof)
+    }
+}
+
+#[cfg(not(feature = "verifier"))]
+impl Prover for Groth16 {
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        self.session.prove(witness_json)
+    }
+}
+
+/// PLONK over a compiled sparse circuit and its keys.
+#[cfg(not(feature = "verifier"))]
+#[derive(Debug)]
+pub struct Plonk {
+    circuit: CompiledCircuit,
+    pk: PlonkProvingKey,
+    vk: PlonkVerifyingKey,
+    config: PlonkConfig,
+}
+
+#[cfg(not(feature = "verifier"))]
+impl Plonk {
+    /// Prove `circuit` with `pk` and verify with `vk`, as returned by
+    /// [`CompiledCircuit::plonk_setup`], using the default [`PlonkConfig`].
+    pub fn new(circuit: CompiledCircuit, pk: PlonkProvingKey, vk: PlonkVerifyingKey) -> Self {
+        Self {
+            circuit,
+            pk,
+            vk,
+            config: PlonkConfig::default(),
+        }
+    }
+
+    /// Prove and verify with the hash functions of `config`.
+    pub fn with_config(mut self, config: PlonkConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The verifying key proofs are checked against.
+    pub fn verifying_key(&self) -> &PlonkVerifyingKey {
+        &self.vk
+    }
+}
+
+#[cfg(not(feature = "verifier"))]
+impl ProofSystem for Plonk {
+    type Proof = PlonkProofResult;
+
+    fn prove(&self, witness_json: &str) -> Result<PlonkProofResult> {
+        plonk_prove(&self.circuit, &self.pk, witness_json, &self.config)
+    }
+
+    fn verify(&self, proof: &PlonkProofResult) -> Result<bool> {
+        plonk_verify(&self.vk, proof, &self.config)
+    }
+}
@@ -0,0 +1,144 @@
+//! Public inputs from typed values.
+
+use crate::Fr;
+
+/// Builds the public inputs of a proof from the values an app has at hand,
+/// with the encodings Solidity verifiers and most circom and gnark circuits
+/// use: integers and addresses are big-endian numbers, so an address is
+/// `uint160(addr)`, and 32-byte values such as hashes are reduced modulo the
+/// field like `uint256(h) % r`, or split into two 128-bit halves for circuits
+/// that take them that way.
+///
+/// Values are appended in circuit order, the order of the circuit's public
+/// variables:
+///
+/// ```
+/// use rust_gnark::PublicInputsBuilder;
+///
+/// let mut inputs = PublicInputsBuilder::new();
+/// inputs
+///     .push_u64(35)
+///     .push_address(&[0x11; 20])
+///     .push_bytes32_be(&[0xff; 32]);
+/// assert_eq!(inputs.len(), 3);
+/// let public_inputs_hex = inputs.to_hex();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicInputsBuilder {
+    inputs: Vec<Fr>,
+}
+
+impl PublicInputsBuilder {
+    /// An empty list of public inputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a field element as it is.
+    pub fn push(&mut self, value: Fr) -> &mut Self {
+        self.inputs.push(value);
+        self
+    }
+
+    /// Append an unsigned integer.
+    pub fn push_u64(&mut self, value: u64) -> &mut Self {
+        self.push(Fr::from(value))
+    }
+
+    /// Append a 20-byte Ethereum address as the number `uint160(address)`.
+    pub fn push_address(&mut self, address: &[u8; 20]) -> &mut Self {
+        self.push(reduce_be(address))
+    }
+
+    /// Append 32 big-endian bytes, e.g. a hash, as the number they encode
+    /// modulo the field order, like Solidity's `uint256(value) % r`.
+    ///
+    /// Every value of `r` and up is reduced, so values that differ by a
+    /// multiple of `r` give the same input; circuits that need the whole value
+    /// take it with [`PublicInputsBuilder::push_bytes32_hi_lo`].
+    pub fn push_bytes32_be(&mut self, value: &[u8; 32]) -> &mut Self {
+        self.push(reduce_be(value))
+    }
+
+    /// Append 32 big-endian bytes as two inputs, the high 16 bytes and then
+    /// the low 16 bytes, each a 128-bit number.
+    pub fn push_bytes32_hi_lo(&mut self, value: &[u8; 32]) -> &mut Self {
+        self.push(reduce_be(&value[..16]));
+        self.push(reduce_be(&value[16..]))
+    }
+
+    /// The number of inputs appended.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Whether no input was appended.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// The inputs, in the order they were appended.
+    pub fn inputs(&self) -> &[Fr] {
+        &self.inputs
+    }
+
+    /// The inputs as a public witness in gnark's `witness.MarshalBinary()`
+    /// format: `u32 nbPublic || u32 nbSecret || u32 len || len * Fr`, each
+    /// element 32 bytes big-endian.
+    pub fn to_gnark_bytes(&self) -> Vec<u8> {
+        public_witness_bytes(&self.inputs)
+    }
+
+    /// [`PublicInputsBuilder::to_gnark_bytes`] hex-encoded, as in
+    /// `Groth16ProofResult::public_inputs`.
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_gnark_bytes())
+    }
+}
+
+impl From<PublicInputsBuilder> for Vec<Fr> {
+    fn from(builder: PublicInputsBuilder) -> Self {
+        builder.inputs
+    }
+}
+
+#[cfg(feature = "native-verify")]
+impl From<PublicInputsBuilder> for crate::native::PublicInputs {
+    fn from(builder: PublicInputsBuilder) -> Self {
+        Self(builder.inputs.into_iter().map(Into::into).collect())
+    }
+}
+
+/// `inputs` as a public witness in gnark's `witness.MarshalBinary()` format,
+/// see [`PublicInputsBuilder::to_gnark_bytes`].
+pub(crate) fn public_witness_bytes(inputs: &[Fr]) -> Vec<u8> {
+    let len = u32::try_from(inputs.len()).expect("fewer than 2^32 public inputs");
+    let mut out = Vec::with_capacity(12 + 32 * inputs.len());
+    // nbPublic || nbSecret || vector length || elements.
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out.extend_from_slice(&len.to_be_bytes());
+    for x in inputs {
+        out.extend_from_slice(&x.to_bytes_be());
+    }
+    out
+}
+
+/// Encode bytes as lowercase hex, like Go's `hex.EncodeToString`.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    out
+}
+
+/// The big-endian number `bytes` encode, modulo the field order.
+fn reduce_be(bytes: &[u8]) -> Fr {
+    let base = Fr::from(256u64);
+    bytes
+        .iter()
+        .fold(Fr::ZERO, |acc, &b| acc * base + Fr::from(u64::from(b)))
+}
@@ -0,0 +1,176 @@
+//! Verification off the calling thread, at a chosen scheduling priority.
+//!
+//! A cgo call runs on the thread that makes it, so verifying from an app's
+//! main thread competes with its UI, and verifying at the main thread's
+//! priority keeps the cores busy at a level the OS takes for interactive
+//! work. [`VerifierConfig::with_qos`] hands the call to a pool of threads of
+//! the chosen [`Qos`] and blocks until it returns. Go code gnark hands to
+//! goroutines runs on the Go runtime's own threads, at their priority.
+
+use anyhow::{anyhow, Result};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use crate::HashFunction;
+
+/// How urgent a verification is to the OS scheduler.
+///
+/// On Apple platforms this is the thread's QoS class; on Linux and Android,
+/// its nice value (10 and -2, Android's `THREAD_PRIORITY_BACKGROUND` and
+/// `THREAD_PRIORITY_FOREGROUND`). Raising the priority needs a privilege
+/// plain Linux processes lack, and then the threads run at the default.
+/// Elsewhere the threads run at the default priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Qos {
+    /// Work the user does not wait for, e.g. checking proofs in the
+    /// background: scheduled last, on efficiency cores where there are any.
+    Background,
+    /// Work the user started and is waiting for, e.g. a verification behind
+    /// a spinner.
+    UserInitiated,
+}
+
+/// Verifier settings that do not affect the verdict.
+///
+/// ```no_run
+/// # fn example(result: &rust_gnark::Groth16ProofResult) -> anyhow::Result<()> {
+/// use rust_gnark::{Qos, VerifierConfig};
+///
+/// let config = VerifierConfig::default().with_qos(Qos::Background);
+/// let valid = rust_gnark::groth16_verify_with_config("circuit.r1cs", "circuit.vk", result, &config)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifierConfig {
+    pub(crate) hash: HashFunction,
+    pub(crate) qos: Option<Qos>,
+}
+
+impl VerifierConfig {
+    /// Verify proofs generated with `hash` (see
+    /// [`crate::ProverConfig::with_hash_function`]).
+    pub fn with_hash_function(mut self, hash: HashFunction) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Verify on a thread of `qos` instead of the calling thread, which
+    /// blocks until the verification is done.
+    pub fn with_qos(mut self, qos: Qos) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// Run `verify` as configured: on the calling thread, or on the pool of
+    /// [`VerifierConfig::with_qos`].
+    pub(crate) fn run<T: Send + 'static>(
+        &self,
+        verify: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        match self.qos {
+            None => verify(),
+            Some(qos) => pool(qos).run(verify),
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads of one [`Qos`], as many as the machine has cores, started on first
+/// use and kept for the life of the process.
+struct Pool {
+    jobs: Mutex<Sender<Job>>,
+}
+
+impl Pool {
+    fn start(qos: Qos) -> Self {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        let workers = thread::available_parallelism().map_or(1, usize::from);
+        for i in 0..workers {
+            let queue = Arc::clone(&queue);
+            // If no thread starts, the queue has no receiver and run fails.
+            let _ = thread::Builder::new()
+                .name(format!("gnark-verify-{qos:?}-{i}").to_lowercase())
+                .spawn(move || {
+                    set_current_thread_qos(qos);
+                    work(&queue);
+                });
+        }
+        Self {
+            jobs: Mutex::new(jobs),
+        }
+    }
+
+    fn run<T: Send + 'static>(
+        &self,
+        verify: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let (done, result) = mpsc::channel();
+        // Caught, so a panic reaches the caller and the worker lives on.
+        let job: Job = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(verify))
+                .unwrap_or_else(|_| Err(anyhow!("the verification panicked")));
+            let _ = done.send(result);
+        });
+        self.jobs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .send(job)
+            .map_err(|_| anyhow!("the verifier threads are gone"))?;
+        result
+            .recv()
+            .map_err(|_| anyhow!("the verifier thread stopped before finishing"))?
+    }
+}
+
+fn work(queue: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match queue.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        job();
+    }
+}
+
+fn pool(qos: Qos) -> &'static Pool {
+    static BACKGROUND: OnceLock<Pool> = OnceLock::new();
+    static USER_INITIATED: OnceLock<Pool> = OnceLock::new();
+    match qos {
+        Qos::Background => BACKGROUND.get_or_init(|| Pool::start(qos)),
+        Qos::UserInitiated => USER_INITIATED.get_or_init(|| Pool::start(qos)),
+    }
+}
+
+/// Apply `qos` to the calling thread, as far as the OS lets it. Failures are
+/// ignored: the thread then keeps the default priority.
+#[cfg(target_vendor = "apple")]
+fn set_current_thread_qos(qos: Qos) {
+    let class = match qos {
+        Qos::Background => libc::qos_class_t::QOS_CLASS_BACKGROUND,
+        Qos::UserInitiated => libc::qos_class_t::QOS_CLASS_USER_INITIATED,
+    };
+    unsafe { libc::pthread_set_qos_class_self_np(class, 0) };
+}
+
+/// Apply `qos` to the calling thread, as far as the OS lets it. Failures are
+/// ignored: the thread then keeps the default priority.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_current_thread_qos(qos: Qos) {
+    let nice = match qos {
+        Qos::Background => 10,
+        Qos::UserInitiated => -2,
+    };
+    // With a thread id, setpriority sets the nice value of that thread alone.
+    unsafe {
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        libc::setpriority(libc::PRIO_PROCESS as _, tid, nice);
+    }
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux", target_os = "android")))]
+fn set_current_thread_qos(_qos: Qos) {}
@@ -0,0 +1,352 @@
+//! Two-layer recursion over BLS12-377 and BW6-761, with an optional BN254
+//! wrap for the EVM.
+//!
+//! BLS12-377's base field is BW6-761's scalar field, so a BW6-761 circuit
+//! verifies BLS12-377 proofs with native arithmetic, about a hundred times
+//! cheaper than [`Aggregator`](crate::Aggregator)'s emulated BN254 verifier.
+//! A [`RecursionPipeline`] sets up the three circuits involved, inner, outer
+//! and wrap, and threads the proofs between them with the hash-to-field each
+//! verifier expects, which is easy to get wrong by hand.
+
+use anyhow::{ensure, Result};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+use crate::handle::Handle;
+use crate::metrics::{self, Scheme};
+use crate::{
+    bind, take_go_string, AggregatedProof, CircuitBuilder, GnarkError, Groth16ProofResult,
+    VerifyingKey,
+};
+
+/// Every proof of a [`RecursionPipeline::prove`] run.
+#[derive(Debug, Clone)]
+pub struct RecursionProof {
+    /// The BLS12-377 proofs of the inner circuit, in witness order.
+    pub inner: Vec<Groth16ProofResult>,
+    /// The BW6-761 proof that all inner proofs are valid; its public inputs
+    /// are theirs, in order.
+    pub aggregated: AggregatedProof,
+    /// The BN254 proof that `aggregated` is valid, if the pipeline wraps.
+    pub wrapped: Option<Groth16ProofResult>,
+}
+
+/// Proves a circuit over BLS12-377, aggregates a fixed number of its proofs
+/// in an outer circuit over BW6-761 and, optionally, wraps the outer proof in
+/// a BN254 proof that gnark's Solidity verifier checks.
+///
+/// Setup compiles all circuits and runs a local Groth16 setup for each, which
+/// takes minutes with the BN254 wrap (it verifies a BW6-761 proof in emulated
+/// arithmetic); build one pipeline and reuse it.
+///
+/// ```no_run
+/// # fn example(circuit: &rust_gnark::CircuitBuilder) -> anyhow::Result<()> {
+/// use rust_gnark::RecursionPipeline;
+///
+/// let pipeline = RecursionPipeline::new_with_evm_wrap(circuit, 2)?;
+/// let proof = pipeline.prove(&[r#"{"X": "3", "Y": "35"}"#, r#"{"X": "2", "Y": "15"}"#])?;
+/// let wrapped = proof.wrapped.expect("the pipeline wraps");
+/// assert!(pipeline.verify_wrapped(&wrapped)?);
+/// let solidity = pipeline.wrap_verifying_key()?.export_solidity()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RecursionPipeline {
+    handle: Handle,
+    capacity: usize,
+    evm_wrap: bool,
+}
+
+impl RecursionPipeline {
+    /// Set up the pipeline for `capacity` proofs of `inner`, compiled over
+    /// BLS12-377, without the BN254 wrap.
+    ///
+    /// The setups are generated locally by a single party, so the proofs are
+    /// only as trustworthy as the machine that ran them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `capacity` is zero, `inner`'s description is
+    /// invalid (see [`CircuitBuilder::to_description`]) or uses an operation
+    /// BLS12-377 lacks, or compilation or setup fails.
+    pub fn new(inner: &CircuitBuilder, capacity: usize) -> Result<Self> {
+        Self::setup(inner, capacity, false)
+    }
+
+    /// Like [`RecursionPipeline::new`], with a BN254 wrap circuit for
+    /// verifying the result on the EVM.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RecursionPipeline::new`].
+    pub fn new_with_evm_wrap(inner: &CircuitBuilder, capacity: usize) -> Result<Self> {
+        Self::setup(inner, capacity, true)
+    }
+
+    fn setup(inner: &CircuitBuilder, capacity: usize, evm_wrap: bool) -> Result<Self> {
+        ensure!(capacity > 0, "recursion pipeline needs at least one proof");
+        let desc = CString::new(inner.to_description()?)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_recursion_new(
+                desc.as_ptr() as *mut c_char,
+                u32::try_from(capacity)?,
+                evm_wrap as c_int,
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+            capacity,
+            evm_wrap,
+        })
+    }
+
+    /// Number of inner proofs every aggregation takes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Whether the pipeline wraps outer proofs in BN254 ones.
+    pub fn evm_wrap(&self) -> bool {
+        self.evm_wrap
+    }
+
+    /// Run the whole pipeline: prove the inner circuit for each of the
+    /// [`RecursionPipeline::capacity`] witnesses (JSON objects, as for
+    /// [`crate::groth16_prove`]), aggregate the proofs and wrap the result if
+    /// the pipeline wraps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of witnesses differs from the capacity,
+    /// or for the reasons of the steps' methods.
+    pub fn prove(&self, witnesses: &[&str]) -> Result<RecursionProof> {
+        ensure!(
+            witnesses.len() == self.capacity,
+            "recursion pipeline expects {} witnesses, got {}",
+            self.capacity,
+            witnesses.len()
+        );
+        let inner = witnesses
+            .iter()
+            .map(|witness| self.prove_inner(witness))
+            .collect::<Result<Vec<_>>>()?;
+        let aggregated = self.aggregate(&inner)?;
+        let wrapped = if self.evm_wrap {
+            Some(self.wrap(&aggregated)?)
+        } else {
+            None
+        };
+        Ok(RecursionProof {
+            inner,
+            aggregated,
+            wrapped,
+        })
+    }
+
+    /// Prove the inner circuit over BLS12-377 for `witness_json`.
+    ///
+    /// The proof is hex-encoded like a BN254 [`Groth16ProofResult`] but only
+    /// [`RecursionPipeline::aggregate`] takes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the witness does not parse, lacks an input or does
+    /// not satisfy the circuit.
+    pub fn prove_inner(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        metrics::record_prove(Scheme::Groth16, 1, false, || {
+            let witness = CString::new(witness_json)?;
+            let mut out_proof = std::ptr::null_mut();
+            let mut out_public = std::ptr::null_mut();
+            let err_ptr = unsafe {
+                bind::gnark_recursion_prove_inner(
+                    self.handle.id(),
+                    witness.as_ptr() as *mut c_char,
+                    &mut out_proof,
+                    &mut out_public,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            unsafe {
+                Ok(Groth16ProofResult {
+                    proof: take_go_string(out_proof),
+                    public_inputs: take_go_string(out_public),
+                })
+            }
+        })
+    }
+
+    /// Prove over BW6-761 that every proof in `proofs`, from
+    /// [`RecursionPipeline::prove_inner`], is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `proofs.len()` differs from the capacity, a proof
+    /// fails to decode, or [`GnarkError::InvalidProof`] naming the first
+    /// invalid one.
+    pub fn aggregate(&self, proofs: &[Groth16ProofResult]) -> Result<AggregatedProof> {
+        metrics::record_prove(Scheme::Groth16, 1, false, || {
+            ensure!(
+                proofs.len() == self.capacity,
+                "recursion pipeline expects {} proofs, got {}",
+                self.capacity,
+                proofs.len()
+            );
+            let proof_strs = proofs
+                .iter()
+                .map(|p| CString::new(p.proof.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let public_strs = proofs
+                .iter()
+                .map(|p| CString::new(p.public_inputs.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut proof_ptrs: Vec<*mut c_char> =
+                proof_strs.iter().map(|s| s.as_ptr() as *mut _).collect();
+            let mut public_ptrs: Vec<*mut c_char> =
+                public_strs.iter().map(|s| s.as_ptr() as *mut _).collect();
+
+            let mut out_proof = std::ptr::null_mut();
+            let mut out_public = std::ptr::null_mut();
+            let err_ptr = unsafe {
+                bind::gnark_recursion_aggregate(
+                    self.handle.id(),
+                    proof_ptrs.as_mut_ptr(),
+                    public_ptrs.as_mut_ptr(),
+                    u32::try_from(proofs.len())?,
+                    &mut out_proof,
+                    &mut out_public,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            unsafe {
+                Ok(AggregatedProof {
+                    proof: take_go_string(out_proof),
+                    public_inputs: take_go_string(out_public),
+                })
+            }
+        })
+    }
+
+    /// Prove over BN254 that `aggregated` is valid.
+    ///
+    /// The proof uses [`HashFunction::Sha256`](crate::HashFunction::Sha256)
+    /// for its commitment, as gnark's Solidity verifier (from
+    /// [`RecursionPipeline::wrap_verifying_key`]) expects, and encodes for it
+    /// with [`Groth16ProofResult::solidity_calldata`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipeline does not wrap, or
+    /// [`GnarkError::InvalidProof`] if `aggregated` is invalid.
+    pub fn wrap(&self, aggregated: &AggregatedProof) -> Result<Groth16ProofResult> {
+        metrics::record_prove(Scheme::Groth16, 1, false, || {
+            ensure!(
+                self.evm_wrap,
+                "recursion pipeline was built without the BN254 wrap"
+            );
+            let proof = CString::new(aggregated.proof.as_str())?;
+            let public_inputs = CString::new(aggregated.public_inputs.as_str())?;
+            let mut out_proof = std::ptr::null_mut();
+            let mut out_public = std::ptr::null_mut();
+            let err_ptr = unsafe {
+                bind::gnark_recursion_wrap(
+                    self.handle.id(),
+                    proof.as_ptr() as *mut c_char,
+                    public_inputs.as_ptr() as *mut c_char,
+                    &mut out_proof,
+                    &mut out_public,
+                )
+            };
+            if !err_ptr.is_null() {
+                return Err(unsafe { GnarkError::take(err_ptr) }.into());
+            }
+            unsafe {
+                Ok(Groth16ProofResult {
+                    proof: take_go_string(out_proof),
+                    public_inputs: take_go_string(out_public),
+                })
+            }
+        })
+    }
+
+    /// Verify an outer proof from [`RecursionPipeline::aggregate`].
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the proof is valid, `Ok(false)` if it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof or public inputs fail to deserialize.
+    pub fn verify(&self, aggregated: &AggregatedProof) -> Result<bool> {
+        self.verify_raw(&aggregated.proof, &aggregated.public_inputs, false)
+    }
+
+    /// Verify a BN254 proof from [`RecursionPipeline::wrap`].
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the proof is valid, `Ok(false)` if it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipeline does not wrap, or the proof or public
+    /// inputs fail to deserialize.
+    pub fn verify_wrapped(&self, wrapped: &Groth16ProofResult) -> Result<bool> {
+        ensure!(
+            self.evm_wrap,
+            "recursion pipeline was built without the BN254 wrap"
+        );
+        self.verify_raw(&wrapped.proof, &wrapped.public_inputs, true)
+    }
+
+    fn verify_raw(&self, proof: &str, public_inputs: &str, wrapped: bool) -> Result<bool> {
+        metrics::record_verify(Scheme::Groth16, false, || {
+            let proof = CString::new(proof)?;
+            let public_inputs = CString::new(public_inputs)?;
+            let err_ptr = unsafe {
+                bind::gnark_recursion_verify(
+                    self.handle.id(),
+                    proof.as_ptr() as *mut c_char,
+                    public_inputs.as_ptr() as *mut c_char,
+                    wrapped as c_int,
+                )
+            };
+            if err_ptr.is_null() {
+                return Ok(true);
+            }
+            match unsafe { GnarkError::take(err_ptr) } {
+                GnarkError::InvalidProof(_) => Ok(false),
+                err => Err(err.into()),
+            }
+        })
+    }
+
+    /// The BN254 wrap circuit's verifying key, e.g. for
+    /// [`VerifyingKey::export_solidity`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipeline does not wrap.
+    pub fn wrap_verifying_key(&self) -> Result<VerifyingKey> {
+        ensure!(
+            self.evm_wrap,
+            "recursion pipeline was built without the BN254 wrap"
+        );
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_recursion_wrap_vk(self.handle.id(), &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(VerifyingKey::from_handle(Handle::from_raw(id)))
+    }
+}
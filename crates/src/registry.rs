@@ -0,0 +1,225 @@
+//! Several circuits loaded side by side, addressed by handle.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::{groth16_verify, Groth16ProofResult, ProverSession, VerifyingKey};
+
+/// Distinguishes the handles of different registries.
+static NEXT_REGISTRY: AtomicU64 = AtomicU64::new(1);
+
+/// A circuit registered with a [`CircuitRegistry`].
+///
+/// Handles are cheap to copy and compare, and are only valid with the registry
+/// that returned them; a handle whose circuit has been unregistered stays
+/// invalid even if the name is registered again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CircuitHandle {
+    registry: u64,
+    index: usize,
+}
+
+struct Entry {
+    name: String,
+    session: Arc<ProverSession>,
+    r1cs_path: String,
+    vk_path: String,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Indexed by [`CircuitHandle::index`]; slots are never reused.
+    entries: Vec<Option<Arc<Entry>>>,
+    by_name: HashMap<String, usize>,
+}
+
+/// Circuits loaded once and proved by [`CircuitHandle`].
+///
+/// Registering a circuit loads it into a [`ProverSession`] and checks its
+/// verifying key against the proving key and constraint system (see
+/// [`ProverSession::check_verifying_key`]), so artifacts from different setups
+/// or circuits are rejected up front instead of producing proofs that never
+/// verify. Proving then looks the circuit up by index, without hashing a name
+/// or touching the files, and errors name the circuit.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// let registry = rust_gnark::CircuitRegistry::new();
+/// let cubic = registry.register_circuit("cubic", "cubic.r1cs", "cubic.pk", "cubic.vk")?;
+/// let proof = registry.prove(cubic, r#"{"X": "3", "Y": "35"}"#)?;
+/// assert!(registry.verify(cubic, &proof)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The registry can be shared between threads; proofs of the same or different
+/// circuits run concurrently.
+pub struct CircuitRegistry {
+    id: u64,
+    inner: RwLock<Inner>,
+}
+
+impl CircuitRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_REGISTRY.fetch_add(1, Ordering::Relaxed),
+            inner: RwLock::default(),
+        }
+    }
+
+    /// Load the circuit at `r1cs_path` with the keys at `pk_path` (read as
+    /// [`ProverSession::new`] reads it) and `vk_path`, under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a circuit named `name` is already registered, if a
+    /// file cannot be read, or [`crate::GnarkError::InvalidKey`] if the keys
+    /// do not belong together or to the circuit.
+    pub fn register_circuit(
+        &self,
+        name: &str,
+        r1cs_path: &str,
+        pk_path: &str,
+        vk_path: &str,
+    ) -> Result<CircuitHandle> {
+        if self.handle(name).is_some() {
+            bail!("a circuit named {name:?} is already registered");
+        }
+        let load = || -> Result<ProverSession> {
+            let session = ProverSession::new(r1cs_path, pk_path)?;
+            session.check_verifying_key(&VerifyingKey::open(vk_path)?)?;
+            Ok(session)
+        };
+        let session = load().with_context(|| format!("failed to register circuit {name:?}"))?;
+
+        let mut inner = self.inner.write().expect("circuit registry poisoned");
+        // Checked again, since another thread may have registered the name
+        // while this one was loading.
+        if inner.by_name.contains_key(name) {
+            bail!("a circuit named {name:?} is already registered");
+        }
+        let index = inner.entries.len();
+        inner.entries.push(Some(Arc::new(Entry {
+            name: name.to_owned(),
+            session: Arc::new(session),
+            r1cs_path: r1cs_path.to_owned(),
+            vk_path: vk_path.to_owned(),
+        })));
+        inner.by_name.insert(name.to_owned(), index);
+        Ok(CircuitHandle {
+            registry: self.id,
+            index,
+        })
+    }
+
+    /// The handle of the circuit registered as `name`.
+    pub fn handle(&self, name: &str) -> Option<CircuitHandle> {
+        let inner = self.inner.read().expect("circuit registry poisoned");
+        inner.by_name.get(name).map(|&index| CircuitHandle {
+            registry: self.id,
+            index,
+        })
+    }
+
+    /// The name `handle` was registered under.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not registered with this registry.
+    pub fn name(&self, handle: CircuitHandle) -> Result<String> {
+        Ok(self.entry(handle)?.name.clone())
+    }
+
+    /// The circuit's session, e.g. to submit it to a [`crate::ProvePool`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not registered with this registry.
+    pub fn session(&self, handle: CircuitHandle) -> Result<Arc<ProverSession>> {
+        Ok(Arc::clone(&self.entry(handle)?.session))
+    }
+
+    /// Generate a proof for `witness_json` (see [`crate::groth16_prove`]) with
+    /// the circuit `handle`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not registered with this registry, or
+    /// for the same reasons as [`ProverSession::prove`].
+    pub fn prove(&self, handle: CircuitHandle, witness_json: &str) -> Result<Groth16ProofResult> {
+        let entry = self.entry(handle)?;
+        entry
+            .session
+            .prove(witness_json)
+            .with_context(|| format!("failed to prove circuit {:?}", entry.name))
+    }
+
+    /// Verify a proof of the circuit `handle` (see [`groth16_verify`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not registered with this registry, or
+    /// for the same reasons as [`groth16_verify`].
+    pub fn verify(&self, handle: CircuitHandle, proof: &Groth16ProofResult) -> Result<bool> {
+        let entry = self.entry(handle)?;
+        groth16_verify(&entry.r1cs_path, &entry.vk_path, proof)
+            .with_context(|| format!("failed to verify a proof of circuit {:?}", entry.name))
+    }
+
+    /// Remove the circuit `handle`, returning whether it was registered. Its
+    /// session is released once running proofs finish.
+    pub fn unregister(&self, handle: CircuitHandle) -> bool {
+        if handle.registry != self.id {
+            return false;
+        }
+        let mut inner = self.inner.write().expect("circuit registry poisoned");
+        let Some(entry) = inner.entries.get_mut(handle.index).and_then(Option::take) else {
+            return false;
+        };
+        inner.by_name.remove(&entry.name);
+        true
+    }
+
+    /// The names of the registered circuits, in registration order.
+    pub fn names(&self) -> Vec<String> {
+        let inner = self.inner.read().expect("circuit registry poisoned");
+        inner
+            .entries
+            .iter()
+            .flatten()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    /// The entry for `handle`, cloned out so the lock is not held while
+    /// proving.
+    fn entry(&self, handle: CircuitHandle) -> Result<Arc<Entry>> {
+        if handle.registry != self.id {
+            return Err(anyhow!("the circuit handle belongs to another registry"));
+        }
+        let inner = self.inner.read().expect("circuit registry poisoned");
+        inner
+            .entries
+            .get(handle.index)
+            .cloned()
+            .flatten()
+            .ok_or_else(|| anyhow!("the circuit handle has been unregistered"))
+    }
+}
+
+impl Default for CircuitRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for CircuitRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitRegistry")
+            .field("circuits", &self.names())
+            .finish()
+    }
+}
@@ -0,0 +1,224 @@
+//! Proving on a remote service (the `remote` feature).
+//!
+//! A [`RemoteProver`] implements [`Prover`] by posting the witness to a
+//! proving service over HTTPS, so a weak device can hand its proofs to a
+//! server without changing the code that asks for them.
+//!
+//! The protocol is one request per proof: `POST <url>` with the witness JSON
+//! as the body (`Content-Type: application/json`), answered by `200` and a
+//! [`GnarkProofBundle`] (its [`GnarkProofBundle::to_bytes`] encoding). A
+//! witness that does not fit the circuit is answered by `400`, one that does
+//! not satisfy it by `422`, with the reason as the body. Each circuit has its
+//! own URL.
+
+use anyhow::{anyhow, Context, Result};
+use std::fmt;
+use std::time::Duration;
+
+use crate::metrics::{self, Scheme};
+use crate::{
+    groth16_verify, GnarkError, GnarkProofBundle, Groth16ProofResult, ProofSystem, Prover,
+    VerifyingKey,
+};
+
+/// The circuit files a [`RemoteProver`] checks its proofs against.
+struct Expected {
+    r1cs_path: String,
+    vk_path: String,
+    vk: VerifyingKey,
+}
+
+/// A [`Prover`] that delegates to a proving service.
+///
+/// Built with [`RemoteProver::new`] and the `with_*` methods:
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use rust_gnark::{Prover, RemoteProver};
+///
+/// let prover = RemoteProver::new("https://prover.example.com/v1/circuits/age-check/prove")
+///     .with_header("Authorization", "Bearer <token>")
+///     .with_verifying_key("circuit.r1cs", "circuit.vk")?;
+/// let proof = prover.prove(r#"{"X": "3", "Y": "35"}"#)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The service sees the witness, secret inputs included: a Groth16 prover
+/// cannot work from a blinded witness. What can be blinded is the proof, see
+/// [`RemoteProver::with_rerandomization`].
+pub struct RemoteProver {
+    url: String,
+    agent: ureq::Agent,
+    headers: Vec<(String, String)>,
+    expected: Option<Expected>,
+    rerandomize: bool,
+}
+
+impl fmt::Debug for RemoteProver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values are typically credentials.
+        let headers: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("RemoteProver")
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("vk_path", &self.expected.as_ref().map(|e| &e.vk_path))
+            .field("rerandomize", &self.rerandomize)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteProver {
+    /// Prove by posting witnesses to `url`.
+    ///
+    /// Requests time out after 30 seconds without a connection, or 10 minutes
+    /// in all; see [`RemoteProver::with_agent`] to change that.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(30))
+                .timeout(Duration::from_secs(600))
+                .build(),
+            headers: Vec::new(),
+            expected: None,
+            rerandomize: false,
+        }
+    }
+
+    /// Use `agent` for requests, e.g. for a proxy or custom timeouts.
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Send the header `name: value` with every request, e.g. for
+    /// authentication.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Check every proof the service returns before handing it out: the
+    /// bundle must be for the circuit of the `.vk` at `vk_path`, and the proof
+    /// must verify against it and the `.r1cs` at `r1cs_path`.
+    ///
+    /// Without it the service is trusted to prove the right circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the verifying key cannot be read.
+    pub fn with_verifying_key(mut self, r1cs_path: &str, vk_path: &str) -> Result<Self> {
+        self.expected = Some(Expected {
+            r1cs_path: r1cs_path.to_owned(),
+            vk_path: vk_path.to_owned(),
+            vk: VerifyingKey::open(vk_path)?,
+        });
+        Ok(self)
+    }
+
+    /// Re-randomize every proof the service returns (see
+    /// [`Groth16ProofResult::rerandomize`]), so the service cannot recognize
+    /// the proofs the app publishes and link them to the witnesses it saw.
+    ///
+    /// Needs [`RemoteProver::with_verifying_key`]; proving fails with
+    /// [`GnarkError::InvalidInput`] without it.
+    #[cfg(not(feature = "verifier"))]
+    pub fn with_rerandomization(mut self, rerandomize: bool) -> Self {
+        self.rerandomize = rerandomize;
+        self
+    }
+
+    /// The service's URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn request(&self, witness_json: &str) -> Result<GnarkProofBundle> {
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        let body = match request.send_string(witness_json) {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("failed to read the response of {}", self.url))?,
+            Err(ureq::Error::Status(status, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                return Err(match status {
+                    400 => GnarkError::InvalidWitness(message).into(),
+                    422 => GnarkError::WitnessSolveFailed {
+                        constraint_index: None,
+                        message,
+                    }
+                    .into(),
+                    _ => anyhow!("{}: HTTP {status}: {message}", self.url),
+                });
+            }
+            Err(err @ ureq::Error::Transport(_)) => {
+                return Err(anyhow!(err).context(format!("failed to reach {}", self.url)));
+            }
+        };
+        GnarkProofBundle::from_bytes(body.as_bytes())
+            .with_context(|| format!("{} did not answer with a proof bundle", self.url))
+    }
+
+    fn prove_remote(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        if self.rerandomize && self.expected.is_none() {
+            return Err(GnarkError::InvalidInput(
+                "re-randomizing remote proofs needs with_verifying_key".into(),
+            )
+            .into());
+        }
+        let bundle = self.request(witness_json)?;
+        let Some(expected) = &self.expected else {
+            return Ok(bundle.proof);
+        };
+        bundle.check(&expected.vk)?;
+        if !groth16_verify(&expected.r1cs_path, &expected.vk_path, &bundle.proof)? {
+            return Err(GnarkError::InvalidProof(format!(
+                "{} returned a proof that does not verify",
+                self.url
+            ))
+            .into());
+        }
+        #[cfg(not(feature = "verifier"))]
+        if self.rerandomize {
+            return bundle.proof.rerandomize(&expected.vk);
+        }
+        Ok(bundle.proof)
+    }
+}
+
+impl Prover for RemoteProver {
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        let _span = enter_span!("prove", remote = self.url.as_str());
+        crate::trace::timed(|| {
+            metrics::record_prove(Scheme::Groth16, 1, true, || self.prove_remote(witness_json))
+        })
+    }
+}
+
+/// Proves remotely; verifies locally against the circuit files of
+/// [`RemoteProver::with_verifying_key`], and fails with
+/// [`GnarkError::InvalidInput`] without them.
+impl ProofSystem for RemoteProver {
+    type Proof = Groth16ProofResult;
+
+    fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        Prover::prove(self, witness_json)
+    }
+
+    fn verify(&self, proof: &Groth16ProofResult) -> Result<bool> {
+        let Some(expected) = &self.expected else {
+            return Err(GnarkError::InvalidInput(
+                "verifying remote proofs needs with_verifying_key".into(),
+            )
+            .into());
+        };
+        groth16_verify(&expected.r1cs_path, &expected.vk_path, proof)
+    }
+}
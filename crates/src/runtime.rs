@@ -0,0 +1,246 @@
+//! Memory usage of the Go runtime behind the prover, the CPU features it
+//! relies on, and how it shares the process's threads and signals with the
+//! host.
+//!
+//! Proving grows the Go heap to the peak the circuit needs, and Go hands the
+//! freed memory back to the OS only slowly. Apps that run under memory
+//! pressure (iOS and Android kill the largest processes first) can report
+//! the prover's footprint with [`read_mem_stats`] and release it right after
+//! proving with [`free_os_memory`]:
+//!
+//! ```no_run
+//! # fn example() -> anyhow::Result<()> {
+//! let proof = rust_gnark::groth16_prove("circuit.r1cs", "circuit.pk", r#"{"X": "3", "Y": "35"}"#)?;
+//! rust_gnark::runtime::free_os_memory();
+//! let stats = rust_gnark::runtime::read_mem_stats();
+//! println!("Go heap: {} MiB, from the OS: {} MiB", stats.heap_in_use >> 20, stats.sys >> 20);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`cpu_features`] tells whether this CPU can run the library at all: on
+//! x86-64, gnark-crypto's BN254 assembly needs ADX and BMI2, which some older
+//! servers lack. The `portable` feature builds the library without it.
+//!
+//! By default Go runs its code on one thread per CPU and the prover keeps
+//! them all busy, which oversubscribes the CPU when the host has its own
+//! pools (rayon, tokio). [`set_thread_budget`] bounds the runtime for the
+//! whole process, and [`raise_thread_budget`] lifts the bound while a proof
+//! that should take more runs:
+//!
+//! ```no_run
+//! rust_gnark::runtime::set_thread_budget(2);
+//! // ...
+//! let _guard = rust_gnark::runtime::raise_thread_budget(8);
+//! let proof = rust_gnark::groth16_prove("circuit.r1cs", "circuit.pk", r#"{"X": "3", "Y": "35"}"#);
+//! ```
+//!
+//! The Go runtime handles `SIGSEGV` and the other synchronous signals itself
+//! (it turns faults in Go code into panics) and forwards those raised outside
+//! Go to the handler that was installed before it. Crash reporters
+//! (Crashlytics, Sentry, breakpad) that install their handlers later replace
+//! Go's, and keep the library working as long as they chain to the previous
+//! handler, which they all do by default. Go also needs every handler to run
+//! on the alternate signal stack (`SA_ONSTACK`), or it aborts the first time a
+//! signal lands on a goroutine: [`crate::init_with_options`] and
+//! [`fix_signal_stacks`] set it on handlers installed without it.
+
+use std::ffi::CString;
+use std::os::raw::c_int;
+
+use crate::bind;
+
+/// Settings for [`crate::init_with_options`]. `InitOptions::default()`
+/// gives what [`crate::init`] uses.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// // Let the app's crash reporter record fatal Go errors.
+/// let options = rust_gnark::runtime::InitOptions::default().with_crash_on_fatal(true);
+/// rust_gnark::init_with_options(&options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitOptions {
+    pub(crate) async_preemption: Option<bool>,
+    pub(crate) godebug: Vec<String>,
+    pub(crate) crash_on_fatal: bool,
+}
+
+impl InitOptions {
+    /// Whether the Go scheduler preempts goroutines with `SIGURG`. By
+    /// default it is off on iOS and macOS, where the signals flood debuggers
+    /// and crash reporters, and on everywhere else. Long proofs stay correct
+    /// without it; only tight loops are no longer interrupted.
+    pub fn with_async_preemption(mut self, enabled: bool) -> Self {
+        self.async_preemption = Some(enabled);
+        self
+    }
+
+    /// Add a `GODEBUG` setting such as `"madvdontneed=1"`, after the ones in
+    /// the environment. Only settings the Go runtime rereads when `GODEBUG`
+    /// changes apply this way; those read once at startup (like `cgocheck`)
+    /// must be in the environment before the library is loaded.
+    pub fn with_godebug(mut self, setting: impl Into<String>) -> Self {
+        self.godebug.push(setting.into());
+        self
+    }
+
+    /// Have a fatal Go error (an unrecovered panic outside a prover call, a
+    /// deadlock, running out of memory) abort the process with `SIGABRT`
+    /// after printing its goroutines (`GOTRACEBACK=crash`), so that the host's
+    /// crash reporter records it. Without this Go exits with status 2, which
+    /// crash reporters do not see.
+    pub fn with_crash_on_fatal(mut self, enabled: bool) -> Self {
+        self.crash_on_fatal = enabled;
+        self
+    }
+
+    pub(crate) fn async_preemption_raw(&self) -> c_int {
+        match self.async_preemption {
+            None => -1,
+            Some(enabled) => enabled as c_int,
+        }
+    }
+
+    pub(crate) fn godebug_raw(&self) -> anyhow::Result<CString> {
+        Ok(CString::new(self.godebug.join(","))?)
+    }
+}
+
+/// Set `SA_ONSTACK` on the host's handlers for the synchronous signals
+/// (`SIGSEGV`, `SIGBUS`, `SIGABRT`...) that lack it, and return how many it
+/// changed. Call it after installing a crash reporter once the library is
+/// initialized; [`crate::init_with_options`] does the same for the handlers
+/// installed before it. Does nothing on Windows.
+pub fn fix_signal_stacks() -> usize {
+    unsafe { bind::gnark_fix_signal_stacks() }.max(0) as usize
+}
+
+/// Go runtime memory statistics, in bytes (see Go's `runtime.MemStats`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct MemStats {
+    /// Live heap objects, plus garbage not collected yet (`HeapAlloc`).
+    pub heap_alloc: u64,
+    /// Heap spans holding at least one object (`HeapInuse`).
+    pub heap_in_use: u64,
+    /// Heap memory already returned to the OS (`HeapReleased`).
+    pub heap_released: u64,
+    /// Everything the runtime has obtained from the OS, heap or not (`Sys`).
+    pub sys: u64,
+    /// Completed garbage collection cycles.
+    pub num_gc: u64,
+}
+
+/// Read the Go runtime's memory statistics. Briefly pauses all Go code,
+/// including proofs in progress.
+pub fn read_mem_stats() -> MemStats {
+    let mut stats = MemStats::default();
+    unsafe {
+        bind::gnark_read_mem_stats(
+            &mut stats.heap_alloc,
+            &mut stats.heap_in_use,
+            &mut stats.heap_released,
+            &mut stats.sys,
+            &mut stats.num_gc,
+        )
+    };
+    stats
+}
+
+/// Collect garbage and return as much memory as possible to the OS.
+///
+/// Blocks for a full garbage collection, which takes longer the larger the
+/// live heap; call it after proving, not during.
+pub fn free_os_memory() {
+    unsafe { bind::gnark_free_os_memory() };
+}
+
+/// Bound the Go runtime to `threads` threads running Go code (`GOMAXPROCS`),
+/// and size the prover's pools that default to one worker per CPU (the
+/// witness solver's, the concurrent proofs of
+/// [`ProverSession::prove_many`](crate::ProverSession::prove_many)) to it.
+/// `0` lifts the budget, back to one thread per CPU. Returns the previous
+/// budget, `0` if there was none.
+///
+/// Calls with [`ProverConfig::with_threads`](crate::ProverConfig::with_threads)
+/// still use their own thread count, and return to the budget afterwards.
+/// This waits for those in progress.
+pub fn set_thread_budget(threads: usize) -> usize {
+    let threads = threads.try_into().unwrap_or(c_int::MAX);
+    unsafe { bind::gnark_set_thread_budget(threads, 0) }.max(0) as usize
+}
+
+/// Raise the thread budget to `threads` until the returned guard is dropped,
+/// e.g. for a proof the user is waiting on. A larger budget, or none, is
+/// kept as it is.
+///
+/// Guards restore the budget they replaced, so nested ones must be dropped
+/// in reverse order, and [`set_thread_budget`] calls made meanwhile are
+/// undone.
+pub fn raise_thread_budget(threads: usize) -> ThreadBudgetGuard {
+    let c_threads = threads.try_into().unwrap_or(c_int::MAX);
+    let previous = unsafe { bind::gnark_set_thread_budget(c_threads, 1) }.max(0) as usize;
+    ThreadBudgetGuard {
+        previous: (previous != 0 && previous < threads).then_some(previous),
+    }
+}
+
+/// Restores the thread budget [`raise_thread_budget`] replaced when dropped.
+#[must_use = "the budget is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ThreadBudgetGuard {
+    previous: Option<usize>,
+}
+
+impl Drop for ThreadBudgetGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous {
+            set_thread_budget(previous);
+        }
+    }
+}
+
+/// CPU features gnark-crypto's assembly depends on, as the Go runtime
+/// detects them (all `false` off x86-64).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuFeatures {
+    /// ADX (`ADCX`, `ADOX`).
+    pub adx: bool,
+    /// BMI2 (`MULX`).
+    pub bmi2: bool,
+    /// AVX2.
+    pub avx2: bool,
+    /// AVX-512 Foundation.
+    pub avx512f: bool,
+    /// Whether the library was built with gnark-crypto's assembly, that is
+    /// without the `portable` feature.
+    pub assembly: bool,
+}
+
+impl CpuFeatures {
+    /// Whether the library runs on this CPU: the assembly needs ADX and BMI2
+    /// on x86-64, while the portable build and other architectures run
+    /// anywhere.
+    pub fn supported(&self) -> bool {
+        !self.assembly || !cfg!(target_arch = "x86_64") || (self.adx && self.bmi2)
+    }
+}
+
+/// Detect this CPU's [`CpuFeatures`]. Runs no field arithmetic, so it is safe
+/// to call on a CPU the library does not support.
+pub fn cpu_features() -> CpuFeatures {
+    let (mut adx, mut bmi2, mut avx2, mut avx512f, mut assembly) = (0, 0, 0, 0, 0);
+    unsafe {
+        bind::gnark_cpu_features(&mut adx, &mut bmi2, &mut avx2, &mut avx512f, &mut assembly)
+    };
+    CpuFeatures {
+        adx: adx != 0,
+        bmi2: bmi2 != 0,
+        avx2: avx2 != 0,
+        avx512f: avx512f != 0,
+        assembly: assembly != 0,
+    }
+}
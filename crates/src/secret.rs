@@ -0,0 +1,154 @@
+//! Witnesses held in locked, zeroized memory.
+
+use anyhow::{ensure, Result};
+use std::alloc::{self, Layout};
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr::NonNull;
+use zeroize::Zeroize;
+
+use crate::Witness;
+
+/// A witness JSON kept out of swap and wiped when dropped.
+///
+/// The bytes live in their own page-aligned allocation, which is `mlock`ed on
+/// Unix so the kernel never writes it to swap (or a hibernation image), and is
+/// zeroized before it is freed. The `String` it is built from is zeroized as
+/// soon as it has been copied.
+///
+/// Proving with [`crate::groth16_prove_secret`] or
+/// [`crate::ProverSession::prove_secret`] passes the locked bytes to Go
+/// without another copy on the Rust side, and turns on
+/// [`crate::ProverConfig::with_witness_scrubbing`] for the Go side's copies.
+///
+/// ```no_run
+/// # fn example(session: &rust_gnark::ProverSession) -> anyhow::Result<()> {
+/// let witness = rust_gnark::SecretWitness::new(r#"{"X": "3", "Y": "35"}"#.to_owned())?;
+/// let proof = session.prove_secret(&witness)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Locking is best effort: it fails when the process is over its
+/// `RLIMIT_MEMLOCK`, and is not attempted on other platforms; see
+/// [`SecretWitness::is_locked`]. Wiping on drop always happens.
+pub struct SecretWitness {
+    ptr: NonNull<u8>,
+    /// Length of the JSON, without the NUL terminator.
+    len: usize,
+    layout: Layout,
+    locked: bool,
+}
+
+// The allocation is owned and never written after construction.
+unsafe impl Send for SecretWitness {}
+unsafe impl Sync for SecretWitness {}
+
+impl SecretWitness {
+    /// Take `witness_json` (as [`crate::groth16_prove`] takes it) into locked
+    /// memory, zeroizing the original.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON contains a NUL byte, in which case
+    /// `witness_json` is zeroized all the same.
+    pub fn new(mut witness_json: String) -> Result<Self> {
+        let nul = witness_json.bytes().any(|b| b == 0);
+        if nul {
+            witness_json.zeroize();
+        }
+        ensure!(!nul, "witness JSON contains a NUL byte");
+
+        let len = witness_json.len();
+        let page = page_size();
+        let size = (len + 1).div_ceil(page) * page;
+        let layout = Layout::from_size_align(size, page)?;
+        // Zeroed, so the byte after the JSON is its NUL terminator.
+        let ptr = NonNull::new(unsafe { alloc::alloc_zeroed(layout) })
+            .unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        // Lock before copying so the secret is never in swappable memory.
+        let locked = lock(ptr, size);
+        unsafe { std::ptr::copy_nonoverlapping(witness_json.as_ptr(), ptr.as_ptr(), len) };
+        witness_json.zeroize();
+        Ok(Self {
+            ptr,
+            len,
+            layout,
+            locked,
+        })
+    }
+
+    /// [`SecretWitness::new`] on [`Witness::to_json`].
+    ///
+    /// The [`Witness`] itself is not wiped; drop it as soon as possible.
+    ///
+    /// # Errors
+    ///
+    /// As [`SecretWitness::new`].
+    pub fn from_witness(witness: &Witness) -> Result<Self> {
+        Self::new(witness.to_json())
+    }
+
+    /// Whether the memory is locked against swapping.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub(crate) fn as_c_str(&self) -> &CStr {
+        let bytes = unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len + 1) };
+        CStr::from_bytes_with_nul(bytes).expect("witness JSON is NUL-terminated")
+    }
+}
+
+impl Drop for SecretWitness {
+    fn drop(&mut self) {
+        let size = self.layout.size();
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), size) }.zeroize();
+        if self.locked {
+            unlock(self.ptr, size);
+        }
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+impl fmt::Debug for SecretWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretWitness")
+            .field("len", &self.len)
+            .field("locked", &self.locked)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Allocations are rounded to whole pages, since `mlock` locks pages and
+/// unlocking one witness must not unlock a neighbour's memory.
+#[cfg(unix)]
+fn page_size() -> usize {
+    match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as usize,
+        _ => 4096,
+    }
+}
+
+#[cfg(not(unix))]
+fn page_size() -> usize {
+    4096
+}
+
+#[cfg(unix)]
+fn lock(ptr: NonNull<u8>, size: usize) -> bool {
+    unsafe { libc::mlock(ptr.as_ptr().cast(), size) == 0 }
+}
+
+#[cfg(not(unix))]
+fn lock(_ptr: NonNull<u8>, _size: usize) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn unlock(ptr: NonNull<u8>, size: usize) {
+    unsafe { libc::munlock(ptr.as_ptr().cast(), size) };
+}
+
+#[cfg(not(unix))]
+fn unlock(_ptr: NonNull<u8>, _size: usize) {}
@@ -0,0 +1,78 @@
+//! A startup check that the linked prover works on this device.
+
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::{bind, GnarkError};
+
+/// Phase timings of a passing [`self_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SelfTestReport {
+    /// Compiling the test circuit.
+    pub compile: Duration,
+    /// The Groth16 setup.
+    pub setup: Duration,
+    /// Proving, witness solving included.
+    pub prove: Duration,
+    /// Verifying the proof.
+    pub verify: Duration,
+}
+
+impl SelfTestReport {
+    /// The time of all four phases.
+    pub fn total(&self) -> Duration {
+        self.compile + self.setup + self.prove + self.verify
+    }
+}
+
+/// Run a tiny built-in circuit (`x³ + x + 5 == y`) through compilation,
+/// setup, proving and verification in memory, and check that its proof is
+/// rejected for another public input.
+///
+/// It takes milliseconds, so it can run at every startup (after [`crate::init`])
+/// to catch a broken or mismatched prebuilt library, or a device it crashes
+/// on, before a user's first real proof. A panic in Go is reported as
+/// [`GnarkError::InternalPanic`]. The circuit is far too small to say
+/// anything about memory limits: see [`crate::ProverConfig::with_memory_limit`]
+/// and [`crate::CompiledCircuit::stats`] for real circuits.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// rust_gnark::init()?;
+/// let report = rust_gnark::self_test()?;
+/// println!("prover ok, {:?} for a proof", report.prove);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GnarkError::Other`] without running anything if the library's
+/// assembly cannot run on this CPU (see
+/// [`crate::runtime::CpuFeatures::supported`]), the error of the first phase
+/// that fails, or [`GnarkError::InvalidProof`] if the proof does not verify
+/// or verifies against the wrong public input.
+pub fn self_test() -> Result<SelfTestReport> {
+    let _span = enter_span!("self_test");
+    let cpu = crate::runtime::cpu_features();
+    if !cpu.supported() {
+        return Err(GnarkError::Other(format!(
+            "this CPU lacks the ADX and BMI2 instructions the prover's assembly needs \
+             ({cpu:?}); build with the `portable` feature"
+        ))
+        .into());
+    }
+    let mut nanos = [0i64; 4];
+    let err_ptr = unsafe { bind::gnark_self_test(nanos.as_mut_ptr()) };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    let [compile, setup, prove, verify] = nanos.map(|n| Duration::from_nanos(n.max(0) as u64));
+    Ok(SelfTestReport {
+        compile,
+        setup,
+        prove,
+        verify,
+    })
+}
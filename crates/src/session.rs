@@ -0,0 +1,343 @@
+//! Prover sessions that keep a circuit loaded across proofs.
+
+use anyhow::{Context, Result};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use crate::handle::Handle;
+use crate::metrics::{self, Scheme};
+use crate::{
+    bind, c_prove_options, run_prove, take_proof_result, CancelToken, GnarkError,
+    Groth16ProofResult, ProgressReporter, ProveOptions, ProverConfig, ProvingKey, SecretWitness,
+    SerializationFormat, VerifyingKey, WitnessFeeder,
+};
+
+/// A constraint system and proving key deserialized once and kept in Go
+/// memory.
+///
+/// [`crate::groth16_prove`] reads both files on every call, and
+/// [`crate::groth16_prove_with_key`] still re-reads the `.r1cs`. A session
+/// does neither, so repeated proofs only pay for witness solving and the
+/// prover itself. Both objects are released when the session is dropped.
+///
+/// The session is only read while proving, so one session can be shared
+/// between threads and prove concurrently. Its proving key can be replaced
+/// without stopping it, with [`ProverSession::swap_key`].
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// let session = rust_gnark::ProverSession::new("circuit.r1cs", "circuit.pk")?;
+/// for x in 0..10u64 {
+///     let y = x * x * x + x + 5;
+///     let proof = session.prove(&format!(r#"{{"X": "{x}", "Y": "{y}"}}"#))?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ProverSession {
+    handle: Handle,
+}
+
+impl ProverSession {
+    /// Load the `.r1cs` at `r1cs_path` and memory-map the `.pk` at `pk_path`.
+    ///
+    /// The key is read like [`crate::ProvingKey::open_mmap`], without point
+    /// validation, so it must be a trusted raw key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read or is invalid.
+    pub fn new(r1cs_path: &str, pk_path: &str) -> Result<Self> {
+        Self::with_key_format(r1cs_path, pk_path, SerializationFormat::RawUnchecked)
+    }
+
+    /// Like [`ProverSession::new`], but reading the proving key in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read or is invalid.
+    pub fn with_key_format(
+        r1cs_path: &str,
+        pk_path: &str,
+        format: SerializationFormat,
+    ) -> Result<Self> {
+        let r1cs = CString::new(r1cs_path)?;
+        let pk = CString::new(pk_path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_prover_session_new(
+                r1cs.as_ptr() as *mut c_char,
+                pk.as_ptr() as *mut c_char,
+                format.as_raw(),
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Generate a proof for `witness_json` (see [`crate::groth16_prove`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if witness construction or proof generation fails.
+    pub fn prove(&self, witness_json: &str) -> Result<Groth16ProofResult> {
+        self.prove_with_options(witness_json, &ProveOptions::default())
+    }
+
+    /// Generate a proof using the prover settings in `config`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::prove`].
+    pub fn prove_with_config(
+        &self,
+        witness_json: &str,
+        config: &ProverConfig,
+    ) -> Result<Groth16ProofResult> {
+        let options = ProveOptions {
+            config: Some(config),
+            ..Default::default()
+        };
+        self.prove_with_options(witness_json, &options)
+    }
+
+    /// Generate a proof that can be aborted through `token`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof is cancelled, or for the same reasons as
+    /// [`ProverSession::prove`].
+    pub fn prove_cancellable(
+        &self,
+        witness_json: &str,
+        token: &CancelToken,
+    ) -> Result<Groth16ProofResult> {
+        let options = ProveOptions {
+            cancel: Some(token),
+            ..Default::default()
+        };
+        self.prove_with_options(witness_json, &options)
+    }
+
+    /// Generate a proof, reporting progress to `reporter`. No loading phases
+    /// are reported, since nothing is loaded.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::prove`].
+    pub fn prove_with_progress(
+        &self,
+        witness_json: &str,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<Groth16ProofResult> {
+        let options = ProveOptions {
+            progress: Some(reporter),
+            ..Default::default()
+        };
+        self.prove_with_options(witness_json, &options)
+    }
+
+    /// Generate a proof, asking `feeder` for the circuit's
+    /// [`crate::CircuitBuilder::feed`] values (see
+    /// [`crate::groth16_prove_with_feeder`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns the feeder's error if it fails, or an error for the same
+    /// reasons as [`ProverSession::prove`].
+    pub fn prove_with_feeder(
+        &self,
+        witness_json: &str,
+        feeder: &dyn WitnessFeeder,
+    ) -> Result<Groth16ProofResult> {
+        let options = ProveOptions {
+            feeder: Some(feeder),
+            ..Default::default()
+        };
+        self.prove_with_options(witness_json, &options)
+    }
+
+    /// Generate a proof for a [`SecretWitness`], with
+    /// [`ProverConfig::with_witness_scrubbing`] on.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::prove`].
+    pub fn prove_secret(&self, witness: &SecretWitness) -> Result<Groth16ProofResult> {
+        let config = ProverConfig::default().with_witness_scrubbing(true);
+        let options = ProveOptions {
+            config: Some(&config),
+            ..Default::default()
+        };
+        self.prove_c(witness.as_c_str(), &options)
+    }
+
+    /// Prove every witness of `witnesses` (see [`crate::groth16_prove`]),
+    /// returning the proofs in the same order.
+    ///
+    /// For throughput rather than latency: the proofs share the session's
+    /// circuit and key (the key current when the batch starts, with the FFT
+    /// domain gnark keeps in it), and Go runs a few at a time, so that one
+    /// proof's witness solving overlaps another's MSMs. Each proof still uses
+    /// every core while it runs; for a bound on threads or memory, see
+    /// [`ProverSession::prove_many_with_config`].
+    ///
+    /// ```no_run
+    /// # fn example(session: &rust_gnark::ProverSession) -> anyhow::Result<()> {
+    /// let witnesses: Vec<String> = (0..16u64)
+    ///     .map(|x| format!(r#"{{"X": "{x}", "Y": "{}"}}"#, x * x * x + x + 5))
+    ///     .collect();
+    /// let proofs = session.prove_many(&witnesses)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first witness whose proof fails, naming its
+    /// index. The other proofs still run.
+    pub fn prove_many(&self, witnesses: &[impl AsRef<str>]) -> Result<Vec<Groth16ProofResult>> {
+        self.prove_many_with_config(witnesses, &ProverConfig::default())
+    }
+
+    /// Like [`ProverSession::prove_many`], using the prover settings in
+    /// `config` for every proof; [`ProverConfig::with_threads`] and
+    /// [`ProverConfig::with_memory_limit`] bound the whole batch.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ProverSession::prove_many`].
+    pub fn prove_many_with_config(
+        &self,
+        witnesses: &[impl AsRef<str>],
+        config: &ProverConfig,
+    ) -> Result<Vec<Groth16ProofResult>> {
+        if witnesses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let _span = enter_span!("prove_many", count = witnesses.len());
+        crate::ensure_init();
+        let proofs = witnesses.len();
+        crate::trace::timed(|| {
+            metrics::record_prove(Scheme::Groth16, proofs, false, || {
+                let jsons = witnesses
+                    .iter()
+                    .map(|w| CString::new(w.as_ref()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut json_ptrs: Vec<*mut c_char> =
+                    jsons.iter().map(|s| s.as_ptr() as *mut _).collect();
+                let mut results = vec![std::ptr::null_mut(); witnesses.len()];
+                let mut c_options = c_prove_options(config, None);
+                let err_ptr = unsafe {
+                    bind::gnark_prover_session_prove_many(
+                        self.handle.id(),
+                        json_ptrs.as_mut_ptr(),
+                        json_ptrs.len() as u64,
+                        &mut c_options,
+                        results.as_mut_ptr(),
+                    )
+                };
+                if !err_ptr.is_null() {
+                    return Err(unsafe { GnarkError::take(err_ptr) }.into());
+                }
+                // Take every result, so that all are freed, before failing.
+                let results: Vec<_> = results
+                    .into_iter()
+                    .map(|result| unsafe { take_proof_result(result) })
+                    .collect();
+                results
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, result)| result.with_context(|| format!("witness {i}")))
+                    .collect()
+            })
+        })
+    }
+
+    /// Replace the session's proving key with `pk`, e.g. when a circuit's keys
+    /// are rotated, without reloading the constraint system.
+    ///
+    /// Proofs that have already started finish with the old key; proofs
+    /// started afterwards use `pk`. The session shares the Go key with `pk`,
+    /// which can be dropped right away, and the old key's memory is released
+    /// once its last proof finishes and every [`ProvingKey`] referring to it
+    /// (see [`ProverSession::proving_key`]) is dropped.
+    ///
+    /// Only the key's size is checked against the circuit, not that it
+    /// matches the verifying key its proofs will be checked with (see
+    /// [`ProverSession::check_verifying_key`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidKey`] if `pk` is sized for another
+    /// circuit, in which case the session keeps its key.
+    pub fn swap_key(&self, pk: &ProvingKey) -> Result<()> {
+        let err_ptr =
+            unsafe { bind::gnark_prover_session_swap_key(self.handle.id(), pk.handle_id()) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// The session's current proving key, as a [`ProvingKey`] sharing its Go
+    /// memory. It stays valid after [`ProverSession::swap_key`] replaces it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session is invalid.
+    pub fn proving_key(&self) -> Result<ProvingKey> {
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_prover_session_key(self.handle.id(), &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(ProvingKey::from_handle(Handle::from_raw(id)))
+    }
+
+    /// Check that `vk` comes from the same setup as the session's proving key,
+    /// and that both match its circuit's size, public inputs and commitments,
+    /// so proofs from the session verify against it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GnarkError::InvalidKey`] naming the first mismatch.
+    pub fn check_verifying_key(&self, vk: &VerifyingKey) -> Result<()> {
+        let err_ptr =
+            unsafe { bind::gnark_prover_session_check_vk(self.handle.id(), vk.handle_id()) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn handle_id(&self) -> u64 {
+        self.handle.id()
+    }
+
+    fn prove_with_options(
+        &self,
+        witness_json: &str,
+        options: &ProveOptions<'_>,
+    ) -> Result<Groth16ProofResult> {
+        self.prove_c(&CString::new(witness_json)?, options)
+    }
+
+    fn prove_c(&self, witness: &CStr, options: &ProveOptions<'_>) -> Result<Groth16ProofResult> {
+        run_prove(options, |c_options| {
+            Ok(unsafe {
+                bind::gnark_prover_session_prove(
+                    self.handle.id(),
+                    witness.as_ptr() as *mut c_char,
+                    c_options,
+                )
+            })
+        })
+    }
+}
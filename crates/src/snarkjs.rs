@@ -0,0 +1,211 @@
+//! snarkjs-compatible representation of Groth16 BN254 proofs.
+//!
+//! snarkjs writes proofs as JSON objects of decimal strings with points in
+//! projective form (`z = 1`, or `[0, 1, 0]` for infinity). `G2` coordinates are
+//! ordered `[c0, c1]`, the opposite of gnark's binary encoding.
+//!
+//! With the `snarkjs` feature, [`Proof`] and [`PublicInputs`] read and write
+//! snarkjs' `proof.json` and `public.json` directly, so gnark proofs can be fed
+//! to pipelines that already verify snarkjs artifacts (and vice versa):
+//!
+//! ```no_run
+//! # #[cfg(feature = "snarkjs")]
+//! # fn example(result: &rust_gnark::Groth16ProofResult) -> anyhow::Result<()> {
+//! let (proof, public) = rust_gnark::snarkjs::from_proof_result(result)?;
+//! std::fs::write("proof.json", proof.to_snarkjs_json())?;
+//! std::fs::write("public.json", public.to_snarkjs_json())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{ensure, Context, Result};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ff::{BigInt, PrimeField};
+
+use crate::native::Proof;
+#[cfg(feature = "snarkjs")]
+use crate::native::{decode_hex, PublicInputs};
+#[cfg(feature = "snarkjs")]
+use crate::Groth16ProofResult;
+
+/// A proof in snarkjs' `proof.json` layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnarkjsProof {
+    /// `[A]1` as `[x, y, z]`.
+    pub pi_a: [String; 3],
+    /// `[B]2` as `[[x.c0, x.c1], [y.c0, y.c1], [z.c0, z.c1]]`.
+    pub pi_b: [[String; 2]; 3],
+    /// `[C]1` as `[x, y, z]`.
+    pub pi_c: [String; 3],
+    /// Always `"groth16"`.
+    pub protocol: String,
+    /// Always `"bn128"` (snarkjs' name for BN254).
+    pub curve: String,
+}
+
+impl From<&Proof> for SnarkjsProof {
+    fn from(proof: &Proof) -> Self {
+        Self {
+            pi_a: g1_to_strings(&proof.ar),
+            pi_b: g2_to_strings(&proof.bs),
+            pi_c: g1_to_strings(&proof.krs),
+            protocol: "groth16".to_string(),
+            curve: "bn128".to_string(),
+        }
+    }
+}
+
+impl TryFrom<&SnarkjsProof> for Proof {
+    type Error = anyhow::Error;
+
+    fn try_from(proof: &SnarkjsProof) -> Result<Self> {
+        ensure!(
+            proof.protocol == "groth16",
+            "unsupported protocol {:?}",
+            proof.protocol
+        );
+        ensure!(
+            proof.curve == "bn128" || proof.curve == "bn254",
+            "unsupported curve {:?}",
+            proof.curve
+        );
+        Ok(Self {
+            ar: g1_from_strings(&proof.pi_a).context("pi_a")?,
+            bs: g2_from_strings(&proof.pi_b).context("pi_b")?,
+            krs: g1_from_strings(&proof.pi_c).context("pi_c")?,
+            commitments: Vec::new(),
+            commitment_pok: G1Affine::identity(),
+        })
+    }
+}
+
+#[cfg(feature = "snarkjs")]
+impl Proof {
+    /// Encode as snarkjs' `proof.json`.
+    pub fn to_snarkjs_json(&self) -> String {
+        serde_json::to_string_pretty(&SnarkjsProof::from(self))
+            .expect("snarkjs proof serializes to JSON")
+    }
+
+    /// Decode a snarkjs `proof.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a snarkjs Groth16 BN254 proof or a
+    /// point is invalid.
+    pub fn from_snarkjs_json(json: &str) -> Result<Self> {
+        let proof: SnarkjsProof =
+            serde_json::from_str(json).context("failed to parse snarkjs proof JSON")?;
+        Self::try_from(&proof)
+    }
+}
+
+#[cfg(feature = "snarkjs")]
+impl PublicInputs {
+    /// Encode as snarkjs' `public.json`: an array of decimal strings.
+    pub fn to_snarkjs_json(&self) -> String {
+        let values: Vec<String> = self.0.iter().map(to_decimal).collect();
+        serde_json::to_string_pretty(&values).expect("public inputs serialize to JSON")
+    }
+
+    /// Decode a snarkjs `public.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not an array of canonical decimal field elements.
+    pub fn from_snarkjs_json(json: &str) -> Result<Self> {
+        let values: Vec<String> =
+            serde_json::from_str(json).context("failed to parse snarkjs public inputs JSON")?;
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| from_decimal(v).with_context(|| format!("public input {i}")))
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+/// Decode the hex-encoded prover output into the types that convert to snarkjs JSON.
+///
+/// # Errors
+///
+/// Returns an error if the proof or public inputs fail to decode, or the proof
+/// carries Pedersen commitments (which snarkjs has no representation for).
+#[cfg(feature = "snarkjs")]
+pub fn from_proof_result(result: &Groth16ProofResult) -> Result<(Proof, PublicInputs)> {
+    let proof = Proof::from_gnark_bytes(&decode_hex(&result.proof)?)?;
+    ensure!(
+        proof.commitments.is_empty(),
+        "the proof carries Pedersen commitments, which snarkjs cannot represent"
+    );
+    let public_inputs = PublicInputs::from_gnark_bytes(&decode_hex(&result.public_inputs)?)?;
+    Ok((proof, public_inputs))
+}
+
+/// Decimal string of a field element's canonical integer.
+pub(crate) fn to_decimal<F: PrimeField>(x: &F) -> String {
+    x.into_bigint().to_string()
+}
+
+/// Parse a canonical decimal string into a field element.
+pub(crate) fn from_decimal<F: PrimeField<BigInt = BigInt<4>>>(s: &str) -> Result<F> {
+    let n: BigInt<4> = s
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid decimal integer {s:?}"))?;
+    F::from_bigint(n).with_context(|| format!("{s} is not a canonical field element"))
+}
+
+fn g1_to_strings(p: &G1Affine) -> [String; 3] {
+    if p.infinity {
+        return ["0".into(), "1".into(), "0".into()];
+    }
+    [to_decimal(&p.x), to_decimal(&p.y), "1".into()]
+}
+
+fn g2_to_strings(p: &G2Affine) -> [[String; 2]; 3] {
+    if p.infinity {
+        return [
+            ["0".into(), "0".into()],
+            ["1".into(), "0".into()],
+            ["0".into(), "0".into()],
+        ];
+    }
+    [
+        [to_decimal(&p.x.c0), to_decimal(&p.x.c1)],
+        [to_decimal(&p.y.c0), to_decimal(&p.y.c1)],
+        ["1".into(), "0".into()],
+    ]
+}
+
+fn g1_from_strings(p: &[String; 3]) -> Result<G1Affine> {
+    match p[2].as_str() {
+        "0" => return Ok(G1Affine::identity()),
+        "1" => {}
+        z => anyhow::bail!("expected affine point with z = 1, got z = {z}"),
+    }
+    let point = G1Affine::new_unchecked(from_decimal::<Fq>(&p[0])?, from_decimal::<Fq>(&p[1])?);
+    ensure!(point.is_on_curve(), "G1 point is not on the curve");
+    Ok(point)
+}
+
+fn g2_from_strings(p: &[[String; 2]; 3]) -> Result<G2Affine> {
+    let fq2 = |c: &[String; 2]| -> Result<Fq2> {
+        Ok(Fq2::new(from_decimal(&c[0])?, from_decimal(&c[1])?))
+    };
+    let z = fq2(&p[2])?;
+    if z == Fq2::from(0u64) {
+        return Ok(G2Affine::identity());
+    }
+    ensure!(
+        z == Fq2::from(1u64),
+        "expected affine point with z = [1, 0]"
+    );
+    let point = G2Affine::new_unchecked(fq2(&p[0])?, fq2(&p[1])?);
+    ensure!(point.is_on_curve(), "G2 point is not on the curve");
+    ensure!(
+        point.is_in_correct_subgroup_assuming_on_curve(),
+        "G2 point is not in the prime-order subgroup"
+    );
+    Ok(point)
+}
@@ -0,0 +1,165 @@
+//! Groth16 BN254 keys and proofs in the layout of Solana's `alt_bn128`
+//! syscalls.
+//!
+//! Solana's `alt_bn128_addition`, `_multiplication` and `_pairing` syscalls
+//! take points in the EIP-196/197 encoding: big-endian coordinates, a G1 point
+//! as `x || y` (64 bytes), a G2 point as `x.c1 || x.c0 || y.c1 || y.c0` (128
+//! bytes) and the point at infinity as all zeros. The types here hold a
+//! verifying key, proof and public inputs in that encoding, field by field as
+//! the `groth16-solana` verifier takes them:
+//!
+//! ```no_run
+//! # fn example(vk_bytes: &[u8], result: &rust_gnark::Groth16ProofResult) -> anyhow::Result<()> {
+//! let vk = rust_gnark::native::VerifyingKey::from_gnark_bytes(vk_bytes)?;
+//! let vk = rust_gnark::solana::SolanaVerifyingKey::try_from(&vk)?;
+//! let (proof, public_inputs) = rust_gnark::solana::from_proof_result(result)?;
+//! // Instruction data for the program: the proof, then the inputs.
+//! let mut data = proof.to_bytes().to_vec();
+//! data.extend(public_inputs.iter().flatten());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Like `groth16-solana`, [`SolanaProof::a`] holds `-A`, so the program checks
+//! `e(-A, B) · e(α, β) · e(L, γ) · e(C, δ) = 1` with one pairing syscall.
+//! Proofs with Pedersen commitments cannot be checked this way and are
+//! rejected.
+
+use anyhow::{ensure, Result};
+use ark_bn254::{Fq, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+
+use crate::native::{decode_hex, Proof, PublicInputs, VerifyingKey};
+use crate::Groth16ProofResult;
+
+/// A G1 point: `x || y`, big-endian.
+pub type G1Bytes = [u8; 64];
+
+/// A G2 point: `x.c1 || x.c0 || y.c1 || y.c0`, big-endian.
+pub type G2Bytes = [u8; 128];
+
+/// A verifying key as `groth16-solana`'s `Groth16Verifyingkey` holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaVerifyingKey {
+    /// `[α]1`
+    pub alpha_g1: G1Bytes,
+    /// `[β]2`
+    pub beta_g2: G2Bytes,
+    /// `[γ]2`
+    pub gamma_g2: G2Bytes,
+    /// `[δ]2`
+    pub delta_g2: G2Bytes,
+    /// `[Kvk]1`: the constant wire's point, then one per public input.
+    pub ic: Vec<G1Bytes>,
+}
+
+/// A proof as `groth16-solana`'s `Groth16Verifier` takes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolanaProof {
+    /// `-[A]1`, negated so the pairing check needs no negation on-chain.
+    pub a: G1Bytes,
+    /// `[B]2`
+    pub b: G2Bytes,
+    /// `[C]1`
+    pub c: G1Bytes,
+}
+
+impl SolanaProof {
+    /// `a || b || c`, 256 bytes.
+    pub fn to_bytes(&self) -> [u8; 256] {
+        let mut out = [0u8; 256];
+        out[..64].copy_from_slice(&self.a);
+        out[64..192].copy_from_slice(&self.b);
+        out[192..].copy_from_slice(&self.c);
+        out
+    }
+}
+
+impl TryFrom<&VerifyingKey> for SolanaVerifyingKey {
+    type Error = anyhow::Error;
+
+    /// # Errors
+    ///
+    /// Returns an error if the key has Pedersen commitments.
+    fn try_from(vk: &VerifyingKey) -> Result<Self> {
+        ensure!(
+            vk.commitment_keys.is_empty(),
+            "the verifying key has Pedersen commitments, which the alt_bn128 pairing check cannot verify"
+        );
+        Ok(Self {
+            alpha_g1: g1_to_bytes(&vk.alpha_g1),
+            beta_g2: g2_to_bytes(&vk.beta_g2),
+            gamma_g2: g2_to_bytes(&vk.gamma_g2),
+            delta_g2: g2_to_bytes(&vk.delta_g2),
+            ic: vk.k.iter().map(g1_to_bytes).collect(),
+        })
+    }
+}
+
+impl TryFrom<&Proof> for SolanaProof {
+    type Error = anyhow::Error;
+
+    /// # Errors
+    ///
+    /// Returns an error if the proof carries Pedersen commitments.
+    fn try_from(proof: &Proof) -> Result<Self> {
+        ensure!(
+            proof.commitments.is_empty(),
+            "the proof carries Pedersen commitments, which the alt_bn128 pairing check cannot verify"
+        );
+        Ok(Self {
+            a: g1_to_bytes(&-proof.ar),
+            b: g2_to_bytes(&proof.bs),
+            c: g1_to_bytes(&proof.krs),
+        })
+    }
+}
+
+/// Public inputs as 32-byte big-endian integers.
+pub fn public_inputs(inputs: &[Fr]) -> Vec<[u8; 32]> {
+    inputs.iter().map(fr_to_bytes).collect()
+}
+
+/// Decode the hex-encoded prover output into a [`SolanaProof`] and its
+/// public inputs.
+///
+/// # Errors
+///
+/// Returns an error if the proof or public inputs fail to decode, or the proof
+/// carries Pedersen commitments.
+pub fn from_proof_result(result: &Groth16ProofResult) -> Result<(SolanaProof, Vec<[u8; 32]>)> {
+    let proof = Proof::from_gnark_bytes(&decode_hex(&result.proof)?)?;
+    let inputs = PublicInputs::from_gnark_bytes(&decode_hex(&result.public_inputs)?)?;
+    Ok((SolanaProof::try_from(&proof)?, public_inputs(&inputs.0)))
+}
+
+fn fq_to_bytes(x: &Fq) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&x.into_bigint().to_bytes_be());
+    out
+}
+
+fn fr_to_bytes(x: &Fr) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&x.into_bigint().to_bytes_be());
+    out
+}
+
+fn g1_to_bytes(p: &G1Affine) -> G1Bytes {
+    let mut out = [0u8; 64];
+    if !p.infinity {
+        out[..32].copy_from_slice(&fq_to_bytes(&p.x));
+        out[32..].copy_from_slice(&fq_to_bytes(&p.y));
+    }
+    out
+}
+
+fn g2_to_bytes(p: &G2Affine) -> G2Bytes {
+    let mut out = [0u8; 128];
+    if !p.infinity {
+        for (i, c) in [p.x.c1, p.x.c0, p.y.c1, p.y.c0].iter().enumerate() {
+            out[32 * i..32 * (i + 1)].copy_from_slice(&fq_to_bytes(c));
+        }
+    }
+    out
+}
@@ -0,0 +1,211 @@
+//! KZG structured reference strings for PLONK and [`crate::Kzg`].
+
+use anyhow::Result;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::buffer::GnarkBuffer;
+use crate::handle::Handle;
+use crate::{bind, GnarkError, SerializationFormat};
+
+/// A BN254 KZG SRS (powers of tau) owned by the Go runtime.
+///
+/// PLONK needs a universal setup rather than a per-circuit one; importing an
+/// existing ceremony avoids running (and having to trust) a new one.
+#[derive(Debug)]
+pub struct Srs {
+    handle: Handle,
+}
+
+impl Srs {
+    /// Import a snarkjs / Perpetual Powers of Tau `.ptau` file.
+    ///
+    /// Reads `2^power + 3` G1 powers, enough for PLONK circuits of up to
+    /// `2^power` constraints, where `power` is the file's own size. Both
+    /// prepared (`pot*_final.ptau`) and raw contribution files work, since
+    /// only the powers of tau are read. Points are checked to be on the curve
+    /// (and, in G2, in the subgroup).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a `.ptau` file,
+    /// is over another curve, or holds invalid points.
+    pub fn from_ptau(path: &str) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_srs_from_ptau(path.as_ptr() as *mut c_char, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Generate an SRS of `size` G1 powers from a random tau.
+    ///
+    /// Tau passes through this process's memory, so anyone who can read it
+    /// could forge proofs: for tests and benchmarks only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is less than 2.
+    pub fn generate_insecure(size: u64) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_srs_generate_insecure(size, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// A copy holding only the first `size` G1 powers, for circuits that need
+    /// that many (see [`crate::CompiledCircuit::srs_size_needed`]).
+    ///
+    /// Commitments under the trimmed SRS equal those under the full one; only
+    /// the maximum polynomial degree shrinks, and with it the file size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is less than 2 or more than [`Srs::size`].
+    pub fn trim(&self, size: u64) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe { bind::gnark_srs_trim(self.handle.id(), size, &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Read and validate an SRS written by [`Srs::write`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a BN254 SRS.
+    pub fn read(path: &str) -> Result<Self> {
+        Self::read_with_format(path, SerializationFormat::Compressed)
+    }
+
+    /// Like [`Srs::read`], but reading the file in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a BN254 SRS.
+    pub fn read_with_format(path: &str, format: SerializationFormat) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut id = 0u64;
+        let err_ptr =
+            unsafe { bind::gnark_srs_read(path.as_ptr() as *mut c_char, format.as_raw(), &mut id) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Deserialize and validate an SRS from memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a BN254 SRS.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_format(bytes, SerializationFormat::Compressed)
+    }
+
+    /// Like [`Srs::from_bytes`], but reading `bytes` in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a BN254 SRS.
+    pub fn from_bytes_with_format(bytes: &[u8], format: SerializationFormat) -> Result<Self> {
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_srs_from_bytes(
+                bytes.as_ptr() as *mut u8,
+                bytes.len() as u64,
+                format.as_raw(),
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Number of G1 powers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Go side no longer knows this SRS.
+    pub fn size(&self) -> Result<u64> {
+        let mut n = 0u64;
+        let err_ptr = unsafe { bind::gnark_srs_size(self.handle.id(), &mut n) };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(n)
+    }
+
+    /// Write the SRS to `path` in gnark's `kzg.SRS` format with compressed
+    /// points.
+    ///
+    /// The compressed encoding is canonical: an SRS always writes the same
+    /// bytes, however it was obtained, so files can be compared or hashed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: &str) -> Result<()> {
+        self.write_with_format(path, SerializationFormat::Compressed)
+    }
+
+    /// Like [`Srs::write`], but in `format`. Raw points make a file twice
+    /// the size that loads faster.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write_with_format(&self, path: &str, format: SerializationFormat) -> Result<()> {
+        let path = CString::new(path)?;
+        let err_ptr = unsafe {
+            bind::gnark_srs_write(
+                self.handle.id(),
+                path.as_ptr() as *mut c_char,
+                format.as_raw(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Serialize the SRS in `format`, as [`Srs::write_with_format`] would.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self, format: SerializationFormat) -> Result<GnarkBuffer> {
+        let mut data = std::ptr::null_mut();
+        let mut len = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_srs_to_bytes(self.handle.id(), format.as_raw(), &mut data, &mut len)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { GnarkBuffer::from_raw(data, len) })
+    }
+
+    pub(crate) fn handle_id(&self) -> u64 {
+        self.handle.id()
+    }
+}
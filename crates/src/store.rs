@@ -0,0 +1,467 @@
+//! Circuit artifacts (R1CS, keys, SRS) kept on disk.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::{bind, GnarkError, KeyFingerprint, VerifyingKey};
+#[cfg(not(feature = "verifier"))]
+use crate::{ProvingKey, Srs};
+
+/// Version of the directory layout, the first path component under the root.
+const LAYOUT_VERSION: u32 = 1;
+
+/// Suffix of the file holding an artifact's checksum, next to the artifact.
+const CHECKSUM_SUFFIX: &str = ".sha256";
+
+/// A file kept in an [`ArtifactStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    /// The `.r1cs` constraint system.
+    ConstraintSystem,
+    /// The Groth16 proving key, raw (as [`ProvingKey::open_mmap`] reads it).
+    ProvingKey,
+    /// The Groth16 verifying key.
+    VerifyingKey,
+    /// A KZG SRS, as written by [`Srs::write`].
+    Srs,
+}
+
+impl ArtifactKind {
+    /// The artifact's file name inside its version directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::ConstraintSystem => "circuit.r1cs",
+            Self::ProvingKey => "circuit.pk",
+            Self::VerifyingKey => "circuit.vk",
+            Self::Srs => "kzg.srs",
+        }
+    }
+}
+
+impl fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.file_name())
+    }
+}
+
+/// Circuit artifacts on disk, by circuit name and version.
+///
+/// Files live at `<root>/v1/<circuit>/<version>/<file>` (see
+/// [`ArtifactKind::file_name`]), each with a `<file>.sha256` checksum written
+/// when it is inserted (the file's SHA-256, as a [`KeyFingerprint`]). Keys and
+/// SRSs are loaded on first use, after checking the file against its
+/// checksum, and shared by later calls.
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use rust_gnark::{ArtifactKind, ArtifactStore};
+///
+/// let store = ArtifactStore::open_default("com.example.app")?;
+/// if !store.contains("age-check", "1.2.0", ArtifactKind::ProvingKey) {
+///     store.insert("age-check", "1.2.0", ArtifactKind::ProvingKey, "download/circuit.pk".as_ref())?;
+/// }
+/// let pk = store.proving_key("age-check", "1.2.0")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArtifactStore {
+    root: PathBuf,
+    loaded: Mutex<HashMap<PathBuf, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl ArtifactStore {
+    /// A store rooted at `root`, created on first insert.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            loaded: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A store at [`ArtifactStore::default_root`]`(app_id)`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ArtifactStore::default_root`].
+    pub fn open_default(app_id: &str) -> Result<Self> {
+        Ok(Self::new(Self::default_root(app_id)?))
+    }
+
+    /// The platform's directory for persistent app data, under `app_id`:
+    ///
+    /// - iOS and macOS: `~/Library/Application Support/<app_id>/rust-gnark`
+    ///   (inside the app container on iOS)
+    /// - Android: `/data/data/<app_id>/files/rust-gnark`, the app's files
+    ///   directory when `app_id` is its package name
+    /// - Windows: `%APPDATA%\<app_id>\rust-gnark`
+    /// - other Unix: `$XDG_DATA_HOME/<app_id>/rust-gnark`, defaulting to
+    ///   `~/.local/share`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `app_id` is not a single path component or the
+    /// platform's base directory is unknown (e.g. `HOME` is unset).
+    pub fn default_root(app_id: &str) -> Result<PathBuf> {
+        check_component("app id", app_id)?;
+        Ok(platform_data_dir(app_id)?.join("rust-gnark"))
+    }
+
+    /// The root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the `kind` artifact of `circuit` at `version` is (or would be)
+    /// stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `circuit` or `version` is empty, `.`/`..`, or
+    /// contains a path separator.
+    pub fn path(&self, circuit: &str, version: &str, kind: ArtifactKind) -> Result<PathBuf> {
+        Ok(self.version_dir(circuit, version)?.join(kind.file_name()))
+    }
+
+    /// Whether the artifact and its checksum are present (without verifying
+    /// the checksum).
+    pub fn contains(&self, circuit: &str, version: &str, kind: ArtifactKind) -> bool {
+        self.path(circuit, version, kind)
+            .is_ok_and(|path| path.is_file() && checksum_path(&path).is_file())
+    }
+
+    /// Copy the file at `src` into the store, replacing any previous version,
+    /// and return its checksum.
+    ///
+    /// The file is written under a temporary name and renamed into place, so
+    /// readers never see a partial artifact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the names are invalid (see [`ArtifactStore::path`])
+    /// or a file cannot be read or written.
+    pub fn insert(
+        &self,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+        src: &Path,
+    ) -> Result<KeyFingerprint> {
+        self.insert_with(circuit, version, kind, None, |tmp| {
+            std::fs::copy(src, tmp)
+                .map(drop)
+                .with_context(|| format!("failed to copy {}", src.display()))
+        })
+    }
+
+    /// Like [`ArtifactStore::insert`], from bytes in memory.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ArtifactStore::insert`].
+    pub fn insert_bytes(
+        &self,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+        bytes: &[u8],
+    ) -> Result<KeyFingerprint> {
+        self.insert_with(circuit, version, kind, None, |tmp| {
+            std::fs::write(tmp, bytes).with_context(|| format!("failed to write {}", tmp.display()))
+        })
+    }
+
+    /// Write an artifact with `write` to a temporary path, then checksum it
+    /// and move both files into place. A file that does not match `expected`
+    /// is deleted instead. The temporary file is kept if `write` fails.
+    pub(crate) fn insert_with(
+        &self,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+        expected: Option<&KeyFingerprint>,
+        write: impl FnOnce(&Path) -> Result<()>,
+    ) -> Result<KeyFingerprint> {
+        let path = self.path(circuit, version, kind)?;
+        let dir = path.parent().expect("artifact paths have a parent");
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        let tmp = partial_path(&path);
+        write(&tmp)?;
+        let checksum = file_sha256(&tmp)?;
+        if let Some(expected) = expected.filter(|&expected| *expected != checksum) {
+            remove_if_exists(&tmp)?;
+            bail!("{kind} does not match its checksum (expected {expected}, got {checksum})");
+        }
+
+        self.evict(&path);
+        // Drop the old checksum first: an artifact without one is absent.
+        remove_if_exists(&checksum_path(&path))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("failed to move {} into place", path.display()))?;
+        std::fs::write(checksum_path(&path), format!("{checksum}\n"))?;
+        Ok(checksum)
+    }
+
+    /// The checksum recorded when the artifact was inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is not in the store.
+    pub fn checksum(
+        &self,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+    ) -> Result<KeyFingerprint> {
+        let path = self.path(circuit, version, kind)?;
+        read_checksum(&path)
+    }
+
+    /// Check the artifact's file against its recorded checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is missing or its contents changed
+    /// since it was inserted (e.g. a truncated or corrupted file).
+    pub fn verify(&self, circuit: &str, version: &str, kind: ArtifactKind) -> Result<()> {
+        verify_file(&self.path(circuit, version, kind)?)
+    }
+
+    /// The proving key of `circuit` at `version`, memory-mapped on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing, fails [`ArtifactStore::verify`]
+    /// or does not load.
+    #[cfg(not(feature = "verifier"))]
+    pub fn proving_key(&self, circuit: &str, version: &str) -> Result<Arc<ProvingKey>> {
+        self.load(
+            circuit,
+            version,
+            ArtifactKind::ProvingKey,
+            ProvingKey::open_mmap,
+        )
+    }
+
+    /// The verifying key of `circuit` at `version`, read on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing, fails [`ArtifactStore::verify`]
+    /// or does not load.
+    pub fn verifying_key(&self, circuit: &str, version: &str) -> Result<Arc<VerifyingKey>> {
+        self.load(
+            circuit,
+            version,
+            ArtifactKind::VerifyingKey,
+            VerifyingKey::open,
+        )
+    }
+
+    /// The SRS of `circuit` at `version`, read on first use.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ArtifactStore::proving_key`].
+    #[cfg(not(feature = "verifier"))]
+    pub fn srs(&self, circuit: &str, version: &str) -> Result<Arc<Srs>> {
+        self.load(circuit, version, ArtifactKind::Srs, Srs::read)
+    }
+
+    /// The path of the verified constraint system of `circuit` at `version`,
+    /// as [`crate::groth16_prove`] and [`crate::ProverSession::new`] take it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is missing or fails
+    /// [`ArtifactStore::verify`].
+    pub fn constraint_system_path(&self, circuit: &str, version: &str) -> Result<String> {
+        let path = self.path(circuit, version, ArtifactKind::ConstraintSystem)?;
+        verify_file(&path)?;
+        path_str(&path).map(str::to_owned)
+    }
+
+    /// Delete every artifact of `circuit` at `version`, and forget loaded
+    /// ones. Values already returned stay usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the names are invalid or the directory cannot be
+    /// removed.
+    pub fn remove(&self, circuit: &str, version: &str) -> Result<()> {
+        let dir = self.version_dir(circuit, version)?;
+        self.loaded
+            .lock()
+            .expect("artifact cache poisoned")
+            .retain(|path, _| !path.starts_with(&dir));
+        match std::fs::remove_dir_all(&dir) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("failed to remove {}", dir.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn version_dir(&self, circuit: &str, version: &str) -> Result<PathBuf> {
+        check_component("circuit name", circuit)?;
+        check_component("circuit version", version)?;
+        Ok(self
+            .root
+            .join(format!("v{LAYOUT_VERSION}"))
+            .join(circuit)
+            .join(version))
+    }
+
+    fn load<T: Send + Sync + 'static>(
+        &self,
+        circuit: &str,
+        version: &str,
+        kind: ArtifactKind,
+        open: fn(&str) -> Result<T>,
+    ) -> Result<Arc<T>> {
+        let path = self.path(circuit, version, kind)?;
+        // Held while loading, so concurrent first uses load once.
+        let mut loaded = self.loaded.lock().expect("artifact cache poisoned");
+        if let Some(value) = loaded.get(&path) {
+            return Ok(Arc::clone(value)
+                .downcast()
+                .expect("one type per artifact kind"));
+        }
+        verify_file(&path)?;
+        let value = Arc::new(open(path_str(&path)?)?);
+        loaded.insert(path, value.clone());
+        Ok(value)
+    }
+
+    fn evict(&self, path: &Path) {
+        self.loaded
+            .lock()
+            .expect("artifact cache poisoned")
+            .remove(path);
+    }
+}
+
+/// Reject names that would escape their directory.
+fn check_component(what: &str, name: &str) -> Result<()> {
+    ensure!(
+        !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']),
+        "{what} {name:?} must be a single path component"
+    );
+    Ok(())
+}
+
+#[cfg(target_vendor = "apple")]
+fn platform_data_dir(app_id: &str) -> Result<PathBuf> {
+    Ok(home()?
+        .join("Library")
+        .join("Application Support")
+        .join(app_id))
+}
+
+#[cfg(target_os = "android")]
+fn platform_data_dir(app_id: &str) -> Result<PathBuf> {
+    Ok(Path::new("/data/data").join(app_id).join("files"))
+}
+
+#[cfg(windows)]
+fn platform_data_dir(app_id: &str) -> Result<PathBuf> {
+    let appdata = std::env::var_os("APPDATA").ok_or_else(|| anyhow!("APPDATA is not set"))?;
+    Ok(PathBuf::from(appdata).join(app_id))
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "android", windows)))]
+fn platform_data_dir(app_id: &str) -> Result<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home()?.join(".local").join("share"),
+    };
+    Ok(base.join(app_id))
+}
+
+#[cfg(not(any(target_os = "android", windows)))]
+fn home() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("HOME is not set"))
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(CHECKSUM_SUFFIX);
+    name.into()
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".partial");
+    name.into()
+}
+
+/// Write `contents` to `path` under a temporary name and rename it into
+/// place, so an interrupted write never leaves a truncated file.
+#[cfg(not(feature = "verifier"))]
+pub(crate) fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let tmp = partial_path(path);
+    std::fs::write(&tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("failed to move {} into place", path.display()))
+}
+
+fn path_str(path: &Path) -> Result<&str> {
+    path.to_str()
+        .ok_or_else(|| anyhow!("{} is not valid UTF-8", path.display()))
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_checksum(path: &Path) -> Result<KeyFingerprint> {
+    let recorded = std::fs::read_to_string(checksum_path(path))
+        .with_context(|| format!("{} is not in the artifact store", path.display()))?;
+    recorded
+        .trim()
+        .parse()
+        .with_context(|| format!("corrupt checksum file for {}", path.display()))
+}
+
+fn verify_file(path: &Path) -> Result<()> {
+    let recorded = read_checksum(path)?;
+    let actual = file_sha256(path)?;
+    if actual != recorded {
+        bail!(
+            "{} does not match its checksum (expected {recorded}, got {actual})",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// SHA-256 of the file at `path`.
+pub(crate) fn file_sha256(path: &Path) -> Result<KeyFingerprint> {
+    let path = CString::new(path_str(path)?)?;
+    let mut out = [0u8; 32];
+    let err_ptr =
+        unsafe { bind::gnark_file_sha256(path.as_ptr() as *mut c_char, out.as_mut_ptr()) };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Ok(KeyFingerprint(out))
+}
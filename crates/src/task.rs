@@ -0,0 +1,292 @@
+//! Non-blocking proving.
+//!
+//! A cgo call pins the calling OS thread until Go returns, so proving directly
+//! from an async task starves the executor for the duration of the proof. The
+//! types here move the call onto a small pool of prover threads and hand back
+//! a [`ProveTask`] that can be `.await`ed from any executor, waited on
+//! synchronously, or cancelled.
+
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    prove_with_options, CancelToken, Groth16ProofResult, PkRef, ProgressReporter, ProveOptions,
+};
+
+/// State shared between a [`ProveTask`] and its worker thread.
+#[derive(Default)]
+struct Shared {
+    inner: Mutex<Inner>,
+    done: Condvar,
+}
+
+#[derive(Default)]
+struct Inner {
+    result: Option<Result<Groth16ProofResult>>,
+    waker: Option<Waker>,
+    then: Option<Then>,
+}
+
+/// Takes the result of a task handed to [`ProveTask::then`].
+type Then = Box<dyn FnOnce(Result<Groth16ProofResult>) + Send>;
+
+impl Shared {
+    fn complete(&self, result: Result<Groth16ProofResult>) {
+        let mut inner = self.inner.lock().expect("prove task state poisoned");
+        if let Some(then) = inner.then.take() {
+            drop(inner);
+            then(result);
+            return;
+        }
+        inner.result = Some(result);
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+        self.done.notify_all();
+    }
+}
+
+/// Threads proving for [`ProveTask::spawn`]. gnark already spreads a proof over
+/// every core, so more concurrent proofs would mostly add memory.
+const PROVER_THREADS: usize = 2;
+
+/// Handle to a proof running on a background thread.
+///
+/// Implements [`Future`], so it can be `.await`ed from any async runtime without
+/// blocking it. Outside of async code, use [`ProveTask::wait`].
+///
+/// Dropping the task does not stop the proof; call [`ProveTask::cancel`] first.
+#[must_use = "a ProveTask does nothing useful unless awaited or waited on"]
+pub struct ProveTask {
+    shared: Arc<Shared>,
+    token: Arc<CancelToken>,
+}
+
+/// The producing side of a [`ProveTask`], for proofs run by someone other than
+/// [`ProveTask::spawn`] (e.g. a [`crate::ProvePool`] worker).
+///
+/// A slot dropped without [`TaskSlot::complete`], e.g. while its proof
+/// unwinds, resolves the task to an error, so the task never waits forever.
+pub(crate) struct TaskSlot {
+    shared: Arc<Shared>,
+    token: Arc<CancelToken>,
+    completed: bool,
+}
+
+impl TaskSlot {
+    /// The token cancelling the task's proof.
+    pub(crate) fn token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    /// Resolve the task to `result`.
+    pub(crate) fn complete(mut self, result: Result<Groth16ProofResult>) {
+        self.completed = true;
+        self.shared.complete(result);
+    }
+}
+
+impl Drop for TaskSlot {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.shared
+                .complete(Err(anyhow!("the proof was abandoned before it finished")));
+        }
+    }
+}
+
+impl ProveTask {
+    /// A task that resolves once its [`TaskSlot`] is completed.
+    pub(crate) fn detached() -> (Self, TaskSlot) {
+        let shared = Arc::new(Shared::default());
+        let token = Arc::new(CancelToken::new());
+        let slot = TaskSlot {
+            shared: Arc::clone(&shared),
+            token: Arc::clone(&token),
+            completed: false,
+        };
+        (Self { shared, token }, slot)
+    }
+
+    /// Run `prove` on the crate's prover threads and return a handle to its
+    /// result, e.g. `ProveTask::spawn(move |token| session.prove_cancellable(&json, token))`.
+    ///
+    /// `prove` gets the token [`ProveTask::cancel`] cancels. At most two
+    /// proofs run at once; later ones wait for a thread. If `prove` panics,
+    /// the task resolves to an error.
+    pub fn spawn<F>(prove: F) -> Self
+    where
+        F: FnOnce(&CancelToken) -> Result<Groth16ProofResult> + Send + 'static,
+    {
+        let (task, slot) = Self::detached();
+        let job: Job = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| prove(slot.token())))
+                .unwrap_or_else(|_| Err(anyhow!("the proof panicked")));
+            slot.complete(result);
+        });
+        // A job the pool refuses is dropped with its slot, failing the task.
+        let _ = provers()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .send(job);
+        task
+    }
+
+    /// Ask the Go prover to abandon this proof.
+    ///
+    /// The task then resolves to a cancellation error once the prover has
+    /// stopped: right away while it reads the key or solves the witness's
+    /// hints, and otherwise when gnark's MSMs and FFTs, which cannot be
+    /// interrupted, finish. Has no effect once the proof is done.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Pass the result to `then` once the proof is done, on the thread that
+    /// finishes it (or this one, if it already has), instead of waiting for it.
+    pub(crate) fn then(self, then: impl FnOnce(Result<Groth16ProofResult>) + Send + 'static) {
+        let mut inner = self.shared.inner.lock().expect("prove task state poisoned");
+        match inner.result.take() {
+            Some(result) => {
+                drop(inner);
+                then(result);
+            }
+            None => inner.then = Some(Box::new(then)),
+        }
+    }
+
+    /// Returns `true` once the proof has finished (successfully or not).
+    pub fn is_finished(&self) -> bool {
+        self.shared
+            .inner
+            .lock()
+            .expect("prove task state poisoned")
+            .result
+            .is_some()
+    }
+
+    /// Block the current thread until the proof is done.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`crate::groth16_prove`], or a cancellation
+    /// error if [`ProveTask::cancel`] was called.
+    pub fn wait(self) -> Result<Groth16ProofResult> {
+        let mut inner = self.shared.inner.lock().expect("prove task state poisoned");
+        loop {
+            if let Some(result) = inner.result.take() {
+                return result;
+            }
+            inner = self
+                .shared
+                .done
+                .wait(inner)
+                .expect("prove task state poisoned");
+        }
+    }
+}
+
+impl Future for ProveTask {
+    type Output = Result<Groth16ProofResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.shared.inner.lock().expect("prove task state poisoned");
+        match inner.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                inner.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The queue of the [`PROVER_THREADS`] threads, started on first use and kept
+/// for the life of the process.
+fn provers() -> &'static Mutex<Sender<Job>> {
+    static PROVERS: OnceLock<Mutex<Sender<Job>>> = OnceLock::new();
+    PROVERS.get_or_init(|| {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        for i in 0..PROVER_THREADS {
+            let queue = Arc::clone(&queue);
+            // If no thread starts, the queue has no receiver and jobs fail.
+            let _ = thread::Builder::new()
+                .name(format!("rust-gnark-prove-{i}"))
+                .spawn(move || work(&queue));
+        }
+        Mutex::new(jobs)
+    })
+}
+
+/// Run jobs from `queue`; jobs catch their own panics, so a thread lives as
+/// long as the process.
+fn work(queue: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match queue.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        job();
+    }
+}
+
+/// Generate a Groth16 BN254 proof without blocking the calling thread.
+///
+/// Takes the same arguments as [`crate::groth16_prove`]; the proof runs on a
+/// prover thread (see [`ProveTask::spawn`]) and the returned [`ProveTask`]
+/// resolves to its result.
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// let result = rust_gnark::prove_async("circuit.r1cs", "circuit.pk", r#"{"X": "3", "Y": "35"}"#).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn prove_async(r1cs_path: &str, pk_path: &str, witness_json: &str) -> ProveTask {
+    let r1cs_path = r1cs_path.to_owned();
+    let pk_path = pk_path.to_owned();
+    let witness_json = witness_json.to_owned();
+    ProveTask::spawn(move |token| {
+        let options = ProveOptions {
+            cancel: Some(token),
+            ..Default::default()
+        };
+        prove_with_options(&r1cs_path, PkRef::Path(&pk_path), &witness_json, &options)
+    })
+}
+
+/// [`prove_async`] with progress reporting.
+///
+/// `reporter` is called from the background proving thread; see
+/// [`crate::groth16_prove_with_progress`].
+pub fn prove_async_with_progress<R>(
+    r1cs_path: &str,
+    pk_path: &str,
+    witness_json: &str,
+    reporter: R,
+) -> ProveTask
+where
+    R: ProgressReporter + 'static,
+{
+    let r1cs_path = r1cs_path.to_owned();
+    let pk_path = pk_path.to_owned();
+    let witness_json = witness_json.to_owned();
+    ProveTask::spawn(move |token| {
+        let options = ProveOptions {
+            cancel: Some(token),
+            progress: Some(&reporter),
+            ..Default::default()
+        };
+        prove_with_options(&r1cs_path, PkRef::Path(&pk_path), &witness_json, &options)
+    })
+}
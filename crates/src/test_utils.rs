@@ -0,0 +1,84 @@
+//! Unit testing circuits with gnark's test engine.
+//!
+//! The engine runs a [`CircuitBuilder`] circuit directly on a witness's
+//! values and stops at the first assertion that fails. Nothing is compiled,
+//! set up or proven, so a test can check many assignments in milliseconds:
+//! valid ones for completeness, invalid ones for soundness.
+//!
+//! ```no_run
+//! # fn example() -> anyhow::Result<()> {
+//! use rust_gnark::{test_utils::assert_circuit, CircuitBuilder, Witness};
+//!
+//! let mut c = CircuitBuilder::new();
+//! let x = c.secret_input("X");
+//! let y = c.public_input("Y");
+//! let xx = c.mul(x, x);
+//! c.assert_eq(xx, y);
+//!
+//! let abi = c.abi();
+//! assert_circuit(
+//!     &c,
+//!     &[Witness::from_assignments(&abi, [("X", "3"), ("Y", "9")])?],
+//!     &[Witness::from_assignments(&abi, [("X", "3"), ("Y", "10")])?],
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Result;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::{bind, take_go_string, CircuitBuilder, GnarkError, Witness};
+
+/// Run `circuit` on `witness` with gnark's test engine.
+///
+/// Returns `None` if every assertion holds, or the failing one otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the circuit description is invalid or `witness` lacks
+/// one of its inputs.
+pub fn check_assignment(circuit: &CircuitBuilder, witness: &Witness) -> Result<Option<String>> {
+    let desc = CString::new(circuit.to_description()?)?;
+    let witness = CString::new(witness.to_json())?;
+    let mut failure = std::ptr::null_mut();
+    let err_ptr = unsafe {
+        bind::gnark_circuit_test_engine(
+            desc.as_ptr() as *mut c_char,
+            witness.as_ptr() as *mut c_char,
+            &mut failure,
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    if failure.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(unsafe { take_go_string(failure) }))
+}
+
+/// Assert that `circuit` accepts every witness in `valid` and rejects every
+/// one in `invalid`.
+///
+/// # Panics
+///
+/// Panics, naming the witness, if one is accepted or rejected wrongly, or if
+/// the engine cannot run (see [`check_assignment`]).
+pub fn assert_circuit(circuit: &CircuitBuilder, valid: &[Witness], invalid: &[Witness]) {
+    for (i, witness) in valid.iter().enumerate() {
+        match check_assignment(circuit, witness) {
+            Ok(None) => {}
+            Ok(Some(failure)) => panic!("valid witness {i} was rejected: {failure}"),
+            Err(e) => panic!("valid witness {i}: {e:#}"),
+        }
+    }
+    for (i, witness) in invalid.iter().enumerate() {
+        match check_assignment(circuit, witness) {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("invalid witness {i} was accepted"),
+            Err(e) => panic!("invalid witness {i}: {e:#}"),
+        }
+    }
+}
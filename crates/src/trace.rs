@@ -0,0 +1,76 @@
+//! `tracing` integration: spans around setup, proving and verification, and
+//! forwarding of gnark's Go-side log output as `tracing` events.
+
+/// Enter an `info` span named `$name`, with optional span fields, until the
+/// returned guard is dropped. Without the `tracing` feature the guard is a
+/// [`NoSpan`] and the fields are not evaluated.
+#[cfg(feature = "tracing")]
+macro_rules! enter_span {
+    ($name:literal $(, $($field:tt)*)?) => {
+        tracing::info_span!($name $(, $($field)*)?).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! enter_span {
+    ($name:literal $(, $($field:tt)*)?) => {
+        $crate::trace::NoSpan
+    };
+}
+
+/// The [`enter_span!`] guard without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// Run `f`, recording its duration and outcome in the current span.
+#[cfg(feature = "tracing")]
+pub(crate) fn timed<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    let start = std::time::Instant::now();
+    let result = f();
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1e3;
+    match &result {
+        Ok(_) => tracing::info!(elapsed_ms, "done"),
+        Err(err) => tracing::info!(elapsed_ms, error = %format_args!("{err:#}"), "failed"),
+    }
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn timed<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    f()
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) use imp::*;
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use std::ffi::CStr;
+    use std::os::raw::{c_char, c_int};
+    use std::panic::catch_unwind;
+
+    use crate::bind;
+
+    /// Route gnark's logger to [`forward_log`]. Every level is forwarded; the
+    /// installed subscriber decides what is kept.
+    pub(crate) fn forward_go_logs() {
+        unsafe { bind::gnark_set_log_callback(Some(forward_log), 0) };
+    }
+
+    /// C callback for `gnark_log_cb`, emitting the event with target `gnark`.
+    unsafe extern "C" fn forward_log(level: c_int, message: *const c_char, fields: *const c_char) {
+        // Unwinding into Go would abort the process.
+        let _ = catch_unwind(|| {
+            let message = CStr::from_ptr(message).to_string_lossy();
+            let fields = CStr::from_ptr(fields).to_string_lossy();
+            let fields = (!fields.is_empty()).then_some(&*fields);
+            match level {
+                0 => tracing::trace!(target: "gnark", fields, "{message}"),
+                1 => tracing::debug!(target: "gnark", fields, "{message}"),
+                2 => tracing::info!(target: "gnark", fields, "{message}"),
+                3 => tracing::warn!(target: "gnark", fields, "{message}"),
+                _ => tracing::error!(target: "gnark", fields, "{message}"),
+            }
+        });
+    }
+}
@@ -0,0 +1,151 @@
+//! Fiat-Shamir transcripts matching gnark's.
+
+use anyhow::Result;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+use crate::handle::Handle;
+use crate::{bind, Fr, GnarkError};
+
+/// Hash a [`Transcript`] derives its challenges with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TranscriptHash {
+    /// SHA-256, which gnark's PLONK prover and verifier use by default.
+    #[default]
+    Sha256,
+    /// Legacy Keccak-256 (Ethereum's `keccak256`).
+    Keccak256,
+    /// gnark-crypto's Merkle-Damgård Poseidon2 over BN254, cheap to recompute
+    /// in a circuit (inputs shorter than 32 bytes are left-padded).
+    Poseidon2,
+}
+
+impl TranscriptHash {
+    /// The id the Go side expects.
+    pub(crate) fn as_raw(self) -> c_int {
+        match self {
+            Self::Sha256 => 0,
+            Self::Keccak256 => 1,
+            Self::Poseidon2 => 2,
+        }
+    }
+}
+
+/// A Fiat-Shamir transcript, gnark-crypto's `fiat-shamir.Transcript`.
+///
+/// Challenges are declared up front and computed in that order. Each one
+/// hashes its label, the previous challenge and the data
+/// [`append`](Transcript::append)ed to it, so a transcript fed the same
+/// values as gnark's prover gives the same challenges, e.g. to check a gnark
+/// PLONK proof with a verifier written in Rust:
+///
+/// ```no_run
+/// # fn example(public_inputs: &[u8], commitments: &[u8]) -> anyhow::Result<()> {
+/// use rust_gnark::{Transcript, TranscriptHash};
+///
+/// let mut fs = Transcript::new(TranscriptHash::Sha256, &Transcript::PLONK_CHALLENGES)?;
+/// fs.append("gamma", public_inputs)?;
+/// fs.append("gamma", commitments)?;
+/// let gamma = fs.challenge_scalar("gamma")?;
+/// let beta = fs.challenge_scalar("beta")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Transcript {
+    handle: Handle,
+}
+
+impl Transcript {
+    /// The challenges of gnark's PLONK, in order.
+    pub const PLONK_CHALLENGES: [&'static str; 4] = ["gamma", "beta", "alpha", "zeta"];
+
+    /// A transcript over `hash` for the challenges `labels`, in the order
+    /// they will be computed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a label contains a NUL byte.
+    pub fn new(hash: TranscriptHash, labels: &[&str]) -> Result<Self> {
+        let labels = labels
+            .iter()
+            .map(|label| CString::new(*label))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut ptrs: Vec<*mut c_char> = labels.iter().map(|s| s.as_ptr() as *mut _).collect();
+        let mut id = 0u64;
+        let err_ptr = unsafe {
+            bind::gnark_transcript_new(
+                hash.as_raw(),
+                ptrs.as_mut_ptr(),
+                u32::try_from(ptrs.len())?,
+                &mut id,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(Self {
+            handle: Handle::from_raw(id),
+        })
+    }
+
+    /// Append `data` to the inputs of the challenge `label`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `label` was not declared or its challenge has
+    /// already been computed.
+    pub fn append(&mut self, label: &str, data: &[u8]) -> Result<()> {
+        let label = CString::new(label)?;
+        let err_ptr = unsafe {
+            bind::gnark_transcript_bind(
+                self.handle.id(),
+                label.as_ptr() as *mut c_char,
+                data.as_ptr() as *mut u8,
+                data.len() as u64,
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(())
+    }
+
+    /// Compute the challenge `label` as the hash's 32-byte digest.
+    ///
+    /// Computing a challenge again returns the same digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `label` was not declared or the challenge before it
+    /// has not been computed yet.
+    pub fn challenge(&mut self, label: &str) -> Result<[u8; 32]> {
+        let label = CString::new(label)?;
+        let mut out = [0u8; 32];
+        let err_ptr = unsafe {
+            bind::gnark_transcript_challenge(
+                self.handle.id(),
+                label.as_ptr() as *mut c_char,
+                out.as_mut_ptr(),
+            )
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(out)
+    }
+
+    /// [`Transcript::challenge`] as a BN254 scalar: the digest, big-endian,
+    /// reduced modulo the field, as gnark's verifiers read challenges.
+    ///
+    /// # Errors
+    ///
+    /// As [`Transcript::challenge`].
+    pub fn challenge_scalar(&mut self, label: &str) -> Result<Fr> {
+        let digest = self.challenge(label)?;
+        let radix = Fr::from(u64::MAX) + Fr::from(1);
+        Ok(digest.chunks_exact(8).fold(Fr::from(0), |acc, limb| {
+            acc * radix + Fr::from(u64::from_be_bytes(limb.try_into().expect("8-byte limb")))
+        }))
+    }
+}
@@ -0,0 +1,99 @@
+//! What the linked Go library was built from.
+
+use crate::{bind, take_go_string};
+
+/// A Go module compiled into the library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoModule {
+    /// Module path, e.g. `github.com/consensys/gnark`.
+    pub path: String,
+    /// Version built, e.g. `v0.14.0` (empty for a local replacement).
+    pub version: String,
+    /// The module's `go.sum` hash (`h1:...`), empty for a local replacement.
+    pub sum: String,
+}
+
+/// Build information of the linked Go library, for recording which gnark
+/// release produced a deployment's proofs.
+///
+/// The module list is what `go version -m` prints for the library: every
+/// dependency with its exact version and checksum. Source builds check
+/// `go/go.sum` against `go/go.sum.sha256` before compiling, so the modules
+/// cannot change without that file changing in review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// The Go release the library was compiled with, e.g. `go1.24.0`.
+    pub go_version: String,
+    /// Go modules compiled in, empty if the build carries no module
+    /// information.
+    pub modules: Vec<GoModule>,
+    /// The rust-gnark git commit the library was built from, if the build was
+    /// stamped with one.
+    pub git_revision: Option<String>,
+    /// Whether the working tree had uncommitted changes at build time.
+    pub git_modified: bool,
+    /// Go build tags, e.g. `icicle` for the `gpu` feature or `no_bw6_761`
+    /// when a curve feature is off.
+    pub build_tags: Vec<String>,
+}
+
+impl VersionInfo {
+    /// The compiled-in module at `path`.
+    pub fn module(&self, path: &str) -> Option<&GoModule> {
+        self.modules.iter().find(|m| m.path == path)
+    }
+
+    /// The gnark version, e.g. `v0.14.0`.
+    pub fn gnark(&self) -> Option<&str> {
+        self.module("github.com/consensys/gnark")
+            .map(|m| m.version.as_str())
+    }
+
+    /// The gnark-crypto version, e.g. `v0.19.0`.
+    pub fn gnark_crypto(&self) -> Option<&str> {
+        self.module("github.com/consensys/gnark-crypto")
+            .map(|m| m.version.as_str())
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut info = Self {
+            go_version: String::new(),
+            modules: Vec::new(),
+            git_revision: None,
+            git_modified: false,
+            build_tags: Vec::new(),
+        };
+        for line in s.lines() {
+            let mut fields = line.split('\t');
+            let mut next = || fields.next().unwrap_or_default().to_owned();
+            match next().as_str() {
+                "go" => info.go_version = next(),
+                "module" => info.modules.push(GoModule {
+                    path: next(),
+                    version: next(),
+                    sum: next(),
+                }),
+                "vcs" => {
+                    info.git_revision = Some(next());
+                    info.git_modified = next() == "true";
+                }
+                "tags" => {
+                    info.build_tags = next()
+                        .split(',')
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_owned)
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+}
+
+/// How the linked Go library was built: the Go release, module versions,
+/// rust-gnark git revision and build tags.
+pub fn version_info() -> VersionInfo {
+    let ptr = unsafe { bind::gnark_version_info() };
+    VersionInfo::parse(&unsafe { take_go_string(ptr) })
+}
@@ -0,0 +1,30 @@
+//! JavaScript bindings for the pure-Rust verifier.
+//!
+//! Enabled by the `wasm` feature. Inputs use the same encodings as the native
+//! prover output: the verifying key as raw gnark bytes (`vk.WriteTo()`), and the
+//! proof and public inputs as the hex strings found in [`crate::Groth16ProofResult`].
+//!
+//! ```js
+//! import init, { groth16_verify } from "rust_gnark";
+//! await init();
+//! const vk = new Uint8Array(await (await fetch("circuit.vk")).arrayBuffer());
+//! const valid = groth16_verify(vk, result.proof, result.public_inputs);
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::{native, Groth16ProofResult};
+
+/// Verify a Groth16 BN254 proof produced by gnark.
+///
+/// Returns `true` if the proof is valid and `false` if the pairing check fails.
+/// Throws if any input cannot be decoded.
+#[wasm_bindgen]
+pub fn groth16_verify(vk: &[u8], proof: &str, public_inputs: &str) -> Result<bool, JsError> {
+    let proof_result = Groth16ProofResult {
+        proof: proof.to_string(),
+        public_inputs: public_inputs.to_string(),
+    };
+    native::groth16_verify_with_vk_bytes(vk, &proof_result)
+        .map_err(|e| JsError::new(&format!("{e:#}")))
+}
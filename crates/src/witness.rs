@@ -0,0 +1,407 @@
+//! Named witness assignments.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::circuit::push_json_string;
+use crate::public_inputs::{encode_hex, public_witness_bytes};
+use crate::{bind, take_go_string, Fr, GnarkError, APP_CONTEXT};
+
+/// Name of the public input of a circuit built with
+/// [`crate::CircuitBuilder::hash_public_inputs`].
+pub const PUBLIC_INPUTS_HASH: &str = "PublicInputsHash";
+
+/// The commitment to `inputs` that [`crate::CircuitBuilder::hash_public_inputs`]
+/// checks in-circuit: SHA-256 over their 32-byte big-endian encodings, with
+/// the top three bits of the digest cleared (the digest modulo `2^253`).
+///
+/// In Solidity this is
+/// `uint256(sha256(abi.encodePacked(inputs))) & ((1 << 253) - 1)`.
+///
+/// # Errors
+///
+/// Returns an error if the Go side fails, which it does not for valid
+/// field elements.
+pub fn public_inputs_hash(inputs: &[Fr]) -> Result<Fr> {
+    let bytes: Vec<u8> = inputs.iter().flat_map(|x| x.to_bytes_be()).collect();
+    let mut out = [0u8; 32];
+    let err_ptr = unsafe {
+        bind::gnark_public_inputs_hash(
+            bytes.as_ptr() as *mut u8,
+            inputs.len() as u64,
+            out.as_mut_ptr(),
+        )
+    };
+    if !err_ptr.is_null() {
+        return Err(unsafe { GnarkError::take(err_ptr) }.into());
+    }
+    Fr::from_bytes_be(&out)
+}
+
+/// The inputs of a circuit, by name and in witness order.
+///
+/// Names are gnark's flattened leaf names: a field `X` is `X`, element `i` of
+/// an array `A` is `A_i`, and field `F` of a nested struct `S` is `S_F`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitAbi {
+    public: Vec<String>,
+    secret: Vec<String>,
+    hashed: Vec<String>,
+}
+
+impl CircuitAbi {
+    /// An ABI with the given public and secret input names, each in witness order.
+    pub fn new(public: Vec<String>, secret: Vec<String>) -> Self {
+        Self {
+            public,
+            secret,
+            hashed: Vec::new(),
+        }
+    }
+
+    /// Declare that [`PUBLIC_INPUTS_HASH`] is the hash of the secret inputs
+    /// `names`, in order (see [`crate::CircuitBuilder::hash_public_inputs`]),
+    /// so that [`Witness::from_elements`] fills it in.
+    pub fn with_hashed_public_inputs(mut self, names: Vec<String>) -> Self {
+        self.hashed = names;
+        self
+    }
+
+    /// The inputs hashed into [`PUBLIC_INPUTS_HASH`], in hash order; empty
+    /// unless the circuit hashes its public inputs.
+    pub fn hashed_public_inputs(&self) -> &[String] {
+        &self.hashed
+    }
+
+    /// Read the input names of the BN254 R1CS at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a BN254 R1CS.
+    pub fn from_r1cs(path: &str) -> Result<Self> {
+        let path = CString::new(path)?;
+        let mut public = std::ptr::null_mut();
+        let mut secret = std::ptr::null_mut();
+        let err_ptr = unsafe {
+            bind::gnark_r1cs_inputs(path.as_ptr() as *mut c_char, &mut public, &mut secret)
+        };
+        if !err_ptr.is_null() {
+            return Err(unsafe { GnarkError::take(err_ptr) }.into());
+        }
+        Ok(unsafe { Self::from_go(public, secret) })
+    }
+
+    /// Take the two name lists written by the Go `writeInputNames`.
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be Go-allocated strings that nothing else frees.
+    pub(crate) unsafe fn from_go(public: *mut c_char, secret: *mut c_char) -> Self {
+        let split = |s: String| -> Vec<String> {
+            if s.is_empty() {
+                Vec::new()
+            } else {
+                s.split('\x1f').map(str::to_owned).collect()
+            }
+        };
+        Self::new(split(take_go_string(public)), split(take_go_string(secret)))
+    }
+
+    /// Public input names, in witness order.
+    pub fn public(&self) -> &[String] {
+        &self.public
+    }
+
+    /// Secret input names, in witness order.
+    pub fn secret(&self) -> &[String] {
+        &self.secret
+    }
+}
+
+/// A full assignment of a circuit's inputs, reduced into the BN254 scalar field.
+///
+/// Build one from named values (e.g. a snarkjs-style `input.json` with
+/// [`Witness::from_json`]) and pass [`Witness::to_json`] to
+/// [`crate::groth16_prove`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    public: Vec<(String, Fr)>,
+    secret: Vec<(String, Fr)>,
+    /// Built by [`Witness::from_sparse`]: [`Witness::to_json`] leaves out
+    /// the zero values.
+    sparse: bool,
+}
+
+impl Witness {
+    /// Assign `values`, keyed by flattened input name, to the inputs of `abi`.
+    ///
+    /// Values are decimal or `0x`-prefixed hexadecimal integers, optionally
+    /// negative, of any size; they are reduced modulo the BN254 scalar field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value does not parse, a name is given twice, an
+    /// input of `abi` has no value, or a name is not an input of `abi`.
+    pub fn from_assignments<I, K, V>(abi: &CircuitAbi, values: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: AsRef<str>,
+    {
+        Self::from_elements(abi, parse_values(values)?)
+    }
+
+    /// Assign field elements, keyed by flattened input name, to the inputs of
+    /// `abi`.
+    ///
+    /// If `abi` has [hashed public inputs](CircuitAbi::hashed_public_inputs),
+    /// [`PUBLIC_INPUTS_HASH`] may be left out and is computed from them.
+    /// [`APP_CONTEXT`] may be left out too, and is then zero until set with
+    /// [`Witness::with_app_context`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name is given twice, an input of `abi` has no
+    /// value, a name is not an input of `abi`, or a given
+    /// [`PUBLIC_INPUTS_HASH`] is not the hash of the hashed inputs.
+    pub fn from_elements<I, K>(abi: &CircuitAbi, values: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, Fr)>,
+        K: Into<String>,
+    {
+        Self::assign(abi, values, false)
+    }
+
+    /// Assign only the non-zero inputs of `abi`, keyed by flattened input
+    /// name, as in [`Witness::from_assignments`]; every input left out is
+    /// zero.
+    ///
+    /// For circuits with thousands of optional signals, e.g. airdrop or
+    /// Merkle circuits with unused slots. [`Witness::to_json`] then only
+    /// holds the non-zero values, and the Go side fills in the rest, so the
+    /// JSON crossing the FFI stays as small as the assignment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value does not parse, a name is given twice, a
+    /// name is not an input of `abi`, or a given [`PUBLIC_INPUTS_HASH`] is
+    /// not the hash of the hashed inputs.
+    pub fn from_sparse<I, K, V>(abi: &CircuitAbi, values: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: AsRef<str>,
+    {
+        Self::assign(abi, parse_values(values)?, true)
+    }
+
+    fn assign<I, K>(abi: &CircuitAbi, values: I, sparse: bool) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, Fr)>,
+        K: Into<String>,
+    {
+        let mut map = HashMap::new();
+        for (name, element) in values {
+            let name = name.into();
+            ensure!(
+                map.insert(name.clone(), element).is_none(),
+                "input {name:?} is assigned twice"
+            );
+        }
+        if !abi.hashed.is_empty() {
+            let inputs = abi
+                .hashed
+                .iter()
+                .map(|name| {
+                    map.get(name)
+                        .copied()
+                        .or(sparse.then_some(Fr::ZERO))
+                        .ok_or_else(|| anyhow!("missing value for hashed public input {name:?}"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let hash = public_inputs_hash(&inputs)?;
+            let given = *map.entry(PUBLIC_INPUTS_HASH.to_owned()).or_insert(hash);
+            ensure!(
+                given == hash,
+                "{PUBLIC_INPUTS_HASH} is not the hash of the hashed public inputs"
+            );
+        }
+        if abi.public.last().is_some_and(|name| name == APP_CONTEXT) {
+            map.entry(APP_CONTEXT.to_owned()).or_insert(Fr::ZERO);
+        }
+        let mut take = |names: &[String], kind: &str| -> Result<Vec<(String, Fr)>> {
+            names
+                .iter()
+                .map(|name| {
+                    map.remove(name)
+                        .or(sparse.then_some(Fr::ZERO))
+                        .map(|v| (name.clone(), v))
+                        .ok_or_else(|| anyhow!("missing value for {kind} input {name:?}"))
+                })
+                .collect()
+        };
+        let public = take(&abi.public, "public")?;
+        let secret = take(&abi.secret, "secret")?;
+        if let Some(name) = map.keys().min() {
+            bail!("{name:?} is not an input of this circuit");
+        }
+        Ok(Self {
+            public,
+            secret,
+            sparse,
+        })
+    }
+
+    /// Assign the inputs of `abi` from a JSON object, the way snarkjs reads
+    /// `input.json`.
+    ///
+    /// Arrays and nested objects are flattened to gnark's names (`{"A": [1, 2]}`
+    /// assigns `A_0` and `A_1`, `{"S": {"F": 3}}` assigns `S_F`). Leaves are
+    /// integers, decimal or hex strings, or booleans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not an object, contains a non-integer
+    /// leaf, or does not match `abi` (see [`Witness::from_assignments`]).
+    #[cfg(feature = "json")]
+    pub fn from_json(abi: &CircuitAbi, value: &serde_json::Value) -> Result<Self> {
+        ensure!(value.is_object(), "witness JSON must be an object");
+        let mut flat = Vec::new();
+        flatten("", value, &mut flat)?;
+        Self::from_assignments(abi, flat)
+    }
+
+    /// [`Witness::from_json`] on a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` does not parse, or as [`Witness::from_json`].
+    #[cfg(feature = "json")]
+    pub fn from_json_str(abi: &CircuitAbi, json: &str) -> Result<Self> {
+        Self::from_json(abi, &serde_json::from_str(json)?)
+    }
+
+    /// The reduced value of input `name`, in decimal.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.element(name).map(|v| v.to_string())
+    }
+
+    /// The value of input `name`.
+    pub fn element(&self, name: &str) -> Option<Fr> {
+        self.public
+            .iter()
+            .chain(&self.secret)
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| *v)
+    }
+
+    /// Set the [`APP_CONTEXT`] input of a circuit built with
+    /// [`crate::CircuitBuilder::bind_app_context`] to `context`, from
+    /// [`crate::app_context`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit has no such input.
+    pub fn with_app_context(mut self, context: Fr) -> Result<Self> {
+        let (_, value) = self
+            .public
+            .iter_mut()
+            .find(|(n, _)| n == APP_CONTEXT)
+            .ok_or_else(|| anyhow!("the circuit has no {APP_CONTEXT:?} input"))?;
+        *value = context;
+        Ok(self)
+    }
+
+    /// The public inputs, in witness order (the order of
+    /// [`CircuitAbi::public`], as gnark's compiled constraint system lays them
+    /// out), without the constant wire.
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        self.public.iter().map(|(_, v)| *v).collect()
+    }
+
+    /// The public inputs as a hex-encoded gnark public witness
+    /// (`witness.MarshalBinary()`), as in [`crate::Groth16ProofResult`].
+    pub fn public_inputs_hex(&self) -> String {
+        encode_hex(&public_witness_bytes(&self.public_inputs()))
+    }
+
+    /// The witness as the flat `{"name": "decimal"}` JSON object that
+    /// [`crate::groth16_prove`] takes.
+    ///
+    /// For a [`Witness::from_sparse`] witness the object marks itself with
+    /// `"$sparse": true` and holds only the non-zero values.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        if self.sparse {
+            out.push_str(r#""$sparse":true"#);
+        }
+        let values = self.public.iter().chain(&self.secret);
+        for (name, value) in values.filter(|(_, v)| !(self.sparse && v.is_zero())) {
+            if out.len() > 1 {
+                out.push(',');
+            }
+            push_json_string(&mut out, name);
+            out.push(':');
+            push_json_string(&mut out, &value.to_string());
+        }
+        out.push('}');
+        out
+    }
+}
+
+/// Parse named decimal or hex values into field elements.
+fn parse_values<I, K, V>(values: I) -> Result<Vec<(String, Fr)>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: AsRef<str>,
+{
+    values
+        .into_iter()
+        .map(|(name, value)| {
+            let name: String = name.into();
+            let element = value
+                .as_ref()
+                .parse::<Fr>()
+                .map_err(|e| anyhow!("input {name:?}: {e}"))?;
+            Ok((name, element))
+        })
+        .collect()
+}
+
+/// Flatten a JSON value into gnark leaf names and integer strings.
+#[cfg(feature = "json")]
+fn flatten(name: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) -> Result<()> {
+    use serde_json::Value;
+
+    let child = |key: &dyn std::fmt::Display| {
+        if name.is_empty() {
+            key.to_string()
+        } else {
+            format!("{name}_{key}")
+        }
+    };
+    match value {
+        Value::Object(fields) => {
+            for (key, v) in fields {
+                flatten(&child(key), v, out)?;
+            }
+        }
+        Value::Array(items) => {
+            ensure!(!name.is_empty(), "witness JSON must be an object");
+            for (i, v) in items.iter().enumerate() {
+                flatten(&child(&i), v, out)?;
+            }
+        }
+        Value::String(s) => out.push((name.to_owned(), s.clone())),
+        Value::Number(n) if n.is_i64() || n.is_u64() => {
+            out.push((name.to_owned(), n.to_string()));
+        }
+        Value::Number(n) => bail!("input {name:?}: {n} is not an integer"),
+        Value::Bool(b) => out.push((name.to_owned(), u8::from(*b).to_string())),
+        Value::Null => bail!("input {name:?} is null"),
+    }
+    Ok(())
+}
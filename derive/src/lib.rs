@@ -0,0 +1,241 @@
+//! `#[derive(GnarkWitness)]`: typed witness structs for rust-gnark.
+//!
+//! Use it through the `derive` feature of `rust-gnark`, which re-exports the
+//! macro next to the `GnarkWitness` trait it implements; see the trait's
+//! documentation for the attributes.
+
+mod json;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Result, Type};
+
+/// Implement `rust_gnark::GnarkWitness` for a struct with named fields.
+///
+/// Field attributes: `#[gnark(public)]` makes an input public,
+/// `#[gnark(name = "...")]` sets its circuit name (by default the field's
+/// name in UpperCamelCase). Struct attribute: `#[gnark(abi = "...")]` checks
+/// the struct against an ABI JSON file, relative to `CARGO_MANIFEST_DIR`.
+#[proc_macro_derive(GnarkWitness, attributes(gnark))]
+pub fn derive_gnark_witness(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// An input declared by a struct field.
+struct Field {
+    ident: Ident,
+    ty: Type,
+    name: String,
+    public: bool,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "GnarkWitness can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            "GnarkWitness needs a struct with named fields",
+        ));
+    };
+
+    let mut fields = Vec::new();
+    for field in &named.named {
+        let ident = field.ident.clone().expect("named fields have idents");
+        let mut name = None;
+        let mut public = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("gnark")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("public") {
+                    public = true;
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    if lit.value().is_empty() {
+                        return Err(Error::new(lit.span(), "input names must not be empty"));
+                    }
+                    name = Some(lit.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `public` or `name = \"...\"`"))
+                }
+            })?;
+        }
+        let name = name.unwrap_or_else(|| upper_camel(&ident.unraw().to_string()));
+        if let Some(other) = fields.iter().find(|f: &&Field| f.name == name) {
+            return Err(Error::new(
+                ident.span(),
+                format!(
+                    "input {name:?} is already declared by field `{}`",
+                    other.ident
+                ),
+            ));
+        }
+        fields.push(Field {
+            ident,
+            ty: field.ty.clone(),
+            name,
+            public,
+        });
+    }
+    // gnark lays out public inputs before secret ones.
+    fields.sort_by_key(|f| !f.public);
+
+    let mut abi_path = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("gnark")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("abi") {
+                abi_path = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `abi = \"...\"`"))
+            }
+        })?;
+    }
+    let abi_check = match &abi_path {
+        Some(path) => {
+            if !input.generics.params.is_empty() {
+                return Err(Error::new(
+                    input.generics.span(),
+                    "#[gnark(abi)] does not support generic structs",
+                ));
+            }
+            check_abi(path, &fields)?
+        }
+        None => TokenStream2::new(),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let pushes = fields.iter().map(|f| {
+        let (ty, name, public) = (&f.ty, &f.name, f.public);
+        let list = if public {
+            quote!(public)
+        } else {
+            quote!(secret)
+        };
+        quote! {
+            #list.extend(::rust_gnark::AbiInput {
+                name: ::std::borrow::ToOwned::to_owned(#name),
+                public: #public,
+                shape: <#ty as ::rust_gnark::WitnessValue>::shape(),
+            }.leaf_names());
+        }
+    });
+    let elements = fields.iter().map(|f| {
+        let (field, name) = (&f.ident, &f.name);
+        quote! {
+            ::rust_gnark::WitnessValue::push_elements(&self.#field, #name, &mut out);
+        }
+    });
+    Ok(quote! {
+        #abi_check
+
+        impl #impl_generics ::rust_gnark::GnarkWitness for #ident #ty_generics #where_clause {
+            fn abi() -> ::rust_gnark::CircuitAbi {
+                let mut public = ::std::vec::Vec::new();
+                let mut secret = ::std::vec::Vec::new();
+                #(#pushes)*
+                ::rust_gnark::CircuitAbi::new(public, secret)
+            }
+
+            fn elements(&self) -> ::std::vec::Vec<(::std::string::String, ::rust_gnark::Fr)> {
+                let mut out = ::std::vec::Vec::new();
+                #(#elements)*
+                out
+            }
+        }
+    })
+}
+
+/// Compare `fields` with the ABI file at `path`: names and visibility here,
+/// shapes through const assertions on the field types.
+fn check_abi(path: &LitStr, fields: &[Field]) -> Result<TokenStream2> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| Error::new(path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+    let full = std::path::Path::new(&dir).join(path.value());
+    let text = std::fs::read_to_string(&full).map_err(|e| {
+        Error::new(
+            path.span(),
+            format!("failed to read {}: {e}", full.display()),
+        )
+    })?;
+    let inputs = json::parse_abi(&text)
+        .map_err(|e| Error::new(path.span(), format!("{}: {e}", full.display())))?;
+
+    for (i, input) in inputs.iter().enumerate() {
+        let kind = if input.public { "public" } else { "secret" };
+        let Some(field) = fields.get(i) else {
+            return Err(Error::new(
+                path.span(),
+                format!("the struct has no field for {kind} input {:?}", input.name),
+            ));
+        };
+        if field.name != input.name || field.public != input.public {
+            return Err(Error::new(
+                field.ident.span(),
+                format!(
+                    "expected {kind} input {:?} here, as in {}",
+                    input.name,
+                    path.value()
+                ),
+            ));
+        }
+    }
+    if let Some(extra) = fields.get(inputs.len()) {
+        return Err(Error::new(
+            extra.ident.span(),
+            format!("input {:?} is not in {}", extra.name, path.value()),
+        ));
+    }
+
+    let assertions = fields.iter().zip(&inputs).map(|(field, input)| {
+        let ty = &field.ty;
+        let rank = input.shape.len();
+        let len: usize = input.shape.iter().product();
+        let message = LitStr::new(
+            &format!(
+                "field `{}` does not have the shape {:?} of input {:?}",
+                field.ident, input.shape, input.name
+            ),
+            Span::call_site(),
+        );
+        quote! {
+            assert!(
+                <#ty as ::rust_gnark::WitnessValue>::RANK == #rank
+                    && <#ty as ::rust_gnark::WitnessValue>::LEN == #len,
+                #message
+            );
+        }
+    });
+    let full = LitStr::new(&full.to_string_lossy(), path.span());
+    Ok(quote! {
+        const _: () = {
+            // Rebuild when the ABI changes.
+            const _: &[u8] = include_bytes!(#full);
+            #(#assertions)*
+        };
+    })
+}
+
+/// `path_elements` -> `PathElements`.
+fn upper_camel(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().expect("parts are not empty");
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
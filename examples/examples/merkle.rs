@@ -0,0 +1,44 @@
+//! Build a Merkle tree on the host, then prove membership of one of its
+//! leaves with the `merkle_membership` example circuit.
+//!
+//! ```sh
+//! cargo run --release -p rust-gnark-examples --example merkle
+//! ```
+
+use anyhow::{ensure, Result};
+use rust_gnark::hash::Curve;
+use rust_gnark::merkle::{MerkleTree, TreeHash};
+use rust_gnark::{groth16_prove, groth16_verify, CompiledCircuit, Witness};
+use rust_gnark_examples::Example;
+
+/// Leaves of the circuit's depth-4 tree.
+const LEAVES: usize = 16;
+
+fn main() -> Result<()> {
+    let leaves: Vec<[u8; 32]> = (0..LEAVES)
+        .map(|i| {
+            let mut leaf = [0u8; 32];
+            leaf[24..].copy_from_slice(&(1000 + i as u64).to_be_bytes());
+            leaf
+        })
+        .collect();
+    let tree = MerkleTree::new(Curve::Bn254, TreeHash::Mimc, &leaves)?;
+    let index = 11;
+    let proof = tree.proof(index)?;
+
+    rust_gnark_examples::prepare_fixtures()?;
+    let example = Example::MerkleMembership;
+    let abi = CompiledCircuit::read(&example.r1cs_path())?.abi()?;
+    let mut assignments = proof.assignments("Proof");
+    assignments.push(("Root".into(), assignments[0].1.clone()));
+    assignments.push(("Index".into(), index.to_string()));
+    let witness = Witness::from_assignments(&abi, assignments)?;
+
+    let result = groth16_prove(&example.r1cs_path(), &example.pk_path(), &witness.to_json())?;
+    ensure!(
+        groth16_verify(&example.r1cs_path(), &example.vk_path(), &result)?,
+        "the membership proof does not verify"
+    );
+    println!("leaf {index} is in the tree, proof {}", result.proof);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Prove and verify the example circuits from their fixtures.
+//!
+//! ```sh
+//! cargo run --release -p rust-gnark-examples --example prove [name...]
+//! ```
+//!
+//! Without names, every example is run.
+
+use anyhow::{bail, ensure, Result};
+use rust_gnark::{groth16_prove, groth16_verify};
+use rust_gnark_examples::Example;
+use std::time::Instant;
+
+fn main() -> Result<()> {
+    let mut examples = Vec::new();
+    for name in std::env::args().skip(1) {
+        let Some(example) = Example::from_name(&name) else {
+            let names: Vec<_> = Example::ALL.iter().map(|e| e.name()).collect();
+            bail!(
+                "unknown example {name:?}, expected one of {}",
+                names.join(", ")
+            );
+        };
+        examples.push(example);
+    }
+    if examples.is_empty() {
+        examples = Example::ALL.to_vec();
+    }
+
+    rust_gnark_examples::prepare_fixtures()?;
+    for example in examples {
+        let start = Instant::now();
+        let proof = groth16_prove(
+            &example.r1cs_path(),
+            &example.pk_path(),
+            &example.witness_json()?,
+        )?;
+        let proved = start.elapsed();
+        let valid = groth16_verify(&example.r1cs_path(), &example.vk_path(), &proof)?;
+        ensure!(valid, "the {} proof does not verify", example.name());
+        println!(
+            "{:<18} proved in {proved:?}, verified in {:?}",
+            example.name(),
+            start.elapsed() - proved
+        );
+    }
+    Ok(())
+}
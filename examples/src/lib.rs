@@ -0,0 +1,172 @@
+//! Small example circuits, compiled, with Groth16 keys and a satisfying
+//! witness, for trying rust-gnark end to end and for testing it on circuits
+//! built from gnark's standard library.
+//!
+//! The fixtures are generated by `go/cmd/gen_examples/main.go`:
+//!
+//! ```sh
+//! cd go && go run ./cmd/gen_examples
+//! ```
+//!
+//! Without them in `fixtures/`, [`prepare_fixtures`] runs the generator into a
+//! temporary directory, which needs a Go toolchain.
+//!
+//! ```no_run
+//! use rust_gnark_examples::Example;
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! rust_gnark_examples::prepare_fixtures()?;
+//! let example = Example::MerkleMembership;
+//! let proof = rust_gnark::groth16_prove(
+//!     &example.r1cs_path(),
+//!     &example.pk_path(),
+//!     &example.witness_json()?,
+//! )?;
+//! assert!(rust_gnark::groth16_verify(&example.r1cs_path(), &example.vk_path(), &proof)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{ensure, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// An example circuit over BN254.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Example {
+    /// `A * B == C`, with `C` public.
+    Multiplier,
+    /// Membership of the leaf `Index` in a depth-4 MiMC Merkle tree with the
+    /// public root `Root`, using gnark's `std/accumulator/merkle` in the
+    /// layout of [`rust_gnark::merkle::MerkleTree`]. The proof is the
+    /// `Proof` input, as [`rust_gnark::merkle::MerkleProof::assignments`]
+    /// names it.
+    MerkleMembership,
+    /// An EdDSA signature over BN254's twisted Edwards curve with MiMC, as
+    /// [`rust_gnark::eddsa`] makes them, on the public `PublicKey` and
+    /// `Message`, with gnark's `std/signature/eddsa`.
+    EddsaVerify,
+    /// Knowledge of a two-element `Preimage` of the public Poseidon2
+    /// `Digest`, as [`rust_gnark::hash::poseidon2`] computes it.
+    PoseidonPreimage,
+}
+
+impl Example {
+    /// Every example.
+    pub const ALL: [Example; 4] = [
+        Example::Multiplier,
+        Example::MerkleMembership,
+        Example::EddsaVerify,
+        Example::PoseidonPreimage,
+    ];
+
+    /// File stem of the example's fixtures, e.g. `merkle_membership`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Example::Multiplier => "multiplier",
+            Example::MerkleMembership => "merkle_membership",
+            Example::EddsaVerify => "eddsa_verify",
+            Example::PoseidonPreimage => "poseidon_preimage",
+        }
+    }
+
+    /// The example named `name`, as [`Example::name`] gives it.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.name() == name)
+    }
+
+    /// Path to the compiled constraint system.
+    pub fn r1cs_path(self) -> String {
+        self.fixture("r1cs")
+    }
+
+    /// Path to the Groth16 proving key.
+    pub fn pk_path(self) -> String {
+        self.fixture("pk")
+    }
+
+    /// Path to the Groth16 verifying key.
+    pub fn vk_path(self) -> String {
+        self.fixture("vk")
+    }
+
+    /// A satisfying witness, as JSON for [`rust_gnark::groth16_prove`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fixture cannot be read.
+    pub fn witness_json(self) -> Result<String> {
+        let path = self.fixture("witness.json");
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {path}"))
+    }
+
+    fn fixture(self, ext: &str) -> String {
+        fixtures_dir()
+            .join(format!("{}.{ext}", self.name()))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Fixtures [`prepare_fixtures`] generated, if it had to.
+static GENERATED: OnceLock<PathBuf> = OnceLock::new();
+
+/// Directory holding the fixtures: the crate's `fixtures/`, the directory
+/// [`prepare_fixtures`] generated them into, or `./fixtures` on Android, where
+/// `cargo xtask test-android` pushes them next to the test binary.
+pub fn fixtures_dir() -> PathBuf {
+    if cfg!(target_os = "android") {
+        return PathBuf::from("./fixtures");
+    }
+    GENERATED.get().cloned().unwrap_or_else(committed_dir)
+}
+
+/// Make sure every example has its fixtures, generating them with
+/// `go/cmd/gen_examples` into a temporary directory if the crate's
+/// `fixtures/` does not have them, and return the directory
+/// [`fixtures_dir`] now names.
+///
+/// # Errors
+///
+/// Returns an error if the fixtures are missing and the generator cannot be
+/// run, e.g. without a Go toolchain.
+pub fn prepare_fixtures() -> Result<PathBuf> {
+    static GENERATING: Mutex<()> = Mutex::new(());
+    let _guard = GENERATING.lock().unwrap_or_else(|e| e.into_inner());
+    if cfg!(target_os = "android") || GENERATED.get().is_some() || complete(&committed_dir()) {
+        return Ok(fixtures_dir());
+    }
+
+    let dir = std::env::temp_dir().join(format!("rust-gnark-examples-{}", std::process::id()));
+    let go_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../go");
+    let status = Command::new("go")
+        .args(["run", "./cmd/gen_examples"])
+        .arg(&dir)
+        .current_dir(&go_dir)
+        .status()
+        .with_context(|| format!("failed to run go in {}", go_dir.display()))?;
+    ensure!(
+        status.success(),
+        "go run ./cmd/gen_examples failed: {status}"
+    );
+    ensure!(
+        complete(&dir),
+        "go run ./cmd/gen_examples did not write every fixture to {}",
+        dir.display()
+    );
+    Ok(GENERATED.get_or_init(|| dir).clone())
+}
+
+fn committed_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+/// Whether `dir` has every fixture of every example.
+fn complete(dir: &Path) -> bool {
+    Example::ALL.iter().all(|example| {
+        ["r1cs", "pk", "vk", "witness.json"]
+            .iter()
+            .all(|ext| dir.join(format!("{}.{ext}", example.name())).is_file())
+    })
+}
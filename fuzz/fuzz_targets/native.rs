@@ -0,0 +1,25 @@
+//! The pure-Rust decoders of gnark's verifying key, proof and public witness
+//! encodings. Whatever decodes must re-encode to a fixed point.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_gnark::native::{Proof, PublicInputs, VerifyingKey};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(vk) = VerifyingKey::from_gnark_bytes(data) {
+        let bytes = vk.to_gnark_bytes();
+        let again = VerifyingKey::from_gnark_bytes(&bytes).expect("re-encoded key decodes");
+        assert_eq!(again.to_gnark_bytes(), bytes);
+    }
+    if let Ok(proof) = Proof::from_gnark_bytes(data) {
+        let bytes = proof.to_gnark_bytes();
+        let again = Proof::from_gnark_bytes(&bytes).expect("re-encoded proof decodes");
+        assert_eq!(again.to_gnark_bytes(), bytes);
+    }
+    if let Ok(inputs) = PublicInputs::from_gnark_bytes(data) {
+        let again = PublicInputs::from_gnark_bytes(&inputs.to_gnark_bytes())
+            .expect("re-encoded inputs decode");
+        assert_eq!(again, inputs);
+    }
+});
@@ -0,0 +1,64 @@
+//! Proofs and public witnesses from untrusted bytes, through every Go entry
+//! point that decodes a proof: verification, Solidity calldata and
+//! rerandomization.
+//!
+//! The input is a big-endian `u16` proof length, the proof, then the public
+//! witness, checked against a one-constraint circuit set up on first use.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_gnark::{groth16_verify, CircuitBuilder, Groth16ProofResult, VerifyingKey};
+use std::sync::OnceLock;
+
+struct Setup {
+    r1cs: String,
+    vk_path: String,
+    vk: VerifyingKey,
+}
+
+fn setup() -> &'static Setup {
+    static SETUP: OnceLock<Setup> = OnceLock::new();
+    SETUP.get_or_init(|| {
+        rust_gnark::init().expect("init");
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let circuit = c.compile().expect("compile");
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-fuzz-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create setup directory");
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("c.r1cs")).expect("write r1cs");
+        circuit
+            .groth16_setup(&path("c.pk"), &path("c.vk"))
+            .expect("setup");
+        Setup {
+            r1cs: path("c.r1cs"),
+            vk: VerifyingKey::load(&path("c.vk")).expect("load vk"),
+            vk_path: path("c.vk"),
+        }
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((len, rest)) = data.split_first_chunk::<2>() else {
+        return;
+    };
+    let (proof, public) = rest.split_at((u16::from_be_bytes(*len) as usize).min(rest.len()));
+    let result = Groth16ProofResult {
+        proof: hex(proof),
+        public_inputs: hex(public),
+    };
+
+    let s = setup();
+    let _ = groth16_verify(&s.r1cs, &s.vk_path, &result);
+    let _ = result.solidity_calldata();
+    let _ = result.rerandomize(&s.vk);
+});
@@ -0,0 +1,17 @@
+//! Verifying keys from untrusted bytes (`gnark_groth16_vk_from_bytes`).
+//!
+//! `RawUnchecked` is left out: it skips subgroup checks and is documented as
+//! trusted-input only.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_gnark::{SerializationFormat, VerifyingKey};
+
+fuzz_target!(|data: &[u8]| {
+    for format in [SerializationFormat::Compressed, SerializationFormat::Raw] {
+        if let Ok(vk) = VerifyingKey::from_bytes_with_format(data, format) {
+            vk.to_bytes(format).expect("a key that loads serializes");
+        }
+    }
+});
@@ -0,0 +1,18 @@
+//! Witness construction from untrusted JSON, e.g. a prover service's request
+//! body.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_gnark::{CircuitAbi, Witness};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    let abi = CircuitAbi::new(vec!["Y".into()], vec!["X".into(), "Path".into()]);
+    if let Ok(witness) = Witness::from_json_str(&abi, json) {
+        let _ = witness.to_json();
+        let _ = witness.public_inputs_hex();
+    }
+});
@@ -0,0 +1,332 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"compress/gzip"
+	"encoding/binary"
+	"fmt"
+	"io"
+	"math/big"
+	"sort"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+	"github.com/consensys/gnark/frontend/cs/scs"
+)
+
+// Noir ACIR programs as gnark circuits. The input is the gzipped bincode
+// serialization of an acir `Program` (the `bytecode` of a nargo artifact,
+// base64-decoded). Only the main function is read, and only its AssertZero
+// opcodes are supported: every witness that is not a parameter or return
+// value must be determined by the first opcode it appears in, linearly. Brillig
+// calls, black-box functions, memory and call opcodes are rejected.
+
+// acirExpr is sum(mul) + sum(linear) + constant == 0.
+type acirExpr struct {
+	mul      []acirMulTerm
+	linear   []acirTerm
+	constant *big.Int
+}
+
+type acirMulTerm struct {
+	coeff *big.Int
+	a, b  uint32
+}
+
+type acirTerm struct {
+	coeff *big.Int
+	w     uint32
+}
+
+type acirProgram struct {
+	opcodes []acirExpr
+	private []uint32
+	public  []uint32 // public parameters and return values, sorted
+}
+
+// acirReader decodes bincode's legacy encoding: little-endian fixed-width
+// integers, u64 lengths and u32 enum tags.
+type acirReader struct {
+	data []byte
+	err  error
+}
+
+func (r *acirReader) take(n uint64) []byte {
+	if r.err != nil {
+		return nil
+	}
+	if uint64(len(r.data)) < n {
+		r.err = fmt.Errorf("truncated program")
+		return nil
+	}
+	b := r.data[:n]
+	r.data = r.data[n:]
+	return b
+}
+
+func (r *acirReader) u32() uint32 {
+	if b := r.take(4); b != nil {
+		return binary.LittleEndian.Uint32(b)
+	}
+	return 0
+}
+
+func (r *acirReader) u64() uint64 {
+	if b := r.take(8); b != nil {
+		return binary.LittleEndian.Uint64(b)
+	}
+	return 0
+}
+
+// len reads a sequence length, bounded by the remaining input so corrupt
+// lengths fail instead of allocating.
+func (r *acirReader) len() int {
+	n := r.u64()
+	if r.err == nil && n > uint64(len(r.data)) {
+		r.err = fmt.Errorf("sequence length %d exceeds the program size", n)
+	}
+	return int(n)
+}
+
+// field reads a FieldElement, serialized as a hex string.
+func (r *acirReader) field() *big.Int {
+	s := string(r.take(r.u64()))
+	if r.err != nil {
+		return nil
+	}
+	v, ok := new(big.Int).SetString(s, 16)
+	if !ok {
+		r.err = fmt.Errorf("invalid field element %q", s)
+	}
+	return v
+}
+
+func (r *acirReader) witnesses() []uint32 {
+	ws := make([]uint32, r.len())
+	for i := range ws {
+		ws[i] = r.u32()
+	}
+	return ws
+}
+
+func (r *acirReader) expr() acirExpr {
+	var e acirExpr
+	e.mul = make([]acirMulTerm, r.len())
+	for i := range e.mul {
+		e.mul[i] = acirMulTerm{coeff: r.field(), a: r.u32(), b: r.u32()}
+	}
+	e.linear = make([]acirTerm, r.len())
+	for i := range e.linear {
+		e.linear[i] = acirTerm{coeff: r.field(), w: r.u32()}
+	}
+	e.constant = r.field()
+	return e
+}
+
+func parseACIR(data []byte) (*acirProgram, error) {
+	if len(data) >= 2 && data[0] == 0x1f && data[1] == 0x8b {
+		zr, err := gzip.NewReader(bytes.NewReader(data))
+		if err != nil {
+			return nil, errorf(errInvalidInput, "acir: %w", err)
+		}
+		if data, err = io.ReadAll(zr); err != nil {
+			return nil, errorf(errInvalidInput, "acir: %w", err)
+		}
+	}
+
+	r := &acirReader{data: data}
+	if n := r.len(); r.err == nil && n == 0 {
+		return nil, errorf(errInvalidInput, "acir: program has no functions")
+	}
+	r.u32() // current_witness_index
+	prog := &acirProgram{opcodes: make([]acirExpr, r.len())}
+	for i := range prog.opcodes {
+		if tag := r.u32(); r.err == nil && tag != 0 {
+			return nil, errorf(errInvalidInput, "acir: opcode %d is not AssertZero (tag %d); Brillig, black-box, memory and call opcodes are not supported", i, tag)
+		}
+		prog.opcodes[i] = r.expr()
+	}
+	if r.u32() == 1 { // ExpressionWidth::Bounded { width }
+		r.u64()
+	}
+	prog.private = r.witnesses()
+	public := r.witnesses()
+	returns := r.witnesses()
+	if r.err != nil {
+		return nil, errorf(errInvalidInput, "acir: %w", r.err)
+	}
+
+	seen := map[uint32]bool{}
+	for _, w := range append(public, returns...) {
+		if !seen[w] {
+			seen[w] = true
+			prog.public = append(prog.public, w)
+		}
+	}
+	sort.Slice(prog.public, func(i, j int) bool { return prog.public[i] < prog.public[j] })
+	return prog, nil
+}
+
+func acirName(w uint32) string {
+	return fmt.Sprintf("w%d", w)
+}
+
+// acirCircuit replays an acirProgram; inputs are named after their witness
+// index once compiled.
+type acirCircuit struct {
+	Public []frontend.Variable `gnark:",public"`
+	Secret []frontend.Variable `gnark:",secret"`
+	Prog   *acirProgram        `gnark:"-"`
+}
+
+func (c *acirCircuit) Define(api frontend.API) error {
+	known := map[uint32]frontend.Variable{}
+	for i, w := range c.Prog.public {
+		known[w] = c.Public[i]
+	}
+	for i, w := range c.Prog.private {
+		known[w] = c.Secret[i]
+	}
+
+	for i, e := range c.Prog.opcodes {
+		// The witnesses this opcode is the first to mention.
+		var unknown []uint32
+		note := func(w uint32) {
+			if _, ok := known[w]; !ok {
+				for _, u := range unknown {
+					if u == w {
+						return
+					}
+				}
+				unknown = append(unknown, w)
+			}
+		}
+		for _, t := range e.mul {
+			note(t.a)
+			note(t.b)
+		}
+		for _, t := range e.linear {
+			note(t.w)
+		}
+
+		switch len(unknown) {
+		case 0:
+			api.AssertIsEqual(acirEval(api, e, known, nil), 0)
+		case 1:
+			w := unknown[0]
+			// e = coeff * w + rest, where coeff only involves known witnesses.
+			var coeff []frontend.Variable
+			for _, t := range e.mul {
+				switch {
+				case t.a == w && t.b == w:
+					return fmt.Errorf("acir opcode %d: witness %d first appears squared", i, w)
+				case t.a == w:
+					coeff = append(coeff, api.Mul(t.coeff, known[t.b]))
+				case t.b == w:
+					coeff = append(coeff, api.Mul(t.coeff, known[t.a]))
+				}
+			}
+			for _, t := range e.linear {
+				if t.w == w {
+					coeff = append(coeff, t.coeff)
+				}
+			}
+			rest := api.Neg(acirEval(api, e, known, &w))
+			known[w] = api.Div(rest, acirSum(api, coeff))
+		default:
+			return fmt.Errorf("acir opcode %d: witnesses %v are not determined by earlier opcodes (Brillig is not supported)", i, unknown)
+		}
+	}
+	return nil
+}
+
+// acirSum adds terms, which may be empty.
+func acirSum(api frontend.API, terms []frontend.Variable) frontend.Variable {
+	switch len(terms) {
+	case 0:
+		return 0
+	case 1:
+		return terms[0]
+	default:
+		return api.Add(terms[0], terms[1], terms[2:]...)
+	}
+}
+
+// acirEval evaluates e over known witnesses, leaving out the terms that
+// mention skip.
+func acirEval(api frontend.API, e acirExpr, known map[uint32]frontend.Variable, skip *uint32) frontend.Variable {
+	terms := []frontend.Variable{e.constant}
+	for _, t := range e.mul {
+		if skip != nil && (t.a == *skip || t.b == *skip) {
+			continue
+		}
+		terms = append(terms, api.Mul(t.coeff, known[t.a], known[t.b]))
+	}
+	for _, t := range e.linear {
+		if skip != nil && t.w == *skip {
+			continue
+		}
+		terms = append(terms, api.Mul(t.coeff, known[t.w]))
+	}
+	return acirSum(api, terms)
+}
+
+// gnark_acir_load parses the n-byte ACIR program at data into a program
+// handle.
+//
+//export gnark_acir_load
+func gnark_acir_load(data *C.uint8_t, n C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	prog, err := parseACIR(borrowBytes(data, n))
+	if err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(prog)
+	return nil
+}
+
+// gnark_acir_compile compiles an ACIR program over BN254 into an R1CS
+// (sparse = 0) or a SparseR1CS (sparse != 0) circuit handle, with inputs
+// named "w<index>".
+//
+//export gnark_acir_compile
+func gnark_acir_compile(handle C.uint64_t, sparse C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	prog, err := lookupHandle[*acirProgram](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid acir program handle: %w", err)
+	}
+	circuit := acirCircuit{
+		Public: make([]frontend.Variable, len(prog.public)),
+		Secret: make([]frontend.Variable, len(prog.private)),
+		Prog:   prog,
+	}
+	builder := r1cs.NewBuilder
+	if sparse != 0 {
+		builder = scs.NewBuilder
+	}
+	ccs, err := compileCircuit(ecc.BN254.ScalarField(), builder, &circuit, nil)
+	if err != nil {
+		return cErrorf(errInvalidInput, "failed to compile acir program: %w", err)
+	}
+	sys, err := systemOf(ccs)
+	if err != nil {
+		return cError(err)
+	}
+	for i, w := range prog.public {
+		sys.Public[len(sys.Public)-len(prog.public)+i] = acirName(w)
+	}
+	for i, w := range prog.private {
+		sys.Secret[i] = acirName(w)
+	}
+	*out_handle = newHandle(ccs)
+	return nil
+}
@@ -0,0 +1,272 @@
+//go:build !no_bw6_761 && !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/hex"
+	"fmt"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/groth16"
+	"github.com/consensys/gnark/backend/witness"
+	"github.com/consensys/gnark/constraint"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+	"github.com/consensys/gnark/std/algebra/emulated/sw_bn254"
+	stdgroth16 "github.com/consensys/gnark/std/recursion/groth16"
+)
+
+// Proof aggregation: an outer Groth16 circuit over BW6-761 that verifies N
+// inner BN254 Groth16 proofs (same inner verifying key) with gnark's in-circuit
+// verifier. BN254 arithmetic is emulated in the BW6-761 scalar field, so the
+// outer circuit is large (millions of constraints per inner proof) but a
+// single outer proof attests to all N inner ones.
+//
+// Building with the no_bw6_761 tag leaves aggregation out.
+
+type (
+	innerProof   = stdgroth16.Proof[sw_bn254.G1Affine, sw_bn254.G2Affine]
+	innerVK      = stdgroth16.VerifyingKey[sw_bn254.G1Affine, sw_bn254.G2Affine, sw_bn254.GTEl]
+	innerWitness = stdgroth16.Witness[sw_bn254.ScalarField]
+)
+
+var outerCurve = ecc.BW6_761
+
+type aggregationCircuit struct {
+	Proofs    []innerProof
+	Witnesses []innerWitness `gnark:",public"`
+	// Fixed inner verifying key, compiled into the circuit as constants.
+	VerifyingKey innerVK `gnark:"-"`
+}
+
+func (c *aggregationCircuit) Define(api frontend.API) error {
+	verifier, err := stdgroth16.NewVerifier[sw_bn254.ScalarField, sw_bn254.G1Affine, sw_bn254.G2Affine, sw_bn254.GTEl](api)
+	if err != nil {
+		return fmt.Errorf("new verifier: %w", err)
+	}
+	for i := range c.Proofs {
+		if err := verifier.AssertProof(c.VerifyingKey, c.Proofs[i], c.Witnesses[i]); err != nil {
+			return fmt.Errorf("assert proof %d: %w", i, err)
+		}
+	}
+	return nil
+}
+
+// aggregator is the Go object behind an aggregator handle.
+type aggregator struct {
+	n   int
+	ccs constraint.ConstraintSystem
+	pk  groth16.ProvingKey
+	vk  groth16.VerifyingKey
+}
+
+// gnark_aggregator_new compiles the aggregation circuit for n proofs of the
+// inner circuit (r1cs_path, vk_path) and runs a Groth16 setup for it.
+//
+// The setup is a local, single-party one: fine for testing and for
+// deployments that trust the aggregating party, not a ceremony.
+//
+//export gnark_aggregator_new
+func gnark_aggregator_new(r1cs_path *C.char, vk_path *C.char, n C.uint32_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if n == 0 {
+		return cErrorf(errInvalidInput, "aggregator needs at least one proof")
+	}
+
+	innerCS := groth16.NewCS(ecc.BN254)
+	if err := readFile(C.GoString(r1cs_path), innerCS.ReadFrom); err != nil {
+		return cErrorf(errSerialization, "failed to read inner r1cs: %w", err)
+	}
+	innerKey := groth16.NewVerifyingKey(ecc.BN254)
+	if err := readFile(C.GoString(vk_path), innerKey.ReadFrom); err != nil {
+		return cErrorf(errInvalidKey, "failed to read inner verifying key: %w", err)
+	}
+	fixedVK, err := stdgroth16.ValueOfVerifyingKeyFixed[sw_bn254.G1Affine, sw_bn254.G2Affine, sw_bn254.GTEl](innerKey)
+	if err != nil {
+		return cErrorf(errInvalidKey, "unsupported inner verifying key: %w", err)
+	}
+
+	circuit := aggregationCircuit{
+		Proofs:       make([]innerProof, n),
+		Witnesses:    make([]innerWitness, n),
+		VerifyingKey: fixedVK,
+	}
+	for i := range circuit.Proofs {
+		circuit.Proofs[i] = stdgroth16.PlaceholderProof[sw_bn254.G1Affine, sw_bn254.G2Affine](innerCS)
+		circuit.Witnesses[i] = stdgroth16.PlaceholderWitness[sw_bn254.ScalarField](innerCS)
+	}
+
+	ccs, err := compileCircuit(outerCurve.ScalarField(), r1cs.NewBuilder, &circuit, nil)
+	if err != nil {
+		return cErrorf(errOther, "failed to compile aggregation circuit: %w", err)
+	}
+	pk, vk, err := groth16.Setup(ccs)
+	if err != nil {
+		return cErrorf(errOther, "aggregation setup failed: %w", err)
+	}
+
+	*out_handle = newHandle(&aggregator{n: int(n), ccs: ccs, pk: pk, vk: vk})
+	return nil
+}
+
+// gnark_aggregator_aggregate proves that all n (proof, public inputs) pairs,
+// hex-encoded as returned by gnark_groth16_prove, verify against the inner key.
+// On success *out_proof and *out_public_inputs are set to hex strings the
+// caller frees with gnark_free_string.
+//
+//export gnark_aggregator_aggregate
+func gnark_aggregator_aggregate(
+	handle C.uint64_t,
+	proofs_hex **C.char,
+	public_inputs_hex **C.char,
+	n C.uint32_t,
+	out_proof **C.char,
+	out_public_inputs **C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	agg, err := lookupHandle[*aggregator](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid aggregator handle: %w", err)
+	}
+	if int(n) != agg.n {
+		return cErrorf(errInvalidInput, "aggregator expects %d proofs, got %d", agg.n, n)
+	}
+
+	proofs := unsafe.Slice(proofs_hex, int(n))
+	publics := unsafe.Slice(public_inputs_hex, int(n))
+	assignment := aggregationCircuit{
+		Proofs:    make([]innerProof, n),
+		Witnesses: make([]innerWitness, n),
+	}
+	for i := range assignment.Proofs {
+		proof, err := decodeProof(ecc.BN254, C.GoString(proofs[i]))
+		if err != nil {
+			return cErrorf(errSerialization, "proof %d: %w", i, err)
+		}
+		if assignment.Proofs[i], err = stdgroth16.ValueOfProof[sw_bn254.G1Affine, sw_bn254.G2Affine](proof); err != nil {
+			return cErrorf(errSerialization, "proof %d: %w", i, err)
+		}
+		pub, err := decodePublicWitness(ecc.BN254, C.GoString(publics[i]))
+		if err != nil {
+			return cErrorf(errSerialization, "public inputs %d: %w", i, err)
+		}
+		if assignment.Witnesses[i], err = stdgroth16.ValueOfWitness[sw_bn254.ScalarField](pub); err != nil {
+			return cErrorf(errSerialization, "public inputs %d: %w", i, err)
+		}
+	}
+
+	full, err := frontend.NewWitness(&assignment, outerCurve.ScalarField())
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build aggregation witness: %w", err)
+	}
+	proof, err := groth16.Prove(agg.ccs, agg.pk, full)
+	if err != nil {
+		return cErrorf(errOther, "aggregation proof failed: %w", err)
+	}
+	return writeProofHex(proof, full, out_proof, out_public_inputs)
+}
+
+// writeProofHex sets *out_proof and *out_public_inputs to proof and the
+// public part of full, hex-encoded like gnark_groth16_prove's; the caller
+// frees them with gnark_free_string.
+func writeProofHex(proof groth16.Proof, full witness.Witness, out_proof **C.char, out_public_inputs **C.char) *C.char {
+	var proofBuf bytes.Buffer
+	if _, err := proof.WriteTo(&proofBuf); err != nil {
+		return cErrorf(errSerialization, "failed to serialize proof: %w", err)
+	}
+	pub, err := full.Public()
+	if err != nil {
+		return cErrorf(errSerialization, "failed to extract public witness: %w", err)
+	}
+	pubBin, err := pub.MarshalBinary()
+	if err != nil {
+		return cErrorf(errSerialization, "failed to marshal public witness: %w", err)
+	}
+
+	*out_proof = C.CString(hex.EncodeToString(proofBuf.Bytes()))
+	*out_public_inputs = C.CString(hex.EncodeToString(pubBin))
+	return nil
+}
+
+// gnark_aggregator_verify verifies an aggregated proof. Returns NULL if valid,
+// otherwise an error message ("invalid proof: ..." if the check fails).
+//
+//export gnark_aggregator_verify
+func gnark_aggregator_verify(handle C.uint64_t, proof_hex *C.char, public_inputs_hex *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	agg, err := lookupHandle[*aggregator](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid aggregator handle: %w", err)
+	}
+	proof, err := decodeProof(outerCurve, C.GoString(proof_hex))
+	if err != nil {
+		return cError(err)
+	}
+	pub, err := decodePublicWitness(outerCurve, C.GoString(public_inputs_hex))
+	if err != nil {
+		return cError(err)
+	}
+	if err := groth16.Verify(proof, agg.vk, pub); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
+	}
+	return nil
+}
+
+// gnark_aggregator_vk_to_bytes serializes the outer (BW6-761) verifying key
+// into a C buffer that the caller frees with gnark_free_buffer.
+//
+//export gnark_aggregator_vk_to_bytes
+func gnark_aggregator_vk_to_bytes(handle C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	agg, err := lookupHandle[*aggregator](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid aggregator handle: %w", err)
+	}
+	var buf cBuffer
+	if _, err := agg.vk.WriteTo(&buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize verifying key: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+func decodeProof(curve ecc.ID, proofHex string) (groth16.Proof, error) {
+	b, err := hex.DecodeString(proofHex)
+	if err != nil {
+		return nil, errorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	if err := checkProofEncoding(curve, b); err != nil {
+		return nil, errorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	proof := groth16.NewProof(curve)
+	if _, err := proof.ReadFrom(bytes.NewReader(b)); err != nil {
+		return nil, errorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	return proof, nil
+}
+
+func decodePublicWitness(curve ecc.ID, publicHex string) (witness.Witness, error) {
+	b, err := hex.DecodeString(publicHex)
+	if err != nil {
+		return nil, errorf(errSerialization, "failed to decode public inputs hex: %w", err)
+	}
+	if err := checkPublicWitnessEncoding(curve, b); err != nil {
+		return nil, errorf(errSerialization, "failed to unmarshal public witness: %w", err)
+	}
+	w, err := witness.New(curve.ScalarField())
+	if err != nil {
+		return nil, errorf(errSerialization, "failed to create witness: %w", err)
+	}
+	if err := w.UnmarshalBinary(b); err != nil {
+		return nil, errorf(errSerialization, "failed to unmarshal public witness: %w", err)
+	}
+	return w, nil
+}
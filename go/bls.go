@@ -0,0 +1,224 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"unsafe"
+)
+
+// BLS signatures over BLS12-381 in the minimal-pubkey-size variant of the
+// IETF draft (draft-irtf-cfrg-bls-signature): public keys are compressed G1
+// points (48 bytes), signatures compressed G2 points (96 bytes), both in the
+// zcash encoding that Ethereum consensus clients use. Secret keys are 32-byte
+// big-endian scalars. The hash-to-curve domain separation tag is chosen by
+// the caller, which selects the ciphersuite (basic, augmented or
+// proof-of-possession).
+
+const (
+	blsSecretKeySize = 32
+	blsPublicKeySize = 48
+	blsSignatureSize = 96
+)
+
+// blsScheme is implemented in bls_bls12_381.go; it is nil when BLS12-381 is
+// not compiled in.
+type blsScheme interface {
+	keyGen(ikm []byte) ([]byte, error)
+	publicKey(sk []byte) ([]byte, error)
+	sign(sk, msg, dst []byte) ([]byte, error)
+	checkPublicKey(pk []byte) error
+	checkSignature(sig []byte) error
+	aggregatePublicKeys(pks [][]byte) ([]byte, error)
+	aggregateSignatures(sigs [][]byte) ([]byte, error)
+	// verify checks sig against the pairs (pks[i], msgs[i]); one pair is a
+	// plain verification.
+	verify(pks, msgs [][]byte, sig, dst []byte) (bool, error)
+}
+
+func blsImpl() (blsScheme, error) {
+	if blsSignatures == nil {
+		return nil, errBLS12381Disabled
+	}
+	return blsSignatures, nil
+}
+
+// splitFixed cuts n concatenated elements of size bytes each out of data.
+func splitFixed(data *C.uint8_t, n C.uint64_t, size int) [][]byte {
+	flat := borrowBytes(data, n*C.uint64_t(size))
+	out := make([][]byte, int(n))
+	for i := range out {
+		out[i] = flat[i*size : (i+1)*size]
+	}
+	return out
+}
+
+// gnark_bls_keygen derives a secret key from at least 32 bytes of key
+// material with the draft's HKDF-based KeyGen.
+//
+//export gnark_bls_keygen
+func gnark_bls_keygen(ikm *C.uint8_t, ikm_len C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	sk, err := bls.keyGen(borrowBytes(ikm, ikm_len))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(sk, out_data, out_len)
+}
+
+// gnark_bls_public_key derives the compressed G1 public key of a secret key.
+//
+//export gnark_bls_public_key
+func gnark_bls_public_key(sk *C.uint8_t, sk_len C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	pk, err := bls.publicKey(borrowBytes(sk, sk_len))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(pk, out_data, out_len)
+}
+
+// gnark_bls_sign signs msg, hashed to G2 with domain separation tag dst.
+//
+//export gnark_bls_sign
+func gnark_bls_sign(
+	sk *C.uint8_t, sk_len C.uint64_t,
+	msg *C.uint8_t, msg_len C.uint64_t,
+	dst *C.uint8_t, dst_len C.uint64_t,
+	out_data **C.uint8_t, out_len *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	sig, err := bls.sign(borrowBytes(sk, sk_len), borrowBytes(msg, msg_len), borrowBytes(dst, dst_len))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(sig, out_data, out_len)
+}
+
+// gnark_bls_check_public_key fails unless pk is a compressed G1 point in the
+// prime-order subgroup other than the identity (the draft's KeyValidate).
+//
+//export gnark_bls_check_public_key
+func gnark_bls_check_public_key(pk *C.uint8_t, pk_len C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	if err := bls.checkPublicKey(borrowBytes(pk, pk_len)); err != nil {
+		return cError(err)
+	}
+	return nil
+}
+
+// gnark_bls_check_signature fails unless sig is a compressed G2 point in the
+// prime-order subgroup.
+//
+//export gnark_bls_check_signature
+func gnark_bls_check_signature(sig *C.uint8_t, sig_len C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	if err := bls.checkSignature(borrowBytes(sig, sig_len)); err != nil {
+		return cError(err)
+	}
+	return nil
+}
+
+// gnark_bls_aggregate_public_keys adds n concatenated 48-byte public keys.
+//
+//export gnark_bls_aggregate_public_keys
+func gnark_bls_aggregate_public_keys(pks *C.uint8_t, n C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	agg, err := bls.aggregatePublicKeys(splitFixed(pks, n, blsPublicKeySize))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(agg, out_data, out_len)
+}
+
+// gnark_bls_aggregate_signatures adds n concatenated 96-byte signatures.
+//
+//export gnark_bls_aggregate_signatures
+func gnark_bls_aggregate_signatures(sigs *C.uint8_t, n C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	agg, err := bls.aggregateSignatures(splitFixed(sigs, n, blsSignatureSize))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(agg, out_data, out_len)
+}
+
+// gnark_bls_verify checks sig against n (public key, message) pairs: pks
+// holds n concatenated 48-byte keys, msgs the concatenated messages and
+// msg_lens their n lengths. A malformed signature is reported as invalid;
+// a malformed public key is an error.
+//
+//export gnark_bls_verify
+func gnark_bls_verify(
+	pks *C.uint8_t, n C.uint64_t,
+	msgs *C.uint8_t, msgs_len C.uint64_t,
+	msg_lens *C.uint64_t,
+	sig *C.uint8_t, sig_len C.uint64_t,
+	dst *C.uint8_t, dst_len C.uint64_t,
+	out_valid *C.int,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	bls, err := blsImpl()
+	if err != nil {
+		return cError(err)
+	}
+	flat := borrowBytes(msgs, msgs_len)
+	var lens []C.uint64_t
+	if n > 0 {
+		lens = unsafe.Slice(msg_lens, int(n))
+	}
+	messages := make([][]byte, int(n))
+	offset := uint64(0)
+	for i, l := range lens {
+		end := offset + uint64(l)
+		if end < offset || end > uint64(len(flat)) {
+			return cErrorf(errInvalidInput, "message %d overruns the %d message bytes", i, len(flat))
+		}
+		messages[i] = flat[offset:end]
+		offset = end
+	}
+	if offset != uint64(len(flat)) {
+		return cErrorf(errInvalidInput, "message lengths sum to %d, not %d", offset, len(flat))
+	}
+	ok, err := bls.verify(splitFixed(pks, n, blsPublicKeySize), messages, borrowBytes(sig, sig_len), borrowBytes(dst, dst_len))
+	if err != nil {
+		return cError(err)
+	}
+	*out_valid = 0
+	if ok {
+		*out_valid = 1
+	}
+	return nil
+}
@@ -0,0 +1,185 @@
+//go:build !no_bls12_381 && !verifier
+
+package main
+
+import (
+	"crypto/sha256"
+	"io"
+	"math/big"
+
+	bls12381 "github.com/consensys/gnark-crypto/ecc/bls12-381"
+	bls12381fr "github.com/consensys/gnark-crypto/ecc/bls12-381/fr"
+	"golang.org/x/crypto/hkdf"
+)
+
+// blsMinPK implements blsScheme with gnark-crypto's BLS12-381 arithmetic.
+type blsMinPK struct{}
+
+var blsSignatures blsScheme = blsMinPK{}
+
+func (blsMinPK) keyGen(ikm []byte) ([]byte, error) {
+	if len(ikm) < 32 {
+		return nil, errorf(errInvalidInput, "BLS key material must be at least 32 bytes, got %d", len(ikm))
+	}
+	// KeyGen with empty key_info and L = 48, rehashing the salt until the
+	// key is non-zero.
+	salt := []byte("BLS-SIG-KEYGEN-SALT-")
+	secret := append(append([]byte{}, ikm...), 0)
+	okm := make([]byte, 48)
+	sk := new(big.Int)
+	for sk.Sign() == 0 {
+		h := sha256.Sum256(salt)
+		salt = h[:]
+		prk := hkdf.Extract(sha256.New, secret, salt)
+		if _, err := io.ReadFull(hkdf.Expand(sha256.New, prk, []byte{0, 48}), okm); err != nil {
+			return nil, errorf(errOther, "BLS key derivation failed: %w", err)
+		}
+		sk.SetBytes(okm).Mod(sk, bls12381fr.Modulus())
+	}
+	out := make([]byte, blsSecretKeySize)
+	sk.FillBytes(out)
+	return out, nil
+}
+
+func blsSecretKey(b []byte) (*big.Int, error) {
+	if len(b) != blsSecretKeySize {
+		return nil, errorf(errInvalidKey, "BLS secret key must be %d bytes, got %d", blsSecretKeySize, len(b))
+	}
+	sk := new(big.Int).SetBytes(b)
+	if sk.Sign() == 0 || sk.Cmp(bls12381fr.Modulus()) >= 0 {
+		return nil, errorf(errInvalidKey, "BLS secret key is not in [1, r)")
+	}
+	return sk, nil
+}
+
+func blsPublicKeyPoint(b []byte) (*bls12381.G1Affine, error) {
+	var p bls12381.G1Affine
+	if len(b) != blsPublicKeySize {
+		return nil, errorf(errInvalidKey, "BLS public key must be %d bytes, got %d", blsPublicKeySize, len(b))
+	}
+	// SetBytes checks subgroup membership.
+	if _, err := p.SetBytes(b); err != nil {
+		return nil, errorf(errInvalidKey, "invalid BLS public key: %w", err)
+	}
+	if p.IsInfinity() {
+		return nil, errorf(errInvalidKey, "BLS public key is the identity")
+	}
+	return &p, nil
+}
+
+func blsSignaturePoint(b []byte) (*bls12381.G2Affine, error) {
+	var p bls12381.G2Affine
+	if len(b) != blsSignatureSize {
+		return nil, errorf(errInvalidInput, "BLS signature must be %d bytes, got %d", blsSignatureSize, len(b))
+	}
+	if _, err := p.SetBytes(b); err != nil {
+		return nil, errorf(errInvalidInput, "invalid BLS signature: %w", err)
+	}
+	return &p, nil
+}
+
+func (blsMinPK) publicKey(b []byte) ([]byte, error) {
+	sk, err := blsSecretKey(b)
+	if err != nil {
+		return nil, err
+	}
+	var pk bls12381.G1Affine
+	pk.ScalarMultiplicationBase(sk)
+	out := pk.Bytes()
+	return out[:], nil
+}
+
+func (blsMinPK) sign(b, msg, dst []byte) ([]byte, error) {
+	sk, err := blsSecretKey(b)
+	if err != nil {
+		return nil, err
+	}
+	h, err := bls12381.HashToG2(msg, dst)
+	if err != nil {
+		return nil, errorf(errInvalidInput, "failed to hash message to G2: %w", err)
+	}
+	var sig bls12381.G2Affine
+	sig.ScalarMultiplication(&h, sk)
+	out := sig.Bytes()
+	return out[:], nil
+}
+
+func (blsMinPK) checkPublicKey(pk []byte) error {
+	_, err := blsPublicKeyPoint(pk)
+	return err
+}
+
+func (blsMinPK) checkSignature(sig []byte) error {
+	_, err := blsSignaturePoint(sig)
+	return err
+}
+
+func (blsMinPK) aggregatePublicKeys(pks [][]byte) ([]byte, error) {
+	if len(pks) == 0 {
+		return nil, errorf(errInvalidInput, "no public keys to aggregate")
+	}
+	var acc bls12381.G1Jac
+	for i, b := range pks {
+		p, err := blsPublicKeyPoint(b)
+		if err != nil {
+			return nil, errorf(errInvalidKey, "public key %d: %w", i, err)
+		}
+		acc.AddMixed(p)
+	}
+	var agg bls12381.G1Affine
+	agg.FromJacobian(&acc)
+	if agg.IsInfinity() {
+		return nil, errorf(errInvalidKey, "aggregate public key is the identity")
+	}
+	out := agg.Bytes()
+	return out[:], nil
+}
+
+func (blsMinPK) aggregateSignatures(sigs [][]byte) ([]byte, error) {
+	if len(sigs) == 0 {
+		return nil, errorf(errInvalidInput, "no signatures to aggregate")
+	}
+	var acc bls12381.G2Jac
+	for i, b := range sigs {
+		p, err := blsSignaturePoint(b)
+		if err != nil {
+			return nil, errorf(errInvalidInput, "signature %d: %w", i, err)
+		}
+		acc.AddMixed(p)
+	}
+	var agg bls12381.G2Affine
+	agg.FromJacobian(&acc)
+	out := agg.Bytes()
+	return out[:], nil
+}
+
+func (blsMinPK) verify(pks, msgs [][]byte, sigBytes, dst []byte) (bool, error) {
+	if len(pks) == 0 {
+		return false, errorf(errInvalidInput, "no public keys to verify against")
+	}
+	sig, err := blsSignaturePoint(sigBytes)
+	if err != nil {
+		return false, nil
+	}
+	// e(pk_1, H(m_1)) · … · e(pk_n, H(m_n)) · e(-g1, sig) = 1
+	ps := make([]bls12381.G1Affine, 0, len(pks)+1)
+	qs := make([]bls12381.G2Affine, 0, len(pks)+1)
+	for i := range pks {
+		pk, err := blsPublicKeyPoint(pks[i])
+		if err != nil {
+			return false, errorf(errInvalidKey, "public key %d: %w", i, err)
+		}
+		h, err := bls12381.HashToG2(msgs[i], dst)
+		if err != nil {
+			return false, errorf(errInvalidInput, "failed to hash message %d to G2: %w", i, err)
+		}
+		ps = append(ps, *pk)
+		qs = append(qs, h)
+	}
+	_, _, g1, _ := bls12381.Generators()
+	var negG1 bls12381.G1Affine
+	negG1.Neg(&g1)
+	ps = append(ps, negG1)
+	qs = append(qs, *sig)
+	return bls12381.PairingCheck(ps, qs)
+}
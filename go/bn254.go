@@ -0,0 +1,40 @@
+package main
+
+import (
+	"github.com/consensys/gnark/backend"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/backend/witness"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+)
+
+// The prover core uses gnark's BN254 packages directly instead of the
+// curve-generic backend/groth16 API, which dispatches over (and so links) the
+// prover of every curve gnark supports. Other curves are only linked in by the
+// optional features that need them (see curve_bls12_381.go, aggregate.go).
+
+// provingKey is a BN254 Groth16 proving key: *groth16_bn254.ProvingKey, or
+// ICICLE's device-aware variant of it in GPU builds. Keys are created with
+// newProvingKey and used by proveBN254 (gpu_cpu.go / gpu_icicle.go).
+type provingKey interface {
+	gnarkEncoder
+	gnarkDecoder
+}
+
+// readR1CS reads a BN254 R1CS from path.
+func readR1CS(path string) (*cs_bn254.R1CS, error) {
+	r1cs := new(cs_bn254.R1CS)
+	if err := readFile(path, r1cs.ReadFrom); err != nil {
+		return nil, errorf(errSerialization, "failed to read r1cs: %w", err)
+	}
+	return r1cs, nil
+}
+
+// verifyBN254 verifies proof against vk and a public witness.
+func verifyBN254(proof *groth16_bn254.Proof, vk *groth16_bn254.VerifyingKey, public witness.Witness, opts ...backend.VerifierOption) error {
+	vector, ok := public.Vector().(fr.Vector)
+	if !ok {
+		return errorf(errCurveMismatch, "public witness is not over BN254")
+	}
+	return groth16_bn254.Verify(proof, vk, vector, opts...)
+}
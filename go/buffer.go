@@ -0,0 +1,77 @@
+package main
+
+/*
+#include <stdint.h>
+#include <stdlib.h>
+*/
+import "C"
+
+import (
+	"errors"
+	"unsafe"
+)
+
+// Byte buffers are handed to C as (data, len) out-parameters pointing into
+// C.malloc'ed memory. Ownership passes to the caller, who must release the
+// memory with gnark_free_buffer.
+
+// cBuffer is an io.Writer that appends directly into C memory, so serializing
+// a large object costs a single copy instead of Go buffer + C.CBytes.
+type cBuffer struct {
+	data unsafe.Pointer
+	len  uint64
+	cap  uint64
+}
+
+func (b *cBuffer) Write(p []byte) (int, error) {
+	need := b.len + uint64(len(p))
+	if need > b.cap {
+		newCap := max(need, 2*b.cap, 4096)
+		if uint64(C.size_t(newCap)) != newCap {
+			// More than a 32-bit address space can hold.
+			return 0, errOutOfMemoryAlloc
+		}
+		data := C.realloc(b.data, C.size_t(newCap))
+		if data == nil {
+			return 0, errOutOfMemoryAlloc
+		}
+		b.data = data
+		b.cap = newCap
+	}
+	copy(unsafe.Slice((*byte)(b.data), b.cap)[b.len:need], p)
+	b.len = need
+	return len(p), nil
+}
+
+// release transfers ownership of the buffer to the caller.
+func (b *cBuffer) release(out_data **C.uint8_t, out_len *C.uint64_t) {
+	*out_data = (*C.uint8_t)(b.data)
+	*out_len = C.uint64_t(b.len)
+	b.data = nil
+	b.len = 0
+	b.cap = 0
+}
+
+// free releases the buffer if it was not handed over with release.
+func (b *cBuffer) free() {
+	if b.data != nil {
+		C.free(b.data)
+		b.data = nil
+	}
+}
+
+// borrowBytes views C memory as a Go slice without copying. The slice must not
+// be retained after the FFI call returns.
+func borrowBytes(data *C.uint8_t, length C.uint64_t) []byte {
+	if data == nil || length == 0 {
+		return nil
+	}
+	return unsafe.Slice((*byte)(unsafe.Pointer(data)), int(length))
+}
+
+//export gnark_free_buffer
+func gnark_free_buffer(data *C.uint8_t) {
+	if data != nil {
+		C.free(unsafe.Pointer(data))
+	}
+}
@@ -0,0 +1,59 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"time"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	bn254fr "github.com/consensys/gnark-crypto/ecc/bn254/fr"
+)
+
+// maxBenchmarkMSM bounds gnark_msm_benchmark's size, which allocates n points.
+const maxBenchmarkMSM = 1 << 20
+
+// gnark_msm_benchmark times one BN254 G1 multi-scalar multiplication of n
+// random points with GOMAXPROCS and the MSM's task count set to threads, the
+// operation that dominates Groth16 proving, and writes the nanoseconds to
+// out_nanos. gnark-crypto picks the bucket window for n itself.
+//
+//export gnark_msm_benchmark
+func gnark_msm_benchmark(n C.uint64_t, threads C.int, out_nanos *C.int64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if n == 0 || n > maxBenchmarkMSM {
+		return cErrorf(errInvalidInput, "MSM benchmark size %d is not in [1, %d]", uint64(n), maxBenchmarkMSM)
+	}
+	if threads <= 0 {
+		return cErrorf(errInvalidInput, "MSM benchmark needs a positive thread count, got %d", int(threads))
+	}
+
+	// The cost of an MSM does not depend on the points, only on their number.
+	bases := make([]bn254fr.Element, n)
+	scalars := make([]bn254fr.Element, n)
+	for i := range scalars {
+		if _, err := bases[i].SetRandom(); err != nil {
+			return cErrorf(errOther, "failed to draw random scalars: %w", err)
+		}
+		if _, err := scalars[i].SetRandom(); err != nil {
+			return cErrorf(errOther, "failed to draw random scalars: %w", err)
+		}
+	}
+	_, _, g1, _ := bn254.Generators()
+	points := bn254.BatchScalarMultiplicationG1(&g1, bases)
+
+	restore := applyRuntimeLimits(int(threads), 0)
+	defer restore()
+	var res bn254.G1Jac
+	start := time.Now()
+	if _, err := res.MultiExp(points, scalars, ecc.MultiExpConfig{NbTasks: int(threads)}); err != nil {
+		return cErrorf(errOther, "MSM failed: %w", err)
+	}
+	*out_nanos = C.int64_t(time.Since(start).Nanoseconds())
+	return nil
+}
@@ -0,0 +1,99 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"sync"
+)
+
+// Cancellation tokens are Go contexts kept in a registry keyed by an opaque id,
+// since Go pointers cannot be handed across the C boundary. Id 0 is reserved
+// for "no token" and maps to context.Background().
+var (
+	cancelMu     sync.Mutex
+	cancelNextID uint64
+	cancelTokens = map[uint64]cancelToken{}
+)
+
+type cancelToken struct {
+	ctx    context.Context
+	cancel context.CancelFunc
+}
+
+//export gnark_cancel_token_new
+func gnark_cancel_token_new() C.uint64_t {
+	ctx, cancel := context.WithCancel(context.Background())
+
+	cancelMu.Lock()
+	defer cancelMu.Unlock()
+	cancelNextID++
+	cancelTokens[cancelNextID] = cancelToken{ctx: ctx, cancel: cancel}
+	return C.uint64_t(cancelNextID)
+}
+
+//export gnark_cancel
+func gnark_cancel(id C.uint64_t) {
+	cancelMu.Lock()
+	t, ok := cancelTokens[uint64(id)]
+	cancelMu.Unlock()
+	if ok {
+		t.cancel()
+	}
+}
+
+//export gnark_cancel_token_free
+func gnark_cancel_token_free(id C.uint64_t) {
+	cancelMu.Lock()
+	t, ok := cancelTokens[uint64(id)]
+	delete(cancelTokens, uint64(id))
+	cancelMu.Unlock()
+	if ok {
+		// Releases the context's resources; a no-op if already cancelled.
+		t.cancel()
+	}
+}
+
+// contextFor returns the context registered under id, or context.Background()
+// for id 0 and ids that have already been freed.
+func contextFor(id C.uint64_t) context.Context {
+	if id == 0 {
+		return context.Background()
+	}
+	cancelMu.Lock()
+	defer cancelMu.Unlock()
+	if t, ok := cancelTokens[uint64(id)]; ok {
+		return t.ctx
+	}
+	return context.Background()
+}
+
+// cancelledError returns a non-nil error once ctx has been cancelled.
+// It wraps ctx.Err(), which cError maps to errCancelled.
+func cancelledError(ctx context.Context) error {
+	if err := ctx.Err(); err != nil {
+		return fmt.Errorf("cancelled: %w", err)
+	}
+	return nil
+}
+
+// ctxReader fails reads once ctx is cancelled, so deserializing a large proving
+// key stops part-way instead of running to completion.
+type ctxReader struct {
+	ctx context.Context
+	r   io.Reader
+}
+
+func (c ctxReader) Read(p []byte) (int, error) {
+	if err := cancelledError(c.ctx); err != nil {
+		return 0, err
+	}
+	return c.r.Read(p)
+}
@@ -0,0 +1,243 @@
+//go:build !no_ceremony && !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"io"
+	"unsafe"
+
+	"github.com/consensys/gnark/backend/groth16/bn254/mpcsetup"
+)
+
+// Groth16 BN254 trusted-setup ceremonies (gnark's mpcsetup): phase 1 builds
+// circuit-independent powers of tau, phase 2 specializes them to one R1CS.
+// Each participant deserializes the previous state, contributes, and passes
+// the serialized result on; the coordinator verifies the chain and finalizes
+// it with a public random beacon.
+//
+// Building with the no_ceremony tag leaves ceremonies out: mpcsetup returns
+// keys through gnark's curve-generic Groth16 API, which links every curve.
+
+// Kinds for gnark_mpc_from_bytes; keep in sync with the Rust ceremony module.
+const (
+	mpcPhase1 = iota
+	mpcPhase2
+	mpcSrsCommons
+)
+
+type mpcObject interface {
+	io.WriterTo
+	io.ReaderFrom
+}
+
+// gnark_mpc_phase1_new creates the initial phase 1 state for circuits of up to
+// n constraints (n a power of two).
+//
+//export gnark_mpc_phase1_new
+func gnark_mpc_phase1_new(n C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if n == 0 || n&(n-1) != 0 {
+		return cErrorf(errInvalidInput, "phase 1 size must be a power of two, got %d", uint64(n))
+	}
+	p := new(mpcsetup.Phase1)
+	p.Initialize(uint64(n))
+	*out_handle = newHandle(p)
+	return nil
+}
+
+// gnark_mpc_phase2_new creates the initial phase 2 state for the R1CS at
+// r1cs_path from finalized phase 1 commons.
+//
+//export gnark_mpc_phase2_new
+func gnark_mpc_phase2_new(r1cs_path *C.char, commons_handle C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	commons, err := lookupHandle[*mpcsetup.SrsCommons](commons_handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs commons handle: %w", err)
+	}
+	r1cs, err := readR1CS(C.GoString(r1cs_path))
+	if err != nil {
+		return cError(err)
+	}
+	p := new(mpcsetup.Phase2)
+	p.Initialize(r1cs, commons)
+	*out_handle = newHandle(p)
+	return nil
+}
+
+// gnark_mpc_contribute adds a fresh random contribution to a phase 1 or
+// phase 2 state in place.
+//
+//export gnark_mpc_contribute
+func gnark_mpc_contribute(handle C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	v, err := lookupHandle[any](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid ceremony handle: %w", err)
+	}
+	switch p := v.(type) {
+	case *mpcsetup.Phase1:
+		p.Contribute()
+	case *mpcsetup.Phase2:
+		p.Contribute()
+	default:
+		return cErrorf(errInvalidInput, "handle %d holds %T, not a ceremony phase", uint64(handle), v)
+	}
+	return nil
+}
+
+// gnark_mpc_verify checks that next is a valid contribution on top of prev
+// (both phase 1 or both phase 2).
+//
+//export gnark_mpc_verify
+func gnark_mpc_verify(prev_handle C.uint64_t, next_handle C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	prev, err := lookupHandle[any](prev_handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid ceremony handle: %w", err)
+	}
+	var verr error
+	switch p := prev.(type) {
+	case *mpcsetup.Phase1:
+		next, err := lookupHandle[*mpcsetup.Phase1](next_handle)
+		if err != nil {
+			return cErrorf(errInvalidInput, "invalid ceremony handle: %w", err)
+		}
+		verr = p.Verify(next)
+	case *mpcsetup.Phase2:
+		next, err := lookupHandle[*mpcsetup.Phase2](next_handle)
+		if err != nil {
+			return cErrorf(errInvalidInput, "invalid ceremony handle: %w", err)
+		}
+		verr = p.Verify(next)
+	default:
+		return cErrorf(errInvalidInput, "handle %d holds %T, not a ceremony phase", uint64(prev_handle), prev)
+	}
+	if verr != nil {
+		return cErrorf(errInvalidProof, "invalid contribution: %w", verr)
+	}
+	return nil
+}
+
+// gnark_mpc_to_bytes serializes a phase 1, phase 2 or commons object into a C
+// buffer that the caller frees with gnark_free_buffer.
+//
+//export gnark_mpc_to_bytes
+func gnark_mpc_to_bytes(handle C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	obj, err := lookupHandle[mpcObject](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid ceremony handle: %w", err)
+	}
+	var buf cBuffer
+	if _, err := obj.WriteTo(&buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize ceremony state: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_mpc_from_bytes deserializes an object of the given kind.
+//
+//export gnark_mpc_from_bytes
+func gnark_mpc_from_bytes(kind C.int, data *C.uint8_t, length C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	var obj mpcObject
+	switch kind {
+	case mpcPhase1:
+		obj = new(mpcsetup.Phase1)
+	case mpcPhase2:
+		obj = new(mpcsetup.Phase2)
+	case mpcSrsCommons:
+		obj = new(mpcsetup.SrsCommons)
+	default:
+		return cErrorf(errInvalidInput, "unknown ceremony object kind %d", int(kind))
+	}
+	if _, err := obj.ReadFrom(bytes.NewReader(borrowBytes(data, length))); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize ceremony state: %w", err)
+	}
+	*out_handle = newHandle(obj)
+	return nil
+}
+
+// lookupPhases resolves n handles of type T.
+func lookupPhases[T any](handles *C.uint64_t, n C.uint32_t) ([]T, error) {
+	ids := unsafe.Slice(handles, int(n))
+	out := make([]T, len(ids))
+	for i, id := range ids {
+		p, err := lookupHandle[T](id)
+		if err != nil {
+			return nil, errorf(errInvalidInput, "contribution %d: %w", i, err)
+		}
+		out[i] = p
+	}
+	return out, nil
+}
+
+// gnark_mpc_phase1_finalize verifies the n phase 1 contributions (in order,
+// excluding the initial state) for size size and seals them with the beacon.
+//
+//export gnark_mpc_phase1_finalize
+func gnark_mpc_phase1_finalize(
+	size C.uint64_t,
+	beacon *C.uint8_t, beacon_len C.uint64_t,
+	contributions *C.uint64_t, n C.uint32_t,
+	out_handle *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	phases, err := lookupPhases[*mpcsetup.Phase1](contributions, n)
+	if err != nil {
+		return cError(err)
+	}
+	commons, err := mpcsetup.VerifyPhase1(uint64(size), bytes.Clone(borrowBytes(beacon, beacon_len)), phases...)
+	if err != nil {
+		return cErrorf(errInvalidProof, "phase 1 verification failed: %w", err)
+	}
+	*out_handle = newHandle(&commons)
+	return nil
+}
+
+// gnark_mpc_phase2_finalize verifies the n phase 2 contributions for the R1CS
+// at r1cs_path, seals them with the beacon and writes the resulting proving
+// key (raw format) and verifying key.
+//
+//export gnark_mpc_phase2_finalize
+func gnark_mpc_phase2_finalize(
+	r1cs_path *C.char,
+	commons_handle C.uint64_t,
+	beacon *C.uint8_t, beacon_len C.uint64_t,
+	contributions *C.uint64_t, n C.uint32_t,
+	pk_path *C.char, vk_path *C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	commons, err := lookupHandle[*mpcsetup.SrsCommons](commons_handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs commons handle: %w", err)
+	}
+	r1cs, err := readR1CS(C.GoString(r1cs_path))
+	if err != nil {
+		return cError(err)
+	}
+	phases, err := lookupPhases[*mpcsetup.Phase2](contributions, n)
+	if err != nil {
+		return cError(err)
+	}
+	pk, vk, err := mpcsetup.VerifyPhase2(r1cs, commons, bytes.Clone(borrowBytes(beacon, beacon_len)), phases...)
+	if err != nil {
+		return cErrorf(errInvalidProof, "phase 2 verification failed: %w", err)
+	}
+	if err := writeFile(C.GoString(pk_path), pk.WriteRawTo); err != nil {
+		return cErrorf(errIO, "failed to write proving key: %w", err)
+	}
+	if err := writeFile(C.GoString(vk_path), vk.WriteTo); err != nil {
+		return cErrorf(errIO, "failed to write verifying key: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,166 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark/backend/witness"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+)
+
+// Witness checking without proving: run the solver and, if a constraint fails,
+// describe it in terms of the circuit's wires.
+
+// constraintViolation describes the first constraint a witness fails.
+type constraintViolation struct {
+	index int
+	// constraint is the symbolic form, with input wires named.
+	constraint string
+	// detail is the solver's evaluated form, e.g. "9 ⋅ 3 != 35".
+	detail string
+	// inputs are the circuit inputs the constraint reads, as "name=value".
+	inputs []string
+}
+
+// checkWitness solves ccs for w. It returns nil if every constraint holds and
+// an error only if solving fails for another reason (e.g. a hint error).
+func checkWitness(ccs constraint.ConstraintSystem, full witness.Witness) (*constraintViolation, error) {
+	w, ok := full.Vector().(fr.Vector)
+	if !ok {
+		return nil, errorf(errCurveMismatch, "expected a BN254 witness, got %T", full.Vector())
+	}
+	err := ccs.IsSolved(full)
+	if err == nil {
+		return nil, nil
+	}
+	msg := err.Error()
+	m := unsatisfiedConstraint.FindStringSubmatchIndex(msg)
+	if m == nil {
+		return nil, errorf(errInvalidWitness, "failed to solve witness: %w", err)
+	}
+	index, _ := strconv.Atoi(msg[m[2]:m[3]])
+	v := &constraintViolation{
+		index:  index,
+		detail: strings.TrimPrefix(msg[m[1]:], ": "),
+	}
+
+	sys, err := systemOf(ccs)
+	if err != nil {
+		return nil, err
+	}
+	// Wires below nbInputs are inputs; an R1CS's wire 0 is the constant "1",
+	// which the witness vector does not contain.
+	nbInputs := len(sys.Public) + len(sys.Secret)
+	offset := 0
+	var wires []int
+	switch cs := ccs.(type) {
+	case *cs_bn254.R1CS:
+		offset = 1
+		r1cs := cs.GetR1Cs()
+		if index >= len(r1cs) {
+			return v, nil
+		}
+		c := r1cs[index]
+		v.constraint = c.String(cs)
+		for _, l := range []constraint.LinearExpression{c.L, c.R, c.O} {
+			for _, t := range l {
+				wires = append(wires, t.WireID())
+			}
+		}
+	case *cs_bn254.SparseR1CS:
+		scs := cs.GetSparseR1Cs()
+		if index >= len(scs) {
+			return v, nil
+		}
+		c := scs[index]
+		v.constraint = c.String(cs)
+		wires = []int{int(c.XA), int(c.XB), int(c.XC)}
+	}
+
+	seen := map[int]bool{}
+	for _, id := range wires {
+		if id < offset || id >= nbInputs || seen[id] {
+			continue
+		}
+		seen[id] = true
+		v.inputs = append(v.inputs, fmt.Sprintf("%s=%s", sys.VariableToString(id), w[id-offset].String()))
+	}
+	return v, nil
+}
+
+// gnark_circuit_read loads the BN254 R1CS at r1cs_path as a circuit handle.
+//
+//export gnark_circuit_read
+func gnark_circuit_read(r1cs_path *C.char, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	r1cs, err := readR1CS(C.GoString(r1cs_path))
+	if err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(constraint.ConstraintSystem(r1cs))
+	return nil
+}
+
+// gnark_circuit_read_sparse loads the BN254 SparseR1CS at path, as written by
+// gnark_circuit_write, as a circuit handle.
+//
+//export gnark_circuit_read_sparse
+func gnark_circuit_read_sparse(path *C.char, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	spr := new(cs_bn254.SparseR1CS)
+	if err := readFile(C.GoString(path), spr.ReadFrom); err != nil {
+		return cErrorf(errSerialization, "failed to read sparse r1cs: %w", err)
+	}
+	*out_handle = newHandle(constraint.ConstraintSystem(spr))
+	return nil
+}
+
+// gnark_circuit_check_witness checks a JSON witness (as for gnark_groth16_prove)
+// against a circuit without proving. If every constraint holds, *out_index is
+// set to -1. Otherwise *out_index is the first failing constraint and
+// *out_constraint, *out_detail and *out_inputs (unit-separator joined
+// "name=value" pairs) describe it; the caller frees them with
+// gnark_free_string.
+//
+//export gnark_circuit_check_witness
+func gnark_circuit_check_witness(
+	handle C.uint64_t,
+	witness_json *C.char,
+	out_index *C.int64_t,
+	out_constraint **C.char,
+	out_detail **C.char,
+	out_inputs **C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	w, err := buildWitnessFromJSON(C.GoString(witness_json), ccs)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+	}
+	v, err := checkWitness(ccs, w)
+	if err != nil {
+		return cError(err)
+	}
+	if v == nil {
+		*out_index = -1
+		return nil
+	}
+	*out_index = C.int64_t(v.index)
+	*out_constraint = C.CString(v.constraint)
+	*out_detail = C.CString(v.detail)
+	*out_inputs = C.CString(strings.Join(v.inputs, "\x1f"))
+	return nil
+}
@@ -0,0 +1,411 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"math/big"
+	"sync"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/witness"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark/constraint/solver"
+)
+
+// Checkpointed proving: gnark_prover_session_solve solves a witness ahead of
+// the proof and serializes what the prover needs to redo it cheaply, so an
+// app that is killed mid-proof resumes from the solved witness.
+//
+// gnark's prover solves the constraint system itself and runs the MSMs in
+// the same call, so neither the solution nor the MSM phases can be handed to
+// it. What it can be handed is every hint's outputs: replaying them skips the
+// hints (which include witness feeders, Rust hints and the gadgets' big
+// computations), leaving the prover only the linear part of the solve.
+
+// solvedMagic starts a serialized solved witness; the last byte is the
+// format version.
+var solvedMagic = [4]byte{'G', 'S', 'W', 1}
+
+// hintKey identifies a hint call by its hint and inputs.
+type hintKey [sha256.Size]byte
+
+func newHintKey(id solver.HintID, inputs []*big.Int) hintKey {
+	h := sha256.New()
+	var buf [8]byte
+	binary.BigEndian.PutUint32(buf[:4], uint32(id))
+	binary.BigEndian.PutUint32(buf[4:], uint32(len(inputs)))
+	h.Write(buf[:])
+	for _, in := range inputs {
+		writeBigInt(h, in)
+	}
+	var key hintKey
+	h.Sum(key[:0])
+	return key
+}
+
+// hintLog records the outputs of every hint call of a solve, or replays them
+// into another solve of the same witness. A replayed call that was not
+// recorded runs the hint.
+//
+// Calls are told apart by their inputs, since the solver runs the hints of a
+// level in parallel and so in no fixed order.
+type hintLog struct {
+	replay bool
+
+	mu      sync.Mutex
+	outputs map[hintKey][]*big.Int
+}
+
+func (l *hintLog) wrap(id solver.HintID, h solver.Hint) solver.Hint {
+	return func(field *big.Int, inputs, outputs []*big.Int) error {
+		key := newHintKey(id, inputs)
+		if l.replay {
+			if recorded, ok := l.outputs[key]; ok && len(recorded) == len(outputs) {
+				for i := range outputs {
+					outputs[i].Set(recorded[i])
+				}
+				return nil
+			}
+			return h(field, inputs, outputs)
+		}
+		if err := h(field, inputs, outputs); err != nil {
+			return err
+		}
+		recorded := make([]*big.Int, len(outputs))
+		for i, out := range outputs {
+			recorded[i] = new(big.Int).Set(out)
+		}
+		l.mu.Lock()
+		defer l.mu.Unlock()
+		if l.outputs == nil {
+			l.outputs = map[hintKey][]*big.Int{}
+		}
+		l.outputs[key] = recorded
+		return nil
+	}
+}
+
+// solvedWitness is a full witness and the hint outputs of solving it.
+type solvedWitness struct {
+	public, secret int
+	constraints    int
+	witness        []byte
+	log            *hintLog
+}
+
+func writeBigInt(w io.Writer, x *big.Int) {
+	var head [5]byte
+	if x.Sign() < 0 {
+		head[0] = 1
+	}
+	b := x.Bytes()
+	binary.BigEndian.PutUint32(head[1:], uint32(len(b)))
+	w.Write(head[:])
+	w.Write(b)
+}
+
+func readBigInt(r *bytes.Reader) (*big.Int, error) {
+	var head [5]byte
+	if _, err := io.ReadFull(r, head[:]); err != nil {
+		return nil, err
+	}
+	n := binary.BigEndian.Uint32(head[1:])
+	if int64(n) > int64(r.Len()) {
+		return nil, io.ErrUnexpectedEOF
+	}
+	b := make([]byte, n)
+	io.ReadFull(r, b)
+	x := new(big.Int).SetBytes(b)
+	if head[0] != 0 {
+		x.Neg(x)
+	}
+	return x, nil
+}
+
+// encode serializes s as: magic, the circuit's public, secret and constraint
+// counts (u32, u32, u64), the witness's length (u64) and gnark binary
+// encoding, then the number of hint calls (u64) and for each its key, output
+// count (u32) and outputs (sign byte, u32 length, big-endian magnitude).
+func (s *solvedWitness) encode(w io.Writer) error {
+	var b bytes.Buffer
+	b.Write(solvedMagic[:])
+	binary.Write(&b, binary.BigEndian, uint32(s.public))
+	binary.Write(&b, binary.BigEndian, uint32(s.secret))
+	binary.Write(&b, binary.BigEndian, uint64(s.constraints))
+	binary.Write(&b, binary.BigEndian, uint64(len(s.witness)))
+	b.Write(s.witness)
+	binary.Write(&b, binary.BigEndian, uint64(len(s.log.outputs)))
+	for key, outputs := range s.log.outputs {
+		b.Write(key[:])
+		binary.Write(&b, binary.BigEndian, uint32(len(outputs)))
+		for _, out := range outputs {
+			writeBigInt(&b, out)
+		}
+	}
+	_, err := w.Write(b.Bytes())
+	return err
+}
+
+func decodeSolvedWitness(data []byte) (*solvedWitness, error) {
+	r := bytes.NewReader(data)
+	var magic [4]byte
+	if _, err := io.ReadFull(r, magic[:]); err != nil || magic != solvedMagic {
+		return nil, errors.New("not a solved witness, or from another version")
+	}
+	var head struct {
+		Public, Secret uint32
+		Constraints    uint64
+		WitnessLen     uint64
+	}
+	if err := binary.Read(r, binary.BigEndian, &head); err != nil {
+		return nil, err
+	}
+	if head.WitnessLen > uint64(r.Len()) {
+		return nil, io.ErrUnexpectedEOF
+	}
+	s := &solvedWitness{
+		public:      int(head.Public),
+		secret:      int(head.Secret),
+		constraints: int(head.Constraints),
+		witness:     make([]byte, head.WitnessLen),
+		log:         &hintLog{replay: true, outputs: map[hintKey][]*big.Int{}},
+	}
+	io.ReadFull(r, s.witness)
+
+	var calls uint64
+	if err := binary.Read(r, binary.BigEndian, &calls); err != nil {
+		return nil, err
+	}
+	for ; calls > 0; calls-- {
+		var key hintKey
+		var n uint32
+		if _, err := io.ReadFull(r, key[:]); err != nil {
+			return nil, err
+		}
+		if err := binary.Read(r, binary.BigEndian, &n); err != nil {
+			return nil, err
+		}
+		// Every output takes at least its 5-byte header.
+		if uint64(n)*5 > uint64(r.Len()) {
+			return nil, io.ErrUnexpectedEOF
+		}
+		outputs := make([]*big.Int, n)
+		for i := range outputs {
+			out, err := readBigInt(r)
+			if err != nil {
+				return nil, err
+			}
+			outputs[i] = out
+		}
+		s.log.outputs[key] = outputs
+	}
+	if r.Len() != 0 {
+		return nil, fmt.Errorf("%d trailing bytes", r.Len())
+	}
+	return s, nil
+}
+
+// check checks that s was solved for cs.
+func (s *solvedWitness) check(cs constraint.ConstraintSystem) error {
+	if s.public != cs.GetNbPublicVariables() || s.secret != cs.GetNbSecretVariables() ||
+		s.constraints != cs.GetNbConstraints() {
+		return fmt.Errorf("the witness was solved for a circuit with %d public and %d secret inputs and %d constraints, "+
+			"this one has %d, %d and %d", s.public, s.secret, s.constraints,
+			cs.GetNbPublicVariables(), cs.GetNbSecretVariables(), cs.GetNbConstraints())
+	}
+	return nil
+}
+
+// solveTo solves a JSON witness against ccs, recording every hint call, and
+// hands the serialized solved witness to the caller as (out_data, out_len).
+func solveTo(
+	ccs constraint.ConstraintSystem,
+	witnessJSON string,
+	config solverConfig,
+	out_data **C.uint8_t,
+	out_len *C.uint64_t,
+) *C.char {
+	w, err := buildWitnessFromJSON(witnessJSON, ccs)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+	}
+	defer scrubWitness(w)
+	log := new(hintLog)
+	if _, err := ccs.Solve(w, config.options(nil, nil, log)...); err != nil {
+		// Uncoded, so cError reports an unsatisfied constraint with its index.
+		return cError(fmt.Errorf("failed to solve witness: %w", err))
+	}
+	encoded, err := w.MarshalBinary()
+	if err != nil {
+		return cErrorf(errSerialization, "failed to serialize witness: %w", err)
+	}
+	solved := solvedWitness{
+		public:      ccs.GetNbPublicVariables(),
+		secret:      ccs.GetNbSecretVariables(),
+		constraints: ccs.GetNbConstraints(),
+		witness:     encoded,
+		log:         log,
+	}
+	var buf cBuffer
+	if err := solved.encode(&buf); err != nil {
+		buf.free()
+		return cError(err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// proveSolvedInto is proveInto for a serialized solved witness, whose hint
+// outputs are replayed instead of computed again.
+func proveSolvedInto(
+	result *C.C_Groth16ProofResult,
+	o proveOptions,
+	cs *cs_bn254.R1CS,
+	pk provingKey,
+	data []byte,
+) {
+	solved, err := decodeSolvedWitness(data)
+	if err != nil {
+		result.error = cErrorf(errSerialization, "failed to read solved witness: %w", err)
+		return
+	}
+	if err := solved.check(cs); err != nil {
+		result.error = cErrorf(errInvalidWitness, "%w", err)
+		return
+	}
+	fullWitness, err := witness.New(ecc.BN254.ScalarField())
+	if err != nil {
+		result.error = cError(err)
+		return
+	}
+	if err := fullWitness.UnmarshalBinary(solved.witness); err != nil {
+		result.error = cErrorf(errSerialization, "failed to read solved witness: %w", err)
+		return
+	}
+	o.hintLog = solved.log
+	proveWitnessInto(result, o, cs, pk, fullWitness)
+}
+
+// gnark_prover_session_solve solves a JSON witness (as for
+// gnark_groth16_prove) against the session's circuit, recording every hint
+// call, and stores the serialized solved witness in *out_data, which the
+// caller frees with gnark_free_buffer. solver_tasks and hint_timeout_ms are
+// as in C_ProveOptions.
+//
+// The buffer holds the secret inputs and hint outputs in the clear.
+//
+//export gnark_prover_session_solve
+func gnark_prover_session_solve(
+	handle C.uint64_t,
+	witness_json *C.char,
+	solver_tasks C.int,
+	hint_timeout_ms C.int64_t,
+	out_data **C.uint8_t,
+	out_len *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+	}
+	config := solverConfigFrom(solver_tasks, hint_timeout_ms)
+	return solveTo(s.cs, C.GoString(witness_json), config, out_data, out_len)
+}
+
+// gnark_prover_session_prove_solved is gnark_prover_session_prove for a
+// witness serialized by gnark_prover_session_solve or gnark_circuit_solve.
+// The options' feeder is only called for feeds the solved witness did not
+// record.
+//
+//export gnark_prover_session_prove_solved
+func gnark_prover_session_prove_solved(
+	handle C.uint64_t,
+	data *C.uint8_t,
+	length C.uint64_t,
+	opts *C.C_ProveOptions,
+) (result *C.C_Groth16ProofResult) {
+	result = newProofResult()
+	defer catchPanic(&result.error)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		result.error = cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+		return result
+	}
+	proveSolvedInto(result, o, s.cs, s.key(), borrowBytes(data, length))
+	return result
+}
+
+// gnark_circuit_solve is gnark_prover_session_solve for a circuit handle,
+// which needs no proving key.
+//
+//export gnark_circuit_solve
+func gnark_circuit_solve(
+	handle C.uint64_t,
+	witness_json *C.char,
+	solver_tasks C.int,
+	hint_timeout_ms C.int64_t,
+	out_data **C.uint8_t,
+	out_len *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	config := solverConfigFrom(solver_tasks, hint_timeout_ms)
+	return solveTo(ccs, C.GoString(witness_json), config, out_data, out_len)
+}
+
+// gnark_groth16_prove_solved is gnark_prover_session_prove_solved with a
+// circuit handle and a proving key handle, which must be sized for it.
+//
+//export gnark_groth16_prove_solved
+func gnark_groth16_prove_solved(
+	circuit_handle C.uint64_t,
+	pk_handle C.uint64_t,
+	data *C.uint8_t,
+	length C.uint64_t,
+	opts *C.C_ProveOptions,
+) (result *C.C_Groth16ProofResult) {
+	result = newProofResult()
+	defer catchPanic(&result.error)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	ccs, err := lookupHandle[constraint.ConstraintSystem](circuit_handle)
+	if err != nil {
+		result.error = cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+		return result
+	}
+	cs, ok := ccs.(*cs_bn254.R1CS)
+	if !ok {
+		result.error = cErrorf(errInvalidInput, "groth16 proving needs an R1CS, got %T", ccs)
+		return result
+	}
+	pk, err := lookupHandle[provingKey](pk_handle)
+	if err != nil {
+		result.error = cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+		return result
+	}
+	if err := checkProvingKey(cs, pk); err != nil {
+		result.error = cError(err)
+		return result
+	}
+	proveSolvedInto(result, o, cs, pk, borrowBytes(data, length))
+	return result
+}
@@ -0,0 +1,548 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"strings"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark/constraint/solver"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+	"github.com/consensys/gnark/frontend/cs/scs"
+	"github.com/consensys/gnark/std"
+	"github.com/consensys/gnark/std/hash"
+	"github.com/consensys/gnark/std/hash/mimc"
+	gkr_mimc "github.com/consensys/gnark/std/hash/mimc/gkr-mimc"
+	"github.com/consensys/gnark/std/rangecheck"
+)
+
+// Circuits described from Rust: a flat list of operations over numbered
+// variables, replayed against a frontend.API at compile time. Every op that
+// produces values appends them to the variable list in order, so the n-th
+// value produced is variable n on both sides of the FFI.
+
+const circuitDescVersion = 1
+
+type circuitDesc struct {
+	Version int         `json:"version"`
+	Ops     []circuitOp `json:"ops"`
+}
+
+type circuitOp struct {
+	Op      string `json:"op"`
+	Name    string `json:"name,omitempty"`
+	Public  bool   `json:"public,omitempty"`
+	Value   string `json:"value,omitempty"`
+	Args    []int  `json:"args,omitempty"`
+	Hint    string `json:"hint,omitempty"`
+	Outputs int    `json:"outputs,omitempty"`
+	Bits    int    `json:"bits,omitempty"`
+}
+
+// Hints a description can call by name. They are registered with the solver
+// at load time so circuits compiled here can be proven by any entry point.
+var circuitHints = map[string]solver.Hint{
+	"inverse": hintInverse,
+	"div":     hintDiv,
+	"bits":    hintBits,
+}
+
+func init() {
+	for _, h := range circuitHints {
+		solver.RegisterHint(h)
+	}
+	solver.RegisterHint(hintFeed, hintRust)
+	// The hints of gnark's std gadgets, for constraint systems compiled
+	// elsewhere (e.g. by a Go circuit, see go_circuit.rs) with gadgets this
+	// library does not use itself. GKR needs nothing more: the solver
+	// installs its hints from the constraint system, and the Fiat-Shamir
+	// hashes it names are registered by the gnark-crypto packages of
+	// hash.go.
+	std.RegisterHints()
+}
+
+// hintFeed stands in for the witness feeder of feed ops, which is passed per
+// proof (see witnessFeeder in wrapper.go) and overrides it.
+func hintFeed(_ *big.Int, inputs, _ []*big.Int) error {
+	return errorf(errInvalidWitness, "feed %s: the circuit reads values from a witness feeder, but none was given", inputs[0])
+}
+
+// hintInverse returns 1/x, or 0 for x = 0.
+func hintInverse(field *big.Int, inputs, outputs []*big.Int) error {
+	if len(inputs) != 1 || len(outputs) != 1 {
+		return fmt.Errorf("inverse: expected 1 input and 1 output")
+	}
+	if inputs[0].Sign() == 0 {
+		outputs[0].SetUint64(0)
+		return nil
+	}
+	outputs[0].ModInverse(inputs[0], field)
+	return nil
+}
+
+// hintDiv returns a/b, or 0 for b = 0.
+func hintDiv(field *big.Int, inputs, outputs []*big.Int) error {
+	if len(inputs) != 2 || len(outputs) != 1 {
+		return fmt.Errorf("div: expected 2 inputs and 1 output")
+	}
+	if inputs[1].Sign() == 0 {
+		outputs[0].SetUint64(0)
+		return nil
+	}
+	outputs[0].ModInverse(inputs[1], field)
+	outputs[0].Mul(outputs[0], inputs[0]).Mod(outputs[0], field)
+	return nil
+}
+
+// hintBits returns the len(outputs) low bits of x, least significant first.
+func hintBits(_ *big.Int, inputs, outputs []*big.Int) error {
+	if len(inputs) != 1 {
+		return fmt.Errorf("bits: expected 1 input")
+	}
+	for i := range outputs {
+		outputs[i].SetUint64(uint64(inputs[0].Bit(i)))
+	}
+	return nil
+}
+
+// dslCircuit replays a circuitDesc. Inputs are placeholders in declaration
+// order; their names are patched into the compiled system afterwards.
+type dslCircuit struct {
+	Public []frontend.Variable `gnark:",public"`
+	Secret []frontend.Variable `gnark:",secret"`
+	Desc   *circuitDesc        `gnark:"-"`
+}
+
+func (c *dslCircuit) Define(api frontend.API) error {
+	var vars []frontend.Variable
+	arg := func(op circuitOp, i int) (frontend.Variable, error) {
+		if i >= len(op.Args) {
+			return nil, fmt.Errorf("%s: missing argument %d", op.Op, i)
+		}
+		v := op.Args[i]
+		if v < 0 || v >= len(vars) {
+			return nil, fmt.Errorf("%s: unknown variable %d", op.Op, v)
+		}
+		return vars[v], nil
+	}
+	all := func(op circuitOp) ([]frontend.Variable, error) {
+		args := make([]frontend.Variable, len(op.Args))
+		for j := range op.Args {
+			v, err := arg(op, j)
+			if err != nil {
+				return nil, err
+			}
+			args[j] = v
+		}
+		return args, nil
+	}
+	binary := func(op circuitOp) (frontend.Variable, frontend.Variable, error) {
+		a, err := arg(op, 0)
+		if err != nil {
+			return nil, nil, err
+		}
+		b, err := arg(op, 1)
+		return a, b, err
+	}
+
+	// One range checker per circuit, so all checks share a single lookup
+	// table and commitment.
+	var checker frontend.Rangechecker
+	// Likewise one GKR hasher, so all its permutations are instances of one
+	// GKR circuit with a single proof.
+	var gkrHasher hash.FieldHasher
+
+	nbPublic, nbSecret := 0, 0
+	for i, op := range c.Desc.Ops {
+		switch op.Op {
+		case "input":
+			if op.Public {
+				vars = append(vars, c.Public[nbPublic])
+				nbPublic++
+			} else {
+				vars = append(vars, c.Secret[nbSecret])
+				nbSecret++
+			}
+		case "const":
+			v, ok := new(big.Int).SetString(op.Value, 10)
+			if !ok {
+				return fmt.Errorf("op %d: invalid constant %q", i, op.Value)
+			}
+			vars = append(vars, v)
+		case "add", "sub", "mul":
+			a, b, err := binary(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			switch op.Op {
+			case "add":
+				vars = append(vars, api.Add(a, b))
+			case "sub":
+				vars = append(vars, api.Sub(a, b))
+			default:
+				vars = append(vars, api.Mul(a, b))
+			}
+		case "assert_eq":
+			a, b, err := binary(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			api.AssertIsEqual(a, b)
+		case "hint":
+			args, err := all(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			h, ok := circuitHints[op.Hint]
+			if op.Hint == "rust" {
+				// A Rust closure, selected by the id passed first.
+				id, valid := new(big.Int).SetString(op.Value, 10)
+				if !valid {
+					return fmt.Errorf("op %d: invalid Rust hint id %q", i, op.Value)
+				}
+				h, ok = hintRust, true
+				args = append([]frontend.Variable{id}, args...)
+			}
+			if !ok {
+				return fmt.Errorf("op %d: unknown hint %q", i, op.Hint)
+			}
+			outs, err := api.Compiler().NewHint(h, op.Outputs, args...)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			vars = append(vars, outs...)
+		case "feed":
+			// Values supplied by the prover's witness feeder while solving,
+			// keyed by the feed id passed as the hint's only input.
+			id, ok := new(big.Int).SetString(op.Value, 10)
+			if !ok || op.Outputs <= 0 {
+				return fmt.Errorf("op %d: invalid feed", i)
+			}
+			outs, err := api.Compiler().NewHint(hintFeed, op.Outputs, id)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			vars = append(vars, outs...)
+		case "commit":
+			// A Pedersen commitment to the arguments; the output is its
+			// hash, a challenge the prover cannot choose.
+			committer, ok := api.(frontend.Committer)
+			if !ok {
+				return fmt.Errorf("op %d: the constraint system does not support commitments", i)
+			}
+			args, err := all(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			v, err := committer.Commit(args...)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			vars = append(vars, v)
+		case "sha256_fields":
+			// The public-input commitment of publichash.go.
+			args, err := all(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			v, err := sha256Fields(api, args)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			vars = append(vars, v)
+		case "range_check":
+			// gnark's log-derivative lookup argument. It commits to the
+			// checked values: a Pedersen commitment in an R1CS, a BSB22
+			// commitment gate in a SparseR1CS.
+			v, err := arg(op, 0)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			if op.Bits <= 0 {
+				return fmt.Errorf("op %d: range_check needs a positive bit width", i)
+			}
+			if checker == nil {
+				checker = rangecheck.New(api)
+			}
+			checker.Check(v, op.Bits)
+		case "mimc", "mimc_gkr":
+			args, err := all(op)
+			if err != nil {
+				return fmt.Errorf("op %d: %w", i, err)
+			}
+			if len(args) == 0 {
+				return fmt.Errorf("op %d: %s needs at least one value", i, op.Op)
+			}
+			var h hash.FieldHasher
+			if op.Op == "mimc" {
+				m, err := mimc.NewMiMC(api)
+				if err != nil {
+					return fmt.Errorf("op %d: %w", i, err)
+				}
+				h = &m
+			} else {
+				if gkrHasher == nil {
+					if gkrHasher, err = gkr_mimc.New(api); err != nil {
+						return fmt.Errorf("op %d: %w", i, err)
+					}
+				}
+				h = gkrHasher
+				h.Reset()
+			}
+			h.Write(args...)
+			vars = append(vars, h.Sum())
+		default:
+			return fmt.Errorf("op %d: unknown op %q", i, op.Op)
+		}
+	}
+	return nil
+}
+
+// parseCircuitDesc parses a JSON circuit description into a placeholder
+// circuit and its input names, public and secret, in declaration order.
+func parseCircuitDesc(descJSON string) (circuit *dslCircuit, publicNames, secretNames []string, err error) {
+	var desc circuitDesc
+	if err := json.Unmarshal([]byte(descJSON), &desc); err != nil {
+		return nil, nil, nil, errorf(errInvalidInput, "failed to parse circuit description: %w", err)
+	}
+	if desc.Version != circuitDescVersion {
+		return nil, nil, nil, errorf(errInvalidInput, "unsupported circuit description version %d", desc.Version)
+	}
+
+	for _, op := range desc.Ops {
+		if op.Op != "input" {
+			continue
+		}
+		if op.Public {
+			publicNames = append(publicNames, op.Name)
+		} else {
+			secretNames = append(secretNames, op.Name)
+		}
+	}
+
+	circuit = &dslCircuit{
+		Public: make([]frontend.Variable, len(publicNames)),
+		Secret: make([]frontend.Variable, len(secretNames)),
+		Desc:   &desc,
+	}
+	return circuit, publicNames, secretNames, nil
+}
+
+// compileCircuitDesc parses and compiles a JSON circuit description, then
+// renames the input wires so witnesses can refer to them by name. gadgets is
+// as for compileCircuit.
+func compileCircuitDesc(descJSON string, sparse bool, gadgets *[]gadgetCount) (constraint.ConstraintSystem, error) {
+	circuit, publicNames, secretNames, err := parseCircuitDesc(descJSON)
+	if err != nil {
+		return nil, err
+	}
+	builder := r1cs.NewBuilder
+	if sparse {
+		builder = scs.NewBuilder
+	}
+	ccs, err := compileCircuit(ecc.BN254.ScalarField(), builder, circuit, gadgets)
+	if err != nil {
+		return nil, errorf(errInvalidInput, "failed to compile circuit: %w", err)
+	}
+
+	sys, err := systemOf(ccs)
+	if err != nil {
+		return nil, err
+	}
+	copy(sys.Public[len(sys.Public)-len(publicNames):], publicNames)
+	copy(sys.Secret, secretNames)
+	return ccs, nil
+}
+
+// systemOf returns the variable bookkeeping shared by BN254 constraint systems.
+func systemOf(ccs constraint.ConstraintSystem) (*constraint.System, error) {
+	switch cs := ccs.(type) {
+	case *cs_bn254.R1CS:
+		return &cs.System, nil
+	case *cs_bn254.SparseR1CS:
+		return &cs.System, nil
+	default:
+		return nil, errorf(errCurveMismatch, "expected a BN254 constraint system, got %T", ccs)
+	}
+}
+
+// inputNames returns the names of a circuit's public and secret inputs, in
+// witness order. An R1CS also lists the constant wire "1" as its first public
+// variable; it is not an input.
+func inputNames(ccs constraint.ConstraintSystem) (public, secret []string, err error) {
+	sys, err := systemOf(ccs)
+	if err != nil {
+		return nil, nil, err
+	}
+	public = sys.Public
+	if _, ok := ccs.(*cs_bn254.R1CS); ok && len(public) > 0 {
+		public = public[1:]
+	}
+	return public, sys.Secret, nil
+}
+
+// writeInputNames reports inputNames as two C strings of unit-separator
+// (0x1f) joined names, freed by the caller with gnark_free_string.
+func writeInputNames(ccs constraint.ConstraintSystem, out_public **C.char, out_secret **C.char) *C.char {
+	public, secret, err := inputNames(ccs)
+	if err != nil {
+		return cError(err)
+	}
+	*out_public = C.CString(strings.Join(public, "\x1f"))
+	*out_secret = C.CString(strings.Join(secret, "\x1f"))
+	return nil
+}
+
+// gnark_r1cs_inputs lists the inputs of the BN254 R1CS at r1cs_path; see
+// writeInputNames.
+//
+//export gnark_r1cs_inputs
+func gnark_r1cs_inputs(r1cs_path *C.char, out_public **C.char, out_secret **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	r1cs, err := readR1CS(C.GoString(r1cs_path))
+	if err != nil {
+		return cError(err)
+	}
+	return writeInputNames(r1cs, out_public, out_secret)
+}
+
+// gnark_circuit_inputs lists the inputs of a compiled circuit; see
+// writeInputNames.
+//
+//export gnark_circuit_inputs
+func gnark_circuit_inputs(handle C.uint64_t, out_public **C.char, out_secret **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	return writeInputNames(ccs, out_public, out_secret)
+}
+
+// gnark_circuit_compile compiles a JSON circuit description over BN254, into
+// an R1CS (sparse = 0, for Groth16) or a SparseR1CS (sparse != 0, for PLONK).
+// If profile is non-zero, *out_gadgets is set to the constraints each
+// function added, one "name\town\tcum" line per function (see
+// compileCircuit); the caller frees it with gnark_free_string.
+//
+//export gnark_circuit_compile
+func gnark_circuit_compile(desc *C.char, sparse C.int, profile C.int, out_handle *C.uint64_t, out_gadgets **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	var gadgets *[]gadgetCount
+	if profile != 0 {
+		gadgets = new([]gadgetCount)
+	}
+	ccs, err := compileCircuitDesc(C.GoString(desc), sparse != 0, gadgets)
+	if err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(ccs)
+	if gadgets != nil {
+		*out_gadgets = C.CString(formatGadgets(*gadgets))
+	}
+	return nil
+}
+
+// gnark_circuit_nb_constraints reports the number of constraints in a
+// compiled circuit.
+//
+//export gnark_circuit_nb_constraints
+func gnark_circuit_nb_constraints(handle C.uint64_t, out *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	*out = C.uint64_t(ccs.GetNbConstraints())
+	return nil
+}
+
+// gnark_circuit_srs_size reports the number of KZG SRS G1 powers a PLONK
+// setup of a compiled SparseR1CS needs: the next power of two above its
+// constraints and public inputs, plus 3.
+//
+//export gnark_circuit_srs_size
+func gnark_circuit_srs_size(handle C.uint64_t, out *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	spr, ok := ccs.(*cs_bn254.SparseR1CS)
+	if !ok {
+		return cErrorf(errInvalidInput, "a KZG SRS is only needed for a SparseR1CS, got %T", ccs)
+	}
+	n := uint64(spr.GetNbConstraints() + spr.GetNbPublicVariables())
+	*out = C.uint64_t(ecc.NextPowerOfTwo(n) + 3)
+	return nil
+}
+
+// gnark_circuit_write serializes a compiled circuit to path, in the format
+// gnark_groth16_prove reads.
+//
+//export gnark_circuit_write
+func gnark_circuit_write(handle C.uint64_t, path *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	if err := writeFile(C.GoString(path), ccs.WriteTo); err != nil {
+		return cErrorf(errIO, "failed to write circuit: %w", err)
+	}
+	return nil
+}
+
+// gnark_circuit_groth16_setup runs a local Groth16 setup for a compiled R1CS
+// and writes the proving key (raw format) and verifying key to the given paths.
+//
+//export gnark_circuit_groth16_setup
+func gnark_circuit_groth16_setup(handle C.uint64_t, pk_path *C.char, vk_path *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	return groth16SetupTo(handle, pk_path, vk_path)
+}
+
+// gnark_circuit_groth16_setup_seeded is gnark_circuit_groth16_setup with the
+// toxic waste drawn from the deterministic RNG of applyTestRNG, so the same
+// circuit and seed always give the same keys. Anyone who knows the seed can
+// forge proofs: for test fixtures only.
+//
+//export gnark_circuit_groth16_setup_seeded
+func gnark_circuit_groth16_setup_seeded(handle C.uint64_t, seed C.uint64_t, pk_path *C.char, vk_path *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	defer applyTestRNG(true, uint64(seed))()
+	return groth16SetupTo(handle, pk_path, vk_path)
+}
+
+func groth16SetupTo(handle C.uint64_t, pk_path *C.char, vk_path *C.char) *C.char {
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	r1cs, ok := ccs.(*cs_bn254.R1CS)
+	if !ok {
+		return cErrorf(errInvalidInput, "groth16 setup needs an R1CS, got %T", ccs)
+	}
+	pk, vk, err := setupBN254(r1cs)
+	if err != nil {
+		return cErrorf(errOther, "groth16 setup failed: %w", err)
+	}
+	if err := writeFile(C.GoString(pk_path), pk.WriteRawTo); err != nil {
+		return cErrorf(errIO, "failed to write proving key: %w", err)
+	}
+	if err := writeFile(C.GoString(vk_path), vk.WriteTo); err != nil {
+		return cErrorf(errIO, "failed to write verifying key: %w", err)
+	}
+	return nil
+}
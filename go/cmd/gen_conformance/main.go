@@ -0,0 +1,173 @@
+// gen_conformance generates the canonical vectors of rust_gnark::conformance
+// with upstream gnark alone, none of this crate's Go code: for each circuit
+// below, the .r1cs and proving key, the verifying key in both encodings, a
+// proof in both encodings and as Solidity calldata, and the public witness,
+// written to <output dir>/<circuit>/, plus the gnark version used in
+// <output dir>/GNARK_VERSION. The output dir defaults to ../crates/conformance.
+//
+// Usage: go run ./cmd/gen_conformance [output dir]
+//
+// Run it with another gnark (go get github.com/consensys/gnark@<version>)
+// and an output directory to check a Go service's gnark against this crate
+// with rust_gnark::conformance::check.
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"runtime/debug"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/groth16"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+)
+
+// CubicCircuit defines x^3 + x + 5 == y.
+type CubicCircuit struct {
+	X frontend.Variable `gnark:"X"`
+	Y frontend.Variable `gnark:"Y,public"`
+}
+
+func (circuit *CubicCircuit) Define(api frontend.API) error {
+	x3 := api.Mul(circuit.X, circuit.X, circuit.X)
+	api.AssertIsEqual(api.Add(x3, circuit.X, 5), circuit.Y)
+	return nil
+}
+
+// CommittedCircuit is CubicCircuit with a commitment to X, so its keys and
+// proofs carry the commitment fields.
+type CommittedCircuit struct {
+	X frontend.Variable `gnark:"X"`
+	Y frontend.Variable `gnark:"Y,public"`
+}
+
+func (circuit *CommittedCircuit) Define(api frontend.API) error {
+	x3 := api.Mul(circuit.X, circuit.X, circuit.X)
+	api.AssertIsEqual(api.Add(x3, circuit.X, 5), circuit.Y)
+	committer, ok := api.(frontend.Committer)
+	if !ok {
+		return fmt.Errorf("the builder does not support commitments")
+	}
+	commitment, err := committer.Commit(circuit.X)
+	if err != nil {
+		return err
+	}
+	api.AssertIsDifferent(commitment, 0)
+	return nil
+}
+
+// The assignment of every circuit; rust_gnark::conformance::ASSIGNMENT.
+const x, y = 3, 35
+
+func main() {
+	outDir := filepath.Join("..", "crates", "conformance")
+	if len(os.Args) > 1 {
+		outDir = os.Args[1]
+	}
+	vectors := []struct {
+		name             string
+		circuit, witness frontend.Circuit
+	}{
+		{"cubic", &CubicCircuit{}, &CubicCircuit{X: x, Y: y}},
+		{"committed", &CommittedCircuit{}, &CommittedCircuit{X: x, Y: y}},
+	}
+	for _, v := range vectors {
+		dir := filepath.Join(outDir, v.name)
+		if err := generate(dir, v.circuit, v.witness); err != nil {
+			panic(fmt.Sprintf("%s: %v", v.name, err))
+		}
+	}
+	if err := os.WriteFile(filepath.Join(outDir, "GNARK_VERSION"), []byte(gnarkVersion()+"\n"), 0o644); err != nil {
+		panic(fmt.Sprintf("failed to write GNARK_VERSION: %v", err))
+	}
+	fmt.Println("Conformance vectors generated successfully in", outDir)
+}
+
+func generate(dir string, circuit, assignment frontend.Circuit) error {
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		return fmt.Errorf("failed to create output dir: %w", err)
+	}
+	cs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, circuit)
+	if err != nil {
+		return fmt.Errorf("failed to compile circuit: %w", err)
+	}
+	pk, vk, err := groth16.Setup(cs)
+	if err != nil {
+		return fmt.Errorf("failed to run setup: %w", err)
+	}
+	full, err := frontend.NewWitness(assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		return fmt.Errorf("failed to build witness: %w", err)
+	}
+	proof, err := groth16.Prove(cs, pk, full)
+	if err != nil {
+		return fmt.Errorf("failed to prove: %w", err)
+	}
+	public, err := full.Public()
+	if err != nil {
+		return fmt.Errorf("failed to extract public witness: %w", err)
+	}
+	if err := groth16.Verify(proof, vk, public); err != nil {
+		return fmt.Errorf("the proof does not verify: %w", err)
+	}
+	publicBin, err := public.MarshalBinary()
+	if err != nil {
+		return fmt.Errorf("failed to marshal public witness: %w", err)
+	}
+
+	files := []struct {
+		name  string
+		write func(io.Writer) (int64, error)
+	}{
+		{"circuit.r1cs", cs.WriteTo},
+		// WriteRawTo, as the key files of gen_test_vectors.
+		{"circuit.pk", pk.WriteRawTo},
+		{"vk.compressed", vk.WriteTo},
+		{"vk.raw", vk.WriteRawTo},
+		{"proof.compressed", proof.WriteTo},
+		{"proof.raw", proof.WriteRawTo},
+		{"proof.solidity", bytesWriter(proof.(*groth16_bn254.Proof).MarshalSolidity())},
+		{"public_witness", bytesWriter(publicBin)},
+	}
+	for _, f := range files {
+		if err := writeFile(filepath.Join(dir, f.name), f.write); err != nil {
+			return fmt.Errorf("failed to write %s: %w", f.name, err)
+		}
+	}
+	return nil
+}
+
+func bytesWriter(b []byte) func(io.Writer) (int64, error) {
+	return func(w io.Writer) (int64, error) {
+		n, err := w.Write(b)
+		return int64(n), err
+	}
+}
+
+func writeFile(path string, write func(io.Writer) (int64, error)) error {
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	if _, err := write(f); err != nil {
+		f.Close()
+		return err
+	}
+	return f.Close()
+}
+
+// gnarkVersion is the version of the gnark module this binary was built with.
+func gnarkVersion() string {
+	if info, ok := debug.ReadBuildInfo(); ok {
+		for _, dep := range info.Deps {
+			if dep.Path == "github.com/consensys/gnark" {
+				return dep.Version
+			}
+		}
+	}
+	return "unknown"
+}
@@ -0,0 +1,244 @@
+// gen_examples compiles the example circuits of the rust-gnark-examples
+// crate, runs a Groth16 setup for each and exports <name>.r1cs, <name>.pk,
+// <name>.vk and a satisfying <name>.witness.json to the output dir, by
+// default ../examples/fixtures/.
+//
+// Usage: go run ./cmd/gen_examples [output dir]
+package main
+
+import (
+	"crypto/rand"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"path/filepath"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	bn254mimc "github.com/consensys/gnark-crypto/ecc/bn254/fr/mimc"
+	bn254poseidon2 "github.com/consensys/gnark-crypto/ecc/bn254/fr/poseidon2"
+	bn254eddsa "github.com/consensys/gnark-crypto/ecc/bn254/twistededwards/eddsa"
+	tedwards "github.com/consensys/gnark-crypto/ecc/twistededwards"
+	"github.com/consensys/gnark/backend/groth16"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+	"github.com/consensys/gnark/std/accumulator/merkle"
+	"github.com/consensys/gnark/std/algebra/native/twistededwards"
+	"github.com/consensys/gnark/std/hash/mimc"
+	"github.com/consensys/gnark/std/hash/poseidon2"
+	"github.com/consensys/gnark/std/signature/eddsa"
+)
+
+// merkleDepth is the depth of MerkleMembershipCircuit's tree, 16 leaves.
+const merkleDepth = 4
+
+// MultiplierCircuit proves knowledge of a factorization A * B == C.
+type MultiplierCircuit struct {
+	A frontend.Variable
+	B frontend.Variable
+	C frontend.Variable `gnark:",public"`
+}
+
+func (c *MultiplierCircuit) Define(api frontend.API) error {
+	api.AssertIsEqual(api.Mul(c.A, c.B), c.C)
+	return nil
+}
+
+// MerkleMembershipCircuit proves that leaf Index of a MiMC tree, laid out
+// like rust_gnark::merkle::MerkleTree, has the public Root.
+type MerkleMembershipCircuit struct {
+	Root  frontend.Variable `gnark:",public"`
+	Proof merkle.MerkleProof
+	Index frontend.Variable
+}
+
+func (c *MerkleMembershipCircuit) Define(api frontend.API) error {
+	h, err := mimc.NewMiMC(api)
+	if err != nil {
+		return err
+	}
+	api.AssertIsEqual(c.Proof.RootHash, c.Root)
+	c.Proof.VerifyProof(api, &h, c.Index)
+	return nil
+}
+
+// EddsaVerifyCircuit checks an EdDSA signature over BN254's twisted Edwards
+// curve with MiMC, as rust_gnark::eddsa signs.
+type EddsaVerifyCircuit struct {
+	PublicKey eddsa.PublicKey  `gnark:",public"`
+	Message   frontend.Variable `gnark:",public"`
+	Signature eddsa.Signature
+}
+
+func (c *EddsaVerifyCircuit) Define(api frontend.API) error {
+	curve, err := twistededwards.NewEdCurve(api, tedwards.BN254)
+	if err != nil {
+		return err
+	}
+	h, err := mimc.NewMiMC(api)
+	if err != nil {
+		return err
+	}
+	return eddsa.Verify(curve, c.Signature, c.Message, c.PublicKey, &h)
+}
+
+// PoseidonPreimageCircuit proves knowledge of two elements hashing to the
+// public Digest with Poseidon2, as rust_gnark::hash::poseidon2 hashes.
+type PoseidonPreimageCircuit struct {
+	Preimage [2]frontend.Variable
+	Digest   frontend.Variable `gnark:",public"`
+}
+
+func (c *PoseidonPreimageCircuit) Define(api frontend.API) error {
+	h, err := poseidon2.NewMerkleDamgardHasher(api)
+	if err != nil {
+		return err
+	}
+	h.Write(c.Preimage[:]...)
+	api.AssertIsEqual(h.Sum(), c.Digest)
+	return nil
+}
+
+func main() {
+	outDir := filepath.Join("..", "examples", "fixtures")
+	if len(os.Args) > 1 {
+		outDir = os.Args[1]
+	}
+	if err := os.MkdirAll(outDir, 0o755); err != nil {
+		panic(fmt.Sprintf("failed to create output dir: %v", err))
+	}
+
+	export(outDir, "multiplier", &MultiplierCircuit{}, &MultiplierCircuit{A: 3, B: 11, C: 33})
+	export(outDir, "merkle_membership", merkleCircuit(), merkleAssignment())
+	export(outDir, "eddsa_verify", &EddsaVerifyCircuit{}, eddsaAssignment())
+	export(outDir, "poseidon_preimage", &PoseidonPreimageCircuit{}, poseidonAssignment())
+
+	fmt.Println("Example fixtures generated successfully in", outDir)
+}
+
+func merkleCircuit() *MerkleMembershipCircuit {
+	return &MerkleMembershipCircuit{
+		Proof: merkle.MerkleProof{Path: make([]frontend.Variable, merkleDepth+1)},
+	}
+}
+
+// merkleAssignment proves leaf 5 of the tree over the leaves 1..16.
+func merkleAssignment() *MerkleMembershipCircuit {
+	const index = 5
+	hash := func(elems ...fr.Element) fr.Element {
+		h := bn254mimc.NewMiMC()
+		for _, e := range elems {
+			b := e.Bytes()
+			h.Write(b[:])
+		}
+		var out fr.Element
+		out.SetBytes(h.Sum(nil))
+		return out
+	}
+	leaves := make([]fr.Element, 1<<merkleDepth)
+	level := make([]fr.Element, len(leaves))
+	for i := range leaves {
+		leaves[i].SetUint64(uint64(i + 1))
+		level[i] = hash(leaves[i])
+	}
+	path := []frontend.Variable{leaves[index]}
+	for i := index; len(level) > 1; i >>= 1 {
+		path = append(path, level[i^1])
+		next := make([]fr.Element, len(level)/2)
+		for j := range next {
+			next[j] = hash(level[2*j], level[2*j+1])
+		}
+		level = next
+	}
+	return &MerkleMembershipCircuit{
+		Root:  level[0],
+		Proof: merkle.MerkleProof{RootHash: level[0], Path: path},
+		Index: index,
+	}
+}
+
+func eddsaAssignment() *EddsaVerifyCircuit {
+	key, err := bn254eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		panic(fmt.Sprintf("failed to generate EdDSA key: %v", err))
+	}
+	var msg fr.Element
+	msg.SetUint64(42)
+	msgBytes := msg.Bytes()
+	sig, err := key.Sign(msgBytes[:], bn254mimc.NewMiMC())
+	if err != nil {
+		panic(fmt.Sprintf("failed to sign: %v", err))
+	}
+	var c EddsaVerifyCircuit
+	c.PublicKey.Assign(tedwards.BN254, key.PublicKey.Bytes())
+	c.Signature.Assign(tedwards.BN254, sig)
+	c.Message = msg
+	return &c
+}
+
+func poseidonAssignment() *PoseidonPreimageCircuit {
+	var a, b fr.Element
+	a.SetUint64(1)
+	b.SetUint64(2)
+	h := bn254poseidon2.NewMerkleDamgardHasher()
+	for _, e := range []fr.Element{a, b} {
+		bytes := e.Bytes()
+		h.Write(bytes[:])
+	}
+	var digest fr.Element
+	digest.SetBytes(h.Sum(nil))
+	return &PoseidonPreimageCircuit{Preimage: [2]frontend.Variable{a, b}, Digest: digest}
+}
+
+// export compiles circuit, runs the setup and writes the artifacts of name,
+// with assignment as its witness JSON: the inputs by their flat names (the
+// ones the constraint system records), as decimal strings.
+func export(outDir, name string, circuit, assignment frontend.Circuit) {
+	cs, err := frontend.Compile(ecc.BN254.ScalarField(), r1cs.NewBuilder, circuit)
+	if err != nil {
+		panic(fmt.Sprintf("%s: failed to compile circuit: %v", name, err))
+	}
+	pk, vk, err := groth16.Setup(cs)
+	if err != nil {
+		panic(fmt.Sprintf("%s: failed to run setup: %v", name, err))
+	}
+	w, err := frontend.NewWitness(assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		panic(fmt.Sprintf("%s: failed to build witness: %v", name, err))
+	}
+
+	sys := cs.(*cs_bn254.R1CS)
+	names := append(append([]string{}, sys.Public[1:]...), sys.Secret...)
+	values := w.Vector().(fr.Vector)
+	if len(names) != len(values) {
+		panic(fmt.Sprintf("%s: %d inputs but %d witness values", name, len(names), len(values)))
+	}
+	inputs := make(map[string]string, len(names))
+	for i, n := range names {
+		var v big.Int
+		values[i].BigInt(&v)
+		inputs[n] = v.String()
+	}
+	witnessJSON, err := json.MarshalIndent(inputs, "", "  ")
+	if err != nil {
+		panic(fmt.Sprintf("%s: failed to encode witness: %v", name, err))
+	}
+
+	write := func(ext string, writeTo func(*os.File) error) {
+		f, err := os.Create(filepath.Join(outDir, name+ext))
+		if err != nil {
+			panic(fmt.Sprintf("%s: failed to create %s file: %v", name, ext, err))
+		}
+		defer f.Close()
+		if err := writeTo(f); err != nil {
+			panic(fmt.Sprintf("%s: failed to write %s: %v", name, ext, err))
+		}
+	}
+	write(".r1cs", func(f *os.File) error { _, err := cs.WriteTo(f); return err })
+	// WriteRawTo produces uncompressed binary, paired with UnsafeReadFrom on load
+	write(".pk", func(f *os.File) error { _, err := pk.WriteRawTo(f); return err })
+	write(".vk", func(f *os.File) error { _, err := vk.WriteTo(f); return err })
+	write(".witness.json", func(f *os.File) error { _, err := f.Write(append(witnessJSON, '\n')); return err })
+}
@@ -0,0 +1,149 @@
+package main
+
+import (
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/binary"
+	"hash"
+	"runtime"
+	"runtime/debug"
+	"sync"
+	"sync/atomic"
+
+	"github.com/consensys/gnark/backend"
+	"golang.org/x/crypto/chacha20"
+	"golang.org/x/crypto/sha3"
+)
+
+// Hash-to-field functions selectable through C_ProveOptions.hash and
+// gnark_groth16_verify_with_hash. Must match HashFunction on the Rust side.
+const (
+	hashDefault = iota // gnark's default (RFC 9380 expand_message_xmd over SHA-256)
+	hashSHA256
+	hashKeccak256
+)
+
+// hashToField returns the hash used to derive commitment challenges, or nil
+// for gnark's default.
+func hashToField(id int) (hash.Hash, error) {
+	switch id {
+	case hashDefault:
+		return nil, nil
+	case hashSHA256:
+		return sha256.New(), nil
+	case hashKeccak256:
+		return sha3.NewLegacyKeccak256(), nil
+	default:
+		return nil, errorf(errInvalidInput, "unknown hash function %d", id)
+	}
+}
+
+func proverHashOptions(id int) ([]backend.ProverOption, error) {
+	h, err := hashToField(id)
+	if err != nil || h == nil {
+		return nil, err
+	}
+	return []backend.ProverOption{backend.WithProverHashToFieldFunction(h)}, nil
+}
+
+func verifierHashOptions(id int) ([]backend.VerifierOption, error) {
+	h, err := hashToField(id)
+	if err != nil || h == nil {
+		return nil, err
+	}
+	return []backend.VerifierOption{backend.WithVerifierHashToFieldFunction(h)}, nil
+}
+
+// GOMAXPROCS and the soft memory limit are process-wide, so calls that
+// override them are serialized and the previous values restored afterwards.
+var runtimeLimitsMu sync.Mutex
+
+// threadBudget is the host's bound on the runtime's threads
+// (gnark_set_thread_budget); 0 if it set none. GOMAXPROCS is the budget
+// outside calls that override it, and pools sized per CPU by default, like
+// the solver's workers, are sized to it instead.
+var threadBudget atomic.Int32
+
+// setThreadBudget sets threadBudget and GOMAXPROCS to threads, or the
+// number of CPUs if threads is 0, and returns the previous budget. With
+// raise, it only ever increases the budget: it keeps a larger one or none.
+// It waits for calls overriding GOMAXPROCS, which would otherwise restore the
+// old value when they return.
+func setThreadBudget(threads int, raise bool) int {
+	runtimeLimitsMu.Lock()
+	defer runtimeLimitsMu.Unlock()
+	prev := int(threadBudget.Load())
+	if raise && (prev == 0 || prev >= threads) {
+		return prev
+	}
+	threads = max(threads, 0)
+	if threads > 0 {
+		runtime.GOMAXPROCS(threads)
+	} else {
+		runtime.GOMAXPROCS(runtime.NumCPU())
+	}
+	threadBudget.Store(int32(threads))
+	return prev
+}
+
+// applyRuntimeLimits sets GOMAXPROCS to threads and GOMEMLIMIT to memLimit
+// bytes (each only if > 0) and returns a function restoring the old values.
+func applyRuntimeLimits(threads int, memLimit int64) (restore func()) {
+	if threads <= 0 && memLimit <= 0 {
+		return func() {}
+	}
+	runtimeLimitsMu.Lock()
+	prevProcs, prevLimit := -1, int64(-1)
+	if threads > 0 {
+		prevProcs = runtime.GOMAXPROCS(threads)
+	}
+	if memLimit > 0 {
+		prevLimit = debug.SetMemoryLimit(memLimit)
+	}
+	return func() {
+		if prevProcs > 0 {
+			runtime.GOMAXPROCS(prevProcs)
+		}
+		if prevLimit >= 0 {
+			debug.SetMemoryLimit(prevLimit)
+		}
+		runtimeLimitsMu.Unlock()
+	}
+}
+
+// testRNGMu serializes proofs with a deterministic RNG, since the RNG is
+// swapped in process-wide.
+var testRNGMu sync.Mutex
+
+// applyTestRNG, if enabled, replaces crypto/rand.Reader (where gnark draws the
+// Groth16 blinding factors from) with a ChaCha20 stream keyed by seed, and
+// returns a function restoring it. Anything else reading crypto/rand.Reader
+// in the meantime, including concurrent proofs, reads the stream too: for
+// reproducible tests only.
+func applyTestRNG(enabled bool, seed uint64) (restore func()) {
+	if !enabled {
+		return func() {}
+	}
+	testRNGMu.Lock()
+	var key [chacha20.KeySize]byte
+	binary.LittleEndian.PutUint64(key[:], seed)
+	// Cannot fail: the key and nonce have the right sizes.
+	stream, _ := chacha20.NewUnauthenticatedCipher(key[:], make([]byte, chacha20.NonceSize))
+	prev := rand.Reader
+	rand.Reader = streamReader{stream}
+	return func() {
+		rand.Reader = prev
+		testRNGMu.Unlock()
+	}
+}
+
+// streamReader reads a cipher's keystream.
+type streamReader struct {
+	stream *chacha20.Cipher
+}
+
+func (r streamReader) Read(p []byte) (int, error) {
+	clear(p)
+	r.stream.XORKeyStream(p, p)
+	return len(p), nil
+}
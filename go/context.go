@@ -0,0 +1,43 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+)
+
+// Application contexts: a BN254 scalar derived from an application's domain
+// and a challenge (a nonce, a session id, a message), which circuits built
+// with CircuitBuilder::bind_app_context expose as their last public input.
+// A proof then verifies only against the context it was made for, so it
+// cannot be replayed in another application, or in another session of the
+// same one. The scalar is RFC 9380 hash_to_field (expand_message_xmd over
+// SHA-256) of the challenge, with the domain in the domain separation tag.
+
+const appContextDST = "rust-gnark-app-context-v1:"
+
+// gnark_app_context writes the context of domain (a NUL-terminated string)
+// and the challenge_len bytes at challenge to out, as a 32-byte big-endian
+// field element.
+//
+//export gnark_app_context
+func gnark_app_context(domain *C.char, challenge *C.uint8_t, challenge_len C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	dst := []byte(appContextDST + C.GoString(domain))
+	// expand_message_xmd takes tags of at most 255 bytes.
+	if len(dst) > 255 {
+		return cErrorf(errInvalidInput, "the domain is longer than %d bytes", 255-len(appContextDST))
+	}
+	elems, err := fr.Hash(borrowBytes(challenge, challenge_len), dst, 1)
+	if err != nil {
+		return cErrorf(errInvalidInput, "failed to hash the challenge: %w", err)
+	}
+	bytes := elems[0].Bytes()
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), fr.Bytes), bytes[:])
+	return nil
+}
@@ -0,0 +1,34 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import "golang.org/x/sys/cpu"
+
+// CPU features gnark-crypto's assembly depends on. Its BN254 field
+// arithmetic on x86-64 multiplies with MULX, ADCX and ADOX (BMI2 and ADX), so
+// a CPU without them faults (SIGILL) in the first proof. The portable build
+// (the purego tag) uses gnark-crypto's generic Go code instead, which runs on
+// any CPU, several times slower.
+
+func cFlag(b bool) C.int {
+	if b {
+		return 1
+	}
+	return 0
+}
+
+// gnark_cpu_features reports the CPU's ADX, BMI2, AVX2 and AVX-512F support
+// (always 0 off x86-64) as Go detects it, and whether the library was built
+// with gnark-crypto's assembly.
+//
+//export gnark_cpu_features
+func gnark_cpu_features(out_adx *C.int, out_bmi2 *C.int, out_avx2 *C.int, out_avx512f *C.int, out_assembly *C.int) {
+	*out_adx = cFlag(cpu.X86.HasADX)
+	*out_bmi2 = cFlag(cpu.X86.HasBMI2)
+	*out_avx2 = cFlag(cpu.X86.HasAVX2)
+	*out_avx512f = cFlag(cpu.X86.HasAVX512F)
+	*out_assembly = cFlag(assembly)
+}
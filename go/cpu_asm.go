@@ -0,0 +1,6 @@
+//go:build !purego
+
+package main
+
+// assembly reports whether gnark-crypto's assembly is compiled in.
+const assembly = true
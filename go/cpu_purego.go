@@ -0,0 +1,6 @@
+//go:build purego
+
+package main
+
+// The portable build: gnark-crypto's generic Go code, no assembly.
+const assembly = false
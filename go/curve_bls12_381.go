@@ -0,0 +1,86 @@
+//go:build !no_bls12_381 && !verifier
+
+package main
+
+import (
+	"hash"
+	"io"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	bls12381 "github.com/consensys/gnark-crypto/ecc/bls12-381"
+	bandersnatcheddsa "github.com/consensys/gnark-crypto/ecc/bls12-381/bandersnatch/eddsa"
+	bls12381fr "github.com/consensys/gnark-crypto/ecc/bls12-381/fr"
+	bls12381fft "github.com/consensys/gnark-crypto/ecc/bls12-381/fr/fft"
+	bls12381mimc "github.com/consensys/gnark-crypto/ecc/bls12-381/fr/mimc"
+	bls12381poseidon2 "github.com/consensys/gnark-crypto/ecc/bls12-381/fr/poseidon2"
+	jubjubeddsa "github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"github.com/consensys/gnark-crypto/signature"
+)
+
+// BLS12-381 host-side primitives: Poseidon2/MiMC, MSM and FFTs over its
+// scalar field and EdDSA over the embedded Jubjub and Bandersnatch curves. Building with the
+// no_bls12_381 tag leaves them out (see curve_no_bls12_381.go).
+
+var bls12381Field = &fieldSupport{
+	poseidon2: func() hash.Hash { return bls12381poseidon2.NewMerkleDamgardHasher() },
+	mimc:      func() hash.Hash { return bls12381mimc.NewMiMC() },
+	canonical: func(b []byte) error {
+		var e bls12381fr.Element
+		return e.SetBytesCanonical(b)
+	},
+	msmG1:     bls12381MSMG1,
+	fft:       bls12381FFT,
+	g1Size:    bls12381.SizeOfG1AffineUncompressed,
+	fftMaxLog: 32,
+}
+
+var jubjubKeyGen eddsaKeyGen = func(r io.Reader) (signature.Signer, error) {
+	return jubjubeddsa.GenerateKey(r)
+}
+
+var bandersnatchKeyGen eddsaKeyGen = func(r io.Reader) (signature.Signer, error) {
+	return bandersnatcheddsa.GenerateKey(r)
+}
+
+func bls12381MSMG1(points, scalars []byte, n int) ([]byte, error) {
+	ps := make([]bls12381.G1Affine, n)
+	ss := make([]bls12381fr.Element, n)
+	for i := range ps {
+		p := points[i*bls12381.SizeOfG1AffineUncompressed : (i+1)*bls12381.SizeOfG1AffineUncompressed]
+		if read, err := ps[i].SetBytes(p); err != nil || read != len(p) {
+			return nil, errorf(errInvalidInput, "point %d is not an uncompressed G1 point: %v", i, err)
+		}
+		if err := ss[i].SetBytesCanonical(scalars[i*frBytes : (i+1)*frBytes]); err != nil {
+			return nil, errorf(errInvalidInput, "scalar %d is not a canonical field element: %w", i, err)
+		}
+	}
+	var res bls12381.G1Affine // the zero value is the point at infinity
+	if n > 0 {
+		if _, err := res.MultiExp(ps, ss, ecc.MultiExpConfig{}); err != nil {
+			return nil, err
+		}
+	}
+	raw := res.RawBytes()
+	return raw[:], nil
+}
+
+func bls12381FFT(coeffs []byte, inverse bool) error {
+	a := make([]bls12381fr.Element, len(coeffs)/frBytes)
+	for i := range a {
+		if err := a[i].SetBytesCanonical(coeffs[i*frBytes : (i+1)*frBytes]); err != nil {
+			return errorf(errInvalidInput, "coefficient %d is not a canonical field element: %w", i, err)
+		}
+	}
+	domain := bls12381fft.NewDomain(uint64(len(a)))
+	if inverse {
+		domain.FFTInverse(a, bls12381fft.DIF)
+	} else {
+		domain.FFT(a, bls12381fft.DIF)
+	}
+	bls12381fft.BitReverse(a)
+	for i := range a {
+		b := a[i].Bytes()
+		copy(coeffs[i*frBytes:], b[:])
+	}
+	return nil
+}
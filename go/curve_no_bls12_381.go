@@ -0,0 +1,11 @@
+//go:build no_bls12_381 && !verifier
+
+package main
+
+// Built without BLS12-381: requests for it fail with errBLS12381Disabled.
+
+var bls12381Field *fieldSupport
+
+var jubjubKeyGen, bandersnatchKeyGen eddsaKeyGen
+
+var blsSignatures blsScheme
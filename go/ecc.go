@@ -0,0 +1,249 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"fmt"
+	"math/big"
+	"math/bits"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	bn254fr "github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	bn254fft "github.com/consensys/gnark-crypto/ecc/bn254/fr/fft"
+)
+
+// Raw gnark-crypto primitives for protocols built outside gnark (KZG,
+// IPA, ...): G1 multi-scalar multiplication and FFTs over the scalar field.
+// Scalars use the 32-byte big-endian encoding of the hash exports; G1 points
+// use gnark-crypto's uncompressed encoding (G1Affine.RawBytes).
+
+// gnark_msm_g1 computes sum(scalars[i] * points[i]) over the n points and
+// scalars, writing the uncompressed result (g1Size bytes for curve) to out.
+//
+//export gnark_msm_g1
+func gnark_msm_g1(curve C.int, points *C.uint8_t, scalars *C.uint8_t, n C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	field, err := fieldOf(curve)
+	if err != nil {
+		return cError(err)
+	}
+	res, err := field.msmG1(
+		borrowBytes(points, n*C.uint64_t(field.g1Size)),
+		borrowBytes(scalars, n*frBytes),
+		int(n),
+	)
+	if err != nil {
+		return cError(err)
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), field.g1Size), res)
+	return nil
+}
+
+// gnark_fft replaces the n coefficients at coeffs with their evaluations on
+// the size-n subgroup of roots of unity (natural order), or with inverse != 0,
+// evaluations with coefficients. n must be a power of two.
+//
+//export gnark_fft
+func gnark_fft(curve C.int, coeffs *C.uint8_t, n C.uint64_t, inverse C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	field, err := fieldOf(curve)
+	if err != nil {
+		return cError(err)
+	}
+	if n == 0 || n&(n-1) != 0 {
+		return cErrorf(errInvalidInput, "FFT size %d is not a power of two", uint64(n))
+	}
+	if log := bits.TrailingZeros64(uint64(n)); log > field.fftMaxLog {
+		return cErrorf(errInvalidInput, "FFT size 2^%d exceeds the field's maximum of 2^%d", log, field.fftMaxLog)
+	}
+	if err := field.fft(borrowBytes(coeffs, n*frBytes), inverse != 0); err != nil {
+		return cError(err)
+	}
+	return nil
+}
+
+func bn254MSMG1(points, scalars []byte, n int) ([]byte, error) {
+	ps := make([]bn254.G1Affine, n)
+	ss := make([]bn254fr.Element, n)
+	for i := range ps {
+		p := points[i*bn254.SizeOfG1AffineUncompressed : (i+1)*bn254.SizeOfG1AffineUncompressed]
+		if read, err := ps[i].SetBytes(p); err != nil || read != len(p) {
+			return nil, errorf(errInvalidInput, "point %d is not an uncompressed G1 point: %v", i, err)
+		}
+		if err := ss[i].SetBytesCanonical(scalars[i*frBytes : (i+1)*frBytes]); err != nil {
+			return nil, errorf(errInvalidInput, "scalar %d is not a canonical field element: %w", i, err)
+		}
+	}
+	var res bn254.G1Affine // the zero value is the point at infinity
+	if n > 0 {
+		if _, err := res.MultiExp(ps, ss, ecc.MultiExpConfig{}); err != nil {
+			return nil, err
+		}
+	}
+	raw := res.RawBytes()
+	return raw[:], nil
+}
+
+func bn254FFT(coeffs []byte, inverse bool) error {
+	a := make([]bn254fr.Element, len(coeffs)/frBytes)
+	for i := range a {
+		if err := a[i].SetBytesCanonical(coeffs[i*frBytes : (i+1)*frBytes]); err != nil {
+			return errorf(errInvalidInput, "coefficient %d is not a canonical field element: %w", i, err)
+		}
+	}
+	domain := bn254fft.NewDomain(uint64(len(a)))
+	if inverse {
+		domain.FFTInverse(a, bn254fft.DIF)
+	} else {
+		domain.FFT(a, bn254fft.DIF)
+	}
+	bn254fft.BitReverse(a)
+	for i := range a {
+		b := a[i].Bytes()
+		copy(coeffs[i*frBytes:], b[:])
+	}
+	return nil
+}
+
+// BN254 group arithmetic and pairings. G1 points are uncompressed as above
+// (64 bytes), G2 points uncompressed as G2Affine.RawBytes (128 bytes: x.A1,
+// x.A0, y.A1, y.A0); invalid or out-of-subgroup points are rejected.
+
+func readBN254G1(b *C.uint8_t, what string) (bn254.G1Affine, error) {
+	var p bn254.G1Affine
+	data := borrowBytes(b, bn254.SizeOfG1AffineUncompressed)
+	if read, err := p.SetBytes(data); err != nil || read != len(data) {
+		return p, errorf(errInvalidInput, "%s is not an uncompressed G1 point: %v", what, err)
+	}
+	return p, nil
+}
+
+func readBN254G2(b *C.uint8_t, what string) (bn254.G2Affine, error) {
+	var p bn254.G2Affine
+	data := borrowBytes(b, bn254.SizeOfG2AffineUncompressed)
+	if read, err := p.SetBytes(data); err != nil || read != len(data) {
+		return p, errorf(errInvalidInput, "%s is not an uncompressed G2 point: %v", what, err)
+	}
+	return p, nil
+}
+
+func readBN254Scalar(b *C.uint8_t) (*big.Int, error) {
+	var e bn254fr.Element
+	if err := e.SetBytesCanonical(borrowBytes(b, frBytes)); err != nil {
+		return nil, errorf(errInvalidInput, "scalar is not a canonical field element: %w", err)
+	}
+	return e.BigInt(new(big.Int)), nil
+}
+
+//export gnark_bn254_g1_add
+func gnark_bn254_g1_add(a *C.uint8_t, b *C.uint8_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := readBN254G1(a, "a")
+	if err != nil {
+		return cError(err)
+	}
+	q, err := readBN254G1(b, "b")
+	if err != nil {
+		return cError(err)
+	}
+	raw := p.Add(&p, &q).RawBytes()
+	writeBytes(out, raw[:])
+	return nil
+}
+
+//export gnark_bn254_g1_neg
+func gnark_bn254_g1_neg(a *C.uint8_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := readBN254G1(a, "a")
+	if err != nil {
+		return cError(err)
+	}
+	raw := p.Neg(&p).RawBytes()
+	writeBytes(out, raw[:])
+	return nil
+}
+
+//export gnark_bn254_g1_scalar_mul
+func gnark_bn254_g1_scalar_mul(a *C.uint8_t, scalar *C.uint8_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := readBN254G1(a, "a")
+	if err != nil {
+		return cError(err)
+	}
+	k, err := readBN254Scalar(scalar)
+	if err != nil {
+		return cError(err)
+	}
+	raw := p.ScalarMultiplication(&p, k).RawBytes()
+	writeBytes(out, raw[:])
+	return nil
+}
+
+//export gnark_bn254_g2_add
+func gnark_bn254_g2_add(a *C.uint8_t, b *C.uint8_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := readBN254G2(a, "a")
+	if err != nil {
+		return cError(err)
+	}
+	q, err := readBN254G2(b, "b")
+	if err != nil {
+		return cError(err)
+	}
+	raw := p.Add(&p, &q).RawBytes()
+	writeBytes(out, raw[:])
+	return nil
+}
+
+//export gnark_bn254_g2_scalar_mul
+func gnark_bn254_g2_scalar_mul(a *C.uint8_t, scalar *C.uint8_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := readBN254G2(a, "a")
+	if err != nil {
+		return cError(err)
+	}
+	k, err := readBN254Scalar(scalar)
+	if err != nil {
+		return cError(err)
+	}
+	raw := p.ScalarMultiplication(&p, k).RawBytes()
+	writeBytes(out, raw[:])
+	return nil
+}
+
+// gnark_bn254_pairing_check sets *out to 1 if prod e(g1[i], g2[i]) == 1 over
+// the n pairs, 0 otherwise.
+//
+//export gnark_bn254_pairing_check
+func gnark_bn254_pairing_check(g1 *C.uint8_t, g2 *C.uint8_t, n C.uint64_t, out *C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ps := make([]bn254.G1Affine, n)
+	qs := make([]bn254.G2Affine, n)
+	for i := range ps {
+		var err error
+		g1i := (*C.uint8_t)(unsafe.Add(unsafe.Pointer(g1), i*bn254.SizeOfG1AffineUncompressed))
+		if ps[i], err = readBN254G1(g1i, fmt.Sprintf("G1 point %d", i)); err != nil {
+			return cError(err)
+		}
+		g2i := (*C.uint8_t)(unsafe.Add(unsafe.Pointer(g2), i*bn254.SizeOfG2AffineUncompressed))
+		if qs[i], err = readBN254G2(g2i, fmt.Sprintf("G2 point %d", i)); err != nil {
+			return cError(err)
+		}
+	}
+	ok, err := bn254.PairingCheck(ps, qs)
+	if err != nil {
+		return cErrorf(errInvalidInput, "pairing check: %w", err)
+	}
+	*out = 0
+	if ok {
+		*out = 1
+	}
+	return nil
+}
@@ -0,0 +1,156 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"crypto/rand"
+	"hash"
+	"io"
+
+	bn254mimc "github.com/consensys/gnark-crypto/ecc/bn254/fr/mimc"
+	bn254eddsa "github.com/consensys/gnark-crypto/ecc/bn254/twistededwards/eddsa"
+	"github.com/consensys/gnark-crypto/signature"
+)
+
+// EdDSA over the twisted Edwards curves embedded in BN254 and BLS12-381, as
+// verified in-circuit by gnark's std/signature/eddsa. Keys and signatures use
+// gnark-crypto's Bytes()/SetBytes() encodings; messages are hashed with the
+// MiMC of the embedding curve's scalar field, as the gadget does.
+
+// Edwards curve ids; keep in sync with eddsa::EdwardsCurve on the Rust side.
+const (
+	edwardsBabyJubjub = iota
+	edwardsJubjub
+	edwardsBandersnatch
+)
+
+// eddsaKeyGen generates a private key on one Edwards curve.
+type eddsaKeyGen func(r io.Reader) (signature.Signer, error)
+
+func edwardsCurve(curve C.int) (eddsaKeyGen, hash.Hash, error) {
+	switch curve {
+	case edwardsBabyJubjub:
+		return func(r io.Reader) (signature.Signer, error) { return bn254eddsa.GenerateKey(r) }, bn254mimc.NewMiMC(), nil
+	case edwardsJubjub, edwardsBandersnatch:
+		if bls12381Field == nil {
+			return nil, nil, errBLS12381Disabled
+		}
+		if curve == edwardsJubjub {
+			return jubjubKeyGen, bls12381Field.mimc(), nil
+		}
+		return bandersnatchKeyGen, bls12381Field.mimc(), nil
+	default:
+		return nil, nil, errorf(errInvalidInput, "unsupported edwards curve id %d", int(curve))
+	}
+}
+
+// eddsaSigner returns a signer for curve, loaded from priv if it is non-empty.
+func eddsaSigner(curve C.int, priv []byte) (signature.Signer, hash.Hash, error) {
+	generate, h, err := edwardsCurve(curve)
+	if err != nil {
+		return nil, nil, err
+	}
+	signer, err := generate(rand.Reader)
+	if err != nil {
+		return nil, nil, errorf(errOther, "failed to create eddsa key: %w", err)
+	}
+	if len(priv) > 0 {
+		if _, err := signer.SetBytes(priv); err != nil {
+			return nil, nil, errorf(errInvalidKey, "invalid eddsa private key: %w", err)
+		}
+	}
+	return signer, h, nil
+}
+
+// releaseBytes copies b into a C buffer the caller frees with gnark_free_buffer.
+func releaseBytes(b []byte, out_data **C.uint8_t, out_len *C.uint64_t) *C.char {
+	var buf cBuffer
+	if _, err := buf.Write(b); err != nil {
+		buf.free()
+		return cError(err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_eddsa_generate creates a private key from crypto/rand.
+//
+//export gnark_eddsa_generate
+func gnark_eddsa_generate(curve C.int, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	signer, _, err := eddsaSigner(curve, nil)
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(signer.Bytes(), out_data, out_len)
+}
+
+// gnark_eddsa_public_key derives the compressed public key of a private key.
+//
+//export gnark_eddsa_public_key
+func gnark_eddsa_public_key(curve C.int, key *C.uint8_t, key_len C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	signer, _, err := eddsaSigner(curve, borrowBytes(key, key_len))
+	if err != nil {
+		return cError(err)
+	}
+	return releaseBytes(signer.Public().Bytes(), out_data, out_len)
+}
+
+// gnark_eddsa_sign signs n 32-byte field elements.
+//
+//export gnark_eddsa_sign
+func gnark_eddsa_sign(
+	curve C.int,
+	key *C.uint8_t, key_len C.uint64_t,
+	msg *C.uint8_t, n C.uint64_t,
+	out_data **C.uint8_t, out_len *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	signer, h, err := eddsaSigner(curve, borrowBytes(key, key_len))
+	if err != nil {
+		return cError(err)
+	}
+	sig, err := signer.Sign(borrowBytes(msg, n*frBytes), h)
+	if err != nil {
+		return cErrorf(errInvalidInput, "eddsa sign failed: %w", err)
+	}
+	return releaseBytes(sig, out_data, out_len)
+}
+
+// gnark_eddsa_verify checks a signature over n 32-byte field elements and sets
+// *out_valid to 1 if it is valid, 0 otherwise.
+//
+//export gnark_eddsa_verify
+func gnark_eddsa_verify(
+	curve C.int,
+	public_key *C.uint8_t, public_key_len C.uint64_t,
+	msg *C.uint8_t, n C.uint64_t,
+	sig *C.uint8_t, sig_len C.uint64_t,
+	out_valid *C.int,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	signer, h, err := eddsaSigner(curve, nil)
+	if err != nil {
+		return cError(err)
+	}
+	pub := signer.Public()
+	if _, err := pub.SetBytes(borrowBytes(public_key, public_key_len)); err != nil {
+		return cErrorf(errInvalidKey, "invalid eddsa public key: %w", err)
+	}
+	ok, err := pub.Verify(borrowBytes(sig, sig_len), borrowBytes(msg, n*frBytes), h)
+	if err != nil {
+		// Malformed signatures and out-of-range messages are just invalid.
+		ok = false
+	}
+	*out_valid = 0
+	if ok {
+		*out_valid = 1
+	}
+	return nil
+}
@@ -0,0 +1,129 @@
+package main
+
+/*
+#include <stdlib.h>
+*/
+import "C"
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io/fs"
+	"regexp"
+	"runtime/debug"
+)
+
+// Error codes passed to the Rust side (GnarkError). Values are part of the
+// FFI contract: append only.
+const (
+	errOther = iota
+	errInvalidInput
+	errIO
+	errInvalidWitness
+	errWitnessSolveFailed
+	errInvalidKey
+	errCurveMismatch
+	errSerialization
+	errCancelled
+	errOutOfMemory
+	errInvalidProof
+	errInternalPanic
+)
+
+// errOutOfMemoryAlloc is returned when a C allocation fails.
+var errOutOfMemoryAlloc = errors.New("out of memory")
+
+// codedError attaches an error code to err.
+type codedError struct {
+	code int
+	err  error
+}
+
+func (e *codedError) Error() string { return e.err.Error() }
+func (e *codedError) Unwrap() error { return e.err }
+
+// errorf is fmt.Errorf with an error code.
+func errorf(code int, format string, args ...any) error {
+	return &codedError{code: code, err: fmt.Errorf(format, args...)}
+}
+
+// panicError is a recovered panic, with the stack of the goroutine that
+// panicked.
+type panicError struct {
+	value any
+	stack []byte
+}
+
+func (e *panicError) Error() string { return fmt.Sprintf("panic: %v", e.value) }
+
+// newPanicError wraps a value returned by recover. Call it from the deferred
+// function, so the stack still includes the panicking frames.
+func newPanicError(p any) error {
+	return &panicError{value: p, stack: debug.Stack()}
+}
+
+// catchPanic turns a panic into an errInternalPanic error in *out, so that it
+// reaches Rust as GnarkError::InternalPanic instead of aborting the process
+// (and the app embedding it). Every export that can report an error defers it
+// first; the rest (frees, cancellation, runtime statistics) call nothing that
+// panics.
+func catchPanic(out **C.char) {
+	if p := recover(); p != nil {
+		*out = cError(newPanicError(p))
+	}
+}
+
+// gnark's solver reports "constraint #<id> is not satisfied".
+var unsatisfiedConstraint = regexp.MustCompile(`constraint #(\d+) is not satisfied`)
+
+// cError encodes err for the C side as "<code>\x1f<constraint index>\x1f<message>",
+// where the constraint index is empty unless code is errWitnessSolveFailed.
+// For errInternalPanic the message is followed by "\x1e" and the stack.
+// The innermost codedError in the chain decides the code, since it is the
+// most specific. The caller frees the result with gnark_free_string.
+func cError(err error) *C.char {
+	code := errOther
+	var pathErr *fs.PathError
+	var panicErr *panicError
+	switch {
+	case errors.As(err, &panicErr):
+		msg := fmt.Sprintf("%s\x1e%s", err, panicErr.stack)
+		return C.CString(fmt.Sprintf("%d\x1f\x1f%s", errInternalPanic, msg))
+	case errors.Is(err, context.Canceled), errors.Is(err, context.DeadlineExceeded):
+		code = errCancelled
+	case errors.Is(err, errOutOfMemoryAlloc):
+		code = errOutOfMemory
+	default:
+		for e := err; e != nil; e = errors.Unwrap(e) {
+			if coded, ok := e.(*codedError); ok {
+				code = coded.code
+			}
+		}
+		if code == errOther && errors.As(err, &pathErr) {
+			code = errIO
+		}
+	}
+
+	msg := err.Error()
+	constraintIndex := ""
+	if m := unsatisfiedConstraint.FindStringSubmatch(msg); m != nil && code == errOther {
+		code = errWitnessSolveFailed
+		constraintIndex = m[1]
+	}
+	return C.CString(fmt.Sprintf("%d\x1f%s\x1f%s", code, constraintIndex, msg))
+}
+
+// cErrorf is cError(errorf(code, format, args...)).
+func cErrorf(code int, format string, args ...any) *C.char {
+	return cError(errorf(code, format, args...))
+}
+
+// gnark_debug_panic panics with message, for testing that panics reach the
+// caller as errors.
+//
+//export gnark_debug_panic
+func gnark_debug_panic(message *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	panic(C.GoString(message))
+}
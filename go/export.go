@@ -0,0 +1,377 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bufio"
+	"encoding/binary"
+	"encoding/json"
+	"io"
+	"math/big"
+	"slices"
+	"strconv"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+)
+
+// Constraint systems exported for external tools: a documented JSON dump
+// (R1CS and SparseR1CS) and zkInterface's FlatBuffers messages (R1CS).
+
+// jsonDumpVersion is the "version" of gnark_circuit_export_json's output;
+// bump it on any change to the format documented in crates/src/circuit.rs.
+const jsonDumpVersion = 1
+
+// gnark_circuit_export_json writes a compiled circuit's wires and
+// constraints to path as JSON, in the format CompiledCircuit::export_json
+// documents.
+//
+//export gnark_circuit_export_json
+func gnark_circuit_export_json(handle C.uint64_t, path *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	if _, err := systemOf(ccs); err != nil {
+		return cError(err)
+	}
+	err = writeFile(C.GoString(path), func(w io.Writer) (int64, error) {
+		return 0, writeJSONDump(bufio.NewWriter(w), ccs)
+	})
+	if err != nil {
+		return cErrorf(errIO, "failed to export circuit: %w", err)
+	}
+	return nil
+}
+
+// writeJSONDump streams the dump, since large circuits have millions of
+// constraints.
+func writeJSONDump(w *bufio.Writer, ccs constraint.ConstraintSystem) error {
+	sys, _ := systemOf(ccs)
+	var coeffs []fr.Element
+	kind := "r1cs"
+	switch cs := ccs.(type) {
+	case *cs_bn254.R1CS:
+		coeffs = cs.Coefficients
+	case *cs_bn254.SparseR1CS:
+		coeffs = cs.Coefficients
+		kind = "sparse_r1cs"
+	}
+	coeff := func(id uint32) string {
+		return coeffs[id].BigInt(new(big.Int)).String()
+	}
+	str := func(s string) string {
+		b, _ := json.Marshal(s)
+		return string(b)
+	}
+	names := func(list []string) string {
+		b, _ := json.Marshal(list)
+		return string(b)
+	}
+
+	w.WriteString(`{"version":` + strconv.Itoa(jsonDumpVersion))
+	w.WriteString(`,"curve":"bn254","system":` + str(kind))
+	w.WriteString(`,"field":` + str(fr.Modulus().String()))
+	w.WriteString(`,"public":` + names(sys.Public))
+	w.WriteString(`,"secret":` + names(sys.Secret))
+	w.WriteString(`,"internal":` + strconv.Itoa(ccs.GetNbInternalVariables()))
+	w.WriteString(`,"constraints":[`)
+	switch cs := ccs.(type) {
+	case *cs_bn254.R1CS:
+		linear := func(l constraint.LinearExpression) {
+			w.WriteByte('[')
+			for i, t := range l {
+				if i > 0 {
+					w.WriteByte(',')
+				}
+				w.WriteString("[" + strconv.Itoa(t.WireID()) + `,"` + coeff(t.CID) + `"]`)
+			}
+			w.WriteByte(']')
+		}
+		for i, c := range cs.GetR1Cs() {
+			if i > 0 {
+				w.WriteByte(',')
+			}
+			w.WriteString(`{"a":`)
+			linear(c.L)
+			w.WriteString(`,"b":`)
+			linear(c.R)
+			w.WriteString(`,"c":`)
+			linear(c.O)
+			w.WriteByte('}')
+		}
+	case *cs_bn254.SparseR1CS:
+		for i, c := range cs.GetSparseR1Cs() {
+			if i > 0 {
+				w.WriteByte(',')
+			}
+			w.WriteString(`{"xa":` + strconv.Itoa(int(c.XA)) +
+				`,"xb":` + strconv.Itoa(int(c.XB)) +
+				`,"xc":` + strconv.Itoa(int(c.XC)) +
+				`,"ql":"` + coeff(c.QL) + `","qr":"` + coeff(c.QR) +
+				`","qo":"` + coeff(c.QO) + `","qm":"` + coeff(c.QM) +
+				`","qc":"` + coeff(c.QC) + `"}`)
+		}
+	}
+	w.WriteString("]}\n")
+	return w.Flush()
+}
+
+// gnark_circuit_export_zkif writes a compiled R1CS to path as a zkInterface
+// CircuitHeader message followed by a ConstraintSystem message.
+//
+// gnark's wire numbering is zkInterface's: wire 0 is the constant one, the
+// public inputs follow (the header's instance variables), then the secret
+// inputs and internal wires.
+//
+//export gnark_circuit_export_zkif
+func gnark_circuit_export_zkif(handle C.uint64_t, path *C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	r1cs, ok := ccs.(*cs_bn254.R1CS)
+	if !ok {
+		return cErrorf(errInvalidInput, "zkInterface export needs an R1CS, got %T", ccs)
+	}
+	err = writeFile(C.GoString(path), func(w io.Writer) (int64, error) {
+		n, err := w.Write(zkifHeader(r1cs))
+		if err != nil {
+			return int64(n), err
+		}
+		m, err := w.Write(zkifConstraints(r1cs))
+		return int64(n + m), err
+	})
+	if err != nil {
+		return cErrorf(errIO, "failed to export circuit: %w", err)
+	}
+	return nil
+}
+
+// zkInterface message types, the Message union of zkinterface.fbs.
+const (
+	zkifCircuitHeader    = 1
+	zkifConstraintSystem = 2
+)
+
+// zkifElement is the little-endian encoding zkInterface uses for field
+// elements, fr.Bytes wide.
+func zkifElement(e *fr.Element) []byte {
+	b := e.Bytes()
+	slices.Reverse(b[:])
+	return b[:]
+}
+
+func zkifHeader(cs *cs_bn254.R1CS) []byte {
+	internal, secret, public := cs.GetNbVariables()
+	instance := make([]uint64, public-1)
+	for i := range instance {
+		instance[i] = uint64(i + 1)
+	}
+	var max fr.Element
+	max.SetOne()
+	max.Neg(&max)
+	return fbMessage(zkifCircuitHeader, fbTable{
+		// instance_variables, free_variable_id, field_maximum
+		fbTable{fbU64s(instance)},
+		fbU64(uint64(internal + secret + public)),
+		fbBytes(zkifElement(&max)),
+	})
+}
+
+func zkifConstraints(cs *cs_bn254.R1CS) []byte {
+	variables := func(l constraint.LinearExpression) fbTable {
+		ids := make([]uint64, len(l))
+		values := make([]byte, 0, fr.Bytes*len(l))
+		for i, t := range l {
+			ids[i] = uint64(t.WireID())
+			values = append(values, zkifElement(&cs.Coefficients[t.CID])...)
+		}
+		// variable_ids, values
+		return fbTable{fbU64s(ids), fbBytes(values)}
+	}
+	r1cs := cs.GetR1Cs()
+	constraints := make(fbTables, len(r1cs))
+	for i, c := range r1cs {
+		// linear_combination_a, _b, _c
+		constraints[i] = fbTable{variables(c.L), variables(c.R), variables(c.O)}
+	}
+	// constraints
+	return fbMessage(zkifConstraintSystem, fbTable{constraints})
+}
+
+// A minimal FlatBuffers writer, for the few zkInterface tables above.
+// Objects are written front to back, each reference before what it refers
+// to, since offsets are unsigned and point forward.
+
+// fbObject is a table field or vector element. A nil field is absent.
+type fbObject interface {
+	// write appends the object at its alignment and returns its position.
+	write(w *fbWriter) int
+}
+
+// fbU64 is a uint64 table field.
+type fbU64 uint64
+
+// fbU8 is a ubyte table field.
+type fbU8 uint8
+
+// fbTable is a table, with its fields in schema order.
+type fbTable []fbObject
+
+// fbTables is a vector of tables.
+type fbTables []fbObject
+
+// fbU64s is a vector of uint64.
+type fbU64s []uint64
+
+// fbBytes is a vector of ubyte.
+type fbBytes []byte
+
+type fbWriter struct {
+	buf []byte
+}
+
+func (w *fbWriter) align(n int) {
+	for len(w.buf)%n != 0 {
+		w.buf = append(w.buf, 0)
+	}
+}
+
+func (w *fbWriter) u16(v uint16) { w.buf = binary.LittleEndian.AppendUint16(w.buf, v) }
+func (w *fbWriter) u32(v uint32) { w.buf = binary.LittleEndian.AppendUint32(w.buf, v) }
+
+// patch points the uoffset at slot to pos.
+func (w *fbWriter) patch(slot, pos int) {
+	binary.LittleEndian.PutUint32(w.buf[slot:], uint32(pos-slot))
+}
+
+func (fbU64) write(*fbWriter) int { panic("scalars are written inline") }
+func (fbU8) write(*fbWriter) int { panic("scalars are written inline") }
+
+func (t fbTable) write(w *fbWriter) int {
+	// Inline layout: the vtable soffset, 8-byte scalars, uoffsets, bytes.
+	offsets := make([]uint16, len(t))
+	size := 4
+	hasU64 := false
+	for _, f := range t {
+		if _, ok := f.(fbU64); ok {
+			hasU64 = true
+		}
+	}
+	if hasU64 {
+		size = 8
+	}
+	place := func(match func(fbObject) bool, width int) {
+		for i, f := range t {
+			if f != nil && match(f) {
+				offsets[i] = uint16(size)
+				size += width
+			}
+		}
+	}
+	place(func(f fbObject) bool { _, ok := f.(fbU64); return ok }, 8)
+	place(func(f fbObject) bool {
+		switch f.(type) {
+		case fbU64, fbU8:
+			return false
+		}
+		return true
+	}, 4)
+	place(func(f fbObject) bool { _, ok := f.(fbU8); return ok }, 1)
+	for size%4 != 0 {
+		size++
+	}
+
+	w.align(2)
+	vtable := len(w.buf)
+	w.u16(uint16(4 + 2*len(t)))
+	w.u16(uint16(size))
+	for _, o := range offsets {
+		w.u16(o)
+	}
+	if hasU64 {
+		w.align(8)
+	} else {
+		w.align(4)
+	}
+	pos := len(w.buf)
+	w.buf = append(w.buf, make([]byte, size)...)
+	binary.LittleEndian.PutUint32(w.buf[pos:], uint32(int32(pos-vtable)))
+	for i, f := range t {
+		switch v := f.(type) {
+		case nil:
+		case fbU64:
+			binary.LittleEndian.PutUint64(w.buf[pos+int(offsets[i]):], uint64(v))
+		case fbU8:
+			w.buf[pos+int(offsets[i])] = byte(v)
+		}
+	}
+	for i, f := range t {
+		switch f.(type) {
+		case nil, fbU64, fbU8:
+			continue
+		}
+		slot := pos + int(offsets[i])
+		w.patch(slot, f.write(w))
+	}
+	return pos
+}
+
+func (v fbTables) write(w *fbWriter) int {
+	w.align(4)
+	pos := len(w.buf)
+	w.u32(uint32(len(v)))
+	slots := len(w.buf)
+	w.buf = append(w.buf, make([]byte, 4*len(v))...)
+	for i, t := range v {
+		w.patch(slots+4*i, t.write(w))
+	}
+	return pos
+}
+
+func (v fbU64s) write(w *fbWriter) int {
+	// The elements, after the length, are 8-byte aligned.
+	w.align(4)
+	if len(w.buf)%8 == 0 {
+		w.u32(0)
+	}
+	pos := len(w.buf)
+	w.u32(uint32(len(v)))
+	for _, x := range v {
+		w.buf = binary.LittleEndian.AppendUint64(w.buf, x)
+	}
+	return pos
+}
+
+func (v fbBytes) write(w *fbWriter) int {
+	w.align(4)
+	pos := len(w.buf)
+	w.u32(uint32(len(v)))
+	w.buf = append(w.buf, v...)
+	return pos
+}
+
+// fbMessage encodes a size-prefixed zkInterface Root holding message of
+// type kind, with the "zkif" file identifier.
+func fbMessage(kind uint8, message fbTable) []byte {
+	w := &fbWriter{}
+	w.u32(0) // size prefix
+	w.u32(0) // root table
+	w.buf = append(w.buf, "zkif"...)
+	// Root { message_type, message }
+	w.patch(4, fbTable{fbU8(kind), message}.write(w))
+	// The aligned positions above are relative to the message start, which
+	// stays aligned in a file of several messages.
+	w.align(8)
+	binary.LittleEndian.PutUint32(w.buf, uint32(len(w.buf)-4))
+	return w.buf
+}
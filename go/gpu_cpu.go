@@ -0,0 +1,37 @@
+//go:build !icicle && !verifier
+
+package main
+
+import (
+	"github.com/consensys/gnark/backend"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/backend/witness"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+)
+
+// gpuProverOptions always falls back to CPU proving when the library is built
+// without the icicle tag (the Rust `gpu` feature).
+func gpuProverOptions(deviceID int) ([]backend.ProverOption, string) {
+	return nil, "built without GPU support, proving on CPU"
+}
+
+func newProvingKey() provingKey {
+	return new(groth16_bn254.ProvingKey)
+}
+
+// bn254ProvingKey returns the key's gnark BN254 proving key.
+func bn254ProvingKey(pk provingKey) *groth16_bn254.ProvingKey {
+	return pk.(*groth16_bn254.ProvingKey)
+}
+
+func setupBN254(r1cs *cs_bn254.R1CS) (provingKey, *groth16_bn254.VerifyingKey, error) {
+	pk, vk := new(groth16_bn254.ProvingKey), new(groth16_bn254.VerifyingKey)
+	if err := groth16_bn254.Setup(r1cs, pk, vk); err != nil {
+		return nil, nil, err
+	}
+	return pk, vk, nil
+}
+
+func proveBN254(r1cs *cs_bn254.R1CS, pk provingKey, full witness.Witness, opts ...backend.ProverOption) (*groth16_bn254.Proof, error) {
+	return groth16_bn254.Prove(r1cs, pk.(*groth16_bn254.ProvingKey), full, opts...)
+}
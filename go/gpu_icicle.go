@@ -0,0 +1,70 @@
+//go:build icicle && !verifier
+
+package main
+
+import (
+	"fmt"
+	"sync"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend"
+	"github.com/consensys/gnark/backend/groth16"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	icicle_bn254 "github.com/consensys/gnark/backend/groth16/bn254/icicle"
+	"github.com/consensys/gnark/backend/witness"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	icicle_runtime "github.com/ingonyama-zk/icicle-gnark/v3/wrappers/golang/runtime"
+)
+
+// gpuProverOptions selects CUDA device deviceID for ICICLE-accelerated MSM/NTT.
+// It returns no options (plain CPU proving) when the device is not available,
+// together with a warning describing why.
+func gpuProverOptions(deviceID int) ([]backend.ProverOption, string) {
+	if err := icicle_runtime.LoadBackendFromEnvOrDefault(); err != icicle_runtime.Success {
+		return nil, fmt.Sprintf("ICICLE backend not loaded (%v), proving on CPU", err)
+	}
+	device := icicle_runtime.CreateDevice("CUDA", deviceID)
+	if !icicle_runtime.IsDeviceAvailable(&device) {
+		return nil, fmt.Sprintf("CUDA device %d not available, proving on CPU", deviceID)
+	}
+	if err := icicle_runtime.SetDevice(&device); err != icicle_runtime.Success {
+		return nil, fmt.Sprintf("failed to select CUDA device %d (%v), proving on CPU", deviceID, err)
+	}
+	return []backend.ProverOption{backend.WithIcicleAcceleration()}, ""
+}
+
+// GPU builds go through the curve-generic groth16 API, which is where gnark
+// switches to ICICLE's proving key and prover; this links every curve's
+// prover regardless of the curve features.
+
+func newProvingKey() provingKey {
+	return groth16.NewProvingKey(ecc.BN254)
+}
+
+// bn254ProvingKey returns the CPU-side key inside ICICLE's proving key.
+func bn254ProvingKey(pk provingKey) *groth16_bn254.ProvingKey {
+	return pk.(*icicle_bn254.ProvingKey).ProvingKey
+}
+
+func setupBN254(r1cs *cs_bn254.R1CS) (provingKey, *groth16_bn254.VerifyingKey, error) {
+	pk, vk, err := groth16.Setup(r1cs)
+	if err != nil {
+		return nil, nil, err
+	}
+	return pk, vk.(*groth16_bn254.VerifyingKey), nil
+}
+
+// ICICLE copies a proving key to the device on its first proof without any
+// locking, and concurrent proofs would only contend for the same device, so GPU
+// proofs run one at a time.
+var icicleProveMu sync.Mutex
+
+func proveBN254(r1cs *cs_bn254.R1CS, pk provingKey, full witness.Witness, opts ...backend.ProverOption) (*groth16_bn254.Proof, error) {
+	icicleProveMu.Lock()
+	defer icicleProveMu.Unlock()
+	proof, err := groth16.Prove(r1cs, pk.(groth16.ProvingKey), full, opts...)
+	if err != nil {
+		return nil, err
+	}
+	return proof.(*groth16_bn254.Proof), nil
+}
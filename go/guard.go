@@ -0,0 +1,182 @@
+package main
+
+import (
+	"encoding/binary"
+	"fmt"
+
+	"github.com/consensys/gnark-crypto/ecc"
+)
+
+// gnark-crypto's decoders allocate a slice as soon as they read its uint32
+// length prefix, so four bytes of untrusted input can ask for hundreds of
+// gigabytes and kill the process, which catchPanic cannot recover. The checks
+// below walk a proof, verifying key or public witness encoding without
+// decoding any point and reject it if a length prefix claims more elements
+// than the bytes left could hold. They return uncoded errors: the caller's
+// code (errSerialization, errInvalidKey) applies.
+
+// Point encoding flags in the top two bits of a point's first byte; every
+// flag but uncompressed means the compressed size.
+const (
+	pointFlagMask     byte = 0b11 << 6
+	pointUncompressed byte = 0b00 << 6
+)
+
+// encodingGuard is a cursor over an encoding being checked.
+type encodingGuard struct {
+	b          []byte
+	g1, g2, fr uint64 // compressed G1 and G2 and scalar sizes
+}
+
+func newEncodingGuard(curve ecc.ID, b []byte) *encodingGuard {
+	g := &encodingGuard{b: b, fr: uint64((curve.ScalarField().BitLen() + 7) / 8)}
+	switch curve {
+	case ecc.BLS12_381, ecc.BLS12_377:
+		g.g1, g.g2 = 48, 96
+	case ecc.BW6_761:
+		g.g1, g.g2 = 96, 96
+	default:
+		g.g1, g.g2 = 32, 64
+	}
+	return g
+}
+
+func (g *encodingGuard) empty() bool { return len(g.b) == 0 }
+
+func (g *encodingGuard) skip(n uint64, field string) error {
+	if n > uint64(len(g.b)) {
+		return fmt.Errorf("%s: needs %d bytes, %d left", field, n, len(g.b))
+	}
+	g.b = g.b[n:]
+	return nil
+}
+
+func (g *encodingGuard) u32(field string) (uint64, error) {
+	if len(g.b) < 4 {
+		return 0, fmt.Errorf("%s: unexpected end of input", field)
+	}
+	n := binary.BigEndian.Uint32(g.b)
+	g.b = g.b[4:]
+	return uint64(n), nil
+}
+
+// count reads a slice length and checks that n elements of at least size bytes
+// each fit in what is left.
+func (g *encodingGuard) count(size uint64, field string) (uint64, error) {
+	n, err := g.u32(field)
+	if err != nil {
+		return 0, err
+	}
+	if n*size > uint64(len(g.b)) {
+		return 0, fmt.Errorf("%s: length %d does not fit in the %d bytes left", field, n, len(g.b))
+	}
+	return n, nil
+}
+
+// point skips a point whose compressed encoding takes size bytes.
+func (g *encodingGuard) point(size uint64, field string) error {
+	if g.empty() {
+		return fmt.Errorf("%s: unexpected end of input", field)
+	}
+	if g.b[0]&pointFlagMask == pointUncompressed {
+		size *= 2
+	}
+	return g.skip(size, field)
+}
+
+func (g *encodingGuard) points(size uint64, field string) error {
+	n, err := g.count(size, "len("+field+")")
+	if err != nil {
+		return err
+	}
+	for i := uint64(0); i < n; i++ {
+		if err := g.point(size, field); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// checkProofEncoding checks a Groth16 proof's encoding (proof.WriteTo or
+// WriteRawTo): Ar, Bs, Krs, then optionally the commitments and their proof
+// of knowledge.
+func checkProofEncoding(curve ecc.ID, b []byte) error {
+	g := newEncodingGuard(curve, b)
+	if err := g.point(g.g1, "proof: Ar"); err != nil {
+		return err
+	}
+	if err := g.point(g.g2, "proof: Bs"); err != nil {
+		return err
+	}
+	if err := g.point(g.g1, "proof: Krs"); err != nil {
+		return err
+	}
+	if g.empty() {
+		return nil
+	}
+	if err := g.points(g.g1, "proof: Commitments"); err != nil {
+		return err
+	}
+	return g.point(g.g1, "proof: CommitmentPok")
+}
+
+// checkVerifyingKeyEncoding checks a Groth16 verifying key's encoding: the
+// six fixed points, K, then optionally the committed wire lists and the
+// Pedersen keys.
+func checkVerifyingKeyEncoding(curve ecc.ID, b []byte) error {
+	g := newEncodingGuard(curve, b)
+	for _, p := range []struct {
+		size  uint64
+		field string
+	}{
+		{g.g1, "vk: [α]1"}, {g.g1, "vk: [β]1"}, {g.g2, "vk: [β]2"},
+		{g.g2, "vk: [γ]2"}, {g.g1, "vk: [δ]1"}, {g.g2, "vk: [δ]2"},
+	} {
+		if err := g.point(p.size, p.field); err != nil {
+			return err
+		}
+	}
+	if err := g.points(g.g1, "vk: K"); err != nil {
+		return err
+	}
+	if g.empty() {
+		return nil
+	}
+	nbCommitted, err := g.count(4, "vk: len(PublicAndCommitmentCommitted)")
+	if err != nil {
+		return err
+	}
+	for i := uint64(0); i < nbCommitted; i++ {
+		n, err := g.count(8, "vk: len(PublicAndCommitmentCommitted[i])")
+		if err != nil {
+			return err
+		}
+		if err := g.skip(8*n, "vk: PublicAndCommitmentCommitted"); err != nil {
+			return err
+		}
+	}
+	nbKeys, err := g.count(2*g.g2, "vk: len(CommitmentKeys)")
+	if err != nil {
+		return err
+	}
+	for i := uint64(0); i < 2*nbKeys; i++ {
+		if err := g.point(g.g2, "vk: CommitmentKeys"); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// checkPublicWitnessEncoding checks a witness.MarshalBinary encoding: the
+// public and secret counts, then a length-prefixed vector of scalars.
+func checkPublicWitnessEncoding(curve ecc.ID, b []byte) error {
+	g := newEncodingGuard(curve, b)
+	if err := g.skip(8, "witness: header"); err != nil {
+		return err
+	}
+	n, err := g.count(g.fr, "witness: len(vector)")
+	if err != nil {
+		return err
+	}
+	return g.skip(n*g.fr, "witness: vector")
+}
@@ -0,0 +1,56 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"fmt"
+	"sync"
+)
+
+// Objects that must outlive a single FFI call (deserialized keys, sessions, ...)
+// live in this registry and are referred to from C by an opaque id, since Go
+// pointers cannot be retained by C code. Id 0 is never issued.
+var (
+	handleMu   sync.Mutex
+	handleNext uint64
+	handles    = map[uint64]any{}
+)
+
+// newHandle registers v and returns its id.
+func newHandle(v any) C.uint64_t {
+	handleMu.Lock()
+	defer handleMu.Unlock()
+	handleNext++
+	handles[handleNext] = v
+	return C.uint64_t(handleNext)
+}
+
+// lookupHandle returns the object registered under h, which must be of type T.
+func lookupHandle[T any](h C.uint64_t) (T, error) {
+	handleMu.Lock()
+	v, ok := handles[uint64(h)]
+	handleMu.Unlock()
+
+	var zero T
+	if !ok {
+		return zero, fmt.Errorf("unknown handle %d", uint64(h))
+	}
+	t, ok := v.(T)
+	if !ok {
+		return zero, fmt.Errorf("handle %d holds %T, not %T", uint64(h), v, zero)
+	}
+	return t, nil
+}
+
+// gnark_handle_free releases the object registered under h. The memory is
+// reclaimed by the Go GC once no in-flight call still uses it.
+//
+//export gnark_handle_free
+func gnark_handle_free(h C.uint64_t) {
+	handleMu.Lock()
+	delete(handles, uint64(h))
+	handleMu.Unlock()
+}
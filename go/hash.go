@@ -0,0 +1,117 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"hash"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	bn254fr "github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	bn254mimc "github.com/consensys/gnark-crypto/ecc/bn254/fr/mimc"
+	bn254poseidon2 "github.com/consensys/gnark-crypto/ecc/bn254/fr/poseidon2"
+)
+
+// Host-side versions of the hashes gnark's std library computes in-circuit,
+// so that witness values (commitments, Merkle roots, ...) match exactly.
+// Field elements cross the FFI as 32-byte big-endian canonical encodings.
+
+// Curve ids for the hash and ecc exports; keep in sync with hash::Curve on the Rust side.
+const (
+	hashCurveBN254 = iota
+	hashCurveBLS12_381
+)
+
+const frBytes = 32
+
+// fieldSupport holds the host-side primitives of one curve.
+type fieldSupport struct {
+	poseidon2 func() hash.Hash
+	mimc      func() hash.Hash
+	// canonical checks that a 32-byte input is a canonical field element.
+	canonical func([]byte) error
+	// msmG1 and fft back gnark_msm_g1 and gnark_fft (see ecc.go); inputs are
+	// already sized for n elements.
+	msmG1 func(points, scalars []byte, n int) ([]byte, error)
+	fft   func(coeffs []byte, inverse bool) error
+	// g1Size is the size of an uncompressed G1 point, fftMaxLog the log2 of
+	// the largest power-of-two subgroup of the scalar field.
+	g1Size    int
+	fftMaxLog int
+}
+
+var bn254Field = &fieldSupport{
+	poseidon2: func() hash.Hash { return bn254poseidon2.NewMerkleDamgardHasher() },
+	mimc:      func() hash.Hash { return bn254mimc.NewMiMC() },
+	canonical: func(b []byte) error {
+		var e bn254fr.Element
+		return e.SetBytesCanonical(b)
+	},
+	msmG1:     bn254MSMG1,
+	fft:       bn254FFT,
+	g1Size:    bn254.SizeOfG1AffineUncompressed,
+	fftMaxLog: 28,
+}
+
+// errBLS12381Disabled is returned for BLS12-381 requests when the library is
+// built with the no_bls12_381 tag (the Rust crate's bls12-381 feature is off).
+var errBLS12381Disabled = errorf(errInvalidInput, "BLS12-381 support is not compiled in (enable the bls12-381 feature)")
+
+// fieldOf returns the scalar-field primitives of curve.
+func fieldOf(curve C.int) (*fieldSupport, error) {
+	switch curve {
+	case hashCurveBN254:
+		return bn254Field, nil
+	case hashCurveBLS12_381:
+		if bls12381Field == nil {
+			return nil, errBLS12381Disabled
+		}
+		return bls12381Field, nil
+	default:
+		return nil, errorf(errInvalidInput, "unsupported curve id %d", int(curve))
+	}
+}
+
+// hashElements hashes the n field elements at inputs with the hasher hasher
+// picks for curve and stores the 32-byte digest at out.
+func hashElements(curve C.int, hasher func(*fieldSupport) func() hash.Hash, inputs *C.uint8_t, n C.uint64_t, out *C.uint8_t) *C.char {
+	field, err := fieldOf(curve)
+	if err != nil {
+		return cError(err)
+	}
+	h := hasher(field)()
+	data := borrowBytes(inputs, n*frBytes)
+	for i := 0; i < len(data); i += frBytes {
+		if err := field.canonical(data[i : i+frBytes]); err != nil {
+			return cErrorf(errInvalidInput, "input %d is not a canonical field element: %w", i/frBytes, err)
+		}
+		h.Write(data[i : i+frBytes])
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), frBytes), h.Sum(nil))
+	return nil
+}
+
+// gnark_poseidon2 hashes n field elements with gnark-crypto's Poseidon2
+// Merkle-Damgård hasher (default parameters), the construction used by
+// gnark's std/hash/poseidon2. out must point to 32 writable bytes.
+//
+//export gnark_poseidon2
+func gnark_poseidon2(curve C.int, inputs *C.uint8_t, n C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	return hashElements(curve, func(f *fieldSupport) func() hash.Hash { return f.poseidon2 }, inputs, n, out)
+}
+
+// gnark_mimc hashes n field elements with gnark-crypto's MiMC (Miyaguchi-Preneel
+// over the default MiMC permutation), the construction used by gnark's
+// std/hash/mimc. out must point to 32 writable bytes.
+//
+//export gnark_mimc
+func gnark_mimc(curve C.int, inputs *C.uint8_t, n C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	return hashElements(curve, func(f *fieldSupport) func() hash.Hash { return f.mimc }, inputs, n, out)
+}
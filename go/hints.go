@@ -0,0 +1,86 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+
+// Rust hint callback: computes the n_out outputs of hint id from its n_in
+// inputs, all as 32-byte big-endian field elements. On failure it returns
+// non-zero and writes a NUL-terminated message of at most err_cap bytes to err.
+typedef int (*gnark_hint_cb)(uint64_t id, const uint8_t *inputs, uint64_t n_in,
+                             uint8_t *outputs, uint64_t n_out, char *err, uint64_t err_cap);
+
+// Go cannot call C function pointers directly.
+static inline int gnark_call_hint(gnark_hint_cb cb, uint64_t id, const uint8_t *inputs, uint64_t n_in,
+                                  uint8_t *outputs, uint64_t n_out, char *err, uint64_t err_cap) {
+    return cb(id, inputs, n_in, outputs, n_out, err, err_cap);
+}
+*/
+import "C"
+
+import (
+	"math/big"
+	"sync"
+	"unsafe"
+)
+
+// Hints implemented in Rust. Every such hint is the one solver hint hintRust,
+// whose first input is the id the Rust side registered the closure under;
+// the Rust registry resolves ids, so registering a closure needs no call here.
+
+var (
+	rustHintMu sync.RWMutex
+	rustHintCb C.gnark_hint_cb
+)
+
+// gnark_set_hint_callback sets the callback hintRust forwards to. A NULL cb
+// makes Rust hints fail.
+//
+//export gnark_set_hint_callback
+func gnark_set_hint_callback(cb C.gnark_hint_cb) {
+	rustHintMu.Lock()
+	defer rustHintMu.Unlock()
+	rustHintCb = cb
+}
+
+// hintRust computes outputs with the Rust closure registered under
+// inputs[0], from the remaining inputs. The solver may call it from several
+// goroutines at once.
+func hintRust(field *big.Int, inputs, outputs []*big.Int) error {
+	if len(inputs) == 0 {
+		return errorf(errInvalidInput, "rust hint: missing hint id")
+	}
+	id := inputs[0].Uint64()
+	rustHintMu.RLock()
+	cb := rustHintCb
+	rustHintMu.RUnlock()
+	if cb == nil {
+		return errorf(errInvalidWitness, "hint %d: no Rust hints are registered", id)
+	}
+
+	args := inputs[1:]
+	// One spare byte keeps &buf[0] valid for hints without inputs.
+	in := make([]byte, 32*len(args)+1)
+	out := make([]byte, 32*len(outputs)+1)
+	// Hint values are witness values; don't leave them in freed buffers.
+	defer clear(in)
+	defer clear(out)
+	for i, v := range args {
+		new(big.Int).Mod(v, field).FillBytes(in[32*i : 32*(i+1)])
+	}
+	msg := make([]byte, 512)
+	if C.gnark_call_hint(cb, C.uint64_t(id),
+		(*C.uint8_t)(&in[0]), C.uint64_t(len(args)),
+		(*C.uint8_t)(&out[0]), C.uint64_t(len(outputs)),
+		(*C.char)(unsafe.Pointer(&msg[0])), C.uint64_t(len(msg))) != 0 {
+		return errorf(errInvalidWitness, "hint %d: %s", id, C.GoString((*C.char)(unsafe.Pointer(&msg[0]))))
+	}
+	for i := range outputs {
+		outputs[i].SetBytes(out[32*i : 32*(i+1)])
+		if outputs[i].Cmp(field) >= 0 {
+			return errorf(errInvalidWitness, "hint %d: output %d is not a field element", id, i)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,243 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bufio"
+	"encoding/binary"
+	"io"
+	"os"
+	"runtime/debug"
+	"strings"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+)
+
+// Versioned key files: gnark's own encoding of a key after a short header,
+//
+//	magic "GNRKKEY\0" | u16 file version | u8 kind | u8 format | u8 curve | u8 n | n-byte gnark version
+//
+// (big-endian, like gnark), where curve is gnark-crypto's ecc.ID. Version 1
+// files have no curve byte; they are all BN254. The curve and gnark version
+// are checked before decoding, so a key for another curve or from an
+// incompatible gnark fails with an error naming the mismatch instead of
+// somewhere inside the decoder, and tooling can tell what a file holds from
+// its header alone (gnark_artifact_sniff).
+
+const (
+	keyFileMagic   = "GNRKKEY\x00"
+	keyFileVersion = 2
+)
+
+const (
+	keyKindGroth16PK = 1
+	keyKindGroth16VK = 2
+)
+
+var keyKindNames = map[byte]string{
+	keyKindGroth16PK: "a Groth16 proving key",
+	keyKindGroth16VK: "a Groth16 verifying key",
+}
+
+// gnarkVersion is the version of the gnark module linked into this library,
+// or "" if the build carries no module information.
+func gnarkVersion() string {
+	if info, ok := debug.ReadBuildInfo(); ok {
+		for _, dep := range info.Deps {
+			if dep.Path == "github.com/consensys/gnark" {
+				return dep.Version
+			}
+		}
+	}
+	return ""
+}
+
+// gnarkCompatible reports whether keys written by gnark version a decode with
+// version b. gnark is pre-1.0 and changes its encodings between minor
+// releases, so the major and minor versions must match.
+func gnarkCompatible(a, b string) bool {
+	minor := func(v string) string {
+		parts := strings.SplitN(v, ".", 3)
+		if len(parts) < 2 {
+			return v
+		}
+		return parts[0] + "." + parts[1]
+	}
+	return minor(a) == minor(b)
+}
+
+// writeKeyFile writes obj to path in format behind a header for kind. A
+// partially written file is removed.
+func writeKeyFile(path string, kind byte, format C.int, obj gnarkEncoder) error {
+	write := func(w io.Writer) (int64, error) {
+		return 0, writeKey(w, kind, format, obj)
+	}
+	if err := writeFile(path, write); err != nil {
+		os.Remove(path)
+		return err
+	}
+	return nil
+}
+
+// writeKey writes obj to w as a versioned key file for kind, in format.
+func writeKey(w io.Writer, kind byte, format C.int, obj gnarkEncoder) error {
+	version := gnarkVersion()
+	header := binary.BigEndian.AppendUint16([]byte(keyFileMagic), keyFileVersion)
+	header = append(header, kind, byte(format), byte(ecc.BN254), byte(len(version)))
+	header = append(header, version...)
+	if _, err := w.Write(header); err != nil {
+		return err
+	}
+	return writeWithFormat(obj, format, w)
+}
+
+// keyFileHeader is the decoded header of a versioned key file.
+type keyFileHeader struct {
+	version uint16
+	kind    byte
+	format  byte
+	curve   ecc.ID
+	gnark   string
+}
+
+// readKeyFileHeader reads a key file header from r, leaving r at the key.
+func readKeyFileHeader(r io.Reader) (keyFileHeader, error) {
+	var head struct {
+		Magic   [8]byte
+		Version uint16
+		Kind    byte
+		Format  byte
+	}
+	if err := binary.Read(r, binary.BigEndian, &head); err != nil || string(head.Magic[:]) != keyFileMagic {
+		return keyFileHeader{}, errorf(errSerialization, "not a versioned key file (keys written by gnark itself are read with open)")
+	}
+	if head.Version > keyFileVersion {
+		return keyFileHeader{}, errorf(errSerialization, "key file version %d is newer than this library supports (%d)", head.Version, keyFileVersion)
+	}
+	header := keyFileHeader{version: head.Version, kind: head.Kind, format: head.Format, curve: ecc.BN254}
+	// The curve byte (from version 2), then the gnark version's length.
+	rest := make([]byte, 1, 2)
+	if head.Version >= 2 {
+		rest = rest[:2]
+	}
+	if _, err := io.ReadFull(r, rest); err != nil {
+		return keyFileHeader{}, errorf(errSerialization, "truncated key file header: %w", err)
+	}
+	if len(rest) == 2 {
+		header.curve = ecc.ID(rest[0])
+	}
+	written := make([]byte, rest[len(rest)-1])
+	if _, err := io.ReadFull(r, written); err != nil {
+		return keyFileHeader{}, errorf(errSerialization, "truncated key file header: %w", err)
+	}
+	header.gnark = string(written)
+	return header, nil
+}
+
+// readKeyFile checks the header of the key file at path against kind, BN254
+// and the linked gnark version, then decodes the rest into obj in format.
+func readKeyFile(path string, kind byte, format C.int, obj gnarkDecoder) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return errorf(errIO, "failed to open key file: %w", err)
+	}
+	defer f.Close()
+	return readKey(bufio.NewReaderSize(f, 1<<20), kind, format, obj)
+}
+
+// readKey is readKeyFile for a versioned key file read from r.
+func readKey(r io.Reader, kind byte, format C.int, obj gnarkDecoder) error {
+	head, err := readKeyFileHeader(r)
+	if err != nil {
+		return err
+	}
+	if head.kind != kind {
+		found, ok := keyKindNames[head.kind]
+		if !ok {
+			found = "an unknown kind of key"
+		}
+		return errorf(errInvalidKey, "the file holds %s, expected %s", found, keyKindNames[kind])
+	}
+	if head.curve != ecc.BN254 {
+		return errorf(errCurveMismatch, "the key is over %s, expected %s", head.curve, ecc.BN254)
+	}
+	written := head.gnark
+	if linked := gnarkVersion(); linked != "" && !gnarkCompatible(written, linked) {
+		return errorf(errInvalidKey,
+			"the key was written with gnark %s, which is incompatible with gnark %s in this build; "+
+				"regenerate it or convert it with a rust-gnark release built on gnark %s",
+			written, linked, written)
+	}
+	if err := readWithFormat(obj, format, r); err != nil {
+		return errorf(errInvalidKey, "failed to decode key: %w", err)
+	}
+	return nil
+}
+
+// gnark_groth16_vk_save writes a verifying key to path as a versioned key
+// file in the given format.
+//
+//export gnark_groth16_vk_save
+func gnark_groth16_vk_save(handle C.uint64_t, path *C.char, format C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+	if err := writeKeyFile(C.GoString(path), keyKindGroth16VK, format, vk); err != nil {
+		return cErrorf(errIO, "failed to save verifying key: %w", err)
+	}
+	return nil
+}
+
+// gnark_groth16_vk_load reads a versioned verifying key file, decoding the
+// key in the given format, into the handle registry.
+//
+//export gnark_groth16_vk_load
+func gnark_groth16_vk_load(path *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk := new(groth16_bn254.VerifyingKey)
+	if err := readKeyFile(C.GoString(path), keyKindGroth16VK, format, vk); err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(vk)
+	return nil
+}
+
+// Curve ids of gnark_artifact_sniff, as hash.rs's Curve::as_raw numbers them.
+var sniffCurveIDs = map[ecc.ID]C.int{
+	ecc.BN254:     0,
+	ecc.BLS12_381: 1,
+}
+
+// gnark_artifact_sniff reads the header of the versioned key file at path
+// without decoding the key, reporting its kind (1 for a Groth16 proving key,
+// 2 for a Groth16 verifying key) and curve.
+//
+//export gnark_artifact_sniff
+func gnark_artifact_sniff(path *C.char, out_kind *C.int, out_curve *C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	f, err := os.Open(C.GoString(path))
+	if err != nil {
+		return cErrorf(errIO, "failed to open key file: %w", err)
+	}
+	defer f.Close()
+	head, err := readKeyFileHeader(bufio.NewReader(f))
+	if err != nil {
+		return cError(err)
+	}
+	if _, ok := keyKindNames[head.kind]; !ok {
+		return cErrorf(errInvalidKey, "the file holds an unknown kind of key (%d)", head.kind)
+	}
+	curve, ok := sniffCurveIDs[head.curve]
+	if !ok {
+		return cErrorf(errCurveMismatch, "the key is over %s, which rust-gnark does not support", head.curve)
+	}
+	*out_kind = C.int(head.kind)
+	*out_curve = curve
+	return nil
+}
@@ -0,0 +1,155 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"io"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+)
+
+// Serialization formats for keys and proofs; keep in sync with
+// SerializationFormat on the Rust side.
+//
+// Compressed points are about half the size of raw ones but must be
+// decompressed (a square root per point) when loading. Decoding auto-detects
+// compression, so the format only decides how objects are written and whether
+// points are subgroup-checked when read.
+const (
+	formatCompressed   = iota // WriteTo / ReadFrom
+	formatRaw                 // WriteRawTo / ReadFrom
+	formatRawUnchecked        // WriteRawTo / UnsafeReadFrom (trusted input only)
+)
+
+type gnarkEncoder interface {
+	io.WriterTo
+	WriteRawTo(w io.Writer) (int64, error)
+}
+
+type gnarkDecoder interface {
+	io.ReaderFrom
+	UnsafeReadFrom(r io.Reader) (int64, error)
+}
+
+func writeWithFormat(obj gnarkEncoder, format C.int, w io.Writer) error {
+	switch format {
+	case formatCompressed:
+		_, err := obj.WriteTo(w)
+		return err
+	case formatRaw, formatRawUnchecked:
+		_, err := obj.WriteRawTo(w)
+		return err
+	default:
+		return errorf(errInvalidInput, "unknown serialization format %d", int(format))
+	}
+}
+
+func readWithFormat(obj gnarkDecoder, format C.int, r io.Reader) (err error) {
+	// gnark's decoders can panic on input they do not expect, e.g. a length
+	// field from another encoding version; report that as an error.
+	defer func() {
+		if p := recover(); p != nil {
+			err = errorf(errSerialization, "malformed encoding: %v", p)
+		}
+	}()
+	switch format {
+	case formatCompressed, formatRaw:
+		_, err := obj.ReadFrom(r)
+		return err
+	case formatRawUnchecked:
+		_, err := obj.UnsafeReadFrom(r)
+		return err
+	default:
+		return errorf(errInvalidInput, "unknown serialization format %d", int(format))
+	}
+}
+
+// fingerprint stores at out the SHA-256 of the raw (uncompressed) encoding
+// written by writeRaw. Hashing the raw form makes the digest independent of how
+// the key was stored on disk.
+func fingerprint(writeRaw func(io.Writer) (int64, error), out *C.uint8_t) error {
+	h := sha256.New()
+	if _, err := writeRaw(h); err != nil {
+		return err
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), sha256.Size), h.Sum(nil))
+	return nil
+}
+
+// gnark_groth16_vk_read reads the verifying key at path in the given format
+// into the handle registry.
+//
+//export gnark_groth16_vk_read
+func gnark_groth16_vk_read(path *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk := new(groth16_bn254.VerifyingKey)
+	read := func(r io.Reader) (int64, error) { return 0, readWithFormat(vk, format, r) }
+	if err := readFile(C.GoString(path), read); err != nil {
+		return cErrorf(errInvalidKey, "failed to read verifying key: %w", err)
+	}
+
+	*out_handle = newHandle(vk)
+	return nil
+}
+
+// gnark_groth16_vk_from_bytes deserializes a verifying key in the given format
+// from caller-owned memory into the handle registry.
+//
+//export gnark_groth16_vk_from_bytes
+func gnark_groth16_vk_from_bytes(data *C.uint8_t, length C.uint64_t, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	b := borrowBytes(data, length)
+	if err := checkVerifyingKeyEncoding(ecc.BN254, b); err != nil {
+		return cErrorf(errInvalidKey, "failed to read verifying key: %w", err)
+	}
+	vk := new(groth16_bn254.VerifyingKey)
+	if err := readWithFormat(vk, format, bytes.NewReader(b)); err != nil {
+		return cErrorf(errInvalidKey, "failed to read verifying key: %w", err)
+	}
+
+	*out_handle = newHandle(vk)
+	return nil
+}
+
+// gnark_groth16_vk_to_bytes serializes a verifying key handle in the given
+// format into a C buffer that the caller frees with gnark_free_buffer.
+//
+//export gnark_groth16_vk_to_bytes
+func gnark_groth16_vk_to_bytes(handle C.uint64_t, format C.int, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+
+	var buf cBuffer
+	if err := writeWithFormat(vk, format, &buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize verifying key: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_groth16_vk_fingerprint writes the 32-byte SHA-256 of a verifying key's
+// raw encoding to out.
+//
+//export gnark_groth16_vk_fingerprint
+func gnark_groth16_vk_fingerprint(handle C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+	if err := fingerprint(vk.WriteRawTo, out); err != nil {
+		return cErrorf(errSerialization, "failed to serialize verifying key: %w", err)
+	}
+	return nil
+}
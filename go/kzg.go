@@ -0,0 +1,150 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"errors"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	bn254fr "github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark-crypto/ecc/bn254/kzg"
+)
+
+// KZG polynomial commitments over an SRS handle (see srs.go). Polynomials are
+// n coefficients, lowest degree first, in the 32-byte big-endian encoding of
+// the hash exports; commitments and opening proofs are compressed G1 points
+// (G1Affine.Bytes).
+
+func kzgSRS(handle C.uint64_t) (*kzg.SRS, error) {
+	srs, err := lookupHandle[*kzg.SRS](handle)
+	if err != nil {
+		return nil, errorf(errInvalidInput, "invalid srs handle: %w", err)
+	}
+	return srs, nil
+}
+
+func readPolynomial(coeffs *C.uint8_t, n C.uint64_t) ([]bn254fr.Element, error) {
+	data := borrowBytes(coeffs, n*frBytes)
+	p := make([]bn254fr.Element, n)
+	for i := range p {
+		if err := p[i].SetBytesCanonical(data[i*frBytes : (i+1)*frBytes]); err != nil {
+			return nil, errorf(errInvalidInput, "coefficient %d is not a canonical field element: %w", i, err)
+		}
+	}
+	return p, nil
+}
+
+func readScalar(b *C.uint8_t, what string) (bn254fr.Element, error) {
+	var e bn254fr.Element
+	if err := e.SetBytesCanonical(borrowBytes(b, frBytes)); err != nil {
+		return e, errorf(errInvalidInput, "%s is not a canonical field element: %w", what, err)
+	}
+	return e, nil
+}
+
+func readG1(b *C.uint8_t, what string) (bn254.G1Affine, error) {
+	var p bn254.G1Affine
+	if _, err := p.SetBytes(borrowBytes(b, bn254.SizeOfG1AffineCompressed)); err != nil {
+		return p, errorf(errInvalidInput, "%s is not a compressed G1 point: %w", what, err)
+	}
+	return p, nil
+}
+
+func writeBytes(out *C.uint8_t, b []byte) {
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), len(b)), b)
+}
+
+// gnark_kzg_commit commits to the n coefficients at coeffs, writing the
+// 32-byte compressed commitment to out.
+//
+//export gnark_kzg_commit
+func gnark_kzg_commit(srs_handle C.uint64_t, coeffs *C.uint8_t, n C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := kzgSRS(srs_handle)
+	if err != nil {
+		return cError(err)
+	}
+	p, err := readPolynomial(coeffs, n)
+	if err != nil {
+		return cError(err)
+	}
+	digest, err := kzg.Commit(p, srs.Pk)
+	if err != nil {
+		return cErrorf(errInvalidInput, "kzg commit: %w", err)
+	}
+	b := digest.Bytes()
+	writeBytes(out, b[:])
+	return nil
+}
+
+// gnark_kzg_open evaluates the polynomial at point and proves the
+// evaluation, writing the compressed proof to out_proof and the value to
+// out_value (32 bytes each).
+//
+//export gnark_kzg_open
+func gnark_kzg_open(srs_handle C.uint64_t, coeffs *C.uint8_t, n C.uint64_t, point *C.uint8_t, out_proof *C.uint8_t, out_value *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := kzgSRS(srs_handle)
+	if err != nil {
+		return cError(err)
+	}
+	p, err := readPolynomial(coeffs, n)
+	if err != nil {
+		return cError(err)
+	}
+	z, err := readScalar(point, "point")
+	if err != nil {
+		return cError(err)
+	}
+	proof, err := kzg.Open(p, z, srs.Pk)
+	if err != nil {
+		return cErrorf(errInvalidInput, "kzg open: %w", err)
+	}
+	h := proof.H.Bytes()
+	writeBytes(out_proof, h[:])
+	v := proof.ClaimedValue.Bytes()
+	writeBytes(out_value, v[:])
+	return nil
+}
+
+// gnark_kzg_verify checks that proof opens commitment to value at point.
+// Returns NULL if it does and an errInvalidProof error if it does not.
+//
+//export gnark_kzg_verify
+func gnark_kzg_verify(srs_handle C.uint64_t, commitment *C.uint8_t, proof *C.uint8_t, point *C.uint8_t, value *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := kzgSRS(srs_handle)
+	if err != nil {
+		return cError(err)
+	}
+	digest, err := readG1(commitment, "commitment")
+	if err != nil {
+		return cError(err)
+	}
+	h, err := readG1(proof, "proof")
+	if err != nil {
+		return cError(err)
+	}
+	z, err := readScalar(point, "point")
+	if err != nil {
+		return cError(err)
+	}
+	v, err := readScalar(value, "value")
+	if err != nil {
+		return cError(err)
+	}
+	opening := kzg.OpeningProof{H: h, ClaimedValue: v}
+	if err := kzg.Verify(&digest, &opening, z, srs.Vk); err != nil {
+		if errors.Is(err, kzg.ErrVerifyOpeningProof) {
+			return cErrorf(errInvalidProof, "kzg opening does not verify")
+		}
+		return cErrorf(errInvalidInput, "kzg verify: %w", err)
+	}
+	return nil
+}
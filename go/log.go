@@ -0,0 +1,124 @@
+package main
+
+/*
+#include <stdlib.h>
+
+// Log callback: level is one of the log* constants in log.go, message is the
+// event's message and fields its other fields as space-separated key=value
+// pairs (possibly empty). Both strings are only valid during the call.
+typedef void (*gnark_log_cb)(int level, const char *message, const char *fields);
+
+// Go cannot call C function pointers directly.
+static inline void gnark_call_log(gnark_log_cb cb, int level, const char *message, const char *fields) {
+    if (cb != NULL) {
+        cb(level, message, fields);
+    }
+}
+*/
+import "C"
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+	"unsafe"
+
+	"github.com/consensys/gnark/logger"
+	"github.com/rs/zerolog"
+)
+
+// Log levels passed to gnark_log_cb; anything above logError disables logging.
+const (
+	logTrace = iota
+	logDebug
+	logInfo
+	logWarn
+	logError
+)
+
+// gnark_set_log_callback routes gnark's logger (zerolog, written to stderr by
+// default) to cb for events at min_level and above. A NULL cb disables
+// logging altogether.
+//
+//export gnark_set_log_callback
+func gnark_set_log_callback(cb C.gnark_log_cb, min_level C.int) {
+	if cb == nil {
+		logger.Disable()
+		return
+	}
+	logger.Set(zerolog.New(logForwarder{cb: cb}).Level(zerologLevel(int(min_level))))
+}
+
+func zerologLevel(level int) zerolog.Level {
+	switch level {
+	case logTrace:
+		return zerolog.TraceLevel
+	case logDebug:
+		return zerolog.DebugLevel
+	case logInfo:
+		return zerolog.InfoLevel
+	case logWarn:
+		return zerolog.WarnLevel
+	case logError:
+		return zerolog.ErrorLevel
+	default:
+		return zerolog.Disabled
+	}
+}
+
+// logForwarder is a zerolog.LevelWriter decoding each JSON event and handing
+// it to a gnark_log_cb.
+type logForwarder struct {
+	cb C.gnark_log_cb
+}
+
+func (w logForwarder) Write(p []byte) (int, error) {
+	return w.WriteLevel(zerolog.NoLevel, p)
+}
+
+func (w logForwarder) WriteLevel(level zerolog.Level, p []byte) (int, error) {
+	var event map[string]any
+	if err := json.Unmarshal(p, &event); err != nil {
+		event = map[string]any{zerolog.MessageFieldName: strings.TrimSpace(string(p))}
+	}
+	message, _ := event[zerolog.MessageFieldName].(string)
+	delete(event, zerolog.MessageFieldName)
+	delete(event, zerolog.LevelFieldName)
+	delete(event, zerolog.TimestampFieldName)
+
+	keys := make([]string, 0, len(event))
+	for k := range event {
+		keys = append(keys, k)
+	}
+	sort.Strings(keys)
+	var fields strings.Builder
+	for i, k := range keys {
+		if i > 0 {
+			fields.WriteByte(' ')
+		}
+		fmt.Fprintf(&fields, "%s=%v", k, event[k])
+	}
+
+	cMessage := C.CString(message)
+	defer C.free(unsafe.Pointer(cMessage))
+	cFields := C.CString(fields.String())
+	defer C.free(unsafe.Pointer(cFields))
+	C.gnark_call_log(w.cb, C.int(callbackLevel(level)), cMessage, cFields)
+	return len(p), nil
+}
+
+func callbackLevel(level zerolog.Level) int {
+	switch level {
+	case zerolog.TraceLevel:
+		return logTrace
+	case zerolog.DebugLevel:
+		return logDebug
+	case zerolog.WarnLevel:
+		return logWarn
+	case zerolog.ErrorLevel, zerolog.FatalLevel, zerolog.PanicLevel:
+		return logError
+	default:
+		return logInfo
+	}
+}
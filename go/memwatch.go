@@ -0,0 +1,93 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"runtime/metrics"
+	"sync"
+	"time"
+)
+
+// Peak memory of a call, for proving statistics: while at least one watch is
+// open, a goroutine samples the memory the Go runtime holds from the OS and
+// raises every open watch's peak to it. runtime/metrics reads do not stop
+// the world, unlike runtime.ReadMemStats, so sampling does not slow proofs
+// down. The runtime's memory is process-wide: a watch over a proof also
+// counts whatever else runs at the same time.
+
+const memWatchInterval = 5 * time.Millisecond
+
+var memWatch struct {
+	sync.Mutex
+	next    uint64
+	peaks   map[uint64]uint64
+	running bool
+}
+
+// goMemory is the memory the runtime has mapped and not returned to the OS.
+func goMemory() uint64 {
+	samples := []metrics.Sample{
+		{Name: "/memory/classes/total:bytes"},
+		{Name: "/memory/classes/heap/released:bytes"},
+	}
+	metrics.Read(samples)
+	return samples[0].Value.Uint64() - samples[1].Value.Uint64()
+}
+
+func sampleMemory() {
+	ticker := time.NewTicker(memWatchInterval)
+	defer ticker.Stop()
+	for range ticker.C {
+		m := goMemory()
+		memWatch.Lock()
+		if len(memWatch.peaks) == 0 {
+			memWatch.running = false
+			memWatch.Unlock()
+			return
+		}
+		for id, peak := range memWatch.peaks {
+			memWatch.peaks[id] = max(peak, m)
+		}
+		memWatch.Unlock()
+	}
+}
+
+// gnark_memory_watch_start opens a watch of the runtime's peak memory and
+// stores its id at out_id, for gnark_memory_watch_stop.
+//
+//export gnark_memory_watch_start
+func gnark_memory_watch_start(out_id *C.uint64_t) {
+	m := goMemory()
+	memWatch.Lock()
+	defer memWatch.Unlock()
+	if memWatch.peaks == nil {
+		memWatch.peaks = make(map[uint64]uint64)
+	}
+	memWatch.next++
+	memWatch.peaks[memWatch.next] = m
+	if !memWatch.running {
+		memWatch.running = true
+		go sampleMemory()
+	}
+	*out_id = C.uint64_t(memWatch.next)
+}
+
+// gnark_memory_watch_stop closes a watch and stores the peak memory it saw,
+// in bytes, at out_peak (0 for an unknown id).
+//
+//export gnark_memory_watch_stop
+func gnark_memory_watch_stop(id C.uint64_t, out_peak *C.uint64_t) {
+	m := goMemory()
+	memWatch.Lock()
+	defer memWatch.Unlock()
+	peak, ok := memWatch.peaks[uint64(id)]
+	if !ok {
+		*out_peak = 0
+		return
+	}
+	delete(memWatch.peaks, uint64(id))
+	*out_peak = C.uint64_t(max(peak, m))
+}
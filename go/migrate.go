@@ -0,0 +1,208 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"io"
+	"os"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+)
+
+// Migration of keys and proofs written by other gnark versions. gnark's
+// encodings change between minor releases, and readKeyFile refuses keys from
+// another one rather than risk misreading them. Here the linked gnark decodes
+// the old bytes anyway and encodes the result again: if that reproduces the
+// old bytes, the encoding did not change and the object is rewritten as the
+// current version; if not, or if decoding fails, no faithful conversion is
+// possible here and the caller is told to run the setup (or the prover)
+// again.
+
+// decodeAll decodes data into obj with gnark's checked decoder, failing
+// unless the whole of data is read.
+func decodeAll(obj io.ReaderFrom, data []byte) (err error) {
+	defer func() {
+		if p := recover(); p != nil {
+			err = fmt.Errorf("malformed encoding: %v", p)
+		}
+	}()
+	n, err := obj.ReadFrom(bytes.NewReader(data))
+	if err != nil {
+		return err
+	}
+	if n != int64(len(data)) {
+		return fmt.Errorf("%d trailing bytes", int64(len(data))-n)
+	}
+	return nil
+}
+
+// sameEncoding reports whether obj encodes in format to exactly data.
+func sameEncoding(obj gnarkEncoder, format C.int, data []byte) bool {
+	h := sha256.New()
+	if err := writeWithFormat(obj, format, h); err != nil {
+		return false
+	}
+	want := sha256.Sum256(data)
+	return bytes.Equal(h.Sum(nil), want[:])
+}
+
+// decodeKey decodes data as a kind of key, trying a verifying key then a
+// proving key if kind is 0, and returns the key and its kind.
+func decodeKey(kind byte, data []byte) (gnarkEncoder, byte, error) {
+	var errs []error
+	if kind == 0 || kind == keyKindGroth16VK {
+		vk := new(groth16_bn254.VerifyingKey)
+		err := checkVerifyingKeyEncoding(ecc.BN254, data)
+		if err == nil {
+			err = decodeAll(vk, data)
+		}
+		if err == nil {
+			return vk, keyKindGroth16VK, nil
+		}
+		errs = append(errs, err)
+	}
+	if kind == 0 || kind == keyKindGroth16PK {
+		pk := newProvingKey()
+		err := decodeAll(pk, data)
+		if err == nil {
+			return pk, keyKindGroth16PK, nil
+		}
+		errs = append(errs, err)
+	}
+	return nil, 0, errs[len(errs)-1]
+}
+
+// resetupError explains that a key from gnark version from (unknown if "")
+// cannot be converted by this build.
+func resetupError(from string, cause error) error {
+	linked := gnarkVersion()
+	if from == "" {
+		return errorf(errInvalidKey,
+			"the key is not in the encoding of gnark %s (%v); run the setup again, "+
+				"or convert it with a rust-gnark release built on the gnark that wrote it",
+			linked, cause)
+	}
+	return errorf(errInvalidKey,
+		"the key was written with gnark %s, whose encoding gnark %s cannot read (%v); run the setup again, "+
+			"or convert it with a rust-gnark release built on gnark %s",
+		from, linked, cause, from)
+}
+
+// gnark_migrate_key converts the key at in_path, a versioned key file or a
+// key written by gnark itself, to a current versioned key file at out_path,
+// which may be in_path. It reports the key's kind (as gnark_artifact_sniff),
+// the gnark version the file records in a C string freed with
+// gnark_free_string ("" for files without a header), and whether a file was
+// written: nothing is written if the input is already current.
+//
+//export gnark_migrate_key
+func gnark_migrate_key(in_path *C.char, out_path *C.char, out_kind *C.int, out_from **C.char, out_migrated *C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	data, unmap, err := mmapFile(C.GoString(in_path))
+	if err != nil {
+		return cErrorf(errIO, "failed to map key file: %w", err)
+	}
+	defer unmap()
+
+	var head keyFileHeader
+	payload := data
+	formats := []C.int{formatCompressed, formatRaw}
+	if bytes.HasPrefix(data, []byte(keyFileMagic)) {
+		r := bytes.NewReader(data)
+		if head, err = readKeyFileHeader(r); err != nil {
+			return cError(err)
+		}
+		if _, ok := keyKindNames[head.kind]; !ok {
+			return cErrorf(errInvalidKey, "the file holds an unknown kind of key (%d)", head.kind)
+		}
+		if head.curve != ecc.BN254 {
+			return cErrorf(errCurveMismatch, "the key is over %s, expected %s", head.curve, ecc.BN254)
+		}
+		payload = data[len(data)-r.Len():]
+		formats = []C.int{C.int(head.format)}
+	}
+	linked := gnarkVersion()
+	if head.version == keyFileVersion && (linked == "" || gnarkCompatible(head.gnark, linked)) {
+		*out_kind = C.int(head.kind)
+		*out_from = C.CString(head.gnark)
+		*out_migrated = 0
+		return nil
+	}
+
+	key, kind, err := decodeKey(head.kind, payload)
+	if err != nil {
+		return cError(resetupError(head.gnark, err))
+	}
+	format := C.int(-1)
+	for _, f := range formats {
+		if sameEncoding(key, f, payload) {
+			format = f
+			break
+		}
+	}
+	if format < 0 {
+		return cError(resetupError(head.gnark, fmt.Errorf("it decodes, but not to the same key")))
+	}
+
+	// Write next to the output and rename, so that migrating in place never
+	// truncates the mapped input.
+	out := C.GoString(out_path)
+	tmp := out + ".migrating"
+	if err := writeKeyFile(tmp, kind, format, key); err != nil {
+		return cErrorf(errIO, "failed to write key file: %w", err)
+	}
+	if err := os.Rename(tmp, out); err != nil {
+		os.Remove(tmp)
+		return cErrorf(errIO, "failed to write key file: %w", err)
+	}
+	*out_kind = C.int(kind)
+	*out_from = C.CString(head.gnark)
+	*out_migrated = 1
+	return nil
+}
+
+// gnark_migrate_proof decodes a hex-encoded Groth16 BN254 proof with the
+// linked gnark and returns it in the current encoding, in a C string freed
+// with gnark_free_string, reporting whether that differs from the input.
+//
+//export gnark_migrate_proof
+func gnark_migrate_proof(proof_hex *C.char, out_proof **C.char, out_changed *C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	proof := new(groth16_bn254.Proof)
+	err = checkProofEncoding(ecc.BN254, proofBytes)
+	if err == nil {
+		err = decodeAll(proof, proofBytes)
+	}
+	if err != nil {
+		return cErrorf(errInvalidProof,
+			"the proof is not in the encoding of gnark %s (%v); prove again", gnarkVersion(), err)
+	}
+	for _, format := range []C.int{formatCompressed, formatRaw} {
+		if sameEncoding(proof, format, proofBytes) {
+			*out_proof = C.CString(hex.EncodeToString(proofBytes))
+			*out_changed = 0
+			return nil
+		}
+	}
+	var buf bytes.Buffer
+	if err := writeWithFormat(proof, formatCompressed, &buf); err != nil {
+		return cErrorf(errSerialization, "failed to serialize proof: %w", err)
+	}
+	*out_proof = C.CString(hex.EncodeToString(buf.Bytes()))
+	*out_changed = 1
+	return nil
+}
@@ -0,0 +1,243 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/hex"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254/kzg"
+	"github.com/consensys/gnark/backend"
+	"github.com/consensys/gnark/backend/plonk"
+	"github.com/consensys/gnark/backend/witness"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+)
+
+// PLONK over BN254 with a KZG SRS. The Fiat-Shamir hash (challenge_hash, a
+// transcript* id) derives the challenges and folds the KZG openings; the
+// hash-to-field function (a hash* id) derives the BSB22 commitment
+// challenges. Third-party verifiers fix both, e.g. gnark's Solidity verifier
+// recomputes SHA-256 transcripts, so the prover and verifier must agree.
+
+// plonkProverOptions returns the prover options for the given hashes.
+func plonkProverOptions(challengeHash, hashToFieldID C.int) ([]backend.ProverOption, error) {
+	h, err := transcriptHash(challengeHash)
+	if err != nil {
+		return nil, err
+	}
+	// The folding hash is a second instance: hash.Hash is stateful.
+	folding, _ := transcriptHash(challengeHash)
+	opts := []backend.ProverOption{
+		backend.WithProverChallengeHashFunction(h),
+		backend.WithProverKZGFoldingHashFunction(folding),
+	}
+	htf, err := hashToField(int(hashToFieldID))
+	if err != nil {
+		return nil, err
+	}
+	if htf != nil {
+		opts = append(opts, backend.WithProverHashToFieldFunction(htf))
+	}
+	return opts, nil
+}
+
+// plonkVerifierOptions is plonkProverOptions for the verifier.
+func plonkVerifierOptions(challengeHash, hashToFieldID C.int) ([]backend.VerifierOption, error) {
+	h, err := transcriptHash(challengeHash)
+	if err != nil {
+		return nil, err
+	}
+	folding, _ := transcriptHash(challengeHash)
+	opts := []backend.VerifierOption{
+		backend.WithVerifierChallengeHashFunction(h),
+		backend.WithVerifierKZGFoldingHashFunction(folding),
+	}
+	htf, err := hashToField(int(hashToFieldID))
+	if err != nil {
+		return nil, err
+	}
+	if htf != nil {
+		opts = append(opts, backend.WithVerifierHashToFieldFunction(htf))
+	}
+	return opts, nil
+}
+
+// gnark_plonk_setup derives PLONK proving and verifying keys for a compiled
+// SparseR1CS from an SRS with at least gnark_circuit_srs_size powers.
+//
+//export gnark_plonk_setup
+func gnark_plonk_setup(circuit C.uint64_t, srs_handle C.uint64_t, out_pk *C.uint64_t, out_vk *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](circuit)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	spr, ok := ccs.(*cs_bn254.SparseR1CS)
+	if !ok {
+		return cErrorf(errInvalidInput, "PLONK needs a SparseR1CS (compile_sparse), got %T", ccs)
+	}
+	srs, err := lookupHandle[*kzg.SRS](srs_handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid SRS handle: %w", err)
+	}
+	size := ecc.NextPowerOfTwo(uint64(spr.GetNbConstraints() + spr.GetNbPublicVariables()))
+	if uint64(len(srs.Pk.G1)) < size+3 {
+		return cErrorf(errInvalidInput, "the SRS has %d G1 powers, the circuit needs %d", len(srs.Pk.G1), size+3)
+	}
+	lagrange, err := kzg.ToLagrangeG1(srs.Pk.G1[:size])
+	if err != nil {
+		return cErrorf(errOther, "failed to compute the Lagrange SRS: %w", err)
+	}
+	srsLagrange := &kzg.SRS{Vk: srs.Vk}
+	srsLagrange.Pk.G1 = lagrange
+
+	pk, vk, err := plonk.Setup(ccs, srs, srsLagrange)
+	if err != nil {
+		return cErrorf(errOther, "PLONK setup failed: %w", err)
+	}
+	*out_pk = newHandle(pk)
+	*out_vk = newHandle(vk)
+	return nil
+}
+
+// gnark_plonk_prove proves a JSON witness (as for gnark_groth16_prove). The
+// proof (WriteTo) and public witness (MarshalBinary) are returned hex-encoded
+// in C strings freed with gnark_free_string.
+//
+//export gnark_plonk_prove
+func gnark_plonk_prove(
+	circuit C.uint64_t,
+	pk_handle C.uint64_t,
+	witness_json *C.char,
+	challenge_hash C.int,
+	hash_to_field C.int,
+	out_proof **C.char,
+	out_public **C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](circuit)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	pk, err := lookupHandle[plonk.ProvingKey](pk_handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid PLONK proving key handle: %w", err)
+	}
+	opts, err := plonkProverOptions(challenge_hash, hash_to_field)
+	if err != nil {
+		return cError(err)
+	}
+	w, err := buildWitnessFromJSON(C.GoString(witness_json), ccs)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+	}
+	proof, err := plonk.Prove(ccs, pk, w, opts...)
+	if err != nil {
+		return cErrorf(errOther, "PLONK proving failed: %w", err)
+	}
+
+	var proofBuf bytes.Buffer
+	if _, err := proof.WriteTo(&proofBuf); err != nil {
+		return cErrorf(errSerialization, "failed to serialize proof: %w", err)
+	}
+	public, err := w.Public()
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to extract public witness: %w", err)
+	}
+	publicBytes, err := public.MarshalBinary()
+	if err != nil {
+		return cErrorf(errSerialization, "failed to serialize public witness: %w", err)
+	}
+	*out_proof = C.CString(hex.EncodeToString(proofBuf.Bytes()))
+	*out_public = C.CString(hex.EncodeToString(publicBytes))
+	return nil
+}
+
+// gnark_plonk_verify checks a hex-encoded proof and public witness from
+// gnark_plonk_prove. An invalid proof is reported as errInvalidProof.
+//
+//export gnark_plonk_verify
+func gnark_plonk_verify(
+	vk_handle C.uint64_t,
+	proof_hex *C.char,
+	public_hex *C.char,
+	challenge_hash C.int,
+	hash_to_field C.int,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[plonk.VerifyingKey](vk_handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid PLONK verifying key handle: %w", err)
+	}
+	opts, err := plonkVerifierOptions(challenge_hash, hash_to_field)
+	if err != nil {
+		return cError(err)
+	}
+	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	proof := plonk.NewProof(ecc.BN254)
+	if _, err := proof.ReadFrom(bytes.NewReader(proofBytes)); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	publicBytes, err := hex.DecodeString(C.GoString(public_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode public inputs hex: %w", err)
+	}
+	if err := checkPublicWitnessEncoding(ecc.BN254, publicBytes); err != nil {
+		return cErrorf(errSerialization, "failed to unmarshal public witness: %w", err)
+	}
+	public, err := witness.New(ecc.BN254.ScalarField())
+	if err != nil {
+		return cErrorf(errSerialization, "failed to create witness: %w", err)
+	}
+	if err := public.UnmarshalBinary(publicBytes); err != nil {
+		return cErrorf(errSerialization, "failed to unmarshal public witness: %w", err)
+	}
+
+	if err := plonk.Verify(proof, vk, public, opts...); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
+	}
+	return nil
+}
+
+// gnark_plonk_vk_to_bytes serializes a PLONK verifying key (WriteTo) into a
+// C buffer that the caller frees with gnark_free_buffer.
+//
+//export gnark_plonk_vk_to_bytes
+func gnark_plonk_vk_to_bytes(handle C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[plonk.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid PLONK verifying key handle: %w", err)
+	}
+	var buf cBuffer
+	if _, err := vk.WriteTo(&buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize verifying key: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_plonk_vk_from_bytes loads a PLONK verifying key written by
+// gnark_plonk_vk_to_bytes.
+//
+//export gnark_plonk_vk_from_bytes
+func gnark_plonk_vk_from_bytes(data *C.uint8_t, length C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk := plonk.NewVerifyingKey(ecc.BN254)
+	if err := readWithFormat(vk, formatCompressed, bytes.NewReader(borrowBytes(data, length))); err != nil {
+		return cErrorf(errInvalidKey, "failed to read verifying key: %w", err)
+	}
+	*out_handle = newHandle(vk)
+	return nil
+}
@@ -0,0 +1,144 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark/backend"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+)
+
+// preparedVk is a BN254 verifying key with what verifying costs regardless of
+// the proof computed once: e(α, β), and the Miller loop lines of -γ and -δ,
+// which groth16_bn254.Verify recomputes on every call.
+//
+// Keys with commitments are verified by gnark, which checks the commitments'
+// proof of knowledge and hashes them into the public inputs its own way; they
+// still save reading the key on every call.
+type preparedVk struct {
+	vk         *groth16_bn254.VerifyingKey
+	alphaBeta  bn254.GT
+	gammaLines [2][len(bn254.LoopCounter)]bn254.LineEvaluationAff
+	deltaLines [2][len(bn254.LoopCounter)]bn254.LineEvaluationAff
+}
+
+func prepareVk(vk *groth16_bn254.VerifyingKey) (*preparedVk, error) {
+	alphaBeta, err := bn254.Pair([]bn254.G1Affine{vk.G1.Alpha}, []bn254.G2Affine{vk.G2.Beta})
+	if err != nil {
+		return nil, err
+	}
+	var gammaNeg, deltaNeg bn254.G2Affine
+	gammaNeg.Neg(&vk.G2.Gamma)
+	deltaNeg.Neg(&vk.G2.Delta)
+	return &preparedVk{
+		vk:         vk,
+		alphaBeta:  alphaBeta,
+		gammaLines: bn254.PrecomputeLines(gammaNeg),
+		deltaLines: bn254.PrecomputeLines(deltaNeg),
+	}, nil
+}
+
+// verify checks e(A, B) · e(Σ xᵢ·Kᵢ, -γ) · e(C, -δ) == e(α, β), with the
+// subgroup checks of groth16_bn254.Verify.
+func (p *preparedVk) verify(proof *groth16_bn254.Proof, public fr.Vector, opts ...backend.VerifierOption) error {
+	vk := p.vk
+	if len(vk.CommitmentKeys) > 0 {
+		return groth16_bn254.Verify(proof, vk, public, opts...)
+	}
+	if len(public) != len(vk.G1.K)-1 {
+		return fmt.Errorf("invalid witness size, got %d, expected %d (public)", len(public), len(vk.G1.K)-1)
+	}
+	if !proof.Ar.IsInSubGroup() || !proof.Krs.IsInSubGroup() || !proof.Bs.IsInSubGroup() {
+		return errors.New("proof points are not in the correct subgroup")
+	}
+
+	var kSum bn254.G1Jac
+	if _, err := kSum.MultiExp(vk.G1.K[1:], public, ecc.MultiExpConfig{}); err != nil {
+		return err
+	}
+	kSum.AddMixed(&vk.G1.K[0])
+	var kSumAff bn254.G1Affine
+	kSumAff.FromJacobian(&kSum)
+
+	fixed, err := bn254.MillerLoopFixedQ(
+		[]bn254.G1Affine{kSumAff, proof.Krs},
+		[][2][len(bn254.LoopCounter)]bn254.LineEvaluationAff{p.gammaLines, p.deltaLines},
+	)
+	if err != nil {
+		return err
+	}
+	ab, err := bn254.MillerLoop([]bn254.G1Affine{proof.Ar}, []bn254.G2Affine{proof.Bs})
+	if err != nil {
+		return err
+	}
+	fixed.Mul(&fixed, &ab)
+	if right := bn254.FinalExponentiation(&fixed); !p.alphaBeta.Equal(&right) {
+		return errors.New("pairing check failed")
+	}
+	return nil
+}
+
+// gnark_groth16_vk_prepare prepares the verifying key of a handle for
+// verifying many proofs, into a new handle.
+//
+//export gnark_groth16_vk_prepare
+func gnark_groth16_vk_prepare(handle C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+	prepared, err := prepareVk(vk)
+	if err != nil {
+		return cErrorf(errInvalidKey, "failed to prepare verifying key: %w", err)
+	}
+
+	*out_handle = newHandle(prepared)
+	return nil
+}
+
+// gnark_groth16_prepared_vk_verify is gnark_groth16_verify_with_hash against
+// a prepared verifying key.
+//
+//export gnark_groth16_prepared_vk_verify
+func gnark_groth16_prepared_vk_verify(
+	handle C.uint64_t,
+	proof_hex *C.char,
+	public_inputs_hex *C.char,
+	hash C.int,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	prepared, err := lookupHandle[*preparedVk](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid prepared verifying key handle: %w", err)
+	}
+	verifierOpts, err := verifierHashOptions(int(hash))
+	if err != nil {
+		return cError(err)
+	}
+	proof, err := decodeBN254Proof(C.GoString(proof_hex))
+	if err != nil {
+		return cError(err)
+	}
+	pubWitness, err := decodeBN254PublicWitness(C.GoString(public_inputs_hex))
+	if err != nil {
+		return cError(err)
+	}
+	vector, ok := pubWitness.Vector().(fr.Vector)
+	if !ok {
+		return cErrorf(errCurveMismatch, "public witness is not over BN254")
+	}
+
+	if err := prepared.verify(proof, vector, verifierOpts...); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,559 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+#include <stdlib.h>
+
+// Progress callback: phase is one of the phase* constants in wrapper.go,
+// done/total are byte counts while loading files and 0/1 otherwise.
+typedef void (*gnark_progress_cb)(void *user_data, int phase, uint64_t done, uint64_t total);
+
+// Witness feeder callback: writes the n values of feed feed_id to out, as
+// 32-byte big-endian field elements. Returns 0 on success.
+typedef int (*gnark_feed_cb)(void *user_data, uint64_t feed_id, uint8_t *out, uint64_t n);
+
+// Optional knobs for gnark_groth16_prove_with_options. Zero means "default".
+typedef struct {
+    uint64_t cancel_token;         // token from gnark_cancel_token_new, 0 = not cancellable
+    gnark_progress_cb progress;    // progress callback or NULL
+    void *progress_user_data;      // passed back to progress unchanged
+    int gpu_enabled;               // non-zero: prove with ICICLE on gpu_device, if available
+    int gpu_device;                // CUDA device id
+    int threads;                   // GOMAXPROCS for the duration of the call, 0 = unchanged
+    int64_t memory_limit;          // soft memory limit (GOMEMLIMIT) in bytes, 0 = unchanged
+    int hash;                      // hash-to-field function, see hash* constants in config.go
+    int proof_format;              // proof encoding, see format* constants in keys.go
+    int test_rng_enabled;          // non-zero: draw prover randomness from test_rng_seed (tests only)
+    uint64_t test_rng_seed;        // seed of the deterministic prover RNG
+    int scrub_witness;             // non-zero: zero the witness and release freed memory after proving
+    gnark_feed_cb feed;            // witness feeder for circuits with feed ops, or NULL
+    void *feed_user_data;          // passed back to feed unchanged
+    int solver_tasks;              // parallel witness solver workers, 0 = one per CPU
+    int64_t hint_timeout_ms;       // deadline of each hint call in milliseconds, 0 = none
+} C_ProveOptions;
+
+// Go cannot call C function pointers directly.
+static inline void gnark_call_progress(gnark_progress_cb cb, void *user_data, int phase, uint64_t done, uint64_t total) {
+    if (cb != NULL) {
+        cb(user_data, phase, done, total);
+    }
+}
+
+static inline int gnark_call_feed(gnark_feed_cb cb, void *user_data, uint64_t feed_id, uint8_t *out, uint64_t n) {
+    return cb(user_data, feed_id, out, n);
+}
+*/
+import "C"
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math/big"
+	"os"
+	"time"
+	"unsafe"
+
+	"github.com/consensys/gnark/backend"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/backend/witness"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+	"github.com/consensys/gnark/constraint/solver"
+)
+
+//export gnark_groth16_prove
+func gnark_groth16_prove(
+	r1cs_path *C.char,
+	pk_path *C.char,
+	witness_json *C.char,
+) *C.C_Groth16ProofResult {
+	return gnark_groth16_prove_with_options(r1cs_path, pk_path, witness_json, nil)
+}
+
+// gnark_groth16_prove_with_options is gnark_groth16_prove with optional
+// cancellation and progress reporting (opts may be NULL).
+//
+// gnark's prover does not take a context, so cancellation is checked while
+// reading files, between phases and at every hint call of the solver. If the
+// token is cancelled during the prover itself, this waits for gnark to stop,
+// at the next hint or at the end of the proof, so a cancelled call never
+// leaves a proof running behind it, under runtime limits it has restored.
+// The progress callback is only ever invoked before this function returns.
+//
+//export gnark_groth16_prove_with_options
+func gnark_groth16_prove_with_options(
+	r1cs_path *C.char,
+	pk_path *C.char,
+	witness_json *C.char,
+	opts *C.C_ProveOptions,
+) (result *C.C_Groth16ProofResult) {
+	result = newProofResult()
+	defer catchPanic(&result.error)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	cs, err := loadConstraintSystem(o, C.GoString(r1cs_path))
+	if err != nil {
+		result.error = cError(err)
+		return result
+	}
+
+	pk, err := loadProvingKey(o, C.GoString(pk_path))
+	if err != nil {
+		result.error = cError(err)
+		return result
+	}
+
+	proveInto(result, o, cs, pk, C.GoString(witness_json))
+	return result
+}
+
+// gnark_groth16_prove_with_key is gnark_groth16_prove_with_options using a
+// proving key handle (see gnark_groth16_pk_open_mmap) instead of a file path,
+// so the key is only deserialized once across proofs.
+//
+//export gnark_groth16_prove_with_key
+func gnark_groth16_prove_with_key(
+	r1cs_path *C.char,
+	pk_handle C.uint64_t,
+	witness_json *C.char,
+	opts *C.C_ProveOptions,
+) (result *C.C_Groth16ProofResult) {
+	result = newProofResult()
+	defer catchPanic(&result.error)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	pk, err := lookupHandle[provingKey](pk_handle)
+	if err != nil {
+		result.error = cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+		return result
+	}
+
+	cs, err := loadConstraintSystem(o, C.GoString(r1cs_path))
+	if err != nil {
+		result.error = cError(err)
+		return result
+	}
+
+	proveInto(result, o, cs, pk, C.GoString(witness_json))
+	return result
+}
+
+func newProofResult() *C.C_Groth16ProofResult {
+	result := (*C.C_Groth16ProofResult)(C.malloc(C.size_t(unsafe.Sizeof(C.C_Groth16ProofResult{}))))
+	result.proof = nil
+	result.public_inputs = nil
+	result.error = nil
+	result.nb_constraints = 0
+	return result
+}
+
+// proveOptions is the Go view of C_ProveOptions.
+type proveOptions struct {
+	ctx      context.Context
+	progress progressReporter
+	gpu      bool
+	gpuID    int
+	threads  int
+	memLimit int64
+	hash     int
+	format   C.int
+	testRNG  bool
+	rngSeed  uint64
+	scrub    bool
+	feeder   witnessFeeder
+	solver   solverConfig
+
+	// hintLog replays the hint outputs of a solved witness (see
+	// gnark_prover_session_prove_solved).
+	hintLog *hintLog
+}
+
+func proveOptionsFrom(opts *C.C_ProveOptions) proveOptions {
+	var o C.C_ProveOptions
+	if opts != nil {
+		o = *opts
+	}
+	return proveOptions{
+		ctx:      contextFor(o.cancel_token),
+		progress: progressReporter{cb: o.progress, userData: o.progress_user_data},
+		gpu:      o.gpu_enabled != 0,
+		gpuID:    int(o.gpu_device),
+		threads:  int(o.threads),
+		memLimit: int64(o.memory_limit),
+		hash:     int(o.hash),
+		format:   o.proof_format,
+		testRNG:  o.test_rng_enabled != 0,
+		rngSeed:  uint64(o.test_rng_seed),
+		scrub:    o.scrub_witness != 0,
+		feeder:   witnessFeeder{cb: o.feed, userData: o.feed_user_data},
+		solver: solverConfig{
+			tasks:       int(o.solver_tasks),
+			hintTimeout: time.Duration(o.hint_timeout_ms) * time.Millisecond,
+		},
+	}
+}
+
+// proverOptions returns the gnark prover options for o.
+func (o proveOptions) proverOptions() ([]backend.ProverOption, error) {
+	opts, err := proverHashOptions(o.hash)
+	if err != nil {
+		return nil, err
+	}
+	if o.gpu {
+		gpuOpts, warning := gpuProverOptions(o.gpuID)
+		if warning != "" {
+			fmt.Fprintln(os.Stderr, "rust-gnark:", warning)
+		}
+		opts = append(opts, gpuOpts...)
+	}
+	var feed solver.Hint
+	if o.feeder.cb != nil {
+		feed = o.feeder.hint
+	}
+	solverConfig := o.solver
+	if o.ctx.Done() != nil {
+		solverConfig.ctx = o.ctx
+	}
+	if solverOpts := solverConfig.options(feed, nil, o.hintLog); len(solverOpts) > 0 {
+		opts = append(opts, backend.WithSolverOptions(solverOpts...))
+	}
+	return opts, nil
+}
+
+func loadConstraintSystem(o proveOptions, path string) (*cs_bn254.R1CS, error) {
+	cs := new(cs_bn254.R1CS)
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, errorf(errIO, "failed to open r1cs file: %w", err)
+	}
+	defer f.Close()
+
+	r := o.progress.reader(phaseLoadConstraintSystem, f)
+	if _, err := cs.ReadFrom(bufio.NewReaderSize(ctxReader{o.ctx, r}, 1<<20)); err != nil {
+		if cerr := cancelledError(o.ctx); cerr != nil {
+			return nil, cerr
+		}
+		return nil, errorf(errSerialization, "failed to read r1cs: %w", err)
+	}
+	r.finish()
+	return cs, nil
+}
+
+func loadProvingKey(o proveOptions, path string) (provingKey, error) {
+	// UnsafeReadFrom for speed -- trusted local file, skip validation
+	pk := newProvingKey()
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, errorf(errIO, "failed to open pk file: %w", err)
+	}
+	defer f.Close()
+
+	// Streamed through a buffer: keys can be far larger than memory
+	// allows reading at once, and the decoder reads point by point.
+	r := o.progress.reader(phaseLoadProvingKey, f)
+	if _, err := pk.UnsafeReadFrom(bufio.NewReaderSize(ctxReader{o.ctx, r}, 1<<20)); err != nil {
+		if cerr := cancelledError(o.ctx); cerr != nil {
+			return nil, cerr
+		}
+		return nil, errorf(errInvalidKey, "failed to read proving key: %w", err)
+	}
+	r.finish()
+	return pk, nil
+}
+
+// proveInto builds the witness, proves, and stores the serialized proof and
+// public inputs (or an error) in result.
+func proveInto(
+	result *C.C_Groth16ProofResult,
+	o proveOptions,
+	cs *cs_bn254.R1CS,
+	pk provingKey,
+	witnessJSON string,
+) {
+	o.progress.report(phaseBuildWitness, 0, 1)
+	fullWitness, err := buildWitnessFromJSON(witnessJSON, cs)
+	if err != nil {
+		result.error = cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+		return
+	}
+	o.progress.report(phaseBuildWitness, 1, 1)
+	proveWitnessInto(result, o, cs, pk, fullWitness)
+}
+
+// proveWitnessInto is proveInto for a witness that is already built. It
+// takes ownership of fullWitness, which it scrubs if o.scrub is set.
+func proveWitnessInto(
+	result *C.C_Groth16ProofResult,
+	o proveOptions,
+	cs *cs_bn254.R1CS,
+	pk provingKey,
+	fullWitness witness.Witness,
+) {
+	result.nb_constraints = C.uint64_t(cs.GetNbConstraints())
+	if o.scrub {
+		defer scrubWitness(fullWitness)
+	}
+
+	if err := cancelledError(o.ctx); err != nil {
+		result.error = cError(err)
+		return
+	}
+
+	type proveOutcome struct {
+		proof *groth16_bn254.Proof
+		err   error
+	}
+	proverOpts, err := o.proverOptions()
+	if err != nil {
+		result.error = cError(err)
+		return
+	}
+	done := make(chan proveOutcome, 1)
+	o.progress.report(phaseProve, 0, 1)
+	go func() {
+		// A panic here would bypass the export's recover and abort the
+		// process.
+		defer func() {
+			if p := recover(); p != nil {
+				done <- proveOutcome{err: newPanicError(p)}
+			}
+		}()
+		restore := applyTestRNG(o.testRNG, o.rngSeed)
+		defer restore()
+		proof, err := proveBN254(cs, pk, fullWitness, proverOpts...)
+		done <- proveOutcome{proof, err}
+	}()
+
+	var proof *groth16_bn254.Proof
+	select {
+	case <-o.ctx.Done():
+		// The hints fail from now on; wait until gnark has stopped reading
+		// the witness and the key.
+		<-done
+		result.error = cError(cancelledError(o.ctx))
+		return
+	case out := <-done:
+		if err := cancelledError(o.ctx); out.err != nil && err != nil {
+			// A hint failed for the cancellation, which gnark may not wrap.
+			result.error = cError(err)
+			return
+		}
+		if out.err != nil {
+			result.error = cErrorf(errOther, "proof generation failed: %w", out.err)
+			return
+		}
+		proof = out.proof
+	}
+	o.progress.report(phaseProve, 1, 1)
+
+	o.progress.report(phaseSerialize, 0, 1)
+	var proofBuf bytes.Buffer
+	if err := writeWithFormat(proof, o.format, &proofBuf); err != nil {
+		result.error = cErrorf(errSerialization, "failed to serialize proof: %w", err)
+		return
+	}
+	result.proof = C.CString(hex.EncodeToString(proofBuf.Bytes()))
+
+	pubWitness, err := fullWitness.Public()
+	if err != nil {
+		result.error = cErrorf(errSerialization, "failed to extract public witness: %w", err)
+		return
+	}
+	pubBin, err := pubWitness.MarshalBinary()
+	if err != nil {
+		result.error = cErrorf(errSerialization, "failed to marshal public witness: %w", err)
+		return
+	}
+	result.public_inputs = C.CString(hex.EncodeToString(pubBin))
+	o.progress.report(phaseSerialize, 1, 1)
+}
+
+// Proving phases reported to gnark_progress_cb, in order.
+const (
+	phaseLoadConstraintSystem C.int = iota
+	phaseLoadProvingKey
+	phaseBuildWitness
+	// groth16.Prove: witness solving, MSMs and FFTs. gnark does not expose
+	// hooks inside Prove, so this is reported as a single step.
+	phaseProve
+	phaseSerialize
+)
+
+// witnessFeeder asks a C callback for the values of feed ops while the
+// solver runs, in place of hintFeed.
+type witnessFeeder struct {
+	cb       C.gnark_feed_cb
+	userData unsafe.Pointer
+}
+
+// hint is a solver.Hint. The solver may call it from several goroutines at
+// once.
+func (f witnessFeeder) hint(field *big.Int, inputs, outputs []*big.Int) error {
+	if len(inputs) != 1 || len(outputs) == 0 {
+		return fmt.Errorf("feed: expected 1 input and at least 1 output")
+	}
+	id := inputs[0].Uint64()
+	buf := make([]byte, 32*len(outputs))
+	// The values are witness secrets; don't leave them in the freed buffer.
+	defer clear(buf)
+	if C.gnark_call_feed(f.cb, f.userData, C.uint64_t(id), (*C.uint8_t)(&buf[0]), C.uint64_t(len(outputs))) != 0 {
+		return errorf(errInvalidWitness, "feed %d: the witness feeder failed", id)
+	}
+	for i := range outputs {
+		outputs[i].SetBytes(buf[32*i : 32*(i+1)])
+		if outputs[i].Cmp(field) >= 0 {
+			return errorf(errInvalidWitness, "feed %d: value %d is not a field element", id, i)
+		}
+	}
+	return nil
+}
+
+// progressReporter forwards progress events to an optional C callback.
+type progressReporter struct {
+	cb       C.gnark_progress_cb
+	userData unsafe.Pointer
+}
+
+func (p progressReporter) report(phase C.int, done, total uint64) {
+	if p.cb == nil {
+		return
+	}
+	C.gnark_call_progress(p.cb, p.userData, phase, C.uint64_t(done), C.uint64_t(total))
+}
+
+// reader wraps f so that reading it reports byte progress for phase, at most
+// once per percent of the file size.
+func (p progressReporter) reader(phase C.int, f *os.File) *progressReader {
+	var total uint64
+	if info, err := f.Stat(); err == nil {
+		total = uint64(info.Size())
+	}
+	p.report(phase, 0, total)
+	return &progressReader{r: f, p: p, phase: phase, total: total, step: total/100 + 1}
+}
+
+type progressReader struct {
+	r     io.Reader
+	p     progressReporter
+	phase C.int
+	done  uint64
+	total uint64
+	step  uint64
+	next  uint64
+}
+
+func (r *progressReader) Read(b []byte) (int, error) {
+	n, err := r.r.Read(b)
+	r.done += uint64(n)
+	if r.done >= r.next && r.done < r.total {
+		r.p.report(r.phase, r.done, r.total)
+		r.next = r.done + r.step
+	}
+	return n, err
+}
+
+// finish reports the phase as complete.
+func (r *progressReader) finish() {
+	r.p.report(r.phase, r.total, r.total)
+}
+
+// buildWitnessFromJSON creates a gnark witness from a JSON object mapping
+// circuit variable names to decimal string values.
+//
+// The JSON format is: {"VarName": "decimal_value", ...}
+// Variable names must match those defined in the circuit (via gnark struct tags).
+// With "$sparse": true in the object, variables it leaves out are zero.
+//
+// The constraint system's variable name lists (see inputNames) give the
+// ordering; witness.Fill then populates the values.
+func buildWitnessFromJSON(jsonStr string, cs constraint.ConstraintSystem) (witness.Witness, error) {
+	publicNames, secretNames, err := inputNames(cs)
+	if err != nil {
+		return nil, err
+	}
+	return buildWitnessFromNames(jsonStr, cs.Field(), publicNames, secretNames)
+}
+
+// buildWitnessFromNames is buildWitnessFromJSON for a circuit over field with
+// the given inputs, for constraint systems inputNames does not know.
+func buildWitnessFromNames(jsonStr string, field *big.Int, publicNames, secretNames []string) (witness.Witness, error) {
+	var flatMap map[string]interface{}
+	if err := json.Unmarshal([]byte(jsonStr), &flatMap); err != nil {
+		return nil, fmt.Errorf("failed to parse witness JSON: %w", err)
+	}
+
+	nbPublic := len(publicNames)
+	nbSecret := len(secretNames)
+	// A sparse witness (Witness::from_sparse) leaves out its zero values.
+	_, sparse := flatMap[sparseWitnessKey]
+
+	// Create a buffered channel to feed values in witness order:
+	// public variables first, then secret variables.
+	values := make(chan any, nbPublic+nbSecret)
+
+	for _, name := range publicNames {
+		val, exists := flatMap[name]
+		if !exists && !sparse {
+			return nil, fmt.Errorf("missing witness value for public variable %q", name)
+		}
+		values <- toFieldElementOrZero(val, exists)
+	}
+
+	for _, name := range secretNames {
+		val, exists := flatMap[name]
+		if !exists && !sparse {
+			return nil, fmt.Errorf("missing witness value for secret variable %q", name)
+		}
+		values <- toFieldElementOrZero(val, exists)
+	}
+	close(values)
+
+	w, err := witness.New(field)
+	if err != nil {
+		return nil, errorf(errSerialization, "failed to create witness: %w", err)
+	}
+
+	if err := w.Fill(nbPublic, nbSecret, values); err != nil {
+		return nil, fmt.Errorf("failed to fill witness: %w", err)
+	}
+
+	return w, nil
+}
+
+// sparseWitnessKey marks a witness JSON whose missing inputs are zero. It
+// cannot clash with an input name, which are Go identifiers.
+const sparseWitnessKey = "$sparse"
+
+// toFieldElementOrZero is toFieldElement(v), or 0 for a value that does not
+// exist.
+func toFieldElementOrZero(v interface{}, exists bool) interface{} {
+	if !exists {
+		return 0
+	}
+	return toFieldElement(v)
+}
+
+// toFieldElement converts a JSON value to a type gnark accepts as a field element.
+// gnark field elements can be constructed from: string (decimal), int64, *big.Int.
+func toFieldElement(v interface{}) interface{} {
+	switch val := v.(type) {
+	case string:
+		return val // gnark accepts decimal strings directly
+	case float64:
+		// JSON numbers are decoded as float64 by default
+		return int64(val)
+	case json.Number:
+		return val.String()
+	default:
+		return fmt.Sprintf("%v", val)
+	}
+}
@@ -0,0 +1,129 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import "bytes"
+
+// Proving keys, which the verifier build (see wrapper.go) leaves out along
+// with the prover; verifying keys are in keys.go and keyfile.go.
+
+// gnark_groth16_pk_open_mmap memory-maps the proving key at path, deserializes
+// it in the given format and stores it in the handle registry. The mapping is
+// released once the key is decoded, so the file contents are never copied into
+// an intermediate heap buffer. A path that is the directory of a segmented key
+// (see segments.go) has its segments mapped and read as one file.
+//
+// Returns NULL on success (with *out_handle set) or an error message.
+//
+//export gnark_groth16_pk_open_mmap
+func gnark_groth16_pk_open_mmap(path *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if dir := C.GoString(path); isSegmented(dir) {
+		pk := newProvingKey()
+		if err := readSegmentedKey(dir, keyKindGroth16PK, format, pk); err != nil {
+			return cError(err)
+		}
+		*out_handle = newHandle(pk)
+		return nil
+	}
+	data, unmap, err := mmapFile(C.GoString(path))
+	if err != nil {
+		return cErrorf(errIO, "failed to map pk file: %w", err)
+	}
+	defer unmap()
+
+	pk := newProvingKey()
+	if err := readWithFormat(pk, format, bytes.NewReader(data)); err != nil {
+		return cErrorf(errInvalidKey, "failed to read proving key: %w", err)
+	}
+
+	*out_handle = newHandle(pk)
+	return nil
+}
+
+// gnark_groth16_pk_from_bytes deserializes a proving key in the given format
+// from caller-owned memory (read in place, not copied) into the handle
+// registry.
+//
+//export gnark_groth16_pk_from_bytes
+func gnark_groth16_pk_from_bytes(data *C.uint8_t, length C.uint64_t, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk := newProvingKey()
+	if err := readWithFormat(pk, format, bytes.NewReader(borrowBytes(data, length))); err != nil {
+		return cErrorf(errInvalidKey, "failed to read proving key: %w", err)
+	}
+
+	*out_handle = newHandle(pk)
+	return nil
+}
+
+// gnark_groth16_pk_to_bytes serializes a proving key handle in the given format
+// directly into a C buffer that the caller frees with gnark_free_buffer.
+//
+//export gnark_groth16_pk_to_bytes
+func gnark_groth16_pk_to_bytes(handle C.uint64_t, format C.int, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk, err := lookupHandle[provingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+	}
+
+	var buf cBuffer
+	if err := writeWithFormat(pk, format, &buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize proving key: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_groth16_pk_fingerprint writes the 32-byte SHA-256 of a proving key's
+// raw encoding to out.
+//
+//export gnark_groth16_pk_fingerprint
+func gnark_groth16_pk_fingerprint(handle C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk, err := lookupHandle[provingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+	}
+	if err := fingerprint(pk.WriteRawTo, out); err != nil {
+		return cErrorf(errSerialization, "failed to serialize proving key: %w", err)
+	}
+	return nil
+}
+
+// gnark_groth16_pk_save writes a proving key to path as a versioned key file
+// in the given format.
+//
+//export gnark_groth16_pk_save
+func gnark_groth16_pk_save(handle C.uint64_t, path *C.char, format C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk, err := lookupHandle[provingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+	}
+	if err := writeKeyFile(C.GoString(path), keyKindGroth16PK, format, pk); err != nil {
+		return cErrorf(errIO, "failed to save proving key: %w", err)
+	}
+	return nil
+}
+
+// gnark_groth16_pk_load reads a versioned proving key file, decoding the key
+// in the given format, into the handle registry.
+//
+//export gnark_groth16_pk_load
+func gnark_groth16_pk_load(path *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk := newProvingKey()
+	if err := readKeyFile(C.GoString(path), keyKindGroth16PK, format, pk); err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(pk)
+	return nil
+}
@@ -0,0 +1,92 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"crypto/sha256"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/std/hash/sha2"
+	"github.com/consensys/gnark/std/math/uints"
+)
+
+// Public-input commitments: SHA-256 over the 32-byte big-endian encodings of
+// a list of field elements, with the digest's top three bits cleared so it
+// fits in the BN254 scalar field (digest mod 2^253). A circuit that exposes
+// only this digest costs an on-chain verifier one cheap hash instead of a
+// scalar multiplication per public input. The same value in Solidity is
+// uint256(sha256(abi.encodePacked(inputs))) & ((1 << 253) - 1).
+
+const publicHashBits = 253
+
+// sha256Fields computes the commitment to vars in-circuit.
+func sha256Fields(api frontend.API, vars []frontend.Variable) (frontend.Variable, error) {
+	bf, err := uints.New[uints.U32](api)
+	if err != nil {
+		return nil, err
+	}
+	h, err := sha2.New(api)
+	if err != nil {
+		return nil, err
+	}
+	nbBits := api.Compiler().FieldBitLen()
+	for _, v := range vars {
+		// A full-width decomposition is constrained to be the canonical one,
+		// so the prover cannot hash v + r instead of v.
+		bits := api.ToBinary(v, nbBits)
+		for len(bits) < 8*fr.Bytes {
+			bits = append(bits, 0)
+		}
+		// Most significant byte first.
+		bytes := make([]uints.U8, fr.Bytes)
+		for j := range bytes {
+			lo := 8 * (fr.Bytes - 1 - j)
+			bytes[j] = bf.ByteValueOf(api.FromBinary(bits[lo : lo+8]...))
+		}
+		h.Write(bytes)
+	}
+	digest := h.Sum()
+	// The digest as a big-endian integer, least significant bit first, with
+	// its top bits dropped.
+	var bits []frontend.Variable
+	for j := len(digest) - 1; j >= 0; j-- {
+		bits = append(bits, api.ToBinary(digest[j].Val, 8)...)
+	}
+	return api.FromBinary(bits[:publicHashBits]...), nil
+}
+
+// publicInputsHash computes the commitment to n concatenated canonical
+// 32-byte big-endian field elements.
+func publicInputsHash(inputs []byte, n int) ([fr.Bytes]byte, error) {
+	var out [fr.Bytes]byte
+	for i := 0; i < n; i++ {
+		var e fr.Element
+		if err := e.SetBytesCanonical(inputs[i*fr.Bytes : (i+1)*fr.Bytes]); err != nil {
+			return out, errorf(errInvalidInput, "input %d is not a canonical field element: %w", i, err)
+		}
+	}
+	out = sha256.Sum256(inputs[:n*fr.Bytes])
+	out[0] &= 0xff >> (8*fr.Bytes - publicHashBits)
+	return out, nil
+}
+
+// gnark_public_inputs_hash writes the commitment to n 32-byte field elements
+// to out (32 bytes, big-endian).
+//
+//export gnark_public_inputs_hash
+func gnark_public_inputs_hash(inputs *C.uint8_t, n C.uint64_t, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	digest, err := publicInputsHash(borrowBytes(inputs, n*fr.Bytes), int(n))
+	if err != nil {
+		return cError(err)
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), fr.Bytes), digest[:])
+	return nil
+}
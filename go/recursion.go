@@ -0,0 +1,375 @@
+//go:build !no_bw6_761 && !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"fmt"
+	"math/big"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend"
+	"github.com/consensys/gnark/backend/groth16"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/constraint"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+	"github.com/consensys/gnark/std/algebra/emulated/sw_bw6761"
+	"github.com/consensys/gnark/std/algebra/native/sw_bls12377"
+	stdgroth16 "github.com/consensys/gnark/std/recursion/groth16"
+)
+
+// Two-layer recursion over the BLS12-377/BW6-761 2-chain: inner circuits,
+// described from Rust, are proven over BLS12-377, whose base field is
+// BW6-761's scalar field, so an outer circuit over BW6-761 verifies N of them
+// with native arithmetic (tens of thousands of constraints per proof, not
+// the millions of aggregate.go's emulated BN254 verifier). An optional third
+// circuit over BN254 verifies the outer proof in emulated arithmetic, for
+// verifiers that only have BN254 pairings, like the EVM.
+//
+// Each proof is generated with the hash-to-field its verifier recomputes:
+// gnark's in-circuit one for the inner and outer proofs, SHA-256 for the
+// BN254 proof, as gnark's Solidity verifier expects.
+//
+// Building with the no_bw6_761 tag leaves recursion out.
+
+type (
+	blsProof   = stdgroth16.Proof[sw_bls12377.G1Affine, sw_bls12377.G2Affine]
+	blsVK      = stdgroth16.VerifyingKey[sw_bls12377.G1Affine, sw_bls12377.G2Affine, sw_bls12377.GT]
+	blsWitness = stdgroth16.Witness[sw_bls12377.ScalarField]
+
+	bw6Proof   = stdgroth16.Proof[sw_bw6761.G1Affine, sw_bw6761.G2Affine]
+	bw6VK      = stdgroth16.VerifyingKey[sw_bw6761.G1Affine, sw_bw6761.G2Affine, sw_bw6761.GTEl]
+	bw6Witness = stdgroth16.Witness[sw_bw6761.ScalarField]
+)
+
+var innerCurve = ecc.BLS12_377
+
+// recursionCircuit verifies N BLS12-377 proofs for one fixed key over BW6-761.
+type recursionCircuit struct {
+	Proofs       []blsProof
+	Witnesses    []blsWitness `gnark:",public"`
+	VerifyingKey blsVK        `gnark:"-"`
+}
+
+func (c *recursionCircuit) Define(api frontend.API) error {
+	verifier, err := stdgroth16.NewVerifier[sw_bls12377.ScalarField, sw_bls12377.G1Affine, sw_bls12377.G2Affine, sw_bls12377.GT](api)
+	if err != nil {
+		return fmt.Errorf("new verifier: %w", err)
+	}
+	for i := range c.Proofs {
+		if err := verifier.AssertProof(c.VerifyingKey, c.Proofs[i], c.Witnesses[i]); err != nil {
+			return fmt.Errorf("assert proof %d: %w", i, err)
+		}
+	}
+	return nil
+}
+
+// wrapCircuit verifies one BW6-761 proof for a fixed key over BN254.
+type wrapCircuit struct {
+	Proof        bw6Proof
+	Witness      bw6Witness `gnark:",public"`
+	VerifyingKey bw6VK      `gnark:"-"`
+}
+
+func (c *wrapCircuit) Define(api frontend.API) error {
+	verifier, err := stdgroth16.NewVerifier[sw_bw6761.ScalarField, sw_bw6761.G1Affine, sw_bw6761.G2Affine, sw_bw6761.GTEl](api)
+	if err != nil {
+		return fmt.Errorf("new verifier: %w", err)
+	}
+	return verifier.AssertProof(c.VerifyingKey, c.Proof, c.Witness)
+}
+
+// recursionStage is one circuit of a pipeline with its keys.
+type recursionStage struct {
+	ccs constraint.ConstraintSystem
+	pk  groth16.ProvingKey
+	vk  groth16.VerifyingKey
+}
+
+func newRecursionStage(field *big.Int, circuit frontend.Circuit) (*recursionStage, error) {
+	ccs, err := compileCircuit(field, r1cs.NewBuilder, circuit, nil)
+	if err != nil {
+		return nil, err
+	}
+	pk, vk, err := groth16.Setup(ccs)
+	if err != nil {
+		return nil, err
+	}
+	return &recursionStage{ccs: ccs, pk: pk, vk: vk}, nil
+}
+
+// recursionPipeline is the Go object behind a recursion pipeline handle.
+type recursionPipeline struct {
+	n                        int
+	publicNames, secretNames []string
+	inner, outer             *recursionStage
+	wrap                     *recursionStage // nil without the BN254 wrap
+}
+
+// The hash-to-field of each layer's proofs, for the layer that verifies them.
+func innerProverOptions() backend.ProverOption {
+	return stdgroth16.GetNativeProverOptions(outerCurve.ScalarField(), innerCurve.ScalarField())
+}
+
+func innerVerifierOptions() backend.VerifierOption {
+	return stdgroth16.GetNativeVerifierOptions(outerCurve.ScalarField(), innerCurve.ScalarField())
+}
+
+func outerProverOptions() backend.ProverOption {
+	return stdgroth16.GetNativeProverOptions(ecc.BN254.ScalarField(), outerCurve.ScalarField())
+}
+
+func outerVerifierOptions() backend.VerifierOption {
+	return stdgroth16.GetNativeVerifierOptions(ecc.BN254.ScalarField(), outerCurve.ScalarField())
+}
+
+// gnark_recursion_new compiles the JSON circuit description desc over
+// BLS12-377, an outer circuit over BW6-761 verifying n of its proofs and,
+// if wrap is non-zero, a BN254 circuit verifying an outer proof, and runs a
+// Groth16 setup for each.
+//
+// The setups are local, single-party ones, as for gnark_aggregator_new.
+//
+//export gnark_recursion_new
+func gnark_recursion_new(desc *C.char, n C.uint32_t, wrap C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if n == 0 {
+		return cErrorf(errInvalidInput, "recursion pipeline needs at least one proof")
+	}
+
+	circuit, publicNames, secretNames, err := parseCircuitDesc(C.GoString(desc))
+	if err != nil {
+		return cError(err)
+	}
+	p := &recursionPipeline{n: int(n), publicNames: publicNames, secretNames: secretNames}
+	if p.inner, err = newRecursionStage(innerCurve.ScalarField(), circuit); err != nil {
+		return cErrorf(errInvalidInput, "failed to set up the inner circuit: %w", err)
+	}
+
+	innerKey, err := stdgroth16.ValueOfVerifyingKeyFixed[sw_bls12377.G1Affine, sw_bls12377.G2Affine, sw_bls12377.GT](p.inner.vk)
+	if err != nil {
+		return cErrorf(errInvalidKey, "unsupported inner verifying key: %w", err)
+	}
+	outer := recursionCircuit{
+		Proofs:       make([]blsProof, n),
+		Witnesses:    make([]blsWitness, n),
+		VerifyingKey: innerKey,
+	}
+	for i := range outer.Proofs {
+		outer.Proofs[i] = stdgroth16.PlaceholderProof[sw_bls12377.G1Affine, sw_bls12377.G2Affine](p.inner.ccs)
+		outer.Witnesses[i] = stdgroth16.PlaceholderWitness[sw_bls12377.ScalarField](p.inner.ccs)
+	}
+	if p.outer, err = newRecursionStage(outerCurve.ScalarField(), &outer); err != nil {
+		return cErrorf(errOther, "failed to set up the outer circuit: %w", err)
+	}
+
+	if wrap != 0 {
+		outerKey, err := stdgroth16.ValueOfVerifyingKeyFixed[sw_bw6761.G1Affine, sw_bw6761.G2Affine, sw_bw6761.GTEl](p.outer.vk)
+		if err != nil {
+			return cErrorf(errInvalidKey, "unsupported outer verifying key: %w", err)
+		}
+		circuit := wrapCircuit{
+			Proof:        stdgroth16.PlaceholderProof[sw_bw6761.G1Affine, sw_bw6761.G2Affine](p.outer.ccs),
+			Witness:      stdgroth16.PlaceholderWitness[sw_bw6761.ScalarField](p.outer.ccs),
+			VerifyingKey: outerKey,
+		}
+		if p.wrap, err = newRecursionStage(ecc.BN254.ScalarField(), &circuit); err != nil {
+			return cErrorf(errOther, "failed to set up the BN254 wrap circuit: %w", err)
+		}
+	}
+
+	*out_handle = newHandle(p)
+	return nil
+}
+
+// gnark_recursion_prove_inner proves the inner circuit over BLS12-377 for a
+// witness JSON object as gnark_groth16_prove takes. On success *out_proof and
+// *out_public_inputs are set to hex strings the caller frees with
+// gnark_free_string.
+//
+//export gnark_recursion_prove_inner
+func gnark_recursion_prove_inner(handle C.uint64_t, witness_json *C.char, out_proof **C.char, out_public_inputs **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := lookupHandle[*recursionPipeline](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid recursion pipeline handle: %w", err)
+	}
+	full, err := buildWitnessFromNames(C.GoString(witness_json), innerCurve.ScalarField(), p.publicNames, p.secretNames)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+	}
+	proof, err := groth16.Prove(p.inner.ccs, p.inner.pk, full, innerProverOptions())
+	if err != nil {
+		return cErrorf(errInvalidWitness, "inner proof failed: %w", err)
+	}
+	return writeProofHex(proof, full, out_proof, out_public_inputs)
+}
+
+// gnark_recursion_aggregate proves over BW6-761 that all n inner (proof,
+// public inputs) pairs, hex-encoded as returned by
+// gnark_recursion_prove_inner, verify. Outputs are as for
+// gnark_recursion_prove_inner.
+//
+//export gnark_recursion_aggregate
+func gnark_recursion_aggregate(
+	handle C.uint64_t,
+	proofs_hex **C.char,
+	public_inputs_hex **C.char,
+	n C.uint32_t,
+	out_proof **C.char,
+	out_public_inputs **C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := lookupHandle[*recursionPipeline](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid recursion pipeline handle: %w", err)
+	}
+	if int(n) != p.n {
+		return cErrorf(errInvalidInput, "recursion pipeline expects %d proofs, got %d", p.n, n)
+	}
+
+	proofs := unsafe.Slice(proofs_hex, int(n))
+	publics := unsafe.Slice(public_inputs_hex, int(n))
+	assignment := recursionCircuit{
+		Proofs:    make([]blsProof, n),
+		Witnesses: make([]blsWitness, n),
+	}
+	for i := range assignment.Proofs {
+		proof, err := decodeProof(innerCurve, C.GoString(proofs[i]))
+		if err != nil {
+			return cErrorf(errSerialization, "proof %d: %w", i, err)
+		}
+		if assignment.Proofs[i], err = stdgroth16.ValueOfProof[sw_bls12377.G1Affine, sw_bls12377.G2Affine](proof); err != nil {
+			return cErrorf(errSerialization, "proof %d: %w", i, err)
+		}
+		pub, err := decodePublicWitness(innerCurve, C.GoString(publics[i]))
+		if err != nil {
+			return cErrorf(errSerialization, "public inputs %d: %w", i, err)
+		}
+		// A failed outer proof does not say which inner proof is invalid.
+		if err := groth16.Verify(proof, p.inner.vk, pub, innerVerifierOptions()); err != nil {
+			return cErrorf(errInvalidProof, "invalid proof %d: %w", i, err)
+		}
+		if assignment.Witnesses[i], err = stdgroth16.ValueOfWitness[sw_bls12377.ScalarField](pub); err != nil {
+			return cErrorf(errSerialization, "public inputs %d: %w", i, err)
+		}
+	}
+
+	full, err := frontend.NewWitness(&assignment, outerCurve.ScalarField())
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build outer witness: %w", err)
+	}
+	proof, err := groth16.Prove(p.outer.ccs, p.outer.pk, full, outerProverOptions())
+	if err != nil {
+		return cErrorf(errOther, "outer proof failed: %w", err)
+	}
+	return writeProofHex(proof, full, out_proof, out_public_inputs)
+}
+
+// gnark_recursion_wrap proves over BN254 that an outer proof from
+// gnark_recursion_aggregate verifies. Outputs are as for
+// gnark_recursion_prove_inner, and the proof verifies with the pipeline's
+// BN254 key and the SHA-256 hash-to-field.
+//
+//export gnark_recursion_wrap
+func gnark_recursion_wrap(handle C.uint64_t, proof_hex *C.char, public_inputs_hex *C.char, out_proof **C.char, out_public_inputs **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := lookupHandle[*recursionPipeline](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid recursion pipeline handle: %w", err)
+	}
+	if p.wrap == nil {
+		return cErrorf(errInvalidInput, "recursion pipeline was built without the BN254 wrap")
+	}
+	proof, err := decodeProof(outerCurve, C.GoString(proof_hex))
+	if err != nil {
+		return cError(err)
+	}
+	pub, err := decodePublicWitness(outerCurve, C.GoString(public_inputs_hex))
+	if err != nil {
+		return cError(err)
+	}
+	if err := groth16.Verify(proof, p.outer.vk, pub, outerVerifierOptions()); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
+	}
+
+	var assignment wrapCircuit
+	if assignment.Proof, err = stdgroth16.ValueOfProof[sw_bw6761.G1Affine, sw_bw6761.G2Affine](proof); err != nil {
+		return cErrorf(errSerialization, "outer proof: %w", err)
+	}
+	if assignment.Witness, err = stdgroth16.ValueOfWitness[sw_bw6761.ScalarField](pub); err != nil {
+		return cErrorf(errSerialization, "outer public inputs: %w", err)
+	}
+	full, err := frontend.NewWitness(&assignment, ecc.BN254.ScalarField())
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build wrap witness: %w", err)
+	}
+	opts, err := proverHashOptions(hashSHA256)
+	if err != nil {
+		return cError(err)
+	}
+	wrapped, err := groth16.Prove(p.wrap.ccs, p.wrap.pk, full, opts...)
+	if err != nil {
+		return cErrorf(errOther, "BN254 wrap proof failed: %w", err)
+	}
+	return writeProofHex(wrapped, full, out_proof, out_public_inputs)
+}
+
+// gnark_recursion_verify verifies an outer proof (wrapped = 0) or a BN254
+// wrap proof (wrapped != 0). Returns NULL if valid, otherwise an error
+// message ("invalid proof: ..." if the check fails).
+//
+//export gnark_recursion_verify
+func gnark_recursion_verify(handle C.uint64_t, proof_hex *C.char, public_inputs_hex *C.char, wrapped C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := lookupHandle[*recursionPipeline](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid recursion pipeline handle: %w", err)
+	}
+	curve, vk := outerCurve, p.outer.vk
+	opts := []backend.VerifierOption{outerVerifierOptions()}
+	if wrapped != 0 {
+		if p.wrap == nil {
+			return cErrorf(errInvalidInput, "recursion pipeline was built without the BN254 wrap")
+		}
+		curve, vk = ecc.BN254, p.wrap.vk
+		if opts, err = verifierHashOptions(hashSHA256); err != nil {
+			return cError(err)
+		}
+	}
+	proof, err := decodeProof(curve, C.GoString(proof_hex))
+	if err != nil {
+		return cError(err)
+	}
+	pub, err := decodePublicWitness(curve, C.GoString(public_inputs_hex))
+	if err != nil {
+		return cError(err)
+	}
+	if err := groth16.Verify(proof, vk, pub, opts...); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
+	}
+	return nil
+}
+
+// gnark_recursion_wrap_vk registers the pipeline's BN254 verifying key as a
+// verifying key handle, e.g. to export it as a Solidity verifier.
+//
+//export gnark_recursion_wrap_vk
+func gnark_recursion_wrap_vk(handle C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	p, err := lookupHandle[*recursionPipeline](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid recursion pipeline handle: %w", err)
+	}
+	if p.wrap == nil {
+		return cErrorf(errInvalidInput, "recursion pipeline was built without the BN254 wrap")
+	}
+	*out_handle = newHandle(p.wrap.vk.(*groth16_bn254.VerifyingKey))
+	return nil
+}
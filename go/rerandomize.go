@@ -0,0 +1,92 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/hex"
+	"math/big"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+)
+
+// Groth16 proof re-randomization. For fresh random r, s the proof
+// (A/r, r·B + r·s·δ, C + s·A) satisfies the same pairing equation as
+// (A, B, C), for the same public inputs, but is unlinkable to it:
+//
+//	e(A/r, r·B + r·s·δ) = e(A, B) · e(s·A, δ)
+
+// rerandomizeProof applies fresh randomness to proof in place. Commitments
+// and their proof of knowledge do not take part in the pairing equation
+// above and are kept.
+func rerandomizeProof(proof *groth16_bn254.Proof, vk *groth16_bn254.VerifyingKey) error {
+	var r, s fr.Element
+	if _, err := r.SetRandom(); err != nil {
+		return errorf(errOther, "failed to draw randomness: %w", err)
+	}
+	if _, err := s.SetRandom(); err != nil {
+		return errorf(errOther, "failed to draw randomness: %w", err)
+	}
+	if r.IsZero() {
+		return errorf(errOther, "drew a zero scalar")
+	}
+	var rInv, rs fr.Element
+	rInv.Inverse(&r)
+	rs.Mul(&r, &s)
+
+	var a, c bn254.G1Affine
+	a.ScalarMultiplication(&proof.Ar, rInv.BigInt(new(big.Int)))
+	c.ScalarMultiplication(&proof.Ar, s.BigInt(new(big.Int)))
+	c.Add(&c, &proof.Krs)
+
+	var b, d bn254.G2Affine
+	b.ScalarMultiplication(&proof.Bs, r.BigInt(new(big.Int)))
+	d.ScalarMultiplication(&vk.G2.Delta, rs.BigInt(new(big.Int)))
+	b.Add(&b, &d)
+
+	proof.Ar, proof.Bs, proof.Krs = a, b, c
+	return nil
+}
+
+// gnark_groth16_proof_rerandomize re-randomizes a hex-encoded proof for a
+// verifying key handle and returns it hex-encoded in the given format, in a
+// C string freed with gnark_free_string.
+//
+//export gnark_groth16_proof_rerandomize
+func gnark_groth16_proof_rerandomize(vk_handle C.uint64_t, proof_hex *C.char, format C.int, out_proof **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](vk_handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	if err := checkProofEncoding(ecc.BN254, proofBytes); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	proof := new(groth16_bn254.Proof)
+	if _, err := proof.ReadFrom(bytes.NewReader(proofBytes)); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+
+	if err := rerandomizeProof(proof, vk); err != nil {
+		return cError(err)
+	}
+
+	var buf bytes.Buffer
+	if err := writeWithFormat(proof, format, &buf); err != nil {
+		return cErrorf(errSerialization, "failed to serialize proof: %w", err)
+	}
+	*out_proof = C.CString(hex.EncodeToString(buf.Bytes()))
+	return nil
+}
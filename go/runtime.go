@@ -0,0 +1,135 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"os"
+	"runtime"
+	"runtime/debug"
+	"strings"
+	"sync"
+)
+
+// Go runtime setup for the host process, and memory reporting and control.
+// The runtime shares the process's signals with the host, whose debuggers and
+// crash reporters need its cooperation. The Go heap grows to the peak of a
+// proof and the runtime only returns the freed pages to the OS gradually, so
+// apps under memory pressure report and release it explicitly.
+
+// gnark_init configures the Go runtime for the host process. godebug is a
+// comma-separated list of GODEBUG settings appended to the environment's;
+// the runtime applies the ones it rereads on os.Setenv, while the rest only
+// take effect from the environment the library is loaded with.
+// async_preempt is 1 to keep asynchronous preemption, 0 to turn it off and
+// -1 for the default, off on Apple platforms, where the SIGURG it sends
+// floods debuggers and crash reporters. crash_on_fatal makes fatal Go errors
+// abort the process with SIGABRT, so the host's crash reporter records them,
+// instead of exiting with status 2. The host's signal handlers installed since
+// the library was loaded get SA_ONSTACK (see fixSignalStacks).
+//
+//export gnark_init
+func gnark_init(godebug *C.char, async_preempt C.int, crash_on_fatal C.int) C.int {
+	var settings []string
+	if env := os.Getenv("GODEBUG"); env != "" {
+		settings = append(settings, env)
+	}
+	apple := runtime.GOOS == "ios" || runtime.GOOS == "darwin"
+	if async_preempt == 0 || (async_preempt < 0 && apple) {
+		settings = append(settings, "asyncpreemptoff=1")
+	}
+	if extra := C.GoString(godebug); extra != "" {
+		settings = append(settings, extra)
+	}
+	if len(settings) > 0 {
+		os.Setenv("GODEBUG", strings.Join(settings, ","))
+	}
+	if crash_on_fatal != 0 {
+		debug.SetTraceback("crash")
+	}
+	fixSignalStacks()
+	return 0
+}
+
+// gnark_fix_signal_stacks gives SA_ONSTACK to the host's signal handlers
+// that lack it and returns how many it changed. Go runs goroutines on small
+// stacks and requires every handler for a signal that can arrive on one to
+// switch to the alternate signal stack; it fixes the handlers it finds at
+// startup, and this fixes those installed later, e.g. by a crash reporter.
+//
+//export gnark_fix_signal_stacks
+func gnark_fix_signal_stacks() C.int {
+	return C.int(fixSignalStacks())
+}
+
+// gnark_set_thread_budget bounds the runtime's threads to threads (see
+// threadBudget), or lifts the bound if threads is 0, and returns the
+// previous budget. With raise it only increases the budget (see
+// setThreadBudget).
+//
+//export gnark_set_thread_budget
+func gnark_set_thread_budget(threads C.int, raise C.int) C.int {
+	return C.int(setThreadBudget(int(threads), raise != 0))
+}
+
+// gnark_shutdown releases what the runtime holds once the host is done
+// proving: it returns the heap to the OS and retires the OS threads the
+// prover's goroutines left parked. The runtime itself cannot stop; later
+// calls start threads again as they need them.
+//
+//export gnark_shutdown
+func gnark_shutdown() {
+	debug.FreeOSMemory()
+	retireThreads(runtime.GOMAXPROCS(0))
+}
+
+// retireThreads ends up to n of the runtime's idle OS threads. A goroutine
+// that exits while locked to its thread takes the thread with it, and the
+// scheduler runs new goroutines on idle threads before it starts any; the n
+// goroutines wait for each other so that each holds a different thread.
+func retireThreads(n int) {
+	var locked, done sync.WaitGroup
+	locked.Add(n)
+	done.Add(n)
+	for range n {
+		go func() {
+			defer done.Done()
+			runtime.LockOSThread()
+			locked.Done()
+			locked.Wait()
+		}()
+	}
+	done.Wait()
+}
+
+// gnark_read_mem_stats reports Go memory statistics: bytes of live heap
+// objects, bytes of heap spans in use, heap bytes returned to the OS, total
+// bytes obtained from the OS and completed GC cycles. It briefly stops the
+// world.
+//
+//export gnark_read_mem_stats
+func gnark_read_mem_stats(
+	out_heap_alloc *C.uint64_t,
+	out_heap_in_use *C.uint64_t,
+	out_heap_released *C.uint64_t,
+	out_sys *C.uint64_t,
+	out_num_gc *C.uint64_t,
+) {
+	var m runtime.MemStats
+	runtime.ReadMemStats(&m)
+	*out_heap_alloc = C.uint64_t(m.HeapAlloc)
+	*out_heap_in_use = C.uint64_t(m.HeapInuse)
+	*out_heap_released = C.uint64_t(m.HeapReleased)
+	*out_sys = C.uint64_t(m.Sys)
+	*out_num_gc = C.uint64_t(m.NumGC)
+}
+
+// gnark_free_os_memory runs a garbage collection and returns as much memory
+// to the OS as possible.
+//
+//export gnark_free_os_memory
+func gnark_free_os_memory() {
+	debug.FreeOSMemory()
+}
@@ -0,0 +1,27 @@
+//go:build !verifier
+
+package main
+
+import (
+	"runtime/debug"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark/backend/witness"
+)
+
+// scrubWitness zeroes the assignment of w and returns the freed heap to the
+// operating system, so neither the witness nor the JSON it was parsed from
+// (unreachable by now) lingers in the process after proving. Released pages
+// are dropped by the kernel rather than kept in Go's free lists.
+//
+// gnark's solver allocates its own copies of the wire values, which are freed
+// but not zeroed; this is a best-effort cleanup for callers that opt in with
+// C_ProveOptions.scrub_witness, not a guarantee.
+func scrubWitness(w witness.Witness) {
+	if v, ok := w.Vector().(fr.Vector); ok {
+		for i := range v {
+			v[i].SetZero()
+		}
+	}
+	debug.FreeOSMemory()
+}
@@ -0,0 +1,248 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bufio"
+	"bytes"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"hash"
+	"io"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// Segmented proving keys: a versioned key file (see keyfile.go) split into
+// numbered files of at most a chunk size each, for download and package size
+// limits, in a directory next to a manifest,
+//
+//	rust-gnark-segments 1
+//	pk.000 <size> <sha256>
+//	pk.001 <size> <sha256>
+//	...
+//
+// listing each segment in order with its size and SHA-256 in hex. Loading
+// checks every segment against the manifest before decoding the key from the
+// segments stitched together. The manifest is written last, so a directory
+// whose save was interrupted has none and does not load.
+
+const (
+	segmentManifestName    = "manifest"
+	segmentManifestMagic   = "rust-gnark-segments"
+	segmentManifestVersion = 1
+)
+
+// segmentInfo is a manifest entry.
+type segmentInfo struct {
+	name   string
+	size   int64
+	sha256 string
+}
+
+// segmentWriter writes into dir/pk.000, dir/pk.001, ..., starting a new
+// segment whenever the current one holds chunk bytes.
+type segmentWriter struct {
+	dir      string
+	chunk    int64
+	file     *os.File
+	buf      *bufio.Writer
+	hash     hash.Hash
+	segments []segmentInfo
+}
+
+func (w *segmentWriter) Write(p []byte) (int, error) {
+	written := 0
+	for len(p) > 0 {
+		if w.file == nil || w.segments[len(w.segments)-1].size == w.chunk {
+			if err := w.next(); err != nil {
+				return written, err
+			}
+		}
+		cur := &w.segments[len(w.segments)-1]
+		n := int(min(int64(len(p)), w.chunk-cur.size))
+		if _, err := w.buf.Write(p[:n]); err != nil {
+			return written, err
+		}
+		w.hash.Write(p[:n])
+		cur.size += int64(n)
+		written += n
+		p = p[n:]
+	}
+	return written, nil
+}
+
+// next closes the current segment and opens the next.
+func (w *segmentWriter) next() error {
+	if err := w.close(); err != nil {
+		return err
+	}
+	name := fmt.Sprintf("pk.%03d", len(w.segments))
+	f, err := os.Create(filepath.Join(w.dir, name))
+	if err != nil {
+		return err
+	}
+	w.file, w.buf, w.hash = f, bufio.NewWriterSize(f, 1<<20), sha256.New()
+	w.segments = append(w.segments, segmentInfo{name: name})
+	return nil
+}
+
+// close flushes and closes the current segment, recording its hash.
+func (w *segmentWriter) close() error {
+	if w.file == nil {
+		return nil
+	}
+	f := w.file
+	w.file = nil
+	if err := w.buf.Flush(); err != nil {
+		f.Close()
+		return err
+	}
+	w.segments[len(w.segments)-1].sha256 = hex.EncodeToString(w.hash.Sum(nil))
+	return f.Close()
+}
+
+// writeSegmentManifest writes the manifest for segments into dir.
+func writeSegmentManifest(dir string, segments []segmentInfo) error {
+	var b strings.Builder
+	fmt.Fprintf(&b, "%s %d\n", segmentManifestMagic, segmentManifestVersion)
+	for _, s := range segments {
+		fmt.Fprintf(&b, "%s %d %s\n", s.name, s.size, s.sha256)
+	}
+	path := filepath.Join(dir, segmentManifestName)
+	tmp := path + ".tmp"
+	if err := os.WriteFile(tmp, []byte(b.String()), 0o644); err != nil {
+		os.Remove(tmp)
+		return err
+	}
+	return os.Rename(tmp, path)
+}
+
+// readSegmentManifest parses the manifest in dir.
+func readSegmentManifest(dir string) ([]segmentInfo, error) {
+	data, err := os.ReadFile(filepath.Join(dir, segmentManifestName))
+	if err != nil {
+		return nil, errorf(errIO, "failed to read the segment manifest: %w", err)
+	}
+	lines := strings.Split(strings.TrimSuffix(string(data), "\n"), "\n")
+	magic := strings.Fields(lines[0])
+	if len(magic) != 2 || magic[0] != segmentManifestMagic {
+		return nil, errorf(errSerialization, "%s is not a segment manifest", segmentManifestName)
+	}
+	if v, err := strconv.Atoi(magic[1]); err != nil || v > segmentManifestVersion {
+		return nil, errorf(errSerialization, "segment manifest version %s is newer than this library supports (%d)", magic[1], segmentManifestVersion)
+	}
+	var segments []segmentInfo
+	for i, line := range lines[1:] {
+		fields := strings.Fields(line)
+		if len(fields) != 3 || strings.ContainsAny(fields[0], `/\`) {
+			return nil, errorf(errSerialization, "malformed line %d of the segment manifest", i+2)
+		}
+		size, err := strconv.ParseInt(fields[1], 10, 64)
+		if err != nil || size < 0 {
+			return nil, errorf(errSerialization, "malformed size on line %d of the segment manifest", i+2)
+		}
+		segments = append(segments, segmentInfo{name: fields[0], size: size, sha256: fields[2]})
+	}
+	if len(segments) == 0 {
+		return nil, errorf(errSerialization, "the segment manifest lists no segments")
+	}
+	return segments, nil
+}
+
+// isSegmented reports whether path is a directory of a segmented key.
+func isSegmented(path string) bool {
+	info, err := os.Stat(filepath.Join(path, segmentManifestName))
+	return err == nil && !info.IsDir()
+}
+
+// readSegmentedKey maps the segments in dir, checks them against the
+// manifest and decodes the versioned key file they make up into obj.
+func readSegmentedKey(dir string, kind byte, format C.int, obj gnarkDecoder) error {
+	segments, err := readSegmentManifest(dir)
+	if err != nil {
+		return err
+	}
+	readers := make([]io.Reader, 0, len(segments))
+	for _, s := range segments {
+		data, unmap, err := mmapFile(filepath.Join(dir, s.name))
+		if err != nil {
+			return errorf(errIO, "failed to map segment %s: %w", s.name, err)
+		}
+		defer unmap()
+		if int64(len(data)) != s.size {
+			return errorf(errInvalidKey, "segment %s is %d bytes, the manifest says %d", s.name, len(data), s.size)
+		}
+		if sum := sha256.Sum256(data); hex.EncodeToString(sum[:]) != s.sha256 {
+			return errorf(errInvalidKey, "segment %s does not match its SHA-256 in the manifest", s.name)
+		}
+		readers = append(readers, bytes.NewReader(data))
+	}
+	return readKey(io.MultiReader(readers...), kind, format, obj)
+}
+
+// gnark_groth16_pk_save_segmented writes a proving key as a versioned key
+// file in the given format, split into segments of at most chunk_size bytes
+// in the directory dir (created if needed), and stores the number of
+// segments at out_segments.
+//
+//export gnark_groth16_pk_save_segmented
+func gnark_groth16_pk_save_segmented(handle C.uint64_t, dir *C.char, chunk_size C.uint64_t, format C.int, out_segments *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk, err := lookupHandle[provingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+	}
+	if chunk_size == 0 || uint64(chunk_size) > 1<<62 {
+		return cErrorf(errInvalidInput, "invalid segment size %d", uint64(chunk_size))
+	}
+	path := C.GoString(dir)
+	if err := os.MkdirAll(path, 0o755); err != nil {
+		return cErrorf(errIO, "failed to create %s: %w", path, err)
+	}
+	// An old manifest would describe the segments being overwritten.
+	if err := os.Remove(filepath.Join(path, segmentManifestName)); err != nil && !os.IsNotExist(err) {
+		return cErrorf(errIO, "failed to remove the old segment manifest: %w", err)
+	}
+	w := &segmentWriter{dir: path, chunk: int64(chunk_size)}
+	err = writeKey(w, keyKindGroth16PK, format, pk)
+	if err == nil {
+		err = w.close()
+	} else {
+		w.close()
+	}
+	if err == nil {
+		err = writeSegmentManifest(path, w.segments)
+	}
+	if err != nil {
+		for _, s := range w.segments {
+			os.Remove(filepath.Join(path, s.name))
+		}
+		return cErrorf(errIO, "failed to save proving key segments: %w", err)
+	}
+	*out_segments = C.uint64_t(len(w.segments))
+	return nil
+}
+
+// gnark_groth16_pk_load_segmented reads a proving key saved with
+// gnark_groth16_pk_save_segmented from dir, decoding it in the given format,
+// into the handle registry.
+//
+//export gnark_groth16_pk_load_segmented
+func gnark_groth16_pk_load_segmented(dir *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	pk := newProvingKey()
+	if err := readSegmentedKey(C.GoString(dir), keyKindGroth16PK, format, pk); err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(pk)
+	return nil
+}
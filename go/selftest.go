@@ -0,0 +1,83 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"time"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/backend/groth16"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/frontend/cs/r1cs"
+)
+
+// selfTestCircuit is x³ + x + 5 == y, the circuit of gnark's examples.
+type selfTestCircuit struct {
+	X frontend.Variable
+	Y frontend.Variable `gnark:",public"`
+}
+
+func (c *selfTestCircuit) Define(api frontend.API) error {
+	api.AssertIsEqual(c.Y, api.Add(api.Mul(c.X, c.X, c.X), c.X, 5))
+	return nil
+}
+
+// gnark_self_test compiles selfTestCircuit, runs a Groth16 setup, proves
+// and verifies in memory, and checks that the proof does not verify against
+// another public input. It stores the nanoseconds of the four phases at
+// out_nanos[0..4].
+//
+//export gnark_self_test
+func gnark_self_test(out_nanos *C.int64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	nanos := unsafe.Slice((*int64)(unsafe.Pointer(out_nanos)), 4)
+	start := time.Now()
+	lap := func(phase int) {
+		nanos[phase] = time.Since(start).Nanoseconds()
+		start = time.Now()
+	}
+	field := ecc.BN254.ScalarField()
+
+	ccs, err := frontend.Compile(field, r1cs.NewBuilder, &selfTestCircuit{})
+	if err != nil {
+		return cErrorf(errOther, "self-test: failed to compile: %w", err)
+	}
+	lap(0)
+	pk, vk, err := groth16.Setup(ccs)
+	if err != nil {
+		return cErrorf(errOther, "self-test: setup failed: %w", err)
+	}
+	lap(1)
+	full, err := frontend.NewWitness(&selfTestCircuit{X: 3, Y: 35}, field)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "self-test: failed to build witness: %w", err)
+	}
+	proof, err := groth16.Prove(ccs, pk, full)
+	if err != nil {
+		return cErrorf(errOther, "self-test: proof generation failed: %w", err)
+	}
+	lap(2)
+	public, err := full.Public()
+	if err != nil {
+		return cErrorf(errSerialization, "self-test: failed to extract public witness: %w", err)
+	}
+	if err := groth16.Verify(proof, vk, public); err != nil {
+		return cErrorf(errInvalidProof, "self-test: the proof does not verify: %w", err)
+	}
+	lap(3)
+
+	wrong, err := frontend.NewWitness(&selfTestCircuit{Y: 36}, field, frontend.PublicOnly())
+	if err != nil {
+		return cErrorf(errInvalidWitness, "self-test: failed to build witness: %w", err)
+	}
+	if groth16.Verify(proof, vk, wrong) == nil {
+		return cErrorf(errInvalidProof, "self-test: the proof verifies against a wrong public input")
+	}
+	return nil
+}
@@ -0,0 +1,239 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"runtime"
+	"sync"
+	"unsafe"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"github.com/consensys/gnark/constraint"
+	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
+)
+
+// proverSession keeps a deserialized constraint system and proving key alive
+// across proofs. Both are only read by the prover, so one session can serve
+// concurrent calls.
+//
+// The key can be replaced while proofs run: each proof takes the key current
+// when it starts and keeps it alive until it finishes, and the GC reclaims a
+// replaced key once the last of them (and any handle to it) is gone.
+type proverSession struct {
+	cs *cs_bn254.R1CS
+
+	mu sync.RWMutex
+	pk provingKey
+}
+
+// key returns the session's current proving key.
+func (s *proverSession) key() provingKey {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.pk
+}
+
+// checkProvingKey checks that pk was set up for a circuit of cs's size.
+func checkProvingKey(cs *cs_bn254.R1CS, pk provingKey) error {
+	// Setup sizes the domain to the constraint count, rounded up to a power
+	// of two.
+	cardinality := bn254ProvingKey(pk).Domain.Cardinality
+	constraints := uint64(cs.GetNbConstraints())
+	if cardinality != ecc.NextPowerOfTwo(constraints) {
+		return errorf(errInvalidKey, "the proving key is for a domain of %d constraints, the circuit has %d",
+			cardinality, constraints)
+	}
+	return nil
+}
+
+// gnark_prover_session_new loads the R1CS at r1cs_path and memory-maps the
+// proving key at pk_path (decoded in the given format) into a session handle.
+//
+//export gnark_prover_session_new
+func gnark_prover_session_new(r1cs_path *C.char, pk_path *C.char, pk_format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	cs, err := readR1CS(C.GoString(r1cs_path))
+	if err != nil {
+		return cError(err)
+	}
+
+	data, unmap, err := mmapFile(C.GoString(pk_path))
+	if err != nil {
+		return cErrorf(errIO, "failed to map pk file: %w", err)
+	}
+	defer unmap()
+	pk := newProvingKey()
+	if err := readWithFormat(pk, pk_format, bytes.NewReader(data)); err != nil {
+		return cErrorf(errInvalidKey, "failed to read proving key: %w", err)
+	}
+
+	*out_handle = newHandle(&proverSession{cs: cs, pk: pk})
+	return nil
+}
+
+// gnark_prover_session_prove is gnark_groth16_prove_with_options using the
+// constraint system and proving key held by a session, so nothing is read or
+// deserialized per proof.
+//
+//export gnark_prover_session_prove
+func gnark_prover_session_prove(
+	handle C.uint64_t,
+	witness_json *C.char,
+	opts *C.C_ProveOptions,
+) (result *C.C_Groth16ProofResult) {
+	result = newProofResult()
+	defer catchPanic(&result.error)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		result.error = cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+		return result
+	}
+
+	proveInto(result, o, s.cs, s.key(), C.GoString(witness_json))
+	return result
+}
+
+// batchInstances is how many proofs of a batch of n run at once. gnark's
+// prover already spreads one proof over every core; a few overlapping
+// instances keep the cores busy through each proof's serial parts (witness
+// solving, serialization) without holding the working memory of many proofs
+// at once.
+func batchInstances(n int) int {
+	return max(1, min(n, 4, runtime.GOMAXPROCS(0)/2))
+}
+
+// gnark_prover_session_prove_many proves count witnesses against the session,
+// several at a time, storing at out_results[i] the result for witnesses[i]
+// (freed with gnark_free_proof_result). Every instance shares the session's
+// constraint system and the key current when the batch starts, with the FFT
+// domain gnark precomputes in it. opts must not set a progress callback or a
+// witness feeder, which are not safe to call concurrently.
+//
+//export gnark_prover_session_prove_many
+func gnark_prover_session_prove_many(
+	handle C.uint64_t,
+	witnesses **C.char,
+	count C.uint64_t,
+	opts *C.C_ProveOptions,
+	out_results **C.C_Groth16ProofResult,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	o := proveOptionsFrom(opts)
+	defer applyRuntimeLimits(o.threads, o.memLimit)()
+
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+	}
+	n := int(count)
+	jsons := unsafe.Slice(witnesses, n)
+	results := unsafe.Slice(out_results, n)
+	for i := range results {
+		results[i] = newProofResult()
+	}
+
+	pk := s.key()
+	slots := make(chan struct{}, batchInstances(n))
+	var wg sync.WaitGroup
+	for i := range n {
+		witnessJSON := C.GoString(jsons[i])
+		slots <- struct{}{}
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			defer func() { <-slots }()
+			defer catchPanic(&results[i].error)
+			proveInto(results[i], o, s.cs, pk, witnessJSON)
+		}()
+	}
+	wg.Wait()
+	return nil
+}
+
+// gnark_prover_session_check_vk checks that the verifying key vk_handle came
+// from the same setup as the session's proving key, and that both fit its
+// circuit, failing with errInvalidKey naming the first mismatch.
+//
+//export gnark_prover_session_check_vk
+func gnark_prover_session_check_vk(handle C.uint64_t, vk_handle C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+	}
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](vk_handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+	pk := bn254ProvingKey(s.key())
+
+	// The setup's secrets, which both keys carry as the same points.
+	if !pk.G1.Alpha.Equal(&vk.G1.Alpha) || !pk.G1.Beta.Equal(&vk.G1.Beta) ||
+		!pk.G1.Delta.Equal(&vk.G1.Delta) || !pk.G2.Beta.Equal(&vk.G2.Beta) ||
+		!pk.G2.Delta.Equal(&vk.G2.Delta) {
+		return cErrorf(errInvalidKey, "the proving and verifying keys come from different setups")
+	}
+	commitments := len(s.cs.CommitmentInfo.(constraint.Groth16Commitments))
+	if len(vk.PublicAndCommitmentCommitted) != commitments {
+		return cErrorf(errInvalidKey, "the verifying key has %d commitments, the circuit %d",
+			len(vk.PublicAndCommitmentCommitted), commitments)
+	}
+	// K holds one point per public variable (the constant 1 included) and
+	// commitment.
+	if public := s.cs.GetNbPublicVariables(); len(vk.G1.K)-commitments != public {
+		return cErrorf(errInvalidKey, "the verifying key has %d public inputs, the circuit %d",
+			len(vk.G1.K)-commitments-1, public-1)
+	}
+	if err := checkProvingKey(s.cs, s.key()); err != nil {
+		return cError(err)
+	}
+	return nil
+}
+
+// gnark_prover_session_swap_key replaces the session's proving key with the
+// key pk_handle, after checking it fits the circuit. Proofs already running
+// finish with the old key; the handle stays valid and owned by the caller.
+//
+//export gnark_prover_session_swap_key
+func gnark_prover_session_swap_key(handle C.uint64_t, pk_handle C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+	}
+	pk, err := lookupHandle[provingKey](pk_handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid proving key handle: %w", err)
+	}
+	if err := checkProvingKey(s.cs, pk); err != nil {
+		return cError(err)
+	}
+	s.mu.Lock()
+	s.pk = pk
+	s.mu.Unlock()
+	return nil
+}
+
+// gnark_prover_session_key stores the session's current proving key under a
+// new handle, which keeps it alive after a swap until it is freed.
+//
+//export gnark_prover_session_key
+func gnark_prover_session_key(handle C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	s, err := lookupHandle[*proverSession](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid prover session handle: %w", err)
+	}
+	*out_handle = newHandle(s.key())
+	return nil
+}
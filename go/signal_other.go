@@ -0,0 +1,8 @@
+//go:build !unix
+
+package main
+
+// fixSignalStacks does nothing on platforms without POSIX signals.
+func fixSignalStacks() int {
+	return 0
+}
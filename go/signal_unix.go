@@ -0,0 +1,43 @@
+//go:build unix
+
+package main
+
+/*
+#include <signal.h>
+#include <stddef.h>
+
+// The synchronous signals crash reporters catch. All of them can arrive
+// while a goroutine runs, on its small stack.
+static const int host_signals[] = {SIGABRT, SIGBUS, SIGFPE, SIGILL, SIGSEGV, SIGSYS, SIGTRAP};
+
+// set_onstack_all adds SA_ONSTACK to the handlers installed for
+// host_signals that lack it, and returns how many it changed.
+static int set_onstack_all(void) {
+	int changed = 0;
+	for (size_t i = 0; i < sizeof(host_signals) / sizeof(host_signals[0]); i++) {
+		struct sigaction sa;
+		if (sigaction(host_signals[i], NULL, &sa) != 0) {
+			continue;
+		}
+		if ((sa.sa_flags & SA_SIGINFO) == 0 && (sa.sa_handler == SIG_DFL || sa.sa_handler == SIG_IGN)) {
+			continue;
+		}
+		if (sa.sa_flags & SA_ONSTACK) {
+			continue;
+		}
+		sa.sa_flags |= SA_ONSTACK;
+		if (sigaction(host_signals[i], &sa, NULL) == 0) {
+			changed++;
+		}
+	}
+	return changed;
+}
+*/
+import "C"
+
+// fixSignalStacks makes the host's signal handlers run on the alternate
+// signal stack, as Go does at startup for handlers installed before it, and
+// returns how many handlers it changed.
+func fixSignalStacks() int {
+	return int(C.set_onstack_all())
+}
@@ -0,0 +1,123 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/hex"
+	"fmt"
+	"strings"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
+	"golang.org/x/crypto/sha3"
+)
+
+// Solidity verifiers. Proofs of circuits with Pedersen commitments must be
+// generated with the SHA-256 hash-to-field function (hashSHA256), which is
+// what the generated contract recomputes on-chain.
+
+// gnark_groth16_vk_export_solidity writes the Solidity verifier contract of
+// a verifying key handle into a C buffer freed with gnark_free_buffer.
+//
+//export gnark_groth16_vk_export_solidity
+func gnark_groth16_vk_export_solidity(handle C.uint64_t, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	vk, err := lookupHandle[*groth16_bn254.VerifyingKey](handle)
+	if err != nil {
+		return cErrorf(errInvalidKey, "invalid verifying key handle: %w", err)
+	}
+
+	var buf cBuffer
+	if err := vk.ExportSolidity(&buf); err != nil {
+		buf.free()
+		return cErrorf(errInvalidKey, "failed to export solidity verifier: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_groth16_proof_solidity_calldata converts a hex-encoded proof into the
+// proof bytes the Solidity verifier takes (A, B, C, then any commitments and
+// their proof of knowledge, uncompressed), in a C buffer freed with
+// gnark_free_buffer.
+//
+//export gnark_groth16_proof_solidity_calldata
+func gnark_groth16_proof_solidity_calldata(proof_hex *C.char, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	if err := checkProofEncoding(ecc.BN254, proofBytes); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	proof := new(groth16_bn254.Proof)
+	if _, err := proof.ReadFrom(bytes.NewReader(proofBytes)); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+
+	var buf cBuffer
+	if _, err := buf.Write(proof.MarshalSolidity()); err != nil {
+		buf.free()
+		return cError(err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// verifyProofSignature is the signature of the Solidity verifier's
+// verifyProof for a proof with nbCommitments commitments and nbPublic public
+// inputs, as gnark's contract template declares it.
+func verifyProofSignature(nbCommitments, nbPublic int) string {
+	if nbCommitments == 0 {
+		return fmt.Sprintf("verifyProof(uint256[8],uint256[%d])", nbPublic)
+	}
+	return fmt.Sprintf("verifyProof(uint256[8],uint256[%d],uint256[2],uint256[%d])", 2*nbCommitments, nbPublic)
+}
+
+// gnark_groth16_solidity_verify_call encodes a call of the Solidity
+// verifier's verifyProof with a hex-encoded proof and public witness: the
+// function selector, then the proof words and the public inputs (every
+// argument is a fixed-size uint256 array). The calldata is returned in a C
+// buffer freed with gnark_free_buffer.
+//
+//export gnark_groth16_solidity_verify_call
+func gnark_groth16_solidity_verify_call(proof_hex *C.char, public_hex *C.char, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode proof hex: %w", err)
+	}
+	if err := checkProofEncoding(ecc.BN254, proofBytes); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	proof := new(groth16_bn254.Proof)
+	if _, err := proof.ReadFrom(bytes.NewReader(proofBytes)); err != nil {
+		return cErrorf(errSerialization, "failed to deserialize proof: %w", err)
+	}
+	publicBytes, err := hex.DecodeString(strings.TrimPrefix(C.GoString(public_hex), "0x"))
+	if err != nil {
+		return cErrorf(errSerialization, "failed to decode public inputs hex: %w", err)
+	}
+	// A public witness is a 12-byte header (public, secret and vector
+	// lengths) followed by the inputs as 32-byte big-endian words.
+	if len(publicBytes) < 12 || (len(publicBytes)-12)%32 != 0 {
+		return cErrorf(errSerialization, "malformed public witness of %d bytes", len(publicBytes))
+	}
+	inputs := publicBytes[12:]
+
+	selector := sha3.NewLegacyKeccak256()
+	selector.Write([]byte(verifyProofSignature(len(proof.Commitments), len(inputs)/32)))
+	calldata := append(selector.Sum(nil)[:4], proof.MarshalSolidity()...)
+	var buf cBuffer
+	if _, err := buf.Write(append(calldata, inputs...)); err != nil {
+		buf.free()
+		return cError(err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
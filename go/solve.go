@@ -0,0 +1,250 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"context"
+	"fmt"
+	"math/big"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/consensys/gnark/constraint"
+	"github.com/consensys/gnark/constraint/solver"
+)
+
+// Solver tuning shared by proving and gnark_circuit_solve_profile.
+
+// solverConfig holds the solver knobs of C_ProveOptions. Zero means default.
+type solverConfig struct {
+	// tasks is the number of parallel solver workers (the thread budget, or
+	// one per CPU, if 0).
+	tasks int
+	// hintTimeout bounds each hint call (unbounded if 0).
+	hintTimeout time.Duration
+	// ctx, if set, fails every hint call once cancelled, which stops the
+	// solve at its next hint.
+	ctx context.Context
+}
+
+// solverConfigFrom reads the solver_tasks and hint_timeout_ms arguments of
+// an export, as in C_ProveOptions.
+func solverConfigFrom(solver_tasks C.int, hint_timeout_ms C.int64_t) solverConfig {
+	return solverConfig{
+		tasks:       int(solver_tasks),
+		hintTimeout: time.Duration(hint_timeout_ms) * time.Millisecond,
+	}
+}
+
+// options returns the gnark solver options for c. feed, if not nil, replaces
+// hintFeed; profile, if not nil, records every hint call; log, if not nil,
+// records or replays the hints' outputs (see hintLog).
+func (c solverConfig) options(feed solver.Hint, profile *hintProfile, log *hintLog) []solver.Option {
+	var opts []solver.Option
+	tasks := c.tasks
+	if tasks <= 0 {
+		tasks = int(threadBudget.Load())
+	}
+	if tasks > 0 {
+		opts = append(opts, solver.WithNbTasks(tasks))
+	}
+	feedID := solver.GetHintID(hintFeed)
+	for _, h := range solver.GetRegisteredHints() {
+		id := solver.GetHintID(h)
+		name := hintName(h)
+		wrapped := h
+		if id == feedID && feed != nil {
+			wrapped = feed
+		}
+		// The feeder's callback data lives on the caller's stack, so a feed
+		// call must not outlive the solve; it is never abandoned.
+		if c.hintTimeout > 0 && id != feedID {
+			wrapped = withHintTimeout(name, wrapped, c.hintTimeout)
+		}
+		if c.ctx != nil {
+			wrapped = withCancel(c.ctx, wrapped)
+		}
+		if profile != nil {
+			wrapped = profile.wrap(name, wrapped)
+		}
+		if log != nil {
+			wrapped = log.wrap(id, wrapped)
+		}
+		if id == feedID && feed != nil || c.hintTimeout > 0 || c.ctx != nil || profile != nil || log != nil {
+			opts = append(opts, solver.OverrideHint(id, wrapped))
+		}
+	}
+	return opts
+}
+
+// hintName is the name a circuit description calls h by, or gnark's name for
+// it (e.g. "github.com/consensys/gnark/std/math/bits.nNaf").
+func hintName(h solver.Hint) string {
+	for name, ch := range circuitHints {
+		if solver.GetHintID(ch) == solver.GetHintID(h) {
+			return name
+		}
+	}
+	switch solver.GetHintID(h) {
+	case solver.GetHintID(hintFeed):
+		return "feed"
+	case solver.GetHintID(hintRust):
+		return "rust"
+	}
+	return solver.GetHintName(h)
+}
+
+// withCancel fails calls of h once ctx is cancelled. gnark's solver stops at
+// the first hint error, so this is where a cancelled solve ends.
+func withCancel(ctx context.Context, h solver.Hint) solver.Hint {
+	return func(field *big.Int, inputs, outputs []*big.Int) error {
+		if err := cancelledError(ctx); err != nil {
+			return err
+		}
+		return h(field, inputs, outputs)
+	}
+}
+
+// withHintTimeout fails calls of h that take longer than timeout. The call
+// keeps running in the background and its outputs are discarded, which is
+// safe for every hint but the feeder (Rust hints live in a global registry).
+func withHintTimeout(name string, h solver.Hint, timeout time.Duration) solver.Hint {
+	return func(field *big.Int, inputs, outputs []*big.Int) error {
+		// The abandoned call must not write into the solver's outputs.
+		out := make([]*big.Int, len(outputs))
+		for i := range out {
+			out[i] = new(big.Int)
+		}
+		done := make(chan error, 1)
+		go func() {
+			defer func() {
+				if p := recover(); p != nil {
+					done <- newPanicError(p)
+				}
+			}()
+			done <- h(field, inputs, out)
+		}()
+		timer := time.NewTimer(timeout)
+		defer timer.Stop()
+		select {
+		case err := <-done:
+			if err != nil {
+				return err
+			}
+			for i := range outputs {
+				outputs[i].Set(out[i])
+			}
+			return nil
+		case <-timer.C:
+			return errorf(errInvalidWitness, "hint %s timed out after %s", name, timeout)
+		}
+	}
+}
+
+// hintTiming is the time spent in one hint, over all its calls.
+type hintTiming struct {
+	calls int
+	total time.Duration
+}
+
+// hintProfile records the hint calls of one solve. Rust hints are told
+// apart by their registry id.
+type hintProfile struct {
+	mu      sync.Mutex
+	timings map[string]*hintTiming
+	order   []string
+}
+
+func (p *hintProfile) wrap(name string, h solver.Hint) solver.Hint {
+	return func(field *big.Int, inputs, outputs []*big.Int) error {
+		key := name
+		if name == "rust" && len(inputs) > 0 {
+			key = "rust:" + inputs[0].String()
+		}
+		start := time.Now()
+		err := h(field, inputs, outputs)
+		p.record(key, time.Since(start))
+		return err
+	}
+}
+
+func (p *hintProfile) record(key string, d time.Duration) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	t, ok := p.timings[key]
+	if !ok {
+		if p.timings == nil {
+			p.timings = map[string]*hintTiming{}
+		}
+		t = &hintTiming{}
+		p.timings[key] = t
+		p.order = append(p.order, key)
+	}
+	t.calls++
+	t.total += d
+}
+
+// gnark_circuit_solve_profile solves a JSON witness (as for
+// gnark_groth16_prove) against a circuit and describes where the time went,
+// one record per line with tab-separated fields:
+//
+//	solve       <total nanoseconds>
+//	levels      <comma-separated constraint counts>
+//	hint        <name> <calls> <nanoseconds>
+//
+// gnark solves the constraints of one level in parallel and the levels in
+// order, so the level sizes bound how much solver_tasks can help. Hint
+// records come in order of first call. solver_tasks and hint_timeout_ms are
+// as in C_ProveOptions. The caller frees *out_profile with gnark_free_string.
+//
+//export gnark_circuit_solve_profile
+func gnark_circuit_solve_profile(
+	handle C.uint64_t,
+	witness_json *C.char,
+	solver_tasks C.int,
+	hint_timeout_ms C.int64_t,
+	out_profile **C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	sys, err := systemOf(ccs)
+	if err != nil {
+		return cError(err)
+	}
+	w, err := buildWitnessFromJSON(C.GoString(witness_json), ccs)
+	if err != nil {
+		return cErrorf(errInvalidWitness, "failed to build witness: %w", err)
+	}
+	config := solverConfigFrom(solver_tasks, hint_timeout_ms)
+	profile := new(hintProfile)
+	start := time.Now()
+	if _, err := ccs.Solve(w, config.options(nil, profile, nil)...); err != nil {
+		// Uncoded, so cError reports an unsatisfied constraint with its index.
+		return cError(fmt.Errorf("failed to solve witness: %w", err))
+	}
+	elapsed := time.Since(start)
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "solve\t%d\n", elapsed.Nanoseconds())
+	levels := make([]string, len(sys.Levels))
+	for i, level := range sys.Levels {
+		levels[i] = strconv.Itoa(len(level))
+	}
+	fmt.Fprintf(&b, "levels\t%s\n", strings.Join(levels, ","))
+	for _, key := range profile.order {
+		t := profile.timings[key]
+		fmt.Fprintf(&b, "hint\t%s\t%d\t%d\n", key, t.calls, t.total.Nanoseconds())
+	}
+	*out_profile = C.CString(b.String())
+	return nil
+}
@@ -0,0 +1,300 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bufio"
+	"bytes"
+	"crypto/rand"
+	"encoding/binary"
+	"io"
+	"math/big"
+	"os"
+
+	"github.com/consensys/gnark-crypto/ecc/bn254"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fp"
+	"github.com/consensys/gnark-crypto/ecc/bn254/fr"
+	"github.com/consensys/gnark-crypto/ecc/bn254/kzg"
+)
+
+// KZG structured reference strings for PLONK, imported from snarkjs /
+// Perpetual Powers of Tau `.ptau` files.
+//
+// A .ptau file is "ptau", a u32 version, a u32 section count, then sections of
+// (u32 type, u64 size, data). Section 1 holds the header (u32 n8, n8-byte
+// base field modulus, u32 power, u32 ceremony power), section 2 the powers of
+// tau in G1 and section 3 those in G2. Field elements are little-endian in
+// Montgomery form with R = 2^256, the same internal representation as
+// gnark-crypto's fp.Element, so limbs are copied as they are.
+
+const (
+	ptauSectionHeader = 1
+	ptauSectionTauG1  = 2
+	ptauSectionTauG2  = 3
+)
+
+type ptauSection struct {
+	offset, size int64
+}
+
+// readPtauSections indexes the sections of a .ptau file.
+func readPtauSections(f *os.File) (map[uint32]ptauSection, error) {
+	var head struct {
+		Magic    [4]byte
+		Version  uint32
+		Sections uint32
+	}
+	if err := binary.Read(f, binary.LittleEndian, &head); err != nil {
+		return nil, errorf(errSerialization, "failed to read ptau header: %w", err)
+	}
+	if string(head.Magic[:]) != "ptau" {
+		return nil, errorf(errSerialization, "not a ptau file")
+	}
+	sections := map[uint32]ptauSection{}
+	offset := int64(12)
+	for i := uint32(0); i < head.Sections; i++ {
+		var sh struct {
+			Type uint32
+			Size uint64
+		}
+		if err := binary.Read(io.NewSectionReader(f, offset, 12), binary.LittleEndian, &sh); err != nil {
+			return nil, errorf(errSerialization, "failed to read ptau section %d: %w", i, err)
+		}
+		offset += 12
+		sections[sh.Type] = ptauSection{offset: offset, size: int64(sh.Size)}
+		offset += int64(sh.Size)
+	}
+	return sections, nil
+}
+
+func readFp(r io.Reader, e *fp.Element) error {
+	var b [fp.Bytes]byte
+	if _, err := io.ReadFull(r, b[:]); err != nil {
+		return err
+	}
+	for i := range e {
+		e[i] = binary.LittleEndian.Uint64(b[8*i:])
+	}
+	return nil
+}
+
+// loadPtau converts the powers of tau in path to a KZG SRS with 2^power + 3
+// G1 points, enough for PLONK circuits of up to 2^power constraints.
+func loadPtau(path string) (*kzg.SRS, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, errorf(errIO, "failed to open ptau file: %w", err)
+	}
+	defer f.Close()
+
+	sections, err := readPtauSections(f)
+	if err != nil {
+		return nil, err
+	}
+	for _, t := range []uint32{ptauSectionHeader, ptauSectionTauG1, ptauSectionTauG2} {
+		if _, ok := sections[t]; !ok {
+			return nil, errorf(errSerialization, "ptau file has no section %d", t)
+		}
+	}
+
+	hs := sections[ptauSectionHeader]
+	hr := io.NewSectionReader(f, hs.offset, hs.size)
+	var n8 uint32
+	if err := binary.Read(hr, binary.LittleEndian, &n8); err != nil {
+		return nil, errorf(errSerialization, "failed to read ptau header: %w", err)
+	}
+	if n8 != fp.Bytes {
+		return nil, errorf(errCurveMismatch, "ptau field size is %d bytes, BN254 needs %d", n8, fp.Bytes)
+	}
+	q := make([]byte, n8)
+	if _, err := io.ReadFull(hr, q); err != nil {
+		return nil, errorf(errSerialization, "failed to read ptau header: %w", err)
+	}
+	for i, j := 0, len(q)-1; i < j; i, j = i+1, j-1 {
+		q[i], q[j] = q[j], q[i]
+	}
+	if new(big.Int).SetBytes(q).Cmp(fp.Modulus()) != 0 {
+		return nil, errorf(errCurveMismatch, "ptau file is not over BN254")
+	}
+	var power uint32
+	if err := binary.Read(hr, binary.LittleEndian, &power); err != nil {
+		return nil, errorf(errSerialization, "failed to read ptau header: %w", err)
+	}
+
+	g1s := sections[ptauSectionTauG1]
+	n := int64(1)<<power + 3
+	if g1s.size < n*2*fp.Bytes {
+		return nil, errorf(errSerialization, "ptau G1 section holds fewer than %d points", n)
+	}
+	srs := &kzg.SRS{}
+	srs.Pk.G1 = make([]bn254.G1Affine, n)
+	g1r := bufio.NewReaderSize(io.NewSectionReader(f, g1s.offset, g1s.size), 1<<20)
+	for i := range srs.Pk.G1 {
+		p := &srs.Pk.G1[i]
+		if err := readFp(g1r, &p.X); err != nil {
+			return nil, errorf(errSerialization, "failed to read tau^%d G1: %w", i, err)
+		}
+		if err := readFp(g1r, &p.Y); err != nil {
+			return nil, errorf(errSerialization, "failed to read tau^%d G1: %w", i, err)
+		}
+		if !p.IsOnCurve() {
+			return nil, errorf(errSerialization, "tau^%d G1 is not on the curve", i)
+		}
+	}
+
+	g2s := sections[ptauSectionTauG2]
+	g2r := io.NewSectionReader(f, g2s.offset, g2s.size)
+	for i := range srs.Vk.G2 {
+		p := &srs.Vk.G2[i]
+		for _, e := range []*fp.Element{&p.X.A0, &p.X.A1, &p.Y.A0, &p.Y.A1} {
+			if err := readFp(g2r, e); err != nil {
+				return nil, errorf(errSerialization, "failed to read tau^%d G2: %w", i, err)
+			}
+		}
+		if !p.IsInSubGroup() {
+			return nil, errorf(errSerialization, "tau^%d G2 is not in the subgroup", i)
+		}
+	}
+	srs.Vk.G1 = srs.Pk.G1[0]
+	srs.Vk.Lines[0] = bn254.PrecomputeLines(srs.Vk.G2[0])
+	srs.Vk.Lines[1] = bn254.PrecomputeLines(srs.Vk.G2[1])
+	return srs, nil
+}
+
+// gnark_srs_from_ptau loads a BN254 .ptau file into a KZG SRS handle.
+//
+//export gnark_srs_from_ptau
+func gnark_srs_from_ptau(path *C.char, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := loadPtau(C.GoString(path))
+	if err != nil {
+		return cError(err)
+	}
+	*out_handle = newHandle(srs)
+	return nil
+}
+
+// gnark_srs_size reports the number of G1 powers in an SRS.
+//
+//export gnark_srs_size
+func gnark_srs_size(handle C.uint64_t, out *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := lookupHandle[*kzg.SRS](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs handle: %w", err)
+	}
+	*out = C.uint64_t(len(srs.Pk.G1))
+	return nil
+}
+
+// gnark_srs_generate_insecure creates an SRS of size G1 powers from a random
+// tau that is known to this process, for tests only.
+//
+//export gnark_srs_generate_insecure
+func gnark_srs_generate_insecure(size C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	if size < 2 {
+		return cErrorf(errInvalidInput, "an srs needs at least 2 G1 powers, got %d", uint64(size))
+	}
+	tau, err := rand.Int(rand.Reader, fr.Modulus())
+	if err != nil {
+		return cErrorf(errOther, "failed to sample tau: %w", err)
+	}
+	srs, err := kzg.NewSRS(uint64(size), tau)
+	if err != nil {
+		return cErrorf(errOther, "failed to generate srs: %w", err)
+	}
+	*out_handle = newHandle(srs)
+	return nil
+}
+
+// gnark_srs_trim creates an SRS holding the first size G1 powers of another
+// and the same G2 points.
+//
+//export gnark_srs_trim
+func gnark_srs_trim(handle C.uint64_t, size C.uint64_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := lookupHandle[*kzg.SRS](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs handle: %w", err)
+	}
+	if size < 2 || uint64(size) > uint64(len(srs.Pk.G1)) {
+		return cErrorf(errInvalidInput, "cannot trim an srs of %d G1 powers to %d", len(srs.Pk.G1), uint64(size))
+	}
+	trimmed := &kzg.SRS{Vk: srs.Vk}
+	trimmed.Pk.G1 = append([]bn254.G1Affine(nil), srs.Pk.G1[:size]...)
+	*out_handle = newHandle(trimmed)
+	return nil
+}
+
+// gnark_srs_write serializes an SRS to path in gnark's kzg.SRS format.
+//
+//export gnark_srs_write
+func gnark_srs_write(handle C.uint64_t, path *C.char, format C.int) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := lookupHandle[*kzg.SRS](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs handle: %w", err)
+	}
+	write := func(w io.Writer) (int64, error) { return 0, writeWithFormat(srs, format, w) }
+	if err := writeFile(C.GoString(path), write); err != nil {
+		return cErrorf(errIO, "failed to write srs: %w", err)
+	}
+	return nil
+}
+
+// gnark_srs_read deserializes an SRS written by gnark_srs_write into a handle.
+//
+//export gnark_srs_read
+func gnark_srs_read(path *C.char, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	f, err := os.Open(C.GoString(path))
+	if err != nil {
+		return cErrorf(errIO, "failed to open srs file: %w", err)
+	}
+	defer f.Close()
+	srs := new(kzg.SRS)
+	if err := readWithFormat(srs, format, bufio.NewReaderSize(f, 1<<20)); err != nil {
+		return cErrorf(errSerialization, "failed to read srs: %w", err)
+	}
+	*out_handle = newHandle(srs)
+	return nil
+}
+
+// gnark_srs_to_bytes serializes an SRS in the given format into a C buffer
+// that the caller frees with gnark_free_buffer.
+//
+//export gnark_srs_to_bytes
+func gnark_srs_to_bytes(handle C.uint64_t, format C.int, out_data **C.uint8_t, out_len *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs, err := lookupHandle[*kzg.SRS](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid srs handle: %w", err)
+	}
+	var buf cBuffer
+	if err := writeWithFormat(srs, format, &buf); err != nil {
+		buf.free()
+		return cErrorf(errSerialization, "failed to serialize srs: %w", err)
+	}
+	buf.release(out_data, out_len)
+	return nil
+}
+
+// gnark_srs_from_bytes deserializes an SRS in the given format from
+// caller-owned memory into a handle.
+//
+//export gnark_srs_from_bytes
+func gnark_srs_from_bytes(data *C.uint8_t, length C.uint64_t, format C.int, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	srs := new(kzg.SRS)
+	if err := readWithFormat(srs, format, bytes.NewReader(borrowBytes(data, length))); err != nil {
+		return cErrorf(errSerialization, "failed to read srs: %w", err)
+	}
+	*out_handle = newHandle(srs)
+	return nil
+}
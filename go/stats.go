@@ -0,0 +1,198 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"fmt"
+	"math/big"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+
+	"github.com/consensys/gnark/constraint"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/profile"
+	pprof "github.com/google/pprof/profile"
+)
+
+// Circuit size: variable and constraint counts of a compiled system, and
+// which functions added the constraints, from gnark's profiler.
+
+// gadgetCount is the number of constraints a function added.
+type gadgetCount struct {
+	name string
+	// own counts constraints the function added itself, cum those added by
+	// it and everything it called.
+	own, cum int64
+}
+
+// compileMu keeps other compilations out of a profiled one: gnark's profiler
+// records every constraint added in the process while it runs.
+var compileMu sync.RWMutex
+
+// compileCircuit compiles circuit over field. If gadgets is not nil, it is
+// set to the constraints each function in the circuit's call tree added.
+func compileCircuit(field *big.Int, newBuilder frontend.NewBuilder, circuit frontend.Circuit, gadgets *[]gadgetCount) (constraint.ConstraintSystem, error) {
+	if gadgets == nil {
+		compileMu.RLock()
+		defer compileMu.RUnlock()
+		return frontend.Compile(field, newBuilder, circuit)
+	}
+
+	compileMu.Lock()
+	defer compileMu.Unlock()
+	dir, err := os.MkdirTemp("", "rust-gnark-profile")
+	if err != nil {
+		return nil, errorf(errIO, "failed to create profile directory: %w", err)
+	}
+	defer os.RemoveAll(dir)
+	path := filepath.Join(dir, "gnark.pprof")
+	p := profile.Start(profile.WithPath(path))
+	ccs, err := frontend.Compile(field, newBuilder, circuit)
+	p.Stop()
+	if err != nil {
+		return nil, err
+	}
+	if *gadgets, err = readGadgets(path); err != nil {
+		return nil, errorf(errSerialization, "failed to read constraint profile: %w", err)
+	}
+	return ccs, nil
+}
+
+// readGadgets sums the pprof profile gnark's profiler wrote to path by
+// function, skipping the frontend's own frames. Functions are ordered by
+// decreasing cumulative count.
+func readGadgets(path string) ([]gadgetCount, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	prof, err := pprof.Parse(f)
+	if err != nil {
+		return nil, err
+	}
+
+	counts := map[string]*gadgetCount{}
+	for _, s := range prof.Sample {
+		if len(s.Value) == 0 {
+			continue
+		}
+		n := s.Value[0]
+		seen := map[string]bool{}
+	frames:
+		// Leaf first; inlined functions come before their caller.
+		for _, loc := range s.Location {
+			for _, line := range loc.Line {
+				if line.Function == nil {
+					continue
+				}
+				name := line.Function.Name
+				if strings.HasPrefix(name, "github.com/consensys/gnark/frontend.") {
+					// frontend.Compile and the parser: the circuit's
+					// Define is below.
+					break frames
+				}
+				if internalFrame(name) || seen[name] {
+					continue
+				}
+				c, ok := counts[name]
+				if !ok {
+					c = &gadgetCount{name: name}
+					counts[name] = c
+				}
+				if len(seen) == 0 {
+					c.own += n
+				}
+				c.cum += n
+				seen[name] = true
+			}
+		}
+	}
+
+	gadgets := make([]gadgetCount, 0, len(counts))
+	for _, c := range counts {
+		gadgets = append(gadgets, *c)
+	}
+	sort.Slice(gadgets, func(i, j int) bool {
+		if gadgets[i].cum != gadgets[j].cum {
+			return gadgets[i].cum > gadgets[j].cum
+		}
+		return gadgets[i].name < gadgets[j].name
+	})
+	return gadgets, nil
+}
+
+// internalFrame reports whether a function is part of gnark's constraint
+// builders or the runtime rather than of a circuit or gadget.
+func internalFrame(name string) bool {
+	for _, prefix := range []string{
+		"github.com/consensys/gnark/frontend/",
+		"github.com/consensys/gnark/internal/",
+		"github.com/consensys/gnark/profile",
+		"runtime.",
+	} {
+		if strings.HasPrefix(name, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+// formatGadgets encodes gadgets for the FFI, one "name\town\tcum" line each.
+func formatGadgets(gadgets []gadgetCount) string {
+	var b strings.Builder
+	for _, g := range gadgets {
+		fmt.Fprintf(&b, "%s\t%d\t%d\n", g.name, g.own, g.cum)
+	}
+	return b.String()
+}
+
+// gnark_circuit_stats reports the size of a compiled circuit: its
+// constraints, public and secret inputs (without an R1CS's constant wire)
+// and internal variables.
+//
+//export gnark_circuit_stats
+func gnark_circuit_stats(
+	handle C.uint64_t,
+	out_constraints *C.uint64_t,
+	out_public *C.uint64_t,
+	out_secret *C.uint64_t,
+	out_internal *C.uint64_t,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	public, secret, err := inputNames(ccs)
+	if err != nil {
+		return cError(err)
+	}
+	*out_constraints = C.uint64_t(ccs.GetNbConstraints())
+	*out_public = C.uint64_t(len(public))
+	*out_secret = C.uint64_t(len(secret))
+	*out_internal = C.uint64_t(ccs.GetNbInternalVariables())
+	return nil
+}
+
+// gnark_circuit_nb_commitments reports the number of commitments (from
+// api.Commit) in a compiled circuit; each adds a point to its proofs.
+//
+//export gnark_circuit_nb_commitments
+func gnark_circuit_nb_commitments(handle C.uint64_t, out *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	ccs, err := lookupHandle[constraint.ConstraintSystem](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid circuit handle: %w", err)
+	}
+	*out = C.uint64_t(len(ccs.GetCommitments().CommitmentIndexes()))
+	return nil
+}
@@ -0,0 +1,62 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"bufio"
+	"crypto/sha256"
+	"io"
+	"os"
+	"unsafe"
+)
+
+// gnark_file_sha256 writes the 32-byte SHA-256 of the file at path to out,
+// for checksumming stored artifacts.
+//
+//export gnark_file_sha256
+func gnark_file_sha256(path *C.char, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	f, err := os.Open(C.GoString(path))
+	if err != nil {
+		return cErrorf(errIO, "failed to open %s: %w", C.GoString(path), err)
+	}
+	defer f.Close()
+	h := sha256.New()
+	if _, err := io.Copy(h, f); err != nil {
+		return cErrorf(errIO, "failed to read %s: %w", C.GoString(path), err)
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), sha256.Size), h.Sum(nil))
+	return nil
+}
+
+// readFile streams the file at path into read, which decoders of keys and
+// constraint systems above 4 GiB rely on: nothing is read into memory whole.
+func readFile(path string, read func(r io.Reader) (int64, error)) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	_, err = read(bufio.NewReaderSize(f, 1<<20))
+	return err
+}
+
+func writeFile(path string, write func(w io.Writer) (int64, error)) error {
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	w := bufio.NewWriterSize(f, 1<<20)
+	if _, err := write(w); err != nil {
+		f.Close()
+		return err
+	}
+	if err := w.Flush(); err != nil {
+		f.Close()
+		return err
+	}
+	return f.Close()
+}
@@ -0,0 +1,63 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"encoding/json"
+
+	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/test"
+)
+
+// gnark's test engine runs a circuit's Define directly on the assigned
+// values, failing at the first assertion that does not hold. There is no
+// compilation, constraint system or setup, which makes it the quick way to
+// unit test a circuit.
+
+// gnark_circuit_test_engine runs the JSON circuit description desc on a JSON
+// witness (as for gnark_groth16_prove) with gnark's test engine over BN254. If
+// the witness satisfies the circuit *out_failure is left NULL; otherwise it is
+// set to the failing assertion, which the caller frees with gnark_free_string.
+//
+//export gnark_circuit_test_engine
+func gnark_circuit_test_engine(desc *C.char, witness_json *C.char, out_failure **C.char) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	circuit, publicNames, secretNames, err := parseCircuitDesc(C.GoString(desc))
+	if err != nil {
+		return cError(err)
+	}
+	var values map[string]interface{}
+	if err := json.Unmarshal([]byte(C.GoString(witness_json)), &values); err != nil {
+		return cErrorf(errInvalidWitness, "failed to parse witness JSON: %w", err)
+	}
+	_, sparse := values[sparseWitnessKey]
+	assign := func(names []string, kind string) ([]frontend.Variable, error) {
+		vars := make([]frontend.Variable, len(names))
+		for i, name := range names {
+			v, ok := values[name]
+			if !ok && !sparse {
+				return nil, errorf(errInvalidWitness, "missing witness value for %s variable %q", kind, name)
+			}
+			vars[i] = toFieldElementOrZero(v, ok)
+		}
+		return vars, nil
+	}
+	assignment := &dslCircuit{Desc: circuit.Desc}
+	if assignment.Public, err = assign(publicNames, "public"); err != nil {
+		return cError(err)
+	}
+	if assignment.Secret, err = assign(secretNames, "secret"); err != nil {
+		return cError(err)
+	}
+
+	if err := test.IsSolved(circuit, assignment, ecc.BN254.ScalarField()); err != nil {
+		*out_failure = C.CString(err.Error())
+	}
+	return nil
+}
@@ -0,0 +1,102 @@
+//go:build !verifier
+
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"crypto/sha256"
+	"hash"
+	"slices"
+	"unsafe"
+
+	bn254poseidon2 "github.com/consensys/gnark-crypto/ecc/bn254/fr/poseidon2"
+	fiatshamir "github.com/consensys/gnark-crypto/fiat-shamir"
+	"golang.org/x/crypto/sha3"
+)
+
+// Fiat-Shamir transcripts from gnark-crypto's fiat-shamir package, which
+// gnark's PLONK prover and verifier derive their challenges with, so that
+// protocols built outside gnark compute the same challenges.
+
+// Transcript hashes for gnark_transcript_new; must match TranscriptHash on
+// the Rust side.
+const (
+	transcriptSHA256 = iota // gnark's PLONK default
+	transcriptKeccak256
+	transcriptPoseidon2 // BN254, Merkle-Damgård, as in-circuit verifiers use
+)
+
+func transcriptHash(id C.int) (hash.Hash, error) {
+	switch id {
+	case transcriptSHA256:
+		return sha256.New(), nil
+	case transcriptKeccak256:
+		return sha3.NewLegacyKeccak256(), nil
+	case transcriptPoseidon2:
+		return bn254poseidon2.NewMerkleDamgardHasher(), nil
+	default:
+		return nil, errorf(errInvalidInput, "unknown transcript hash %d", int(id))
+	}
+}
+
+// gnark_transcript_new creates a transcript over the hash with id hash_id for
+// the n challenges named by labels, to be computed in that order.
+//
+//export gnark_transcript_new
+func gnark_transcript_new(hash_id C.int, labels **C.char, n C.uint32_t, out_handle *C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	h, err := transcriptHash(hash_id)
+	if err != nil {
+		return cError(err)
+	}
+	ids := make([]string, n)
+	for i, label := range unsafe.Slice(labels, int(n)) {
+		ids[i] = C.GoString(label)
+	}
+	*out_handle = newHandle(fiatshamir.NewTranscript(h, ids...))
+	return nil
+}
+
+// gnark_transcript_bind appends n bytes at data to the inputs of the
+// challenge label.
+//
+//export gnark_transcript_bind
+func gnark_transcript_bind(handle C.uint64_t, label *C.char, data *C.uint8_t, n C.uint64_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	fs, err := lookupHandle[*fiatshamir.Transcript](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid transcript handle: %w", err)
+	}
+	// The transcript keeps the bytes until the challenge is computed.
+	value := slices.Clone(borrowBytes(data, n))
+	if err := fs.Bind(C.GoString(label), value); err != nil {
+		return cErrorf(errInvalidInput, "%w", err)
+	}
+	return nil
+}
+
+// gnark_transcript_challenge computes the challenge label (hashing its name,
+// the previous challenge and its bound inputs) and writes its 32-byte digest
+// to out.
+//
+//export gnark_transcript_challenge
+func gnark_transcript_challenge(handle C.uint64_t, label *C.char, out *C.uint8_t) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	fs, err := lookupHandle[*fiatshamir.Transcript](handle)
+	if err != nil {
+		return cErrorf(errInvalidInput, "invalid transcript handle: %w", err)
+	}
+	challenge, err := fs.ComputeChallenge(C.GoString(label))
+	if err != nil {
+		return cErrorf(errInvalidInput, "%w", err)
+	}
+	if len(challenge) != 32 {
+		return cErrorf(errOther, "challenge has %d bytes, expected 32", len(challenge))
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(out)), 32), challenge)
+	return nil
+}
@@ -0,0 +1,60 @@
+package main
+
+/*
+#include <stdint.h>
+*/
+import "C"
+
+import (
+	"runtime"
+	"runtime/debug"
+	"strings"
+)
+
+// gnark_version_info describes how this library was built, one record per
+// line with tab-separated fields:
+//
+//	go          <Go version>
+//	module      <path> <version> <go.sum hash>
+//	vcs         <revision> <"true" if the tree had local changes>
+//	tags        <comma-separated build tags>
+//
+// Module and VCS records are missing if the build carries no such
+// information. The caller frees the string with gnark_free_string.
+//
+//export gnark_version_info
+func gnark_version_info() *C.char {
+	var b strings.Builder
+	record := func(fields ...string) {
+		b.WriteString(strings.Join(fields, "\t"))
+		b.WriteByte('\n')
+	}
+	record("go", runtime.Version())
+	if info, ok := debug.ReadBuildInfo(); ok {
+		for _, dep := range info.Deps {
+			// A replaced module is reported under its own path, with the
+			// version that was built.
+			built := dep
+			if dep.Replace != nil {
+				built = dep.Replace
+			}
+			record("module", dep.Path, built.Version, built.Sum)
+		}
+		var revision, modified, tags string
+		for _, s := range info.Settings {
+			switch s.Key {
+			case "vcs.revision":
+				revision = s.Value
+			case "vcs.modified":
+				modified = s.Value
+			case "-tags":
+				tags = s.Value
+			}
+		}
+		if revision != "" {
+			record("vcs", revision, modified)
+		}
+		record("tags", tags)
+	}
+	return C.CString(b.String())
+}
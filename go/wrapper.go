@@ -1,174 +1,139 @@
 package main
 
 /*
+#include <stdint.h>
 #include <stdlib.h>
 
 // Result struct for Groth16 proof generation.
 // On success: proof and public_inputs are set, error is NULL.
 // On failure: error is set, proof and public_inputs are NULL.
 typedef struct {
-    char *proof;          // hex-encoded binary proof (WriteTo serialization)
-    char *public_inputs;  // hex-encoded binary public witness (MarshalBinary)
-    char *error;          // error message or NULL on success
+    char *proof;             // hex-encoded binary proof (WriteTo serialization)
+    char *public_inputs;     // hex-encoded binary public witness (MarshalBinary)
+    char *error;             // encoded error (see cError in errors.go) or NULL on success
+    uint64_t nb_constraints; // constraints of the circuit, 0 if it was not loaded
 } C_Groth16ProofResult;
 */
 import "C"
 
+// Built with -tags=verifier (the rust-gnark-sys `verifier` feature), the
+// library only holds what verifying needs: this file, verifying keys, Solidity
+// export and the runtime helpers. The prover, circuit compilation, setup and
+// every file tagged !verifier are left out, and with them most of gnark and
+// gnark-crypto, so apps that only verify ship a fraction of the library.
+
 import (
 	"bytes"
 	"encoding/hex"
-	"encoding/json"
-	"fmt"
 	"os"
-	"runtime"
 	"unsafe"
 
 	"github.com/consensys/gnark-crypto/ecc"
-	"github.com/consensys/gnark/backend/groth16"
+	groth16_bn254 "github.com/consensys/gnark/backend/groth16/bn254"
 	"github.com/consensys/gnark/backend/witness"
-	"github.com/consensys/gnark/constraint"
 	cs_bn254 "github.com/consensys/gnark/constraint/bn254"
 )
 
-//export gnark_init
-func gnark_init() C.int {
-	// On iOS debug builds, disable async preemption to prevent signal flood
-	// crashes with the Go runtime.
-	if runtime.GOOS == "ios" || runtime.GOOS == "darwin" {
-		os.Setenv("GODEBUG", "asyncpreemptoff=1")
-	}
-	return 0
+//export gnark_groth16_verify
+func gnark_groth16_verify(
+	r1cs_path *C.char,
+	vk_path *C.char,
+	proof_hex *C.char,
+	public_inputs_hex *C.char,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	return gnark_groth16_verify_with_hash(r1cs_path, vk_path, proof_hex, public_inputs_hex, hashDefault)
 }
 
-//export gnark_groth16_prove
-func gnark_groth16_prove(
+// gnark_groth16_verify_with_hash is gnark_groth16_verify for proofs generated
+// with a non-default hash-to-field function (C_ProveOptions.hash).
+//
+//export gnark_groth16_verify_with_hash
+func gnark_groth16_verify_with_hash(
 	r1cs_path *C.char,
-	pk_path *C.char,
-	witness_json *C.char,
-) *C.C_Groth16ProofResult {
-	result := (*C.C_Groth16ProofResult)(C.malloc(C.size_t(unsafe.Sizeof(C.C_Groth16ProofResult{}))))
-	result.proof = nil
-	result.public_inputs = nil
-	result.error = nil
+	vk_path *C.char,
+	proof_hex *C.char,
+	public_inputs_hex *C.char,
+	hash C.int,
+) (errOut *C.char) {
+	defer catchPanic(&errOut)
+	verifierOpts, err := verifierHashOptions(int(hash))
+	if err != nil {
+		return cError(err)
+	}
 
-	cs := groth16.NewCS(ecc.BN254)
+	cs := new(cs_bn254.R1CS)
 	r1csFile, err := os.Open(C.GoString(r1cs_path))
 	if err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to open r1cs file: %v", err))
-		return result
+		return cErrorf(errIO, "failed to open r1cs file: %w", err)
 	}
 	defer r1csFile.Close()
 
 	if _, err := cs.ReadFrom(r1csFile); err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to read r1cs: %v", err))
-		return result
+		return cErrorf(errSerialization, "failed to read r1cs: %w", err)
 	}
 
-	// UnsafeReadFrom for speed -- trusted local file, skip validation
-	pk := groth16.NewProvingKey(ecc.BN254)
-	pkFile, err := os.Open(C.GoString(pk_path))
+	vk := new(groth16_bn254.VerifyingKey)
+	vkFile, err := os.Open(C.GoString(vk_path))
 	if err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to open pk file: %v", err))
-		return result
+		return cErrorf(errIO, "failed to open vk file: %w", err)
 	}
-	defer pkFile.Close()
+	defer vkFile.Close()
 
-	if _, err := pk.UnsafeReadFrom(pkFile); err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to read proving key: %v", err))
-		return result
+	if _, err := vk.ReadFrom(vkFile); err != nil {
+		return cErrorf(errInvalidKey, "failed to read verifying key: %w", err)
 	}
 
-	witnessJSON := C.GoString(witness_json)
-	fullWitness, err := buildWitnessFromJSON(witnessJSON, cs)
+	proof, err := decodeBN254Proof(C.GoString(proof_hex))
 	if err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to build witness: %v", err))
-		return result
+		return cError(err)
 	}
-
-	proof, err := groth16.Prove(cs, pk, fullWitness)
+	pubWitness, err := decodeBN254PublicWitness(C.GoString(public_inputs_hex))
 	if err != nil {
-		result.error = C.CString(fmt.Sprintf("proof generation failed: %v", err))
-		return result
-	}
-
-	var proofBuf bytes.Buffer
-	if _, err := proof.WriteTo(&proofBuf); err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to serialize proof: %v", err))
-		return result
+		return cError(err)
 	}
-	result.proof = C.CString(hex.EncodeToString(proofBuf.Bytes()))
 
-	pubWitness, err := fullWitness.Public()
-	if err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to extract public witness: %v", err))
-		return result
+	if err := verifyBN254(proof, vk, pubWitness, verifierOpts...); err != nil {
+		return cErrorf(errInvalidProof, "invalid proof: %w", err)
 	}
-	pubBin, err := pubWitness.MarshalBinary()
-	if err != nil {
-		result.error = C.CString(fmt.Sprintf("failed to marshal public witness: %v", err))
-		return result
-	}
-	result.public_inputs = C.CString(hex.EncodeToString(pubBin))
 
-	return result
+	// NULL = valid proof
+	return nil
 }
 
-//export gnark_groth16_verify
-func gnark_groth16_verify(
-	r1cs_path *C.char,
-	vk_path *C.char,
-	proof_hex *C.char,
-	public_inputs_hex *C.char,
-) *C.char {
-	cs := groth16.NewCS(ecc.BN254)
-	r1csFile, err := os.Open(C.GoString(r1cs_path))
-	if err != nil {
-		return C.CString(fmt.Sprintf("failed to open r1cs file: %v", err))
-	}
-	defer r1csFile.Close()
-
-	if _, err := cs.ReadFrom(r1csFile); err != nil {
-		return C.CString(fmt.Sprintf("failed to read r1cs: %v", err))
-	}
-
-	vk := groth16.NewVerifyingKey(ecc.BN254)
-	vkFile, err := os.Open(C.GoString(vk_path))
+// decodeBN254Proof decodes a hex-encoded BN254 Groth16 proof.
+func decodeBN254Proof(proofHex string) (*groth16_bn254.Proof, error) {
+	proofBytes, err := hex.DecodeString(proofHex)
 	if err != nil {
-		return C.CString(fmt.Sprintf("failed to open vk file: %v", err))
-	}
-	defer vkFile.Close()
-
-	if _, err := vk.ReadFrom(vkFile); err != nil {
-		return C.CString(fmt.Sprintf("failed to read verifying key: %v", err))
+		return nil, errorf(errSerialization, "failed to decode proof hex: %w", err)
 	}
-
-	proofBytes, err := hex.DecodeString(C.GoString(proof_hex))
-	if err != nil {
-		return C.CString(fmt.Sprintf("failed to decode proof hex: %v", err))
+	if err := checkProofEncoding(ecc.BN254, proofBytes); err != nil {
+		return nil, errorf(errSerialization, "failed to deserialize proof: %w", err)
 	}
-	proof := groth16.NewProof(ecc.BN254)
+	proof := new(groth16_bn254.Proof)
 	if _, err := proof.ReadFrom(bytes.NewReader(proofBytes)); err != nil {
-		return C.CString(fmt.Sprintf("failed to deserialize proof: %v", err))
+		return nil, errorf(errSerialization, "failed to deserialize proof: %w", err)
 	}
+	return proof, nil
+}
 
-	pubBytes, err := hex.DecodeString(C.GoString(public_inputs_hex))
+// decodeBN254PublicWitness decodes a hex-encoded BN254 public witness.
+func decodeBN254PublicWitness(publicHex string) (witness.Witness, error) {
+	pubBytes, err := hex.DecodeString(publicHex)
 	if err != nil {
-		return C.CString(fmt.Sprintf("failed to decode public inputs hex: %v", err))
+		return nil, errorf(errSerialization, "failed to decode public inputs hex: %w", err)
+	}
+	if err := checkPublicWitnessEncoding(ecc.BN254, pubBytes); err != nil {
+		return nil, errorf(errSerialization, "failed to unmarshal public witness: %w", err)
 	}
 	pubWitness, err := witness.New(ecc.BN254.ScalarField())
 	if err != nil {
-		return C.CString(fmt.Sprintf("failed to create witness: %v", err))
+		return nil, errorf(errSerialization, "failed to create witness: %w", err)
 	}
 	if err := pubWitness.UnmarshalBinary(pubBytes); err != nil {
-		return C.CString(fmt.Sprintf("failed to unmarshal public witness: %v", err))
+		return nil, errorf(errSerialization, "failed to unmarshal public witness: %w", err)
 	}
-
-	if err := groth16.Verify(proof, vk, pubWitness); err != nil {
-		return C.CString(fmt.Sprintf("invalid proof: %v", err))
-	}
-
-	// NULL = valid proof
-	return nil
+	return pubWitness, nil
 }
 
 //export gnark_free_proof_result
@@ -195,82 +160,4 @@ func gnark_free_string(s *C.char) {
 	}
 }
 
-// buildWitnessFromJSON creates a gnark witness from a JSON object mapping
-// circuit variable names to decimal string values.
-//
-// The JSON format is: {"VarName": "decimal_value", ...}
-// Variable names must match those defined in the circuit (via gnark struct tags).
-//
-// This function accesses the R1CS's embedded variable name lists (Public/Secret)
-// to determine the correct ordering, then uses witness.Fill to populate values.
-func buildWitnessFromJSON(jsonStr string, cs constraint.ConstraintSystem) (witness.Witness, error) {
-	var flatMap map[string]interface{}
-	if err := json.Unmarshal([]byte(jsonStr), &flatMap); err != nil {
-		return nil, fmt.Errorf("failed to parse witness JSON: %w", err)
-	}
-
-	// Type-assert to the concrete BN254 R1CS to access variable name lists.
-	// The constraint.System struct (embedded in R1CS) stores Public and Secret
-	// variable names as []string. Public[0] is always "1" (the constant wire).
-	r1cs, ok := cs.(*cs_bn254.R1CS)
-	if !ok {
-		return nil, fmt.Errorf("expected BN254 R1CS, got %T", cs)
-	}
-
-	// Skip "1" constant wire in public variables
-	publicNames := r1cs.Public[1:]
-	secretNames := r1cs.Secret
-
-	nbPublic := len(publicNames)
-	nbSecret := len(secretNames)
-
-	// Create a buffered channel to feed values in witness order:
-	// public variables first, then secret variables.
-	values := make(chan any, nbPublic+nbSecret)
-
-	for _, name := range publicNames {
-		val, exists := flatMap[name]
-		if !exists {
-			return nil, fmt.Errorf("missing witness value for public variable %q", name)
-		}
-		values <- toFieldElement(val)
-	}
-
-	for _, name := range secretNames {
-		val, exists := flatMap[name]
-		if !exists {
-			return nil, fmt.Errorf("missing witness value for secret variable %q", name)
-		}
-		values <- toFieldElement(val)
-	}
-	close(values)
-
-	w, err := witness.New(cs.Field())
-	if err != nil {
-		return nil, fmt.Errorf("failed to create witness: %w", err)
-	}
-
-	if err := w.Fill(nbPublic, nbSecret, values); err != nil {
-		return nil, fmt.Errorf("failed to fill witness: %w", err)
-	}
-
-	return w, nil
-}
-
-// toFieldElement converts a JSON value to a type gnark accepts as a field element.
-// gnark field elements can be constructed from: string (decimal), int64, *big.Int.
-func toFieldElement(v interface{}) interface{} {
-	switch val := v.(type) {
-	case string:
-		return val // gnark accepts decimal strings directly
-	case float64:
-		// JSON numbers are decoded as float64 by default
-		return int64(val)
-	case json.Number:
-		return val.String()
-	default:
-		return fmt.Sprintf("%v", val)
-	}
-}
-
 func main() {} // required for c-archive build mode
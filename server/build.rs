@@ -0,0 +1,12 @@
+fn main() {
+    println!("cargo:rerun-if-changed=proto/prover.proto");
+    #[cfg(feature = "grpc")]
+    {
+        // A vendored protoc, so building the server needs no system install.
+        std::env::set_var(
+            "PROTOC",
+            protoc_bin_vendored::protoc_bin_path().expect("no vendored protoc for this host"),
+        );
+        tonic_build::compile_protos("proto/prover.proto").expect("failed to compile prover.proto");
+    }
+}
@@ -0,0 +1,326 @@
+//! `rust-gnark-server`: a gRPC proving service, for running rust-gnark as a
+//! sidecar instead of linking it into every service that needs proofs.
+//!
+//! The API is in `proto/prover.proto`. Circuits are loaded into
+//! [`ProverSession`]s, by `--circuit` at startup or the `LoadCircuit` call, and
+//! proofs run on a [`ProvePool`], so a full queue is answered with
+//! `RESOURCE_EXHAUSTED` rather than buffered, and a proof is cancelled when its
+//! client disconnects.
+
+use anyhow::{bail, Context, Result};
+use rust_gnark::{
+    groth16_verify, GnarkError, Groth16ProofResult, ProvePool, ProveTask, ProverSession,
+    VerifyingKey,
+};
+use std::collections::BTreeMap;
+use std::env;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tonic::{Request, Response, Status, Streaming};
+
+mod proto {
+    tonic::include_proto!("rust_gnark.v1");
+}
+
+use proto::prove_request::Part;
+use proto::prover_server::{Prover, ProverServer};
+
+const USAGE: &str = "\
+Usage: rust-gnark-server [options]
+
+Options:
+  --listen <addr>                  Address to serve on (default: 127.0.0.1:50051)
+  --workers <n>                    Proofs run at once (default: 1)
+  --queue <n>                      Proofs waiting for a worker before new ones are refused (default: 16)
+  --max-witness-bytes <n>          Largest accepted witness upload (default: 64 MiB)
+  --circuit <name>=<r1cs>,<pk>,<vk>  Load a circuit at startup; may be repeated";
+
+struct Args {
+    listen: SocketAddr,
+    workers: usize,
+    queue: usize,
+    max_witness_bytes: usize,
+    circuits: Vec<(String, CircuitPaths)>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self {
+            listen: "127.0.0.1:50051".parse()?,
+            workers: 1,
+            queue: 16,
+            max_witness_bytes: 64 << 20,
+            circuits: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("{arg} needs a value\n\n{USAGE}"))
+            };
+            match arg.as_str() {
+                "--listen" => parsed.listen = value()?.parse()?,
+                "--workers" => parsed.workers = value()?.parse()?,
+                "--queue" => parsed.queue = value()?.parse()?,
+                "--max-witness-bytes" => parsed.max_witness_bytes = value()?.parse()?,
+                "--circuit" => parsed.circuits.push(parse_circuit(&value()?)?),
+                "--help" | "-h" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                _ => bail!("unknown option `{arg}`\n\n{USAGE}"),
+            }
+        }
+        if parsed.workers == 0 {
+            bail!("need at least one worker\n\n{USAGE}");
+        }
+        Ok(parsed)
+    }
+}
+
+/// Parse `<name>=<r1cs>,<pk>,<vk>`.
+fn parse_circuit(spec: &str) -> Result<(String, CircuitPaths)> {
+    let parsed = spec.split_once('=').and_then(|(name, paths)| {
+        let mut paths = paths.split(',');
+        let circuit = CircuitPaths {
+            r1cs: paths.next()?.into(),
+            pk: paths.next()?.into(),
+            vk: paths.next()?.into(),
+        };
+        paths.next().is_none().then(|| (name.to_owned(), circuit))
+    });
+    parsed.with_context(|| format!("invalid circuit `{spec}`, expected <name>=<r1cs>,<pk>,<vk>"))
+}
+
+struct CircuitPaths {
+    r1cs: String,
+    pk: String,
+    vk: String,
+}
+
+/// A loaded circuit.
+struct Circuit {
+    session: Arc<ProverSession>,
+    paths: CircuitPaths,
+    vk_fingerprint: String,
+}
+
+impl Circuit {
+    fn load(paths: CircuitPaths) -> Result<Self> {
+        let session = ProverSession::new(&paths.r1cs, &paths.pk)?;
+        let vk_fingerprint = VerifyingKey::open(&paths.vk)?.fingerprint()?.to_string();
+        Ok(Self {
+            session: Arc::new(session),
+            paths,
+            vk_fingerprint,
+        })
+    }
+
+    fn describe(&self, name: &str) -> proto::Circuit {
+        proto::Circuit {
+            name: name.to_owned(),
+            vk_fingerprint: self.vk_fingerprint.clone(),
+        }
+    }
+}
+
+struct Service {
+    circuits: RwLock<BTreeMap<String, Arc<Circuit>>>,
+    pool: ProvePool,
+    max_witness_bytes: usize,
+}
+
+impl Service {
+    fn circuit(&self, name: &str) -> Result<Arc<Circuit>, Status> {
+        self.circuits
+            .read()
+            .expect("circuit registry poisoned")
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("no circuit named {name:?} is loaded")))
+    }
+}
+
+#[tonic::async_trait]
+impl Prover for Service {
+    async fn load_circuit(
+        &self,
+        request: Request<proto::LoadCircuitRequest>,
+    ) -> Result<Response<proto::LoadCircuitResponse>, Status> {
+        let request = request.into_inner();
+        if request.name.is_empty() {
+            return Err(Status::invalid_argument("a circuit needs a name"));
+        }
+        let paths = CircuitPaths {
+            r1cs: request.r1cs_path,
+            pk: request.pk_path,
+            vk: request.vk_path,
+        };
+        let circuit = blocking(move || Circuit::load(paths)).await?;
+        let described = circuit.describe(&request.name);
+        self.circuits
+            .write()
+            .expect("circuit registry poisoned")
+            .insert(request.name, Arc::new(circuit));
+        Ok(Response::new(proto::LoadCircuitResponse {
+            circuit: Some(described),
+        }))
+    }
+
+    async fn unload_circuit(
+        &self,
+        request: Request<proto::UnloadCircuitRequest>,
+    ) -> Result<Response<proto::UnloadCircuitResponse>, Status> {
+        let name = request.into_inner().name;
+        self.circuits
+            .write()
+            .expect("circuit registry poisoned")
+            .remove(&name)
+            .ok_or_else(|| Status::not_found(format!("no circuit named {name:?} is loaded")))?;
+        Ok(Response::new(proto::UnloadCircuitResponse {}))
+    }
+
+    async fn list_circuits(
+        &self,
+        _request: Request<proto::ListCircuitsRequest>,
+    ) -> Result<Response<proto::ListCircuitsResponse>, Status> {
+        let circuits = self
+            .circuits
+            .read()
+            .expect("circuit registry poisoned")
+            .iter()
+            .map(|(name, circuit)| circuit.describe(name))
+            .collect();
+        Ok(Response::new(proto::ListCircuitsResponse { circuits }))
+    }
+
+    async fn prove(
+        &self,
+        request: Request<Streaming<proto::ProveRequest>>,
+    ) -> Result<Response<proto::ProveResponse>, Status> {
+        let mut stream = request.into_inner();
+        let name = match stream.message().await?.and_then(|m| m.part) {
+            Some(Part::Circuit(name)) => name,
+            _ => {
+                return Err(Status::invalid_argument(
+                    "the first message must name the circuit",
+                ))
+            }
+        };
+        let circuit = self.circuit(&name)?;
+
+        let mut witness = Vec::new();
+        while let Some(message) = stream.message().await? {
+            let Some(Part::WitnessChunk(chunk)) = message.part else {
+                return Err(Status::invalid_argument(
+                    "only witness chunks may follow the circuit name",
+                ));
+            };
+            if witness.len() + chunk.len() > self.max_witness_bytes {
+                return Err(Status::resource_exhausted(format!(
+                    "the witness is larger than {} bytes",
+                    self.max_witness_bytes
+                )));
+            }
+            witness.extend_from_slice(&chunk);
+        }
+        let witness = String::from_utf8(witness)
+            .map_err(|_| Status::invalid_argument("the witness is not UTF-8 JSON"))?;
+
+        let task = self
+            .pool
+            .try_submit(&circuit.session, &witness)
+            .ok_or_else(|| Status::resource_exhausted("the prove queue is full"))?;
+        let mut task = CancelOnDrop(task);
+        let proof = (&mut task.0).await.map_err(status)?;
+        Ok(Response::new(proto::ProveResponse {
+            proof: proof.proof,
+            public_inputs: proof.public_inputs,
+        }))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<proto::VerifyRequest>,
+    ) -> Result<Response<proto::VerifyResponse>, Status> {
+        let request = request.into_inner();
+        let circuit = self.circuit(&request.circuit)?;
+        let proof = Groth16ProofResult {
+            proof: request.proof,
+            public_inputs: request.public_inputs,
+        };
+        let valid =
+            blocking(move || groth16_verify(&circuit.paths.r1cs, &circuit.paths.vk, &proof))
+                .await?;
+        Ok(Response::new(proto::VerifyResponse { valid }))
+    }
+}
+
+/// Cancels a proof whose request is dropped, which tonic does when the client
+/// disconnects or its deadline passes.
+struct CancelOnDrop(ProveTask);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.0.is_finished() {
+            self.0.cancel();
+        }
+    }
+}
+
+/// Run a blocking rust-gnark call off the async workers.
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T, Status> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(status)
+}
+
+/// The gRPC status for a rust-gnark error.
+fn status(err: anyhow::Error) -> Status {
+    let message = format!("{err:#}");
+    match err.downcast_ref::<GnarkError>() {
+        Some(
+            GnarkError::InvalidInput(_)
+            | GnarkError::InvalidWitness(_)
+            | GnarkError::WitnessSolveFailed { .. }
+            | GnarkError::SerializationError(_)
+            | GnarkError::InvalidProof(_),
+        ) => Status::invalid_argument(message),
+        Some(GnarkError::Io(_)) => Status::not_found(message),
+        Some(GnarkError::InvalidKey(_) | GnarkError::CurveMismatch(_)) => {
+            Status::failed_precondition(message)
+        }
+        Some(GnarkError::Cancelled) => Status::cancelled(message),
+        Some(GnarkError::OutOfMemory) => Status::resource_exhausted(message),
+        _ => Status::internal(message),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse(env::args().skip(1))?;
+    rust_gnark::init()?;
+
+    let mut circuits = BTreeMap::new();
+    for (name, paths) in args.circuits {
+        let circuit =
+            Circuit::load(paths).with_context(|| format!("failed to load circuit {name:?}"))?;
+        circuits.insert(name, Arc::new(circuit));
+    }
+    let service = Service {
+        circuits: RwLock::new(circuits),
+        pool: ProvePool::new(args.workers, args.queue)?,
+        max_witness_bytes: args.max_witness_bytes,
+    };
+
+    eprintln!("rust-gnark-server listening on {}", args.listen);
+    tonic::transport::Server::builder()
+        .add_service(ProverServer::new(service))
+        .serve_with_shutdown(args.listen, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
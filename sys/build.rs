@@ -0,0 +1,1177 @@
+//! Build script for rust-gnark-sys.
+//!
+//! Three resolution tiers:
+//! 1. **Local prebuilt** (`prebuilt/<target>/` exists): Uses pre-placed library and header.
+//! 2. **Development** (`go/` directory exists): Compiles Go from source.
+//!    Requires Go toolchain (1.24+).
+//! 3. **Download** (published crate): Downloads prebuilt library from the GitHub Release
+//!    matching the crate version and checks it against the SHA-256 recorded in
+//!    `prebuilt.sha256` at release time. No Go toolchain required.
+//!
+//! `RUST_GNARK_PREBUILT_DIR` replaces tiers 2 and 3 for offline builds: it points to a
+//! directory holding either the release archive (`prebuilt-<target>.tar.gz`, verified
+//! like a download) or an extracted `<target>/` directory.
+//!
+//! Android targets use `-buildmode=c-shared` (`.so`) because Go does not support
+//! `c-archive` on `GOOS=android`. Windows MSVC targets also use `c-shared`
+//! (`libgnark.dll` plus the `gnark.lib` import library), since `link.exe` cannot link
+//! the MinGW-built Go archive. All other targets, including `windows-gnu`, use
+//! `c-archive` (`.a`).
+//!
+//! Source builds first check `go/go.sum` against the SHA-256 pinned in
+//! `go/go.sum.sha256`, so the gnark, gnark-crypto and other module versions
+//! compiled in only change together with that file.
+//!
+//! Cross-compilation can also be configured explicitly via the `RUST_GNARK_GO_ENVS`
+//! environment variable (format: `"GOOS=ios;GOARCH=arm64;CC=/path/to/cc"`).
+//!
+//! Tier 2 keeps Go's build cache and each built library under `target/go-build`
+//! (or `RUST_GNARK_GO_CACHE_DIR`), keyed by a hash of the `go/` tree, the Go
+//! version and the build settings, so switching profiles or features only
+//! reruns `go build` when the library would actually differ.
+//!
+//! The `vendored-go` feature makes tier 2 work without Go installed: the build
+//! downloads the Go toolchain pinned in `go-toolchain.sha256` for the host into
+//! that cache, checks it against the SHA-256 recorded there, and builds with it.
+//! `RUST_GNARK_GO_VERSION` and `RUST_GNARK_GO_SHA256` pin another release.
+//!
+//! The `build-from-source` feature always takes tier 2, compiling `go/` from a
+//! checkout (or `RUST_GNARK_GO_SRC`), or else the Go sources of the matching
+//! GitHub Release (`go-src.tar.gz`, checked against `prebuilt.sha256`), for
+//! targets without a prebuilt library. `force-prebuilt` is the opposite: it
+//! skips tier 2 even in a checkout.
+//!
+//! The `gpu` feature always builds from source (tier 2) with `-tags=icicle` and
+//! links the ICICLE libraries from `ICICLE_LIB_DIR` (default `/usr/local/lib`);
+//! prebuilt libraries are CPU-only.
+//!
+//! The `portable` feature always builds from source (tier 2) with `-tags=purego`,
+//! which compiles gnark-crypto's generic Go arithmetic instead of its assembly,
+//! for x86-64 CPUs without ADX and BMI2; prebuilt libraries use the assembly.
+//!
+//! The `verifier` feature builds with `-tags=verifier`, which leaves the prover,
+//! circuit compilation and setup out of the library, and takes its prebuilt
+//! libraries from `prebuilt/verifier-<target>/` and the release's
+//! `prebuilt-verifier-<target>.tar.gz` instead.
+//!
+//! The curve features (`bls12-381`, `bw6-761`, `ceremony`) map to negative Go build
+//! tags (`no_bls12_381`, ...) when disabled. Prebuilt libraries include everything,
+//! so disabling them only shrinks the library when building from source.
+//!
+//! With the `verify-only` feature (set by rust-gnark's `verify-only`) none of the
+//! above runs: nothing is built or linked, and the crate is empty.
+
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    for var in [
+        "RUST_GNARK_PREBUILT_DIR",
+        "RUST_GNARK_PREBUILT_URL",
+        "RUST_GNARK_PREBUILT_SHA256",
+        "RUST_GNARK_GO_SRC",
+        "RUST_GNARK_GO_VERSION",
+        "RUST_GNARK_GO_SHA256",
+        "IPHONEOS_DEPLOYMENT_TARGET",
+        "TVOS_DEPLOYMENT_TARGET",
+        "XROS_DEPLOYMENT_TARGET",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    if env::var_os("CARGO_FEATURE_VERIFY_ONLY").is_some() {
+        return;
+    }
+
+    let target = env::var("TARGET").expect("TARGET not set");
+    if target.starts_with("wasm32") {
+        panic!(
+            "rust-gnark cannot build the Go prover for {target} (c-archive does not target WASM). \
+             Enable the `wasm` (or `verify-only`) feature to use the pure-Rust verifier."
+        );
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
+    let manifest_dir =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+
+    let is_android = target.contains("linux-android");
+    let is_windows_msvc = target.ends_with("windows-msvc");
+    let (buildmode, lib_name) = if is_android {
+        ("c-shared", "libgnark.so")
+    } else if is_windows_msvc {
+        ("c-shared", "libgnark.dll")
+    } else {
+        ("c-archive", "libgnark.a")
+    };
+    // Files making up the library; MSVC links against the import library.
+    let lib_files: &[&str] = if is_windows_msvc {
+        &[lib_name, WINDOWS_IMPORT_LIB]
+    } else {
+        &[lib_name]
+    };
+
+    let gpu = env::var_os("CARGO_FEATURE_GPU").is_some();
+    let portable = env::var_os("CARGO_FEATURE_PORTABLE").is_some();
+    let verifier = env::var_os("CARGO_FEATURE_VERIFIER").is_some();
+    assert!(
+        !(verifier && gpu),
+        "The `verifier` feature cannot be combined with `gpu`: the verifier build has no prover."
+    );
+    // Prebuilt libraries are named after the target, the verifier build's
+    // with a `verifier-` prefix.
+    let prebuilt_name = if verifier {
+        format!("verifier-{target}")
+    } else {
+        target.clone()
+    };
+    let prebuilt_dir = manifest_dir.join("prebuilt").join(&prebuilt_name);
+    let from_source = gpu || portable || env::var_os("CARGO_FEATURE_BUILD_FROM_SOURCE").is_some();
+    let force_prebuilt = env::var_os("CARGO_FEATURE_FORCE_PREBUILT").is_some();
+    assert!(
+        !(from_source && force_prebuilt),
+        "The `force-prebuilt` feature cannot be combined with `build-from-source`, `gpu` or \
+         `portable`, which compile the Go library."
+    );
+    let go_dir = match env::var_os("RUST_GNARK_GO_SRC") {
+        Some(dir) => PathBuf::from(dir),
+        None => manifest_dir.join("../go"),
+    };
+    let go_dir = if from_source && !go_dir.exists() {
+        // The published crate has no Go sources; fetch those of its release.
+        download_go_source(&manifest_dir, &out_dir)
+    } else {
+        go_dir
+    };
+    let has_source = go_dir.exists() && !force_prebuilt;
+    if has_source {
+        println!("cargo:rerun-if-changed={}", go_dir.display());
+    }
+
+    let tags = go_build_tags(gpu, portable, verifier);
+    let uses_prebuilt = !from_source
+        && (prebuilt_dir.exists()
+            || env::var_os("RUST_GNARK_PREBUILT_DIR").is_some()
+            || !has_source);
+    if uses_prebuilt && tags.iter().any(|t| t.starts_with("no_")) {
+        println!(
+            "cargo:warning=rust-gnark: prebuilt libraries include every curve; \
+             disabled curve features only shrink source builds"
+        );
+    }
+
+    if prebuilt_dir.exists() && !from_source {
+        for file in lib_files.iter().chain(&["libgnark.h"]) {
+            let src = prebuilt_dir.join(file);
+            assert!(
+                src.exists(),
+                "prebuilt/{prebuilt_name}/{file} not found. Rebuild prebuilt libraries."
+            );
+            std::fs::copy(&src, out_dir.join(file))
+                .unwrap_or_else(|e| panic!("Failed to copy prebuilt {file}: {e}"));
+        }
+    } else if let (Some(dir), false) = (env::var_os("RUST_GNARK_PREBUILT_DIR"), from_source) {
+        local_prebuilt(
+            Path::new(&dir),
+            &prebuilt_name,
+            lib_files,
+            &manifest_dir,
+            &out_dir,
+        );
+    } else if has_source {
+        check_go_sum(&go_dir);
+        let dest = out_dir.join(lib_name);
+        let go_envs = detect_go_cross_env(&target, &out_dir);
+
+        if is_android {
+            let has_cc = go_envs.iter().any(|(k, _)| k == "CC");
+            if !has_cc {
+                panic!(
+                    "Building rust-gnark for Android from source requires the Android NDK. \
+                     Set ANDROID_NDK_HOME (or ANDROID_NDK_ROOT) to your NDK root, e.g.:\n  \
+                     export ANDROID_NDK_HOME=~/Library/Android/sdk/ndk/26.1.10909125\n  \
+                     (Get the NDK via Android Studio: SDK Manager → SDK Tools → NDK.)"
+                );
+            }
+        }
+
+        let ldflags = if is_windows_msvc {
+            // Have the MinGW linker emit an import library MSVC can link against.
+            let implib = out_dir.join(WINDOWS_IMPORT_LIB);
+            format!(
+                "-ldflags=-s -w '-extldflags=-Wl,--out-implib,{}'",
+                implib.to_str().expect("Invalid import library path")
+            )
+        } else {
+            "-ldflags=-s -w".to_string()
+        };
+        let cache_dir = go_cache_dir(&out_dir, &target);
+        let go = if env::var_os("CARGO_FEATURE_VENDORED_GO").is_some() {
+            vendored_go(&manifest_dir, &cache_dir)
+        } else {
+            PathBuf::from("go")
+        };
+        let mut cmd = Command::new(&go);
+        if go.is_absolute() {
+            // Keep go.mod's toolchain line from fetching yet another toolchain.
+            cmd.env("GOTOOLCHAIN", "local");
+        }
+        if env::var_os("GOCACHE").is_none() {
+            // Shared by every profile and feature set, unlike OUT_DIR.
+            cmd.env("GOCACHE", cache_dir.join("gocache"));
+        }
+        cmd.current_dir(&go_dir).env("CGO_ENABLED", "1").args([
+            "build",
+            "-mod=readonly",
+            &format!("-buildmode={buildmode}"),
+            &ldflags,
+            "-gcflags=all=-l -B",
+        ]);
+        if !tags.is_empty() {
+            cmd.arg(format!("-tags={}", tags.join(",")));
+        }
+        cmd.args(["-o", dest.to_str().expect("Invalid output path"), "."]);
+
+        for (k, v) in &go_envs {
+            cmd.env(k, v);
+        }
+
+        // The library only depends on the Go sources, the toolchain and these
+        // settings, so a build with the same key is reused from the cache.
+        let key = go_build_key(&go_dir, &go, &target, buildmode, &tags, &go_envs);
+        let cached = cache_dir.join("lib").join(&key);
+        let files: Vec<&str> = lib_files.iter().copied().chain(["libgnark.h"]).collect();
+        if files.iter().all(|f| cached.join(f).exists()) {
+            for file in &files {
+                std::fs::copy(cached.join(file), out_dir.join(file))
+                    .unwrap_or_else(|e| panic!("Failed to copy cached {file}: {e}"));
+            }
+        } else {
+            let status = cmd.status().expect(
+                "Go build failed. Is Go installed? \
+                 Development builds of rust-gnark require Go 1.24+ \
+                 (or the `vendored-go` feature to download it).",
+            );
+            assert!(status.success(), "Go build failed with status: {status}");
+            store_go_build(&cached, &out_dir, &files);
+        }
+    } else {
+        download_prebuilt(&prebuilt_name, lib_files, &manifest_dir, &out_dir);
+    }
+
+    let header_path = out_dir.join("libgnark.h");
+    let mut builder = bindgen::Builder::default()
+        .header(header_path.to_str().expect("Invalid header path"))
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()));
+
+    // For iOS-family targets, bindgen must use the SDK sysroot and a valid clang triple
+    // so that system headers (e.g. stdlib.h) are found and the triple is accepted.
+    if let Some(clang_args) = apple_bindgen_clang_args(&target) {
+        builder = builder.clang_args(clang_args);
+    }
+
+    let bindings = builder
+        .generate()
+        .expect("Failed to generate Rust bindings from libgnark.h");
+    bindings
+        .write_to_file(out_dir.join("bindings.rs"))
+        .expect("Failed to write bindings.rs");
+
+    // DEP_GNARK_INCLUDE for dependents' build scripts.
+    println!("cargo:include={}", out_dir.display());
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    if is_android || is_windows_msvc {
+        println!("cargo:rustc-link-lib=dylib=gnark");
+    } else {
+        println!("cargo:rustc-link-lib=static=gnark");
+    }
+    let libgnark_path = out_dir.join("libgnark.so");
+    if is_android {
+        check_page_alignment(&libgnark_path);
+    }
+
+    // cargo-ndk sets this env var pointing to the jniLibs/<abi>/ folder
+    if let Ok(ndk_output) = env::var("CARGO_NDK_OUTPUT_PATH") {
+        let abi = match target.as_str() {
+            "aarch64-linux-android" => "arm64-v8a",
+            "x86_64-linux-android" => "x86_64",
+            "armv7-linux-androideabi" => "armeabi-v7a",
+            "i686-linux-android" => "x86",
+            _ => panic!("Unsupported target: {}", target),
+        };
+
+        let dest_dir = PathBuf::from(&ndk_output).join(abi);
+        std::fs::create_dir_all(&dest_dir).expect("Failed to create destination directory");
+
+        let dest = dest_dir.join("libgnark.so");
+        std::fs::copy(&libgnark_path, &dest).expect("Failed to copy libgnark.so");
+
+        println!("cargo:warning=Copied libgnark.so to {}", dest.display());
+    }
+
+    if gpu {
+        link_icicle();
+    }
+    link_platform_deps(&target);
+}
+
+/// Go build tags for the enabled features: `icicle` for `gpu`, `purego` for
+/// `portable`, `verifier` for the verifier build, plus a `no_*` tag for each
+/// curve feature that is turned off.
+fn go_build_tags(gpu: bool, portable: bool, verifier: bool) -> Vec<&'static str> {
+    let mut tags = Vec::new();
+    if gpu {
+        tags.push("icicle");
+    }
+    if portable {
+        tags.push("purego");
+    }
+    if verifier {
+        tags.push("verifier");
+    }
+    for (feature, tag) in [
+        ("CARGO_FEATURE_BLS12_381", "no_bls12_381"),
+        ("CARGO_FEATURE_BW6_761", "no_bw6_761"),
+        ("CARGO_FEATURE_CEREMONY", "no_ceremony"),
+    ] {
+        if env::var_os(feature).is_none() {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Link the ICICLE runtime and BN254 libraries the `icicle` Go build depends on.
+///
+/// The CUDA backend itself is loaded at runtime from `ICICLE_BACKEND_INSTALL_DIR`.
+fn link_icicle() {
+    println!("cargo:rerun-if-env-changed=ICICLE_LIB_DIR");
+    let lib_dir = env::var("ICICLE_LIB_DIR").unwrap_or_else(|_| "/usr/local/lib".to_string());
+    println!("cargo:rustc-link-search=native={lib_dir}");
+    for lib in ["icicle_device", "icicle_field_bn254", "icicle_curve_bn254"] {
+        println!("cargo:rustc-link-lib=dylib={lib}");
+    }
+    println!("cargo:rustc-link-lib=stdc++");
+}
+
+const GITHUB_REPO: &str = "FluxePay/rust-gnark";
+
+/// Import library for `libgnark.dll` on Windows MSVC targets.
+const WINDOWS_IMPORT_LIB: &str = "gnark.lib";
+
+/// Download a prebuilt library from the GitHub Release matching the crate version.
+///
+/// Downloads `prebuilt-{name}.tar.gz` from the release, checks its SHA-256 and
+/// extracts the library files and header into `out_dir`. `name` is the target,
+/// or `verifier-{target}` for the verifier build.
+///
+/// The download URL can be overridden via `RUST_GNARK_PREBUILT_URL` env var
+/// (must point to the `.tar.gz` file directly), in which case the expected hash
+/// usually has to be given in `RUST_GNARK_PREBUILT_SHA256` as well.
+fn download_prebuilt(name: &str, lib_files: &[&str], manifest_dir: &Path, out_dir: &Path) {
+    let version = env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set");
+    let url = env::var("RUST_GNARK_PREBUILT_URL").unwrap_or_else(|_| {
+        format!(
+            "https://github.com/{GITHUB_REPO}/releases/download/v{version}/prebuilt-{name}.tar.gz"
+        )
+    });
+
+    println!("cargo:warning=Downloading prebuilt gnark library from {url}");
+
+    let tar_gz_path = out_dir.join(format!("prebuilt-{name}.tar.gz"));
+
+    let resp = ureq::get(&url).call().unwrap_or_else(|e| {
+        panic!(
+            "Failed to download prebuilt library from {url}: {e}\n\
+             Either install Go 1.24+ and place go/ directory adjacent to the crate,\n\
+             set RUST_GNARK_PREBUILT_DIR to a directory holding prebuilt-{name}.tar.gz,\n\
+             or ensure a GitHub Release exists for v{version}."
+        )
+    });
+
+    let mut file =
+        std::fs::File::create(&tar_gz_path).expect("Failed to create temp file for download");
+    let mut reader = resp.into_reader();
+    std::io::copy(&mut reader, &mut file).expect("Failed to write downloaded archive");
+    file.flush().expect("Failed to flush downloaded archive");
+
+    extract_prebuilt(&tar_gz_path, name, lib_files, manifest_dir, out_dir);
+}
+
+/// Download the Go sources of the GitHub Release matching the crate version
+/// (`go-src.tar.gz`, holding the `go/` directory), check them against the
+/// SHA-256 in `prebuilt.sha256` and unpack them into `out_dir`, returning the
+/// `go/` directory.
+fn download_go_source(manifest_dir: &Path, out_dir: &Path) -> PathBuf {
+    let version = env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set");
+    let url =
+        format!("https://github.com/{GITHUB_REPO}/releases/download/v{version}/go-src.tar.gz");
+    println!("cargo:warning=Downloading the gnark Go sources from {url}");
+
+    let resp = ureq::get(&url).call().unwrap_or_else(|e| {
+        panic!(
+            "Failed to download the Go sources from {url}: {e}\n\
+             Set RUST_GNARK_GO_SRC to a checkout's go/ directory to build from source offline."
+        )
+    });
+    let archive = out_dir.join("go-src.tar.gz");
+    let mut file = std::fs::File::create(&archive).expect("Failed to create the source archive");
+    std::io::copy(&mut resp.into_reader(), &mut file).expect("Failed to write the source archive");
+    file.flush().expect("Failed to flush the source archive");
+
+    let expected = expected_sha256("go-src.tar.gz", manifest_dir);
+    let actual = sha256_file(&archive);
+    assert!(
+        actual.eq_ignore_ascii_case(&expected),
+        "SHA-256 mismatch for go-src.tar.gz: expected {expected}, got {actual}. \
+         The archive is corrupted or does not belong to this crate version."
+    );
+    let dest = out_dir.join("go-src");
+    std::fs::create_dir_all(&dest).expect("Failed to create the source directory");
+    let status = Command::new("tar")
+        .args([
+            "xzf",
+            archive.to_str().unwrap(),
+            "-C",
+            dest.to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to run tar. Is tar installed?");
+    assert!(status.success(), "tar extraction failed");
+    dest.join("go")
+}
+
+/// Use a prebuilt library from `RUST_GNARK_PREBUILT_DIR` without touching the network.
+///
+/// Accepts the release archive `{dir}/prebuilt-{name}.tar.gz` (verified and
+/// extracted like a download) or an already extracted `{dir}/{name}/`
+/// directory, which is trusted as is.
+fn local_prebuilt(dir: &Path, name: &str, lib_files: &[&str], manifest_dir: &Path, out_dir: &Path) {
+    let archive = dir.join(format!("prebuilt-{name}.tar.gz"));
+    let extracted = dir.join(name);
+    println!("cargo:rerun-if-changed={}", archive.display());
+    println!("cargo:rerun-if-changed={}", extracted.display());
+
+    let files: Vec<&str> = lib_files.iter().copied().chain(["libgnark.h"]).collect();
+    if archive.exists() {
+        extract_prebuilt(&archive, name, lib_files, manifest_dir, out_dir);
+    } else if files.iter().all(|f| extracted.join(f).exists()) {
+        for file in &files {
+            std::fs::copy(extracted.join(file), out_dir.join(file))
+                .unwrap_or_else(|e| panic!("Failed to copy prebuilt {file}: {e}"));
+        }
+    } else {
+        panic!(
+            "RUST_GNARK_PREBUILT_DIR={} holds neither prebuilt-{name}.tar.gz \
+             nor {name}/ with {}.",
+            dir.display(),
+            files.join(", ")
+        );
+    }
+}
+
+/// Verify `archive` against the expected SHA-256 for `name` and extract it
+/// into `out_dir`.
+fn extract_prebuilt(
+    archive: &Path,
+    name: &str,
+    lib_files: &[&str],
+    manifest_dir: &Path,
+    out_dir: &Path,
+) {
+    let file_name = format!("prebuilt-{name}.tar.gz");
+    let expected = expected_sha256(&file_name, manifest_dir);
+    let actual = sha256_file(archive);
+    assert!(
+        actual.eq_ignore_ascii_case(&expected),
+        "SHA-256 mismatch for {file_name}: expected {expected}, got {actual}. \
+         The archive is corrupted or does not belong to this crate version."
+    );
+
+    let status = Command::new("tar")
+        .args([
+            "xzf",
+            archive.to_str().unwrap(),
+            "-C",
+            out_dir.to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to run tar. Is tar installed?");
+    assert!(status.success(), "tar extraction failed");
+
+    for file in lib_files.iter().chain(&["libgnark.h"]) {
+        assert!(
+            out_dir.join(file).exists(),
+            "Prebuilt archive missing {file}"
+        );
+    }
+}
+
+/// The expected SHA-256 of the release archive `file_name`.
+///
+/// For library archives `RUST_GNARK_PREBUILT_SHA256` takes precedence;
+/// otherwise the hash is looked up in `prebuilt.sha256` (`sha256sum` output),
+/// which the release workflow writes into the published crate.
+fn expected_sha256(file_name: &str, manifest_dir: &Path) -> String {
+    if let (true, Ok(hash)) = (
+        file_name.starts_with("prebuilt-"),
+        env::var("RUST_GNARK_PREBUILT_SHA256"),
+    ) {
+        return hash.trim().to_string();
+    }
+    let sums_path = manifest_dir.join("prebuilt.sha256");
+    let sums = std::fs::read_to_string(&sums_path).unwrap_or_default();
+    sums.lines()
+        .filter_map(|line| {
+            let (hash, name) = line.split_once(char::is_whitespace)?;
+            (name.trim().trim_start_matches('*') == file_name).then(|| hash.to_string())
+        })
+        .next()
+        .unwrap_or_else(|| {
+            panic!(
+                "No SHA-256 known for {file_name} (not listed in {}). \
+                 Set RUST_GNARK_PREBUILT_SHA256 to the archive's expected hash.",
+                sums_path.display()
+            )
+        })
+}
+
+/// SHA-256 of `path` as lowercase hex, via `sha256sum` or `shasum -a 256`.
+fn sha256_file(path: &Path) -> String {
+    let path = path.to_str().expect("Invalid archive path");
+    let output = Command::new("sha256sum")
+        .arg(path)
+        .output()
+        .or_else(|_| Command::new("shasum").args(["-a", "256", path]).output())
+        .expect("Failed to run sha256sum or shasum. Is either installed?");
+    assert!(output.status.success(), "Hashing {path} failed");
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .expect("Unexpected sha256sum output")
+        .to_string()
+}
+
+/// Where source builds keep Go's build cache, vendored toolchains and built
+/// libraries: `RUST_GNARK_GO_CACHE_DIR`, or `go-build` in Cargo's target
+/// directory (`OUT_DIR` is per profile and feature set, so it would start
+/// from scratch too often).
+fn go_cache_dir(out_dir: &Path, target: &str) -> PathBuf {
+    println!("cargo:rerun-if-env-changed=RUST_GNARK_GO_CACHE_DIR");
+    if let Some(dir) = env::var_os("RUST_GNARK_GO_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    // OUT_DIR is <target dir>[/<triple>]/<profile>/build/<package>-<hash>/out.
+    let target_dir = out_dir
+        .ancestors()
+        .find(|p| p.file_name().is_some_and(|n| n == "build"))
+        .and_then(Path::parent)
+        .and_then(Path::parent)
+        .map(|p| match p.file_name() {
+            Some(name) if name == target => p.parent().unwrap_or(p),
+            _ => p,
+        });
+    match target_dir {
+        Some(dir) => dir.join("go-build"),
+        None => out_dir.join("go-build"),
+    }
+}
+
+/// Cache key of a Go library build: a hash of every file under `go_dir`, the
+/// `go version` output and the build settings.
+fn go_build_key(
+    go_dir: &Path,
+    go: &Path,
+    target: &str,
+    buildmode: &str,
+    tags: &[&str],
+    go_envs: &[(String, String)],
+) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_tree(dir: &Path, root: &Path, hasher: &mut DefaultHasher) {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {e}", dir.display()))
+            .map(|e| e.expect("Failed to read directory entry").path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                hash_tree(&path, root, hasher);
+            } else {
+                path.strip_prefix(root).unwrap_or(&path).hash(hasher);
+                std::fs::read(&path)
+                    .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()))
+                    .hash(hasher);
+            }
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    hash_tree(go_dir, go_dir, &mut hasher);
+    let version = Command::new(go).arg("version").output().expect(
+        "Failed to run `go version`. Is Go installed? \
+         Development builds of rust-gnark require Go 1.24+ \
+         (or the `vendored-go` feature to download it).",
+    );
+    version.stdout.hash(&mut hasher);
+    (target, buildmode, tags, go_envs).hash(&mut hasher);
+    format!("{target}-{:016x}", hasher.finish())
+}
+
+/// Copy a fresh build's `files` from `out_dir` into the cache entry `cached`,
+/// renaming the finished entry into place so an interrupted build never
+/// leaves a partial one.
+fn store_go_build(cached: &Path, out_dir: &Path, files: &[&str]) {
+    let staging = cached.with_extension(format!("tmp{}", std::process::id()));
+    let stored = std::fs::create_dir_all(&staging).is_ok()
+        && files
+            .iter()
+            .all(|f| std::fs::copy(out_dir.join(f), staging.join(f)).is_ok())
+        && std::fs::rename(&staging, cached).is_ok();
+    if !stored {
+        // Another build may have stored the same entry first; caching is
+        // best effort either way.
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+}
+
+/// Fail the build unless `go.sum` hashes to the SHA-256 in `go.sum.sha256`
+/// (`sha256sum` output), which pins every Go module the library links.
+fn check_go_sum(go_dir: &Path) {
+    let lock_path = go_dir.join("go.sum.sha256");
+    let lock = std::fs::read_to_string(&lock_path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", lock_path.display()));
+    let expected = lock
+        .lines()
+        .filter_map(|line| {
+            let (hash, name) = line.split_once(char::is_whitespace)?;
+            (name.trim().trim_start_matches('*') == "go.sum").then_some(hash)
+        })
+        .next()
+        .unwrap_or_else(|| panic!("{} lists no hash for go.sum", lock_path.display()));
+    let actual = sha256_file(&go_dir.join("go.sum"));
+    assert!(
+        actual.eq_ignore_ascii_case(expected),
+        "go/go.sum does not match go/go.sum.sha256 (expected {expected}, got {actual}). \
+         Review the Go module changes, then pin them with `cd go && sha256sum go.sum > go.sum.sha256`."
+    );
+}
+
+/// Download and unpack the pinned Go toolchain for the host into `cache_dir`,
+/// returning the path of its `go` binary. An already unpacked toolchain is
+/// reused.
+fn vendored_go(manifest_dir: &Path, cache_dir: &Path) -> PathBuf {
+    let host = env::var("HOST").expect("HOST not set");
+    let platform = go_host_platform(&host)
+        .unwrap_or_else(|| panic!("The `vendored-go` feature has no Go toolchain for {host}."));
+    let extension = if platform.starts_with("windows") {
+        "zip"
+    } else {
+        "tar.gz"
+    };
+    let sums_path = manifest_dir.join("go-toolchain.sha256");
+    println!("cargo:rerun-if-changed={}", sums_path.display());
+    let (archive_name, expected) = pinned_go_toolchain(&sums_path, &platform, extension);
+
+    let root = cache_dir
+        .join("toolchain")
+        .join(archive_name.trim_end_matches(&format!(".{extension}")));
+    let go = root
+        .join("go")
+        .join("bin")
+        .join(if platform.starts_with("windows") {
+            "go.exe"
+        } else {
+            "go"
+        });
+    if go.exists() {
+        return go;
+    }
+
+    let url = format!("https://go.dev/dl/{archive_name}");
+    println!("cargo:warning=Downloading the Go toolchain from {url}");
+    std::fs::create_dir_all(&root).expect("Failed to create the Go toolchain directory");
+    let archive = root.join(&archive_name);
+    let resp = ureq::get(&url).call().unwrap_or_else(|e| {
+        panic!(
+            "Failed to download the Go toolchain from {url}: {e}\n\
+             Install Go 1.24+ and build without the `vendored-go` feature instead."
+        )
+    });
+    let mut file = std::fs::File::create(&archive).expect("Failed to create the Go archive");
+    std::io::copy(&mut resp.into_reader(), &mut file).expect("Failed to write the Go archive");
+    file.flush().expect("Failed to flush the Go archive");
+
+    let actual = sha256_file(&archive);
+    assert!(
+        actual.eq_ignore_ascii_case(&expected),
+        "SHA-256 mismatch for {archive_name}: expected {expected}, got {actual}."
+    );
+    // bsdtar, which Windows ships as tar.exe, also unpacks zip archives.
+    let status = Command::new("tar")
+        .args([
+            "xf",
+            archive.to_str().unwrap(),
+            "-C",
+            root.to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to run tar. Is tar installed?");
+    assert!(status.success(), "Go toolchain extraction failed");
+    let _ = std::fs::remove_file(&archive);
+    assert!(go.exists(), "The Go archive has no {}", go.display());
+    go
+}
+
+/// The Go release name (`linux-amd64`, ...) of the Rust `host` triple.
+fn go_host_platform(host: &str) -> Option<String> {
+    let arch = match host.split('-').next()? {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "i686" => "386",
+        _ => return None,
+    };
+    let os = if host.contains("linux") {
+        "linux"
+    } else if host.contains("apple-darwin") {
+        "darwin"
+    } else if host.contains("windows") {
+        "windows"
+    } else if host.contains("freebsd") {
+        "freebsd"
+    } else {
+        return None;
+    };
+    Some(format!("{os}-{arch}"))
+}
+
+/// The archive name and SHA-256 of the pinned Go toolchain for `platform`.
+///
+/// `RUST_GNARK_GO_VERSION` and `RUST_GNARK_GO_SHA256` take precedence;
+/// otherwise the entry for `platform` is looked up in `go-toolchain.sha256`
+/// (`sha256sum` output for one Go release, written by `cargo xtask pin-go`).
+fn pinned_go_toolchain(sums_path: &Path, platform: &str, extension: &str) -> (String, String) {
+    if let (Ok(version), Ok(hash)) = (
+        env::var("RUST_GNARK_GO_VERSION"),
+        env::var("RUST_GNARK_GO_SHA256"),
+    ) {
+        let version = version.trim().trim_start_matches("go");
+        return (
+            format!("go{version}.{platform}.{extension}"),
+            hash.trim().to_string(),
+        );
+    }
+    let suffix = format!(".{platform}.{extension}");
+    let sums = std::fs::read_to_string(sums_path).unwrap_or_default();
+    sums.lines()
+        .filter_map(|line| {
+            let (hash, name) = line.split_once(char::is_whitespace)?;
+            let name = name.trim().trim_start_matches('*');
+            (name.starts_with("go") && name.ends_with(&suffix))
+                .then(|| (name.to_string(), hash.to_string()))
+        })
+        .next()
+        .unwrap_or_else(|| {
+            panic!(
+                "No pinned Go toolchain for {platform} (not listed in {}). \
+                 Run `cargo xtask pin-go <version>`, or set RUST_GNARK_GO_VERSION and \
+                 RUST_GNARK_GO_SHA256.",
+                sums_path.display()
+            )
+        })
+}
+
+/// Auto-detect Go cross-compilation environment from the Rust `TARGET` triple.
+///
+/// Priority:
+/// 1. `RUST_GNARK_GO_ENVS` env var (explicit override)
+/// 2. Auto-detection from TARGET -> GOOS/GOARCH/CC mapping
+///
+/// For iOS, Mac Catalyst, tvOS and visionOS targets, creates a temporary clang
+/// wrapper script in `OUT_DIR` that invokes `xcrun` with the appropriate SDK and
+/// target triple.
+///
+/// For Android targets, locates the NDK clang from `ANDROID_NDK_HOME`.
+fn detect_go_cross_env(target: &str, out_dir: &Path) -> Vec<(String, String)> {
+    let manual = parse_go_envs();
+    if !manual.is_empty() {
+        return manual;
+    }
+
+    let (goos, goarch) = match target {
+        // Mac Catalyst runs on the macOS kernel and runtime, so Go builds it as
+        // darwin (as gomobile does); only the C side targets the macabi SDK.
+        t if t.ends_with("apple-ios-macabi") => {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("darwin", arch)
+        }
+        // Go has no tvOS/visionOS ports; their runtime is close enough to iOS.
+        t if t.contains("apple-ios")
+            || t.contains("apple-tvos")
+            || t.contains("apple-visionos") =>
+        {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("ios", arch)
+        }
+        t if t.contains("apple-darwin") => {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("darwin", arch)
+        }
+        t if t.contains("linux-android") => {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("android", arch)
+        }
+        t if t.contains("windows") => {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("windows", arch)
+        }
+        t if t.contains("linux-gnu") || t.contains("linux-musl") => {
+            let arch = if t.starts_with("aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            ("linux", arch)
+        }
+        // Unknown target: let Go use host defaults (native build)
+        _ => return Vec::new(),
+    };
+
+    let mut envs = vec![
+        ("GOOS".into(), goos.into()),
+        ("GOARCH".into(), goarch.into()),
+    ];
+
+    if let Some(cc) = detect_cc(target, out_dir) {
+        envs.push(("CC".into(), cc));
+    }
+
+    // Rust links musl executables as static-pie. The Go half of a c-archive is
+    // always position-independent; make sure the cgo C code is too.
+    if target.contains("linux-musl") {
+        envs.push(("CGO_CFLAGS".into(), "-O2 -fPIC".into()));
+    }
+
+    // Google Play requires 16 KB aligned LOAD segments in native libraries.
+    if target.contains("linux-android") {
+        envs.push((
+            "CGO_LDFLAGS".into(),
+            format!("-Wl,-z,max-page-size={ANDROID_PAGE_SIZE}"),
+        ));
+    }
+
+    envs
+}
+
+/// Detect the C compiler for cross-compilation targets.
+///
+/// Returns `None` for targets where the default system compiler works
+/// (e.g., native builds, macOS arm64<->x86_64 cross-compilation via
+/// universal clang).
+fn detect_cc(target: &str, out_dir: &Path) -> Option<String> {
+    // iOS, Mac Catalyst, tvOS and visionOS devices and simulators: xcrun with
+    // the matching SDK
+    if let Some((sdk, clang_target)) = apple_sdk_target(target) {
+        return Some(create_apple_cc_wrapper(out_dir, sdk, &clang_target));
+    }
+
+    match target {
+        // Android: use NDK clang
+        t if t.contains("linux-android") => detect_android_cc(t),
+        // Linux ARM64 cross-compilation from x86_64 host
+        "aarch64-unknown-linux-gnu" => {
+            let host = env::var("HOST").unwrap_or_default();
+            if host.contains("x86_64") {
+                Some("aarch64-linux-gnu-gcc".into())
+            } else {
+                None // native build on ARM64
+            }
+        }
+        // musl: the system compiler on a musl host (Alpine), musl-gcc (musl-tools)
+        // on a glibc host of the same arch, a musl.cc-style cross gcc otherwise
+        "x86_64-unknown-linux-musl" | "aarch64-unknown-linux-musl" => {
+            let arch = target.split('-').next().unwrap_or_default();
+            let host = env::var("HOST").unwrap_or_default();
+            if host == target {
+                None
+            } else if host.starts_with(arch) {
+                Some("musl-gcc".into())
+            } else {
+                Some(format!("{arch}-linux-musl-gcc"))
+            }
+        }
+        // Windows: cgo always needs MinGW gcc, even for MSVC targets. Native
+        // builds use the one on PATH (e.g. MSYS2), cross builds mingw-w64.
+        t if t.contains("windows") => {
+            let host = env::var("HOST").unwrap_or_default();
+            if host.contains("windows") {
+                None
+            } else if t.starts_with("aarch64") {
+                Some("aarch64-w64-mingw32-gcc".into())
+            } else {
+                Some("x86_64-w64-mingw32-gcc".into())
+            }
+        }
+        // macOS and native Linux: system compiler handles it
+        _ => None,
+    }
+}
+
+/// Apple SDK name and clang target triple for the Apple targets that need an
+/// explicit SDK (everything but macOS).
+///
+/// The minimum OS version in the triple comes from the deployment-target
+/// variable rustc reads for the same target (`IPHONEOS_DEPLOYMENT_TARGET`,
+/// `TVOS_DEPLOYMENT_TARGET`, `XROS_DEPLOYMENT_TARGET`), with rustc-compatible
+/// defaults.
+fn apple_sdk_target(target: &str) -> Option<(&'static str, String)> {
+    let (sdk, arch, os, suffix) = match target {
+        "aarch64-apple-ios" => ("iphoneos", "arm64", "ios", ""),
+        "aarch64-apple-ios-sim" => ("iphonesimulator", "arm64", "ios", "-simulator"),
+        "x86_64-apple-ios" => ("iphonesimulator", "x86_64", "ios", "-simulator"),
+        "aarch64-apple-ios-macabi" => ("macosx", "arm64", "ios", "-macabi"),
+        "x86_64-apple-ios-macabi" => ("macosx", "x86_64", "ios", "-macabi"),
+        "aarch64-apple-tvos" => ("appletvos", "arm64", "tvos", ""),
+        "aarch64-apple-tvos-sim" => ("appletvsimulator", "arm64", "tvos", "-simulator"),
+        "x86_64-apple-tvos" => ("appletvsimulator", "x86_64", "tvos", "-simulator"),
+        "aarch64-apple-visionos" => ("xros", "arm64", "xros", ""),
+        "aarch64-apple-visionos-sim" => ("xrsimulator", "arm64", "xros", "-simulator"),
+        _ => return None,
+    };
+    let (version_var, default_version) = match (os, suffix) {
+        // Catalyst needs iOS 13.1+; rustc defaults to 14.0.
+        ("ios", "-macabi") => ("IPHONEOS_DEPLOYMENT_TARGET", "14.0"),
+        ("ios", _) => ("IPHONEOS_DEPLOYMENT_TARGET", "13.0"),
+        ("tvos", _) => ("TVOS_DEPLOYMENT_TARGET", "13.0"),
+        _ => ("XROS_DEPLOYMENT_TARGET", "1.0"),
+    };
+    let version = env::var(version_var).unwrap_or_else(|_| default_version.to_string());
+    Some((sdk, format!("{arch}-apple-{os}{version}{suffix}")))
+}
+
+/// Return clang args for bindgen when targeting iOS, Mac Catalyst, tvOS or
+/// visionOS, so that system headers (e.g. stdlib.h) are found and the target
+/// triple is valid for clang. Without this, bindgen may see an invalid triple
+/// (e.g. 'sim' in arm64-apple-ios-sim) and fail to find the SDK sysroot.
+fn apple_bindgen_clang_args(target: &str) -> Option<Vec<String>> {
+    let (sdk, clang_target) = apple_sdk_target(target)?;
+    let out = Command::new("xcrun")
+        .args(["-sdk", sdk, "--show-sdk-path"])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let sdk_path = String::from_utf8(out.stdout).ok()?.trim().to_string();
+    if sdk_path.is_empty() {
+        return None;
+    }
+    Some(vec![
+        "-isysroot".into(),
+        sdk_path,
+        "-target".into(),
+        clang_target,
+    ])
+}
+
+/// Create a shell wrapper script for Apple cross-compilation via `xcrun`.
+///
+/// The wrapper invokes `xcrun -sdk <sdk> clang -target <triple>` which
+/// automatically resolves the SDK sysroot and applies the correct flags.
+///
+/// # Arguments
+///
+/// * `out_dir` - Directory to write the wrapper script
+/// * `sdk` - Apple SDK name (e.g., `"iphoneos"`, `"iphonesimulator"`)
+/// * `clang_target` - Clang target triple (e.g., `"arm64-apple-ios13.0"`)
+///
+/// # Returns
+///
+/// Absolute path to the generated wrapper script.
+fn create_apple_cc_wrapper(out_dir: &Path, sdk: &str, clang_target: &str) -> String {
+    // Use a unique name per SDK to avoid collisions when building
+    // multiple iOS targets in the same workspace.
+    let script_name = format!("cc_wrapper_{sdk}.sh");
+    let script_path = out_dir.join(&script_name);
+    let script_content =
+        format!("#!/bin/sh\nexec xcrun -sdk {sdk} clang -target {clang_target} \"$@\"\n");
+
+    std::fs::write(&script_path, script_content)
+        .unwrap_or_else(|e| panic!("Failed to write CC wrapper {script_name}: {e}"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))
+            .unwrap_or_else(|e| panic!("Failed to chmod CC wrapper {script_name}: {e}"));
+    }
+
+    script_path
+        .to_str()
+        .expect("Invalid wrapper script path")
+        .into()
+}
+
+/// Detect Android NDK clang for cross-compilation.
+///
+/// Searches for the NDK via `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT`, or under
+/// `ANDROID_HOME`/`ANDROID_SDK_ROOT` (ndk-bundle or ndk/<version>).
+/// Uses API level 21 (Android 5.0) as the minimum supported version.
+fn detect_android_cc(target: &str) -> Option<String> {
+    let ndk = env::var("ANDROID_NDK_HOME")
+        .or_else(|_| env::var("ANDROID_NDK_ROOT"))
+        .ok()
+        .or_else(find_ndk_under_sdk)?;
+
+    // NDK prebuilt host tag: macOS can be darwin-x86_64 or darwin-arm64.
+    let host_tags: Vec<&str> = if cfg!(target_os = "macos") {
+        vec!["darwin-arm64", "darwin-x86_64"]
+    } else {
+        vec!["linux-x86_64"]
+    };
+
+    let clang_name = match target {
+        "aarch64-linux-android" => "aarch64-linux-android21-clang",
+        "x86_64-linux-android" => "x86_64-linux-android21-clang",
+        _ => return None,
+    };
+
+    for host_tag in &host_tags {
+        let cc = format!("{ndk}/toolchains/llvm/prebuilt/{host_tag}/bin/{clang_name}");
+        if Path::new(&cc).exists() {
+            return Some(cc);
+        }
+    }
+
+    println!(
+        "cargo:warning=Android NDK clang not found under {ndk} (tried host tags: {:?}). \
+         Set ANDROID_NDK_HOME to the NDK root.",
+        host_tags
+    );
+    None
+}
+
+/// Page size Android `.so` segments are aligned to (Android 15+ 16 KB pages).
+const ANDROID_PAGE_SIZE: u64 = 16384;
+
+/// Assert that every `PT_LOAD` segment of the ELF64 library at `path` is aligned
+/// to at least [`ANDROID_PAGE_SIZE`], as Google Play requires.
+fn check_page_alignment(path: &Path) {
+    const PT_LOAD: u32 = 1;
+    let elf =
+        std::fs::read(path).unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+    let u16_at = |off: usize| u16::from_le_bytes(elf[off..off + 2].try_into().unwrap());
+    let u32_at = |off: usize| u32::from_le_bytes(elf[off..off + 4].try_into().unwrap());
+    let u64_at = |off: usize| u64::from_le_bytes(elf[off..off + 8].try_into().unwrap());
+    assert!(
+        elf.len() >= 64 && elf.starts_with(b"\x7fELF") && elf[4] == 2 && elf[5] == 1,
+        "{} is not a little-endian ELF64 library",
+        path.display()
+    );
+
+    let phoff = u64_at(0x20) as usize;
+    let phentsize = usize::from(u16_at(0x36));
+    for i in 0..usize::from(u16_at(0x38)) {
+        let ph = phoff + i * phentsize;
+        let align = u64_at(ph + 0x30);
+        assert!(
+            u32_at(ph) != PT_LOAD || align >= ANDROID_PAGE_SIZE,
+            "{} has a LOAD segment aligned to {align} bytes; Google Play requires \
+             {ANDROID_PAGE_SIZE}. Rebuild it with -Wl,-z,max-page-size={ANDROID_PAGE_SIZE}.",
+            path.display()
+        );
+    }
+}
+
+/// Try to find NDK under ANDROID_HOME or ANDROID_SDK_ROOT (ndk-bundle or ndk/<ver>).
+fn find_ndk_under_sdk() -> Option<String> {
+    let sdk = env::var("ANDROID_HOME")
+        .or_else(|_| env::var("ANDROID_SDK_ROOT"))
+        .ok()?;
+    let sdk_path = Path::new(&sdk);
+    let ndk_bundle = sdk_path.join("ndk-bundle");
+    if ndk_bundle.is_dir() {
+        return ndk_bundle.into_os_string().into_string().ok();
+    }
+    let ndk_dir = sdk_path.join("ndk");
+    if ndk_dir.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(&ndk_dir) {
+            let mut versions: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect();
+            versions.sort_by(|a, b| b.cmp(a)); // newest first
+            if let Some(first) = versions.into_iter().next() {
+                return first.into_os_string().into_string().ok();
+            }
+        }
+    }
+    None
+}
+
+/// Parse cross-compilation environment variables from `RUST_GNARK_GO_ENVS`.
+///
+/// Format: `"GOOS=ios;GOARCH=arm64;CC=/path/to/cc"`
+/// Following SP1's `SP1_GNARK_FFI_GO_ENVS` pattern.
+fn parse_go_envs() -> Vec<(String, String)> {
+    let envs_str = env::var("RUST_GNARK_GO_ENVS").unwrap_or_default();
+    if envs_str.is_empty() {
+        return Vec::new();
+    }
+
+    envs_str
+        .split(';')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Add platform-specific link directives for the Go runtime.
+fn link_platform_deps(target: &str) {
+    if target.contains("apple") {
+        println!("cargo:rustc-link-lib=framework=CoreFoundation");
+        println!("cargo:rustc-link-lib=framework=Security");
+        println!("cargo:rustc-link-lib=resolv");
+    } else if target.contains("android") {
+        println!("cargo:rustc-link-lib=c");
+        println!("cargo:rustc-link-lib=log");
+    } else if target.contains("windows") {
+        // Winsock, CNG random numbers and user profile APIs used by the Go runtime.
+        println!("cargo:rustc-link-lib=ws2_32");
+        println!("cargo:rustc-link-lib=bcrypt");
+        println!("cargo:rustc-link-lib=userenv");
+    } else if !target.contains("musl") {
+        // Linux and other Unix-like targets. musl has pthread and the resolver
+        // in libc itself.
+        println!("cargo:rustc-link-lib=pthread");
+        println!("cargo:rustc-link-lib=resolv");
+    }
+}
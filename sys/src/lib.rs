@@ -0,0 +1,20 @@
+//! Raw bindings to libgnark, the gnark Go library compiled via CGO, generated
+//! by `bindgen` from the header `go build -buildmode=c-archive` writes.
+//!
+//! This crate only builds (or downloads) and links the library; see the build
+//! script for how it is found. [rust-gnark](https://crates.io/crates/rust-gnark)
+//! is the safe API on top. Calling these functions directly means following
+//! the Go side's contracts yourself: error strings are freed with
+//! `gnark_free_string`, handles with `gnark_handle_free`, buffers with
+//! `gnark_free_buffer`, and `gnark_init` runs before anything else.
+//!
+//! The header is in the build script's `OUT_DIR`, exported to dependent build
+//! scripts as `DEP_GNARK_INCLUDE`.
+//!
+//! With the `verify-only` feature the crate is empty.
+
+// bindgen output doesn't follow Rust conventions.
+#![allow(warnings, clippy::all)]
+
+#[cfg(not(feature = "verify-only"))]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
@@ -0,0 +1,201 @@
+//! On-chain verification of proofs, with the `evm-tests` feature.
+//!
+//! Each test compiles the contract from [`VerifyingKey::export_solidity`] with
+//! `solc` (from `$SOLC`, or `solc` on the `PATH`), deploys it to an in-memory
+//! revm, and calls `verifyProof` with [`Groth16ProofResult::solidity_calldata`]
+//! and the public inputs, so a change in gnark's contract template or proof
+//! encoding that breaks on-chain verification fails here.
+
+use anyhow::{bail, ensure, Context, Result};
+use revm::db::{CacheDB, EmptyDB};
+use revm::primitives::{Address, Bytes, ExecutionResult, Output, TxKind};
+use revm::Evm;
+use rust_gnark::{
+    groth16_prove, groth16_prove_with_config, init, CircuitBuilder, Groth16ProofResult,
+    HashFunction, ProverConfig, VerifyingKey,
+};
+use std::process::Command;
+
+const R1CS_PATH: &str = "./test-vectors/cubic_circuit.r1cs";
+const PK_PATH: &str = "./test-vectors/cubic_circuit.pk";
+const VK_PATH: &str = "./test-vectors/cubic_circuit.vk";
+
+/// A deployed verifier contract.
+struct Verifier {
+    evm: Evm<'static, (), CacheDB<EmptyDB>>,
+    address: Address,
+    /// The 4-byte selector of `verifyProof`.
+    selector: [u8; 4],
+}
+
+impl Verifier {
+    /// Compile the verifier for `vk` and deploy it.
+    fn deploy(vk: &VerifyingKey) -> Result<Self> {
+        let (bytecode, selector) = compile(&vk.export_solidity()?)?;
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Create;
+                tx.data = bytecode.into();
+            })
+            .build();
+        let address = match evm.transact_commit()? {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => address,
+            other => bail!("deploying the verifier failed: {other:?}"),
+        };
+        Ok(Self {
+            evm,
+            address,
+            selector,
+        })
+    }
+
+    /// Call `verifyProof`, returning whether it accepted the proof. The
+    /// contract reverts on invalid proofs.
+    fn verify(&mut self, proof: &Groth16ProofResult) -> Result<bool> {
+        let mut data = self.selector.to_vec();
+        // Every argument is a fixed-size uint256 array, so the ABI encoding is
+        // the proof words followed by the public inputs.
+        data.extend(proof.solidity_calldata()?);
+        data.extend(public_inputs(proof)?);
+        ensure!(
+            proof.solidity_verify_call()? == data,
+            "solidity_verify_call differs from the calldata solc's selector gives"
+        );
+        let tx = self.evm.tx_mut();
+        tx.transact_to = TxKind::Call(self.address);
+        tx.data = Bytes::from(data);
+        match self.evm.transact()?.result {
+            ExecutionResult::Success { .. } => Ok(true),
+            ExecutionResult::Revert { .. } => Ok(false),
+            halt @ ExecutionResult::Halt { .. } => bail!("verifyProof halted: {halt:?}"),
+        }
+    }
+}
+
+/// Compile `source` with solc, returning the `Verifier` contract's creation
+/// bytecode and the selector of its `verifyProof`.
+fn compile(source: &str) -> Result<(Vec<u8>, [u8; 4])> {
+    let dir = std::env::temp_dir().join(format!("rust-gnark-evm-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Verifier.sol");
+    std::fs::write(&path, source)?;
+    let solc = std::env::var("SOLC").unwrap_or_else(|_| "solc".into());
+    let output = Command::new(&solc)
+        .args(["--optimize", "--combined-json", "bin,hashes"])
+        .arg(&path)
+        .output()
+        .with_context(|| format!("failed to run {solc}; install solc or set $SOLC"))?;
+    ensure!(
+        output.status.success(),
+        "solc failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let contract = json["contracts"]
+        .as_object()
+        .and_then(|contracts| {
+            contracts
+                .iter()
+                .find(|(name, _)| name.ends_with(":Verifier"))
+        })
+        .map(|(_, contract)| contract)
+        .context("solc output has no Verifier contract")?;
+    let bytecode = decode_hex(contract["bin"].as_str().context("no bytecode")?)?;
+    let selector = contract["hashes"]
+        .as_object()
+        .and_then(|hashes| {
+            hashes
+                .iter()
+                .find(|(signature, _)| signature.starts_with("verifyProof("))
+        })
+        .and_then(|(_, selector)| selector.as_str())
+        .context("the verifier has no verifyProof")?;
+    let selector = decode_hex(selector)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("selectors are 4 bytes"))?;
+    std::fs::remove_dir_all(&dir)?;
+    Ok((bytecode, selector))
+}
+
+/// The public inputs as uint256 words: gnark's public witness encoding is a
+/// 12-byte header (public, secret and vector lengths) and then exactly that.
+fn public_inputs(proof: &Groth16ProofResult) -> Result<Vec<u8>> {
+    let bytes = decode_hex(&proof.public_inputs)?;
+    ensure!(
+        bytes.len() >= 12 && (bytes.len() - 12) % 32 == 0,
+        "malformed public witness"
+    );
+    Ok(bytes[12..].to_vec())
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    ensure!(s.len() % 2 == 0, "odd-length hex");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&s[i..i + 2], 16)?))
+        .collect()
+}
+
+/// Test: the cubic circuit's proofs verify on-chain, and a proof against
+/// another statement's public inputs is rejected.
+#[test]
+fn test_evm_verify_cubic() -> Result<()> {
+    init()?;
+    let mut verifier = Verifier::deploy(&VerifyingKey::open(VK_PATH)?)?;
+
+    let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+    assert!(verifier.verify(&proof)?);
+
+    let other = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "4", "Y": "73"}"#)?;
+    let swapped = Groth16ProofResult {
+        proof: proof.proof,
+        public_inputs: other.public_inputs,
+    };
+    assert!(!verifier.verify(&swapped)?);
+    Ok(())
+}
+
+/// Test: proofs of a circuit with a Pedersen commitment, hashed with SHA-256
+/// as the contract expects, verify on-chain.
+#[test]
+fn test_evm_verify_commitment() -> Result<()> {
+    init()?;
+
+    // X^2 == Y, plus a constraint using a commitment to X and Y.
+    let mut c = CircuitBuilder::new();
+    let y = c.public_input("Y");
+    let x = c.secret_input("X");
+    let x2 = c.mul(x, x);
+    c.assert_eq(x2, y);
+    let challenge = c.commit(&[x, y]);
+    let cx = c.mul(challenge, x);
+    let cx2 = c.mul(cx, x);
+    let cy = c.mul(challenge, y);
+    c.assert_eq(cx2, cy);
+
+    let dir = std::env::temp_dir().join(format!("rust-gnark-evm-commit-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let circuit = c.compile()?;
+    circuit.write(&path("c.r1cs"))?;
+    circuit.groth16_setup(&path("c.pk"), &path("c.vk"))?;
+
+    let mut verifier = Verifier::deploy(&VerifyingKey::open(&path("c.vk"))?)?;
+    let config = ProverConfig::default().with_hash_function(HashFunction::Sha256);
+    let proof = groth16_prove_with_config(
+        &path("c.r1cs"),
+        &path("c.pk"),
+        r#"{"X": "3", "Y": "9"}"#,
+        &config,
+    )?;
+    assert!(verifier.verify(&proof)?);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
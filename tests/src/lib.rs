@@ -2,15 +2,41 @@
 //!
 //! Uses pre-generated test vectors from a simple cubic circuit (x^3 + x + 5 == y, with x=3, y=35).
 //! Test vectors are generated by `go/cmd/gen_test_vectors/main.go`.
+//!
+//! With the `evm-tests` feature, `evm` also checks that proofs verify with the
+//! exported Solidity verifier on revm; it needs `solc`.
+
+#[cfg(all(test, feature = "evm-tests"))]
+mod evm;
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use rust_gnark::{groth16_prove, groth16_verify, init};
+    use rust_gnark::{
+        groth16_prove, groth16_prove_with_config, groth16_prove_with_key,
+        groth16_prove_with_progress, groth16_verify, groth16_verify_with_hash, init, prove_async,
+        CircuitAbi, CircuitBuilder, CompiledCircuit, GnarkError, HashFunction, Hint,
+        KeyFingerprint, Progress, ProvePhase, ProvePool, ProverConfig, ProverSession, ProvingKey,
+        SerializationFormat, VerifyingKey, Witness,
+    };
+    use std::sync::Mutex;
+
+    // Absolute, so the suite also runs outside the crate directory, e.g. in
+    // the iOS simulator (`cargo xtask test-ios`); relative on Android, where
+    // `cargo xtask test-android` pushes the vectors next to the test binary.
+    macro_rules! test_vector {
+        ($name:literal) => {
+            if cfg!(target_os = "android") {
+                concat!("./test-vectors/", $name)
+            } else {
+                concat!(env!("CARGO_MANIFEST_DIR"), "/test-vectors/", $name)
+            }
+        };
+    }
 
-    const R1CS_PATH: &str = "./test-vectors/cubic_circuit.r1cs";
-    const PK_PATH: &str = "./test-vectors/cubic_circuit.pk";
-    const VK_PATH: &str = "./test-vectors/cubic_circuit.vk";
+    const R1CS_PATH: &str = test_vector!("cubic_circuit.r1cs");
+    const PK_PATH: &str = test_vector!("cubic_circuit.pk");
+    const VK_PATH: &str = test_vector!("cubic_circuit.vk");
 
     /// Test: Groth16 BN254 prove + verify roundtrip.
     ///
@@ -36,6 +62,26 @@ mod tests {
         Ok(())
     }
 
+    /// Test: every example circuit of rust-gnark-examples proves and verifies
+    /// from its fixtures.
+    #[test]
+    fn test_examples_prove_verify() -> Result<()> {
+        rust_gnark_examples::prepare_fixtures()?;
+        for example in rust_gnark_examples::Example::ALL {
+            let proof = groth16_prove(
+                &example.r1cs_path(),
+                &example.pk_path(),
+                &example.witness_json()?,
+            )?;
+            assert!(
+                groth16_verify(&example.r1cs_path(), &example.vk_path(), &proof)?,
+                "{} should verify",
+                example.name()
+            );
+        }
+        Ok(())
+    }
+
     /// Test: Verification rejects a tampered proof.
     #[test]
     fn test_groth16_verify_rejects_tampered_proof() -> Result<()> {
@@ -59,4 +105,4377 @@ mod tests {
 
         Ok(())
     }
+
+    /// Test: a prepared verifying key accepts the proofs `groth16_verify` does
+    /// and rejects a proof for other public inputs.
+    #[test]
+    fn test_prepared_verifying_key() -> Result<()> {
+        init()?;
+
+        let vk = VerifyingKey::open(VK_PATH)?.prepare()?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(vk.verify(&proof)?);
+        assert!(vk.verify_with_hash(&proof, HashFunction::Default)?);
+
+        let other = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "15"}"#)?;
+        let mixed = rust_gnark::Groth16ProofResult {
+            proof: proof.proof.clone(),
+            public_inputs: other.public_inputs,
+        };
+        assert!(!vk.verify(&mixed)?);
+        assert!(!groth16_verify(R1CS_PATH, VK_PATH, &mixed)?);
+
+        Ok(())
+    }
+
+    /// Test: verification with a QoS runs on the verifier pool and gives the
+    /// verdicts of the calling thread.
+    #[test]
+    fn test_verify_with_qos() -> Result<()> {
+        use rust_gnark::{groth16_verify_with_config, Qos, VerifierConfig};
+        init()?;
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let other = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "15"}"#)?;
+        let mixed = rust_gnark::Groth16ProofResult {
+            proof: proof.proof.clone(),
+            public_inputs: other.public_inputs,
+        };
+        let prepared = VerifyingKey::open(VK_PATH)?.prepare()?;
+        for qos in [Qos::Background, Qos::UserInitiated] {
+            let config = VerifierConfig::default().with_qos(qos);
+            assert!(groth16_verify_with_config(
+                R1CS_PATH, VK_PATH, &proof, &config
+            )?);
+            assert!(!groth16_verify_with_config(
+                R1CS_PATH, VK_PATH, &mixed, &config
+            )?);
+            assert!(prepared.verify_with_config(&proof, &config)?);
+            assert!(!prepared.verify_with_config(&mixed, &config)?);
+        }
+        let config = VerifierConfig::default().with_hash_function(HashFunction::Default);
+        assert!(groth16_verify_with_config(
+            R1CS_PATH, VK_PATH, &proof, &config
+        )?);
+
+        Ok(())
+    }
+
+    /// Test: Proofs produced by the Go prover verify with the pure-Rust verifier.
+    #[test]
+    fn test_native_verify_go_proof() -> Result<()> {
+        init()?;
+
+        let witness_json = r#"{"X": "3", "Y": "35"}"#;
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, witness_json)?;
+
+        let valid = rust_gnark::native::groth16_verify(VK_PATH, &proof_result)?;
+        assert!(valid, "Go proof should verify natively");
+
+        // A proof for a different public input must not verify against y=35.
+        let other = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "15"}"#)?;
+        let mut mixed = proof_result.clone();
+        mixed.public_inputs = other.public_inputs;
+        let valid = rust_gnark::native::groth16_verify(VK_PATH, &mixed)?;
+        assert!(!valid, "Proof with swapped public inputs should not verify");
+
+        Ok(())
+    }
+
+    /// Test: the Solana and NEAR encoders lay out the same points as the EVM
+    /// calldata, big- and little-endian respectively.
+    #[test]
+    fn test_solana_near_encoding() -> Result<()> {
+        init()?;
+        use rust_gnark::{near, solana};
+
+        let result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let calldata = result.solidity_calldata()?;
+        let (proof, inputs) = solana::from_proof_result(&result)?;
+        // B and C as in the calldata; A negated, so only x matches.
+        assert_eq!(&proof.to_bytes()[64..], &calldata[64..256]);
+        assert_eq!(proof.a[..32], calldata[..32]);
+        assert_ne!(proof.a[32..], calldata[32..64]);
+        let mut y35 = [0u8; 32];
+        y35[31] = 35;
+        assert_eq!(inputs, vec![y35]);
+
+        let reversed = |be: &[u8]| be.iter().rev().copied().collect::<Vec<u8>>();
+        let (near_proof, near_inputs) = near::from_proof_result(&result)?;
+        assert_eq!(near_proof.a[..32], reversed(&proof.a[..32])[..]);
+        assert_eq!(near_proof.c[32..], reversed(&proof.c[32..])[..]);
+        // x.c0 leads in NEAR's G2, x.c1 in Solana's.
+        assert_eq!(near_proof.b[..32], reversed(&proof.b[32..64])[..]);
+        let y35_le: [u8; 32] = reversed(&y35).try_into().expect("32 bytes");
+        assert_eq!(near_inputs, vec![y35_le]);
+
+        let vk = rust_gnark::native::VerifyingKey::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let solana_vk = solana::SolanaVerifyingKey::try_from(&vk)?;
+        let near_vk = near::NearVerifyingKey::try_from(&vk)?;
+        assert_eq!(solana_vk.ic.len(), 2);
+        assert_eq!(near_vk.ic.len(), 2);
+        assert_eq!(
+            near_vk.alpha_g1[..32],
+            reversed(&solana_vk.alpha_g1[..32])[..]
+        );
+
+        Ok(())
+    }
+
+    /// Test: The in-memory (WASM) verification path agrees with the Go verifier.
+    #[test]
+    fn test_native_verify_vk_bytes_matches_go() -> Result<()> {
+        init()?;
+
+        let vk_bytes = std::fs::read(VK_PATH)?;
+        let vk = rust_gnark::native::VerifyingKey::from_gnark_bytes(&vk_bytes)?;
+        // One public input (Y) plus the constant wire.
+        assert_eq!(vk.k.len(), 2);
+
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let go = groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?;
+        let native = rust_gnark::native::groth16_verify_with_vk_bytes(&vk_bytes, &proof_result)?;
+        assert_eq!(go, native, "Go and native verifiers should agree");
+        assert!(native);
+
+        Ok(())
+    }
+
+    /// Test: Background proving produces a verifiable proof.
+    #[test]
+    fn test_prove_async_roundtrip() -> Result<()> {
+        init()?;
+
+        let task = prove_async(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#);
+        let proof_result = task.wait()?;
+
+        let valid = groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?;
+        assert!(valid, "Proof from prove_async should be valid");
+
+        Ok(())
+    }
+
+    /// Test: A cancelled background proof resolves to an error.
+    #[test]
+    fn test_prove_async_cancel() -> Result<()> {
+        init()?;
+
+        let task = prove_async(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#);
+        task.cancel();
+        // The cubic circuit may finish before the cancellation is observed;
+        // either outcome is fine, but a late cancel must not corrupt the result.
+        match task.wait() {
+            Ok(proof_result) => assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?),
+            Err(e) => assert_eq!(e.downcast_ref::<GnarkError>(), Some(&GnarkError::Cancelled)),
+        }
+
+        Ok(())
+    }
+
+    /// Test: Cancelling a proof stops gnark's solver: once the task resolves,
+    /// no hint runs any more.
+    #[test]
+    fn test_prove_async_cancel_stops_solver() -> Result<()> {
+        use rust_gnark::{groth16_prove_cancellable, CancelToken, Fr};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        init()?;
+
+        // A chain of halvings, each waiting on the one before it.
+        const LEVELS: usize = 40;
+        let mut c = CircuitBuilder::new();
+        let mut v = c.public_input("X");
+        for _ in 0..LEVELS {
+            let half = c.hint(
+                Hint::Rust {
+                    id: 56905,
+                    outputs: 1,
+                },
+                &[v],
+            )[0];
+            let doubled = c.add(half, half);
+            c.assert_eq(doubled, v);
+            v = half;
+        }
+        let circuit = c.compile()?;
+        let dir = std::env::temp_dir().join(format!("rust-gnark-halvings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("halvings.r1cs"))?;
+        circuit.unsafe_setup_with_seed(5, &path("halvings.pk"), &path("halvings.vk"))?;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        rust_gnark::register_hint(56905, move |inputs: &[Fr], outputs: &mut [Fr]| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(25));
+            outputs[0] = inputs[0] * Fr::from(2).inverse().expect("2 is invertible");
+            Ok(())
+        });
+
+        let token = Arc::new(CancelToken::new());
+        let canceller = Arc::clone(&token);
+        let cancel = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            canceller.cancel();
+        });
+        let err = groth16_prove_cancellable(
+            &path("halvings.r1cs"),
+            &path("halvings.pk"),
+            r#"{"X": "6"}"#,
+            &token,
+        )
+        .expect_err("the proof was cancelled");
+        cancel.join().unwrap();
+        assert_eq!(
+            err.downcast_ref::<GnarkError>(),
+            Some(&GnarkError::Cancelled)
+        );
+
+        let stopped_at = calls.load(Ordering::SeqCst);
+        assert!(stopped_at < LEVELS, "the solver ran to the end");
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            stopped_at,
+            "a hint ran after the call returned"
+        );
+
+        assert!(rust_gnark::unregister_hint(56905));
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: A panicking background job resolves to an error and leaves the
+    /// prover threads running.
+    #[test]
+    fn test_prove_task_panic() -> Result<()> {
+        use rust_gnark::ProveTask;
+
+        init()?;
+
+        for _ in 0..3 {
+            let task = ProveTask::spawn(|_| panic!("the job fails"));
+            assert!(task.wait().is_err());
+        }
+        let task = prove_async(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#);
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &task.wait()?)?);
+        Ok(())
+    }
+
+    /// Test: Progress events cover every phase, in order, ending complete.
+    #[test]
+    fn test_prove_progress_events() -> Result<()> {
+        init()?;
+
+        let events = Mutex::new(Vec::new());
+        let reporter = |p: Progress| events.lock().unwrap().push(p);
+        let proof_result =
+            groth16_prove_with_progress(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#, &reporter)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?);
+
+        let events = events.into_inner().unwrap();
+        let mut phases: Vec<ProvePhase> = events.iter().map(|p| p.phase).collect();
+        phases.dedup();
+        assert_eq!(phases, ProvePhase::ALL);
+
+        let last = events.last().expect("at least one progress event");
+        assert_eq!(last.phase, ProvePhase::Serialize);
+        assert_eq!(last.done, last.total);
+
+        Ok(())
+    }
+
+    /// Test: A memory-mapped proving key can be reused across proofs.
+    #[test]
+    fn test_prove_with_mmap_key() -> Result<()> {
+        init()?;
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        for (x, y) in [("3", "35"), ("2", "15")] {
+            let witness_json = format!(r#"{{"X": "{x}", "Y": "{y}"}}"#);
+            let proof_result = groth16_prove_with_key(R1CS_PATH, &pk, &witness_json)?;
+            assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?);
+        }
+
+        assert!(ProvingKey::open_mmap("./test-vectors/missing.pk").is_err());
+
+        Ok(())
+    }
+
+    /// Test: Proving keys round-trip through Go-owned buffers.
+    #[test]
+    fn test_proving_key_bytes_roundtrip() -> Result<()> {
+        init()?;
+
+        let pk = ProvingKey::from_bytes(&std::fs::read(PK_PATH)?)?;
+        let bytes = pk.to_bytes()?;
+        assert!(!bytes.is_empty());
+
+        let reloaded = ProvingKey::from_bytes(&bytes)?;
+        let proof_result =
+            groth16_prove_with_key(R1CS_PATH, &reloaded, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?);
+
+        Ok(())
+    }
+
+    /// Test: The default prover config proves on CPU and yields a valid proof.
+    #[test]
+    fn test_prove_with_default_config() -> Result<()> {
+        init()?;
+
+        let config = ProverConfig::default();
+        assert_eq!(config.gpu_device(), None);
+
+        let proof_result =
+            groth16_prove_with_config(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#, &config)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof_result)?);
+
+        Ok(())
+    }
+
+    /// Test: Thread/memory limits and a non-default hash still yield a valid proof.
+    #[test]
+    fn test_prove_with_runtime_limits_and_hash() -> Result<()> {
+        init()?;
+
+        let config = ProverConfig::default()
+            .with_threads(1)
+            .with_memory_limit(256 << 20)
+            .with_hash_function(HashFunction::Sha256);
+        assert_eq!(config.threads(), Some(1));
+        assert_eq!(config.memory_limit(), Some(256 << 20));
+
+        let proof_result =
+            groth16_prove_with_config(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#, &config)?;
+        assert!(groth16_verify_with_hash(
+            R1CS_PATH,
+            VK_PATH,
+            &proof_result,
+            HashFunction::Sha256
+        )?);
+
+        Ok(())
+    }
+
+    /// Test: Batch verification flags exactly the tampered proofs.
+    #[test]
+    fn test_native_verify_batch() -> Result<()> {
+        init()?;
+
+        let verifier = rust_gnark::native::Verifier::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let mut results = (0..4)
+            .map(|_| groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#))
+            .collect::<Result<Vec<_>>>()?;
+        assert!(verifier.verify_batch_results(&results)?.is_empty());
+
+        // Swap in another proof's C: still decodes, but fails the pairing check.
+        let c_hex = 2 * (32 + 64);
+        let c = results[3].proof[c_hex..c_hex + 64].to_string();
+        results[1].proof.replace_range(c_hex..c_hex + 64, &c);
+        results[2].proof = "zz".into();
+
+        assert_eq!(verifier.verify_batch_results(&results)?, vec![1, 2]);
+
+        Ok(())
+    }
+
+    /// Test: Verification with limits rejects oversized input before decoding it.
+    #[test]
+    fn test_native_verify_with_limits() -> Result<()> {
+        use rust_gnark::native::Limits;
+        use std::time::Duration;
+
+        init()?;
+
+        let verifier = rust_gnark::native::Verifier::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let unhex = |s: &str| {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+        };
+        let (proof, public) = (unhex(&result.proof)?, unhex(&result.public_inputs)?);
+        let limits = Limits {
+            max_public_inputs: 1,
+            timeout: Some(Duration::from_secs(10)),
+        };
+        assert!(verifier.verify_with_limits(&proof, &public, limits)?);
+
+        // A witness claiming 2^32 - 1 inputs, and one with too many real ones.
+        let mut huge = public.clone();
+        huge[..12].fill(0xff);
+        assert!(verifier.verify_with_limits(&proof, &huge, limits).is_err());
+        let none = Limits {
+            max_public_inputs: 0,
+            ..limits
+        };
+        assert!(verifier.verify_with_limits(&proof, &public, none).is_err());
+
+        let mut long = proof.clone();
+        long.resize(proof.len() + 4096, 0);
+        assert!(verifier.verify_with_limits(&long, &public, limits).is_err());
+        let instant = Limits {
+            timeout: Some(Duration::ZERO),
+            ..limits
+        };
+        assert!(verifier
+            .verify_with_limits(&proof, &public, instant)
+            .is_err());
+        Ok(())
+    }
+
+    /// Test: Strict verification accepts only the canonical proof encoding.
+    #[test]
+    fn test_native_verify_strict() -> Result<()> {
+        use rust_gnark::native::{Proof, Verifier};
+
+        init()?;
+
+        let verifier = Verifier::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let unhex = |s: &str| {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+        };
+        let public = unhex(&result.public_inputs)?;
+        let proof = Proof::from_gnark_bytes(&unhex(&result.proof)?)?.to_gnark_bytes();
+        assert!(verifier.verify_strict(&proof, &public)?);
+
+        // Each decodes to the same proof, but is not the canonical encoding:
+        // a trailing byte, padding after the infinity flag of the commitment
+        // proof of knowledge, and no commitment section at all.
+        let mut trailing = proof.clone();
+        trailing.push(0);
+        let mut padded = proof.clone();
+        *padded.last_mut().unwrap() = 1;
+        let short = proof[..128].to_vec();
+        for encoding in [trailing, padded, short] {
+            assert!(Proof::from_gnark_bytes(&encoding).is_ok());
+            assert!(verifier.verify_strict(&encoding, &public).is_err());
+        }
+
+        let mut long_witness = public.clone();
+        long_witness.push(0);
+        assert!(verifier.verify_strict(&proof, &long_witness).is_err());
+        Ok(())
+    }
+
+    /// Test: Two proofs aggregate into one outer proof that verifies.
+    #[test]
+    #[ignore = "slow: compiles and sets up an emulated BN254 verifier over BW6-761"]
+    fn test_aggregate_proofs() -> Result<()> {
+        init()?;
+
+        let aggregator = rust_gnark::Aggregator::new(R1CS_PATH, VK_PATH, 2)?;
+        let proofs = vec![
+            groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?,
+            groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "15"}"#)?,
+        ];
+
+        let aggregated = aggregator.aggregate(&proofs)?;
+        assert!(aggregator.verify(&aggregated)?);
+        assert!(aggregator.aggregate(&proofs[..1]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: two BLS12-377 proofs aggregate over BW6-761 and wrap into a BN254
+    /// proof that verifies, and a tampered witness is rejected.
+    #[test]
+    #[ignore = "slow: sets up an emulated BW6-761 verifier over BN254"]
+    fn test_recursion_pipeline() -> Result<()> {
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        let x3 = c.mul(x2, x);
+        let five = c.constant(5);
+        let lhs = c.add(x3, x);
+        let lhs = c.add(lhs, five);
+        c.assert_eq(lhs, y);
+
+        let pipeline = rust_gnark::RecursionPipeline::new_with_evm_wrap(&c, 2)?;
+        let proof = pipeline.prove(&[r#"{"X": "3", "Y": "35"}"#, r#"{"X": "2", "Y": "15"}"#])?;
+        assert_eq!(proof.inner.len(), 2);
+        assert!(pipeline.verify(&proof.aggregated)?);
+        let wrapped = proof.wrapped.expect("the pipeline wraps");
+        assert!(pipeline.verify_wrapped(&wrapped)?);
+        assert!(pipeline
+            .wrap_verifying_key()?
+            .export_solidity()?
+            .contains("verifyProof"));
+
+        assert!(pipeline.prove_inner(r#"{"X": "3", "Y": "36"}"#).is_err());
+        let mut swapped = proof.inner.clone();
+        swapped.swap(0, 1);
+        swapped[0].public_inputs = proof.inner[0].public_inputs.clone();
+        assert!(pipeline.aggregate(&swapped).is_err());
+
+        let plain = rust_gnark::RecursionPipeline::new(&c, 1)?;
+        assert!(plain.wrap(&proof.aggregated).is_err());
+
+        Ok(())
+    }
+
+    /// Test: Go-side failures surface as typed `GnarkError`s.
+    #[test]
+    fn test_typed_errors() -> Result<()> {
+        init()?;
+
+        // 2^3 + 2 + 5 = 15, not 35.
+        let err = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "35"}"#).unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<GnarkError>(),
+                Some(GnarkError::WitnessSolveFailed { .. })
+            ),
+            "unexpected error: {err:#}"
+        );
+
+        let err = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3"}"#).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidWitness(_))
+        ));
+
+        let err = groth16_prove(
+            R1CS_PATH,
+            "./test-vectors/missing.pk",
+            r#"{"X": "3", "Y": "35"}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::Io(_))
+        ));
+
+        // A Go panic becomes an error instead of aborting, and the library
+        // keeps working.
+        match GnarkError::debug_panic("boom") {
+            GnarkError::InternalPanic { message, stack } => {
+                assert!(message.contains("boom"));
+                assert!(stack.contains("gnark_debug_panic"));
+            }
+            err => panic!("unexpected error: {err:?}"),
+        }
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        Ok(())
+    }
+
+    /// Test: an installed `Metrics` sees every proof and verification, with
+    /// the constraint count, memory and failure category, and nothing once
+    /// cleared.
+    #[test]
+    fn test_metrics_hooks() -> Result<()> {
+        use rust_gnark::metrics::{self, FailureCategory, Metrics, ProveEvent, VerifyEvent};
+        use std::sync::Arc;
+        use std::thread::ThreadId;
+        init()?;
+
+        // Other tests prove concurrently; keep the events of this thread.
+        #[derive(Default)]
+        struct Recorder {
+            proofs: Mutex<Vec<(ThreadId, ProveEvent)>>,
+            verifications: Mutex<Vec<(ThreadId, VerifyEvent)>>,
+        }
+        struct Shared(Arc<Recorder>);
+        impl Metrics for Shared {
+            fn on_prove(&self, event: &ProveEvent) {
+                let id = std::thread::current().id();
+                self.0.proofs.lock().unwrap().push((id, event.clone()));
+            }
+            fn on_verify(&self, event: &VerifyEvent) {
+                let id = std::thread::current().id();
+                self.0
+                    .verifications
+                    .lock()
+                    .unwrap()
+                    .push((id, event.clone()));
+            }
+        }
+        fn ours<T: Clone>(events: &Mutex<Vec<(ThreadId, T)>>) -> Vec<T> {
+            let me = std::thread::current().id();
+            let events = events.lock().unwrap();
+            events
+                .iter()
+                .filter(|(id, _)| *id == me)
+                .map(|(_, event)| event.clone())
+                .collect()
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        metrics::set_metrics(Shared(recorder.clone()));
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let bad = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "2", "Y": "35"}"#);
+        let valid = groth16_verify(R1CS_PATH, VK_PATH, &proof)?;
+        let verifier = rust_gnark::native::Verifier::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let mut tampered = proof.clone();
+        tampered.proof = tampered.proof.replacen(|c| c != '0', "0", 1);
+        let failed = verifier.verify_batch_results(&[proof.clone(), tampered])?;
+        metrics::clear_metrics();
+        groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(bad.is_err() && valid);
+        assert_eq!(failed, [1]);
+
+        let proofs = ours(&recorder.proofs);
+        assert_eq!(proofs.len(), 2, "{proofs:#?}");
+        assert_eq!(proofs[0].failure, None);
+        assert_eq!(proofs[0].proofs, 1);
+        assert!(!proofs[0].remote);
+        assert!(proofs[0].nb_constraints.is_some_and(|n| n > 0));
+        assert!(proofs[0].peak_memory.is_some_and(|m| m > 0));
+        assert_eq!(proofs[1].failure, Some(FailureCategory::UnsatisfiedWitness));
+
+        let verifications = ours(&recorder.verifications);
+        assert_eq!(verifications.len(), 2, "{verifications:#?}");
+        assert!(verifications[0].valid);
+        assert_eq!(verifications[0].proofs, 1);
+        assert_eq!(verifications[0].failure, None);
+        // One event for the batch, invalid as one of its proofs is.
+        assert_eq!(verifications[1].proofs, 2);
+        assert!(!verifications[1].valid);
+        assert_eq!(verifications[1].failure, None);
+
+        Ok(())
+    }
+
+    /// Test: `PublicInputsBuilder` encodes typed values as the prover's public
+    /// witness does, with big-endian integers and reduced 32-byte values.
+    #[test]
+    fn test_public_inputs_builder() -> Result<()> {
+        use rust_gnark::{Fr, PublicInputsBuilder};
+        init()?;
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let mut inputs = PublicInputsBuilder::new();
+        inputs.push_u64(35);
+        assert_eq!(inputs.to_hex(), proof.public_inputs);
+
+        let mut address = [0u8; 20];
+        address[18] = 1;
+        let mut hash = [0u8; 32];
+        hash[0] = 1;
+        hash[31] = 2;
+        let mut inputs = PublicInputsBuilder::new();
+        inputs
+            .push_address(&address)
+            .push_bytes32_be(&[0xff; 32])
+            .push_bytes32_hi_lo(&hash);
+        assert_eq!(
+            inputs.inputs(),
+            [
+                Fr::from(256u64),
+                // 2^256 - 1 mod r.
+                "6350874878119819312338956282401532410528162663560392320966563075034087161850"
+                    .parse()?,
+                // 2^120, the high half.
+                Fr::from(1u64 << 60) * Fr::from(1u64 << 60),
+                Fr::from(2u64),
+            ]
+        );
+
+        Ok(())
+    }
+
+    /// Test: the C ABI proves, verifies and reports errors with status codes.
+    #[test]
+    fn test_capi() -> Result<()> {
+        use rust_gnark::capi::*;
+        use std::ffi::{CStr, CString};
+        use std::ptr::null_mut;
+
+        init()?;
+
+        let c = |s: &str| CString::new(s).unwrap();
+        let (r1cs, pk, vk) = (c(R1CS_PATH), c(PK_PATH), c(VK_PATH));
+        let witness = c(r#"{"X": "3", "Y": "35"}"#);
+        unsafe {
+            assert_eq!(rust_gnark_abi_version(), RUST_GNARK_ABI_VERSION);
+            assert_eq!(rust_gnark_init(null_mut()), RustGnarkStatus::Ok);
+
+            let mut prover = null_mut();
+            let status = rust_gnark_prover_new(r1cs.as_ptr(), pk.as_ptr(), &mut prover, null_mut());
+            assert_eq!(status, RustGnarkStatus::Ok);
+            let (mut proof, mut public, mut error) = (null_mut(), null_mut(), null_mut());
+            let status = rust_gnark_prover_prove(
+                prover,
+                witness.as_ptr(),
+                &mut proof,
+                &mut public,
+                null_mut(),
+            );
+            assert_eq!(status, RustGnarkStatus::Ok);
+
+            let mut valid = false;
+            let status = rust_gnark_groth16_verify(
+                r1cs.as_ptr(),
+                vk.as_ptr(),
+                proof,
+                public,
+                &mut valid,
+                null_mut(),
+            );
+            assert_eq!(status, RustGnarkStatus::Ok);
+            assert!(valid);
+            rust_gnark_string_free(proof);
+            rust_gnark_string_free(public);
+
+            // 2^3 + 2 + 5 = 15, not 35.
+            let bad = c(r#"{"X": "2", "Y": "35"}"#);
+            let status =
+                rust_gnark_prover_prove(prover, bad.as_ptr(), &mut proof, &mut public, &mut error);
+            assert_eq!(status, RustGnarkStatus::WitnessSolveFailed);
+            assert!(!CStr::from_ptr(error).to_str()?.is_empty());
+            rust_gnark_string_free(error);
+
+            // The async proof outlives the prover and reports to the callback,
+            // from one of the prover threads.
+            type Done = (RustGnarkStatus, bool, bool, bool, bool);
+            extern "C" fn done(
+                user_data: *mut std::ffi::c_void,
+                status: RustGnarkStatus,
+                proof: *mut std::os::raw::c_char,
+                public: *mut std::os::raw::c_char,
+                error: *mut std::os::raw::c_char,
+            ) {
+                let tx = unsafe { Box::from_raw(user_data as *mut std::sync::mpsc::Sender<Done>) };
+                let pooled = std::thread::current()
+                    .name()
+                    .is_some_and(|name| name.starts_with("rust-gnark-prove-"));
+                tx.send((
+                    status,
+                    proof.is_null(),
+                    public.is_null(),
+                    error.is_null(),
+                    pooled,
+                ))
+                .unwrap();
+                unsafe {
+                    rust_gnark_string_free(proof);
+                    rust_gnark_string_free(public);
+                    rust_gnark_string_free(error);
+                }
+            }
+            let (tx, rx) = std::sync::mpsc::channel::<Done>();
+            let status = rust_gnark_prover_prove_async(
+                prover,
+                witness.as_ptr(),
+                done,
+                Box::into_raw(Box::new(tx)) as *mut _,
+                null_mut(),
+            );
+            assert_eq!(status, RustGnarkStatus::Ok);
+            rust_gnark_prover_free(prover);
+            assert_eq!(rx.recv()?, (RustGnarkStatus::Ok, false, false, true, true));
+
+            let status = rust_gnark_groth16_prove(
+                std::ptr::null(),
+                pk.as_ptr(),
+                witness.as_ptr(),
+                &mut proof,
+                &mut public,
+                null_mut(),
+            );
+            assert_eq!(status, RustGnarkStatus::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Test: The native encoders reproduce gnark's bytes exactly.
+    #[test]
+    fn test_native_encoding_matches_gnark() -> Result<()> {
+        init()?;
+
+        let vk_bytes = std::fs::read(VK_PATH)?;
+        let vk = rust_gnark::native::VerifyingKey::from_gnark_bytes(&vk_bytes)?;
+        assert_eq!(vk.to_gnark_bytes(), vk_bytes);
+
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let proof: rust_gnark::native::Proof =
+            serde_json::from_value(serde_json::Value::String(proof_result.proof.clone()))?;
+        assert_eq!(
+            serde_json::to_value(&proof)?,
+            serde_json::Value::String(proof_result.proof.clone())
+        );
+
+        let public_inputs: rust_gnark::native::PublicInputs = serde_json::from_value(
+            serde_json::Value::String(proof_result.public_inputs.clone()),
+        )?;
+        assert_eq!(public_inputs.0.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&public_inputs)?,
+            serde_json::Value::String(proof_result.public_inputs.clone())
+        );
+
+        Ok(())
+    }
+
+    /// Test: Proof results and snarkjs proofs survive a JSON round trip.
+    #[test]
+    fn test_serde_json_roundtrip() -> Result<()> {
+        init()?;
+
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let json = serde_json::to_string(&proof_result)?;
+        let decoded: rust_gnark::Groth16ProofResult = serde_json::from_str(&json)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &decoded)?);
+
+        let proof: rust_gnark::native::Proof =
+            serde_json::from_value(serde_json::Value::String(proof_result.proof))?;
+        let snarkjs = rust_gnark::snarkjs::SnarkjsProof::from(&proof);
+        let json = serde_json::to_value(&snarkjs)?;
+        assert_eq!(json["protocol"], "groth16");
+        assert_eq!(json["pi_a"][2], "1");
+        let back: rust_gnark::snarkjs::SnarkjsProof = serde_json::from_value(json)?;
+        let proof_back = rust_gnark::native::Proof::try_from(&back)?;
+        assert_eq!(proof_back.to_gnark_bytes(), proof.to_gnark_bytes());
+
+        Ok(())
+    }
+
+    /// Test: snarkjs proof.json / public.json export parses back and verifies.
+    #[test]
+    fn test_snarkjs_json_roundtrip() -> Result<()> {
+        use rust_gnark::native::{verify_proof, Proof, PublicInputs, VerifyingKey};
+
+        init()?;
+
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let (proof, public_inputs) = rust_gnark::snarkjs::from_proof_result(&proof_result)?;
+
+        let public_json = public_inputs.to_snarkjs_json();
+        let values: Vec<String> = serde_json::from_str(&public_json)?;
+        assert_eq!(values, vec!["35".to_string()]);
+
+        let proof_json = proof.to_snarkjs_json();
+        let parsed: serde_json::Value = serde_json::from_str(&proof_json)?;
+        assert_eq!(parsed["curve"], "bn128");
+        assert_eq!(parsed["pi_b"][2], serde_json::json!(["1", "0"]));
+
+        let vk = VerifyingKey::from_gnark_bytes(&std::fs::read(VK_PATH)?)?;
+        let proof = Proof::from_snarkjs_json(&proof_json)?;
+        let public_inputs = PublicInputs::from_snarkjs_json(&public_json)?;
+        assert!(verify_proof(&vk, &proof, &public_inputs.0)?);
+
+        assert!(PublicInputs::from_snarkjs_json(r#"["not a number"]"#).is_err());
+
+        Ok(())
+    }
+
+    /// Test: gnark proofs verify with ark-groth16 and convert back losslessly.
+    #[test]
+    fn test_ark_groth16_interop() -> Result<()> {
+        use ark_bn254::Bn254;
+        use rust_gnark::native::{Proof, PublicInputs, VerifyingKey};
+
+        init()?;
+
+        let vk_bytes = std::fs::read(VK_PATH)?;
+        let proof_result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let (proof, public_inputs) = rust_gnark::snarkjs::from_proof_result(&proof_result)?;
+        let proof_bytes = proof.to_gnark_bytes();
+
+        let ark_vk: ark_groth16::VerifyingKey<Bn254> =
+            VerifyingKey::from_gnark_bytes(&vk_bytes)?.into();
+        let ark_proof: ark_groth16::Proof<Bn254> = proof.into();
+        let inputs: Vec<ark_bn254::Fr> = public_inputs.into();
+        let pvk = ark_groth16::prepare_verifying_key(&ark_vk);
+        assert!(ark_groth16::Groth16::<Bn254>::verify_proof(
+            &pvk, &ark_proof, &inputs
+        )?);
+
+        assert_eq!(Proof::from(ark_proof).to_gnark_bytes(), proof_bytes);
+        let vk_back = VerifyingKey::from(ark_vk);
+        assert!(rust_gnark::native::verify_proof(
+            &vk_back,
+            &Proof::from_gnark_bytes(&proof_bytes)?,
+            &PublicInputs::from(inputs).0
+        )?);
+
+        let y = rust_gnark::native::fr_to_gnark_bytes(&ark_bn254::Fr::from(35u64));
+        assert_eq!(
+            rust_gnark::native::fr_from_gnark_bytes(&y)?,
+            ark_bn254::Fr::from(35u64)
+        );
+
+        Ok(())
+    }
+
+    /// Test: a circuit built in Rust compiles, sets up, proves and verifies.
+    #[test]
+    fn test_circuit_builder_roundtrip() -> Result<()> {
+        init()?;
+
+        // x^3 + x + 5 == y, with 1/x computed by a hint and constrained.
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        let x3 = c.mul(x2, x);
+        let five = c.constant(5);
+        let lhs = c.add(x3, x);
+        let lhs = c.add(lhs, five);
+        c.assert_eq(lhs, y);
+        let inv = c.hint(Hint::Inverse, &[x])[0];
+        let one = c.constant(1);
+        let prod = c.mul(x, inv);
+        c.assert_eq(prod, one);
+
+        let circuit = c.compile()?;
+        assert!(circuit.nb_constraints()? > 0);
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-dsl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("dsl.r1cs"))?;
+        circuit.groth16_setup(&path("dsl.pk"), &path("dsl.vk"))?;
+
+        let proof = groth16_prove(
+            &path("dsl.r1cs"),
+            &path("dsl.pk"),
+            r#"{"X": "3", "Y": "35"}"#,
+        )?;
+        assert!(groth16_verify(&path("dsl.r1cs"), &path("dsl.vk"), &proof)?);
+        assert!(groth16_prove(
+            &path("dsl.r1cs"),
+            &path("dsl.pk"),
+            r#"{"X": "3", "Y": "36"}"#
+        )
+        .is_err());
+
+        let mut dup = CircuitBuilder::new();
+        dup.public_input("A");
+        dup.secret_input("A");
+        assert!(dup.compile().is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a proof bound to an application context verifies in that
+    /// context only.
+    #[test]
+    fn test_app_context() -> Result<()> {
+        use rust_gnark::{app_context, groth16_verify_in_context, proof_app_context, APP_CONTEXT};
+
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        c.bind_app_context(true);
+        let abi = c.abi();
+        assert_eq!(abi.public(), ["Y", APP_CONTEXT]);
+        let circuit = c.compile()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-context-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("ctx.r1cs"))?;
+        circuit.groth16_setup(&path("ctx.pk"), &path("ctx.vk"))?;
+
+        let (domain, nonce) = ("com.example.test", b"nonce-1");
+        let context = app_context(domain, nonce)?;
+        assert_eq!(context, app_context(domain, nonce)?);
+        assert_ne!(context, app_context("com.example.other", nonce)?);
+        let witness =
+            Witness::from_assignments(&abi, [("X", "3"), ("Y", "9")])?.with_app_context(context)?;
+        let proof = groth16_prove(&path("ctx.r1cs"), &path("ctx.pk"), &witness.to_json())?;
+        assert_eq!(proof_app_context(&proof)?, context);
+        // Same length, but ending in a two-byte character: an error, not a panic.
+        let mut garbled = proof.clone();
+        garbled
+            .public_inputs
+            .truncate(garbled.public_inputs.len() - 2);
+        garbled.public_inputs.push('é');
+        assert!(proof_app_context(&garbled).is_err());
+
+        let verify = |domain: &str, challenge: &[u8]| {
+            groth16_verify_in_context(
+                &path("ctx.r1cs"),
+                &path("ctx.vk"),
+                &proof,
+                domain,
+                challenge,
+            )
+        };
+        assert!(verify(domain, nonce)?);
+        assert!(!verify(domain, b"nonce-2")?);
+        assert!(!verify("com.example.other", nonce)?);
+        assert!(app_context(&"x".repeat(300), nonce).is_err());
+
+        let mut reserved = CircuitBuilder::new();
+        reserved.public_input(APP_CONTEXT);
+        reserved.bind_app_context(true);
+        assert!(reserved.compile().is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: the test engine accepts satisfying witnesses and rejects the
+    /// others without compiling the circuit.
+    #[test]
+    fn test_circuit_test_engine() -> Result<()> {
+        use rust_gnark::test_utils::{assert_circuit, check_assignment};
+
+        init()?;
+
+        // Y = 1 / X with X in [1, 2^8), through a hint.
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let inv = c.hint(Hint::Inverse, &[x])[0];
+        let one = c.constant(1);
+        let prod = c.mul(x, inv);
+        c.assert_eq(prod, one);
+        c.assert_eq(inv, y);
+        c.range_check(x, 8);
+        let abi = c.abi();
+        let witness = |x: &str, y: &str| Witness::from_assignments(&abi, [("X", x), ("Y", y)]);
+
+        // 1/2 and 1/3 mod r.
+        let half = "10944121435919637611123202872628637544274182200208017171849102093287904247809";
+        let third = "14592161914559516814830937163504850059032242933610689562465469457717205663745";
+        assert_circuit(
+            &c,
+            &[witness("2", half)?, witness("3", third)?],
+            &[
+                witness("2", third)?,
+                witness("0", "0")?,
+                witness("256", "1")?,
+            ],
+        );
+        assert!(check_assignment(&c, &witness("2", "5")?)?.is_some());
+        let partial = CircuitAbi::new(vec![], vec!["X".into()]);
+        assert!(check_assignment(&c, &Witness::from_assignments(&partial, [("X", "2")])?).is_err());
+        Ok(())
+    }
+
+    /// Test: a circuit hashing its public inputs exposes only the hash, which
+    /// the witness fills in and the proof carries.
+    #[test]
+    fn test_public_input_hashing() -> Result<()> {
+        use rust_gnark::{public_inputs_hash, Fr, PUBLIC_INPUTS_HASH};
+
+        init()?;
+
+        // A * X == B with A and B hashed.
+        let mut c = CircuitBuilder::new();
+        c.hash_public_inputs(true);
+        let a = c.public_input("A");
+        let x = c.secret_input("X");
+        let b = c.public_input("B");
+        let ax = c.mul(a, x);
+        c.assert_eq(ax, b);
+        let circuit = c.compile()?;
+
+        let abi = circuit.abi()?;
+        assert_eq!(abi, c.abi());
+        assert_eq!(abi.public(), [PUBLIC_INPUTS_HASH]);
+        assert_eq!(abi.secret(), ["A", "X", "B"]);
+        assert_eq!(abi.hashed_public_inputs(), ["A", "B"]);
+
+        let witness = Witness::from_assignments(&abi, [("A", "3"), ("X", "7"), ("B", "21")])?;
+        let hash = public_inputs_hash(&[Fr::from(3u64), Fr::from(21u64)])?;
+        assert_eq!(
+            hash.to_bytes_be()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>(),
+            "0724553662bc3489b3d23622a6af78a283073567cd9bd5a576d905b7779596a2"
+        );
+        assert_eq!(witness.public_inputs(), [hash]);
+        assert!(circuit.check_witness(&witness)?.is_none());
+        assert!(Witness::from_assignments(
+            &abi,
+            [
+                ("A", "3"),
+                ("X", "7"),
+                ("B", "21"),
+                (PUBLIC_INPUTS_HASH, "1")
+            ]
+        )
+        .is_err());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-pihash-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("c.r1cs"))?;
+        circuit.groth16_setup(&path("c.pk"), &path("c.vk"))?;
+        let proof = groth16_prove(&path("c.r1cs"), &path("c.pk"), &witness.to_json())?;
+        assert_eq!(proof.public_inputs, witness.public_inputs_hex());
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+        std::fs::remove_dir_all(&dir)?;
+
+        let mut none = CircuitBuilder::new();
+        none.hash_public_inputs(true);
+        let s = none.secret_input("S");
+        none.assert_eq(s, s);
+        assert!(none.compile().is_err());
+        Ok(())
+    }
+
+    /// Test: the native commitment matches the hash circuits check.
+    #[test]
+    fn test_public_inputs_commitment_native() -> Result<()> {
+        use rust_gnark::native::{fr_to_gnark_bytes, PublicInputs};
+
+        init()?;
+
+        let inputs = PublicInputs(vec![ark_bn254::Fr::from(3u64), ark_bn254::Fr::from(21u64)]);
+        let hash = rust_gnark::public_inputs_hash(&[3u64.into(), 21u64.into()])?;
+        assert_eq!(fr_to_gnark_bytes(&inputs.commitment()), hash.to_bytes_be());
+        Ok(())
+    }
+
+    /// Test: a PLONK proof verifies only under the hashes it was made with.
+    #[test]
+    fn test_plonk_hash_options() -> Result<()> {
+        use rust_gnark::{
+            plonk_prove, plonk_verify, PlonkConfig, PlonkVerifyingKey, Srs, TranscriptHash,
+        };
+
+        init()?;
+
+        // X * X == Y
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let circuit = c.compile_sparse()?;
+        let witness = Witness::from_assignments(&c.abi(), [("X", "3"), ("Y", "9")])?;
+
+        let srs = Srs::generate_insecure(circuit.srs_size_needed()?)?;
+        let (pk, vk) = circuit.plonk_setup(&srs)?;
+        let keccak = PlonkConfig::default()
+            .with_challenge_hash(TranscriptHash::Keccak256)
+            .with_hash_to_field(HashFunction::Keccak256);
+        assert_eq!(keccak.challenge_hash(), TranscriptHash::Keccak256);
+        let proof = plonk_prove(&circuit, &pk, &witness.to_json(), &keccak)?;
+        assert!(plonk_verify(&vk, &proof, &keccak)?);
+        assert!(!plonk_verify(&vk, &proof, &PlonkConfig::default())?);
+
+        let default = plonk_prove(&circuit, &pk, &witness.to_json(), &PlonkConfig::default())?;
+        let vk = PlonkVerifyingKey::from_bytes(&vk.to_bytes()?)?;
+        assert!(plonk_verify(&vk, &default, &PlonkConfig::default())?);
+        assert!(!plonk_verify(&vk, &default, &keccak)?);
+
+        let bad = Witness::from_assignments(&c.abi(), [("X", "3"), ("Y", "10")])?;
+        assert!(plonk_prove(&circuit, &pk, &bad.to_json(), &keccak).is_err());
+        Ok(())
+    }
+
+    /// Test: stats count a circuit's inputs and constraints, and a profiled
+    /// compile attributes the constraints to functions.
+    #[test]
+    fn test_circuit_stats() -> Result<()> {
+        init()?;
+
+        let cubic = CompiledCircuit::read(R1CS_PATH)?;
+        let stats = cubic.stats()?;
+        assert_eq!(stats.nb_constraints, cubic.nb_constraints()?);
+        assert_eq!((stats.nb_public_inputs, stats.nb_secret_inputs), (1, 1));
+        assert!(stats.nb_internal_variables > 0);
+        assert!(stats.gadgets.is_empty());
+
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        c.range_check(x, 16);
+        assert!(c.compile()?.stats()?.gadgets.is_empty());
+
+        c.profile_gadgets(true);
+        let circuit = c.compile()?;
+        let stats = circuit.stats()?;
+        assert_eq!(stats.nb_constraints, circuit.nb_constraints()?);
+        assert!(stats.gadgets.iter().any(|g| g.name.contains("Define")));
+        assert!(stats
+            .gadgets
+            .iter()
+            .all(|g| g.own_constraints <= g.constraints));
+        let own: u64 = stats.gadgets.iter().map(|g| g.own_constraints).sum();
+        assert!(own > 0 && own <= stats.nb_constraints);
+
+        Ok(())
+    }
+
+    /// Test: circuits export to the documented JSON dump and to zkInterface
+    /// messages.
+    #[test]
+    fn test_circuit_export() -> Result<()> {
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        let cubic = CompiledCircuit::read(R1CS_PATH)?;
+        cubic.export_json(&path("cubic.json"))?;
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path("cubic.json"))?)?;
+        assert_eq!(dump["version"], 1);
+        assert_eq!(dump["system"], "r1cs");
+        assert_eq!(dump["public"], serde_json::json!(["1", "Y"]));
+        assert_eq!(dump["secret"], serde_json::json!(["X"]));
+        let constraints = dump["constraints"].as_array().expect("constraints array");
+        assert_eq!(constraints.len() as u64, cubic.nb_constraints()?);
+        for term in constraints[0]["a"].as_array().expect("terms") {
+            assert!(term[0].is_u64() && term[1].is_string());
+        }
+
+        // Two size-prefixed messages, CircuitHeader then ConstraintSystem.
+        cubic.export_zkinterface(&path("cubic.zkif"))?;
+        let zkif = std::fs::read(path("cubic.zkif"))?;
+        let first = u32::from_le_bytes(zkif[..4].try_into()?) as usize + 4;
+        assert_eq!(&zkif[8..12], b"zkif");
+        assert_eq!(&zkif[first + 8..first + 12], b"zkif");
+        let second = u32::from_le_bytes(zkif[first..first + 4].try_into()?) as usize + 4;
+        assert_eq!(first + second, zkif.len());
+
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        let sparse = c.compile_sparse()?;
+        sparse.export_json(&path("sparse.json"))?;
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path("sparse.json"))?)?;
+        assert_eq!(dump["system"], "sparse_r1cs");
+        assert!(dump["constraints"][0]["qm"].is_string());
+        assert!(sparse.export_zkinterface(&path("sparse.zkif")).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: circuits with a Pedersen commitment prove, verify in Go and in
+    /// Rust, and export to Solidity.
+    #[test]
+    fn test_groth16_commitment() -> Result<()> {
+        use rust_gnark::native;
+
+        init()?;
+        let hex = |s: &str| -> Vec<u8> {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect()
+        };
+
+        // X^2 == Y, plus a constraint using a commitment to X and Y.
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        let r = c.commit(&[x, y]);
+        let rx = c.mul(r, x);
+        let xr = c.mul(x, r);
+        c.assert_eq(rx, xr);
+        let mut empty = CircuitBuilder::new();
+        empty.commit(&[]);
+        assert!(empty.to_description().is_err());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-commit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let circuit = c.compile()?;
+        circuit.write(&path("c.r1cs"))?;
+        circuit.groth16_setup(&path("c.pk"), &path("c.vk"))?;
+
+        let witness = r#"{"X": "3", "Y": "9"}"#;
+        let proof = groth16_prove(&path("c.r1cs"), &path("c.pk"), witness)?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+        let decoded = native::Proof::from_gnark_bytes(&hex(&proof.proof))?;
+        assert_eq!(decoded.commitments.len(), 1);
+        assert!(native::groth16_verify(&path("c.vk"), &proof)?);
+
+        let vk = native::VerifyingKey::from_gnark_bytes(&std::fs::read(path("c.vk"))?)?;
+        assert_eq!(vk.commitment_keys.len(), 1);
+        assert_eq!(
+            native::VerifyingKey::from_gnark_bytes(&vk.to_gnark_bytes())?.k,
+            vk.k
+        );
+        assert_eq!(
+            native::Proof::from_gnark_bytes(&decoded.to_gnark_bytes())?.commitments,
+            decoded.commitments
+        );
+
+        // Another statement's public inputs, or a tampered commitment, fail.
+        let other = groth16_prove(&path("c.r1cs"), &path("c.pk"), r#"{"X": "4", "Y": "16"}"#)?;
+        let swapped = rust_gnark::Groth16ProofResult {
+            proof: proof.proof.clone(),
+            public_inputs: other.public_inputs.clone(),
+        };
+        assert!(!native::groth16_verify(&path("c.vk"), &swapped)?);
+        let mut tampered = decoded.clone();
+        tampered.commitments[0] =
+            native::Proof::from_gnark_bytes(&hex(&other.proof))?.commitments[0];
+        let inputs = native::public_inputs_from_gnark_bytes(&hex(&proof.public_inputs))?;
+        assert!(!native::verify_proof(&vk, &tampered, &inputs)?);
+
+        // Solidity: proofs for the contract hash commitments with SHA-256.
+        let contract = VerifyingKey::open(&path("c.vk"))?.export_solidity()?;
+        assert!(contract.contains("contract Verifier"));
+        let config = ProverConfig::default().with_hash_function(HashFunction::Sha256);
+        let sol_proof =
+            groth16_prove_with_config(&path("c.r1cs"), &path("c.pk"), witness, &config)?;
+        assert!(groth16_verify_with_hash(
+            &path("c.r1cs"),
+            &path("c.vk"),
+            &sol_proof,
+            HashFunction::Sha256
+        )?);
+        let calldata = sol_proof.solidity_calldata()?;
+        assert!(calldata.len() > 256 && calldata.len() % 32 == 0);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a sparse circuit with lookup range checks survives a write/read
+    /// round trip and reports the SRS size PLONK needs.
+    #[test]
+    fn test_sparse_circuit_roundtrip() -> Result<()> {
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        c.range_check(x, 8);
+        c.range_check(y, 16);
+
+        let circuit = c.compile_sparse()?;
+        let n = circuit.nb_constraints()?;
+        let size = circuit.srs_size_needed()?;
+        assert!((size - 3).is_power_of_two() && size - 3 > n);
+        assert!(c.compile()?.srs_size_needed().is_err());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-scs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("c.scs").to_string_lossy().into_owned();
+        circuit.write(&path)?;
+        let read = CompiledCircuit::read_sparse(&path)?;
+        assert_eq!(read.nb_constraints()?, n);
+        assert_eq!(read.srs_size_needed()?, size);
+        assert_eq!(read.abi()?, circuit.abi()?);
+
+        let abi = read.abi()?;
+        let good = Witness::from_assignments(&abi, [("X", "200"), ("Y", "40000")])?;
+        assert_eq!(read.check_witness(&good)?, None);
+        let wide = Witness::from_assignments(&abi, [("X", "300"), ("Y", "90000")])?;
+        assert!(!matches!(read.check_witness(&wide), Ok(None)));
+        assert!(CompiledCircuit::read_sparse(R1CS_PATH).is_err());
+
+        let mut zero = CircuitBuilder::new();
+        let v = zero.secret_input("V");
+        zero.range_check(v, 0);
+        assert!(zero.to_description().is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: witnesses are assigned by name, reduced into the field, and prove.
+    #[test]
+    fn test_witness_from_assignments() -> Result<()> {
+        init()?;
+
+        let abi = CircuitAbi::from_r1cs(R1CS_PATH)?;
+        assert_eq!(abi.public(), ["Y"]);
+        assert_eq!(abi.secret(), ["X"]);
+
+        let witness = Witness::from_assignments(&abi, [("X", "0x3"), ("Y", "35")])?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, &witness.to_json())?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        // -1 and r - 1 are the same field element; r itself reduces to 0.
+        let r = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        let minus_one = Witness::from_assignments(&abi, [("X", "-1"), ("Y", r)])?;
+        assert_eq!(
+            minus_one.get("X").as_deref(),
+            Some("21888242871839275222246405745257275088548364400416034343698204186575808495616")
+        );
+        assert_eq!(minus_one.get("Y").as_deref(), Some("0"));
+
+        assert!(Witness::from_assignments(&abi, [("X", "3")]).is_err());
+        assert!(Witness::from_assignments(&abi, [("X", "3"), ("Y", "35"), ("Z", "1")]).is_err());
+        assert!(Witness::from_assignments(&abi, [("X", "three"), ("Y", "35")]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: a sparse witness sends only its non-zero values and the Go side
+    /// fills in zeros.
+    #[test]
+    fn test_witness_from_sparse() -> Result<()> {
+        init()?;
+
+        let abi = CircuitAbi::from_r1cs(R1CS_PATH)?;
+        // x = 0: 0 + 0 + 5 = 5.
+        let witness = Witness::from_sparse(&abi, [("Y", "5")])?;
+        assert_eq!(witness.to_json(), r#"{"$sparse":true,"Y":"5"}"#);
+        assert_eq!(witness.get("X").as_deref(), Some("0"));
+        let full = Witness::from_assignments(&abi, [("X", "0"), ("Y", "5")])?;
+        assert_eq!(witness.public_inputs_hex(), full.public_inputs_hex());
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, &witness.to_json())?;
+        assert_eq!(proof.public_inputs, witness.public_inputs_hex());
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        // Left out means zero, so the wrong default fails to solve.
+        assert!(groth16_prove(R1CS_PATH, PK_PATH, r#"{"$sparse":true,"Y":"35"}"#).is_err());
+        assert!(Witness::from_sparse(&abi, [("Z", "1")]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: a witness yields the prover's public inputs, and binds them to a proof.
+    #[test]
+    fn test_witness_public_inputs() -> Result<()> {
+        init()?;
+
+        let abi = CircuitAbi::from_r1cs(R1CS_PATH)?;
+        let witness = Witness::from_assignments(&abi, [("X", "3"), ("Y", "35")])?;
+        assert_eq!(witness.public_inputs(), [rust_gnark::Fr::from(35)]);
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, &witness.to_json())?;
+        assert_eq!(witness.public_inputs_hex(), proof.public_inputs);
+
+        let bound = rust_gnark::Groth16ProofResult::bind_public_inputs(proof.proof, &witness);
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &bound)?);
+
+        let other = Witness::from_assignments(&abi, [("X", "3"), ("Y", "36")])?;
+        let wrong = rust_gnark::Groth16ProofResult::bind_public_inputs(bound.proof, &other);
+        assert!(!groth16_verify(R1CS_PATH, VK_PATH, &wrong)?);
+
+        Ok(())
+    }
+
+    /// Test: nested JSON input maps onto gnark's flattened input names.
+    #[test]
+    fn test_witness_from_json() -> Result<()> {
+        let abi = CircuitAbi::new(
+            vec!["Root".into()],
+            vec![
+                "Path_0".into(),
+                "Path_1".into(),
+                "Leaf_Key".into(),
+                "Leaf_Value".into(),
+            ],
+        );
+        let input = serde_json::json!({
+            "Root": "0x10",
+            "Path": [1, "2"],
+            "Leaf": { "Key": true, "Value": -2 },
+        });
+        let witness = Witness::from_json(&abi, &input)?;
+        assert_eq!(witness.get("Root").as_deref(), Some("16"));
+        assert_eq!(witness.get("Path_1").as_deref(), Some("2"));
+        assert_eq!(witness.get("Leaf_Key").as_deref(), Some("1"));
+        assert_eq!(
+            witness.get("Leaf_Value").as_deref(),
+            Some("21888242871839275222246405745257275088548364400416034343698204186575808495615")
+        );
+
+        assert!(Witness::from_json(&abi, &serde_json::json!([1, 2])).is_err());
+        assert!(Witness::from_json_str(&abi, r#"{"Root": 1.5}"#).is_err());
+
+        Ok(())
+    }
+
+    /// Test: Fr arithmetic, parsing and encodings, and witnesses from elements.
+    #[test]
+    fn test_field_elements() -> Result<()> {
+        use rust_gnark::{Fq, Fr};
+
+        let x: Fr = "3".parse()?;
+        let y = x * x * x + x + Fr::from(5);
+        assert_eq!(y, Fr::from_str_radix("23", 16)?);
+        assert_eq!(
+            (-Fr::ONE).to_string(),
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616"
+        );
+        assert_eq!(Fr::from(7) - Fr::from(9), -Fr::from(2));
+        assert_eq!("-0x5".parse::<Fr>()?, -Fr::from(5));
+        for twice_signed in ["--5", "-0x-5", "0x-5", "--0x5"] {
+            assert!(twice_signed.parse::<Fr>().is_err(), "{twice_signed:?}");
+        }
+        assert!(Fr::from_str_radix("--5", 10).is_err());
+        assert_eq!(x * x.inverse().expect("3 is invertible"), Fr::ONE);
+        assert_eq!(Fr::ZERO.inverse(), None);
+        assert_eq!(
+            Fq::from(2) * Fq::from(2).inverse().expect("2 is invertible"),
+            Fq::ONE
+        );
+
+        let r = Fr::random()?;
+        assert_eq!(Fr::from_bytes_be(&r.to_bytes_be())?, r);
+        assert_eq!(Fr::from_bytes_le(&r.to_bytes_le())?, r);
+        assert_eq!(Fr::from_montgomery(r.to_montgomery()), Some(r));
+        assert_eq!(Fr::from(5).to_bytes_le()[0], 5);
+        assert!(Fr::from_bytes_be(&[0xff; 32]).is_err());
+
+        let abi = CircuitAbi::new(vec!["Y".into()], vec!["X".into()]);
+        let witness = Witness::from_elements(&abi, [("X", x), ("Y", y)])?;
+        assert_eq!(
+            witness,
+            Witness::from_assignments(&abi, [("X", "3"), ("Y", "35")])?
+        );
+        assert_eq!(witness.element("Y"), Some(y));
+        assert!(Witness::from_elements(&abi, [("X", x)]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: Fr and Fq agree with arkworks and gnark's byte encoding.
+    #[test]
+    fn test_field_elements_match_arkworks() -> Result<()> {
+        use ark_ff::{Field, PrimeField};
+        use rust_gnark::{Fq, Fr};
+
+        let x = Fr::random()?;
+        let ark_x = ark_bn254::Fr::from(x);
+        assert_eq!(Fr::from(ark_x), x);
+        assert_eq!(
+            rust_gnark::native::fr_to_gnark_bytes(&ark_x),
+            x.to_bytes_be()
+        );
+        assert_eq!(ark_x.into_bigint().0, x.to_limbs());
+        assert_eq!(ark_x.0 .0, x.to_montgomery());
+        assert_eq!(
+            ark_bn254::Fr::from(x.inverse().expect("nonzero")),
+            ark_x.inverse().expect("nonzero")
+        );
+        assert_eq!(ark_bn254::Fr::from(x * x), ark_x.square());
+
+        let q = Fq::random()?;
+        let ark_q = ark_bn254::Fq::from(q);
+        assert_eq!(
+            ark_bn254::Fq::from(q * q + Fq::ONE),
+            ark_q.square() + ark_bn254::Fq::ONE
+        );
+        assert_eq!(q.to_string(), ark_q.into_bigint().to_string());
+
+        Ok(())
+    }
+
+    /// Test: a Go circuit compiled at runtime matches the prebuilt test vector.
+    #[test]
+    fn test_compile_from_go() -> Result<()> {
+        init()?;
+
+        let source = r#"
+package main
+
+import "github.com/consensys/gnark/frontend"
+
+type Circuit struct {
+	X frontend.Variable `gnark:"X"`
+	Y frontend.Variable `gnark:"Y,public"`
+}
+
+func (c *Circuit) Define(api frontend.API) error {
+	api.AssertIsEqual(api.Add(api.Mul(c.X, c.X, c.X), c.X, 5), c.Y)
+	return nil
+}
+"#;
+        let circuit = CompiledCircuit::compile_from_go(source)?;
+        let expected = CompiledCircuit::read(R1CS_PATH)?;
+        assert_eq!(circuit.abi()?, expected.abi()?);
+        assert_eq!(circuit.nb_constraints()?, expected.nb_constraints()?);
+
+        let witness = Witness::from_assignments(&circuit.abi()?, [("X", "3"), ("Y", "35")])?;
+        assert_eq!(circuit.check_witness(&witness)?, None);
+
+        let err = CompiledCircuit::compile_from_go("package main\n\ntype Circuit struct{}\n")
+            .expect_err("Circuit has no Define method");
+        assert!(err.to_string().contains("go "), "{err}");
+
+        Ok(())
+    }
+
+    /// Test: a Brillig-less ACIR program compiles, with intermediate witnesses
+    /// solved from AssertZero opcodes.
+    #[test]
+    fn test_acir_program() -> Result<()> {
+        use rust_gnark::{AcirProgram, Fr};
+
+        init()?;
+
+        // Legacy bincode of an acir `Program` with one function.
+        type Expr = (Vec<(Fr, u32, u32)>, Vec<(Fr, u32)>, Fr);
+        fn program(opcodes: &[Expr], private: &[u32], public: &[u32], returns: &[u32]) -> Vec<u8> {
+            let mut out = Vec::new();
+            let len = |out: &mut Vec<u8>, n: usize| out.extend((n as u64).to_le_bytes());
+            let field = |out: &mut Vec<u8>, x: &Fr| {
+                len(out, 64);
+                out.extend(
+                    x.to_bytes_be()
+                        .iter()
+                        .flat_map(|b| format!("{b:02x}").into_bytes()),
+                );
+            };
+            let witnesses = |out: &mut Vec<u8>, ws: &[u32]| {
+                len(out, ws.len());
+                ws.iter().for_each(|w| out.extend(w.to_le_bytes()));
+            };
+            len(&mut out, 1);
+            out.extend(10u32.to_le_bytes());
+            len(&mut out, opcodes.len());
+            for (mul, linear, constant) in opcodes {
+                out.extend(0u32.to_le_bytes()); // AssertZero
+                len(&mut out, mul.len());
+                for (c, a, b) in mul {
+                    field(&mut out, c);
+                    out.extend(a.to_le_bytes());
+                    out.extend(b.to_le_bytes());
+                }
+                len(&mut out, linear.len());
+                for (c, w) in linear {
+                    field(&mut out, c);
+                    out.extend(w.to_le_bytes());
+                }
+                field(&mut out, constant);
+            }
+            out.extend(0u32.to_le_bytes()); // ExpressionWidth::Unbounded
+            witnesses(&mut out, private);
+            witnesses(&mut out, public);
+            witnesses(&mut out, returns);
+            out
+        }
+
+        // w2 = w0 * w0; w2 * w0 + w0 + 5 == w1.
+        let one = Fr::ONE;
+        let opcodes = [
+            (vec![(one, 0, 0)], vec![(-one, 2)], Fr::ZERO),
+            (vec![(one, 2, 0)], vec![(one, 0), (-one, 1)], Fr::from(5)),
+        ];
+        let acir = AcirProgram::load(&program(&opcodes, &[0], &[1], &[]))?;
+        let circuit = acir.compile()?;
+        let abi = circuit.abi()?;
+        assert_eq!(abi.public(), ["w1"]);
+        assert_eq!(abi.secret(), ["w0"]);
+
+        let good = Witness::from_assignments(&abi, [("w0", "3"), ("w1", "35")])?;
+        assert_eq!(circuit.check_witness(&good)?, None);
+        let bad = Witness::from_assignments(&abi, [("w0", "3"), ("w1", "36")])?;
+        assert!(circuit.check_witness(&bad)?.is_some());
+        assert!(acir.compile_sparse()?.nb_constraints()? > 0);
+
+        // w2 and w3 first appear together, so neither is determined.
+        let unsolvable = [(vec![(one, 2, 3)], vec![(-one, 1)], Fr::ZERO)];
+        let acir = AcirProgram::load(&program(&unsolvable, &[0], &[1], &[]))?;
+        assert!(acir.compile().is_err());
+
+        let mut brillig = program(&[], &[0], &[1], &[]);
+        brillig[12..20].copy_from_slice(&1u64.to_le_bytes());
+        brillig.splice(20..20, 4u32.to_le_bytes());
+        let err = AcirProgram::load(&brillig).expect_err("BrilligCall is unsupported");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    /// Test: check_witness pinpoints the failing constraint without proving.
+    #[test]
+    fn test_check_witness() -> Result<()> {
+        init()?;
+
+        let circuit = CompiledCircuit::read(R1CS_PATH)?;
+        let abi = circuit.abi()?;
+
+        let good = Witness::from_assignments(&abi, [("X", "3"), ("Y", "35")])?;
+        assert_eq!(circuit.check_witness(&good)?, None);
+
+        let bad = Witness::from_assignments(&abi, [("X", "3"), ("Y", "36")])?;
+        let violation = circuit
+            .check_witness(&bad)?
+            .expect("y = 36 must violate a constraint");
+        assert!((violation.constraint_index as u64) < circuit.nb_constraints()?);
+        assert!(!violation.detail.is_empty());
+        assert!(violation.to_string().contains("is not satisfied"));
+        for (name, value) in &violation.inputs {
+            assert_eq!(bad.get(name).as_deref(), Some(value.as_str()));
+        }
+
+        Ok(())
+    }
+
+    /// Test: solve_profile times hints and levels, and a hint timeout fails a
+    /// hint that hangs.
+    #[test]
+    fn test_solve_profile() -> Result<()> {
+        init()?;
+        use rust_gnark::Fr;
+        use std::time::Duration;
+
+        let cubic = CompiledCircuit::read(R1CS_PATH)?;
+        let good = Witness::from_assignments(&cubic.abi()?, [("X", "3"), ("Y", "35")])?;
+        let config = ProverConfig::default().with_solver_threads(2);
+        assert_eq!(config.solver_threads(), Some(2));
+        let profile = cubic.solve_profile(&good, &config)?;
+        assert_eq!(
+            profile.levels.iter().sum::<usize>() as u64,
+            cubic.nb_constraints()?
+        );
+        assert!(profile.hints.is_empty());
+        let bad = Witness::from_assignments(&cubic.abi()?, [("X", "3"), ("Y", "36")])?;
+        let err = cubic
+            .solve_profile(&bad, &config)
+            .expect_err("an unsatisfied witness must not solve");
+        assert!(
+            matches!(
+                err.downcast_ref::<GnarkError>(),
+                Some(GnarkError::WitnessSolveFailed {
+                    constraint_index: Some(_),
+                    ..
+                })
+            ),
+            "unexpected error: {err:#}"
+        );
+
+        let mut c = CircuitBuilder::new();
+        let x = c.public_input("X");
+        let half = c.hint(
+            Hint::Rust {
+                id: 56902,
+                outputs: 1,
+            },
+            &[x],
+        )[0];
+        let doubled = c.add(half, half);
+        c.assert_eq(doubled, x);
+        let circuit = c.compile()?;
+        let witness = Witness::from_assignments(&circuit.abi()?, [("X", "5")])?;
+
+        rust_gnark::register_hint(56902, |inputs: &[Fr], outputs: &mut [Fr]| {
+            outputs[0] = inputs[0] * Fr::from(2).inverse().expect("2 is invertible");
+            Ok(())
+        });
+        let profile = circuit.solve_profile(&witness, &ProverConfig::default())?;
+        assert_eq!(profile.hints.len(), 1);
+        assert_eq!(profile.hints[0].name, "rust:56902");
+        assert_eq!(profile.hints[0].calls, 1);
+
+        rust_gnark::register_hint(56902, |_: &[Fr], _: &mut [Fr]| {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(())
+        });
+        let config = ProverConfig::default().with_hint_timeout(Duration::from_millis(20));
+        let err = circuit
+            .solve_profile(&witness, &config)
+            .expect_err("a hanging hint must time out");
+        assert!(format!("{err:#}").contains("timed out"));
+
+        assert!(rust_gnark::unregister_hint(56902));
+        Ok(())
+    }
+
+    /// Test: Poseidon2 is deterministic, order-sensitive and rejects
+    /// out-of-range inputs.
+    #[test]
+    fn test_poseidon2() -> Result<()> {
+        use rust_gnark::hash::{poseidon2, Curve};
+
+        init()?;
+
+        let fe = |v: u8| {
+            let mut b = [0u8; 32];
+            b[31] = v;
+            b
+        };
+        let h12 = poseidon2(Curve::Bn254, &[fe(1), fe(2)])?;
+        assert_eq!(h12, poseidon2(Curve::Bn254, &[fe(1), fe(2)])?);
+        assert_ne!(h12, poseidon2(Curve::Bn254, &[fe(2), fe(1)])?);
+        assert_ne!(h12, poseidon2(Curve::Bls12_381, &[fe(1), fe(2)])?);
+        assert!(poseidon2(Curve::Bn254, &[[0xff; 32]]).is_err());
+
+        let native = rust_gnark::hash::poseidon2_bn254(&[1u64.into(), 2u64.into()])?;
+        assert_eq!(rust_gnark::native::fr_to_gnark_bytes(&native), h12);
+
+        Ok(())
+    }
+
+    /// Test: MiMC differs from Poseidon2 and agrees across both APIs.
+    #[test]
+    fn test_mimc() -> Result<()> {
+        use rust_gnark::hash::{mimc, poseidon2, Curve};
+
+        init()?;
+
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let h = mimc(Curve::Bn254, &[one])?;
+        assert_eq!(h, mimc(Curve::Bn254, &[one])?);
+        assert_ne!(h, poseidon2(Curve::Bn254, &[one])?);
+        assert_ne!(h, mimc(Curve::Bls12_381, &[one])?);
+        assert!(mimc(Curve::Bn254, &[[0xff; 32]]).is_err());
+
+        let native = rust_gnark::hash::mimc_bn254(&[1u64.into()])?;
+        assert_eq!(rust_gnark::native::fr_to_gnark_bytes(&native), h);
+
+        Ok(())
+    }
+
+    /// Test: ABIs group array leaves, and derived witness structs match the
+    /// circuit's ABI, checked at compile time against its ABI file.
+    #[test]
+    fn test_gnark_witness() -> Result<()> {
+        use rust_gnark::{AbiInput, Fr, GnarkWitness};
+
+        #[derive(GnarkWitness)]
+        #[gnark(abi = "test-vectors/cubic_circuit.abi.json")]
+        struct Cubic {
+            x: Fr,
+            #[gnark(public)]
+            y: u64,
+        }
+
+        #[derive(GnarkWitness)]
+        struct Membership {
+            #[gnark(public)]
+            root: Fr,
+            path: [Fr; 3],
+            #[gnark(name = "M")]
+            matrix: [[u8; 3]; 2],
+        }
+
+        init()?;
+
+        let abi = CircuitAbi::from_r1cs(R1CS_PATH)?;
+        let file = std::fs::read_to_string(test_vector!("cubic_circuit.abi.json"))?;
+        assert_eq!(abi.to_abi_json(), file.trim_end());
+        Cubic::check_abi(&abi)?;
+        assert!(Membership::check_abi(&abi).is_err());
+
+        let witness = Cubic {
+            x: Fr::from(3),
+            y: 35,
+        }
+        .to_witness()?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, &witness.to_json())?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let abi = Membership::abi();
+        assert_eq!(abi.public(), ["Root"]);
+        assert_eq!(abi.secret()[..2], ["Path_0", "Path_1"]);
+        let input = |name: &str, public, shape: &[usize]| AbiInput {
+            name: name.into(),
+            public,
+            shape: shape.to_vec(),
+        };
+        assert_eq!(
+            abi.inputs(),
+            [
+                input("Root", true, &[]),
+                input("Path", false, &[3]),
+                input("M", false, &[2, 3]),
+            ]
+        );
+        let witness = Membership {
+            root: Fr::from(1),
+            path: [Fr::from(2), Fr::from(3), Fr::from(4)],
+            matrix: [[0, 1, 2], [3, 4, 5]],
+        }
+        .to_witness()?;
+        assert_eq!(witness.get("Path_2").as_deref(), Some("4"));
+        assert_eq!(witness.get("M_1_2").as_deref(), Some("5"));
+
+        // Leaves that do not cover a shape stay separate inputs.
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        let sparse = CircuitAbi::new(vec![], names(&["A_0", "A_2", "S_F", "B_1"]));
+        let sparse: Vec<_> = sparse.inputs().into_iter().map(|i| i.name).collect();
+        assert_eq!(sparse, ["A_0", "A_2", "S_F", "B_1"]);
+        Ok(())
+    }
+
+    /// Test: the GKR MiMC gadget computes the host's digests like the plain
+    /// gadget, and its circuits prove and verify.
+    #[test]
+    fn test_mimc_gkr() -> Result<()> {
+        use rust_gnark::hash::{mimc, Curve};
+        use rust_gnark::Fr;
+
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let h = c.public_input("H");
+        let x = c.secret_input("X");
+        let y = c.secret_input("Y");
+        let plain = c.mimc(&[x, y]);
+        c.assert_eq(plain, h);
+        for _ in 0..4 {
+            let digest = c.mimc_gkr(&[x, y]);
+            c.assert_eq(digest, h);
+        }
+        let mut empty = CircuitBuilder::new();
+        empty.mimc_gkr(&[]);
+        assert!(empty.to_description().is_err());
+        let circuit = c.compile()?;
+
+        let digest = mimc(
+            Curve::Bn254,
+            &[Fr::from(3).to_bytes_be(), Fr::from(5).to_bytes_be()],
+        )?;
+        let digest = Fr::from_bytes_be(&digest)?.to_string();
+        let abi = c.abi();
+        let witness =
+            Witness::from_assignments(&abi, [("H", digest.as_str()), ("X", "3"), ("Y", "5")])?;
+        assert!(circuit.check_witness(&witness)?.is_none());
+        let wrong =
+            Witness::from_assignments(&abi, [("H", digest.as_str()), ("X", "3"), ("Y", "6")])?;
+        assert!(circuit.check_witness(&wrong)?.is_some());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-gkr-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("c.r1cs"))?;
+        circuit.unsafe_setup_with_seed(1, &path("c.pk"), &path("c.vk"))?;
+        let proof = groth16_prove(&path("c.r1cs"), &path("c.pk"), &witness.to_json())?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: transcripts derive challenges as gnark-crypto's fiat-shamir does
+    /// (label, previous challenge, bound data) and enforce their order.
+    #[test]
+    fn test_transcript() -> Result<()> {
+        use rust_gnark::{Fr, Transcript, TranscriptHash};
+        use sha2::{Digest, Sha256};
+
+        init()?;
+        let mut fs = Transcript::new(TranscriptHash::Sha256, &Transcript::PLONK_CHALLENGES)?;
+        fs.append("gamma", b"public inputs")?;
+        fs.append("gamma", b"commitments")?;
+        fs.append("beta", &[1, 2, 3])?;
+        assert!(fs.challenge("beta").is_err());
+        assert!(fs.append("delta", b"").is_err());
+
+        let gamma = fs.challenge("gamma")?;
+        let expected: [u8; 32] = Sha256::new()
+            .chain_update("gamma")
+            .chain_update("public inputs")
+            .chain_update("commitments")
+            .finalize()
+            .into();
+        assert_eq!(gamma, expected);
+        assert_eq!(fs.challenge("gamma")?, gamma);
+        assert!(fs.append("gamma", b"late").is_err());
+
+        let beta = fs.challenge_scalar("beta")?;
+        let digest: [u8; 32] = Sha256::new()
+            .chain_update("beta")
+            .chain_update(gamma)
+            .chain_update([1, 2, 3])
+            .finalize()
+            .into();
+        let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(beta, Fr::from_str_radix(&hex, 16)?);
+
+        for hash in [TranscriptHash::Keccak256, TranscriptHash::Poseidon2] {
+            let mut fs = Transcript::new(hash, &["a"])?;
+            fs.append("a", &[0; 32])?;
+            assert_ne!(fs.challenge("a")?, expected);
+        }
+        Ok(())
+    }
+
+    /// Test: BLS12-381 points convert to the EIP-2537 encoding and back, with
+    /// infinity as all zeros and G2 components swapped.
+    #[test]
+    fn test_eip2537_encoding() -> Result<()> {
+        use rust_gnark::ecc::{self, Curve};
+        use rust_gnark::eip2537;
+
+        init()?;
+        let hex = |s: &str| -> Vec<u8> {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect()
+        };
+
+        // The BLS12-381 G1 generator, through gnark-crypto's MSM.
+        let x = hex("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        let y = hex("08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1");
+        let generator = [x.as_slice(), y.as_slice()].concat();
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let g: eip2537::G1Raw = ecc::msm_g1(Curve::Bls12_381, &generator, &[one])?
+            .try_into()
+            .unwrap();
+        let encoded = eip2537::g1_to_eip2537(&g)?;
+        assert_eq!(&encoded[..16], &[0u8; 16]);
+        assert_eq!(&encoded[16..64], x.as_slice());
+        assert_eq!(&encoded[64..80], &[0u8; 16]);
+        assert_eq!(&encoded[80..], y.as_slice());
+        assert_eq!(eip2537::g1_from_eip2537(&encoded)?, g);
+
+        let infinity: eip2537::G1Raw = ecc::msm_g1(Curve::Bls12_381, &[], &[])?.try_into().unwrap();
+        assert_eq!(eip2537::g1_to_eip2537(&infinity)?, [0u8; 128]);
+        assert_eq!(eip2537::g1_from_eip2537(&[0u8; 128])?, infinity);
+        let mut compressed = g;
+        compressed[0] |= 0x80;
+        assert!(eip2537::g1_to_eip2537(&compressed).is_err());
+        let mut unpadded = encoded;
+        unpadded[0] = 1;
+        assert!(eip2537::g1_from_eip2537(&unpadded).is_err());
+
+        // G2: x.A1 || x.A0 || y.A1 || y.A0 becomes x.c0 || x.c1 || y.c0 || y.c1.
+        let mut g2 = [0u8; 192];
+        for (i, fp) in g2.chunks_exact_mut(48).enumerate() {
+            fp[47] = i as u8 + 1;
+        }
+        let encoded = eip2537::g2_to_eip2537(&g2)?;
+        let last_bytes: Vec<u8> = encoded.chunks_exact(64).map(|fp| fp[63]).collect();
+        assert_eq!(last_bytes, [2, 1, 4, 3]);
+        assert_eq!(eip2537::g2_from_eip2537(&encoded)?, g2);
+
+        let input = eip2537::pairing_input(&[g, infinity], &[g2, g2])?;
+        assert_eq!(input.len(), 2 * (128 + 256));
+        assert!(eip2537::pairing_input(&[g], &[]).is_err());
+        Ok(())
+    }
+
+    /// Test: gnark-crypto's MSM and FFT agree with arkworks.
+    #[test]
+    fn test_msm_fft() -> Result<()> {
+        use ark_bn254::{Fr, G1Affine, G1Projective};
+        use ark_ec::{AffineRepr, CurveGroup};
+        use rust_gnark::ecc::{self, Curve};
+
+        init()?;
+
+        let g = G1Affine::generator();
+        let points: Vec<G1Affine> = (1..=4u64)
+            .map(|i| (g * Fr::from(i)).into_affine())
+            .collect();
+        let scalars: Vec<Fr> = (5..=8u64).map(Fr::from).collect();
+        let expected: G1Projective = points.iter().zip(&scalars).map(|(p, s)| *p * s).sum();
+        assert_eq!(
+            ecc::msm_g1_bn254(&points, &scalars)?,
+            expected.into_affine()
+        );
+        assert!(ecc::msm_g1_bn254(&[], &[])?.is_zero());
+        assert!(ecc::msm_g1(Curve::Bn254, &[0u8; 64], &[]).is_err());
+
+        // A constant polynomial evaluates to itself everywhere.
+        let mut values = vec![
+            Fr::from(9u64),
+            Fr::from(0u64),
+            Fr::from(0u64),
+            Fr::from(0u64),
+        ];
+        ecc::fft_bn254(&mut values)?;
+        assert_eq!(values, vec![Fr::from(9u64); 4]);
+
+        let coeffs: Vec<Fr> = (1..=8u64).map(Fr::from).collect();
+        let mut values = coeffs.clone();
+        ecc::fft_bn254(&mut values)?;
+        assert_ne!(values, coeffs);
+        ecc::ifft_bn254(&mut values)?;
+        assert_eq!(values, coeffs);
+
+        let mut bytes = [[0u8; 32]; 4];
+        bytes[0][31] = 1;
+        bytes[1][31] = 2;
+        let original = bytes;
+        ecc::fft(Curve::Bls12_381, &mut bytes)?;
+        ecc::ifft(Curve::Bls12_381, &mut bytes)?;
+        assert_eq!(bytes, original);
+        assert!(ecc::fft(Curve::Bn254, &mut [[0u8; 32]; 3]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: BN254 group arithmetic agrees with itself and a BLS-style
+    /// signature passes the pairing check.
+    #[test]
+    fn test_bn254_group_ops() -> Result<()> {
+        use ark_bn254::{Fr, G1Affine, G2Affine};
+        use ark_ec::AffineRepr;
+        use ark_ff::{BigInteger, PrimeField};
+        use rust_gnark::ecc::{self, G1Bytes, G2Bytes};
+        use rust_gnark::native::fr_to_gnark_bytes;
+
+        init()?;
+
+        let be = |x: &ark_bn254::Fq| x.into_bigint().to_bytes_be();
+        let g1: G1Bytes = [be(&G1Affine::generator().x), be(&G1Affine::generator().y)]
+            .concat()
+            .try_into()
+            .unwrap();
+        let g = G2Affine::generator();
+        let g2: G2Bytes = [be(&g.x.c1), be(&g.x.c0), be(&g.y.c1), be(&g.y.c0)]
+            .concat()
+            .try_into()
+            .unwrap();
+        let scalar = |v: u64| fr_to_gnark_bytes(&Fr::from(v));
+
+        assert_eq!(ecc::g1_add(&g1, &g1)?, ecc::g1_scalar_mul(&g1, &scalar(2))?);
+        assert_eq!(ecc::g2_add(&g2, &g2)?, ecc::g2_scalar_mul(&g2, &scalar(2))?);
+        assert!(ecc::g1_add(&g1, &[1u8; 64]).is_err());
+
+        // Secret key 5, message point H = 3 * G1: e(sig, G2) == e(H, pk).
+        let pk = ecc::g2_scalar_mul(&g2, &scalar(5))?;
+        let h = ecc::g1_scalar_mul(&g1, &scalar(3))?;
+        let sig = ecc::g1_scalar_mul(&h, &scalar(5))?;
+        assert!(ecc::pairing_check(&[sig, ecc::g1_neg(&h)?], &[g2, pk])?);
+        assert!(!ecc::pairing_check(&[h, ecc::g1_neg(&h)?], &[g2, pk])?);
+        assert!(ecc::pairing_check(&[sig], &[g2, pk]).is_err());
+
+        Ok(())
+    }
+
+    /// Test: EdDSA keys round-trip and signatures verify only for their message.
+    #[test]
+    fn test_eddsa_sign_verify() -> Result<()> {
+        use rust_gnark::eddsa::{EdwardsCurve, PublicKey, SigningKey};
+
+        init()?;
+
+        let fe = |v: u8| {
+            let mut b = [0u8; 32];
+            b[31] = v;
+            b
+        };
+        for curve in [
+            EdwardsCurve::BabyJubjub,
+            EdwardsCurve::Jubjub,
+            EdwardsCurve::Bandersnatch,
+        ] {
+            let key = SigningKey::generate(curve)?;
+            let key = SigningKey::from_bytes(curve, key.to_bytes())?;
+            let public = key.public_key()?;
+            let public = PublicKey::from_bytes(curve, public.to_bytes());
+
+            let signature = key.sign(&[fe(42)])?;
+            assert!(public.verify(&[fe(42)], &signature)?);
+            assert!(!public.verify(&[fe(43)], &signature)?);
+
+            let other = SigningKey::generate(curve)?.public_key()?;
+            assert!(!other.verify(&[fe(42)], &signature)?);
+        }
+
+        Ok(())
+    }
+
+    /// Test: BLS keys follow EIP-2333's KeyGen, and single, aggregate and
+    /// fast-aggregate verification accept good signatures and reject bad ones.
+    #[test]
+    fn test_bls_signatures() -> Result<()> {
+        use rust_gnark::bls::{
+            aggregate_verify, fast_aggregate_verify, PublicKey, SecretKey, Signature, DST_POP,
+        };
+
+        init()?;
+
+        let hex = |s: &str| -> Vec<u8> {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect()
+        };
+        // EIP-2333 test case 0: the master key of this seed.
+        let seed = hex("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+        let key = SecretKey::key_gen(&seed)?;
+        assert_eq!(
+            key.to_bytes().to_vec(),
+            hex("0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070")
+        );
+        assert!(SecretKey::key_gen(&[1u8; 31]).is_err());
+        assert!(SecretKey::from_bytes(&[0u8; 32]).is_err());
+
+        let keys: Vec<SecretKey> = (1..=3u8)
+            .map(|i| SecretKey::key_gen(&[i; 32]))
+            .collect::<Result<_>>()?;
+        let public: Vec<PublicKey> = keys.iter().map(|k| k.public_key()).collect::<Result<_>>()?;
+        assert_eq!(PublicKey::from_bytes(&public[0].to_bytes())?, public[0]);
+        assert!(PublicKey::from_bytes(&[0u8; 48]).is_err());
+
+        let signature = keys[0].sign(b"hello", DST_POP)?;
+        assert_eq!(Signature::from_bytes(&signature.to_bytes())?, signature);
+        assert!(public[0].verify(b"hello", DST_POP, &signature)?);
+        assert!(!public[0].verify(b"world", DST_POP, &signature)?);
+        assert!(!public[1].verify(b"hello", DST_POP, &signature)?);
+
+        let proof = keys[1].prove_possession()?;
+        assert!(public[1].verify_possession(&proof)?);
+        assert!(!public[0].verify_possession(&proof)?);
+
+        // Everyone signs the same message.
+        let same: Vec<Signature> = keys
+            .iter()
+            .map(|k| k.sign(b"block", DST_POP))
+            .collect::<Result<_>>()?;
+        let aggregate = Signature::aggregate(&same)?;
+        assert!(fast_aggregate_verify(
+            &public, b"block", DST_POP, &aggregate
+        )?);
+        assert!(!fast_aggregate_verify(
+            &public[..2],
+            b"block",
+            DST_POP,
+            &aggregate
+        )?);
+
+        // Everyone signs their own message.
+        let messages: [&[u8]; 3] = [b"a", b"bb", b"ccc"];
+        let distinct: Vec<Signature> = keys
+            .iter()
+            .zip(messages)
+            .map(|(k, m)| k.sign(m, DST_POP))
+            .collect::<Result<_>>()?;
+        let aggregate = Signature::aggregate(&distinct)?;
+        let signers: Vec<(PublicKey, &[u8])> = public.iter().copied().zip(messages).collect();
+        assert!(aggregate_verify(&signers, DST_POP, &aggregate)?);
+        let swapped = [signers[1], signers[0], signers[2]];
+        assert!(aggregate_verify(&swapped, DST_POP, &aggregate)?);
+        let wrong = [signers[0], signers[1], (public[2], &b"cc"[..])];
+        assert!(!aggregate_verify(&wrong, DST_POP, &aggregate)?);
+
+        Ok(())
+    }
+
+    /// Test: Merkle proofs verify for every leaf and reject a wrong index.
+    #[test]
+    fn test_merkle_tree() -> Result<()> {
+        use rust_gnark::hash::{mimc, Curve};
+        use rust_gnark::merkle::{MerkleTree, TreeHash};
+
+        init()?;
+
+        let leaves: Vec<[u8; 32]> = (1..=5u8)
+            .map(|v| {
+                let mut b = [0u8; 32];
+                b[31] = v;
+                b
+            })
+            .collect();
+        let tree = MerkleTree::new(Curve::Bn254, TreeHash::Mimc, &leaves)?;
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.depth(), 3);
+
+        for i in 0..tree.len() {
+            let proof = tree.proof(i)?;
+            assert_eq!(proof.path.len(), tree.depth() + 1);
+            assert!(proof.verify(Curve::Bn254, TreeHash::Mimc)?);
+        }
+        let mut wrong = tree.proof(2)?;
+        wrong.index = 3;
+        assert!(!wrong.verify(Curve::Bn254, TreeHash::Mimc)?);
+        assert!(tree.proof(8).is_err());
+
+        // Two leaves: root = H(H(a), H(b)).
+        let pair = MerkleTree::new(Curve::Bn254, TreeHash::Mimc, &leaves[..2])?;
+        let a = mimc(Curve::Bn254, &leaves[..1])?;
+        let b = mimc(Curve::Bn254, &leaves[1..2])?;
+        assert_eq!(pair.root(), mimc(Curve::Bn254, &[a, b])?);
+
+        let names: Vec<String> = pair
+            .proof(0)?
+            .assignments("M")
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(names, ["M_RootHash", "M_Path_0", "M_Path_1"]);
+
+        Ok(())
+    }
+
+    /// Write a minimal `.ptau` file of the given power with toxic waste `tau`.
+    fn write_test_ptau(path: &std::path::Path, power: u32, tau: u64) -> Result<()> {
+        use ark_bn254::{Fq, Fr, G1Affine, G2Affine};
+        use ark_ec::{AffineRepr, CurveGroup};
+        use ark_ff::{BigInteger, PrimeField};
+
+        // Montgomery limbs, as ptau stores them.
+        let fq = |x: &Fq| x.0.to_bytes_le();
+        let section = |out: &mut Vec<u8>, kind: u32, data: Vec<u8>| {
+            out.extend(kind.to_le_bytes());
+            out.extend((data.len() as u64).to_le_bytes());
+            out.extend(data);
+        };
+        let tau = Fr::from(tau);
+        let powers = |n: usize| {
+            (0..n).scan(Fr::from(1u64), |acc, _| {
+                let p = *acc;
+                *acc *= tau;
+                Some(p)
+            })
+        };
+
+        let mut header = 32u32.to_le_bytes().to_vec();
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+        let mut g1 = Vec::new();
+        for t in powers((1 << (power + 1)) - 1) {
+            let p = (G1Affine::generator() * t).into_affine();
+            g1.extend(fq(&p.x));
+            g1.extend(fq(&p.y));
+        }
+        let mut g2 = Vec::new();
+        for t in powers(1 << power) {
+            let p = (G2Affine::generator() * t).into_affine();
+            for c in [p.x.c0, p.x.c1, p.y.c0, p.y.c1] {
+                g2.extend(fq(&c));
+            }
+        }
+
+        let mut out = b"ptau".to_vec();
+        out.extend(1u32.to_le_bytes());
+        out.extend(3u32.to_le_bytes());
+        section(&mut out, 1, header);
+        section(&mut out, 2, g1);
+        section(&mut out, 3, g2);
+        std::fs::write(path, out)?;
+        Ok(())
+    }
+
+    /// Test: a .ptau file imports into a gnark KZG SRS.
+    #[test]
+    fn test_srs_from_ptau() -> Result<()> {
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-ptau-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let ptau = dir.join("test.ptau");
+        write_test_ptau(&ptau, 3, 7)?;
+
+        let srs = rust_gnark::Srs::from_ptau(&ptau.to_string_lossy())?;
+        assert_eq!(srs.size()?, (1 << 3) + 3);
+        let out = dir.join("test.srs");
+        srs.write(&out.to_string_lossy())?;
+        assert!(std::fs::metadata(&out)?.len() > 0);
+
+        std::fs::write(&ptau, b"not a ptau file")?;
+        assert!(rust_gnark::Srs::from_ptau(&ptau.to_string_lossy()).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a generated SRS trims to a smaller one that commits the same
+    /// way, and round-trips through bytes and files in every format.
+    #[test]
+    fn test_srs_generate_trim_serialize() -> Result<()> {
+        use rust_gnark::{Kzg, SerializationFormat, Srs};
+
+        init()?;
+
+        let srs = Srs::generate_insecure(16)?;
+        assert_eq!(srs.size()?, 16);
+        assert!(Srs::generate_insecure(1).is_err());
+
+        let trimmed = srs.trim(8)?;
+        assert_eq!(trimmed.size()?, 8);
+        assert!(srs.trim(17).is_err());
+        let poly: Vec<[u8; 32]> = [1u8, 2, 3]
+            .map(|c| {
+                let mut b = [0u8; 32];
+                b[31] = c;
+                b
+            })
+            .to_vec();
+        let commitment = Kzg::new(trimmed).commit(&poly)?;
+        let srs_bytes = srs.to_bytes(SerializationFormat::Compressed)?.to_vec();
+        let srs = Kzg::new(srs);
+        assert_eq!(srs.commit(&poly)?, commitment);
+
+        for format in [
+            SerializationFormat::Compressed,
+            SerializationFormat::Raw,
+            SerializationFormat::RawUnchecked,
+        ] {
+            let bytes = srs.srs().to_bytes(format)?;
+            let copy = Srs::from_bytes_with_format(&bytes, format)?;
+            assert_eq!(copy.to_bytes(format)?.to_vec(), bytes.to_vec());
+        }
+        assert!(Srs::from_bytes(&srs_bytes[..srs_bytes.len() / 2]).is_err());
+
+        // The compressed file is canonical: written, read and written again,
+        // it has the same bytes.
+        let dir = std::env::temp_dir().join(format!("rust-gnark-srs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("test.srs").to_string_lossy().into_owned();
+        srs.srs().write(&path)?;
+        assert_eq!(std::fs::read(&path)?, srs_bytes);
+        let reread = Srs::read(&path)?;
+        assert_eq!(
+            reread.to_bytes(SerializationFormat::Compressed)?.to_vec(),
+            srs_bytes
+        );
+        srs.srs()
+            .write_with_format(&path, SerializationFormat::Raw)?;
+        assert_eq!(
+            Srs::read_with_format(&path, SerializationFormat::Raw)?.size()?,
+            16
+        );
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: KZG commitments equal p(tau) * G1 and openings verify only for
+    /// the true value.
+    #[test]
+    fn test_kzg_commit_open_verify() -> Result<()> {
+        use ark_bn254::{Fr, G1Affine};
+        use ark_ec::{AffineRepr, CurveGroup};
+        use rust_gnark::native::{fr_to_gnark_bytes, g1_from_gnark_bytes};
+        use rust_gnark::{Kzg, Srs};
+
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-kzg-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let ptau = dir.join("test.ptau");
+        write_test_ptau(&ptau, 3, 7)?;
+        let kzg = Kzg::new(Srs::from_ptau(&ptau.to_string_lossy())?);
+        std::fs::remove_dir_all(&dir)?;
+
+        // p(x) = 1 + 2x + 3x^2: p(7) = 162, p(2) = 17.
+        let poly: Vec<_> = [1u64, 2, 3]
+            .map(|c| fr_to_gnark_bytes(&Fr::from(c)))
+            .to_vec();
+        let commitment = kzg.commit(&poly)?;
+        assert_eq!(
+            g1_from_gnark_bytes(&commitment)?,
+            (G1Affine::generator() * Fr::from(162u64)).into_affine()
+        );
+
+        let point = fr_to_gnark_bytes(&Fr::from(2u64));
+        let opening = kzg.open(&poly, &point)?;
+        assert_eq!(opening.value, fr_to_gnark_bytes(&Fr::from(17u64)));
+        assert!(kzg.verify(&commitment, &opening.proof, &point, &opening.value)?);
+        let wrong = fr_to_gnark_bytes(&Fr::from(18u64));
+        assert!(!kzg.verify(&commitment, &opening.proof, &point, &wrong)?);
+
+        let too_long = vec![[0u8; 32]; kzg.srs().size()? as usize + 1];
+        assert!(kzg.commit(&too_long).is_err());
+        Ok(())
+    }
+
+    /// Test: a two-participant ceremony yields keys that prove and verify.
+    #[test]
+    fn test_mpc_ceremony() -> Result<()> {
+        use rust_gnark::ceremony::{finalize_phase1, finalize_phase2, Phase1, Phase2, SrsCommons};
+        init()?;
+
+        let size = 8;
+        let initial = Phase1::new(size)?;
+        let mut first = Phase1::from_bytes(&initial.to_bytes()?)?;
+        first.contribute()?;
+        initial.verify_contribution(&first)?;
+        let mut second = Phase1::from_bytes(&first.to_bytes()?)?;
+        second.contribute()?;
+        first.verify_contribution(&second)?;
+        assert!(Phase1::new(6).is_err());
+
+        let commons = finalize_phase1(size, b"phase 1 beacon", &[&first, &second])?;
+        let commons = SrsCommons::from_bytes(&commons.to_bytes()?)?;
+
+        let initial = Phase2::new(R1CS_PATH, &commons)?;
+        let mut first = Phase2::from_bytes(&initial.to_bytes()?)?;
+        first.contribute()?;
+        initial.verify_contribution(&first)?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-mpc-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let pk = dir.join("cubic.pk").to_string_lossy().into_owned();
+        let vk = dir.join("cubic.vk").to_string_lossy().into_owned();
+        finalize_phase2(R1CS_PATH, &commons, b"phase 2 beacon", &[&first], &pk, &vk)?;
+
+        let proof = groth16_prove(R1CS_PATH, &pk, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, &vk, &proof)?);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: key fingerprints are stable across loading paths and distinguish keys.
+    #[test]
+    fn test_key_fingerprints() -> Result<()> {
+        init()?;
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        let fingerprint = pk.fingerprint()?;
+        let reloaded = ProvingKey::from_bytes(&pk.to_bytes()?)?;
+        assert_eq!(reloaded.fingerprint()?, fingerprint);
+        assert_eq!(fingerprint.to_string().len(), 64);
+        assert_eq!(
+            fingerprint.to_string().parse::<KeyFingerprint>()?,
+            fingerprint
+        );
+        assert!("abc".parse::<KeyFingerprint>().is_err());
+
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let vk_fingerprint = vk.fingerprint()?;
+        let vk_bytes = std::fs::read(VK_PATH)?;
+        assert_eq!(
+            VerifyingKey::from_bytes(&vk_bytes)?.fingerprint()?,
+            vk_fingerprint
+        );
+        assert_ne!(vk_fingerprint, fingerprint);
+        assert!(VerifyingKey::from_bytes(&vk_bytes[..vk_bytes.len() / 2]).is_err());
+        Ok(())
+    }
+
+    /// Test: a proving key saved in segments loads back, through the mmap
+    /// loader too, and a segment that does not match the manifest is
+    /// rejected.
+    #[test]
+    fn test_key_segmented() -> Result<()> {
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-segments-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.to_string_lossy().into_owned();
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        let size = std::fs::metadata(PK_PATH)?.len();
+        let segments = pk.save_segmented(&path, size / 3)?;
+        assert!(segments >= 3, "{segments} segments");
+        assert!(std::fs::metadata(dir.join("pk.000"))?.len() <= size / 3);
+        assert_eq!(
+            ProvingKey::load_segmented(&path)?.fingerprint()?,
+            pk.fingerprint()?
+        );
+        assert_eq!(
+            ProvingKey::open_mmap(&path)?.fingerprint()?,
+            pk.fingerprint()?
+        );
+        assert!(pk.save_segmented(&path, 0).is_err());
+
+        let segment = dir.join("pk.001");
+        let mut bytes = std::fs::read(&segment)?;
+        bytes[0] ^= 1;
+        std::fs::write(&segment, &bytes)?;
+        let err = ProvingKey::load_segmented(&path).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+        std::fs::remove_file(&segment)?;
+        assert!(ProvingKey::open_mmap(&path).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: the crate agrees byte for byte with vectors from upstream gnark,
+    /// and a vector that drifts is reported.
+    #[test]
+    fn test_conformance_vectors() -> Result<()> {
+        use rust_gnark::conformance;
+        init()?;
+
+        let generated =
+            std::env::temp_dir().join(format!("rust-gnark-vectors-{}", std::process::id()));
+        let canonical = if cfg!(target_os = "android") {
+            "./conformance".to_owned()
+        } else if conformance::gnark_version(conformance::CANONICAL_DIR).is_ok() {
+            conformance::CANONICAL_DIR.to_owned()
+        } else {
+            // Not generated into the crate: generate them here, with the gnark
+            // of go/go.mod.
+            let status = std::process::Command::new("go")
+                .args(["run", "./cmd/gen_conformance"])
+                .arg(&generated)
+                .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../go"))
+                .status()?;
+            anyhow::ensure!(status.success(), "go run ./cmd/gen_conformance failed");
+            generated.to_string_lossy().into_owned()
+        };
+        let canonical = canonical.as_str();
+        assert!(conformance::gnark_version(canonical)?.starts_with('v'));
+        let mismatches = conformance::check(canonical)?;
+        assert!(mismatches.is_empty(), "{mismatches:#?}");
+
+        let dir =
+            std::env::temp_dir().join(format!("rust-gnark-conformance-{}", std::process::id()));
+        for circuit in conformance::CIRCUITS {
+            std::fs::create_dir_all(dir.join(circuit))?;
+            for entry in std::fs::read_dir(std::path::Path::new(canonical).join(circuit))? {
+                let entry = entry?;
+                std::fs::copy(entry.path(), dir.join(circuit).join(entry.file_name()))?;
+            }
+        }
+        let public = dir.join("cubic").join("public_witness");
+        let mut bytes = std::fs::read(&public)?;
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(&public, &bytes)?;
+        let mismatches = conformance::check(&dir.to_string_lossy())?;
+        assert!(mismatches
+            .iter()
+            .any(|m| m.circuit == "cubic" && m.check == "public witness"));
+        assert!(mismatches.iter().all(|m| m.circuit == "cubic"));
+
+        std::fs::remove_dir_all(&dir)?;
+        if generated.exists() {
+            std::fs::remove_dir_all(&generated)?;
+        }
+        Ok(())
+    }
+
+    /// Test: versioned key files round-trip and reject the wrong kind of key,
+    /// bare gnark files and keys from an incompatible gnark version.
+    #[test]
+    fn test_key_save_load() -> Result<()> {
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-keyfile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        pk.save(&path("c.pk"))?;
+        assert_eq!(
+            ProvingKey::load(&path("c.pk"))?.fingerprint()?,
+            pk.fingerprint()?
+        );
+        assert_eq!(
+            ProvingKey::load_trusted(&path("c.pk"))?.fingerprint()?,
+            pk.fingerprint()?
+        );
+        let vk = VerifyingKey::open(VK_PATH)?;
+        vk.save(&path("c.vk"))?;
+        assert_eq!(
+            VerifyingKey::load(&path("c.vk"))?.fingerprint()?,
+            vk.fingerprint()?
+        );
+
+        let err = ProvingKey::load(&path("c.vk")).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+        let err = VerifyingKey::load(VK_PATH).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::SerializationError(_))
+        ));
+
+        // The same key, as if written by a gnark release from long ago.
+        let saved = std::fs::read(path("c.vk"))?;
+        let n = saved[13] as usize;
+        let mut old = saved[..13].to_vec();
+        old.push(b"v0.1.0".len() as u8);
+        old.extend_from_slice(b"v0.1.0");
+        old.extend_from_slice(&saved[14 + n..]);
+        std::fs::write(path("old.vk"), &old)?;
+        let err = VerifyingKey::load(&path("old.vk")).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+        assert!(err.to_string().contains("v0.1.0"));
+
+        std::fs::write(path("short.vk"), &saved[..saved.len() - 10])?;
+        assert!(VerifyingKey::load(&path("short.vk")).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a seeded setup gives the same keys for the same seed, different
+    /// keys for another, and keys that prove and verify.
+    #[test]
+    fn test_unsafe_setup_with_seed() -> Result<()> {
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let circuit = c.compile()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-seeded-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("c.r1cs"))?;
+        for (name, seed) in [("a", 1), ("b", 1), ("c", 2)] {
+            circuit.unsafe_setup_with_seed(
+                seed,
+                &path(&format!("{name}.pk")),
+                &path(&format!("{name}.vk")),
+            )?;
+        }
+        let read = |name: &str| std::fs::read(path(name));
+        assert_eq!(read("a.pk")?, read("b.pk")?);
+        assert_eq!(read("a.vk")?, read("b.vk")?);
+        assert_ne!(read("a.vk")?, read("c.vk")?);
+
+        let proof = groth16_prove(&path("c.r1cs"), &path("a.pk"), r#"{"X": "3", "Y": "9"}"#)?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("a.vk"), &proof)?);
+        assert!(!groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: artifact headers tell keys and proof bundles apart and report
+    /// their curve, and version 1 key files (without a curve) still load.
+    #[test]
+    fn test_artifact_sniff() -> Result<()> {
+        init()?;
+        use rust_gnark::hash::Curve;
+        use rust_gnark::{
+            Artifact, ArtifactContent, ArtifactHeader, GnarkProofBundle, ProofScheme,
+        };
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-sniff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        ProvingKey::open_mmap(PK_PATH)?.save(&path("c.pk"))?;
+        let vk = VerifyingKey::open(VK_PATH)?;
+        vk.save(&path("c.vk"))?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        GnarkProofBundle::new(proof, &vk)?.write(path("c.bundle"))?;
+
+        for (name, content) in [
+            ("c.pk", ArtifactContent::ProvingKey),
+            ("c.vk", ArtifactContent::VerifyingKey),
+            ("c.bundle", ArtifactContent::Proof),
+        ] {
+            let header = ArtifactHeader::read(&path(name))?;
+            assert_eq!(header.content, content);
+            assert_eq!(header.scheme, ProofScheme::Groth16);
+            assert_eq!(header.curve, Curve::Bn254);
+        }
+        assert!(matches!(
+            Artifact::load(&path("c.pk"))?,
+            Artifact::ProvingKey(_)
+        ));
+        match Artifact::load(&path("c.vk"))? {
+            Artifact::VerifyingKey(key) => assert_eq!(key.fingerprint()?, vk.fingerprint()?),
+            other => panic!("expected a verifying key, got {other:?}"),
+        }
+        let bundle = Artifact::load(&path("c.bundle"))?;
+        assert_eq!(bundle.curve(), Curve::Bn254);
+        assert!(matches!(bundle, Artifact::Proof(_)));
+
+        // Bare gnark keys have no header.
+        let err = ArtifactHeader::read(VK_PATH).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::SerializationError(_))
+        ));
+
+        // The header's curve byte (gnark-crypto's ecc.ID) set to BLS12-381.
+        let mut saved = std::fs::read(path("c.vk"))?;
+        saved[12] = 3;
+        std::fs::write(path("bls.vk"), &saved)?;
+        assert_eq!(
+            ArtifactHeader::read(&path("bls.vk"))?.curve,
+            Curve::Bls12_381
+        );
+        let err = VerifyingKey::load(&path("bls.vk")).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::CurveMismatch(_))
+        ));
+
+        // Version 1: no curve byte.
+        let saved = std::fs::read(path("c.vk"))?;
+        let mut v1 = saved[..12].to_vec();
+        v1[9] = 1;
+        v1.extend_from_slice(&saved[13..]);
+        std::fs::write(path("v1.vk"), &v1)?;
+        assert_eq!(ArtifactHeader::read(&path("v1.vk"))?.curve, Curve::Bn254);
+        assert_eq!(
+            VerifyingKey::load(&path("v1.vk"))?.fingerprint()?,
+            vk.fingerprint()?
+        );
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: migrate turns bare gnark keys into versioned key files, rewrites
+    /// keys and bundles recorded as from an older gnark, leaves current files
+    /// alone and refuses keys it cannot decode.
+    #[test]
+    fn test_migrate() -> Result<()> {
+        init()?;
+        use rust_gnark::{migrate, ArtifactContent, GnarkProofBundle};
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let vk = VerifyingKey::open(VK_PATH)?;
+
+        let report = migrate(VK_PATH, &path("c.vk"))?;
+        assert_eq!(report.content, ArtifactContent::VerifyingKey);
+        assert_eq!(report.from_gnark, None);
+        assert!(report.migrated);
+        assert_eq!(
+            VerifyingKey::load(&path("c.vk"))?.fingerprint()?,
+            vk.fingerprint()?
+        );
+        let report = migrate(PK_PATH, &path("c.pk"))?;
+        assert_eq!(report.content, ArtifactContent::ProvingKey);
+        ProvingKey::load(&path("c.pk"))?;
+
+        let report = migrate(&path("c.vk"), &path("unused.vk"))?;
+        assert!(!report.migrated);
+        assert!(report.from_gnark.is_some());
+        assert!(!std::path::Path::new(&path("unused.vk")).exists());
+
+        // Record an older gnark in the header: load refuses the key, migrate
+        // rewrites it in place.
+        let saved = std::fs::read(path("c.vk"))?;
+        let n = saved[13] as usize;
+        let mut old = saved[..13].to_vec();
+        old.push(7);
+        old.extend_from_slice(b"v0.13.0");
+        old.extend_from_slice(&saved[14 + n..]);
+        std::fs::write(path("old.vk"), &old)?;
+        assert!(VerifyingKey::load(&path("old.vk")).is_err());
+        let report = migrate(&path("old.vk"), &path("old.vk"))?;
+        assert_eq!(report.from_gnark.as_deref(), Some("v0.13.0"));
+        assert!(report.migrated);
+        assert_eq!(
+            VerifyingKey::load(&path("old.vk"))?.fingerprint()?,
+            vk.fingerprint()?
+        );
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let mut bundle = GnarkProofBundle::new(proof, &vk)?;
+        bundle.gnark_version = "v0.13.0".into();
+        bundle.write(path("old.bundle"))?;
+        let report = migrate(&path("old.bundle"), &path("new.bundle"))?;
+        assert_eq!(report.content, ArtifactContent::Proof);
+        assert!(report.migrated);
+        let migrated = GnarkProofBundle::read(path("new.bundle"))?;
+        assert_ne!(migrated.gnark_version, "v0.13.0");
+        assert!(migrated.verify(R1CS_PATH, VK_PATH)?);
+
+        std::fs::write(path("junk.vk"), [0x5a; 200])?;
+        let err = migrate(&path("junk.vk"), &path("junk.out")).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a circuit whose proving key exceeds 4 GiB sets up, round-trips
+    /// and proves without truncation.
+    ///
+    /// Needs tens of GB of memory and disk and a long time, so it only runs
+    /// with `cargo test -- --ignored test_large_circuit`. `RUST_GNARK_LARGE_LOG2`
+    /// sets the number of constraints as a power of two (default 24).
+    #[test]
+    #[ignore]
+    fn test_large_circuit() -> Result<()> {
+        init()?;
+
+        let log2: u32 = std::env::var("RUST_GNARK_LARGE_LOG2")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24);
+        let n = 1u64 << log2;
+        let circuit = CompiledCircuit::compile_from_go(&format!(
+            r#"
+package main
+
+import "github.com/consensys/gnark/frontend"
+
+type Circuit struct {{
+	X frontend.Variable
+	Y frontend.Variable `gnark:",public"`
+}}
+
+func (c *Circuit) Define(api frontend.API) error {{
+	x := c.X
+	for i := 0; i < {n}; i++ {{
+		x = api.Mul(x, c.X)
+	}}
+	api.AssertIsEqual(x, c.Y)
+	return nil
+}}
+"#
+        ))?;
+        assert!(circuit.nb_constraints()? >= n);
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-large-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("large.r1cs"))?;
+        circuit.groth16_setup(&path("large.pk"), &path("large.vk"))?;
+        let pk_len = std::fs::metadata(path("large.pk"))?.len();
+        if log2 >= 24 {
+            assert!(pk_len > u64::from(u32::MAX));
+        }
+
+        let pk = ProvingKey::open_mmap(&path("large.pk"))?;
+        let proof = groth16_prove_with_key(&path("large.r1cs"), &pk, r#"{"X": "1", "Y": "1"}"#)?;
+        assert!(groth16_verify(
+            &path("large.r1cs"),
+            &path("large.vk"),
+            &proof
+        )?);
+
+        pk.save(&path("large.pk.gnrk"))?;
+        assert!(std::fs::metadata(path("large.pk.gnrk"))?.len() > pk_len);
+        assert_eq!(
+            ProvingKey::load_trusted(&path("large.pk.gnrk"))?.fingerprint()?,
+            pk.fingerprint()?
+        );
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: Go memory statistics are reported and free_os_memory collects.
+    #[test]
+    fn test_go_memory_stats() -> Result<()> {
+        use rust_gnark::runtime;
+
+        init()?;
+        groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let before = runtime::read_mem_stats();
+        assert!(before.heap_alloc > 0);
+        assert!(before.heap_in_use > 0 && before.sys >= before.heap_in_use);
+
+        runtime::free_os_memory();
+        let after = runtime::read_mem_stats();
+        assert!(after.num_gc > before.num_gc);
+        assert!(after.heap_released > 0);
+        Ok(())
+    }
+
+    /// Test: re-randomized proofs verify but share no points with the original.
+    #[test]
+    fn test_rerandomize_proof() -> Result<()> {
+        init()?;
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let a = proof.rerandomize(&vk)?;
+        let b = proof.rerandomize(&vk)?;
+        assert_ne!(a.proof, proof.proof);
+        assert_ne!(a.proof, b.proof);
+        assert_eq!(a.public_inputs, proof.public_inputs);
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &a)?);
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &b)?);
+
+        let garbage = rust_gnark::Groth16ProofResult {
+            proof: "zz".into(),
+            public_inputs: proof.public_inputs.clone(),
+        };
+        assert!(garbage.rerandomize(&vk).is_err());
+        Ok(())
+    }
+
+    /// Test: length prefixes claiming more elements than the input holds are
+    /// rejected before anything is allocated.
+    #[test]
+    fn test_oversized_length_prefixes() -> Result<()> {
+        init()?;
+
+        let hex = |b: &[u8]| b.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        let huge = [0xff; 4];
+
+        // The six fixed points of a compressed key, then len(K) = 2^32 - 1.
+        let vk = VerifyingKey::open(VK_PATH)?.to_bytes(SerializationFormat::Compressed)?;
+        let mut bad_vk = vk[..3 * 32 + 3 * 64].to_vec();
+        bad_vk.extend_from_slice(&huge);
+        assert!(VerifyingKey::from_bytes(&bad_vk).is_err());
+        assert!(rust_gnark::native::VerifyingKey::from_gnark_bytes(&bad_vk).is_err());
+
+        // Ar, Bs and Krs, then len(Commitments) = 2^32 - 1.
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let bad = rust_gnark::Groth16ProofResult {
+            proof: format!("{}{}", &proof.proof[..2 * 128], hex(&huge)),
+            public_inputs: proof.public_inputs.clone(),
+        };
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &bad).is_err());
+        assert!(bad.solidity_calldata().is_err());
+        assert!(bad.rerandomize(&VerifyingKey::open(VK_PATH)?).is_err());
+
+        // One public input, no secret ones, a vector of 2^32 - 1 elements.
+        let bad = rust_gnark::Groth16ProofResult {
+            proof: proof.proof.clone(),
+            public_inputs: hex(&[[0, 0, 0, 1], [0; 4], huge].concat()),
+        };
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &bad).is_err());
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+        Ok(())
+    }
+
+    /// Test: keys and proofs round-trip through every serialization format.
+    #[test]
+    fn test_serialization_formats() -> Result<()> {
+        init()?;
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        let raw = pk.to_bytes_with_format(SerializationFormat::Raw)?;
+        let compressed = pk.to_bytes_with_format(SerializationFormat::Compressed)?;
+        assert!(compressed.len() < raw.len());
+        for format in [
+            SerializationFormat::Compressed,
+            SerializationFormat::Raw,
+            SerializationFormat::RawUnchecked,
+        ] {
+            let key = ProvingKey::from_bytes_with_format(&compressed, format)?;
+            assert_eq!(key.fingerprint()?, pk.fingerprint()?);
+        }
+
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let vk_compressed = vk.to_bytes(SerializationFormat::Compressed)?;
+        assert!(vk_compressed.len() < vk.to_bytes(SerializationFormat::Raw)?.len());
+        let reloaded =
+            VerifyingKey::from_bytes_with_format(&vk_compressed, SerializationFormat::Raw)?;
+        assert_eq!(reloaded.fingerprint()?, vk.fingerprint()?);
+
+        let witness_json = r#"{"X": "3", "Y": "35"}"#;
+        let compressed_proof = groth16_prove_with_key(R1CS_PATH, &pk, witness_json)?;
+        let config = ProverConfig::default().with_proof_format(SerializationFormat::Raw);
+        let raw_proof = groth16_prove_with_config(R1CS_PATH, PK_PATH, witness_json, &config)?;
+        assert!(raw_proof.proof.len() > compressed_proof.proof.len());
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &raw_proof)?);
+        Ok(())
+    }
+
+    /// Test: a seeded prover RNG gives byte-identical, valid proofs.
+    #[test]
+    fn test_prove_with_test_rng() -> Result<()> {
+        init()?;
+
+        let witness_json = r#"{"X": "3", "Y": "35"}"#;
+        let prove = |config: &ProverConfig| {
+            groth16_prove_with_config(R1CS_PATH, PK_PATH, witness_json, config)
+        };
+        let seeded = ProverConfig::default().with_test_rng(7);
+        assert_eq!(seeded.test_rng_seed(), Some(7));
+
+        let a = prove(&seeded)?;
+        let b = prove(&seeded)?;
+        assert_eq!(a.proof, b.proof);
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &a)?);
+
+        let other = prove(&ProverConfig::default().with_test_rng(8))?;
+        assert_ne!(a.proof, other.proof);
+        let random = prove(&ProverConfig::default())?;
+        assert_ne!(a.proof, random.proof);
+
+        Ok(())
+    }
+
+    /// Test: the artifact store checksums, lazily loads and removes artifacts.
+    #[test]
+    fn test_artifact_store() -> Result<()> {
+        use rust_gnark::{ArtifactKind, ArtifactStore};
+        use std::path::Path;
+        use std::sync::Arc;
+
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-store-{}", std::process::id()));
+        let store = ArtifactStore::new(&dir);
+        assert!(!store.contains("cubic", "1", ArtifactKind::ProvingKey));
+        assert!(store.proving_key("cubic", "1").is_err());
+
+        for (kind, src) in [
+            (ArtifactKind::ConstraintSystem, R1CS_PATH),
+            (ArtifactKind::ProvingKey, PK_PATH),
+            (ArtifactKind::VerifyingKey, VK_PATH),
+        ] {
+            let checksum = store.insert("cubic", "1", kind, Path::new(src))?;
+            assert_eq!(store.checksum("cubic", "1", kind)?, checksum);
+            assert!(store.contains("cubic", "1", kind));
+        }
+        assert_eq!(
+            store.path("cubic", "1", ArtifactKind::ProvingKey)?,
+            dir.join("v1").join("cubic").join("1").join("circuit.pk")
+        );
+
+        let pk = store.proving_key("cubic", "1")?;
+        assert!(Arc::ptr_eq(&pk, &store.proving_key("cubic", "1")?));
+        assert_eq!(
+            pk.fingerprint()?,
+            ProvingKey::open_mmap(PK_PATH)?.fingerprint()?
+        );
+        let vk = store.verifying_key("cubic", "1")?;
+        assert_eq!(
+            vk.fingerprint()?,
+            VerifyingKey::open(VK_PATH)?.fingerprint()?
+        );
+        let r1cs = store.constraint_system_path("cubic", "1")?;
+        let proof = groth16_prove_with_key(&r1cs, &pk, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(&r1cs, VK_PATH, &proof)?);
+
+        // A corrupted file fails its checksum instead of loading.
+        let vk_path = store.path("cubic", "1", ArtifactKind::VerifyingKey)?;
+        let mut bytes = std::fs::read(&vk_path)?;
+        bytes.truncate(bytes.len() / 2);
+        std::fs::write(&vk_path, bytes)?;
+        assert!(store
+            .verify("cubic", "1", ArtifactKind::VerifyingKey)
+            .is_err());
+        assert!(store.verifying_key("cubic", "2").is_err());
+
+        assert!(store
+            .path("../cubic", "1", ArtifactKind::ProvingKey)
+            .is_err());
+        assert!(store.path("cubic", "..", ArtifactKind::ProvingKey).is_err());
+        assert!(ArtifactStore::default_root("com.example.app")?.ends_with("rust-gnark"));
+
+        store.remove("cubic", "1")?;
+        assert!(!store.contains("cubic", "1", ArtifactKind::ProvingKey));
+        assert!(store.proving_key("cubic", "1").is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: the calibration is measured once, saved in the store and proves.
+    #[test]
+    fn test_store_calibration() -> Result<()> {
+        use rust_gnark::ArtifactStore;
+
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-calib-{}", std::process::id()));
+        let store = ArtifactStore::new(&dir);
+        let calibration = store.calibration()?;
+        let cpus = std::thread::available_parallelism().map_or(1, usize::from);
+        assert!((1..=cpus).contains(&calibration.threads()));
+        assert!(!calibration.msm_time().is_zero());
+        assert!(dir.join("calibration").exists());
+        assert_eq!(ArtifactStore::new(&dir).calibration()?, calibration);
+
+        let config = calibration.apply(ProverConfig::default());
+        assert_eq!(config.threads(), Some(calibration.threads()));
+        let proof =
+            groth16_prove_with_config(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#, &config)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        std::fs::write(dir.join("calibration"), "threads=0\n")?;
+        assert!(store.calibration()?.threads() >= 1);
+        store.clear_calibration()?;
+        assert!(!dir.join("calibration").exists());
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a circuit's estimate gives its proofs' exact size, commitments
+    /// included, and a memory and time that grow with the circuit.
+    #[test]
+    fn test_circuit_estimate() -> Result<()> {
+        init()?;
+        let calibration = rust_gnark::calibrate()?;
+
+        let cubic = CompiledCircuit::read(R1CS_PATH)?;
+        let estimate = cubic.estimate(&calibration)?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        assert_eq!(estimate.proof_bytes, proof.proof.len() as u64 / 2);
+        assert!(estimate.peak_memory > std::fs::metadata(PK_PATH)?.len() / 2);
+        assert!(estimate.fits_in(estimate.peak_memory));
+        assert!(!estimate.fits_in(estimate.peak_memory - 1));
+
+        // X^2 == Y, plus a constraint using a commitment to X and Y, and a
+        // chain of multiplications to make it larger.
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let x = c.secret_input("X");
+        let x2 = c.mul(x, x);
+        c.assert_eq(x2, y);
+        let challenge = c.commit(&[x, y]);
+        let mut acc = c.mul(challenge, x);
+        for _ in 0..1000 {
+            acc = c.mul(acc, x);
+        }
+        let larger = c.compile()?.estimate(&calibration)?;
+        assert_eq!(larger.proof_bytes, estimate.proof_bytes + 32);
+        assert!(larger.peak_memory > estimate.peak_memory);
+        assert!(larger.prove_time > estimate.prove_time);
+        Ok(())
+    }
+
+    /// Test: artifacts download in ranged chunks, resume after a dropped
+    /// connection and are checked against their fingerprint.
+    #[test]
+    fn test_artifact_store_download() -> Result<()> {
+        use rust_gnark::{ArtifactKind, ArtifactStore, Downloader};
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        init()?;
+
+        // A minimal HTTP server for one file, honoring `Range: bytes=a-b`. It
+        // cuts the first response short to force a resume.
+        let body = std::fs::read(PK_PATH)?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/circuit.pk", listener.local_addr()?);
+        let server_body = body.clone();
+        std::thread::spawn(move || {
+            for (i, stream) in listener.incoming().enumerate() {
+                let Ok(mut stream) = stream else { return };
+                let mut range = None;
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(r) = line.trim().strip_prefix("Range: bytes=") {
+                        let (a, b) = r.split_once('-').unwrap();
+                        range = Some((a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap()));
+                    }
+                }
+                let (a, b) = range.unwrap();
+                let b = b.min(server_body.len() - 1);
+                let chunk = &server_body[a..=b];
+                let sent = if i == 0 {
+                    &chunk[..chunk.len() / 2]
+                } else {
+                    chunk
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\n\
+                     Content-Range: bytes {a}-{b}/{}\r\nConnection: close\r\n\r\n",
+                    chunk.len(),
+                    server_body.len()
+                );
+                let _ = stream.write_all(sent);
+            }
+        });
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-download-{}", std::process::id()));
+        let store = ArtifactStore::new(&dir);
+        let fingerprint = ProvingKey::open_mmap(PK_PATH)?.fingerprint()?;
+        let progress = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let seen = progress.clone();
+        let downloader = Downloader::default()
+            .with_chunk_size(4096)
+            .with_progress(move |done, _| seen.lock().unwrap().push(done));
+
+        store.download(
+            &downloader,
+            "cubic",
+            "1",
+            ArtifactKind::ProvingKey,
+            &url,
+            &fingerprint,
+        )?;
+        assert_eq!(
+            store.checksum("cubic", "1", ArtifactKind::ProvingKey)?,
+            fingerprint
+        );
+        assert_eq!(
+            std::fs::read(store.path("cubic", "1", ArtifactKind::ProvingKey)?)?,
+            body
+        );
+        assert_eq!(progress.lock().unwrap().last(), Some(&(body.len() as u64)));
+        assert_eq!(store.proving_key("cubic", "1")?.fingerprint()?, fingerprint);
+
+        let wrong = KeyFingerprint([0; 32]);
+        assert!(store
+            .download(
+                &downloader,
+                "cubic",
+                "0",
+                ArtifactKind::ProvingKey,
+                &url,
+                &wrong
+            )
+            .is_err());
+        assert!(!store.contains("cubic", "0", ArtifactKind::ProvingKey));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a remote prover proves through the same trait as a session,
+    /// authenticates, maps service errors and rejects proofs that do not
+    /// verify.
+    #[test]
+    fn test_remote_prover() -> Result<()> {
+        use rust_gnark::{GnarkProofBundle, Prover, RemoteProver};
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        init()?;
+
+        // A minimal proving service: `/cubic` proves, `/tampered` answers
+        // with another statement's proof under the requested public inputs.
+        let session = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let base = format!("http://{}", listener.local_addr()?);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { return };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let (mut length, mut authorized) = (0, false);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(n) = line.trim().strip_prefix("Content-Length: ") {
+                        length = n.parse().unwrap();
+                    }
+                    authorized |= line.trim() == "Authorization: Bearer secret";
+                }
+                let mut witness = vec![0; length];
+                reader.read_exact(&mut witness).unwrap();
+                let witness = String::from_utf8(witness).unwrap();
+                let (status, body) = if !authorized {
+                    ("401 Unauthorized", b"no token".to_vec())
+                } else {
+                    match session.prove(&witness) {
+                        Ok(mut proof) => {
+                            if request_line.contains("/tampered") {
+                                proof.proof =
+                                    session.prove(r#"{"X": "4", "Y": "73"}"#).unwrap().proof;
+                            }
+                            (
+                                "200 OK",
+                                GnarkProofBundle::new(proof, &vk).unwrap().to_bytes(),
+                            )
+                        }
+                        Err(err) => ("422 Unprocessable Entity", format!("{err:#}").into_bytes()),
+                    }
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(&body);
+            }
+        });
+
+        fn prove_and_verify(prover: &dyn Prover) -> Result<bool> {
+            groth16_verify(
+                R1CS_PATH,
+                VK_PATH,
+                &prover.prove(r#"{"X": "3", "Y": "35"}"#)?,
+            )
+        }
+        let remote = |path: &str| {
+            RemoteProver::new(format!("{base}{path}")).with_header("Authorization", "Bearer secret")
+        };
+
+        let prover = remote("/cubic")
+            .with_verifying_key(R1CS_PATH, VK_PATH)?
+            .with_rerandomization(true);
+        assert!(prove_and_verify(&prover)?);
+        assert!(prove_and_verify(&ProverSession::new(R1CS_PATH, PK_PATH)?)?);
+
+        let err = prover
+            .prove(r#"{"X": "3", "Y": "36"}"#)
+            .expect_err("an unsatisfied witness must fail remotely too");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::WitnessSolveFailed { .. })
+        ));
+
+        let err = RemoteProver::new(format!("{base}/cubic"))
+            .prove(r#"{"X": "3", "Y": "35"}"#)
+            .expect_err("the service must refuse requests without the token");
+        assert!(format!("{err:#}").contains("HTTP 401"));
+
+        let err = remote("/tampered")
+            .with_verifying_key(R1CS_PATH, VK_PATH)?
+            .prove(r#"{"X": "3", "Y": "35"}"#)
+            .expect_err("a proof that does not verify must be rejected");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidProof(_))
+        ));
+        // Unchecked, the same answer goes through.
+        assert!(!prove_and_verify(&remote("/tampered"))?);
+
+        let err = remote("/cubic")
+            .with_rerandomization(true)
+            .prove(r#"{"X": "3", "Y": "35"}"#)
+            .expect_err("re-randomizing needs the verifying key");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidInput(_))
+        ));
+        Ok(())
+    }
+
+    /// Test: an `EthVerifier` sends the proof's `verifyProof` calldata with
+    /// `eth_call`, reports reverts as invalid and refuses addresses without a
+    /// contract.
+    #[test]
+    fn test_eth_verifier() -> Result<()> {
+        use rust_gnark::EthVerifier;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        init()?;
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let calldata = proof.solidity_verify_call()?;
+        // Selector, 8 proof words and the public input Y.
+        assert_eq!(calldata.len(), 4 + 9 * 32);
+        let expected: String = calldata.iter().map(|b| format!("{b:02x}")).collect();
+        let contract = "0x00000000000000000000000000000000000000aa";
+
+        // A minimal node: the verifier at `contract` accepts exactly the
+        // calldata of `proof`; every other address has no code.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { return };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(n) = line.trim().strip_prefix("Content-Length: ") {
+                        length = n.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                let params = &request["params"];
+                let answer = match request["method"].as_str().unwrap() {
+                    "eth_getCode" if params[0] == contract => {
+                        serde_json::json!({ "result": "0x6080" })
+                    }
+                    "eth_getCode" => serde_json::json!({ "result": "0x" }),
+                    "eth_call" if params[0]["data"] == format!("0x{expected}") => {
+                        serde_json::json!({ "result": "0x" })
+                    }
+                    _ => serde_json::json!({
+                        "error": { "code": 3, "message": "execution reverted", "data": "0x7fcdd1f4" }
+                    }),
+                };
+                let body = answer.to_string();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        let verifier = EthVerifier::new(url.as_str(), contract)?;
+        let result = verifier.verify(&proof)?;
+        assert!(result.valid);
+        assert_eq!(result.block, "latest");
+
+        let other = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "4", "Y": "73"}"#)?;
+        let swapped = rust_gnark::Groth16ProofResult {
+            proof: proof.proof.clone(),
+            public_inputs: other.public_inputs,
+        };
+        let result = verifier.verify(&swapped)?;
+        assert!(!result.valid);
+        assert_eq!(result.revert_reason.as_deref(), Some("execution reverted"));
+        assert_eq!(result.revert_data, [0x7f, 0xcd, 0xd1, 0xf4]);
+
+        let err = EthVerifier::new(url.as_str(), "0x00000000000000000000000000000000000000bb")?
+            .verify(&proof)
+            .expect_err("an address without code must be refused");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidInput(_))
+        ));
+        assert!(EthVerifier::new(url.as_str(), "0x1234").is_err());
+        Ok(())
+    }
+
+    /// Test: code generic over `ProofSystem` proves and verifies with Groth16
+    /// and PLONK alike.
+    #[test]
+    fn test_proof_system() -> Result<()> {
+        use rust_gnark::{Groth16, Plonk, ProofSystem, Srs};
+
+        fn roundtrip<S: ProofSystem>(system: &S, witness_json: &str) -> Result<bool> {
+            let proof = system.prove(witness_json)?;
+            system.verify(&proof)
+        }
+
+        init()?;
+
+        let groth16 = Groth16::new(R1CS_PATH, PK_PATH, VK_PATH)?;
+        assert!(roundtrip(&groth16, r#"{"X": "3", "Y": "35"}"#)?);
+        assert!(roundtrip(&groth16, r#"{"X": "3", "Y": "36"}"#).is_err());
+
+        // X * X == Y
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let circuit = c.compile_sparse()?;
+        let srs = Srs::generate_insecure(circuit.srs_size_needed()?)?;
+        let (pk, vk) = circuit.plonk_setup(&srs)?;
+        let plonk = Plonk::new(circuit, pk, vk);
+        assert!(roundtrip(&plonk, r#"{"X": "3", "Y": "9"}"#)?);
+        Ok(())
+    }
+
+    /// Test: proof bundles round-trip and reject proofs for another circuit.
+    #[test]
+    fn test_proof_bundle() -> Result<()> {
+        use rust_gnark::{GnarkProofBundle, ProofScheme};
+
+        init()?;
+
+        let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let bundle = GnarkProofBundle::new(proof.clone(), &vk)?;
+        assert_eq!(bundle.scheme, ProofScheme::Groth16);
+        assert_eq!(bundle.circuit, vk.fingerprint()?);
+
+        let path = std::env::temp_dir().join(format!("rust-gnark-bundle-{}", std::process::id()));
+        bundle.write(&path)?;
+        let read = GnarkProofBundle::read(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(read.proof.proof, proof.proof);
+        assert_eq!(read.proof.public_inputs, proof.public_inputs);
+        assert_eq!(read.circuit, bundle.circuit);
+        read.check(&vk)?;
+        assert!(read.verify(R1CS_PATH, VK_PATH)?);
+
+        let mut other = read.clone();
+        other.circuit = KeyFingerprint([7; 32]);
+        let err = other.verify(R1CS_PATH, VK_PATH).unwrap_err();
+        assert!(format!("{err:#}").contains("circuit"), "{err:#}");
+
+        let text = String::from_utf8(bundle.to_bytes())?;
+        assert!(text.starts_with("gnark-proof-bundle v1\n"));
+        assert!(GnarkProofBundle::from_bytes(text.replace(" v1", " v2").as_bytes()).is_err());
+        assert!(GnarkProofBundle::from_bytes(text.replace("bn254", "bn256").as_bytes()).is_err());
+        assert!(GnarkProofBundle::from_bytes(b"proof: 00").is_err());
+        Ok(())
+    }
+
+    /// Test: a prover session proves repeatedly, concurrently and with options.
+    #[test]
+    fn test_prover_session() -> Result<()> {
+        init()?;
+
+        let session = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let proof = session.prove(r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (1..=4u64)
+                .map(|x| {
+                    let session = &session;
+                    scope.spawn(move || {
+                        let y = x * x * x + x + 5;
+                        session.prove(&format!(r#"{{"X": "{x}", "Y": "{y}"}}"#))
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let proof = handle.join().expect("prover thread panicked")?;
+                assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+            }
+            anyhow::Ok(())
+        })?;
+
+        let config = ProverConfig::default().with_threads(1);
+        let proof = session.prove_with_config(r#"{"X": "3", "Y": "35"}"#, &config)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let err = session
+            .prove(r#"{"X": "3", "Y": "36"}"#)
+            .expect_err("unsatisfied witness must fail");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::WitnessSolveFailed { .. })
+        ));
+
+        assert!(ProverSession::new(R1CS_PATH, "./does-not-exist.pk").is_err());
+        Ok(())
+    }
+
+    /// Test: a batch proves every witness in order, and a failing witness is
+    /// reported by index.
+    #[test]
+    fn test_prove_many() -> Result<()> {
+        init()?;
+
+        let session = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let witnesses: Vec<String> = (1..=6u64)
+            .map(|x| format!(r#"{{"X": "{x}", "Y": "{}"}}"#, x * x * x + x + 5))
+            .collect();
+        let proofs = session.prove_many(&witnesses)?;
+        assert_eq!(proofs.len(), witnesses.len());
+        for (witness, proof) in witnesses.iter().zip(&proofs) {
+            assert!(groth16_verify(R1CS_PATH, VK_PATH, proof)?);
+            assert_eq!(proof.public_inputs, session.prove(witness)?.public_inputs);
+        }
+        assert!(session.prove_many(&[] as &[&str])?.is_empty());
+
+        let config = ProverConfig::default().with_threads(2);
+        let err = session
+            .prove_many_with_config(
+                &[r#"{"X": "3", "Y": "35"}"#, r#"{"X": "3", "Y": "36"}"#],
+                &config,
+            )
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("witness 1"));
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::WitnessSolveFailed { .. })
+        ));
+        Ok(())
+    }
+
+    /// Test: swapping a session's proving key takes effect for later proofs,
+    /// proofs racing the swap use one key or the other, and ill-sized keys are
+    /// refused.
+    #[test]
+    fn test_prover_session_swap_key() -> Result<()> {
+        init()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-swap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        CompiledCircuit::read(R1CS_PATH)?.unsafe_setup_with_seed(
+            7,
+            &path("rotated.pk"),
+            &path("rotated.vk"),
+        )?;
+
+        let session = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let old_key = session.proving_key()?;
+        assert_eq!(
+            old_key.fingerprint()?,
+            ProvingKey::open_mmap(PK_PATH)?.fingerprint()?
+        );
+
+        std::thread::scope(|scope| {
+            let provers: Vec<_> = (1..=4u64)
+                .map(|x| {
+                    let session = &session;
+                    scope.spawn(move || {
+                        let y = x * x * x + x + 5;
+                        session.prove(&format!(r#"{{"X": "{x}", "Y": "{y}"}}"#))
+                    })
+                })
+                .collect();
+            session.swap_key(&ProvingKey::open_mmap(&path("rotated.pk"))?)?;
+            for prover in provers {
+                let proof = prover.join().expect("prover thread panicked")?;
+                assert!(
+                    groth16_verify(R1CS_PATH, VK_PATH, &proof)?
+                        || groth16_verify(R1CS_PATH, &path("rotated.vk"), &proof)?
+                );
+            }
+            anyhow::Ok(())
+        })?;
+
+        let proof = session.prove(r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, &path("rotated.vk"), &proof)?);
+        assert!(!groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+        session.check_verifying_key(&VerifyingKey::open(&path("rotated.vk"))?)?;
+        assert!(session
+            .check_verifying_key(&VerifyingKey::open(VK_PATH)?)
+            .is_err());
+
+        // The old key outlives the swap through its handle.
+        let proof = groth16_prove_with_key(R1CS_PATH, &old_key, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+        session.swap_key(&old_key)?;
+        drop(old_key);
+        let proof = session.prove(r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        c.compile()?
+            .unsafe_setup_with_seed(1, &path("small.pk"), &path("small.vk"))?;
+        let err = session
+            .swap_key(&ProvingKey::open_mmap(&path("small.pk"))?)
+            .expect_err("a key for another circuit size must be refused");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+        let proof = session.prove(r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a witness solved ahead proves after a restart, replaying its
+    /// hint outputs (the hint is gone by then), and is bound to its circuit.
+    #[test]
+    fn test_prove_from_solved() -> Result<()> {
+        use rust_gnark::{Fr, SolvedWitness};
+
+        init()?;
+
+        let mut c = CircuitBuilder::new();
+        let x = c.public_input("X");
+        let half = c.hint(
+            Hint::Rust {
+                id: 56903,
+                outputs: 1,
+            },
+            &[x],
+        )[0];
+        let doubled = c.add(half, half);
+        c.assert_eq(doubled, x);
+        let circuit = c.compile()?;
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-solved-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("half.r1cs"))?;
+        circuit.unsafe_setup_with_seed(3, &path("half.pk"), &path("half.vk"))?;
+        let session = ProverSession::new(&path("half.r1cs"), &path("half.pk"))?;
+
+        rust_gnark::register_hint(56903, |inputs: &[Fr], outputs: &mut [Fr]| {
+            outputs[0] = inputs[0] * Fr::from(2).inverse().expect("2 is invertible");
+            Ok(())
+        });
+        session
+            .solve_witness(r#"{"X": "6"}"#)?
+            .write(path("half.solved"))?;
+        assert!(rust_gnark::unregister_hint(56903));
+
+        let solved = SolvedWitness::read(path("half.solved"))?;
+        let proof = session.prove_from_solved(&solved)?;
+        assert!(groth16_verify(
+            &path("half.r1cs"),
+            &path("half.vk"),
+            &proof
+        )?);
+        assert!(session.prove(r#"{"X": "6"}"#).is_err());
+
+        let truncated =
+            SolvedWitness::from_bytes(solved.as_bytes()[..solved.as_bytes().len() - 1].to_vec());
+        assert!(session.prove_from_solved(&truncated).is_err());
+        let other = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let err = other
+            .prove_from_solved(&solved)
+            .expect_err("a witness solved for another circuit must be refused");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidWitness(_))
+        ));
+        let err = other
+            .solve_witness(r#"{"X": "3", "Y": "36"}"#)
+            .expect_err("an unsatisfied witness must not solve");
+        assert!(
+            matches!(
+                err.downcast_ref::<GnarkError>(),
+                Some(GnarkError::WitnessSolveFailed {
+                    constraint_index: Some(_),
+                    ..
+                })
+            ),
+            "unexpected error: {err:#}"
+        );
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a witness solved from the circuit alone proves with a separately
+    /// loaded key, which must be sized for the circuit.
+    #[test]
+    fn test_prove_with_full_witness() -> Result<()> {
+        use rust_gnark::{groth16_prove_with_full_witness, SolvedWitness};
+
+        init()?;
+
+        let circuit = CompiledCircuit::read(R1CS_PATH)?;
+        let witness = Witness::from_assignments(&circuit.abi()?, [("X", "3"), ("Y", "35")])?;
+        let config = ProverConfig::default();
+        let bytes = circuit
+            .solve_witness(&witness, &config)?
+            .as_bytes()
+            .to_vec();
+
+        let solved = SolvedWitness::from_bytes(bytes);
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        let proof = groth16_prove_with_full_witness(&circuit, &pk, &solved, &config)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let bad = Witness::from_assignments(&circuit.abi()?, [("X", "3"), ("Y", "36")])?;
+        assert!(circuit.solve_witness(&bad, &config).is_err());
+
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let small = c.compile()?;
+        let dir = std::env::temp_dir().join(format!("rust-gnark-full-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        small.unsafe_setup_with_seed(1, &path("small.pk"), &path("small.vk"))?;
+        let err = groth16_prove_with_full_witness(
+            &circuit,
+            &ProvingKey::open_mmap(&path("small.pk"))?,
+            &solved,
+            &config,
+        )
+        .expect_err("a key for another circuit size must be refused");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a circuit registry proves by handle, and rejects keys from
+    /// another setup, duplicate names and stale handles.
+    #[test]
+    fn test_circuit_registry() -> Result<()> {
+        init()?;
+        use rust_gnark::CircuitRegistry;
+
+        let mut c = CircuitBuilder::new();
+        let x = c.secret_input("X");
+        let y = c.public_input("Y");
+        let xx = c.mul(x, x);
+        c.assert_eq(xx, y);
+        let circuit = c.compile()?;
+        let dir = std::env::temp_dir().join(format!("rust-gnark-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        circuit.write(&path("square.r1cs"))?;
+        circuit.unsafe_setup_with_seed(1, &path("a.pk"), &path("a.vk"))?;
+        circuit.unsafe_setup_with_seed(2, &path("b.pk"), &path("b.vk"))?;
+
+        let registry = CircuitRegistry::new();
+        let cubic = registry.register_circuit("cubic", R1CS_PATH, PK_PATH, VK_PATH)?;
+        let square = registry.register_circuit(
+            "square",
+            &path("square.r1cs"),
+            &path("a.pk"),
+            &path("a.vk"),
+        )?;
+        assert_ne!(cubic, square);
+        assert_eq!(registry.handle("square"), Some(square));
+        assert_eq!(registry.names(), ["cubic", "square"]);
+
+        let proof = registry.prove(cubic, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(registry.verify(cubic, &proof)?);
+        let proof = registry.prove(square, r#"{"X": "3", "Y": "9"}"#)?;
+        assert!(registry.verify(square, &proof)?);
+        assert!(!registry.verify(cubic, &proof).unwrap_or(false));
+
+        let err = registry
+            .prove(square, r#"{"X": "3", "Y": "10"}"#)
+            .expect_err("unsatisfied witness must fail");
+        assert!(format!("{err:#}").contains("\"square\""));
+
+        for (pk, vk) in [("a.pk", "b.vk"), ("b.pk", "a.vk")] {
+            let err = registry
+                .register_circuit("mixed", &path("square.r1cs"), &path(pk), &path(vk))
+                .expect_err("keys from different setups must be rejected");
+            assert!(matches!(
+                err.downcast_ref::<GnarkError>(),
+                Some(GnarkError::InvalidKey(_))
+            ));
+        }
+        let err = registry
+            .register_circuit("mixed", &path("square.r1cs"), PK_PATH, VK_PATH)
+            .expect_err("keys for another circuit must be rejected");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::InvalidKey(_))
+        ));
+        assert!(registry
+            .register_circuit("cubic", R1CS_PATH, PK_PATH, VK_PATH)
+            .is_err());
+
+        assert!(registry.unregister(cubic));
+        assert!(!registry.unregister(cubic));
+        assert!(registry.prove(cubic, r#"{"X": "3", "Y": "35"}"#).is_err());
+        assert_eq!(registry.handle("cubic"), None);
+        let again = registry.register_circuit("cubic", R1CS_PATH, PK_PATH, VK_PATH)?;
+        assert_ne!(again, cubic);
+        assert!(CircuitRegistry::new()
+            .prove(again, r#"{"X": "3", "Y": "35"}"#)
+            .is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: feed values come from the feeder while solving, and a failing or
+    /// missing feeder fails the proof.
+    #[test]
+    fn test_witness_feeder() -> Result<()> {
+        init()?;
+
+        // Y == a * b + c for fed values (a, b, c).
+        let mut c = CircuitBuilder::new();
+        let y = c.public_input("Y");
+        let fed = c.feed(7, 3);
+        let ab = c.mul(fed[0], fed[1]);
+        let sum = c.add(ab, fed[2]);
+        c.assert_eq(sum, y);
+        assert!(CircuitBuilder::new().feed(1, 0).is_empty());
+        let mut empty = CircuitBuilder::new();
+        empty.feed(1, 0);
+        assert!(empty.to_description().is_err());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-feed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let circuit = c.compile()?;
+        circuit.write(&path("c.r1cs"))?;
+        circuit.groth16_setup(&path("c.pk"), &path("c.vk"))?;
+
+        let calls = Mutex::new(Vec::new());
+        let feeder = |id: u64, out: &mut [rust_gnark::Fr]| -> Result<()> {
+            calls.lock().unwrap().push((id, out.len()));
+            for (v, x) in out.iter_mut().zip([2u64, 3, 4]) {
+                *v = x.into();
+            }
+            Ok(())
+        };
+        let proof = rust_gnark::groth16_prove_with_feeder(
+            &path("c.r1cs"),
+            &path("c.pk"),
+            r#"{"Y": "10"}"#,
+            &feeder,
+        )?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+        assert_eq!(calls.lock().unwrap().as_slice(), &[(7, 3)]);
+
+        let session = ProverSession::new(&path("c.r1cs"), &path("c.pk"))?;
+        let proof = session.prove_with_feeder(r#"{"Y": "10"}"#, &feeder)?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+        assert!(session
+            .prove_with_feeder(r#"{"Y": "11"}"#, &feeder)
+            .is_err());
+
+        let failing =
+            |id: u64, _: &mut [rust_gnark::Fr]| -> Result<()> { anyhow::bail!("no data for {id}") };
+        let err = session
+            .prove_with_feeder(r#"{"Y": "10"}"#, &failing)
+            .expect_err("a failing feeder must fail the proof");
+        assert!(format!("{err:#}").contains("no data for 7"));
+
+        assert!(session.prove(r#"{"Y": "10"}"#).is_err());
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: a Rust hint computes values while solving, and a failing or
+    /// unregistered hint fails the proof with its message.
+    #[test]
+    fn test_rust_hint() -> Result<()> {
+        init()?;
+        use rust_gnark::{Fr, Hint};
+
+        // X == 2 * half, with half = X / 2 computed by a Rust hint.
+        let halve = |id: u64| {
+            let mut c = CircuitBuilder::new();
+            let x = c.public_input("X");
+            let half = c.hint(Hint::Rust { id, outputs: 1 }, &[x])[0];
+            let doubled = c.add(half, half);
+            c.assert_eq(doubled, x);
+            c
+        };
+        let mut no_outputs = CircuitBuilder::new();
+        let x = no_outputs.public_input("X");
+        no_outputs.hint(Hint::Rust { id: 1, outputs: 0 }, &[x]);
+        assert!(no_outputs.to_description().is_err());
+
+        let dir = std::env::temp_dir().join(format!("rust-gnark-hint-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let circuit = halve(56901).compile()?;
+        circuit.write(&path("c.r1cs"))?;
+        circuit.groth16_setup(&path("c.pk"), &path("c.vk"))?;
+
+        rust_gnark::register_hint(56901, |inputs: &[Fr], outputs: &mut [Fr]| {
+            outputs[0] = inputs[0] * Fr::from(2).inverse().expect("2 is invertible");
+            Ok(())
+        });
+        // Odd, so the hint has to halve in the field.
+        let proof = groth16_prove(&path("c.r1cs"), &path("c.pk"), r#"{"X": "5"}"#)?;
+        assert!(groth16_verify(&path("c.r1cs"), &path("c.vk"), &proof)?);
+
+        rust_gnark::register_hint(56901, |_: &[Fr], _: &mut [Fr]| {
+            anyhow::bail!("halving is closed today")
+        });
+        let err = groth16_prove(&path("c.r1cs"), &path("c.pk"), r#"{"X": "5"}"#)
+            .expect_err("a failing hint must fail the proof");
+        assert!(format!("{err:#}").contains("halving is closed today"));
+
+        assert!(rust_gnark::unregister_hint(56901));
+        assert!(!rust_gnark::unregister_hint(56901));
+        let err = groth16_prove(&path("c.r1cs"), &path("c.pk"), r#"{"X": "5"}"#)
+            .expect_err("an unregistered hint must fail the proof");
+        assert!(format!("{err:#}").contains("56901"));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// Test: secret witnesses prove through both entry points and reject NUL
+    /// bytes; scrubbing is also available through the prover config.
+    #[test]
+    fn test_secret_witness() -> Result<()> {
+        init()?;
+
+        let witness = rust_gnark::SecretWitness::new(r#"{"X": "3", "Y": "35"}"#.to_owned())?;
+        assert!(!format!("{witness:?}").contains("35"));
+        let proof = rust_gnark::groth16_prove_secret(R1CS_PATH, PK_PATH, &witness)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let session = ProverSession::new(R1CS_PATH, PK_PATH)?;
+        let proof = session.prove_secret(&witness)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+        // The witness is only read, so it proves again.
+        assert!(groth16_verify(
+            R1CS_PATH,
+            VK_PATH,
+            &session.prove_secret(&witness)?
+        )?);
+
+        let config = ProverConfig::default().with_witness_scrubbing(true);
+        assert!(config.witness_scrubbing());
+        let proof = session.prove_with_config(r#"{"X": "3", "Y": "35"}"#, &config)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+
+        let bad = rust_gnark::SecretWitness::new(r#"{"X": "3", "Y": "36"}"#.to_owned())?;
+        assert!(session.prove_secret(&bad).is_err());
+        assert!(rust_gnark::SecretWitness::new("{\0}".to_owned()).is_err());
+        Ok(())
+    }
+
+    /// Test: a prove pool proves queued jobs, pushes back when full and
+    /// cancels queued jobs.
+    #[test]
+    fn test_prove_pool() -> Result<()> {
+        init()?;
+
+        let session = std::sync::Arc::new(ProverSession::new(R1CS_PATH, PK_PATH)?);
+        let pool = ProvePool::new(2, 4)?;
+        assert_eq!(pool.workers(), 2);
+        let tasks: Vec<_> = (1..=8u64)
+            .map(|x| {
+                let y = x * x * x + x + 5;
+                pool.submit(&session, &format!(r#"{{"X": "{x}", "Y": "{y}"}}"#))
+            })
+            .collect();
+        for task in tasks {
+            assert!(groth16_verify(R1CS_PATH, VK_PATH, &task.wait()?)?);
+        }
+
+        let err = pool
+            .submit(&session, r#"{"X": "3", "Y": "36"}"#)
+            .wait()
+            .expect_err("unsatisfied witness must fail");
+        assert!(matches!(
+            err.downcast_ref::<GnarkError>(),
+            Some(GnarkError::WitnessSolveFailed { .. })
+        ));
+
+        // One worker and no queue: a second job is only accepted once the
+        // worker is free again, and cancelling it then resolves it to
+        // Cancelled unless it already finished.
+        let pool = ProvePool::new(1, 0)?;
+        let first = pool.submit(&session, r#"{"X": "3", "Y": "35"}"#);
+        if let Some(second) = pool.try_submit(&session, r#"{"X": "3", "Y": "35"}"#) {
+            second.cancel();
+            match second.wait() {
+                Ok(proof) => assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?),
+                Err(e) => assert_eq!(e.downcast_ref::<GnarkError>(), Some(&GnarkError::Cancelled)),
+            }
+        }
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &first.wait()?)?);
+        Ok(())
+    }
+
+    /// Test: one proving and verifying key shared by threads that prove,
+    /// serialize and fingerprint concurrently, some with per-call runtime limits.
+    #[test]
+    fn test_shared_handles_across_threads() -> Result<()> {
+        init()?;
+
+        let pk = ProvingKey::open_mmap(PK_PATH)?;
+        let vk = VerifyingKey::open(VK_PATH)?;
+        let pk_fingerprint = pk.fingerprint()?;
+        let vk_fingerprint = vk.fingerprint()?;
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (1..=6u64)
+                .map(|x| {
+                    let (pk, vk) = (&pk, &vk);
+                    scope.spawn(move || {
+                        let y = x * x * x + x + 5;
+                        let witness = format!(r#"{{"X": "{x}", "Y": "{y}"}}"#);
+                        let proof = if x % 2 == 0 {
+                            let config = ProverConfig::default().with_threads(x as usize);
+                            groth16_prove_with_config(R1CS_PATH, PK_PATH, &witness, &config)?
+                        } else {
+                            groth16_prove_with_key(R1CS_PATH, pk, &witness)?
+                        };
+                        anyhow::Ok((proof, pk.fingerprint()?, vk.fingerprint()?))
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let (proof, pk_fp, vk_fp) = handle.join().expect("worker thread panicked")?;
+                assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+                assert_eq!(pk_fp, pk_fingerprint);
+                assert_eq!(vk_fp, vk_fingerprint);
+            }
+            anyhow::Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Test: the UniFFI exports prove, report progress and verify.
+    #[test]
+    fn test_uniffi_exports() -> Result<()> {
+        use rust_gnark::mobile::{self, ProgressListener, Prover};
+        use std::sync::Arc;
+
+        struct Collect(Mutex<Vec<Progress>>);
+        impl ProgressListener for Collect {
+            fn on_progress(&self, progress: Progress) {
+                self.0.lock().unwrap().push(progress);
+            }
+        }
+
+        mobile::initialize()?;
+        let witness = r#"{"X": "3", "Y": "35"}"#.to_string();
+        let proof = mobile::generate_proof(R1CS_PATH.into(), PK_PATH.into(), witness.clone())?;
+        assert!(mobile::verify_proof(
+            R1CS_PATH.into(),
+            VK_PATH.into(),
+            proof
+        )?);
+
+        let listener = Arc::new(Collect(Mutex::new(Vec::new())));
+        let prover = Prover::new(R1CS_PATH.into(), PK_PATH.into())?;
+        let proof = prover.prove_with_progress(witness, listener.clone())?;
+        assert!(mobile::verify_proof(
+            R1CS_PATH.into(),
+            VK_PATH.into(),
+            proof
+        )?);
+        let events = listener.0.lock().unwrap();
+        assert!(events.iter().any(|p| p.phase == ProvePhase::Prove));
+
+        let err = prover
+            .prove(r#"{"X": "3", "Y": "36"}"#.into())
+            .expect_err("unsatisfied witness must fail");
+        assert!(matches!(err, GnarkError::WitnessSolveFailed { .. }));
+
+        assert!(mobile::memory_stats().sys > 0);
+        mobile::free_os_memory();
+        Ok(())
+    }
+
+    /// Test: setup, proving and verification each run in their own span.
+    #[test]
+    fn test_tracing_spans() -> Result<()> {
+        use std::sync::Arc;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        #[derive(Default)]
+        struct SpanNames(Mutex<Vec<&'static str>>);
+        impl Subscriber for Arc<SpanNames> {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        init()?;
+        let dir = std::env::temp_dir().join(format!("rust-gnark-tracing-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let (pk, vk) = (dir.join("cubic.pk"), dir.join("cubic.vk"));
+
+        let names = Arc::new(SpanNames::default());
+        tracing::subscriber::with_default(names.clone(), || -> Result<()> {
+            let circuit = CompiledCircuit::read(R1CS_PATH)?;
+            circuit.groth16_setup(pk.to_str().unwrap(), vk.to_str().unwrap())?;
+            let proof = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+            assert!(groth16_verify(R1CS_PATH, VK_PATH, &proof)?);
+            Ok(())
+        })?;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert_eq!(*names.0.lock().unwrap(), ["setup", "prove", "verify"]);
+        Ok(())
+    }
+
+    /// Test: initializing with runtime options, again after `init`, leaves a
+    /// working prover, and a NUL byte in a GODEBUG setting is refused.
+    #[test]
+    fn test_init_with_options() -> Result<()> {
+        use rust_gnark::runtime::InitOptions;
+
+        init()?;
+        let options = InitOptions::default()
+            .with_async_preemption(false)
+            .with_godebug("madvdontneed=1")
+            .with_crash_on_fatal(true);
+        rust_gnark::init_with_options(&options)?;
+        rust_gnark::runtime::fix_signal_stacks();
+
+        let result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &result)?);
+
+        let bad = InitOptions::default().with_godebug("a=\0");
+        assert!(rust_gnark::init_with_options(&bad).is_err());
+        Ok(())
+    }
+
+    /// Test: proving works without `init`, and again after `shutdown`.
+    #[test]
+    fn test_shutdown() -> Result<()> {
+        let witness_json = r#"{"X": "3", "Y": "35"}"#;
+        let result = groth16_prove(R1CS_PATH, PK_PATH, witness_json)?;
+
+        rust_gnark::shutdown();
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &result)?);
+        let result = groth16_prove(R1CS_PATH, PK_PATH, witness_json)?;
+        assert!(groth16_verify(R1CS_PATH, VK_PATH, &result)?);
+        Ok(())
+    }
+
+    /// Test: a thread budget is reported back when replaced, and a guard
+    /// raises it for a proof and restores it.
+    #[test]
+    fn test_thread_budget() -> Result<()> {
+        use rust_gnark::runtime::{raise_thread_budget, set_thread_budget};
+
+        init()?;
+        let original = set_thread_budget(2);
+        assert_eq!(set_thread_budget(2), 2);
+        {
+            let _guard = raise_thread_budget(4);
+            let result = groth16_prove(R1CS_PATH, PK_PATH, r#"{"X": "3", "Y": "35"}"#)?;
+            assert!(groth16_verify(R1CS_PATH, VK_PATH, &result)?);
+            // A smaller budget does not lower it.
+            drop(raise_thread_budget(1));
+            assert_eq!(set_thread_budget(4), 4);
+        }
+        assert_eq!(set_thread_budget(original), 2);
+        Ok(())
+    }
+
+    /// Test: the CPU this test runs on supports the library as built, and
+    /// only x86-64 reports x86 features.
+    #[test]
+    fn test_cpu_features() -> Result<()> {
+        init()?;
+
+        let cpu = rust_gnark::runtime::cpu_features();
+        assert!(cpu.supported());
+        if !cfg!(target_arch = "x86_64") {
+            assert!(!cpu.adx && !cpu.bmi2 && !cpu.avx2 && !cpu.avx512f);
+        }
+        Ok(())
+    }
+
+    /// Test: the self-test passes and reports a time for every phase.
+    #[test]
+    fn test_self_test() -> Result<()> {
+        init()?;
+
+        let report = rust_gnark::self_test()?;
+        for phase in [report.compile, report.setup, report.prove, report.verify] {
+            assert!(!phase.is_zero());
+        }
+        assert_eq!(
+            report.total(),
+            report.compile + report.setup + report.prove + report.verify
+        );
+        Ok(())
+    }
+
+    /// Test: the library reports the gnark and gnark-crypto versions pinned in
+    /// go/go.mod, with their go.sum hashes.
+    #[test]
+    fn test_version_info() -> Result<()> {
+        init()?;
+
+        let info = rust_gnark::version_info();
+        assert!(info.go_version.starts_with("go"));
+        let go_mod = std::fs::read_to_string("../go/go.mod")?;
+        let required = |path: &str| {
+            go_mod.lines().find_map(|line| {
+                let mut fields = line.split_whitespace();
+                (fields.next() == Some(path)).then(|| fields.next().unwrap_or_default())
+            })
+        };
+        assert_eq!(info.gnark(), required("github.com/consensys/gnark"));
+        assert_eq!(
+            info.gnark_crypto(),
+            required("github.com/consensys/gnark-crypto")
+        );
+
+        let gnark = info.module("github.com/consensys/gnark").unwrap();
+        let go_sum = std::fs::read_to_string("../go/go.sum")?;
+        let line = format!("{} {} {}", gnark.path, gnark.version, gnark.sum);
+        assert!(go_sum.lines().any(|l| l == line), "{line} not in go.sum");
+        if let Some(revision) = &info.git_revision {
+            assert!(revision.bytes().all(|b| b.is_ascii_hexdigit()));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,649 @@
+//! Development tasks for rust-gnark, run with `cargo xtask <task>`.
+//!
+//! - `build-xcframework`: package the Go library (or a staticlib crate that
+//!   links rust-gnark) for iOS devices and simulators as an XCFramework, the
+//!   layout Swift Package Manager binary targets expect.
+//! - `pin-go`: record the SHA-256s of a Go release's toolchain archives in
+//!   `sys/go-toolchain.sha256`, for the `vendored-go` feature.
+//! - `test-ios`: run the test suite inside an iOS simulator, where cgo and the
+//!   Go runtime's signal handling behave differently from desktop hosts.
+//! - `test-android`: run the test suite on an Android device or emulator
+//!   through adb, against the shared `libgnark.so` as apps load it.
+
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const USAGE: &str = "\
+Usage: cargo xtask <task> [options]
+
+Tasks:
+  build-xcframework   Build an XCFramework with iOS device and simulator slices
+  pin-go <version>    Pin the Go toolchain the `vendored-go` feature downloads
+  test-ios            Run the test suite in an iOS simulator
+  test-android        Run the test suite on an Android device or emulator
+
+build-xcframework options:
+  --manifest-path <path>  Cargo.toml of a staticlib crate that depends on rust-gnark;
+                          its library (which bundles libgnark) is packaged instead of
+                          the bare Go library
+  --name <name>           Framework and module name (default: Gnark)
+  --features <features>   Features passed to cargo build
+  --headers <dir>         Extra headers (*.h) to ship next to libgnark.h
+  --catalyst              Also add a Mac Catalyst slice
+  --output <dir>          Output directory (default: target/xcframework)
+
+test-ios options:
+  --device <name|udid>    Simulator to boot and run in (default: the first available
+                          iPhone)
+  --target <triple>       Simulator target (default: aarch64-apple-ios-sim)
+  --features <features>   Features passed to cargo test
+  --release               Build the tests with optimizations
+  -- <args>               Passed to the test binary, e.g. a test name filter
+
+test-android options:
+  --device <serial>       adb device to run on (default: the only one connected)
+  --target <triple>       Android target (default: aarch64-linux-android)
+  --features, --release, -- <args>  As for test-ios";
+
+fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("build-xcframework") => build_xcframework(&XcframeworkArgs::parse(args)?),
+        Some("pin-go") => match (args.next(), args.next()) {
+            (Some(version), None) => pin_go(&version),
+            _ => bail!("pin-go takes one Go version, e.g. 1.24.0\n\n{USAGE}"),
+        },
+        Some("test-ios") => test_ios(&TestArgs::parse(args, "aarch64-apple-ios-sim")?),
+        Some("test-android") => test_android(&TestArgs::parse(args, "aarch64-linux-android")?),
+        Some("help" | "--help" | "-h") | None => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(other) => bail!("unknown task `{other}`\n\n{USAGE}"),
+    }
+}
+
+struct XcframeworkArgs {
+    manifest_path: Option<PathBuf>,
+    name: String,
+    features: Option<String>,
+    headers: Option<PathBuf>,
+    catalyst: bool,
+    output: PathBuf,
+}
+
+impl XcframeworkArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self {
+            manifest_path: None,
+            name: "Gnark".into(),
+            features: None,
+            headers: None,
+            catalyst: false,
+            output: workspace_root().join("target").join("xcframework"),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("{arg} needs a value\n\n{USAGE}"))
+            };
+            match arg.as_str() {
+                "--manifest-path" => parsed.manifest_path = Some(value()?.into()),
+                "--name" => parsed.name = value()?,
+                "--features" => parsed.features = Some(value()?),
+                "--headers" => parsed.headers = Some(value()?.into()),
+                "--catalyst" => parsed.catalyst = true,
+                "--output" => parsed.output = value()?.into(),
+                _ => bail!("unknown option `{arg}`\n\n{USAGE}"),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// One XCFramework slice: a platform variant and the Rust targets lipo'd into it.
+struct Slice {
+    platform: &'static str,
+    targets: &'static [&'static str],
+}
+
+const IOS: Slice = Slice {
+    platform: "ios",
+    targets: &["aarch64-apple-ios"],
+};
+const IOS_SIMULATOR: Slice = Slice {
+    platform: "ios-simulator",
+    targets: &["aarch64-apple-ios-sim", "x86_64-apple-ios"],
+};
+const MAC_CATALYST: Slice = Slice {
+    platform: "ios-macabi",
+    targets: &["aarch64-apple-ios-macabi", "x86_64-apple-ios-macabi"],
+};
+
+/// What `cargo build` produced for one target.
+struct TargetBuild {
+    /// The packaged static library: the staticlib crate's, or `libgnark.a`.
+    library: PathBuf,
+    /// rust-gnark-sys' build script `OUT_DIR`, holding `libgnark.h`.
+    gnark_out_dir: PathBuf,
+}
+
+fn build_xcframework(args: &XcframeworkArgs) -> Result<()> {
+    let mut slices = vec![IOS, IOS_SIMULATOR];
+    if args.catalyst {
+        slices.push(MAC_CATALYST);
+    }
+
+    let work_dir = args.output.join("slices");
+    if work_dir.exists() {
+        fs::remove_dir_all(&work_dir)?;
+    }
+    let framework = args.output.join(format!("{}.xcframework", args.name));
+    if framework.exists() {
+        fs::remove_dir_all(&framework)?;
+    }
+
+    let mut xcodebuild = Command::new("xcodebuild");
+    xcodebuild.arg("-create-xcframework");
+    for slice in &slices {
+        let builds = slice
+            .targets
+            .iter()
+            .map(|target| build_target(args, target))
+            .collect::<Result<Vec<_>>>()?;
+
+        let slice_dir = work_dir.join(slice.platform);
+        let headers = slice_dir.join("Headers");
+        fs::create_dir_all(&headers)?;
+        let library = slice_dir.join(format!("lib{}.a", args.name));
+        if let [build] = builds.as_slice() {
+            fs::copy(&build.library, &library)?;
+        } else {
+            run(Command::new("lipo")
+                .arg("-create")
+                .args(builds.iter().map(|b| &b.library))
+                .arg("-output")
+                .arg(&library))?;
+        }
+        write_headers(args, &builds[0].gnark_out_dir, &headers)?;
+
+        xcodebuild
+            .arg("-library")
+            .arg(&library)
+            .arg("-headers")
+            .arg(&headers);
+    }
+    run(xcodebuild.arg("-output").arg(&framework))?;
+
+    println!("Wrote {}", framework.display());
+    Ok(())
+}
+
+/// `cargo build --release` for `target`, locating the outputs from cargo's JSON
+/// messages.
+fn build_target(args: &XcframeworkArgs, target: &str) -> Result<TargetBuild> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let mut cmd = Command::new(cargo);
+    cmd.current_dir(workspace_root()).args([
+        "build",
+        "--release",
+        "--target",
+        target,
+        "--message-format=json-render-diagnostics",
+    ]);
+    match &args.manifest_path {
+        Some(path) => cmd.arg("--manifest-path").arg(path),
+        None => cmd.args(["-p", "rust-gnark"]),
+    };
+    if let Some(features) = &args.features {
+        cmd.args(["--features", features]);
+    }
+
+    eprintln!("Building {target}");
+    let output = cmd
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run cargo")?;
+    if !output.status.success() {
+        bail!("cargo build --target {target} failed ({})", output.status);
+    }
+
+    let mut gnark_out_dir = None;
+    let mut staticlib = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match json_string(line, "reason").as_deref() {
+            Some("build-script-executed") => {
+                let out_dir = json_string(line, "out_dir").map(PathBuf::from);
+                if out_dir.as_deref().is_some_and(is_gnark_out_dir) {
+                    gnark_out_dir = out_dir;
+                }
+            }
+            Some("compiler-artifact") if line.contains("\"staticlib\"") => {
+                staticlib = json_strings(line, "filenames")
+                    .into_iter()
+                    .find(|f| f.ends_with(".a"))
+                    .map(PathBuf::from);
+            }
+            _ => {}
+        }
+    }
+
+    let gnark_out_dir = gnark_out_dir
+        .with_context(|| format!("rust-gnark-sys' build script did not run for {target}"))?;
+    let library = match &args.manifest_path {
+        Some(path) => staticlib.with_context(|| {
+            format!(
+                "{} has no staticlib target (add crate-type = [\"staticlib\"])",
+                path.display()
+            )
+        })?,
+        None => gnark_out_dir.join("libgnark.a"),
+    };
+    Ok(TargetBuild {
+        library,
+        gnark_out_dir,
+    })
+}
+
+/// Copy `libgnark.h` and any extra headers into `dir` and write a module map
+/// exposing them to Swift as `args.name`.
+fn write_headers(args: &XcframeworkArgs, gnark_out_dir: &Path, dir: &Path) -> Result<()> {
+    fs::copy(gnark_out_dir.join("libgnark.h"), dir.join("libgnark.h"))
+        .context("rust-gnark-sys' OUT_DIR has no libgnark.h")?;
+    let mut headers = vec!["libgnark.h".to_string()];
+    if let Some(extra) = &args.headers {
+        for entry in
+            fs::read_dir(extra).with_context(|| format!("failed to read {}", extra.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "h") {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                fs::copy(&path, dir.join(&name))?;
+                headers.push(name);
+            }
+        }
+    }
+
+    let mut modulemap = format!("module {} {{\n", args.name);
+    for header in &headers {
+        modulemap.push_str(&format!("    header \"{header}\"\n"));
+    }
+    modulemap.push_str("    export *\n}\n");
+    fs::write(dir.join("module.modulemap"), modulemap)?;
+    Ok(())
+}
+
+/// Whether `out_dir` (`.../build/<package>-<hash>/out`) belongs to
+/// rust-gnark-sys, which builds the Go library.
+/// Package ids are not used because cargo abbreviates them for path
+/// dependencies.
+fn is_gnark_out_dir(out_dir: &Path) -> bool {
+    out_dir
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str()?.strip_prefix("rust-gnark-sys-"))
+        .is_some_and(|hash| !hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+struct TestArgs {
+    device: Option<String>,
+    target: String,
+    features: Option<String>,
+    release: bool,
+    test_args: Vec<String>,
+}
+
+impl TestArgs {
+    fn parse(mut args: impl Iterator<Item = String>, default_target: &str) -> Result<Self> {
+        let mut parsed = Self {
+            device: None,
+            target: default_target.into(),
+            features: None,
+            release: false,
+            test_args: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("{arg} needs a value\n\n{USAGE}"))
+            };
+            match arg.as_str() {
+                "--device" => parsed.device = Some(value()?),
+                "--target" => parsed.target = value()?,
+                "--features" => parsed.features = Some(value()?),
+                "--release" => parsed.release = true,
+                "--" => {
+                    parsed.test_args = args.collect();
+                    break;
+                }
+                _ => bail!("unknown option `{arg}`\n\n{USAGE}"),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// What `cargo test --no-run` produced.
+struct TestBuild {
+    executables: Vec<PathBuf>,
+    /// rust-gnark-sys' build script `OUT_DIR`, holding `libgnark.so` on
+    /// Android.
+    gnark_out_dir: Option<PathBuf>,
+}
+
+/// Build the test suite (rust-gnark-tests) for `args.target` without running
+/// it.
+fn build_tests(args: &TestArgs, envs: &[(String, String)]) -> Result<TestBuild> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let mut cmd = Command::new(cargo);
+    cmd.envs(envs.iter().map(|(k, v)| (k, v)));
+    cmd.current_dir(workspace_root()).args([
+        "test",
+        "--no-run",
+        "-p",
+        "rust-gnark-tests",
+        "--lib",
+        "--target",
+        &args.target,
+        "--message-format=json-render-diagnostics",
+    ]);
+    if args.release {
+        cmd.arg("--release");
+    }
+    if let Some(features) = &args.features {
+        cmd.args(["--features", features]);
+    }
+
+    eprintln!("Building tests for {}", args.target);
+    let output = cmd
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run cargo")?;
+    if !output.status.success() {
+        bail!(
+            "cargo test --no-run --target {} failed ({})",
+            args.target,
+            output.status
+        );
+    }
+    let mut build = TestBuild {
+        executables: Vec::new(),
+        gnark_out_dir: None,
+    };
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match json_string(line, "reason").as_deref() {
+            Some("build-script-executed") => {
+                let out_dir = json_string(line, "out_dir").map(PathBuf::from);
+                if out_dir.as_deref().is_some_and(is_gnark_out_dir) {
+                    build.gnark_out_dir = out_dir;
+                }
+            }
+            Some("compiler-artifact") => {
+                build
+                    .executables
+                    .extend(json_string(line, "executable").map(PathBuf::from));
+            }
+            _ => {}
+        }
+    }
+    if build.executables.is_empty() {
+        bail!("cargo built no test executable for {}", args.target);
+    }
+    Ok(build)
+}
+
+/// Run the test suite in an iOS simulator with `simctl spawn`. Simulator
+/// processes see the host filesystem, so the binaries and test vectors are
+/// used in place.
+fn test_ios(args: &TestArgs) -> Result<()> {
+    let executables = build_tests(args, &[])?.executables;
+    let device = match &args.device {
+        Some(device) => device.clone(),
+        None => default_simulator()?,
+    };
+
+    eprintln!("Booting simulator {device}");
+    run(Command::new("xcrun").args(["simctl", "bootstatus", &device, "-b"]))?;
+    for executable in &executables {
+        eprintln!("Running {} on {device}", executable.display());
+        // simctl passes SIMCTL_CHILD_* variables to the spawned process.
+        run(Command::new("xcrun")
+            .args(["simctl", "spawn", &device])
+            .arg(executable)
+            .args(&args.test_args)
+            .env("SIMCTL_CHILD_RUST_BACKTRACE", "1"))?;
+    }
+    Ok(())
+}
+
+/// The UDID of the first available iPhone simulator, from lines like
+/// `    iPhone 15 (1F6B5E4A-...) (Shutdown)` of `simctl list`.
+fn default_simulator() -> Result<String> {
+    let output = Command::new("xcrun")
+        .args(["simctl", "list", "devices", "available"])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run xcrun simctl (is Xcode installed?)")?;
+    if !output.status.success() {
+        bail!("xcrun simctl list failed ({})", output.status);
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("iPhone"))
+        .find_map(|line| {
+            line.split(['(', ')'])
+                .find(|part| part.len() == 36 && part.bytes().filter(|&b| b == b'-').count() == 4)
+                .map(str::to_owned)
+        })
+        .context("no iPhone simulator available; create one in Xcode or pass --device")
+}
+
+/// Where `test-android` stages the suite on the device.
+const ANDROID_TEST_DIR: &str = "/data/local/tmp/rust-gnark-tests";
+
+/// Push the test suite, `libgnark.so` and the test vectors to an Android
+/// device with adb and run it there with the library on `LD_LIBRARY_PATH`,
+/// the way the dynamic loader finds it in an app. Running on a 16 KB page
+/// system image (Android 15+) catches misaligned libraries.
+fn test_android(args: &TestArgs) -> Result<()> {
+    let mut envs = Vec::new();
+    let linker_var = format!(
+        "CARGO_TARGET_{}_LINKER",
+        args.target.to_uppercase().replace('-', "_")
+    );
+    if env::var_os(&linker_var).is_none() {
+        envs.extend(android_linker(&args.target).map(|linker| (linker_var, linker)));
+    }
+    let build = build_tests(args, &envs)?;
+    let gnark_out_dir = build
+        .gnark_out_dir
+        .context("rust-gnark-sys' build script did not run")?;
+
+    let adb = |adb_args: &[&str]| {
+        let mut cmd = Command::new("adb");
+        if let Some(serial) = &args.device {
+            cmd.args(["-s", serial]);
+        }
+        cmd.args(adb_args);
+        cmd
+    };
+    run(&mut adb(&["wait-for-device"]))?;
+    let page_size = adb(&["shell", "getconf", "PAGESIZE"])
+        .output()
+        .context("failed to run adb")?;
+    eprintln!(
+        "Device page size: {}",
+        String::from_utf8_lossy(&page_size.stdout).trim()
+    );
+
+    run(&mut adb(&["shell", "rm", "-rf", ANDROID_TEST_DIR]))?;
+    run(&mut adb(&["shell", "mkdir", "-p", ANDROID_TEST_DIR]))?;
+    let vectors = workspace_root().join("tests").join("test-vectors");
+    let fixtures = workspace_root().join("examples").join("fixtures");
+    let conformance = workspace_root().join("crates").join("conformance");
+    // The device has no Go to generate them the way the host tests do.
+    generate_if_missing(&fixtures, "gen_examples", "multiplier.r1cs")?;
+    generate_if_missing(&conformance, "gen_conformance", "GNARK_VERSION")?;
+    let library = gnark_out_dir.join("libgnark.so");
+    for path in build
+        .executables
+        .iter()
+        .chain([&library, &vectors, &fixtures, &conformance])
+    {
+        run(adb(&["push"]).arg(path).arg(ANDROID_TEST_DIR))?;
+    }
+    for executable in &build.executables {
+        let name = executable.file_name().unwrap().to_string_lossy();
+        eprintln!("Running {name} on the device");
+        // adb shell reports the remote command's exit status. The test
+        // binaries open the vectors relative to the working directory there.
+        let mut command =
+            format!("cd {ANDROID_TEST_DIR} && LD_LIBRARY_PATH=. RUST_BACKTRACE=1 ./{name}");
+        for arg in &args.test_args {
+            command.push_str(&format!(" '{}'", arg.replace('\'', "'\\''")));
+        }
+        run(&mut adb(&["shell", &command]))?;
+    }
+    Ok(())
+}
+
+/// The NDK clang that links for `target`, as rust-gnark-sys' build script
+/// finds it for cgo.
+fn android_linker(target: &str) -> Option<String> {
+    let ndk = env::var("ANDROID_NDK_HOME")
+        .or_else(|_| env::var("ANDROID_NDK_ROOT"))
+        .ok()?;
+    let host_tags: &[&str] = if cfg!(target_os = "macos") {
+        &["darwin-arm64", "darwin-x86_64"]
+    } else {
+        &["linux-x86_64"]
+    };
+    host_tags
+        .iter()
+        .map(|tag| format!("{ndk}/toolchains/llvm/prebuilt/{tag}/bin/{target}21-clang"))
+        .find(|cc| Path::new(cc).exists())
+}
+
+/// Hosts the `vendored-go` feature can fetch a toolchain for, as Go names
+/// them; see `go_host_platform` in rust-gnark-sys' build script.
+const GO_PLATFORMS: &[&str] = &[
+    "linux-amd64",
+    "linux-arm64",
+    "linux-386",
+    "darwin-amd64",
+    "darwin-arm64",
+    "windows-amd64",
+    "windows-arm64",
+    "windows-386",
+    "freebsd-amd64",
+    "freebsd-arm64",
+    "freebsd-386",
+];
+
+/// Write the SHA-256 of each of Go `version`'s toolchain archives, as published
+/// next to them on dl.google.com, to `sys/go-toolchain.sha256`.
+fn pin_go(version: &str) -> Result<()> {
+    let version = version.trim_start_matches("go");
+    let mut sums = String::new();
+    for platform in GO_PLATFORMS {
+        let extension = if platform.starts_with("windows") {
+            "zip"
+        } else {
+            "tar.gz"
+        };
+        let name = format!("go{version}.{platform}.{extension}");
+        let output = Command::new("curl")
+            .args(["-fsSL", &format!("https://dl.google.com/go/{name}.sha256")])
+            .stderr(Stdio::inherit())
+            .output()
+            .context("failed to run curl")?;
+        if !output.status.success() {
+            bail!("no SHA-256 published for {name}; is {version} a Go release?");
+        }
+        let hash = String::from_utf8(output.stdout)?.trim().to_owned();
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("unexpected SHA-256 for {name}: {hash:?}");
+        }
+        sums.push_str(&format!("{hash}  {name}\n"));
+    }
+    let path = workspace_root().join("sys").join("go-toolchain.sha256");
+    fs::write(&path, sums).with_context(|| format!("failed to write {}", path.display()))?;
+    println!("pinned Go {version} in {}", path.display());
+    Ok(())
+}
+
+/// Run `go run ./cmd/<generator> <dir>` unless `dir` already holds `marker`.
+fn generate_if_missing(dir: &Path, generator: &str, marker: &str) -> Result<()> {
+    if dir.join(marker).exists() {
+        return Ok(());
+    }
+    run(Command::new("go")
+        .args(["run", &format!("./cmd/{generator}")])
+        .arg(dir)
+        .current_dir(workspace_root().join("go")))
+}
+
+fn run(cmd: &mut Command) -> Result<()> {
+    let status = cmd
+        .status()
+        .with_context(|| format!("failed to run {:?}", cmd.get_program()))?;
+    if !status.success() {
+        bail!("{:?} failed ({status})", cmd.get_program());
+    }
+    Ok(())
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside the workspace")
+        .to_path_buf()
+}
+
+/// The string value of `"key"` in a cargo JSON message.
+fn json_string(line: &str, key: &str) -> Option<String> {
+    let start = line.find(&format!("\"{key}\":\""))? + key.len() + 4;
+    Some(read_json_string(&line[start..]).0)
+}
+
+/// The string values of the array `"key"` in a cargo JSON message.
+fn json_strings(line: &str, key: &str) -> Vec<String> {
+    let Some(start) = line.find(&format!("\"{key}\":[")) else {
+        return Vec::new();
+    };
+    let mut rest = &line[start + key.len() + 4..];
+    let mut values = Vec::new();
+    while let Some(quoted) = rest.strip_prefix('"') {
+        let (value, len) = read_json_string(quoted);
+        values.push(value);
+        let Some(next) = quoted.get(len + 1..) else {
+            break;
+        };
+        rest = next.trim_start_matches(',');
+    }
+    values
+}
+
+/// Read a JSON string body up to its closing quote, returning the unescaped
+/// value and the length of the escaped body.
+fn read_json_string(s: &str) -> (String, usize) {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (value, i),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, other)) => value.push(other),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+    (value, s.len())
+}